@@ -0,0 +1,324 @@
+use std::{fmt::Write, hash::Hasher, path::Path, time::Duration};
+
+use crate::{
+    config::{provenance::CRATE_VERSION, Filter, TypeRef},
+    errors::Result,
+};
+
+use super::{
+    bus::BusBuilder,
+    message_builder::{MessageBuilderUsage, MessageIdTemplate, MessageResolution},
+    message_resolution::{IdLayout, NodeFilterBank},
+    MessageBuilder, MessageFormat, NetworkBuilder, NodeBuilder,
+};
+
+// The id resolution is by far the slowest stage of the build. build_with_cache
+// persists its result (the ids, resolutions and buses of all messages and the
+// filters of all nodes) in a directory, keyed on a hash of everything the
+// resolution reads. The resolution is deterministic (ties are broken by the
+// name or the resolution seed), therefore builds of an unchanged network reuse
+// the stored assignment instead of resolving it again. Unlike the BuildCache
+// of incremental builds the result survives the process. Stale or unreadable
+// cache files are ignored and overwritten.
+
+const CACHE_FILE: &str = "canzero-resolution.cache";
+const HEADER: &str = "# canzero resolution cache";
+// bump on changes to the layout of the cache file.
+const CACHE_FORMAT_VERSION: u32 = 2;
+
+fn names(nodes: &[NodeBuilder]) -> Vec<String> {
+    nodes.iter().map(|node| node.0.borrow().name.clone()).collect()
+}
+
+fn frame_interval(message: &MessageBuilder) -> Option<Duration> {
+    match &message.0.borrow().usage {
+        MessageBuilderUsage::Stream(stream) => Some(stream.0.borrow().interval.1),
+        MessageBuilderUsage::CommandReq(command) | MessageBuilderUsage::CommandResp(command) => {
+            Some(command.frame_interval())
+        }
+        MessageBuilderUsage::External { interval } => *interval,
+        MessageBuilderUsage::Configuration | MessageBuilderUsage::Heartbeat => None,
+    }
+}
+
+/// hash of the inputs of the id resolution, settings is the debug
+/// representation of the resolution settings of the network. The crate
+/// version is part of the hash, such that a changed resolver never
+/// reuses an assignment of an older release.
+pub(crate) fn resolution_fingerprint(
+    messages: &[MessageBuilder],
+    nodes: &[NodeBuilder],
+    buses: &[BusBuilder],
+    types: &[TypeRef],
+    settings: &str,
+) -> String {
+    let mut description = String::new();
+    writeln!(description, "canzero-config {CRATE_VERSION} format {CACHE_FORMAT_VERSION}").unwrap();
+    writeln!(description, "{settings}").unwrap();
+    for bus in buses {
+        writeln!(description, "bus {:?}", bus.0.borrow()).unwrap();
+    }
+    for node in nodes {
+        let node_data = node.0.borrow();
+        let buses: Vec<String> = node_data.buses.iter().map(|bus| bus.0.borrow().name.clone()).collect();
+        writeln!(
+            description,
+            "node {} {buses:?} {} {:?}",
+            node_data.name, node_data.receive_all, node_data.listen_only
+        )
+        .unwrap();
+    }
+    let mut types: Vec<&TypeRef> = types.iter().collect();
+    types.sort_by_key(|ty| ty.name());
+    for ty in types {
+        writeln!(description, "type {ty:?}").unwrap();
+    }
+    for message in messages {
+        let interval = frame_interval(message);
+        let message_data = message.0.borrow();
+        let format = match &message_data.format {
+            MessageFormat::Signals(format) => format!("{:?}", format.0.borrow()),
+            MessageFormat::Types(format) => format!("{:?}", format.0.borrow()),
+            MessageFormat::Empty => String::new(),
+        };
+        let bus = |bus: &Option<BusBuilder>| bus.as_ref().map(|bus| bus.0.borrow().name.clone());
+        writeln!(
+            description,
+            "message {} {:?} {format} {:?} {:?} {:?} {:?} {:?} {interval:?} {:?} {:?} {:?} {}",
+            message_data.name,
+            message_data.id,
+            names(&message_data.receivers),
+            names(&message_data.transmitters),
+            bus(&message_data.bus),
+            message_data.bus_pins,
+            bus(&message_data.fallback_bus),
+            message_data.cmac,
+            message_data.deadline,
+            message_data.tags,
+            message_data.remote_requestable,
+        )
+        .unwrap();
+    }
+    let mut hasher = seahash::SeaHasher::new();
+    hasher.write(description.as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+fn ide_token(ide: bool) -> &'static str {
+    if ide {
+        "ext"
+    } else {
+        "std"
+    }
+}
+
+fn optional_token(value: Option<u32>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_else(|| "-".to_owned())
+}
+
+fn write_filter(cache: &mut String, kind: &str, filter: &Filter) {
+    writeln!(cache, "{kind} {} {} {}", filter.id(), filter.mask(), ide_token(filter.ide())).unwrap();
+}
+
+fn write_cache(
+    fingerprint: &str,
+    messages: &[MessageBuilder],
+    filter_banks: &[NodeFilterBank],
+    id_layout: &IdLayout,
+) -> String {
+    let mut cache = String::new();
+    writeln!(cache, "{HEADER}").unwrap();
+    writeln!(cache, "fingerprint {fingerprint}").unwrap();
+    let bucket_sizes: Vec<String> = id_layout.bucket_sizes.iter().map(|size| size.to_string()).collect();
+    writeln!(cache, "layout {} {}", id_layout.setcode_len, bucket_sizes.join(" ")).unwrap();
+    for message in messages {
+        let message_data = message.0.borrow();
+        let (id, ide) = match message_data.id {
+            MessageIdTemplate::StdId(id) => (id, false),
+            MessageIdTemplate::ExtId(id) => (id, true),
+            _ => panic!("message {} has no id after the resolution", message_data.name),
+        };
+        let bus = message_data
+            .bus
+            .as_ref()
+            .map(|bus| bus.0.borrow().name.clone())
+            .unwrap_or_else(|| "-".to_owned());
+        let resolution = match &message_data.resolution {
+            Some(resolution) => format!(
+                "{} {} {} {} {}",
+                resolution.setcode,
+                resolution.setcode_len,
+                optional_token(resolution.requested_priority),
+                optional_token(resolution.priority_bucket),
+                optional_token(resolution.position),
+            ),
+            None => "-".to_owned(),
+        };
+        writeln!(cache, "message {} {} {id} {bus} {resolution}", message_data.name, ide_token(ide)).unwrap();
+    }
+    for bank in filter_banks {
+        writeln!(cache, "node {}", bank.node().0.borrow().name).unwrap();
+        for filter in bank.filters() {
+            write_filter(&mut cache, "filter", filter);
+        }
+        for filter in bank.software_filters() {
+            write_filter(&mut cache, "software_filter", filter);
+        }
+    }
+    cache
+}
+
+struct CachedMessage {
+    name: String,
+    id: MessageIdTemplate,
+    bus: Option<String>,
+    resolution: Option<MessageResolution>,
+}
+
+struct CachedResolution {
+    id_layout: Option<IdLayout>,
+    messages: Vec<CachedMessage>,
+    // (node, filters, software filters)
+    filter_banks: Vec<(String, Vec<Filter>, Vec<Filter>)>,
+}
+
+fn parse_cache(cache: &str, fingerprint: &str) -> Option<CachedResolution> {
+    let mut lines = cache.lines();
+    if lines.next()? != HEADER || lines.next()?.strip_prefix("fingerprint ")? != fingerprint {
+        return None;
+    }
+    let parse_ide = |token: &str| match token {
+        "std" => Some(false),
+        "ext" => Some(true),
+        _ => None,
+    };
+    let parse_optional = |token: &str| match token {
+        "-" => Some(None),
+        _ => token.parse::<u32>().ok().map(Some),
+    };
+    let mut resolution = CachedResolution {
+        id_layout: None,
+        messages: vec![],
+        filter_banks: vec![],
+    };
+    for line in lines {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens[..] {
+            ["layout", setcode_len, ref bucket_sizes @ ..] => {
+                resolution.id_layout = Some(IdLayout {
+                    setcode_len: setcode_len.parse().ok()?,
+                    bucket_sizes: bucket_sizes
+                        .iter()
+                        .map(|size| size.parse().ok())
+                        .collect::<Option<_>>()?,
+                });
+            }
+            ["message", name, ide, id, bus, ref rest @ ..] => {
+                let id = id.parse::<u32>().ok()?;
+                let id = if parse_ide(ide)? {
+                    MessageIdTemplate::ExtId(id)
+                } else {
+                    MessageIdTemplate::StdId(id)
+                };
+                let message_resolution = match rest {
+                    ["-"] => None,
+                    [setcode, setcode_len, requested_priority, priority_bucket, position] => {
+                        Some(MessageResolution {
+                            setcode: setcode.parse().ok()?,
+                            setcode_len: setcode_len.parse().ok()?,
+                            requested_priority: parse_optional(requested_priority)?,
+                            priority_bucket: parse_optional(priority_bucket)?,
+                            position: parse_optional(position)?,
+                        })
+                    }
+                    _ => return None,
+                };
+                resolution.messages.push(CachedMessage {
+                    name: name.to_owned(),
+                    id,
+                    bus: (bus != "-").then(|| bus.to_owned()),
+                    resolution: message_resolution,
+                });
+            }
+            ["node", name] => resolution.filter_banks.push((name.to_owned(), vec![], vec![])),
+            [kind @ ("filter" | "software_filter"), id, mask, ide] => {
+                let filter = Filter::new(id.parse().ok()?, mask.parse().ok()?, parse_ide(ide)?);
+                let (_, filters, software_filters) = resolution.filter_banks.last_mut()?;
+                if kind == "filter" {
+                    filters.push(filter);
+                } else {
+                    software_filters.push(filter);
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(resolution)
+}
+
+/// applies the cached resolution to the messages, None if there is no
+/// cache for the fingerprint or it doesn't fit the messages.
+pub(crate) fn load_resolution(
+    dir: &Path,
+    fingerprint: &str,
+    messages: &[MessageBuilder],
+    nodes: &[NodeBuilder],
+    buses: &[BusBuilder],
+) -> Option<(Vec<NodeFilterBank>, IdLayout)> {
+    let cache = std::fs::read_to_string(dir.join(CACHE_FILE)).ok()?;
+    let cached = parse_cache(&cache, fingerprint)?;
+    // caches written before the layout was recorded are resolved again.
+    let id_layout = cached.id_layout?;
+    if cached.messages.len() != messages.len() {
+        return None;
+    }
+    // everything is looked up first, such that a cache that doesn't fit
+    // leaves the messages untouched.
+    let find_bus = |name: &str| buses.iter().find(|bus| bus.0.borrow().name == name).cloned();
+    let mut assignments = vec![];
+    for cached_message in cached.messages {
+        let message = messages.iter().find(|m| m.0.borrow().name == cached_message.name)?;
+        let bus = match &cached_message.bus {
+            Some(bus) => Some(find_bus(bus)?),
+            None => None,
+        };
+        assignments.push((message, cached_message, bus));
+    }
+    let mut filter_banks = vec![];
+    for (name, filters, software_filters) in cached.filter_banks {
+        let node = nodes.iter().find(|node| node.0.borrow().name == name)?;
+        filter_banks.push(NodeFilterBank::new(node.clone(), filters, software_filters));
+    }
+    for (message, cached_message, bus) in assignments {
+        let mut message_data = message.0.borrow_mut();
+        message_data.id = cached_message.id;
+        message_data.bus = bus;
+        message_data.resolution = cached_message.resolution;
+    }
+    Some((filter_banks, id_layout))
+}
+
+pub(crate) fn store_resolution(
+    dir: &Path,
+    fingerprint: &str,
+    messages: &[MessageBuilder],
+    filter_banks: &[NodeFilterBank],
+    id_layout: &IdLayout,
+) -> Result<()> {
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join(CACHE_FILE), write_cache(fingerprint, messages, filter_banks, id_layout))?;
+    Ok(())
+}
+
+impl NetworkBuilder {
+    /// same as build, but the id and filter assignment is stored in dir
+    /// and reused by later builds, as long as nothing that the assignment
+    /// depends on changed (e.g. descriptions and object entries, that
+    /// aren't streamed, don't invalidate it).
+    pub fn build_with_cache(&self, dir: impl AsRef<Path>) -> Result<crate::config::NetworkRef> {
+        let previous = self.0.borrow_mut().artifact_cache.replace(dir.as_ref().to_owned());
+        let network = self.build();
+        self.0.borrow_mut().artifact_cache = previous;
+        network
+    }
+}
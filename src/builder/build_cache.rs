@@ -0,0 +1,95 @@
+use crate::config::TypeRef;
+
+use super::{message_resolution::ReceiverSetLayout, NetworkBuilder, TypeBuilder};
+
+// Results of build stages, which are reused by successive builds of the same
+// network builder as long as their inputs didn't change. Inputs are compared
+// by a fingerprint. Build generates the implicit messages (heartbeat, commands,
+// ...) on every call, therefore cached results refer to builders by name.
+
+#[derive(Debug, Default)]
+pub struct BuildCache {
+    types: Option<(String, Vec<TypeRef>)>,
+    receive_sets: Option<(String, Vec<ReceiverSetLayout>)>,
+    stats: BuildCacheStats,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BuildCacheStats {
+    pub type_hits: usize,
+    pub type_misses: usize,
+    pub receive_set_hits: usize,
+    pub receive_set_misses: usize,
+}
+
+impl BuildCache {
+    pub(crate) fn types(&mut self, fingerprint: &str) -> Option<Vec<TypeRef>> {
+        match &self.types {
+            Some((cached, types)) if cached == fingerprint => {
+                self.stats.type_hits += 1;
+                Some(types.clone())
+            }
+            _ => {
+                self.stats.type_misses += 1;
+                None
+            }
+        }
+    }
+    pub(crate) fn store_types(&mut self, fingerprint: String, types: Vec<TypeRef>) {
+        self.types = Some((fingerprint, types));
+    }
+    pub(crate) fn receive_sets(&self, fingerprint: &str) -> Option<&Vec<ReceiverSetLayout>> {
+        match &self.receive_sets {
+            Some((cached, layouts)) if cached == fingerprint => Some(layouts),
+            _ => None,
+        }
+    }
+    /// a cached layout only counts as hit if it could be applied to the messages.
+    pub(crate) fn count_receive_sets(&mut self, hit: bool) {
+        if hit {
+            self.stats.receive_set_hits += 1;
+        } else {
+            self.stats.receive_set_misses += 1;
+        }
+    }
+    pub(crate) fn store_receive_sets(&mut self, fingerprint: String, layouts: Vec<ReceiverSetLayout>) {
+        self.receive_sets = Some((fingerprint, layouts));
+    }
+}
+
+/// identifies the definitions of all types, in the order they were defined.
+pub(crate) fn type_fingerprint(type_builders: &[TypeBuilder]) -> String {
+    let mut fingerprint = String::new();
+    for type_builder in type_builders {
+        let definition = match type_builder {
+            TypeBuilder::Enum(enum_builder) => format!("{:?}", enum_builder.0.borrow()),
+            TypeBuilder::Struct(struct_builder) => format!("{:?}", struct_builder.0.borrow()),
+        };
+        fingerprint.push_str(&definition);
+        fingerprint.push('\n');
+    }
+    fingerprint
+}
+
+impl NetworkBuilder {
+    /// caches type resolution and receive set construction between successive
+    /// builds, e.g. for tooling that rebuilds the network after every edit.
+    /// Stages are only reused if their inputs didn't change, the network is
+    /// the same as without caching.
+    pub fn set_incremental(&self, incremental: bool) {
+        let mut network_data = self.0.borrow_mut();
+        if !incremental {
+            network_data.build_cache = None;
+        } else if network_data.build_cache.is_none() {
+            network_data.build_cache = Some(super::make_builder_ref(BuildCache::default()));
+        }
+    }
+    /// hits and misses of the cache since set_incremental was enabled.
+    pub fn build_cache_stats(&self) -> Option<BuildCacheStats> {
+        self.0
+            .borrow()
+            .build_cache
+            .as_ref()
+            .map(|build_cache| build_cache.borrow().stats)
+    }
+}
@@ -1,15 +1,24 @@
+use crate::config::bus::{BusMode, CableSegment, FramePadding};
+
 use super::{BuilderRef, make_builder_ref};
 
 
 #[derive(Debug, Clone)]
 pub struct BusBuilder(pub BuilderRef<BusData>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BusData {
     pub name : String,
     pub id : u32,
     pub baudrate : u32,
     pub expected_utilization : u32,
+    // overrides the padding of the network.
+    pub padding : Option<FramePadding>,
+    pub max_nodes : Option<u32>,
+    // meters
+    pub max_stub_length : Option<f64>,
+    pub segments : Vec<CableSegment>,
+    pub mode : BusMode,
 }
 
 impl BusBuilder {
@@ -21,11 +30,60 @@ impl BusBuilder {
             id,
             baudrate : baudrate.unwrap_or(1000000),
             expected_utilization : 0,
+            padding : None,
+            max_nodes : None,
+            max_stub_length : None,
+            segments : vec![],
+            mode : BusMode::Can,
         }))
     }
 
     pub fn baudrate(&self, baudrate : u32) {
         self.0.borrow_mut().baudrate = baudrate;
     }
+
+    /// overrides the padding policy of the network for this bus.
+    pub fn padding(&self, padding : FramePadding) {
+        self.0.borrow_mut().padding = Some(padding);
+    }
+
+    /// the bus uses CAN XL frames (experimental, see config::can_xl), the
+    /// data phase is transmitted with the data baudrate.
+    pub fn set_xl_mode(&self, data_baudrate : u32) {
+        assert!(data_baudrate > 0, "data baudrate of bus {} has to be positive", self.0.borrow().name);
+        self.0.borrow_mut().mode = BusMode::Xl { data_baudrate };
+    }
+
+    /// max number of nodes on the bus (e.g. 32 for many ISO 11898 transceivers),
+    /// build fails with ConfigError::InvalidTopology if more nodes are assigned.
+    pub fn set_max_nodes(&self, max_nodes : u32) {
+        self.0.borrow_mut().max_nodes = Some(max_nodes);
+    }
+
+    /// max length of stubs in meters, validated against the stub segments.
+    pub fn set_max_stub_length(&self, meters : f64) {
+        assert!(meters.is_finite() && meters > 0.0, "max stub length of bus {} has to be positive", self.0.borrow().name);
+        self.0.borrow_mut().max_stub_length = Some(meters);
+    }
+
+    /// adds a segment of the trunk (the main line between the terminations).
+    pub fn add_trunk_segment(&self, description : &str, meters : f64) {
+        self.add_segment(CableSegment::trunk(description, meters));
+    }
+
+    /// adds a stub from the trunk to a node.
+    pub fn add_stub_segment(&self, description : &str, meters : f64) {
+        self.add_segment(CableSegment::stub(description, meters));
+    }
+
+    fn add_segment(&self, segment : CableSegment) {
+        assert!(
+            segment.length().is_finite() && segment.length() >= 0.0,
+            "length of segment {} of bus {} can't be negative",
+            segment.description(),
+            self.0.borrow().name
+        );
+        self.0.borrow_mut().segments.push(segment);
+    }
 }
 
@@ -0,0 +1,269 @@
+use std::{cmp::Ordering, collections::BTreeMap, fmt::Debug};
+
+use crate::errors::{self, Result};
+
+// Bus balancing assigns every message without an explicitly assigned bus to
+// one of the buses, after the ids are resolved. The strategy is selected with
+// NetworkBuilder::set_bus_balancer, the GreedyBalancer is the default.
+// Balancers only return a bus for every message, the result is validated
+// (e.g. that no message is placed on its fallback bus) before it's applied.
+
+/// a bus as seen by a BusBalancer.
+#[derive(Debug, Clone)]
+pub struct BalancingBus {
+    pub(crate) name: String,
+    pub(crate) baudrate: u32,
+    pub(crate) load: f64,
+    pub(crate) nodes: Vec<String>,
+    pub(crate) listen_only: Vec<String>,
+}
+
+impl BalancingBus {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn baudrate(&self) -> u32 {
+        self.baudrate
+    }
+    /// nominal bit times per second of the messages that are explicitly
+    /// assigned to the bus and of the load reserved for fallback messages.
+    pub fn load(&self) -> f64 {
+        self.load
+    }
+    /// names of the nodes that are explicitly assigned to the bus
+    /// (see NodeBuilder::assign_bus).
+    pub fn nodes(&self) -> &Vec<String> {
+        &self.nodes
+    }
+    /// names of the nodes that never transmit on the bus
+    /// (see NodeBuilder::set_listen_only).
+    pub fn listen_only(&self) -> &Vec<String> {
+        &self.listen_only
+    }
+}
+
+/// a message without an explicitly assigned bus.
+#[derive(Debug, Clone)]
+pub struct BalancingMessage {
+    pub(crate) name: String,
+    pub(crate) load: f64,
+    pub(crate) transmitters: Vec<String>,
+    pub(crate) receivers: Vec<String>,
+    pub(crate) fallback_bus: Option<usize>,
+}
+
+impl BalancingMessage {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// nominal bit times per second of the message.
+    pub fn load(&self) -> f64 {
+        self.load
+    }
+    pub fn transmitters(&self) -> &Vec<String> {
+        &self.transmitters
+    }
+    pub fn receivers(&self) -> &Vec<String> {
+        &self.receivers
+    }
+    /// index of the fallback bus, a message is never placed on it.
+    pub fn fallback_bus(&self) -> Option<usize> {
+        self.fallback_bus
+    }
+    /// the message can't be placed on its fallback bus or on a bus, which
+    /// one of its transmitters only listens to.
+    pub fn excludes(&self, buses: &[BalancingBus], bus: usize) -> bool {
+        Some(bus) == self.fallback_bus
+            || self
+                .transmitters
+                .iter()
+                .any(|node| buses[bus].listen_only.contains(node))
+    }
+    fn endpoints(&self) -> impl Iterator<Item = &String> {
+        self.transmitters.iter().chain(self.receivers.iter())
+    }
+}
+
+/// The Debug output of a balancer is part of the key of the artifact cache
+/// (NetworkBuilder::build_with_cache), it has to capture the complete
+/// configuration of the balancer, otherwise a changed configuration
+/// reuses a stale assignment.
+pub trait BusBalancer: Debug {
+    /// the index of the bus of every message, in the order of the messages.
+    fn balance(&self, buses: &[BalancingBus], messages: &[BalancingMessage]) -> Result<Vec<usize>>;
+}
+
+// indices of the messages ordered by load, ties keep the order of the messages.
+fn by_load(messages: &[BalancingMessage]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..messages.len()).collect();
+    order.sort_by(|a, b| {
+        messages[*a]
+            .load
+            .partial_cmp(&messages[*b].load)
+            .unwrap_or(Ordering::Equal)
+    });
+    order
+}
+
+fn cmp_load(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+fn no_bus(buses: &[BalancingBus], message: &BalancingMessage) -> errors::ConfigError {
+    match message.fallback_bus {
+        Some(bus) if buses.len() == 1 => errors::ConfigError::InvalidFallbackBus(format!(
+            "message {} can't be assigned to a bus, the only bus {} is its fallback bus",
+            message.name, buses[bus].name
+        )),
+        _ => errors::ConfigError::ListenOnlyTransmission(format!(
+            "message {} can't be assigned to a bus, its transmitters only listen to the remaining buses",
+            message.name
+        )),
+    }
+}
+
+// first bus with the minimal load, excluding the buses the message can't be placed on.
+fn min_load(buses: &[BalancingBus], loads: &[f64], message: &BalancingMessage) -> Result<usize> {
+    (0..loads.len())
+        .filter(|bus| !message.excludes(buses, *bus))
+        .min_by(|a, b| cmp_load(loads[*a], loads[*b]))
+        .ok_or_else(|| no_bus(buses, message))
+}
+
+/// places the messages by increasing load on the bus with the minimal load.
+/// Ignores which nodes exchange the messages, therefore tightly coupled
+/// nodes may end up communicating over a gateway.
+#[derive(Debug, Clone, Default)]
+pub struct GreedyBalancer;
+
+impl BusBalancer for GreedyBalancer {
+    fn balance(&self, buses: &[BalancingBus], messages: &[BalancingMessage]) -> Result<Vec<usize>> {
+        let mut loads: Vec<f64> = buses.iter().map(|bus| bus.load).collect();
+        let mut assignment = vec![0; messages.len()];
+        for i in by_load(messages) {
+            let bus = min_load(buses, &loads, &messages[i])?;
+            loads[bus] += messages[i].load;
+            assignment[i] = bus;
+        }
+        Ok(assignment)
+    }
+}
+
+/// places the messages by decreasing load on the bus, where most of their
+/// transmitters and receivers are connected. A node is connected to a bus if
+/// it's explicitly assigned to it or already exchanges a message on it.
+/// Buses above the max utilization are only used if no other bus is
+/// available, ties are placed on the bus with the minimal load.
+#[derive(Debug, Clone)]
+pub struct LocalityBalancer {
+    max_utilization: f64,
+}
+
+impl Default for LocalityBalancer {
+    fn default() -> Self {
+        Self {
+            max_utilization: 0.7,
+        }
+    }
+}
+
+impl LocalityBalancer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// fraction of the baudrate that a bus is filled up to, defaults to 0.7.
+    pub fn with_max_utilization(mut self, max_utilization: f64) -> Self {
+        assert!(max_utilization > 0.0, "max utilization has to be positive");
+        self.max_utilization = max_utilization;
+        self
+    }
+}
+
+impl BusBalancer for LocalityBalancer {
+    fn balance(&self, buses: &[BalancingBus], messages: &[BalancingMessage]) -> Result<Vec<usize>> {
+        let mut loads: Vec<f64> = buses.iter().map(|bus| bus.load).collect();
+        let mut connected: Vec<Vec<String>> = buses.iter().map(|bus| bus.nodes.clone()).collect();
+        let mut assignment = vec![0; messages.len()];
+        for i in by_load(messages).into_iter().rev() {
+            let message = &messages[i];
+            let locality = |bus: usize| {
+                message
+                    .endpoints()
+                    .filter(|node| connected[bus].contains(node))
+                    .count()
+            };
+            let fits = |bus: usize| {
+                loads[bus] + message.load <= self.max_utilization * buses[bus].baudrate as f64
+            };
+            let bus = (0..buses.len())
+                .filter(|bus| !message.excludes(buses, *bus) && fits(*bus))
+                .min_by(|a, b| locality(*b).cmp(&locality(*a)).then(cmp_load(loads[*a], loads[*b])));
+            let bus = match bus {
+                Some(bus) => bus,
+                None => min_load(buses, &loads, message)?,
+            };
+            loads[bus] += message.load;
+            for node in message.endpoints() {
+                if !connected[bus].contains(node) {
+                    connected[bus].push(node.clone());
+                }
+            }
+            assignment[i] = bus;
+        }
+        Ok(assignment)
+    }
+}
+
+/// places every message, that is transmitted by a pinned node, on the bus of
+/// the node and fills the buses with the remaining messages like the
+/// GreedyBalancer. Unlike NodeBuilder::assign_bus, pinning doesn't
+/// restrict the node to the bus.
+#[derive(Debug, Clone, Default)]
+pub struct PinnedBalancer {
+    // node -> bus
+    // ordered, such that the Debug output (part of the artifact cache key) is stable.
+    pins: BTreeMap<String, String>,
+}
+
+impl PinnedBalancer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn pin(mut self, node: &str, bus: &str) -> Self {
+        self.pins.insert(node.to_owned(), bus.to_owned());
+        self
+    }
+    fn pinned_bus(&self, buses: &[BalancingBus], message: &BalancingMessage) -> Result<Option<usize>> {
+        let Some(bus_name) = message.transmitters.iter().find_map(|node| self.pins.get(node)) else {
+            return Ok(None);
+        };
+        let Some(bus) = buses.iter().position(|bus| &bus.name == bus_name) else {
+            return Err(errors::ConfigError::InvalidBusBalancing(format!(
+                "message {} is pinned to the bus {bus_name}, which doesn't exist",
+                message.name
+            )));
+        };
+        Ok(Some(bus))
+    }
+}
+
+impl BusBalancer for PinnedBalancer {
+    fn balance(&self, buses: &[BalancingBus], messages: &[BalancingMessage]) -> Result<Vec<usize>> {
+        let mut loads: Vec<f64> = buses.iter().map(|bus| bus.load).collect();
+        let mut assignment = vec![None; messages.len()];
+        for (i, message) in messages.iter().enumerate() {
+            if let Some(bus) = self.pinned_bus(buses, message)? {
+                loads[bus] += message.load;
+                assignment[i] = Some(bus);
+            }
+        }
+        for i in by_load(messages) {
+            if assignment[i].is_none() {
+                let bus = min_load(buses, &loads, &messages[i])?;
+                loads[bus] += messages[i].load;
+                assignment[i] = Some(bus);
+            }
+        }
+        Ok(assignment.into_iter().map(|bus| bus.expect("every message is placed")).collect())
+    }
+}
@@ -0,0 +1,126 @@
+use std::time::Duration;
+
+use crate::{
+    config::{bus::BusRef, FrameTimeModel, MessageRef},
+    errors::{self, Result},
+};
+
+use super::{message_builder::MessageBuilderUsage, stream_builder::StreamBuilder, MessageBuilder};
+
+// Bandwidth quotas of tagged messages on a bus. Streams that declare an
+// elastic interval are slowed down (largest savings first) until the
+// quota is met, remaining violations are reported as diagnostics.
+
+#[derive(Debug, Clone)]
+pub struct BusQuota {
+    pub tag: String,
+    pub bus: String,
+    // fraction of the baudrate.
+    pub max_load: f64,
+}
+
+pub(crate) fn validate_quotas(quotas: &[BusQuota], buses: &[BusRef]) -> Result<()> {
+    for quota in quotas {
+        if !buses.iter().any(|bus| bus.name() == quota.bus) {
+            return Err(errors::ConfigError::InvalidBusQuota(format!(
+                "quota of {} refers to the undefined bus {}",
+                quota.tag, quota.bus
+            )));
+        }
+        if !(quota.max_load > 0.0 && quota.max_load <= 1.0) {
+            return Err(errors::ConfigError::InvalidBusQuota(format!(
+                "quota of {} on {} is {}, but has to be in (0, 1]",
+                quota.tag, quota.bus, quota.max_load
+            )));
+        }
+    }
+    Ok(())
+}
+
+// same defaults as simulator::message_interval, which can't be used
+// before the usage of the messages is set.
+fn builder_interval(message: &MessageBuilder) -> Duration {
+    match &message.0.borrow().usage {
+        MessageBuilderUsage::Stream(stream) => stream.0.borrow().interval.1,
+        MessageBuilderUsage::CommandReq(command) | MessageBuilderUsage::CommandResp(command) => {
+            command.frame_interval()
+        }
+        MessageBuilderUsage::Configuration | MessageBuilderUsage::Heartbeat => {
+            Duration::from_millis(100)
+        }
+        MessageBuilderUsage::External { interval } => {
+            interval.unwrap_or(Duration::from_secs(60))
+        }
+    }
+}
+
+fn find_builder<'a>(message: &MessageRef, builders: &'a [MessageBuilder]) -> &'a MessageBuilder {
+    builders
+        .iter()
+        .find(|builder| builder.0.borrow().name == message.name())
+        .expect("message was not created by a builder")
+}
+
+fn message_load(message: &MessageRef, interval: Duration, frame_time_model: &FrameTimeModel) -> f64 {
+    message.bus().load(frame_time_model, message, interval)
+}
+
+fn quota_messages<'a>(
+    quota: &'a BusQuota,
+    messages: &'a [MessageRef],
+    builders: &'a [MessageBuilder],
+) -> impl Iterator<Item = (&'a MessageRef, &'a MessageBuilder)> {
+    messages
+        .iter()
+        .filter(|message| message.bus().name() == quota.bus)
+        .map(|message| (message, find_builder(message, builders)))
+        .filter(|(_, builder)| builder.0.borrow().tags.contains(&quota.tag))
+}
+
+/// expected load of all messages with the tag of the quota on its bus.
+pub(crate) fn tag_load(
+    quota: &BusQuota,
+    messages: &[MessageRef],
+    builders: &[MessageBuilder],
+    frame_time_model: &FrameTimeModel,
+) -> f64 {
+    quota_messages(quota, messages, builders)
+        .map(|(message, builder)| message_load(message, builder_interval(builder), frame_time_model))
+        .sum()
+}
+
+/// sets the interval of elastic streams to their elastic interval,
+/// until the load of all quotas is within their limit (if possible).
+pub(crate) fn degrade_elastic_streams(
+    quotas: &[BusQuota],
+    messages: &[MessageRef],
+    builders: &[MessageBuilder],
+    frame_time_model: &FrameTimeModel,
+) {
+    for quota in quotas {
+        let mut load = tag_load(quota, messages, builders, frame_time_model);
+        if load <= quota.max_load {
+            continue;
+        }
+        let mut candidates: Vec<(StreamBuilder, f64)> = quota_messages(quota, messages, builders)
+            .filter_map(|(message, builder)| {
+                let MessageBuilderUsage::Stream(stream) = &builder.0.borrow().usage else {
+                    return None;
+                };
+                let (_, elastic_max) = stream.0.borrow().elastic_interval?;
+                let savings = message_load(message, stream.0.borrow().interval.1, frame_time_model)
+                    - message_load(message, elastic_max, frame_time_model);
+                (savings > 0.0).then(|| (stream.clone(), savings))
+            })
+            .collect();
+        candidates.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        for (stream, savings) in candidates {
+            if load <= quota.max_load {
+                break;
+            }
+            let mut stream_data = stream.0.borrow_mut();
+            stream_data.interval = stream_data.elastic_interval.unwrap();
+            load -= savings;
+        }
+    }
+}
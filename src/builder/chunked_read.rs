@@ -0,0 +1,87 @@
+use std::rc::Rc;
+
+use crate::errors::Result;
+
+use super::{MessageBuilder, MessagePriority, NetworkBuilder};
+
+// Array entries (e.g. u16[48] cell voltages), that exceed a frame, can only
+// be read as a whole with the get protocol. A chunked read requests count
+// elements starting at start with the chunk_read_req message, which is
+// shared by all nodes. The node answers with ceil(count / elements per frame)
+// frames of {node}_{entry}_chunk_resp. Every response frame carries the
+// client_id and the index of its first element, the index is as wide as the
+// array requires and the remaining bits of the frame are filled with elements.
+
+pub const FRAME_BITS: usize = 64;
+const CLIENT_ID_BITS: usize = 8;
+
+// (element type, len) of an array type string.
+fn array_type(type_name: &str) -> Option<(&str, usize)> {
+    let (element, len) = type_name.strip_suffix(']')?.rsplit_once('[')?;
+    Some((element, len.parse().ok()?))
+}
+
+fn index_bits(len: usize) -> usize {
+    (usize::BITS - len.saturating_sub(1).leading_zeros()).max(1) as usize
+}
+
+fn create_request_message(network_builder: &NetworkBuilder) -> MessageBuilder {
+    let message = network_builder.create_message("chunk_read_req");
+    message.set_any_std_id(MessagePriority::Low);
+    // requests usually come from tools outside of the network.
+    message.mark_external();
+    let header = network_builder.define_struct("chunk_read_req_header");
+    header.add_attribute("od_index", "u13").unwrap();
+    header.add_attribute("client_id", "u8").unwrap();
+    header.add_attribute("server_id", "u8").unwrap();
+    let format = message.make_type_format();
+    format.add_type("chunk_read_req_header", "header");
+    format.add_type("u16", "start");
+    format.add_type("u16", "count");
+    message
+}
+
+/// generates the request and response messages of every array entry, that
+/// exceeds a frame. Arrays, whose elements don't fit into a response frame
+/// next to the index, can only be read with the get protocol.
+pub(crate) fn create_chunked_reads(network_builder: &NetworkBuilder) -> Result<()> {
+    let nodes = network_builder.0.borrow().nodes.borrow().clone();
+    let mut request = None;
+    for node in &nodes {
+        let node_name = node.0.borrow().name.clone();
+        let object_entries = node.0.borrow().object_entries.clone();
+        for object_entry in &object_entries {
+            let ty = object_entry.0.borrow().ty.clone();
+            let Some((element, len)) = array_type(&ty) else {
+                continue;
+            };
+            let Some(element_bits) = network_builder.type_bit_len(element) else {
+                continue;
+            };
+            let index_bits = index_bits(len);
+            let elements_per_frame = (FRAME_BITS - CLIENT_ID_BITS - index_bits) / element_bits.max(1);
+            if element_bits * len <= FRAME_BITS || elements_per_frame == 0 {
+                continue;
+            }
+            let request = request.get_or_insert_with(|| create_request_message(network_builder));
+            if !node.0.borrow().rx_messages.iter().any(|m| Rc::ptr_eq(&m.0, &request.0)) {
+                node.add_rx_message(request);
+            }
+            let entry_name = object_entry.0.borrow().name.clone();
+            let response = network_builder.create_message(&format!("{node_name}_{entry_name}_chunk_resp"));
+            response.hide();
+            response.set_any_std_id(MessagePriority::Low);
+            response.mark_external();
+            let format = response.make_type_format();
+            format.add_type("u8", "client_id");
+            format.add_type(&format!("u{index_bits}"), "index");
+            for i in 0..elements_per_frame {
+                format.add_type(element, &format!("element{i}"));
+            }
+            node.add_tx_message(&response);
+            object_entry.0.borrow_mut().chunked_read =
+                Some((response.0.borrow().name.clone(), index_bits as u8, elements_per_frame));
+        }
+    }
+    Ok(())
+}
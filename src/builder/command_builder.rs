@@ -1,13 +1,13 @@
-use std::time::Duration;
+use std::{rc::Rc, time::Duration};
 
-use crate::config::Visibility;
+use crate::{config::{Annotations, Visibility}, errors};
 
-use super::{BuilderRef, NodeBuilder, MessageBuilder, MessageTypeFormatBuilder, MessagePriority, make_builder_ref};
+use super::{BuilderRef, EnumBuilder, NodeBuilder, MessageBuilder, MessageTypeFormatBuilder, MessageTypeFormatEntry, MessagePriority, make_builder_ref};
 
 
 #[derive(Debug, Clone)]
 pub struct CommandBuilder(pub BuilderRef<CommandData>);
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CommandData {
     pub tx_node: NodeBuilder,
     pub name: String,
@@ -15,8 +15,21 @@ pub struct CommandData {
     pub call_message: MessageBuilder,
     pub call_message_format: MessageTypeFormatBuilder,
     pub resp_message: MessageBuilder,
+    pub resp_message_format: MessageTypeFormatBuilder,
+    // (name, code) of the errors added with add_error.
+    pub errors: Vec<(String, u64)>,
+    // {node}_{command}_erno, only defined if the command has errors.
+    pub erno: Option<EnumBuilder>,
     pub visibility: Visibility,
+    // expected time between two calls (or bursts of calls).
     pub expected_interval : Duration,
+    // number of back to back calls per burst.
+    pub burst : u32,
+    pub timeout : Option<Duration>,
+    // number of requests a caller may send before the first response arrives.
+    pub max_in_flight : u32,
+    pub token : Option<String>,
+    pub annotations : Annotations,
 }
 
 impl CommandBuilder {
@@ -26,13 +39,19 @@ impl CommandBuilder {
         let node_data = tx_node_builder.0.borrow();
         let network_builder = &node_data.network_builder;
         let tx_message =
-            network_builder.create_message(&format!("{}_{}_command_req", node_data.name, name), expected_interval);
+            network_builder.create_message(&format!("{}_{}_command_req", node_data.name, name));
+        if let Some(interval) = expected_interval {
+            tx_message.expect_interval(interval);
+        }
         tx_message.hide();
         tx_message.set_any_std_id(MessagePriority::High);
         let tx_message_format = tx_message.make_type_format();
 
         let rx_message =
-            network_builder.create_message(&format!("{}_{}_command_resp", node_data.name, name), expected_interval);
+            network_builder.create_message(&format!("{}_{}_command_resp", node_data.name, name));
+        if let Some(interval) = expected_interval {
+            rx_message.expect_interval(interval);
+        }
         rx_message.hide();
         rx_message.set_any_std_id(MessagePriority::Low);
         let rx_message_format = rx_message.make_type_format();
@@ -44,9 +63,17 @@ impl CommandBuilder {
             call_message: tx_message.clone(),
             call_message_format: tx_message_format,
             resp_message: rx_message.clone(),
+            resp_message_format: rx_message_format,
+            errors: vec![],
+            erno: None,
             tx_node: tx_node_builder.clone(),
             visibility: Visibility::Global,
-            expected_interval : Duration::from_millis(1000),
+            expected_interval : expected_interval.unwrap_or(Duration::from_millis(1000)),
+            burst : 1,
+            timeout : None,
+            max_in_flight : 1,
+            token : None,
+            annotations : Annotations::new(),
         }));
         tx_message.__assign_to_command_req(&new);
         rx_message.__assign_to_command_resp(&new);
@@ -55,6 +82,46 @@ impl CommandBuilder {
     pub fn expected_interval(&self, interval : Duration) {
         self.0.borrow_mut().expected_interval = interval;
     }
+    /// expected number of calls per second, e.g. 10.0 for a ping
+    /// and 0.01 for a configuration command that is hardly ever called.
+    pub fn set_expected_rate(&self, calls_per_second : f64) {
+        assert!(
+            calls_per_second.is_finite() && calls_per_second > 0.0,
+            "expected rate of command {} has to be positive",
+            self.0.borrow().name
+        );
+        self.0.borrow_mut().expected_interval = Duration::from_secs_f64(1.0 / calls_per_second);
+    }
+    /// the command is called in bursts of calls (e.g. uploading a configuration),
+    /// which are expected with the rate of the command.
+    pub fn set_burst(&self, calls : u32) {
+        assert!(calls > 0, "bursts of command {} can't be empty", self.0.borrow().name);
+        self.0.borrow_mut().burst = calls;
+    }
+    /// mean time between two req (or resp) frames, used to estimate the bus load.
+    pub(crate) fn frame_interval(&self) -> Duration {
+        let command_data = self.0.borrow();
+        command_data.expected_interval / command_data.burst
+    }
+    /// max time between the request and the response, the worst-case
+    /// round trip on the bus is checked against it (CommandTimeout diagnostic).
+    pub fn set_timeout(&self, timeout : Duration) {
+        self.0.borrow_mut().timeout = Some(timeout);
+    }
+    /// callers may pipeline up to calls requests before the responses
+    /// arrive, the callee has to buffer as many responses. Without it
+    /// every caller waits for the response (max in flight is 1).
+    pub fn set_max_in_flight(&self, calls : u32) {
+        assert!(calls > 0, "max in flight of command {} has to be positive", self.0.borrow().name);
+        self.0.borrow_mut().max_in_flight = calls;
+    }
+    /// only one caller at a time may execute the commands of the group,
+    /// it has to acquire the token with {group}_token_request first and
+    /// give it back with {group}_token_release. Both commands and the
+    /// {group}_token_owner object entry are generated by the node.
+    pub fn require_token(&self, group : &str) {
+        self.0.borrow_mut().token = Some(group.to_owned());
+    }
     pub fn hide(&self) {
         let mut command_data = self.0.borrow_mut();
         command_data.visibility = Visibility::Static;
@@ -67,13 +134,77 @@ impl CommandBuilder {
         let mut command_data = self.0.borrow_mut();
         command_data.description = Some(name.to_owned());
     }
+    /// key value metadata for downstream tools, exported to json and dbc.
+    pub fn annotate(&self, key: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .annotations
+            .insert(key.to_owned(), value.to_owned());
+    }
+    /// error code of the response in addition to Success (0) and Error (1).
+    /// A command with errors responds with its own erno enum
+    /// ({node}_{command}_erno) instead of command_resp_erno.
+    pub fn add_error(&self, name: &str, code: u64) -> errors::Result<()> {
+        let mut command_data = self.0.borrow_mut();
+        let collision = [("Success", 0), ("Error", 1)]
+            .into_iter()
+            .chain(command_data.errors.iter().map(|(other, other_code)| (other.as_str(), *other_code)))
+            .find(|(other, other_code)| *other == name || *other_code == code)
+            .map(|(other, other_code)| format!("{other} = {other_code}"));
+        if let Some(collision) = collision {
+            return Err(errors::ConfigError::DuplicatedEnumEntry(format!(
+                "error {name} = {code} of command {} collides with {collision}",
+                command_data.name
+            )));
+        }
+        if command_data.erno.is_none() {
+            let node_data = command_data.tx_node.0.borrow();
+            let erno_name = format!("{}_{}_erno", node_data.name, command_data.name);
+            let erno = node_data.network_builder.define_enum(&erno_name);
+            drop(node_data);
+            erno.add_entry("Success", Some(0))?;
+            erno.add_entry("Error", Some(1))?;
+            for entry in command_data.resp_message_format.0.borrow_mut().0.iter_mut() {
+                if let MessageTypeFormatEntry::Type(type_name, value_name) = entry {
+                    if value_name == "erno" {
+                        *type_name = erno_name.clone();
+                    }
+                }
+            }
+            command_data.erno = Some(erno);
+        }
+        command_data.erno.as_ref().unwrap().add_entry(name, Some(code))?;
+        command_data.errors.push((name.to_owned(), code));
+        Ok(())
+    }
     pub fn add_argument(&self, name: &str, ty: &str) {
         let command_data = self.0.borrow();
         command_data.call_message_format.add_type(ty, name);
     }
-    pub fn add_callee(&self, name: &str) {
-        let network_builder = self.0.borrow().tx_node.0.borrow().network_builder.clone();
+    /// the node is created if it doesn't exist yet,
+    /// adding the same callee twice has no effect.
+    pub fn add_callee(&self, name: &str) -> errors::Result<()> {
+        let command_data = self.0.borrow();
+        let tx_node_data = command_data.tx_node.0.borrow();
+        if tx_node_data.name == name {
+            return Err(errors::ConfigError::InvalidCallee(format!(
+                "{name} can't call its own command {}",
+                command_data.name
+            )));
+        }
+        let network_builder = tx_node_data.network_builder.clone();
+        drop(tx_node_data);
+        drop(command_data);
         let callee = network_builder.create_node(name);
-        callee.add_extern_command(&self);
+        let called = callee
+            .0
+            .borrow()
+            .extern_commands
+            .iter()
+            .any(|command| Rc::ptr_eq(&command.0, &self.0));
+        if !called {
+            callee.add_extern_command(self);
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use super::{message_builder::MessageIdTemplate, MessagePriority, NetworkBuilder};
+
+// Deadline-monotonic selection of the priorities: every message with a
+// deadline (see MessageBuilder::set_deadline) requests the priority of the
+// first bucket, whose threshold its deadline doesn't exceed. The resolver
+// then assigns the ids of every bucket by receive sets as usual, therefore
+// shorter deadlines always win the arbitration against longer ones of
+// another bucket. Messages without a deadline and fixed ids are untouched.
+
+/// upper bounds of the deadlines of the Realtime, High, Normal and Low
+/// buckets, longer deadlines are SuperLow.
+pub const DEFAULT_DEADLINE_THRESHOLDS: [Duration; 4] = [
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(20),
+    Duration::from_millis(100),
+];
+
+/// the priority of a message with the deadline.
+pub fn deadline_priority(deadline: Duration, thresholds: &[Duration; 4]) -> MessagePriority {
+    let bucket = thresholds
+        .iter()
+        .position(|threshold| deadline <= *threshold)
+        .unwrap_or(thresholds.len());
+    MessagePriority::ALL[bucket]
+}
+
+pub(crate) fn assign_deadline_priorities(network_builder: &NetworkBuilder, thresholds: &[Duration; 4]) {
+    let messages = network_builder.0.borrow().messages.borrow().clone();
+    for message in &messages {
+        let mut message_data = message.0.borrow_mut();
+        let Some(deadline) = message_data.deadline else {
+            continue;
+        };
+        let priority = deadline_priority(deadline, thresholds);
+        match &mut message_data.id {
+            MessageIdTemplate::AnyStd(requested)
+            | MessageIdTemplate::AnyExt(requested)
+            | MessageIdTemplate::AnyAny(requested) => *requested = priority,
+            MessageIdTemplate::StdId(_) | MessageIdTemplate::ExtId(_) => (),
+        }
+    }
+}
+
+impl NetworkBuilder {
+    /// derives the priority of every message with a deadline from the
+    /// thresholds (ascending upper bounds of the deadlines of the Realtime,
+    /// High, Normal and Low buckets), overriding the requested priority.
+    pub fn enable_deadline_priorities(&self, thresholds: [Duration; 4]) {
+        assert!(
+            thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+            "deadline thresholds have to be ascending"
+        );
+        self.0.borrow_mut().deadline_priorities = Some(thresholds);
+    }
+}
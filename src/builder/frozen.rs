@@ -0,0 +1,291 @@
+use std::{sync::Arc, time::Duration};
+
+use super::{
+    message_builder::{MessageBuilderUsage, MessageIdTemplate},
+    MessageBuilder, MessageFormat, MessageTypeFormatEntry, NetworkBuilder, NodeBuilder,
+};
+
+// A frozen network is an immutable copy of the builder state, which analysis
+// passes can traverse without borrowing the builders. Builders are shared
+// handles into RefCells, a pass that holds a borrow while calling into a
+// builder method panics with "already borrowed" and the handles can't be sent
+// to other threads. Frozen views own their data and refer to each other by
+// name, they are Send + Sync and don't change if the builders are edited
+// afterwards.
+
+#[derive(Debug, Clone)]
+pub struct FrozenNetwork {
+    nodes: Vec<Arc<FrozenNode>>,
+    messages: Vec<Arc<FrozenMessage>>,
+    buses: Vec<Arc<FrozenBus>>,
+    types: Vec<String>,
+}
+
+impl FrozenNetwork {
+    pub fn nodes(&self) -> &Vec<Arc<FrozenNode>> {
+        &self.nodes
+    }
+    /// all messages, including the ones that are generated for streams,
+    /// commands and the get/set protocol.
+    pub fn messages(&self) -> &Vec<Arc<FrozenMessage>> {
+        &self.messages
+    }
+    pub fn buses(&self) -> &Vec<Arc<FrozenBus>> {
+        &self.buses
+    }
+    /// names of all structs and enums.
+    pub fn types(&self) -> &Vec<String> {
+        &self.types
+    }
+    pub fn node(&self, name: &str) -> Option<&Arc<FrozenNode>> {
+        self.nodes.iter().find(|node| node.name == name)
+    }
+    pub fn message(&self, name: &str) -> Option<&Arc<FrozenMessage>> {
+        self.messages.iter().find(|message| message.name == name)
+    }
+    pub fn bus(&self, name: &str) -> Option<&Arc<FrozenBus>> {
+        self.buses.iter().find(|bus| bus.name == name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrozenNode {
+    name: String,
+    description: Option<String>,
+    buses: Vec<String>,
+    rx_messages: Vec<String>,
+    tx_messages: Vec<String>,
+    commands: Vec<String>,
+    extern_commands: Vec<String>,
+    object_entries: Vec<String>,
+    tx_streams: Vec<String>,
+    rx_streams: Vec<String>,
+    receive_all: bool,
+}
+
+impl FrozenNode {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// names of the buses the node is connected to.
+    pub fn buses(&self) -> &Vec<String> {
+        &self.buses
+    }
+    pub fn rx_messages(&self) -> &Vec<String> {
+        &self.rx_messages
+    }
+    pub fn tx_messages(&self) -> &Vec<String> {
+        &self.tx_messages
+    }
+    pub fn commands(&self) -> &Vec<String> {
+        &self.commands
+    }
+    /// commands of other nodes that the node calls.
+    pub fn extern_commands(&self) -> &Vec<String> {
+        &self.extern_commands
+    }
+    pub fn object_entries(&self) -> &Vec<String> {
+        &self.object_entries
+    }
+    pub fn tx_streams(&self) -> &Vec<String> {
+        &self.tx_streams
+    }
+    pub fn rx_streams(&self) -> &Vec<String> {
+        &self.rx_streams
+    }
+    pub fn receive_all(&self) -> bool {
+        self.receive_all
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrozenMessageUsage {
+    Stream(String),
+    CommandReq(String),
+    CommandResp(String),
+    Configuration,
+    Heartbeat,
+    External { interval: Option<Duration> },
+}
+
+#[derive(Debug, Clone)]
+pub struct FrozenMessage {
+    name: String,
+    description: Option<String>,
+    id: MessageIdTemplate,
+    usage: FrozenMessageUsage,
+    transmitters: Vec<String>,
+    receivers: Vec<String>,
+    bus: Option<String>,
+    attributes: Vec<String>,
+}
+
+impl FrozenMessage {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+    /// the requested id, ids are only assigned by build.
+    pub fn id(&self) -> &MessageIdTemplate {
+        &self.id
+    }
+    pub fn usage(&self) -> &FrozenMessageUsage {
+        &self.usage
+    }
+    pub fn transmitters(&self) -> &Vec<String> {
+        &self.transmitters
+    }
+    pub fn receivers(&self) -> &Vec<String> {
+        &self.receivers
+    }
+    /// the assigned bus, None if the bus is chosen by build.
+    pub fn bus(&self) -> Option<&str> {
+        self.bus.as_deref()
+    }
+    /// names of the signals or type values of the format.
+    pub fn attributes(&self) -> &Vec<String> {
+        &self.attributes
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FrozenBus {
+    name: String,
+    id: u32,
+    baudrate: u32,
+}
+
+impl FrozenBus {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    pub fn baudrate(&self) -> u32 {
+        self.baudrate
+    }
+}
+
+fn freeze_node(node: &NodeBuilder) -> FrozenNode {
+    let node_data = node.0.borrow();
+    let message_names =
+        |messages: &Vec<MessageBuilder>| messages.iter().map(|m| m.0.borrow().name.clone()).collect();
+    FrozenNode {
+        name: node_data.name.clone(),
+        description: node_data.description.clone(),
+        buses: node_data.buses.iter().map(|bus| bus.0.borrow().name.clone()).collect(),
+        rx_messages: message_names(&node_data.rx_messages),
+        tx_messages: message_names(&node_data.tx_messages),
+        commands: node_data.commands.iter().map(|c| c.0.borrow().name.clone()).collect(),
+        extern_commands: node_data
+            .extern_commands
+            .iter()
+            .map(|c| c.0.borrow().name.clone())
+            .collect(),
+        object_entries: node_data
+            .object_entries
+            .iter()
+            .map(|oe| oe.0.borrow().name.clone())
+            .collect(),
+        tx_streams: node_data.tx_streams.iter().map(|s| s.0.borrow().name.clone()).collect(),
+        rx_streams: node_data
+            .rx_streams
+            .iter()
+            .map(|s| s.0.borrow().stream_builder.0.borrow().name.clone())
+            .collect(),
+        receive_all: node_data.receive_all,
+    }
+}
+
+fn freeze_message(message: &MessageBuilder) -> FrozenMessage {
+    let message_data = message.0.borrow();
+    let node_names =
+        |nodes: &Vec<NodeBuilder>| nodes.iter().map(|n| n.0.borrow().name.clone()).collect();
+    let usage = match &message_data.usage {
+        MessageBuilderUsage::Stream(stream) => FrozenMessageUsage::Stream(stream.0.borrow().name.clone()),
+        MessageBuilderUsage::CommandReq(command) => {
+            FrozenMessageUsage::CommandReq(command.0.borrow().name.clone())
+        }
+        MessageBuilderUsage::CommandResp(command) => {
+            FrozenMessageUsage::CommandResp(command.0.borrow().name.clone())
+        }
+        MessageBuilderUsage::Configuration => FrozenMessageUsage::Configuration,
+        MessageBuilderUsage::Heartbeat => FrozenMessageUsage::Heartbeat,
+        MessageBuilderUsage::External { interval } => FrozenMessageUsage::External { interval: *interval },
+    };
+    let attributes = match &message_data.format {
+        MessageFormat::Signals(format) => format
+            .0
+            .borrow()
+            .0
+            .iter()
+            .map(|(signal, _)| signal.name().to_owned())
+            .collect(),
+        MessageFormat::Types(format) => format
+            .0
+            .borrow()
+            .0
+            .iter()
+            .filter_map(|entry| match entry {
+                MessageTypeFormatEntry::Type(_, value_name)
+                | MessageTypeFormatEntry::PlacedType(_, value_name, _) => Some(value_name.clone()),
+                MessageTypeFormatEntry::Reserved(_) => None,
+            })
+            .collect(),
+        MessageFormat::Empty => vec![],
+    };
+    FrozenMessage {
+        name: message_data.name.clone(),
+        description: message_data.description.clone(),
+        id: message_data.id.clone(),
+        usage,
+        transmitters: node_names(&message_data.transmitters),
+        receivers: node_names(&message_data.receivers),
+        bus: message_data.bus.as_ref().map(|bus| bus.0.borrow().name.clone()),
+        attributes,
+    }
+}
+
+impl NetworkBuilder {
+    /// immutable copy of the current state of the builders, which can be
+    /// shared between threads, e.g. to run analysis passes concurrently
+    /// before the final build. Later edits of the builders are not visible
+    /// in the frozen network.
+    pub fn freeze(&self) -> FrozenNetwork {
+        // the handles are cloned, such that no collection is borrowed
+        // while the builders it contains are frozen.
+        let (nodes, messages, buses, types) = {
+            let network_data = self.0.borrow();
+            let nodes = network_data.nodes.borrow().clone();
+            let messages = network_data.messages.borrow().clone();
+            let buses = network_data.buses.borrow().clone();
+            let types = network_data.types.borrow().clone();
+            (nodes, messages, buses, types)
+        };
+        FrozenNetwork {
+            nodes: nodes.iter().map(|node| Arc::new(freeze_node(node))).collect(),
+            messages: messages
+                .iter()
+                .map(|message| Arc::new(freeze_message(message)))
+                .collect(),
+            buses: buses
+                .iter()
+                .map(|bus| {
+                    let bus_data = bus.0.borrow();
+                    Arc::new(FrozenBus {
+                        name: bus_data.name.clone(),
+                        id: bus_data.id,
+                        baudrate: bus_data.baudrate,
+                    })
+                })
+                .collect(),
+            types: types.iter().map(|ty| ty.name()).collect(),
+        }
+    }
+}
@@ -10,12 +10,13 @@ pub fn import_dbc(network_builder: &NetworkBuilder, bus: &str, dbc_path: &str) -
     let mut dbc_file = File::open(dbc_path)?;
     let mut buffer = Vec::new();
     dbc_file.read_to_end(&mut buffer).unwrap();
+    network_builder.record_input_file(dbc_path, &buffer);
 
     let dbc = can_dbc::DBC::from_slice(&buffer)?;
 
     for message in dbc.messages() {
         let name = message.message_name();
-        let message_builder = network_builder.create_message(name, None);
+        let message_builder = network_builder.create_message(name);
         message_builder.assign_bus(bus);
         match dbc.message_comment(message.message_id().clone()){
             Some(desc) => message_builder.add_description(desc),
@@ -0,0 +1,79 @@
+use super::message_builder::MessageIdTemplate;
+use super::NetworkBuilder;
+use crate::config::signal::Signal;
+use crate::config::Network;
+use crate::errors::{ConfigError, Result};
+use crate::simulator;
+
+/// registers the messages of an already built network with their ids,
+/// only messages on buses that also exist in the network_builder (by name) are imported.
+pub fn import_network(network_builder: &NetworkBuilder, network: &Network, as_external: bool) -> Result<()> {
+    for message in network.messages() {
+        let bus_name = message.bus().name();
+        let bus_exists = network_builder
+            .0
+            .borrow()
+            .buses
+            .borrow()
+            .iter()
+            .any(|bus| bus.0.borrow().name == bus_name);
+        if !bus_exists {
+            continue;
+        }
+        let name = if as_external {
+            format!("external_{}", message.name())
+        } else {
+            message.name().to_owned()
+        };
+        let id = message.id().as_u32();
+        let ide = message.id().ide();
+        for existing in network_builder.0.borrow().messages.borrow().iter() {
+            let existing = existing.0.borrow();
+            if existing.name == name {
+                return Err(ConfigError::InvalidImport(format!(
+                    "message {name} is already defined"
+                )));
+            }
+            let same_id = match existing.id {
+                MessageIdTemplate::StdId(existing_id) => !ide && existing_id == id,
+                MessageIdTemplate::ExtId(existing_id) => ide && existing_id == id,
+                _ => false,
+            };
+            let same_bus = existing
+                .bus
+                .as_ref()
+                .is_some_and(|bus| bus.0.borrow().name == bus_name);
+            if same_id && same_bus {
+                return Err(ConfigError::InvalidImport(format!(
+                    "{name} and {} use the same id ({id:#X}) on {bus_name}",
+                    existing.name
+                )));
+            }
+        }
+
+        let message_builder =
+            network_builder.create_message_with_interval(&name, simulator::message_interval(message));
+        message_builder.assign_bus(bus_name);
+        if let Some(description) = message.description() {
+            message_builder.add_description(description);
+        }
+        if ide {
+            message_builder.set_ext_id(id);
+            network_builder.reserve_ext_id_range(id..id + 1);
+        } else {
+            message_builder.set_std_id(id);
+            network_builder.reserve_std_id_range(id..id + 1);
+        }
+        let signal_format = message_builder.make_signal_format();
+        for signal in message.signals() {
+            signal_format.add_signal_at(
+                Signal::new(signal.name(), signal.description(), signal.ty().clone(), 0),
+                signal.byte_offset(),
+            )?;
+        }
+        if as_external {
+            message_builder.mark_external();
+        }
+    }
+    Ok(())
+}
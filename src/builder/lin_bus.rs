@@ -0,0 +1,317 @@
+use std::time::Duration;
+
+use crate::{
+    config::{
+        lin_bus::{self, LinBus, LinBusRef, LinFrame, LinScheduleTable},
+        make_config_ref,
+        signal::Signal,
+        MessageRef,
+    },
+    errors::{self, Result},
+};
+
+use super::{make_builder_ref, message_builder::MessageBuilderUsage, BuilderRef, MessageBuilder, MessageSignalFormatBuilder, NetworkBuilder};
+
+// a slot has to fit the whole frame, including
+// the 40% tolerance of the specification.
+const FRAME_TIME_TOLERANCE: f64 = 1.4;
+
+#[derive(Debug, Clone)]
+pub struct LinBusBuilder(pub BuilderRef<LinBusData>);
+#[derive(Debug, Clone)]
+pub struct LinBusData {
+    pub name: String,
+    pub baudrate: u32,
+    pub network_builder: NetworkBuilder,
+    pub master: Option<String>,
+    pub slaves: Vec<String>,
+    pub frames: Vec<LinFrameBuilder>,
+    pub schedule_tables: Vec<LinScheduleTableBuilder>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinFrameBuilder(pub BuilderRef<LinFrameData>);
+#[derive(Debug, Clone)]
+pub struct LinFrameData {
+    pub name: String,
+    pub lin_bus: String,
+    pub network_builder: NetworkBuilder,
+    pub id: u8,
+    pub publisher: String,
+    pub subscribers: Vec<String>,
+    pub format: MessageSignalFormatBuilder,
+    pub gateway_message: Option<MessageBuilder>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LinScheduleTableBuilder(pub BuilderRef<LinScheduleTableData>);
+#[derive(Debug, Clone)]
+pub struct LinScheduleTableData {
+    pub name: String,
+    // (frame name, delay until the next slot)
+    pub slots: Vec<(String, Duration)>,
+}
+
+impl LinBusBuilder {
+    pub fn new(name: &str, baudrate: Option<u32>, network_builder: &NetworkBuilder) -> Self {
+        LinBusBuilder(make_builder_ref(LinBusData {
+            name: name.to_owned(),
+            baudrate: baudrate.unwrap_or(19200),
+            network_builder: network_builder.clone(),
+            master: None,
+            slaves: vec![],
+            frames: vec![],
+            schedule_tables: vec![],
+        }))
+    }
+    /// the master is a node of the CAN network, which also
+    /// forwards frames between the LIN cluster and CAN.
+    pub fn set_master(&self, node_name: &str) {
+        let network_builder = self.0.borrow().network_builder.clone();
+        network_builder.create_node(node_name);
+        self.0.borrow_mut().master = Some(node_name.to_owned());
+    }
+    /// slaves are only part of the LIN cluster.
+    pub fn add_slave(&self, name: &str) {
+        let mut bus_data = self.0.borrow_mut();
+        if !bus_data.slaves.iter().any(|slave| slave == name) {
+            bus_data.slaves.push(name.to_owned());
+        }
+    }
+    /// publisher is the master or a slave, that responds to the header of the frame.
+    pub fn create_frame(&self, name: &str, id: u8, publisher: &str) -> LinFrameBuilder {
+        let frame = LinFrameBuilder(make_builder_ref(LinFrameData {
+            name: name.to_owned(),
+            lin_bus: self.0.borrow().name.clone(),
+            network_builder: self.0.borrow().network_builder.clone(),
+            id,
+            publisher: publisher.to_owned(),
+            subscribers: vec![],
+            format: MessageSignalFormatBuilder::new(),
+            gateway_message: None,
+        }));
+        self.0.borrow_mut().frames.push(frame.clone());
+        frame
+    }
+    pub fn create_schedule_table(&self, name: &str) -> LinScheduleTableBuilder {
+        let table = LinScheduleTableBuilder(make_builder_ref(LinScheduleTableData {
+            name: name.to_owned(),
+            slots: vec![],
+        }));
+        self.0.borrow_mut().schedule_tables.push(table.clone());
+        table
+    }
+}
+
+impl LinFrameBuilder {
+    pub fn add_subscriber(&self, name: &str) {
+        let mut frame_data = self.0.borrow_mut();
+        if !frame_data.subscribers.iter().any(|subscriber| subscriber == name) {
+            frame_data.subscribers.push(name.to_owned());
+        }
+    }
+    /// the signals of the frame, with the same placement rules as CAN messages.
+    pub fn signal_format(&self) -> MessageSignalFormatBuilder {
+        self.0.borrow().format.clone()
+    }
+    /// the master forwards the frame to a CAN message ({lin bus}_{frame}),
+    /// which gets the signals of the frame and the rate of the schedule on build.
+    pub fn forward_to_can(&self) -> MessageBuilder {
+        let mut frame_data = self.0.borrow_mut();
+        if let Some(message) = &frame_data.gateway_message {
+            return message.clone();
+        }
+        let message = frame_data
+            .network_builder
+            .create_message(&format!("{}_{}", frame_data.lin_bus, frame_data.name));
+        frame_data.gateway_message = Some(message.clone());
+        message
+    }
+}
+
+impl LinScheduleTableBuilder {
+    pub fn add_slot(&self, frame_name: &str, delay: Duration) {
+        self.0
+            .borrow_mut()
+            .slots
+            .push((frame_name.to_owned(), delay));
+    }
+}
+
+// signals with their start bit, placed like the signals of messages.
+fn placed_signals(format: &MessageSignalFormatBuilder) -> Vec<(Signal, usize)> {
+    let mut offset = 0;
+    let mut signals = vec![];
+    for (signal, start_bit) in format.0.borrow().0.iter() {
+        if let Some(start_bit) = start_bit {
+            offset = *start_bit;
+        }
+        signals.push((signal.clone(), offset));
+        offset += signal.size() as usize;
+    }
+    signals
+}
+
+fn frame_dlc(format: &MessageSignalFormatBuilder) -> usize {
+    placed_signals(format)
+        .iter()
+        .map(|(signal, offset)| offset + signal.size() as usize)
+        .max()
+        .unwrap_or(0)
+        .div_ceil(8)
+}
+
+pub(crate) fn check_lin_bus(lin_bus: &LinBusBuilder) -> Result<()> {
+    let bus_data = lin_bus.0.borrow();
+    let bus_name = &bus_data.name;
+    let invalid = |msg: String| Err(errors::ConfigError::InvalidLinConfiguration(msg));
+    let Some(master) = &bus_data.master else {
+        return invalid(format!("lin bus {bus_name} has no master"));
+    };
+    let is_member = |name: &str| name == master || bus_data.slaves.iter().any(|slave| slave == name);
+    for (i, frame) in bus_data.frames.iter().enumerate() {
+        let frame_data = frame.0.borrow();
+        let frame_name = &frame_data.name;
+        if frame_data.id > 0x3F {
+            return invalid(format!("{bus_name}::{frame_name}: id {:#X} is not in 0..=0x3F", frame_data.id));
+        }
+        for other in &bus_data.frames[..i] {
+            let other_data = other.0.borrow();
+            if other_data.id == frame_data.id || &other_data.name == frame_name {
+                return invalid(format!(
+                    "{bus_name}::{frame_name} and {} use the same name or id",
+                    other_data.name
+                ));
+            }
+        }
+        if !is_member(&frame_data.publisher) {
+            return invalid(format!(
+                "{bus_name}::{frame_name}: publisher {} is neither master nor slave",
+                frame_data.publisher
+            ));
+        }
+        if let Some(subscriber) = frame_data.subscribers.iter().find(|s| !is_member(s)) {
+            return invalid(format!(
+                "{bus_name}::{frame_name}: subscriber {subscriber} is neither master nor slave"
+            ));
+        }
+        let dlc = frame_dlc(&frame_data.format);
+        if !(1..=8).contains(&dlc) {
+            return invalid(format!(
+                "{bus_name}::{frame_name}: requires {dlc} bytes, but frames carry 1 to 8 bytes"
+            ));
+        }
+    }
+    for table in &bus_data.schedule_tables {
+        let table_data = table.0.borrow();
+        for (frame_name, delay) in &table_data.slots {
+            let Some(frame) = bus_data
+                .frames
+                .iter()
+                .find(|frame| &frame.0.borrow().name == frame_name)
+            else {
+                return invalid(format!(
+                    "schedule table {bus_name}::{}: frame {frame_name} is not defined",
+                    table_data.name
+                ));
+            };
+            let dlc = frame_dlc(&frame.0.borrow().format) as u8;
+            let min_delay = lin_bus::frame_time(dlc, bus_data.baudrate).mul_f64(FRAME_TIME_TOLERANCE);
+            if *delay < min_delay {
+                return invalid(format!(
+                    "schedule table {bus_name}::{}: slot of {frame_name} is {delay:?}, but the frame takes up to {min_delay:?}",
+                    table_data.name
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+// the first schedule table that contains the frame defines its rate.
+fn frame_interval(bus_data: &LinBusData, frame_name: &str) -> Option<Duration> {
+    bus_data.schedule_tables.iter().find_map(|table| {
+        let table_data = table.0.borrow();
+        let count = table_data
+            .slots
+            .iter()
+            .filter(|(name, _)| name == frame_name)
+            .count() as u32;
+        let cycle_time: Duration = table_data.slots.iter().map(|(_, delay)| *delay).sum();
+        (count > 0).then(|| cycle_time / count)
+    })
+}
+
+pub(crate) fn create_gateway_messages(lin_bus: &LinBusBuilder) -> Result<()> {
+    let bus_data = lin_bus.0.borrow();
+    let master = bus_data.network_builder.create_node(bus_data.master.as_ref().unwrap());
+    for frame in &bus_data.frames {
+        let frame_data = frame.0.borrow();
+        let Some(message) = &frame_data.gateway_message else {
+            continue;
+        };
+        message.0.borrow_mut().usage = MessageBuilderUsage::External {
+            interval: frame_interval(&bus_data, &frame_data.name),
+        };
+        let signal_format = message.make_signal_format();
+        for (signal, offset) in placed_signals(&frame_data.format) {
+            signal_format.add_signal_at(signal, offset)?;
+        }
+        master.add_tx_message(message);
+    }
+    Ok(())
+}
+
+pub(crate) fn build_lin_bus(lin_bus: &LinBusBuilder, messages: &[MessageRef]) -> LinBusRef {
+    let bus_data = lin_bus.0.borrow();
+    let frames = bus_data
+        .frames
+        .iter()
+        .map(|frame| {
+            let frame_data = frame.0.borrow();
+            let signals = placed_signals(&frame_data.format)
+                .into_iter()
+                .map(|(signal, offset)| {
+                    make_config_ref(Signal {
+                        name: format!("{}_{}", frame_data.name, signal.name),
+                        offset,
+                        ..signal
+                    })
+                })
+                .collect();
+            let gateway_message = frame_data.gateway_message.as_ref().map(|message| {
+                let name = &message.0.borrow().name;
+                messages
+                    .iter()
+                    .find(|m| m.name() == name)
+                    .expect("gateway message was not added to the network")
+                    .clone()
+            });
+            make_config_ref(LinFrame::new(
+                frame_data.name.clone(),
+                frame_data.id,
+                frame_data.publisher.clone(),
+                frame_data.subscribers.clone(),
+                signals,
+                frame_dlc(&frame_data.format) as u8,
+                gateway_message,
+            ))
+        })
+        .collect();
+    let schedule_tables = bus_data
+        .schedule_tables
+        .iter()
+        .map(|table| {
+            let table_data = table.0.borrow();
+            LinScheduleTable::new(table_data.name.clone(), table_data.slots.clone())
+        })
+        .collect();
+    make_config_ref(LinBus::new(
+        bus_data.name.clone(),
+        bus_data.baudrate,
+        bus_data.master.clone().unwrap(),
+        bus_data.slaves.clone(),
+        frames,
+        schedule_tables,
+    ))
+}
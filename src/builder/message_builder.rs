@@ -1,33 +1,41 @@
 use std::time::Duration;
 
 use crate::{
-    config::{signal::Signal, Visibility},
+    config::{can_xl::SduType, signal::Signal, Annotations, Visibility},
     errors,
 };
 
 use super::{bus::BusBuilder, make_builder_ref, BuilderRef, NetworkBuilder, NodeBuilder, stream_builder::StreamBuilder, CommandBuilder};
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+// AUTOSAR SecOC profiles commonly truncate the mac to 32 bits.
+const DEFAULT_CMAC_LEN: u8 = 4;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
 pub enum MessagePriority {
     Realtime,
     High,
+    // priority of messages that don't request one.
+    #[default]
     Normal,
     Low,
     SuperLow,
 }
 impl MessagePriority {
+    /// all priorities ordered by their bucket index (see to_u32).
+    pub const ALL: [MessagePriority; 5] = [
+        MessagePriority::Realtime,
+        MessagePriority::High,
+        MessagePriority::Normal,
+        MessagePriority::Low,
+        MessagePriority::SuperLow,
+    ];
+    /// None if i is not a valid bucket index.
+    pub fn try_from_u32(i : u32) -> Option<MessagePriority> {
+        Self::ALL.get(i as usize).copied()
+    }
+    /// saturates to the lowest priority for indices out of range.
     pub fn from_u32(i : u32) -> MessagePriority {
-        if i == 0 {
-            MessagePriority::Realtime
-        }else if i == 1 {
-            MessagePriority::High
-        }else if i == 2{
-            MessagePriority::Normal
-        }else if i == 3 {
-            MessagePriority::Low
-        }else {
-            MessagePriority::SuperLow
-        }
+        Self::try_from_u32(i).unwrap_or(MessagePriority::SuperLow)
     }
     pub fn to_u32(&self) -> u32 {
         match &self {
@@ -39,7 +47,7 @@ impl MessagePriority {
         }
     }
     pub const fn count() -> usize {
-        5
+        Self::ALL.len()
     }
 }
 
@@ -62,10 +70,21 @@ pub enum MessageIdTemplate {
     AnyAny(MessagePriority),
 }
 
+// records where the resolver placed a message.
+#[derive(Debug, Clone)]
+pub struct MessageResolution {
+    pub setcode: u32,
+    pub setcode_len: u32,
+    // bucket of the requested priority, None for fixed ids.
+    pub requested_priority: Option<u32>,
+    pub priority_bucket: Option<u32>,
+    pub position: Option<u32>,
+}
+
 #[derive(Clone, Debug)]
 pub struct MessageBuilder(pub BuilderRef<MessageData>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MessageData {
     pub name: String,
     pub description: Option<String>,
@@ -76,10 +95,41 @@ pub struct MessageData {
     pub transmitters : Vec<NodeBuilder>,
     pub visibility: Visibility,
     pub bus: Option<BusBuilder>,
+    // names of the buses the message was pinned to with assign_bus, in order.
+    pub bus_pins : Vec<String>,
+    pub fallback_bus: Option<BusBuilder>,
     pub usage : MessageBuilderUsage,
+    pub resolution : Option<MessageResolution>,
+    pub version : Option<String>,
+    // (since, note)
+    pub deprecation : Option<(String, String)>,
+    // frames that are sent or received by devices outside of the network.
+    pub external : bool,
+    pub processing_deadline : Option<Duration>,
+    // max time between the release and the end of the transmission.
+    pub deadline : Option<Duration>,
+    // (max frames, window) of event driven bursts.
+    pub burst : Option<(u32, Duration)>,
+    // groups of messages that share a bus quota.
+    pub tags : Vec<String>,
+    // (key id, mac bytes)
+    pub cmac : Option<(u32, u8)>,
+    // traceability of interface reviews.
+    pub author : Option<String>,
+    pub requirement : Option<String>,
+    pub revision_note : Option<String>,
+    pub annotations : Annotations,
+    // CAN XL metadata, only valid on buses in XL mode.
+    pub sdu_type : Option<SduType>,
+    pub acceptance_field : Option<u32>,
+    // the transmitter answers remote frames (RTR) with the message.
+    pub remote_requestable : bool,
+    // names of the nodes that send the remote frames.
+    pub remote_requesters : Vec<String>,
+    pub remote_response_latency : Option<Duration>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum MessageFormat {
     Signals(MessageSignalFormatBuilder),
     Types(MessageTypeFormatBuilder),
@@ -88,12 +138,21 @@ pub enum MessageFormat {
 
 #[derive(Clone, Debug)]
 pub struct MessageSignalFormatBuilder(pub BuilderRef<MessageSignalFormatData>);
-#[derive(Debug)]
-pub struct MessageSignalFormatData(pub Vec<Signal>);
+// signals with an explicit start bit, None for sequential packing.
+#[derive(Debug, Clone)]
+pub struct MessageSignalFormatData(pub Vec<(Signal, Option<usize>)>);
 #[derive(Clone, Debug)]
 pub struct MessageTypeFormatBuilder(pub BuilderRef<MessageTypeFormatData>);
-#[derive(Debug)]
-pub struct MessageTypeFormatData(pub Vec<(String, String)>);
+#[derive(Debug, Clone)]
+pub struct MessageTypeFormatData(pub Vec<MessageTypeFormatEntry>);
+#[derive(Debug, Clone)]
+pub enum MessageTypeFormatEntry {
+    // (type_name, value_name)
+    Type(String, String),
+    // (type_name, value_name, start_bit)
+    PlacedType(String, String, usize),
+    Reserved(u8),
+}
 
 impl MessagePriority {
     pub fn min_id(&self) -> u32 {
@@ -114,32 +173,63 @@ impl MessageBuilder {
         MessageBuilder(make_builder_ref(MessageData {
             name: name.to_owned(),
             description: None,
-            id: MessageIdTemplate::AnyAny(MessagePriority::Normal),
+            id: MessageIdTemplate::AnyAny(MessagePriority::default()),
             format: MessageFormat::Empty,
             network_builder: network_builder.clone(),
             visibility: Visibility::Global,
             bus: None,
+            bus_pins : vec![],
+            fallback_bus: None,
             receivers : vec![],
             transmitters : vec![],
             usage : MessageBuilderUsage::External { interval: expected_interval },
+            resolution : None,
+            version : None,
+            deprecation : None,
+            external : false,
+            processing_deadline : None,
+            deadline : None,
+            burst : None,
+            tags : vec![],
+            cmac : None,
+            author : None,
+            requirement : None,
+            revision_note : None,
+            annotations : Annotations::new(),
+            sdu_type : None,
+            acceptance_field : None,
+            remote_requestable : false,
+            remote_requesters : vec![],
+            remote_response_latency : None,
             // usage,
         }))
     }
+    /// sdu type of the CAN XL frame, the message has to be on a bus in XL mode.
+    pub fn set_sdu_type(&self, sdu_type: SduType) {
+        self.0.borrow_mut().sdu_type = Some(sdu_type);
+    }
+    /// acceptance field of the CAN XL frame (defaults to the priority id),
+    /// the message has to be on a bus in XL mode.
+    pub fn set_acceptance_field(&self, acceptance_field: u32) {
+        self.0.borrow_mut().acceptance_field = Some(acceptance_field);
+    }
+    /// pins the message to the bus, the bus balancing never moves pinned
+    /// messages. Pinning a message to a second bus is reported as a BusPin
+    /// diagnostic by the build.
     pub fn assign_bus(&self, bus_name: &str) -> BusBuilder {
+        let bus = self.place_on_bus(bus_name);
+        self.0.borrow_mut().bus_pins.push(bus_name.to_owned());
+        bus
+    }
+    // sets the bus without pinning the message, used by the bus balancing.
+    pub(crate) fn place_on_bus(&self, bus_name: &str) -> BusBuilder {
         let mut message_data = self.0.borrow_mut();
-        if message_data.bus.is_some() {
-            #[cfg(feature = "logging_info")]
-            println!("WARNING: reassiged bus of message : {}
-                     but messages can only be assigned to one bus,
-                     if splitting is required it is done automatically by 
-                     the id, filter and load balancing code!", message_data.name);
-        }
         let network_data = message_data.network_builder.0.borrow_mut();
         let bus = network_data
             .buses
             .borrow()
             .iter()
-            .find(|bus| &bus.0.borrow().name == bus_name)
+            .find(|bus| bus.0.borrow().name == bus_name)
             .cloned();
         drop(network_data);
         match bus {
@@ -154,6 +244,131 @@ impl MessageBuilder {
             }
         }
     }
+    // the fallback bus is used by the firmware if the transceiver of the
+    // primary bus fails. The id of the message is kept on the fallback bus.
+    pub fn set_fallback_bus(&self, bus_name: &str) -> BusBuilder {
+        let mut message_data = self.0.borrow_mut();
+        let network_data = message_data.network_builder.0.borrow_mut();
+        let bus = network_data
+            .buses
+            .borrow()
+            .iter()
+            .find(|bus| bus.0.borrow().name == bus_name)
+            .cloned();
+        drop(network_data);
+        let bus = match bus {
+            Some(bus) => bus,
+            None => message_data.network_builder.create_bus(bus_name, None),
+        };
+        message_data.fallback_bus = Some(bus.clone());
+        bus
+    }
+    /// semantic version (major.minor.patch) of the message layout.
+    pub fn set_version(&self, version: &str) {
+        self.0.borrow_mut().version = Some(version.to_owned());
+    }
+    /// marks the message as deprecated, removing it from the network
+    /// afterwards is not considered a breaking change by Network::diff.
+    pub fn deprecate(&self, since: &str, note: &str) {
+        self.0.borrow_mut().deprecation = Some((since.to_owned(), note.to_owned()));
+    }
+    /// designer of the message, who has to be consulted on layout changes.
+    pub fn set_author(&self, author: &str) {
+        self.0.borrow_mut().author = Some(author.to_owned());
+    }
+    /// ticket or requirement (id or link) the message implements.
+    pub fn set_requirement(&self, requirement: &str) {
+        self.0.borrow_mut().requirement = Some(requirement.to_owned());
+    }
+    /// rationale of the latest change of the message.
+    pub fn set_revision_note(&self, note: &str) {
+        self.0.borrow_mut().revision_note = Some(note.to_owned());
+    }
+    /// max time between the reception and the processing of the message
+    /// on the receiving nodes, used by the codegen to decide whether
+    /// the message is handled in the isr or the main loop.
+    pub fn set_processing_deadline(&self, deadline: Duration) {
+        self.0.borrow_mut().processing_deadline = Some(deadline);
+    }
+    /// max time between the release of a frame and the end of its
+    /// transmission. Checked against the worst-case response time, with
+    /// NetworkBuilder::enable_deadline_priorities it also selects the priority.
+    pub fn set_deadline(&self, deadline: Duration) {
+        self.0.borrow_mut().deadline = Some(deadline);
+    }
+    /// the transmitter sends the message as answer to a remote frame (RTR)
+    /// with its id, e.g. for sensors that are polled. Only valid for
+    /// classic CAN frames.
+    pub fn set_remote_requestable(&self, requestable: bool) {
+        self.0.borrow_mut().remote_requestable = requestable;
+    }
+    /// the node may poll the message with remote frames,
+    /// requires set_remote_requestable.
+    pub fn add_remote_requester(&self, node_name: &str) {
+        let mut message_data = self.0.borrow_mut();
+        if !message_data.remote_requesters.iter().any(|n| n == node_name) {
+            message_data.remote_requesters.push(node_name.to_owned());
+        }
+    }
+    /// max time between a remote frame and the answer of the transmitter,
+    /// requires set_remote_requestable.
+    pub fn set_remote_response_latency(&self, latency: Duration) {
+        self.0.borrow_mut().remote_response_latency = Some(latency);
+    }
+    /// the message is event driven and up to max_frames frames are sent
+    /// within the window (e.g. 5 frames back to back on a brake event).
+    /// The load and response time analysis assume the worst case, in which
+    /// every window contains a full burst.
+    pub fn set_burst(&self, max_frames: u32, window: Duration) {
+        let name = self.0.borrow().name.clone();
+        assert!(max_frames > 0, "bursts of message {name} can't be empty");
+        assert!(!window.is_zero(), "burst window of message {name} can't be zero");
+        self.0.borrow_mut().burst = Some((max_frames, window));
+    }
+    /// authenticates the frame with a 4 byte truncated AES-CMAC, which is
+    /// appended byte aligned after the signals and counts towards the dlc.
+    /// key_id identifies the key in the security modules of the transmitter
+    /// and the receivers. Building fails if the signals and the mac don't
+    /// fit into a single frame.
+    pub fn enable_cmac(&self, key_id: u32) {
+        self.enable_truncated_cmac(key_id, DEFAULT_CMAC_LEN);
+    }
+    /// see enable_cmac, with a mac of mac_len bytes.
+    pub fn enable_truncated_cmac(&self, key_id: u32, mac_len: u8) {
+        assert!((1..=8).contains(&mac_len), "the mac has to be 1 to 8 bytes long");
+        self.0.borrow_mut().cmac = Some((key_id, mac_len));
+    }
+    /// bits of the payload including the mac (see enable_cmac).
+    pub(crate) fn authenticated_bit_len(&self, payload_bits: usize) -> usize {
+        match self.0.borrow().cmac {
+            Some((_, mac_len)) => payload_bits.div_ceil(8) * 8 + mac_len as usize * 8,
+            None => payload_bits,
+        }
+    }
+    /// marks the message as intentionally sent or received by a device
+    /// outside of the network, such that missing receivers or transmitters
+    /// are not reported as diagnostics.
+    pub fn mark_external(&self) {
+        self.0.borrow_mut().external = true;
+    }
+    /// adds the message to a group, which can be limited
+    /// to a fraction of a bus with NetworkBuilder::set_bus_quota.
+    pub fn add_tag(&self, tag: &str) {
+        let mut message_data = self.0.borrow_mut();
+        if !message_data.tags.iter().any(|t| t == tag) {
+            message_data.tags.push(tag.to_owned());
+        }
+    }
+    pub(crate) fn resolve_fallback_bus(&self) -> Option<BusBuilder> {
+        let message_data = self.0.borrow();
+        match &message_data.fallback_bus {
+            Some(fallback_bus) => Some(fallback_bus.clone()),
+            None if message_data.transmitters.len() == 1 => {
+                message_data.transmitters[0].0.borrow().fallback_bus.clone()
+            }
+            None => None,
+        }
+    }
     pub fn __assign_to_stream(&self, stream : &StreamBuilder) {
         self.0.borrow_mut().usage = MessageBuilderUsage::Stream(stream.clone());
     }
@@ -169,6 +384,17 @@ impl MessageBuilder {
     pub fn __assign_to_heartbeat(&self) {
         self.0.borrow_mut().usage = MessageBuilderUsage::Heartbeat;
     }
+    /// expected transmit interval of the message, used to estimate the bus load.
+    /// Streams and commands derive their interval themselves, so the interval
+    /// is ignored once the message is assigned to one of them.
+    pub fn expect_interval(&self, interval: Duration) -> MessageBuilder {
+        let mut message_data = self.0.borrow_mut();
+        if let MessageBuilderUsage::External { .. } = message_data.usage {
+            message_data.usage = MessageBuilderUsage::External { interval: Some(interval) };
+        }
+        drop(message_data);
+        self.clone()
+    }
     pub fn hide(&self) {
         let mut message_data = self.0.borrow_mut();
         message_data.visibility = Visibility::Static;
@@ -205,6 +431,13 @@ impl MessageBuilder {
         let mut message_data = self.0.borrow_mut();
         message_data.description = Some(name.to_owned());
     }
+    /// key value metadata for downstream tools, exported to json and dbc.
+    pub fn annotate(&self, key: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .annotations
+            .insert(key.to_owned(), value.to_owned());
+    }
     pub fn add_transmitter(&self, node_name: &str) {
         // check if node with {name} exists.
         let message_data = self.0.borrow();
@@ -260,16 +493,45 @@ impl MessageSignalFormatBuilder {
         MessageSignalFormatBuilder(make_builder_ref(MessageSignalFormatData(vec![])))
     }
     pub fn add_signal(&self, signal: Signal) -> errors::Result<()> {
+        self.push_signal(signal, None)
+    }
+    /// places the signal at an explicit start bit instead of packing it
+    /// directly after the previous signal. Following signals are packed after it.
+    pub fn add_signal_at(&self, signal: Signal, start_bit: usize) -> errors::Result<()> {
+        self.push_signal(signal, Some(start_bit))
+    }
+    fn push_signal(&self, signal: Signal, start_bit: Option<usize>) -> errors::Result<()> {
         let mut builder_data = self.0.borrow_mut();
-        if builder_data.0.iter().any(|s| s.name() == signal.name()) {
+        if builder_data.0.iter().any(|(s, _)| s.name() == signal.name()) {
             return Err(errors::ConfigError::DuplicatedSignal(format!(
                 "Dupplicated signal name in message: {}",
                 signal.name()
             )));
         }
-        builder_data.0.push(signal);
+        builder_data.0.push((signal, start_bit));
         Ok(())
     }
+    /// reserves bits for future signals, the offsets of the following
+    /// signals don't change when the reserved bits are used later.
+    pub fn add_reserved(&self, bits: u8) {
+        let mut builder_data = self.0.borrow_mut();
+        let index = builder_data.0.iter().filter(|(s, _)| s.is_reserved()).count();
+        builder_data
+            .0
+            .push((Signal::create_reserved(&format!("reserved{index}"), bits), None));
+    }
+}
+impl MessageSignalFormatData {
+    /// number of bits required to encode all signals.
+    pub fn bit_len(&self) -> usize {
+        let mut offset = 0;
+        let mut bit_len = 0;
+        for (signal, start_bit) in &self.0 {
+            offset = start_bit.unwrap_or(offset) + signal.size() as usize;
+            bit_len = bit_len.max(offset);
+        }
+        bit_len
+    }
 }
 impl MessageTypeFormatBuilder {
     pub fn new() -> MessageTypeFormatBuilder {
@@ -277,8 +539,25 @@ impl MessageTypeFormatBuilder {
     }
     pub fn add_type(&self, type_name: &str, value_name: &str) {
         let mut builder_data = self.0.borrow_mut();
-        builder_data
-            .0
-            .push((type_name.to_owned(), value_name.to_owned()));
+        builder_data.0.push(MessageTypeFormatEntry::Type(
+            type_name.to_owned(),
+            value_name.to_owned(),
+        ));
+    }
+    /// places the value at an explicit start bit instead of packing it
+    /// directly after the previous value. Following values are packed after it.
+    pub fn add_type_at(&self, type_name: &str, value_name: &str, start_bit: usize) {
+        let mut builder_data = self.0.borrow_mut();
+        builder_data.0.push(MessageTypeFormatEntry::PlacedType(
+            type_name.to_owned(),
+            value_name.to_owned(),
+            start_bit,
+        ));
+    }
+    /// reserves bits for future values, the offsets of the following
+    /// values don't change when the reserved bits are used later.
+    pub fn add_reserved(&self, bits: u8) {
+        let mut builder_data = self.0.borrow_mut();
+        builder_data.0.push(MessageTypeFormatEntry::Reserved(bits));
     }
 }
@@ -1,8 +1,13 @@
-use crate::builder::{MessageBuilder, MessagePriority, NodeBuilder};
+use std::ops::Range;
+
+use crate::{
+    builder::{message_builder::MessageResolution, MessageBuilder, MessagePriority, NodeBuilder},
+    errors::{CapacityError, ConfigError, Result},
+};
 
 use super::{
     fixed_messages::FixedMessage,
-    set_minimization::{MinimizedNetwork, MinimizedSet},
+    set_minimization::{MinimizedNetwork, MinimizedSet, EXT_ID_LENGTH, STD_ID_LENGTH},
 };
 
 #[derive(Clone)]
@@ -38,8 +43,13 @@ impl SetCodeAllocator {
             }
         }
     }
-    pub fn allocate_any(&mut self) -> Option<u32> {
-        self.avaiable_setcodes.pop()
+    /// allocates the setcode with the fewest carved out ids,
+    /// ties are broken by the highest setcode.
+    pub fn allocate_any(&mut self, carved_out: impl Fn(u32) -> usize) -> Option<u32> {
+        let pos = (0..self.avaiable_setcodes.len())
+            .rev()
+            .min_by_key(|pos| carved_out(self.avaiable_setcodes[*pos]))?;
+        Some(self.avaiable_setcodes.remove(pos))
     }
 }
 
@@ -73,7 +83,9 @@ pub fn assign_messages_ids(
     fixed_messages: &Vec<FixedMessage>,
     minimized_network: MinimizedNetwork,
     nodes: &Vec<NodeBuilder>,
-) -> Vec<NodeFilterInfo> {
+    reserved_id_ranges: &[(Range<u32>, bool)],
+    carved_out_ids: &[u32],
+) -> Result<Vec<NodeFilterInfo>> {
     let setcode_len = (minimized_network.sets().len() as f64).log2().ceil() as u32;
     let mut setcode_allocator = SetCodeAllocator::new(setcode_len);
     let mut fixed_messages = fixed_messages.clone();
@@ -83,6 +95,7 @@ pub fn assign_messages_ids(
             .log2()
             .ceil() as u32,
     ) as usize;
+    let priority_bits = max_messages_per_set.ilog2();
 
     let mut set_pair: Vec<(Option<AssignedSet>, &MinimizedSet)> =
         std::iter::zip(std::iter::repeat(None), minimized_network.sets()).collect();
@@ -130,6 +143,15 @@ pub fn assign_messages_ids(
         let Some(setcode) = setcode else {
             continue;
         };
+        for fixed_message in &assigned_fixed_messages {
+            fixed_message.message().0.borrow_mut().resolution = Some(MessageResolution {
+                setcode,
+                setcode_len,
+                requested_priority: None,
+                priority_bucket: None,
+                position: None,
+            });
+        }
 
         *assigned_set = Some(AssignedSet {
             setcode,
@@ -143,14 +165,38 @@ pub fn assign_messages_ids(
         });
     }
 
+    // the loop above removed every fixed message that joined a set, the
+    // remaining ones have no set with their receivers, a conflicting
+    // setcode or found their set full. They keep their id and are
+    // received with an exact filter.
+    let preassigned_messages = fixed_messages;
+
+    // reserved ranges and preassigned messages carve ids out of the sets,
+    // a set that finds no free id left is reported as a capacity error.
+    let set_count = set_pair.len();
+    let id_space_error = |assigned_sets: usize, ide: bool| {
+        ConfigError::Capacity(CapacityError::IdSpace {
+            needed_setcodes: set_count,
+            available_setcodes: assigned_sets,
+            priority_bits,
+            id_bits: if ide { EXT_ID_LENGTH } else { STD_ID_LENGTH },
+        })
+    };
+
     // assign other ids.
-    for (assigned_set, set) in set_pair.iter_mut() {
+    for (set_index, (assigned_set, set)) in set_pair.iter_mut().enumerate() {
         let assigned_set = match assigned_set {
             Some(set) => set,
             None => {
+                let setcode_mask = (1u32 << setcode_len) - 1;
                 let setcode = setcode_allocator
-                    .allocate_any()
-                    .expect("ran out of setcodes to allocate");
+                    .allocate_any(|setcode| {
+                        if set.id().ide() {
+                            return 0;
+                        }
+                        carved_out_ids.iter().filter(|id| *id & setcode_mask == setcode).count()
+                    })
+                    .ok_or_else(|| id_space_error(set_index, set.id().ide()))?;
                 *assigned_set = Some(AssignedSet {
                     setcode,
                     fixed_ids: vec![],
@@ -164,30 +210,38 @@ pub fn assign_messages_ids(
         let setcode = assigned_set.setcode;
         let mut reserved_ids: Vec<u32> =
             Vec::from_iter(assigned_set.fixed_ids.clone().into_iter());
+        // ids of reserved ranges are never assigned, only fixed ids may use them.
+        let set_ide = assigned_set.ide;
+        let id_bits = if set_ide { EXT_ID_LENGTH } else { STD_ID_LENGTH };
+        let priority_count = 1u32 << (id_bits - setcode_len);
+        let is_reserved = |id: u32, reserved_ids: &Vec<u32>| {
+            reserved_ids.contains(&id)
+                || reserved_id_ranges
+                    .iter()
+                    .any(|(range, ide)| *ide == set_ide && range.contains(&id))
+                || preassigned_messages
+                    .iter()
+                    .any(|fixed| fixed.ide() == set_ide && fixed.id() == id)
+        };
 
         let bucket_layout = minimized_network.bucket_layout();
 
         let mut bucket_offset = 0;
         for prio in 0..MessagePriority::count() {
             for msg in set.messages_with_priority(prio) {
-                let prio_offset = (0..max_messages_per_set - bucket_offset).find(|prio_offset| {
-                    let priority = (bucket_offset + *prio_offset) as u32;
-                    let id = (priority << setcode_len) | setcode;
-                    !reserved_ids.contains(&id)
-                });
-                let prio_offset: i32 = match prio_offset {
-                    Some(offset) => offset as i32,
-                    None => (-1..-(bucket_offset as i32) - 1)
-                        .find(|prio_offset| {
-                            let priority = (bucket_offset as i32 + *prio_offset) as u32;
-                            let id = (priority << setcode_len) | setcode;
-                            !reserved_ids.contains(&id)
-                        })
-                        .expect(
-                            "fixed message was probably inserted in a set where there wasn't space for it",
-                        ),
-                };
-                let priority = (bucket_offset as i32 + prio_offset) as u32;
+                let is_free =
+                    |priority: u32| !is_reserved((priority << setcode_len) | setcode, &reserved_ids);
+                // search the own bucket and the lower ones first, then the higher
+                // buckets and at last the ids above the bucket layout, which are
+                // only left by the unused id bits.
+                let priority = (bucket_offset..max_messages_per_set)
+                    .map(|priority| priority as u32)
+                    .find(|priority| is_free(*priority))
+                    .or_else(|| (0..bucket_offset as u32).rev().find(|priority| is_free(*priority)))
+                    .or_else(|| {
+                        (max_messages_per_set as u32..priority_count).find(|priority| is_free(*priority))
+                    })
+                    .ok_or_else(|| id_space_error(set_index, assigned_set.ide))?;
                 let id = (priority << setcode_len) | setcode;
                 assert!(!reserved_ids.contains(&id));
                 reserved_ids.push(id);
@@ -196,6 +250,28 @@ pub fn assign_messages_ids(
                 } else {
                     msg.set_std_id(id);
                 }
+                // a message might be moved into a previous bucket if
+                // its own bucket is occupied by fixed messages. Ids past
+                // the layout are counted to the last bucket.
+                let last_bucket = MessagePriority::count() - 1;
+                let mut bucket_start = 0;
+                let mut landed_bucket = last_bucket;
+                for bucket in 0..MessagePriority::count() {
+                    if bucket == last_bucket
+                        || priority < (bucket_start + bucket_layout.bucket_size(bucket)) as u32
+                    {
+                        landed_bucket = bucket;
+                        break;
+                    }
+                    bucket_start += bucket_layout.bucket_size(bucket);
+                }
+                msg.0.borrow_mut().resolution = Some(MessageResolution {
+                    setcode,
+                    setcode_len,
+                    requested_priority: Some(prio as u32),
+                    priority_bucket: Some(landed_bucket as u32),
+                    position: Some(priority - bucket_start as u32),
+                });
                 assigned_set.fixed_messages.push(msg.clone());
                 assigned_set.fixed_ids.push(id);
                 assert!(assigned_set.ide == set.id().ide());
@@ -204,24 +280,47 @@ pub fn assign_messages_ids(
         }
     }
 
-    nodes
+    let setcode_mask = 0xFFFFFFFFu32.checked_shr(32 - setcode_len).unwrap_or(0);
+    Ok(nodes
         .iter()
         .map(|node| {
             let node_name = node.0.borrow().name.clone();
+            let mut filters: Vec<FilterInfo> = set_pair
+                .iter()
+                .map(|(a, _)| a)
+                .flatten()
+                .filter(|x| x.receivers.iter().any(|n| n.0.borrow().name == node_name))
+                .map(|x| FilterInfo::Setcode {
+                    setcode: x.setcode,
+                    setcode_len,
+                    ide: x.ide,
+                })
+                .collect();
+            for fixed in &preassigned_messages {
+                let received = fixed
+                    .message()
+                    .0
+                    .borrow()
+                    .receivers
+                    .iter()
+                    .any(|rx| rx.0.borrow().name == node_name);
+                let accepted = filters.iter().any(|filter| match filter {
+                    FilterInfo::Setcode { setcode, setcode_len: _, ide } => {
+                        *ide == fixed.ide() && fixed.id() & setcode_mask == *setcode
+                    }
+                    FilterInfo::Single { id, ide } => *ide == fixed.ide() && *id == fixed.id(),
+                });
+                if received && !accepted {
+                    filters.push(FilterInfo::Single {
+                        id: fixed.id(),
+                        ide: fixed.ide(),
+                    });
+                }
+            }
             NodeFilterInfo {
                 node: node.clone(),
-                filters: set_pair
-                    .iter()
-                    .map(|(a, _)| a)
-                    .flatten()
-                    .filter(|x| x.receivers.iter().any(|n| n.0.borrow().name == node_name))
-                    .map(|x| FilterInfo::Setcode {
-                        setcode: x.setcode,
-                        setcode_len,
-                        ide: x.ide,
-                    })
-                    .collect(),
+                filters,
             }
         })
-        .collect()
+        .collect())
 }
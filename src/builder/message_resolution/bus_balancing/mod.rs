@@ -1,10 +1,17 @@
 use std::{time::Duration, cmp::Ordering};
 
 use crate::{
-    builder::{bus::BusBuilder, MessageBuilder, NetworkBuilder},
-    config::{TypeRef, Type},
+    builder::{
+        bus::BusBuilder,
+        bus_balancing::{BalancingBus, BalancingMessage, BusBalancer},
+        MessageBuilder, MessageTypeFormatEntry, NetworkBuilder, NodeBuilder,
+    },
+    config::{bus::FramePadding, FrameTimeModel, TypeRef, Type},
+    errors,
 };
 
+use super::message_load;
+
 struct AssignedMessage {
     message: MessageWithLoad,
     bus: String,
@@ -37,78 +44,64 @@ impl MessageBusSplit {
     }
 }
 
-#[derive(PartialEq)]
-struct BusInfo {
-    bus_id: u32,
-    bus_name: String,
-    load: f64,
-}
-
-impl BusInfo {
-    pub fn new(bus_id: u32, bus_name: &str) -> Self {
-        Self {
-            bus_id,
-            bus_name: bus_name.to_owned(),
-            load: 0f64,
-        }
-    }
-}
-
-impl PartialOrd for BusInfo {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        if self.load.is_nan() || other.load.is_nan() {
-            return Some(Ordering::Equal)
-        }
-        if self.load < other.load {
-            return Some(Ordering::Less)
-        }else if self.load > other.load{
-            return Some(Ordering::Greater)
-        }else {
-            return Some(Ordering::Equal)
-        }
-    }
-}
-
-impl Eq for BusInfo { }
-impl Ord for BusInfo {
-
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.load.is_nan() || other.load.is_nan() {
-            return Ordering::Equal
-        }
-        if self.load < other.load {
-            return Ordering::Less
-        }else if self.load > other.load{
-            return Ordering::Greater
-        }else {
-            return Ordering::Equal
-        }
-    }
-}
-
 struct Buses {
-    buses: Vec<BusInfo>,
+    buses: Vec<BalancingBus>,
 }
 
 impl Buses {
-    pub fn from(buses: &Vec<BusBuilder>) -> Self {
+    pub fn from(buses: &[BusBuilder], nodes: &[NodeBuilder]) -> Self {
         Self {
             buses: buses
                 .iter()
-                .enumerate()
-                .map(|(bus_id, builder)| BusInfo::new(bus_id as u32, &builder.0.borrow().name))
+                .map(|builder| {
+                    let bus_data = builder.0.borrow();
+                    BalancingBus {
+                        name: bus_data.name.clone(),
+                        baudrate: bus_data.baudrate,
+                        load: 0f64,
+                        nodes: nodes
+                            .iter()
+                            .filter(|node| node.0.borrow().buses.iter().any(|b| b.0.borrow().name == bus_data.name))
+                            .map(|node| node.0.borrow().name.clone())
+                            .collect(),
+                        listen_only: nodes
+                            .iter()
+                            .filter(|node| node.0.borrow().listen_only.contains(&bus_data.name))
+                            .map(|node| node.0.borrow().name.clone())
+                            .collect(),
+                    }
+                })
                 .collect(),
         }
     }
 
+    fn position(&self, bus_name: &str) -> usize {
+        self.buses.iter().position(|b| b.name == bus_name).expect("invalid bus")
+    }
+
     pub fn add_message(&mut self, message : AssignedMessage) { 
-        let bus = self.buses.iter_mut().find(|b| b.bus_name == message.bus).expect("invalid bus");
-        bus.load += message.message.load;
+        let bus = self.position(&message.bus);
+        self.buses[bus].load += message.message.load;
     }
-    pub fn add_message_to_min_load(&mut self, message : MessageWithLoad) {
-        let bus = self.buses.iter_mut().min().unwrap();
-        message.message.assign_bus(&bus.bus_name);
-        bus.load += message.load;
+    // reserves the load of a message on its fallback bus, such that
+    // the fallback bus is not overloaded in degraded mode.
+    pub fn add_fallback_load(&mut self, fallback_bus : &BusBuilder, message : &MessageWithLoad) {
+        let bus = self.position(&fallback_bus.0.borrow().name);
+        self.buses[bus].load += message.load;
+    }
+    pub fn balancing_message(&self, message : &MessageWithLoad) -> BalancingMessage {
+        let message_data = message.message.0.borrow();
+        let names = |nodes: &Vec<NodeBuilder>| nodes.iter().map(|node| node.0.borrow().name.clone()).collect();
+        BalancingMessage {
+            name: message_data.name.clone(),
+            load: message.load,
+            transmitters: names(&message_data.transmitters),
+            receivers: names(&message_data.receivers),
+            fallback_bus: message
+                .message
+                .resolve_fallback_bus()
+                .map(|bus| self.position(&bus.0.borrow().name)),
+        }
     }
 }
 
@@ -119,7 +112,7 @@ struct MessageWithLoad {
 }
 
 impl MessageWithLoad {
-    pub fn from(types : &Vec<TypeRef>, msg: &MessageBuilder) -> Self {
+    pub fn from(types : &Vec<TypeRef>, msg: &MessageBuilder, frame_time_model: &FrameTimeModel, frame_padding: FramePadding) -> Self {
         let ide = match msg.0.borrow().id {
             crate::builder::message_builder::MessageIdTemplate::StdId(_) => false,
             crate::builder::message_builder::MessageIdTemplate::ExtId(_) => true,
@@ -129,15 +122,8 @@ impl MessageWithLoad {
                 panic!("unresolved id")
             }
         };
-        let dlc = match &msg.0.borrow().format {
-            crate::builder::MessageFormat::Signals(signal_format) => signal_format
-                .0
-                .borrow()
-                .0
-                .iter()
-                .map(|s| s.byte_offset() + s.size() as usize)
-                .max()
-                .unwrap(),
+        let payload_bits = match &msg.0.borrow().format {
+            crate::builder::MessageFormat::Signals(signal_format) => signal_format.0.borrow().bit_len(),
             crate::builder::MessageFormat::Types(type_format) => {
                 fn acc_dlc(ty: &Type) -> usize {
                     match ty {
@@ -147,6 +133,7 @@ impl MessageWithLoad {
                             description: _,
                             attribs,
                             visibility: _,
+                            annotations: _,
                         } => attribs.iter().map(|(_, ty)| acc_dlc(ty)).sum(),
                         crate::config::Type::Enum {
                             name: _,
@@ -154,34 +141,41 @@ impl MessageWithLoad {
                             size,
                             entries: _,
                             visibility: _,
+                            annotations: _,
                         } => *size as usize,
                         crate::config::Type::Array { len: _, ty: _ } => todo!(),
                     }
                 }
                 let mut dlc = 0usize;
-                for (attr_ty, _) in &type_format.0.borrow().0 {
-                    let ty = NetworkBuilder::resolve_type(types, attr_ty)
-                        .expect("failed to resolve type");
-                    dlc += acc_dlc(&ty);
+                let mut offset = 0usize;
+                for entry in &type_format.0.borrow().0 {
+                    match entry {
+                        MessageTypeFormatEntry::Type(attr_ty, _)
+                        | MessageTypeFormatEntry::PlacedType(attr_ty, _, _) => {
+                            if let MessageTypeFormatEntry::PlacedType(_, _, start_bit) = entry {
+                                offset = *start_bit;
+                            }
+                            let ty = NetworkBuilder::resolve_type(types, attr_ty)
+                                .expect("failed to resolve type");
+                            offset += acc_dlc(&ty);
+                        }
+                        MessageTypeFormatEntry::Reserved(bits) => offset += *bits as usize,
+                    }
+                    dlc = dlc.max(offset);
                 }
                 dlc
             }
             crate::builder::MessageFormat::Empty => 0,
         };
+        let payload_bits = msg.authenticated_bit_len(payload_bits);
 
-        let max_bitlen: usize;
-        if ide {
-            max_bitlen = 8 * dlc + 64 + (54 + 8 * dlc - 1) / 4;
-        } else {
-            max_bitlen = 8 * dlc + 44 + (34 + 8 * dlc - 1) / 4;
-        }
         let interval = match &msg.0.borrow().usage {
             crate::builder::message_builder::MessageBuilderUsage::Stream(stream_builder) => {
                 stream_builder.0.borrow().interval.1
             }
             crate::builder::message_builder::MessageBuilderUsage::CommandResp(command_builder)
             | crate::builder::message_builder::MessageBuilderUsage::CommandReq(command_builder) => {
-                command_builder.0.borrow().expected_interval
+                command_builder.frame_interval()
             }
             crate::builder::message_builder::MessageBuilderUsage::Configuration => {
                 Duration::from_millis(100)
@@ -193,7 +187,7 @@ impl MessageWithLoad {
                 interval.unwrap_or(Duration::from_millis(50))
             }
         };
-        let load = (max_bitlen as f64 / interval.as_millis() as f64) * 1e3f64;
+        let load = message_load(frame_time_model, frame_padding, ide, payload_bits, interval);
         Self {
             message : msg.clone(),
             load,
@@ -243,18 +237,50 @@ pub fn balance_buses(
     messages: &Vec<MessageBuilder>,
     types: &Vec<TypeRef>,
     buses: &Vec<BusBuilder>,
-) {
-    let mut buses = Buses::from(buses);
-    let messages : Vec<MessageWithLoad> = messages.iter().map(|msg| MessageWithLoad::from(types, msg)).collect();
+    nodes: &Vec<NodeBuilder>,
+    frame_time_model: &FrameTimeModel,
+    frame_padding: FramePadding,
+    balancer: &dyn BusBalancer,
+) -> errors::Result<()> {
+    let mut buses = Buses::from(buses, nodes);
+    let messages : Vec<MessageWithLoad> = messages.iter().map(|msg| MessageWithLoad::from(types, msg, frame_time_model, frame_padding)).collect();
     let message_split = MessageBusSplit::from(&messages);
     
     for msg in message_split.assigned {
         buses.add_message(msg);
     }
-    let mut unassigned = message_split.unassigned.clone();
-    unassigned.sort();
-    for msg in unassigned {
-        buses.add_message_to_min_load(msg);
+    for msg in &messages {
+        if let Some(fallback_bus) = msg.message.resolve_fallback_bus() {
+            buses.add_fallback_load(&fallback_bus, msg);
+        }
     }
-
+    let unassigned : Vec<BalancingMessage> = message_split
+        .unassigned
+        .iter()
+        .map(|msg| buses.balancing_message(msg))
+        .collect();
+    let assignment = balancer.balance(&buses.buses, &unassigned)?;
+    if assignment.len() != unassigned.len() {
+        return Err(errors::ConfigError::InvalidBusBalancing(format!(
+            "{balancer:?} placed {} of {} messages",
+            assignment.len(),
+            unassigned.len()
+        )));
+    }
+    for ((msg, balancing_msg), bus) in message_split.unassigned.iter().zip(&unassigned).zip(assignment) {
+        let Some(bus_info) = buses.buses.get(bus) else {
+            return Err(errors::ConfigError::InvalidBusBalancing(format!(
+                "{balancer:?} placed message {} on the bus {bus}, which doesn't exist",
+                balancing_msg.name
+            )));
+        };
+        if balancing_msg.fallback_bus == Some(bus) {
+            return Err(errors::ConfigError::InvalidBusBalancing(format!(
+                "{balancer:?} placed message {} on its fallback bus {}",
+                balancing_msg.name, bus_info.name
+            )));
+        }
+        msg.message.place_on_bus(&bus_info.name);
+    }
+    Ok(())
 }
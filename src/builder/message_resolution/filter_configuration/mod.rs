@@ -1,53 +1,219 @@
-use crate::builder::NodeBuilder;
+use crate::{
+    builder::{message_builder::MessageIdTemplate, MessageBuilder, NodeBuilder},
+    config::{Filter, MessageRef, Network, Node, NodeFilters, NodeRef},
+};
 
 use super::assign_messages::NodeFilterInfo;
 
 pub struct NodeFilterBank {
     filters: Vec<Filter>,
+    software_filters: Vec<Filter>,
     node: NodeBuilder,
 }
 
 impl NodeFilterBank {
+    pub(crate) fn new(node: NodeBuilder, filters: Vec<Filter>, software_filters: Vec<Filter>) -> Self {
+        Self {
+            filters,
+            software_filters,
+            node,
+        }
+    }
     pub fn node(&self) -> &NodeBuilder {
         &self.node
     }
     pub fn filters(&self) -> &Vec<Filter> {
         &self.filters
     }
+    /// exact filters of the messages that pass the hardware filters,
+    /// but are not received by the node and have to be dropped in software.
+    pub fn software_filters(&self) -> &Vec<Filter> {
+        &self.software_filters
+    }
 }
 
-pub struct Filter {
-    mask: u32,
-    id: u32,
+/// a setcode of length 0 accepts all ids.
+fn setcode_mask(setcode_len: u32) -> u32 {
+    0xFFFFFFFFu32.checked_shr(32 - setcode_len).unwrap_or(0)
 }
-impl Filter {
-    pub fn mask(&self) -> u32 {
-        self.mask
-    }
-    pub fn id(&self) -> u32 {
-        self.id
+
+fn assigned_id(message: &MessageBuilder) -> (u32, bool) {
+    match message.0.borrow().id {
+        MessageIdTemplate::StdId(id) => (id, false),
+        MessageIdTemplate::ExtId(id) => (id, true),
+        MessageIdTemplate::AnyStd(_)
+        | MessageIdTemplate::AnyExt(_)
+        | MessageIdTemplate::AnyAny(_) => panic!("unresolved id"),
     }
 }
 
-pub fn find_filter_configuration(filter_infos: Vec<NodeFilterInfo>) -> Vec<NodeFilterBank> {
+/// ids of the remote frames (RTR), which the node answers.
+fn remote_requests(node: &NodeBuilder, messages: &[MessageBuilder]) -> Vec<(u32, bool)> {
+    let node_name = node.0.borrow().name.clone();
+    messages
+        .iter()
+        .filter(|message| {
+            let message_data = message.0.borrow();
+            message_data.remote_requestable
+                && message_data.transmitters.iter().any(|tx| tx.0.borrow().name == node_name)
+        })
+        .map(assigned_id)
+        .collect()
+}
+
+/// messages on the buses of the node, which are accepted by the
+/// hardware filters, but not received by the node.
+fn software_filters(node: &NodeBuilder, filters: &[Filter], messages: &[MessageBuilder]) -> Vec<Filter> {
+    let node_data = node.0.borrow();
+    let bus_names: Vec<String> = node_data
+        .buses
+        .iter()
+        .chain(node_data.fallback_bus.iter())
+        .map(|bus| bus.0.borrow().name.clone())
+        .collect();
+    let remote_requests = remote_requests(node, messages);
+    messages
+        .iter()
+        .filter(|message| {
+            let message_data = message.0.borrow();
+            // nodes without explicit buses are connected to all buses.
+            let on_node_bus = bus_names.is_empty()
+                || message_data
+                    .bus
+                    .iter()
+                    .chain(message_data.fallback_bus.iter())
+                    .any(|bus| bus_names.contains(&bus.0.borrow().name));
+            on_node_bus
+                && !message_data
+                    .receivers
+                    .iter()
+                    .any(|rx| rx.0.borrow().name == node_data.name)
+        })
+        .map(assigned_id)
+        .filter(|id| !remote_requests.contains(id))
+        .filter(|(id, ide)| filters.iter().any(|filter| filter.accepts(*id, *ide)))
+        .map(|(id, ide)| Filter::exact(id, ide))
+        .collect()
+}
+
+pub fn find_filter_configuration(
+    filter_infos: Vec<NodeFilterInfo>,
+    messages: &[MessageBuilder],
+) -> Vec<NodeFilterBank> {
     filter_infos
         .iter()
-        .map(|node_filter_info| NodeFilterBank {
-            filters: node_filter_info.filter_infos().iter().map(|filter| -> Filter{
+        .map(|node_filter_info| {
+            let mut filters: Vec<Filter> = node_filter_info.filter_infos().iter().map(|filter| -> Filter{
                 match filter {
-                    super::assign_messages::FilterInfo::Setcode { setcode, setcode_len, ide : _ } => Filter {
-                        mask : 0xFFFFFFFFu32.overflowing_shr(32 - *setcode_len).0,
-                        id : *setcode
-                    },
-                    super::assign_messages::FilterInfo::Single { id, ide : _ } => {
-                        Filter {
-                            mask : 0xFFFFFFFFu32,
-                            id : *id,
-                        }
+                    super::assign_messages::FilterInfo::Setcode { setcode, setcode_len, ide } => {
+                        Filter::new(*setcode, setcode_mask(*setcode_len), *ide)
                     }
+                    super::assign_messages::FilterInfo::Single { id, ide } => {
+                        Filter::exact(*id, *ide)
+                    }
+                }
+            }).collect();
+            // the transmitter of a remote requestable message has to accept its remote frames.
+            for (id, ide) in remote_requests(node_filter_info.node(), messages) {
+                if !filters.iter().any(|filter| filter.accepts(id, ide)) {
+                    filters.push(Filter::exact(id, ide));
                 }
-            }).collect(),
-            node: node_filter_info.node().clone(),
+            }
+            NodeFilterBank {
+                software_filters: software_filters(node_filter_info.node(), &filters, messages),
+                filters,
+                node: node_filter_info.node().clone(),
+            }
+        })
+        .collect()
+}
+
+/// merges filters that only differ in a single bit of their id,
+/// which doesn't accept any additional ids.
+fn merge_filters(mut filters: Vec<Filter>) -> Vec<Filter> {
+    loop {
+        let mergeable = (0..filters.len()).find_map(|i| {
+            (i + 1..filters.len()).find_map(|j| {
+                let (a, b) = (filters[i], filters[j]);
+                let diff = a.id() ^ b.id();
+                (a.ide() == b.ide() && a.mask() == b.mask() && diff.count_ones() == 1)
+                    .then_some((i, j, Filter::new(a.id(), a.mask() & !diff, a.ide())))
+            })
+        });
+        match mergeable {
+            Some((i, j, merged)) => {
+                filters.remove(j);
+                filters[i] = merged;
+            }
+            None => return filters,
+        }
+    }
+}
+
+pub(crate) fn on_node_buses(node: &NodeRef, message: &MessageRef) -> bool {
+    let bus_ids: Vec<u32> = node
+        .buses()
+        .iter()
+        .chain(node.fallback_bus())
+        .map(|bus| bus.id())
+        .collect();
+    // nodes without explicit buses are connected to all buses.
+    bus_ids.is_empty()
+        || std::iter::once(message.bus())
+            .chain(message.fallback_bus())
+            .any(|bus| bus_ids.contains(&bus.id()))
+}
+
+/// hardware filters of the node, derived from the assigned ids of its rx messages.
+pub(crate) fn hardware_filters(node: &Node) -> Vec<Filter> {
+    if node.receive_all() {
+        return vec![Filter::new(0, 0, false), Filter::new(0, 0, true)];
+    }
+    let mut filters: Vec<Filter> = vec![];
+    for message in node.rx_messages() {
+        let info = message.resolution_info();
+        let filter = match info.setcode() {
+            Some(setcode) => Filter::new(setcode, setcode_mask(info.setcode_len()), message.id().ide()),
+            None => Filter::exact(message.id().as_u32(), message.id().ide()),
+        };
+        if !filters.iter().any(|f| f.accepts_id(message.id())) {
+            filters.push(filter);
+        }
+    }
+    for message in node.tx_messages() {
+        if message.remote_request().is_some() && !filters.iter().any(|f| f.accepts_id(message.id())) {
+            filters.push(Filter::exact(message.id().as_u32(), message.id().ide()));
+        }
+    }
+    merge_filters(filters)
+}
+
+/// recomputes the filters of all nodes from the already assigned ids,
+/// without resolving ids or balancing buses again.
+/// Messages that were placed by the resolver are filtered by their setcode,
+/// other messages (e.g. fixed ids imported from a dbc) by their exact id.
+pub fn resolve_filters_only(network: &Network) -> Vec<NodeFilters> {
+    network
+        .nodes()
+        .iter()
+        .map(|node| {
+            let filters = hardware_filters(node);
+            if node.receive_all() {
+                return NodeFilters::new(node.clone(), filters, vec![]);
+            }
+            let software_filters = network
+                .messages()
+                .iter()
+                .filter(|message| {
+                    on_node_buses(node, message)
+                        && !node.rx_messages().iter().any(|m| m.name() == message.name())
+                        && !(message.remote_request().is_some()
+                            && node.tx_messages().iter().any(|m| m.name() == message.name()))
+                        && filters.iter().any(|f| f.accepts_id(message.id()))
+                })
+                .cloned()
+                .collect();
+            NodeFilters::new(node.clone(), filters, software_filters)
         })
         .collect()
 }
@@ -1,10 +1,12 @@
 use std::time::Duration;
 
 use crate::{
-    builder::{MessageBuilder, NetworkBuilder},
-    config::{Type, TypeRef},
+    builder::{MessageBuilder, MessageTypeFormatEntry, NetworkBuilder},
+    config::{bus::FramePadding, FrameTimeModel, Type, TypeRef},
 };
 
+use super::message_load;
+
 
 #[allow(unused)]
 struct MessageInfo {
@@ -16,12 +18,21 @@ struct MessageInfo {
 pub struct LogInfo {
     messages: Vec<MessageInfo>,
     types: Vec<TypeRef>,
+    frame_time_model: FrameTimeModel,
+    frame_padding: FramePadding,
 }
 
 #[allow(unused)]
-pub fn cache_logging_info(types: &Vec<TypeRef>, messages: &Vec<MessageBuilder>) -> LogInfo {
+pub fn cache_logging_info(
+    types: &Vec<TypeRef>,
+    messages: &Vec<MessageBuilder>,
+    frame_time_model: &FrameTimeModel,
+    frame_padding: FramePadding,
+) -> LogInfo {
     LogInfo {
         types: types.clone(),
+        frame_time_model: *frame_time_model,
+        frame_padding,
         messages: messages
             .iter()
             .map(|msg| {
@@ -55,7 +66,12 @@ pub fn cache_logging_info(types: &Vec<TypeRef>, messages: &Vec<MessageBuilder>)
     }
 }
 
-fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
+fn load_estimation(
+    types: &Vec<TypeRef>,
+    msg: &MessageBuilder,
+    frame_time_model: &FrameTimeModel,
+    frame_padding: FramePadding,
+) -> f64 {
     let ide = match msg.0.borrow().id {
         crate::builder::message_builder::MessageIdTemplate::StdId(_) => false,
         crate::builder::message_builder::MessageIdTemplate::ExtId(_) => true,
@@ -63,15 +79,8 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
         | crate::builder::message_builder::MessageIdTemplate::AnyExt(_)
         | crate::builder::message_builder::MessageIdTemplate::AnyAny(_) => panic!("unresolved id"),
     };
-    let dlc = match &msg.0.borrow().format {
-        crate::builder::MessageFormat::Signals(signal_format) => signal_format
-            .0
-            .borrow()
-            .0
-            .iter()
-            .map(|s| s.byte_offset() + s.size() as usize)
-            .max()
-            .unwrap(),
+    let payload_bits = match &msg.0.borrow().format {
+        crate::builder::MessageFormat::Signals(signal_format) => signal_format.0.borrow().bit_len(),
         crate::builder::MessageFormat::Types(type_format) => {
             fn acc_dlc(ty: &Type) -> usize {
                 match ty {
@@ -81,6 +90,7 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
                         description: _,
                         attribs,
                         visibility: _,
+                        annotations: _,
                     } => attribs.iter().map(|(_, ty)| acc_dlc(ty)).sum(),
                     crate::config::Type::Enum {
                         name: _,
@@ -88,34 +98,41 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
                         size,
                         entries: _,
                         visibility: _,
+                        annotations: _,
                     } => *size as usize,
                     crate::config::Type::Array { len: _, ty: _ } => todo!(),
                 }
             }
             let mut dlc = 0usize;
-            for (attr_ty, _) in &type_format.0.borrow().0 {
-                let ty =
-                    NetworkBuilder::resolve_type(types, attr_ty).expect("failed to resolve type");
-                dlc += acc_dlc(&ty);
+            let mut offset = 0usize;
+            for entry in &type_format.0.borrow().0 {
+                match entry {
+                    MessageTypeFormatEntry::Type(attr_ty, _)
+                    | MessageTypeFormatEntry::PlacedType(attr_ty, _, _) => {
+                        if let MessageTypeFormatEntry::PlacedType(_, _, start_bit) = entry {
+                            offset = *start_bit;
+                        }
+                        let ty = NetworkBuilder::resolve_type(types, attr_ty)
+                            .expect("failed to resolve type");
+                        offset += acc_dlc(&ty);
+                    }
+                    MessageTypeFormatEntry::Reserved(bits) => offset += *bits as usize,
+                }
+                dlc = dlc.max(offset);
             }
             dlc
         }
         crate::builder::MessageFormat::Empty => 0,
     };
+    let payload_bits = msg.authenticated_bit_len(payload_bits);
 
-    let max_bitlen: usize;
-    if ide {
-        max_bitlen = 8 * dlc + 64 + (54 + 8 * dlc - 1) / 4;
-    } else {
-        max_bitlen = 8 * dlc + 44 + (34 + 8 * dlc - 1) / 4;
-    }
     let interval = match &msg.0.borrow().usage {
         crate::builder::message_builder::MessageBuilderUsage::Stream(stream_builder) => {
             stream_builder.0.borrow().interval.1
         }
         crate::builder::message_builder::MessageBuilderUsage::CommandResp(command_builder)
         | crate::builder::message_builder::MessageBuilderUsage::CommandReq(command_builder) => {
-            command_builder.0.borrow().expected_interval
+            command_builder.frame_interval()
         }
         crate::builder::message_builder::MessageBuilderUsage::Configuration => {
             Duration::from_millis(100)
@@ -127,7 +144,7 @@ fn load_estimation(types: &Vec<TypeRef>, msg: &MessageBuilder) -> f64 {
             interval.unwrap_or(Duration::from_millis(50))
         }
     };
-    (max_bitlen as f64 / interval.as_millis() as f64) * 1e3f64
+    message_load(frame_time_model, frame_padding, ide, payload_bits, interval)
 }
 
 #[allow(unused)]
@@ -173,7 +190,12 @@ pub fn log_info(log_info: LogInfo) {
         });
         let mut bus_load = 0f64;
         for msg in messages {
-            bus_load += load_estimation(&log_info.types, &msg.message);
+            bus_load += load_estimation(
+                &log_info.types,
+                &msg.message,
+                &log_info.frame_time_model,
+                log_info.frame_padding,
+            );
 
             let prev_prio = &msg.assigned_priority;
             // let prev_bus = &msg.assigned_bus;
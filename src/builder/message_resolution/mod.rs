@@ -1,8 +1,14 @@
-use crate::{config::TypeRef, errors};
+use std::{hash::Hasher, ops::Range, time::Duration};
 
-use self::{filter_configuration::NodeFilterBank, fixed_messages::MessageSplit};
+use crate::{config::{bus::FramePadding, FrameTimeModel, TypeRef}, errors};
 
-use super::{bus::BusBuilder, MessageBuilder, NodeBuilder};
+pub use self::filter_configuration::resolve_filters_only;
+pub(crate) use self::filter_configuration::{hardware_filters, on_node_buses};
+pub(crate) use self::filter_configuration::NodeFilterBank;
+use self::fixed_messages::{FixedMessage, MessageSplit};
+
+pub(crate) use self::receive_set::ReceiverSetLayout;
+use super::{build_cache::BuildCache, bus::BusBuilder, bus_balancing::BusBalancer, BuilderRef, message_builder::MessageIdTemplate, MessageBuilder, MessagePriority, NodeBuilder};
 
 mod assign_messages;
 mod bus_balancing;
@@ -12,12 +18,58 @@ mod logging;
 mod receive_set;
 mod set_minimization;
 
+/// expected load (bits per second) of a message with payload_bits bits of
+/// payload, that is sent every interval. All stages of the resolver estimate
+/// with the frame time model and the padding of the network.
+pub(crate) fn message_load(
+    frame_time_model: &FrameTimeModel,
+    frame_padding: FramePadding,
+    ide: bool,
+    payload_bits: usize,
+    interval: Duration,
+) -> f64 {
+    frame_time_model.load(ide, frame_padding.transmitted_len(payload_bits.div_ceil(8)), interval)
+}
+
+/// std ids that the resolver can't assign, because they are part of a
+/// reserved range or fixed by a message.
+fn carved_out_std_ids(
+    reserved_id_ranges: &[(Range<u32>, bool)],
+    fixed_messages: &[FixedMessage],
+) -> Vec<u32> {
+    let std_ids = 0..(1u32 << 11);
+    std_ids
+        .filter(|id| {
+            reserved_id_ranges
+                .iter()
+                .any(|(range, ide)| !*ide && range.contains(id))
+                || fixed_messages.iter().any(|fixed| !fixed.ide() && fixed.id() == *id)
+        })
+        .collect()
+}
+
+/// the priority regions of the id space chosen by the resolver,
+/// id = priority << setcode_len | setcode.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IdLayout {
+    pub setcode_len: u32,
+    /// number of priorities of each bucket (MessagePriority), in order.
+    pub bucket_sizes: Vec<usize>,
+}
+
 pub fn resolve_ids_filters_and_buses(
     buses: &Vec<BusBuilder>,
     messages: &Vec<MessageBuilder>,
     nodes: &Vec<NodeBuilder>,
     types: &Vec<TypeRef>,
-) -> errors::Result<Vec<NodeFilterBank>> {
+    reserved_id_ranges: &[(Range<u32>, bool)],
+    receiver_set_folding: Option<usize>,
+    resolution_seed: Option<u64>,
+    frame_time_model: &FrameTimeModel,
+    frame_padding: FramePadding,
+    bus_balancer: &dyn BusBalancer,
+    build_cache: Option<&BuilderRef<BuildCache>>,
+) -> errors::Result<(Vec<NodeFilterBank>, IdLayout)> {
     let mut messages = messages.clone();
     let mut nodes = nodes.clone();
     let mut buses = buses.clone();
@@ -27,27 +79,61 @@ pub fn resolve_ids_filters_and_buses(
     nodes.sort_by_key(|k| k.0.borrow().name.clone());
     buses.sort_by_key(|k| k.0.borrow().name.clone());
     types.sort_by_key(|t| t.name());
+    // all later stages break ties by the order of the messages. The sort is
+    // stable, therefore hash collisions still fall back to the name order.
+    if let Some(seed) = resolution_seed {
+        messages.sort_by_cached_key(|k| {
+            let mut hasher = seahash::SeaHasher::with_seeds(seed, seed, seed, seed);
+            hasher.write(k.0.borrow().name.as_bytes());
+            hasher.finish()
+        });
+    }
 
+    // messages that never requested an id only carry a priority,
+    // the resolver stages only handle std and ext requests.
+    for message in &messages {
+        let mut message_data = message.0.borrow_mut();
+        if let MessageIdTemplate::AnyAny(priority) = message_data.id {
+            message_data.id = MessageIdTemplate::AnyStd(priority);
+        }
+    }
 
     #[cfg(feature = "logging_idrp")]
-    let logging_info = logging::cache_logging_info(&types, &messages);
+    let logging_info = logging::cache_logging_info(&types, &messages, frame_time_model, frame_padding);
 
     let message_split = MessageSplit::from(&messages);
     let network_info =
-        receive_set::generate_receive_sets_from_messages(&nodes, message_split.prio_messages());
-    let minimized_network = set_minimization::minimize_sets(network_info);
+        receive_set::generate_receive_sets_from_messages(
+            &nodes,
+            message_split.prio_messages(),
+            receiver_set_folding,
+            build_cache,
+        );
+    let carved_out_ids = carved_out_std_ids(reserved_id_ranges, message_split.fixed_messages());
+    let minimized_network = set_minimization::minimize_sets(network_info, &carved_out_ids)?;
+    let id_layout = IdLayout {
+        setcode_len: (minimized_network.sets().len() as f64).log2().ceil() as u32,
+        bucket_sizes: (0..MessagePriority::count())
+            .map(|prio| minimized_network.bucket_layout().bucket_size(prio))
+            .collect(),
+    };
     let filter_infos = assign_messages::assign_messages_ids(
         message_split.fixed_messages(),
         minimized_network,
         &nodes,
-    );
-    bus_balancing::balance_buses(&messages, &types, &buses);
-    let filter_banks = filter_configuration::find_filter_configuration(filter_infos);
+        reserved_id_ranges,
+        &carved_out_ids,
+    )?;
+    // NOTE ids are unique across all buses (a message keeps its id on the fallback bus),
+    // therefore buses are only balanced after the assignment and there are no
+    // independent per bus stages that could be resolved in parallel.
+    bus_balancing::balance_buses(&messages, &types, &buses, &nodes, frame_time_model, frame_padding, bus_balancer)?;
+    let filter_banks = filter_configuration::find_filter_configuration(filter_infos, &messages);
 
     #[cfg(feature = "logging_idrp")]
     logging::log_info(logging_info);
 
-    Ok(filter_banks)
+    Ok((filter_banks, id_layout))
 }
 
 //
@@ -92,7 +178,7 @@ pub fn resolve_ids_filters_and_buses(
 //             let name = name_gen.next();
 //             let mut hasher = DefaultHasher::new();
 //             name.hash(&mut hasher);
-//             let x = network_builder.create_message(&name, None);
+//             let x = network_builder.create_message(&name);
 //             let priority = MessagePriority::from_u32(
 //                 (hasher.finish() % MessagePriority::count() as u64) as u32,
 //             );
@@ -159,19 +245,19 @@ pub fn resolve_ids_filters_and_buses(
 //         }
 //
 //
-//         // let fixed = network_builder.create_message("fixed_secu1", None);
+//         // let fixed = network_builder.create_message("fixed_secu1");
 //         // fixed.set_std_id(0xFF);
 //         //
-//         // let fixed = network_builder.create_message("fixed_master", None);
+//         // let fixed = network_builder.create_message("fixed_master");
 //         // fixed.set_std_id(0xFA);
 //         //
-//         // let fixed = network_builder.create_message("fixed_clu", None);
+//         // let fixed = network_builder.create_message("fixed_clu");
 //         // fixed.set_std_id(0xFB);
 //         //
-//         // let fixed = network_builder.create_message("fixed_secu2", None);
+//         // let fixed = network_builder.create_message("fixed_secu2");
 //         // fixed.set_std_id(0xFE);
 //         //
-//         // let fixed = network_builder.create_message("fixed_secu3", None);
+//         // let fixed = network_builder.create_message("fixed_secu3");
 //         // fixed.set_ext_id(0xFD);
 //
 //         network_builder.build().unwrap();
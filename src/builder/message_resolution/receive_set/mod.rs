@@ -2,8 +2,12 @@ use std::rc::Rc;
 use std::time::Duration;
 
 use crate::builder::message_resolution::set_minimization::MinimizedSet;
-use crate::builder::{MessageBuilder, MessagePriority, NetworkBuilder, NodeBuilder};
-use crate::config::{Type, TypeRef};
+use crate::builder::build_cache::BuildCache;
+use crate::builder::{
+    BuilderRef, MessageBuilder, MessagePriority, MessageTypeFormatEntry, NetworkBuilder, NodeBuilder,
+};
+use crate::builder::message_resolution::message_load;
+use crate::config::{bus::FramePadding, FrameTimeModel, Type, TypeRef};
 
 use self::node_receive_set::NodeReceiveSet;
 
@@ -62,6 +66,15 @@ impl ReceiverSet {
             }
         }
     }
+    pub fn message_count(&self) -> usize {
+        self.priority_buckets.iter().map(|b| b.message_count()).sum()
+    }
+    /// moves all messages of other into this set, keeping their priorities.
+    pub fn fold(&mut self, other: ReceiverSet) {
+        for (bucket, other_bucket) in self.priority_buckets.iter_mut().zip(other.priority_buckets) {
+            bucket.append(other_bucket);
+        }
+    }
     pub fn set_count(&self, bucket_layout: &BucketLayout) -> usize {
         self.priority_buckets
             .iter()
@@ -124,20 +137,18 @@ impl ReceiverSet {
         minimized_sets
     }
 
-    pub fn bus_load(&self, types: &Vec<TypeRef>) -> f64 {
+    pub fn bus_load(
+        &self,
+        types: &Vec<TypeRef>,
+        frame_time_model: &FrameTimeModel,
+        frame_padding: FramePadding,
+    ) -> f64 {
         let mut bus_load = 0.0f64;
         for priority in 0..MessagePriority::count() {
             for message in self.priority_buckets[priority].messages() {
                 let message_data = message.0.borrow();
-                let dlc = match &message_data.format {
-                    crate::builder::MessageFormat::Signals(signal_format) => signal_format
-                        .0
-                        .borrow()
-                        .0
-                        .iter()
-                        .map(|s| s.byte_offset() + s.size() as usize)
-                        .max()
-                        .unwrap(),
+                let payload_bits = match &message_data.format {
+                    crate::builder::MessageFormat::Signals(signal_format) => signal_format.0.borrow().bit_len(),
                     crate::builder::MessageFormat::Types(type_format) => {
                         fn acc_dlc(ty: &Type) -> usize {
                             match ty {
@@ -149,6 +160,7 @@ impl ReceiverSet {
                                     description: _,
                                     attribs,
                                     visibility: _,
+                                    annotations: _,
                                 } => attribs.iter().map(|(_, ty)| acc_dlc(ty)).sum(),
                                 crate::config::Type::Enum {
                                     name: _,
@@ -156,26 +168,33 @@ impl ReceiverSet {
                                     size,
                                     entries: _,
                                     visibility: _,
+                                    annotations: _,
                                 } => *size as usize,
                                 crate::config::Type::Array { len: _, ty: _ } => todo!(),
                             }
                         }
                         let mut dlc = 0usize;
-                        for (attr_ty, _) in &type_format.0.borrow().0 {
-                            let ty = NetworkBuilder::resolve_type(types, attr_ty)
-                                .expect("failed to resolve type");
-                            dlc += acc_dlc(&ty);
+                        let mut offset = 0usize;
+                        for entry in &type_format.0.borrow().0 {
+                            match entry {
+                                MessageTypeFormatEntry::Type(attr_ty, _)
+                                | MessageTypeFormatEntry::PlacedType(attr_ty, _, _) => {
+                                    if let MessageTypeFormatEntry::PlacedType(_, _, start_bit) = entry {
+                                        offset = *start_bit;
+                                    }
+                                    let ty = NetworkBuilder::resolve_type(types, attr_ty)
+                                        .expect("failed to resolve type");
+                                    offset += acc_dlc(&ty);
+                                }
+                                MessageTypeFormatEntry::Reserved(bits) => offset += *bits as usize,
+                            }
+                            dlc = dlc.max(offset);
                         }
                         dlc
                     }
                     crate::builder::MessageFormat::Empty => 0usize,
                 };
-                let max_bitlen: usize;
-                if self.identifier().ide() {
-                    max_bitlen = 8 * dlc + 64 + (54 + 8 * dlc - 1) / 4;
-                } else {
-                    max_bitlen = 8 * dlc + 44 + (34 + 8 * dlc - 1) / 4;
-                }
+                let payload_bits = message.authenticated_bit_len(payload_bits);
                 let interval = match &message.0.borrow().usage {
                     crate::builder::message_builder::MessageBuilderUsage::Stream(
                         stream_builder,
@@ -185,7 +204,7 @@ impl ReceiverSet {
                     )
                     | crate::builder::message_builder::MessageBuilderUsage::CommandReq(
                         command_builder,
-                    ) => command_builder.0.borrow().expected_interval,
+                    ) => command_builder.frame_interval(),
                     crate::builder::message_builder::MessageBuilderUsage::Configuration => {
                         Duration::from_millis(100)
                     }
@@ -196,14 +215,173 @@ impl ReceiverSet {
                         interval.unwrap_or(Duration::from_millis(50))
                     }
                 };
-                bus_load += max_bitlen as f64 / interval.as_secs() as f64;
+                bus_load += message_load(
+                    frame_time_model,
+                    frame_padding,
+                    self.identifier().ide(),
+                    payload_bits,
+                    interval,
+                );
             }
         }
         bus_load
     }
 }
 
-pub fn generate_receive_sets_from_messages(nodes : &Vec<NodeBuilder>, messages: &Vec<MessageBuilder>) -> NetworkInfo {
+/// number of frames that are received without being required if the
+/// messages of subset are also sent to the receivers of superset.
+/// None if subset can't be folded into superset.
+fn folding_cost(subset: &ReceiverSet, superset: &ReceiverSet) -> Option<usize> {
+    let sub_id = subset.identifier();
+    let super_id = superset.identifier();
+    if sub_id.bus() != super_id.bus()
+        || sub_id.ide() != super_id.ide()
+        || sub_id.receivers().len() >= super_id.receivers().len()
+    {
+        return None;
+    }
+    let is_subset = sub_id.receivers().iter().all(|rx| {
+        super_id
+            .receivers()
+            .iter()
+            .any(|super_rx| super_rx.0.borrow().name == rx.0.borrow().name)
+    });
+    if !is_subset {
+        return None;
+    }
+    let extra_receivers = super_id.receivers().len() - sub_id.receivers().len();
+    Some(extra_receivers * subset.message_count())
+}
+
+/// folds receiver sets into a superset if the cost (see folding_cost) is
+/// at most max_cost. Less receiver sets result in shorter setcodes,
+/// the extra receivers drop the folded messages in software.
+fn fold_receiver_sets(receiver_sets: Vec<ReceiverSet>, max_cost: usize) -> Vec<ReceiverSet> {
+    let mut receiver_sets: Vec<Option<ReceiverSet>> = receiver_sets.into_iter().map(Some).collect();
+    // larger sets first, such that a set is never folded into
+    // a set that is folded itself later on.
+    let mut order: Vec<usize> = (0..receiver_sets.len()).collect();
+    order.sort_by_key(|i| {
+        std::cmp::Reverse(receiver_sets[*i].as_ref().unwrap().identifier().receivers().len())
+    });
+    for i in order {
+        let subset = receiver_sets[i].as_ref().unwrap();
+        let target = receiver_sets
+            .iter()
+            .enumerate()
+            .filter_map(|(j, superset)| {
+                let cost = folding_cost(subset, superset.as_ref()?)?;
+                (cost <= max_cost).then_some((j, cost))
+            })
+            .min_by_key(|(_, cost)| *cost)
+            .map(|(j, _)| j);
+        if let Some(j) = target {
+            let subset = receiver_sets[i].take().unwrap();
+            receiver_sets[j].as_mut().unwrap().fold(subset);
+        }
+    }
+    receiver_sets.into_iter().flatten().collect()
+}
+
+/// receiver set by names, see BuildCache.
+#[derive(Debug, Clone)]
+pub struct ReceiverSetLayout {
+    receivers: Vec<String>,
+    bus: Option<u32>,
+    ide: bool,
+    // in the order of the priority buckets.
+    messages: Vec<String>,
+}
+
+impl ReceiverSetLayout {
+    fn from_receiver_set(receiver_set: &ReceiverSet) -> Self {
+        Self {
+            receivers: receiver_set
+                .identifier()
+                .receivers()
+                .iter()
+                .map(|rx| rx.0.borrow().name.clone())
+                .collect(),
+            bus: *receiver_set.identifier().bus(),
+            ide: receiver_set.identifier().ide(),
+            messages: receiver_set
+                .priority_buckets
+                .iter()
+                .flat_map(|bucket| bucket.messages())
+                .map(|message| message.0.borrow().name.clone())
+                .collect(),
+        }
+    }
+    /// None if a node or message of the layout doesn't exist anymore.
+    fn to_receiver_set(&self, nodes: &[NodeBuilder], messages: &[MessageBuilder]) -> Option<ReceiverSet> {
+        let find_node = |name: &String| nodes.iter().find(|node| &node.0.borrow().name == name).cloned();
+        let receivers = self.receivers.iter().map(find_node).collect::<Option<Vec<_>>>()?;
+        let mut receiver_set = ReceiverSet::new(SetIdentifier::new(&receivers, self.bus, self.ide));
+        for name in &self.messages {
+            let message = messages.iter().find(|message| &message.0.borrow().name == name)?;
+            receiver_set.insert_message(message);
+        }
+        Some(receiver_set)
+    }
+}
+
+/// identifies everything receive set construction depends on.
+fn receive_set_fingerprint(messages: &[MessageBuilder], folding_max_cost: Option<usize>) -> String {
+    let mut fingerprint = format!("{folding_max_cost:?}\n");
+    for message in messages {
+        let message_data = message.0.borrow();
+        let mut receivers: Vec<String> = message_data
+            .receivers
+            .iter()
+            .map(|rx| rx.0.borrow().name.clone())
+            .collect();
+        receivers.sort();
+        let bus = message_data.bus.as_ref().map(|bus| bus.0.borrow().id);
+        fingerprint.push_str(&format!(
+            "{}:{receivers:?}:{bus:?}:{:?}\n",
+            message_data.name, message_data.id
+        ));
+    }
+    fingerprint
+}
+
+pub fn generate_receive_sets_from_messages(
+    nodes: &Vec<NodeBuilder>,
+    messages: &Vec<MessageBuilder>,
+    folding_max_cost: Option<usize>,
+    build_cache: Option<&BuilderRef<BuildCache>>,
+) -> NetworkInfo {
+    let receiver_sets = match build_cache {
+        Some(build_cache) => {
+            let fingerprint = receive_set_fingerprint(messages, folding_max_cost);
+            let cached = build_cache
+                .borrow()
+                .receive_sets(&fingerprint)
+                .and_then(|layouts| {
+                    layouts
+                        .iter()
+                        .map(|layout| layout.to_receiver_set(nodes, messages))
+                        .collect::<Option<Vec<_>>>()
+                });
+            build_cache.borrow_mut().count_receive_sets(cached.is_some());
+            match cached {
+                Some(receiver_sets) => receiver_sets,
+                None => {
+                    let receiver_sets = construct_receiver_sets(messages, folding_max_cost);
+                    build_cache.borrow_mut().store_receive_sets(
+                        fingerprint,
+                        receiver_sets.iter().map(ReceiverSetLayout::from_receiver_set).collect(),
+                    );
+                    receiver_sets
+                }
+            }
+        }
+        None => construct_receiver_sets(messages, folding_max_cost),
+    };
+    network_info(nodes, receiver_sets)
+}
+
+fn construct_receiver_sets(messages: &Vec<MessageBuilder>, folding_max_cost: Option<usize>) -> Vec<ReceiverSet> {
     let mut receiver_sets: Vec<ReceiverSet> = vec![];
     let mut rx_nodes: Vec<NodeBuilder> = vec![];
     for message in messages {
@@ -237,6 +415,13 @@ pub fn generate_receive_sets_from_messages(nodes : &Vec<NodeBuilder>, messages:
         }
     }
 
+    match folding_max_cost {
+        Some(max_cost) => fold_receiver_sets(receiver_sets, max_cost),
+        None => receiver_sets,
+    }
+}
+
+fn network_info(nodes: &Vec<NodeBuilder>, receiver_sets: Vec<ReceiverSet>) -> NetworkInfo {
     let receiver_sets: Vec<Rc<ReceiverSet>> = receiver_sets
         .into_iter()
         .map(|rx_set| Rc::new(rx_set))
@@ -5,13 +5,15 @@ use crate::builder::{
 };
 
 use super::receive_set::NetworkInfo;
+use crate::errors::{CapacityError, ConfigError, Result};
 
 pub mod bucket_layout;
 pub mod priority_bucket;
 pub mod set_identifier;
 
 const MAX_FILTERS_PER_NODE: usize = 8;
-const STD_ID_LENGTH: u32 = 11;
+pub const STD_ID_LENGTH: u32 = 11;
+pub const EXT_ID_LENGTH: u32 = 29;
 
 const LOGGING: bool = false;
 
@@ -61,11 +63,34 @@ impl MinimizedNetwork {
     }
 }
 
+/// true if enough setcodes leave room for a full bucket layout next to the
+/// carved out ids, which share the setcode of their low bits.
+fn fits_next_to_carved_out_ids(
+    set_count: usize,
+    setcode_len: u32,
+    bucket_layout: &BucketLayout,
+    carved_out_ids: &[u32],
+) -> bool {
+    let setcode_mask = (1u32 << setcode_len) - 1;
+    let ids_per_setcode = 1usize << (STD_ID_LENGTH - setcode_len);
+    let mut carved_out_per_setcode = vec![0usize; 1 << setcode_len];
+    for id in carved_out_ids {
+        carved_out_per_setcode[(id & setcode_mask) as usize] += 1;
+    }
+    carved_out_per_setcode
+        .iter()
+        .filter(|carved_out| ids_per_setcode - **carved_out >= bucket_layout.total_bucket_size())
+        .count()
+        >= set_count
+}
+
 /**
  * messages is not allowed to contain messages with fixed id assignments!
+ * carved_out_ids are std ids that can't be assigned (reserved ranges and fixed ids),
+ * the sets are minimized until they fit next to them.
  */
 
-pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
+pub fn minimize_sets(network_info: NetworkInfo, carved_out_ids: &[u32]) -> Result<MinimizedNetwork> {
     if network_info.node_receive_sets().is_empty() {
         assert_eq!(network_info.receive_sets().len(), 1, "It is assumed that if no receiver exists in 
                    the network that all messages are defined by the config itself are are std and non fixed");
@@ -78,15 +103,19 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
             };
             bucket_layout.apply_commit(commit);
         }
-        assert!(
-            bucket_layout.prio_bit_size() + 1 < STD_ID_LENGTH,
-            "Failed to find message id configuration"
-        );
+        if bucket_layout.prio_bit_size() + 1 >= STD_ID_LENGTH {
+            return Err(ConfigError::Capacity(CapacityError::IdSpace {
+                needed_setcodes: 1,
+                available_setcodes: 0,
+                priority_bits: bucket_layout.prio_bit_size(),
+                id_bits: STD_ID_LENGTH,
+            }));
+        }
         let minimized_sets = set.to_minimized_sets(&bucket_layout);
-        return MinimizedNetwork {
+        return Ok(MinimizedNetwork {
             sets: minimized_sets,
             bucket_layout,
-        };
+        });
     }
     if LOGGING {
         println!("receive set count: {}", network_info.receive_sets().len());
@@ -214,7 +243,9 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
             println!("-max_filters : {max_filters}");
         }
 
-        if id_len <= STD_ID_LENGTH && max_filters <= MAX_FILTERS_PER_NODE {
+        let fits_id_space = id_len <= STD_ID_LENGTH
+            && fits_next_to_carved_out_ids(set_count, setcode_len, &bucket_layout, carved_out_ids);
+        if fits_id_space && max_filters <= MAX_FILTERS_PER_NODE {
             break;
         }
 
@@ -237,6 +268,24 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
                 bucket_layout.apply_commit(best_commit)
             }
             None => {
+                // sets of std ids are bound by the std id length.
+                let id_bits = if network_info.receive_sets().iter().any(|rx_set| !rx_set.identifier().ide()) {
+                    STD_ID_LENGTH
+                } else {
+                    EXT_ID_LENGTH
+                };
+                if id_len > id_bits {
+                    return Err(ConfigError::Capacity(CapacityError::IdSpace {
+                        needed_setcodes: set_count,
+                        available_setcodes: if prio_len <= id_bits {
+                            1 << (id_bits - prio_len)
+                        } else {
+                            0
+                        },
+                        priority_bits: prio_len,
+                        id_bits,
+                    }));
+                }
                 println!("WARNING : exit without finding valid id assignment");
                 break;
             }
@@ -283,5 +332,5 @@ pub fn minimize_sets(network_info: NetworkInfo) -> MinimizedNetwork {
         .flatten()
         .collect();
 
-    MinimizedNetwork::new(minimized_sets, bucket_layout)
+    Ok(MinimizedNetwork::new(minimized_sets, bucket_layout))
 }
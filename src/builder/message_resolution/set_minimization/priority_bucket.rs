@@ -13,6 +13,9 @@ impl PriorityBucket {
     pub fn insert_message(&mut self, message: &MessageBuilder) {
         self.messages.push(message.clone());
     }
+    pub fn append(&mut self, other: PriorityBucket) {
+        self.messages.extend(other.messages);
+    }
     pub fn message_count(&self) -> usize {
         self.messages.len()
     }
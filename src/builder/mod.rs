@@ -5,6 +5,7 @@ pub use self::message_builder::MessageBuilder;
 pub use self::message_builder::MessageFormat;
 pub use self::message_builder::MessagePriority;
 pub use self::message_builder::MessageTypeFormatBuilder;
+pub use self::message_builder::MessageTypeFormatEntry;
 pub use self::message_builder::MessageSignalFormatBuilder;
 pub use self::network_builder::NetworkBuilder;
 pub use self::node::NodeBuilder;
@@ -12,6 +13,10 @@ pub use self::object_entry_builder::ObjectEntryBuilder;
 pub use self::type_builder::TypeBuilder;
 pub use self::type_builder::EnumBuilder;
 pub use self::type_builder::StructBuilder;
+pub use self::type_resolution::TypeResolver;
+pub use self::message_resolution::resolve_filters_only;
+pub(crate) use self::message_resolution::{hardware_filters, on_node_buses};
+pub use self::workspace::WorkspaceBuilder;
 
 pub mod command_builder;
 pub mod message_builder;
@@ -20,10 +25,30 @@ pub mod node;
 pub mod object_entry_builder;
 pub mod stream_builder;
 pub mod type_builder;
+pub mod artifact_cache;
+pub mod build_cache;
 pub mod bus;
+pub mod bus_balancing;
+pub mod bus_quota;
+pub mod chunked_read;
+pub mod deadline_priorities;
+pub mod frozen;
+pub mod lin_bus;
+pub mod naming;
+pub mod snapshot;
+pub mod snapshot_stream;
+pub mod stream_merging;
+pub mod supervision;
+pub mod time_triggered;
+pub mod topology;
+pub mod type_resolution;
+pub mod workspace;
 mod message_resolution;
 mod import_dbc;
+mod import_network;
 
+// Builders are shared handles into their data (XBuilder(pub BuilderRef<XData>)),
+// the handle type is part of the public api of every builder.
 type BuilderRef<T> = Rc<RefCell<T>>;
 
 fn make_builder_ref<T>(value: T) -> BuilderRef<T> {
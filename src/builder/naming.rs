@@ -0,0 +1,181 @@
+use regex::Regex;
+
+use crate::{
+    config::{message::MessageUsage, Network, Type},
+    errors::{self, Result},
+};
+
+use super::{message_builder::MessageBuilderUsage, NetworkBuilder, TypeBuilder};
+
+// Naming conventions are regular expressions per kind of object, which are
+// checked against the names of all objects that were defined by the user
+// (generated messages, streams and entries are not checked). Within a pattern
+// {node} is replaced by the name of the node that owns the object (any
+// transmitter for messages) and {bus} by the name of the bus of a message.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingKind {
+    Node,
+    Bus,
+    Message,
+    Stream,
+    Command,
+    ObjectEntry,
+    Type,
+}
+
+impl NamingKind {
+    fn name(&self) -> &'static str {
+        match self {
+            NamingKind::Node => "node",
+            NamingKind::Bus => "bus",
+            NamingKind::Message => "message",
+            NamingKind::Stream => "stream",
+            NamingKind::Command => "command",
+            NamingKind::ObjectEntry => "object entry",
+            NamingKind::Type => "type",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NamingRule {
+    pub kind: NamingKind,
+    pub pattern: String,
+    // the rule only applies to messages on the bus.
+    pub bus: Option<String>,
+}
+
+impl NamingRule {
+    pub(crate) fn new(kind: NamingKind, pattern: &str, bus: Option<&str>) -> Result<Self> {
+        // placeholders are always replaced by escaped names, which can't
+        // turn a valid pattern into an invalid one.
+        if let Err(err) = Regex::new(&Self::substitute(pattern, "node", "bus")) {
+            return Err(errors::ConfigError::InvalidNamingRule(format!(
+                "naming rule {pattern:?} is not a valid regex: {err}"
+            )));
+        }
+        Ok(Self {
+            kind,
+            pattern: pattern.to_owned(),
+            bus: bus.map(str::to_owned),
+        })
+    }
+    fn substitute(pattern: &str, node: &str, bus: &str) -> String {
+        pattern
+            .replace("{node}", &regex::escape(node))
+            .replace("{bus}", &regex::escape(bus))
+    }
+    fn is_match(&self, name: &str, node: &str, bus: &str) -> bool {
+        Regex::new(&Self::substitute(&self.pattern, node, bus))
+            .expect("naming rules are validated when they are added")
+            .is_match(name)
+    }
+}
+
+/// (kind, node, name) of everything the user defined, the node is
+/// empty for objects that don't belong to a node.
+pub(crate) fn user_defined_names(network_builder: &NetworkBuilder) -> Vec<(NamingKind, String, String)> {
+    let network_data = network_builder.0.borrow();
+    let mut names = vec![];
+    for node in network_data.nodes.borrow().iter() {
+        let node_data = node.0.borrow();
+        names.push((NamingKind::Node, String::new(), node_data.name.clone()));
+        for stream in &node_data.tx_streams {
+            names.push((NamingKind::Stream, node_data.name.clone(), stream.0.borrow().name.clone()));
+        }
+        for command in &node_data.commands {
+            names.push((NamingKind::Command, node_data.name.clone(), command.0.borrow().name.clone()));
+        }
+        for object_entry in &node_data.object_entries {
+            let name = object_entry.0.borrow().name.clone();
+            // every node has a config_hash and build_time entry.
+            if name != "config_hash" && name != "build_time" {
+                names.push((NamingKind::ObjectEntry, node_data.name.clone(), name));
+            }
+        }
+    }
+    for bus in network_data.buses.borrow().iter() {
+        names.push((NamingKind::Bus, String::new(), bus.0.borrow().name.clone()));
+    }
+    for message in network_data.messages.borrow().iter() {
+        let message_data = message.0.borrow();
+        if let MessageBuilderUsage::External { interval: _ } = message_data.usage {
+            names.push((NamingKind::Message, String::new(), message_data.name.clone()));
+        }
+    }
+    for ty in network_data.types.borrow().iter() {
+        let name = match ty {
+            TypeBuilder::Enum(enum_builder) => enum_builder.0.borrow().name.clone(),
+            TypeBuilder::Struct(struct_builder) => struct_builder.0.borrow().name.clone(),
+        };
+        names.push((NamingKind::Type, String::new(), name));
+    }
+    names
+}
+
+/// descriptions of all violations of the rules.
+pub(crate) fn check_naming_rules(
+    rules: &[NamingRule],
+    network: &Network,
+    names: &[(NamingKind, String, String)],
+) -> Vec<String> {
+    let mut violations = vec![];
+    for rule in rules {
+        for (kind, node, name) in names.iter().filter(|(kind, _, _)| *kind == rule.kind) {
+            let satisfied = match kind {
+                NamingKind::Message => {
+                    let Some(message) = network.messages().iter().find(|m| m.name() == name) else {
+                        continue;
+                    };
+                    if !matches!(message.usage(), MessageUsage::External { interval: _ }) {
+                        continue;
+                    }
+                    let bus = message.bus().name();
+                    if rule.bus.as_ref().is_some_and(|rule_bus| rule_bus != bus) {
+                        continue;
+                    }
+                    let transmitters: Vec<&str> = network
+                        .nodes()
+                        .iter()
+                        .filter(|n| n.tx_messages().iter().any(|m| m.name() == name))
+                        .map(|n| n.name())
+                        .collect();
+                    if transmitters.is_empty() {
+                        rule.is_match(name, "", bus)
+                    } else {
+                        transmitters.iter().any(|transmitter| rule.is_match(name, transmitter, bus))
+                    }
+                }
+                NamingKind::Type => {
+                    // pruned types are not part of the network anymore.
+                    if !network.types().iter().any(|ty| match ty as &Type {
+                        Type::Struct { name: ty_name, .. } | Type::Enum { name: ty_name, .. } => ty_name == name,
+                        _ => false,
+                    }) {
+                        continue;
+                    }
+                    rule.is_match(name, node, "")
+                }
+                _ => rule.is_match(name, node, ""),
+            };
+            if !satisfied {
+                let qualified = if node.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{node}::{name}")
+                };
+                let scope = match &rule.bus {
+                    Some(bus) => format!(" of bus {bus}"),
+                    None => String::new(),
+                };
+                violations.push(format!(
+                    "{} {qualified} violates the naming rule {:?}{scope}",
+                    rule.kind.name(),
+                    rule.pattern
+                ));
+            }
+        }
+    }
+    violations
+}
@@ -1,6 +1,11 @@
 use std::{
     cell::{OnceCell, RefCell},
     cmp::Ordering,
+    collections::BTreeMap,
+    hash::Hasher,
+    ops::Range,
+    path::{Path, PathBuf},
+    rc::Rc,
     time::Duration,
 };
 
@@ -10,29 +15,83 @@ use crate::{
     builder::message_resolution::resolve_ids_filters_and_buses,
     config::{
         self,
-        bus::BusRef,
+        assignment::{self, AssignmentViolation},
+        bus::{BusRef, BusTopology, FramePadding},
+        can_xl::{self, XlFrame},
         encoding::{CompositeSignalEncoding, PrimitiveSignalEncoding},
         make_config_ref,
-        message::MessageUsage,
+        message::{MessageAuthentication, MessageBurst, MessageDeprecation, MessageDocumentation, MessageResolutionInfo, MessageUsage, RemoteRequest},
         signal::Signal,
-        stream::Stream,
-        Command, ConfigRef, Message, MessageEncoding, MessageId, Network, NetworkRef, Node,
-        ObjectEntry, SignalRef, SignalType, Type, TypeRef, TypeSignalEncoding,
+        stream::{DeltaEncoding, DeltaRollover, Stream},
+        type_usage,
+        Command, ConfigRef, Constant, ConstantRef, Fault, FrameFormat, FrameTimeModel, Message, MessageEncoding, MessageId, Network,
+        NetworkRef, Node, ObjectEntry, object_entry::ChunkedRead, ObjectEntryAccess, ObjectEntryBound, ObjectEntryBounds, ObjectEntryDisplay, ObjectEntryRef, AggregationSource, StreamAggregation,
+        SignalRef, SignalType, TimeEpoch, Type, TypeRef, TypeSignalEncoding, mirror::MirrorTarget,
+        provenance::{InputFile, Provenance},
     },
+    diagnostics::{DiagnosticKind, Diagnostics, Severity},
     errors::Result,
     errors::{self},
+    simulator,
 };
 
 use super::{
-    bus::BusBuilder, import_dbc::import_dbc, make_builder_ref, message_builder::MessageIdTemplate,
-    BuilderRef, EnumBuilder, MessageBuilder, MessageFormat, MessagePriority, NodeBuilder,
-    StructBuilder, TypeBuilder,
+    artifact_cache, bus::BusBuilder, bus_balancing::{BusBalancer, GreedyBalancer}, bus_quota::{self, BusQuota}, chunked_read, build_cache::{self, BuildCache}, naming::{self, NamingKind, NamingRule}, deadline_priorities, snapshot_stream, stream_merging, supervision, topology, import_dbc::import_dbc, lin_bus::{self, LinBusBuilder}, time_triggered::{self, TimeTriggeredBuilder}, import_network::import_network, make_builder_ref, type_resolution::{self, TypeResolver}, message_builder::{MessageBuilderUsage, MessageIdTemplate},
+    BuilderRef, CommandBuilder, EnumBuilder, MessageBuilder, MessageFormat, MessagePriority, MessageTypeFormatEntry,
+    NodeBuilder, node::NodeData,
+    StructBuilder, TypeBuilder, stream_builder::UnmappedPolicy,
 };
 
+// expected bus load above which a warning is emitted.
+const BUS_LOAD_WARNING_THRESHOLD: f64 = 0.7;
+
+// types that are defined by the network builder itself, the generated
+// runtime relies on them even if the configuration doesn't use them.
+// names of the configuration end up as identifiers in the generated c/c++ code.
+const VALID_C_VAR: &str = r"^[a-zA-Z_]+[a-zA-Z0-9_]*$";
+const C_KEYWORDS: &str = r"^(restrict|alignas|alignof|and|and_eq|asm|atomic_cancel|atomic_commit|auto|bitand|bitor|bool|break|case|catch|char|char8_t|char16_t|char32_t|class|compl|concept|const|consteval|constexpr|constinit|const_cast|continue|co_await|co_return|co_yield|decltype|default|delete|do|double|dynamic_cast|else|enum|explicit|export|extern|false|float|for|friend|goto|if|inline|int|long|mutable|namespace|new|noexpect|not|not_eq|nullptr|operator|or|or_eq|private|protected|public|reflexpr|register|reinterpret_cast|require|return|short|signed|sizeof|static|static_assert|static_cast|struct|switch|synchronized|template|this|thread_local|throw|true|try|typedef|typeid|typename|union|unsigned|using|virtual|void|volatile|wchar_t|while|xor|xor_eq)$";
+
+const BUILTIN_TYPES: [&str; 11] = [
+    "get_resp_erno",
+    "set_resp_erno",
+    "get_req_header",
+    "get_resp_header",
+    "set_req_header",
+    "set_resp_header",
+    "command_resp_erno",
+    "date_time",
+    "fault_code",
+    "fault_state",
+    "node_id",
+];
+
+/// how build handles orphan messages (see NetworkBuilder::orphan_messages).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrphanPolicy {
+    /// the messages are part of the network and reported
+    /// as DiagnosticKind::MessageWithoutReceivers.
+    #[default]
+    Keep,
+    /// the messages are removed before the id resolution,
+    /// such that they don't take up ids.
+    Prune,
+}
+
+/// how build handles structs and enums that are not used by any message
+/// or object entry (see Network::type_usage).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnusedTypePolicy {
+    /// the types are part of the network and reported as DiagnosticKind::UnusedType.
+    #[default]
+    Keep,
+    /// the types are removed from Network::types.
+    Prune,
+}
+
 #[derive(Debug, Clone)]
 pub struct NetworkBuilder(pub BuilderRef<NetworkData>);
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NetworkData {
     pub messages: BuilderRef<Vec<MessageBuilder>>,
     pub types: BuilderRef<Vec<TypeBuilder>>,
@@ -42,6 +101,46 @@ pub struct NetworkData {
     pub set_req_message: OnceCell<MessageBuilder>,
     pub set_resp_message: OnceCell<MessageBuilder>,
     pub buses: BuilderRef<Vec<BusBuilder>>,
+    pub lin_buses: Vec<LinBusBuilder>,
+    pub time_triggered_schedules: Vec<TimeTriggeredBuilder>,
+    // (master, udp port)
+    pub mirror_target: Option<(String, u16)>,
+    // control stations (e.g. the ground station), see set_master.
+    pub masters: Vec<String>,
+    pub constants: Vec<ConstantRef>,
+    // (ids, ide) that are never assigned by the id resolution.
+    pub reserved_id_ranges: Vec<(Range<u32>, bool)>,
+    // max cost of folding a receiver set into a superset, None disables folding.
+    pub receiver_set_folding: Option<usize>,
+    // seed of the tie-breaking order of the id resolution, None orders by name.
+    pub resolution_seed: Option<u64>,
+    // thresholds of the deadline-monotonic priorities, None keeps the requested priorities.
+    pub deadline_priorities: Option<[Duration; 4]>,
+    pub frame_time_model: FrameTimeModel,
+    pub time_epoch: TimeEpoch,
+    // padding of buses without their own policy.
+    pub frame_padding: FramePadding,
+    pub bus_balancer: Rc<dyn BusBalancer>,
+    pub bus_quotas: Vec<BusQuota>,
+    pub naming_rules: Vec<NamingRule>,
+    // max bytes of merged streams, None disables merging.
+    pub stream_merging: Option<u8>,
+    pub heartbeat_timeout: Duration,
+    // applies UnmappedPolicy::Error to all receive streams.
+    pub strict_stream_mappings: bool,
+    // bits every stream frame keeps free for signals added later on.
+    pub stream_headroom: u8,
+    pub orphan_policy: OrphanPolicy,
+    pub unused_type_policy: UnusedTypePolicy,
+    pub uds: bool,
+    pub diagnostics: Diagnostics,
+    // shared between snapshots, None unless the build is incremental.
+    pub build_cache: Option<BuilderRef<BuildCache>>,
+    // directory of the persisted id resolution, only set during build_with_cache.
+    pub artifact_cache: Option<PathBuf>,
+    // revision of the config sources, supplied by the caller.
+    pub source_revision: Option<String>,
+    pub input_files: Vec<InputFile>,
 }
 
 impl NetworkBuilder {
@@ -57,6 +156,33 @@ impl NetworkBuilder {
             set_req_message: OnceCell::new(),
             set_resp_message: OnceCell::new(),
             buses: make_builder_ref(vec![]),
+            lin_buses: vec![],
+            time_triggered_schedules: vec![],
+            mirror_target: None,
+            masters: vec![],
+            constants: vec![],
+            reserved_id_ranges: vec![],
+            receiver_set_folding: None,
+            resolution_seed: None,
+            deadline_priorities: None,
+            frame_time_model: FrameTimeModel::default(),
+            time_epoch: TimeEpoch::default(),
+            frame_padding: FramePadding::default(),
+            bus_balancer: Rc::new(GreedyBalancer),
+            bus_quotas: vec![],
+            naming_rules: vec![],
+            stream_merging: None,
+            heartbeat_timeout: supervision::DEFAULT_HEARTBEAT_TIMEOUT,
+            strict_stream_mappings: false,
+            stream_headroom: 0,
+            orphan_policy: OrphanPolicy::default(),
+            unused_type_policy: UnusedTypePolicy::default(),
+            uds: false,
+            diagnostics: Diagnostics::new(),
+            build_cache: None,
+            artifact_cache: None,
+            source_revision: None,
+            input_files: vec![],
         }));
 
         let client_id_name = "client_id";
@@ -74,9 +200,11 @@ impl NetworkBuilder {
         set_resp_erno.add_entry("Success", Some(0)).unwrap();
         set_resp_erno.add_entry("Error", Some(1)).unwrap();
 
-        let get_req_message = network_builder.create_message("get_req", None);
+        let get_req_message = network_builder.create_message("get_req");
         get_req_message.set_any_std_id(MessagePriority::Low);
         get_req_message.__assign_to_configuration();
+        // configuration messages are also used by external clients.
+        get_req_message.mark_external();
         let get_req_format = get_req_message.make_type_format();
         let get_req_header = network_builder.define_struct("get_req_header");
         get_req_header.add_attribute(oe_index_name, "u13").unwrap();
@@ -90,9 +218,10 @@ impl NetworkBuilder {
             .set(get_req_message)
             .unwrap();
 
-        let get_resp_message = network_builder.create_message("get_resp", None);
+        let get_resp_message = network_builder.create_message("get_resp");
         get_resp_message.set_any_std_id(MessagePriority::Low);
         get_resp_message.__assign_to_configuration();
+        get_resp_message.mark_external();
         let get_resp_format = get_resp_message.make_type_format();
         let get_resp_header = network_builder.define_struct("get_resp_header");
         get_resp_header.add_attribute(sof_name, "u1").unwrap();
@@ -110,9 +239,10 @@ impl NetworkBuilder {
             .set(get_resp_message)
             .unwrap();
 
-        let set_req_message = network_builder.create_message("set_req", None);
+        let set_req_message = network_builder.create_message("set_req");
         set_req_message.set_any_std_id(MessagePriority::Low);
         set_req_message.__assign_to_configuration();
+        set_req_message.mark_external();
         let set_req_format = set_req_message.make_type_format();
         let set_req_header = network_builder.define_struct("set_req_header");
         set_req_header.add_attribute(sof_name, "u1").unwrap();
@@ -130,9 +260,10 @@ impl NetworkBuilder {
             .set(set_req_message)
             .unwrap();
 
-        let set_resp_message = network_builder.create_message("set_resp", None);
+        let set_resp_message = network_builder.create_message("set_resp");
         set_resp_message.set_any_std_id(MessagePriority::Low);
         set_resp_message.__assign_to_configuration();
+        set_resp_message.mark_external();
         let set_resp_format = set_resp_message.make_type_format();
         let set_resp_header = network_builder.define_struct("set_resp_header");
         set_resp_header.add_attribute(oe_index_name, "u13").unwrap();
@@ -170,6 +301,80 @@ impl NetworkBuilder {
         import_dbc(self, bus, dbc_path)
     }
 
+    /// registers all messages of an already built network on the buses that both
+    /// networks share (by name) with their fixed ids, e.g. to account for the
+    /// traffic of another pod on the same bus segment. The ids are reserved,
+    /// such that no message of this network is assigned to them.
+    /// If as_external is set, the messages are prefixed with external_ and
+    /// marked as external, such that they are only considered for the bus load
+    /// and the filters.
+    pub fn import_network(&self, network: &Network, as_external: bool) -> Result<()> {
+        import_network(self, network, as_external)
+    }
+
+    /// requires that every entry of a received stream is either mapped
+    /// or explicitly ignored, regardless of the policy of the receive stream.
+    pub fn strict_stream_mappings(&self) {
+        self.0.borrow_mut().strict_stream_mappings = true;
+    }
+    /// every stream has to leave bits free in its frame, such that signals
+    /// (e.g. alive counters or checksums) can be added later on without
+    /// splitting the stream, see DiagnosticKind::StreamOverflow.
+    pub fn set_stream_headroom(&self, bits: u8) {
+        self.0.borrow_mut().stream_headroom = bits;
+    }
+    pub fn set_orphan_policy(&self, policy: OrphanPolicy) {
+        self.0.borrow_mut().orphan_policy = policy;
+    }
+    /// names of the kind have to match the regex, where {node} is replaced by
+    /// the node that owns the object (transmitter of a message) and {bus} by the
+    /// bus of a message, e.g. (Message, "^{node}_[a-z0-9_]+$"). Violations are
+    /// reported as DiagnosticKind::NamingConvention.
+    pub fn add_naming_rule(&self, kind: NamingKind, pattern: &str) -> Result<()> {
+        let rule = NamingRule::new(kind, pattern, None)?;
+        self.0.borrow_mut().naming_rules.push(rule);
+        Ok(())
+    }
+    /// naming rule for the messages that are assigned to the bus.
+    pub fn add_bus_naming_rule(&self, bus: &str, pattern: &str) -> Result<()> {
+        let rule = NamingRule::new(NamingKind::Message, pattern, Some(bus))?;
+        self.0.borrow_mut().naming_rules.push(rule);
+        Ok(())
+    }
+    /// default timeout of the heartbeat supervisions, see builder::supervision.
+    pub fn set_heartbeat_timeout(&self, timeout: Duration) {
+        self.0.borrow_mut().heartbeat_timeout = timeout;
+    }
+    pub fn set_unused_type_policy(&self, policy: UnusedTypePolicy) {
+        self.0.borrow_mut().unused_type_policy = policy;
+    }
+    /// names of the messages that are neither transmitted nor received by any node
+    /// and are not used by a stream or command, e.g. messages that were created
+    /// with create_message and never connected.
+    pub fn orphan_messages(&self) -> Vec<String> {
+        self.0
+            .borrow()
+            .messages
+            .borrow()
+            .iter()
+            .filter(|message| Self::is_orphan(message))
+            .map(|message| message.0.borrow().name.clone())
+            .collect()
+    }
+    fn is_orphan(message: &MessageBuilder) -> bool {
+        let message_data = message.0.borrow();
+        matches!(message_data.usage, MessageBuilderUsage::External { interval: _ })
+            && !message_data.external
+            && message_data.transmitters.is_empty()
+            && message_data.receivers.is_empty()
+    }
+    /// turns all warnings into errors, e.g. for CI builds.
+    pub fn deny_warnings(&self) {
+        self.0.borrow_mut().diagnostics.deny_warnings();
+    }
+    pub fn set_diagnostic_severity(&self, kind: DiagnosticKind, severity: Severity) {
+        self.0.borrow_mut().diagnostics.set_severity(kind, severity);
+    }
     pub fn create_bus(&self, name: &str, baudrate: Option<u32>) -> BusBuilder {
         let network_data = self.0.borrow_mut();
         let id = network_data.buses.borrow().len();
@@ -178,19 +383,190 @@ impl NetworkBuilder {
         bus
     }
 
-    pub fn create_message(
+    /// the master receives all mirrored streams (StreamBuilder::mirror)
+    /// and sends them as protobuf messages (Network::generate_proto) over UDP.
+    pub fn set_mirror_target(&self, master: &str, port: u16) {
+        self.create_node(master);
+        self.0.borrow_mut().mirror_target = Some((master.to_owned(), port));
+    }
+
+    /// revision of the config sources (e.g. the git commit), which is
+    /// part of the provenance of the network.
+    pub fn set_source_revision(&self, revision: &str) {
+        self.0.borrow_mut().source_revision = Some(revision.to_owned());
+    }
+    /// records the hash of a file, the network was generated from, in the
+    /// provenance of the network. Files read by the builder (e.g. import_dbc)
+    /// are recorded implicitly.
+    pub fn add_input_file(&self, path: impl AsRef<Path>) -> errors::Result<()> {
+        let content = std::fs::read(path.as_ref())?;
+        self.record_input_file(&path.as_ref().to_string_lossy(), &content);
+        Ok(())
+    }
+    pub(crate) fn record_input_file(&self, path: &str, content: &[u8]) {
+        let mut hasher = seahash::SeaHasher::new();
+        hasher.write(content);
+        let input_file = InputFile::new(path.to_owned(), hasher.finish());
+        let mut network_data = self.0.borrow_mut();
+        network_data.input_files.retain(|other| other.path() != path);
+        network_data.input_files.push(input_file);
+    }
+
+    /// marks the node as a control station (e.g. the ground station), can be
+    /// called for several nodes. Masters receive all messages, all streams and
+    /// can call the commands of every other node, build wires them accordingly.
+    pub fn set_master(&self, name: &str) {
+        self.create_node(name);
+        let mut network_data = self.0.borrow_mut();
+        if !network_data.masters.iter().any(|master| master == name) {
+            network_data.masters.push(name.to_owned());
+        }
+    }
+
+    /// messages added to the schedule are sent in exclusive time windows
+    /// of the basic cycle (see config::time_triggered), the cycle count has
+    /// to be a power of two. Returns the schedule of the bus, if it already exists.
+    pub fn create_time_triggered_schedule(
         &self,
-        name: &str,
-        expected_interval: Option<Duration>,
-    ) -> MessageBuilder {
+        bus_name: &str,
+        basic_cycle: Duration,
+        cycle_count: u32,
+    ) -> TimeTriggeredBuilder {
+        let existing = self
+            .0
+            .borrow()
+            .time_triggered_schedules
+            .iter()
+            .find(|schedule| schedule.0.borrow().bus == bus_name)
+            .cloned();
+        if let Some(schedule) = existing {
+            return schedule;
+        }
+        self.create_bus(bus_name, None);
+        let schedule = TimeTriggeredBuilder::new(bus_name, basic_cycle, cycle_count);
+        self.0
+            .borrow_mut()
+            .time_triggered_schedules
+            .push(schedule.clone());
+        schedule
+    }
+
+    /// returns the LIN bus with the name, if it already exists.
+    pub fn create_lin_bus(&self, name: &str, baudrate: Option<u32>) -> LinBusBuilder {
+        let existing = self
+            .0
+            .borrow()
+            .lin_buses
+            .iter()
+            .find(|lin_bus| lin_bus.0.borrow().name == name)
+            .cloned();
+        if let Some(lin_bus) = existing {
+            return lin_bus;
+        }
+        let lin_bus = LinBusBuilder::new(name, baudrate, self);
+        self.0.borrow_mut().lin_buses.push(lin_bus.clone());
+        lin_bus
+    }
+
+    pub fn create_message(&self, name: &str) -> MessageBuilder {
         let network_data = self.0.borrow();
-        let message_builder = MessageBuilder::new(name, &self, expected_interval);
+        let message_builder = MessageBuilder::new(name, self, None);
         network_data
             .messages
             .borrow_mut()
             .push(message_builder.clone());
         message_builder
     }
+    /// creates a message that is expected to be transmitted every interval.
+    /// The interval only applies to messages, which are not part of a stream
+    /// or command and is used to estimate the bus load.
+    pub fn create_message_with_interval(&self, name: &str, interval: Duration) -> MessageBuilder {
+        self.create_message(name).expect_interval(interval)
+    }
+    /// standard ids in the range are not used for messages with
+    /// an any id, they can only be used by fixed ids.
+    pub fn reserve_std_id_range(&self, ids: Range<u32>) {
+        self.0.borrow_mut().reserved_id_ranges.push((ids, false));
+    }
+    pub fn reserve_ext_id_range(&self, ids: Range<u32>) {
+        self.0.borrow_mut().reserved_id_ranges.push((ids, true));
+    }
+    /// folds the receiver set of messages into the set of a superset of receivers,
+    /// if the number of frames that the extra receivers have to drop in software
+    /// (extra receivers * messages of the set) is at most max_cost.
+    /// Reduces the number of receiver sets and therefore the setcode length.
+    pub fn fold_receiver_sets(&self, max_cost: usize) {
+        self.0.borrow_mut().receiver_set_folding = Some(max_cost);
+    }
+    /// messages that are equivalent for the id resolution and bus balancing
+    /// (same receivers, priority and load) are ordered by their name.
+    /// With a seed they are ordered by a seeded hash of their name instead,
+    /// which yields a different, but for the same seed reproducible assignment.
+    pub fn set_resolution_seed(&self, seed: u64) {
+        self.0.borrow_mut().resolution_seed = Some(seed);
+    }
+    /// frame layout and bit stuffing used to estimate the bus load
+    /// when balancing buses, defaults to classic CAN with worst case stuffing.
+    pub fn set_frame_time_model(&self, frame_time_model: FrameTimeModel) {
+        self.0.borrow_mut().frame_time_model = frame_time_model;
+    }
+    /// reference point of all timestamp signals (t32<ms>), the boot of the
+    /// transmitting node by default.
+    pub fn set_time_epoch(&self, time_epoch: TimeEpoch) {
+        self.0.borrow_mut().time_epoch = time_epoch;
+    }
+    /// padding of the frames on all buses, that don't override it with
+    /// BusBuilder::padding. Padded frames are accounted for in the bus load,
+    /// bus balancing always estimates with the padding of the network.
+    pub fn set_frame_padding(&self, padding: FramePadding) {
+        self.0.borrow_mut().frame_padding = padding;
+    }
+    /// strategy that assigns messages without an explicit bus to the buses,
+    /// defaults to the GreedyBalancer. See BusBalancer for the requirements
+    /// on its Debug output.
+    pub fn set_bus_balancer(&self, bus_balancer: impl BusBalancer + 'static) {
+        self.0.borrow_mut().bus_balancer = Rc::new(bus_balancer);
+    }
+    /// limits the expected load of all messages with the tag on the bus
+    /// to max_load (fraction of the baudrate). Elastic streams with the tag
+    /// are slowed down to meet the quota, remaining violations are reported
+    /// as BusQuota diagnostics.
+    pub fn set_bus_quota(&self, tag: &str, bus: &str, max_load: f64) {
+        self.0.borrow_mut().bus_quotas.push(BusQuota {
+            tag: tag.to_owned(),
+            bus: bus.to_owned(),
+            max_load,
+        });
+    }
+    /// generates a UDS-on-CAN request and response message for every node
+    /// at the classic physical addresses (0x7E0 + i and 0x7E8 + i),
+    /// such that off-the-shelf diagnostic tools can talk to the nodes.
+    pub fn enable_uds(&self) {
+        self.0.borrow_mut().uds = true;
+    }
+    /// named scalar, which can be used as bound of object entries
+    /// and is exported to the generated code.
+    pub fn define_constant(&self, name: &str, value: f64, unit: &str) -> errors::Result<()> {
+        if !Regex::new(VALID_C_VAR).unwrap().is_match(name)
+            || Regex::new(C_KEYWORDS).unwrap().is_match(name)
+        {
+            return Err(errors::ConfigError::InvalidConstant(format!(
+                "{name} is not a valid constant name"
+            )));
+        }
+        let mut network_data = self.0.borrow_mut();
+        if network_data.constants.iter().any(|c| c.name() == name) {
+            return Err(errors::ConfigError::DuplicatedConstant(format!(
+                "constant {name} is already defined"
+            )));
+        }
+        network_data.constants.push(make_config_ref(Constant::new(
+            name.to_owned(),
+            value,
+            unit.to_owned(),
+        )));
+        Ok(())
+    }
     pub fn define_enum(&self, name: &str) -> EnumBuilder {
         let network_data = self.0.borrow();
         let type_builder = EnumBuilder::new(name);
@@ -246,99 +622,593 @@ impl NetworkBuilder {
 }
 
 impl NetworkBuilder {
-    pub fn resolve_type(
-        defined_types: &Vec<TypeRef>,
-        type_name: &str,
-    ) -> errors::Result<ConfigRef<Type>> {
-        let int_regex = regex::Regex::new(r#"^i(?<size>[0-9]{1,2})$"#).unwrap();
-        match int_regex.captures(type_name) {
-            Some(cap) => {
-                let size = &cap["size"];
-                let size = size.parse::<u8>().unwrap();
-                if size > 0 && size <= 64 {
-                    return Ok(make_config_ref(Type::Primitive(SignalType::SignedInt {
-                        size,
-                    })));
+    fn create_fault_registry(&self) -> errors::Result<()> {
+        let nodes = self.0.borrow().nodes.borrow().clone();
+        if nodes.iter().all(|node| node.0.borrow().faults.is_empty()) {
+            return Ok(());
+        }
+        let fault_code = self.define_enum("fault_code");
+        let fault_state = self.define_enum("fault_state");
+        fault_state.add_entry("Ok", Some(0))?;
+        fault_state.add_entry("Active", Some(1))?;
+        let mut code = 0;
+        for node in &nodes {
+            let faults = node.0.borrow().faults.clone();
+            if faults.is_empty() {
+                continue;
+            }
+            let node_name = node.0.borrow().name.clone();
+            let stream = node.create_stream("fault_status");
+            for (fault, _) in &faults {
+                fault_code.add_entry(&format!("{node_name}_{fault}"), Some(code))?;
+                code += 1;
+                let object_entry = node.create_object_entry(&format!("fault_{fault}"), "fault_state");
+                object_entry.set_access(ObjectEntryAccess::Local);
+                object_entry.mark_generated();
+                stream.add_entry_builder(&object_entry);
+            }
+            let clear_fault = node.create_command("clear_fault", None);
+            clear_fault.add_argument("fault", "fault_code");
+            // clear requests usually come from tools outside of the network.
+            clear_fault.0.borrow().call_message.mark_external();
+        }
+        Ok(())
+    }
+
+    // clients identify themselves with the same client_id as in get/set requests.
+    fn create_command_tokens(&self) -> errors::Result<()> {
+        let nodes = self.0.borrow().nodes.borrow().clone();
+        for node in &nodes {
+            let commands = node.0.borrow().commands.clone();
+            let mut groups: Vec<String> = vec![];
+            for command in &commands {
+                if let Some(group) = &command.0.borrow().token {
+                    if !groups.contains(group) {
+                        groups.push(group.clone());
+                    }
+                }
+            }
+            for group in &groups {
+                let group_commands: Vec<&CommandBuilder> = commands
+                    .iter()
+                    .filter(|command| command.0.borrow().token.as_ref() == Some(group))
+                    .collect();
+                let callers: Vec<String> = nodes
+                    .iter()
+                    .filter(|caller| {
+                        caller.0.borrow().extern_commands.iter().any(|extern_command| {
+                            group_commands
+                                .iter()
+                                .any(|command| Rc::ptr_eq(&command.0, &extern_command.0))
+                        })
+                    })
+                    .map(|caller| caller.0.borrow().name.clone())
+                    .collect();
+                let external = group_commands
+                    .iter()
+                    .any(|command| command.0.borrow().call_message.0.borrow().external);
+
+                let owner = node.create_object_entry(&format!("{group}_token_owner"), "u8");
+                owner.add_description(&format!(
+                    "client_id of the owner of the {group} token, 255 if the token is free"
+                ));
+                owner.set_access(ObjectEntryAccess::Local);
+                owner.mark_generated();
+                for action in ["request", "release"] {
+                    let command = node.create_command(&format!("{group}_token_{action}"), None);
+                    command.add_description(&format!("{action}s the {group} token"));
+                    command.add_argument("client_id", "u8");
+                    for caller in &callers {
+                        command.add_callee(caller)?;
+                    }
+                    if external {
+                        command.0.borrow().call_message.mark_external();
+                    }
                 }
             }
-            None => (),
         }
-        let uint_regex = regex::Regex::new(r#"^u(?<size>[0-9]{1,2})$"#).unwrap();
-        match uint_regex.captures(type_name) {
-            Some(cap) => {
-                let size = &cap["size"];
-                let size = size.parse::<u8>().unwrap();
-                if size > 0 && size <= 64 {
-                    return Ok(make_config_ref(Type::Primitive(SignalType::UnsignedInt {
-                        size,
-                    })));
+        Ok(())
+    }
+
+    // wiring that was done by hand before, calls that already happened are skipped.
+    fn wire_masters(&self) {
+        let masters = self.0.borrow().masters.clone();
+        let nodes = self.0.borrow().nodes.borrow().clone();
+        for master_name in &masters {
+            let master = self.create_node(master_name);
+            master.receive_all();
+            for node in nodes.iter().filter(|node| &node.0.borrow().name != master_name) {
+                let (commands, tx_streams) = {
+                    let node_data = node.0.borrow();
+                    (node_data.commands.clone(), node_data.tx_streams.clone())
+                };
+                for command in &commands {
+                    let calls = master
+                        .0
+                        .borrow()
+                        .extern_commands
+                        .iter()
+                        .any(|extern_command| Rc::ptr_eq(&extern_command.0, &command.0));
+                    if !calls {
+                        master.add_extern_command(command);
+                    }
+                }
+                for stream in &tx_streams {
+                    let message = stream.0.borrow().message.clone();
+                    let receives = master
+                        .0
+                        .borrow()
+                        .rx_messages
+                        .iter()
+                        .any(|rx_message| Rc::ptr_eq(&rx_message.0, &message.0));
+                    if !receives {
+                        master.add_rx_message(&message);
+                    }
                 }
             }
-            None => (),
-        }
-        let dec_regex = regex::Regex::new(r"^d(?<size>[0-9]{1,2})<(?<min>[+-]?([0-9]*[.])?[0-9]+)\.\.(?<max>[+-]?([0-9]*[.])?[0-9]+)>$").unwrap();
-        match dec_regex.captures(type_name) {
-            Some(cap) => {
-                let size = &cap["size"];
-                let size = size.parse::<u8>().unwrap();
-                let min = &cap["min"];
-                let min = min.parse::<f64>().unwrap();
-                let max = &cap["max"];
-                let max = max.parse::<f64>().unwrap();
-                if min >= max {
-                    return Err(errors::ConfigError::InvalidRange(
-                        "invalid decimal range min has to be less than max".to_owned(),
+        }
+    }
+
+    // passive nodes neither send heartbeats nor answer get and set requests,
+    // such that they also don't have to receive the requests.
+    fn silence_passive_nodes(&self) {
+        let nodes = self.0.borrow().nodes.borrow().clone();
+        let heartbeat = self
+            .0
+            .borrow()
+            .messages
+            .borrow()
+            .iter()
+            .find(|message| matches!(message.0.borrow().usage, MessageBuilderUsage::Heartbeat))
+            .cloned();
+        let tx_messages: Vec<MessageBuilder> = [self._get_resp_message(), self._set_resp_message()]
+            .into_iter()
+            .chain(heartbeat)
+            .collect();
+        let rx_messages = [self._get_req_message(), self._set_req_message()];
+        for node in nodes.iter().filter(|node| node.is_passive()) {
+            let node_name = node.0.borrow().name.clone();
+            let is_node = |other: &NodeBuilder| other.0.borrow().name == node_name;
+            for message in &tx_messages {
+                message.0.borrow_mut().transmitters.retain(|tx| !is_node(tx));
+                node.0.borrow_mut().tx_messages.retain(|m| !Rc::ptr_eq(&m.0, &message.0));
+            }
+            for message in &rx_messages {
+                message.0.borrow_mut().receivers.retain(|rx| !is_node(rx));
+                node.0.borrow_mut().rx_messages.retain(|m| !Rc::ptr_eq(&m.0, &message.0));
+            }
+        }
+    }
+
+    // rejects messages that are transmitted on a listen only bus of the node.
+    fn check_listen_only(nodes: &[config::NodeRef], node_builders: &[NodeBuilder], buses: &[BusRef]) -> errors::Result<()> {
+        for (node, node_builder) in nodes.iter().zip(node_builders) {
+            let listen_only: Vec<BusRef> = buses
+                .iter()
+                .filter(|bus| node_builder.0.borrow().listen_only.iter().any(|name| name == bus.name()))
+                .cloned()
+                .collect();
+            node.__set_listen_only(listen_only);
+            for message in node.tx_messages() {
+                let bus = std::iter::once(message.bus())
+                    .chain(message.fallback_bus())
+                    .find(|bus| node.is_listen_only(bus));
+                if let Some(bus) = bus {
+                    return Err(errors::ConfigError::ListenOnlyTransmission(format!(
+                        "{} transmits {} on {}, which the node only listens to",
+                        node.name(),
+                        message.name(),
+                        bus.name()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn subscribe_mirror_master(&self) -> errors::Result<()> {
+        let mirror_target = self.0.borrow().mirror_target.clone();
+        let nodes = self.0.borrow().nodes.borrow().clone();
+        for node in &nodes {
+            let streams = node.0.borrow().tx_streams.clone();
+            for stream in streams.iter().filter(|stream| stream.0.borrow().mirrored) {
+                let stream_data = stream.0.borrow();
+                let Some((master, _)) = &mirror_target else {
+                    return Err(errors::ConfigError::InvalidMirror(format!(
+                        "stream {}::{} is mirrored, but the network has no mirror target",
+                        node.0.borrow().name,
+                        stream_data.name
+                    )));
+                };
+                let master = self.create_node(master);
+                let receives = master
+                    .0
+                    .borrow()
+                    .rx_messages
+                    .iter()
+                    .any(|rx_message| Rc::ptr_eq(&rx_message.0, &stream_data.message.0));
+                if node.0.borrow().name != master.0.borrow().name && !receives {
+                    master.add_rx_message(&stream_data.message);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // UDS single frames: pci (length), service id and up to 6 data bytes.
+    fn create_uds_messages(&self) -> errors::Result<()> {
+        const UDS_REQ_BASE_ID: u32 = 0x7E0;
+        const UDS_RESP_BASE_ID: u32 = 0x7E8;
+        const UDS_MAX_NODES: usize = 8;
+        let nodes = self.0.borrow().nodes.borrow().clone();
+        if nodes.len() > UDS_MAX_NODES {
+            return Err(errors::ConfigError::InvalidUdsConfiguration(format!(
+                "uds supports at most {UDS_MAX_NODES} nodes, the network has {}",
+                nodes.len()
+            )));
+        }
+        self.reserve_std_id_range(UDS_REQ_BASE_ID..UDS_RESP_BASE_ID + UDS_MAX_NODES as u32);
+
+        for (i, node) in nodes.iter().enumerate() {
+            let node_name = node.0.borrow().name.clone();
+            // requests are sent by an external tester.
+            let req_message = self.create_message(&format!("{node_name}_uds_req"));
+            req_message.set_std_id(UDS_REQ_BASE_ID + i as u32);
+            req_message.mark_external();
+            let req_format = req_message.make_type_format();
+            req_format.add_type("u8", "pci");
+            req_format.add_type("u8", "sid");
+            req_format.add_type("u48", "data");
+            node.add_rx_message(&req_message);
+
+            // the response sid is the request sid + 0x40 or 0x7F (negative response).
+            let resp_message = self.create_message(&format!("{node_name}_uds_resp"));
+            resp_message.set_std_id(UDS_RESP_BASE_ID + i as u32);
+            resp_message.mark_external();
+            let resp_format = resp_message.make_type_format();
+            resp_format.add_type("u8", "pci");
+            resp_format.add_type("u8", "sid");
+            resp_format.add_type("u48", "data");
+            node.add_tx_message(&resp_message);
+        }
+        Ok(())
+    }
+
+    // the delta of an entry has to be narrower than its raw value,
+    // otherwise the entry could be transmitted as it is.
+    fn delta_encoding(
+        stream_name: &str,
+        object_entry: &ObjectEntryRef,
+        width: u8,
+        rollover: DeltaRollover,
+    ) -> errors::Result<DeltaEncoding> {
+        let reference_width = match object_entry.ty() as &Type {
+            Type::Primitive(
+                signal_type @ (SignalType::UnsignedInt { size: _ }
+                | SignalType::SignedInt { size: _ }
+                | SignalType::Decimal {
+                    size: _,
+                    offset: _,
+                    scale: _,
+                }
+                | SignalType::Time { .. }),
+            ) => signal_type.size(),
+            _ => {
+                return Err(errors::ConfigError::InvalidDeltaEncoding(format!(
+                    "entry {} of stream {stream_name} can't be delta encoded, its type {} is not an integer or decimal",
+                    object_entry.name(),
+                    object_entry.ty().name()
+                )))
+            }
+        };
+        if width < 2 || width >= reference_width {
+            return Err(errors::ConfigError::InvalidDeltaEncoding(format!(
+                "delta of entry {} of stream {stream_name} has {width} bits, but has to have 2 to {} bits",
+                object_entry.name(),
+                reference_width - 1
+            )));
+        }
+        Ok(DeltaEncoding::new(width, reference_width, rollover))
+    }
+
+    // validates the arguments of all commands before the resolution,
+    // which would otherwise fail with unhelpful panics.
+    fn check_command_arguments(
+        nodes: &Vec<NodeBuilder>,
+        types: &Vec<TypeRef>,
+    ) -> errors::Result<()> {
+        let valid_c_var = Regex::new(VALID_C_VAR).unwrap();
+        let is_c_keyword = Regex::new(C_KEYWORDS).unwrap();
+        let mut problems = vec![];
+        for node in nodes {
+            let node_data = node.0.borrow();
+            for command in &node_data.commands {
+                let command_data = command.0.borrow();
+                let command_name = format!("{}::{}", node_data.name, command_data.name);
+                let format = command_data.call_message_format.0.borrow();
+                let mut names: Vec<&str> = vec![];
+                let mut bits = 0;
+                for entry in &format.0 {
+                    let (ty, name) = match entry {
+                        MessageTypeFormatEntry::Type(ty, name)
+                        | MessageTypeFormatEntry::PlacedType(ty, name, _) => (ty, name),
+                        MessageTypeFormatEntry::Reserved(size) => {
+                            bits += *size as u32;
+                            continue;
+                        }
+                    };
+                    if !valid_c_var.is_match(name) || is_c_keyword.is_match(name) {
+                        problems.push(format!(
+                            "command {command_name}: argument {name:?} is not a valid identifier"
+                        ));
+                    }
+                    if names.contains(&name.as_str()) {
+                        problems.push(format!(
+                            "command {command_name}: duplicated argument {name}"
+                        ));
+                    }
+                    names.push(name);
+                    match Self::resolve_type(types, ty) {
+                        Ok(ty) => bits += ty.size(),
+                        Err(_) => problems.push(format!(
+                            "command {command_name}: argument {name} has an undefined type {ty}"
+                        )),
+                    }
+                }
+                if bits > 64 {
+                    problems.push(format!(
+                        "command {command_name}: arguments require {bits} bits, but only 64 bits fit into a frame"
                     ));
                 }
-                let range = max - min;
-                let scale = range / ((0xFFFFFFFFFFFFFFFF as u64 >> (64 - size)) as f64);
-                let offset = min;
-                if size <= 64 {
-                    return Ok(make_config_ref(Type::Primitive(SignalType::Decimal {
-                        size,
-                        offset,
-                        scale,
-                    })));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(errors::ConfigError::InvalidCommandArguments(
+                problems.join("\n"),
+            ))
+        }
+    }
+
+    // streams and commands are wired by their builders, nodes that were added
+    // by hand to their messages are reported as HiddenMessageWiring diagnostics.
+    fn check_hidden_message_wiring(
+        messages: &[MessageBuilder],
+        nodes: &[NodeBuilder],
+        mirror_master: Option<&str>,
+        masters: &[String],
+    ) -> Vec<String> {
+        let mut problems = vec![];
+        for message in messages {
+            let message_data = message.0.borrow();
+            let (usage, transmitters, receivers): (String, Vec<String>, Vec<String>) = match &message_data.usage {
+                MessageBuilderUsage::Stream(stream) => {
+                    let stream_data = stream.0.borrow();
+                    let tx_node = stream_data.tx_node.0.borrow().name.clone();
+                    let mut receivers: Vec<String> = nodes
+                        .iter()
+                        .filter(|node| {
+                            node.0.borrow().rx_streams.iter().any(|rx_stream| {
+                                Rc::ptr_eq(&rx_stream.0.borrow().stream_builder.0, &stream.0)
+                            })
+                        })
+                        .map(|node| node.0.borrow().name.clone())
+                        .collect();
+                    if let Some(master) = mirror_master.filter(|_| stream_data.mirrored) {
+                        receivers.push(master.to_owned());
+                    }
+                    receivers.extend(masters.iter().filter(|master| **master != tx_node).cloned());
+                    (format!("stream {tx_node}::{}", stream_data.name), vec![tx_node], receivers)
+                }
+                MessageBuilderUsage::CommandReq(command) | MessageBuilderUsage::CommandResp(command) => {
+                    let command_data = command.0.borrow();
+                    let tx_node = command_data.tx_node.0.borrow().name.clone();
+                    let callers: Vec<String> = nodes
+                        .iter()
+                        .filter(|node| {
+                            node.0
+                                .borrow()
+                                .extern_commands
+                                .iter()
+                                .any(|extern_command| Rc::ptr_eq(&extern_command.0, &command.0))
+                        })
+                        .map(|node| node.0.borrow().name.clone())
+                        .collect();
+                    let usage = format!("command {tx_node}::{}", command_data.name);
+                    if matches!(message_data.usage, MessageBuilderUsage::CommandReq(_)) {
+                        (usage, callers, vec![tx_node])
+                    } else {
+                        (usage, vec![tx_node], callers)
+                    }
+                }
+                // configuration and heartbeat messages are used by every node.
+                MessageBuilderUsage::Configuration
+                | MessageBuilderUsage::Heartbeat
+                | MessageBuilderUsage::External { interval: _ } => continue,
+            };
+            for (role, wired, expected) in [
+                ("transmitter", &message_data.transmitters, &transmitters),
+                ("receiver", &message_data.receivers, &receivers),
+            ] {
+                for node in wired {
+                    let node_name = &node.0.borrow().name;
+                    if !expected.contains(node_name) {
+                        problems.push(format!(
+                            "{node_name} was added as a {role} of the message {}, which belongs to the {usage}",
+                            message_data.name
+                        ));
+                    }
                 }
             }
-            None => (),
         }
-        let array_regex =
-                regex::Regex::new(r#"^(?<type>[a-zA-Z][a-zA-Z0-9]*(<[+-]?([0-9]*[.])?[0-9]+\.\.[+-]?([0-9]*[.])?[0-9]+>)?)\[(?<len>[0-9]+)\]$"#).unwrap();
-        match array_regex.captures(type_name) {
-            Some(cap) => {
-                let len = &cap["len"];
-                let len = len.parse::<usize>().unwrap();
-                let ty = &cap["type"];
-                let inner_type = Self::resolve_type(defined_types, ty)?;
-                return Ok(make_config_ref(Type::Array {
-                    len,
-                    ty: inner_type,
-                }));
+        problems
+    }
+
+    // manual bus pins are hard constraints, pins that can't be honored
+    // are reported as BusPin diagnostics.
+    fn check_bus_pins(messages: &Vec<MessageBuilder>) -> Vec<String> {
+        let mut problems = vec![];
+        for message in messages {
+            let message_data = message.0.borrow();
+            let Some(pin) = message_data.bus_pins.last() else {
+                continue;
+            };
+            if let Some(other) = message_data.bus_pins.iter().find(|other| *other != pin) {
+                problems.push(format!(
+                    "message {} is pinned to the buses {other} and {pin}",
+                    message_data.name
+                ));
+            }
+            for transmitter in &message_data.transmitters {
+                let node_data = transmitter.0.borrow();
+                if !node_data.buses.is_empty() && !node_data.buses.iter().any(|bus| &bus.0.borrow().name == pin) {
+                    problems.push(format!(
+                        "message {} is pinned to the bus {pin}, but its transmitter {} is not connected to it",
+                        message_data.name, node_data.name
+                    ));
+                }
             }
-            None => (),
         }
-        for ty in defined_types {
-            match ty as &Type {
-                Type::Struct {
-                    name,
-                    description: _,
-                    attribs: _,
-                    visibility: _,
-                } if name == type_name => return Ok(ty.clone()),
-                Type::Enum {
-                    name,
-                    description: _,
-                    size: _,
-                    entries: _,
-                    visibility: _,
-                } if name == type_name => return Ok(ty.clone()),
-                _ => (),
+        problems
+    }
+
+    // ignored entries have to exist in the tx stream, such that typos
+    // don't hide an entry that was meant to be ignored.
+    fn check_stream_mappings(nodes: &Vec<NodeBuilder>, strict: bool) -> errors::Result<()> {
+        let mut conflicts = vec![];
+        for node in nodes {
+            let node_data = node.0.borrow();
+            for object_entry in &node_data.object_entries {
+                let object_entry_data = object_entry.0.borrow();
+                if object_entry_data.sources.len() > 1 && object_entry_data.aggregation.is_none() {
+                    let sources: Vec<String> = object_entry_data
+                        .sources
+                        .iter()
+                        .map(|(tx_node, stream, tx_oe)| format!("{tx_node}::{stream}::{tx_oe}"))
+                        .collect();
+                    conflicts.push(format!(
+                        "object entry {}::{} is mapped from {} without an aggregation policy (see ObjectEntryBuilder::aggregate)",
+                        node_data.name,
+                        object_entry_data.name,
+                        sources.join(", ")
+                    ));
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(errors::ConfigError::ConflictingStreamMapping(conflicts.join("\n")));
+        }
+        let mut problems = vec![];
+        for node in nodes {
+            let node_data = node.0.borrow();
+            for rx_stream in &node_data.rx_streams {
+                let rx_stream_data = rx_stream.0.borrow();
+                let tx_stream_data = rx_stream_data.stream_builder.0.borrow();
+                let stream_name = format!(
+                    "{}::{} -> {}",
+                    tx_stream_data.tx_node.0.borrow().name,
+                    tx_stream_data.name,
+                    node_data.name
+                );
+                let tx_entries: Vec<String> = tx_stream_data
+                    .object_entries
+                    .iter()
+                    .map(|oe| oe.0.borrow().name.clone())
+                    .collect();
+                for ignored in &rx_stream_data.ignored {
+                    if !tx_entries.contains(ignored) {
+                        problems.push(format!(
+                            "stream {stream_name}: ignored entry {ignored} is not part of the stream"
+                        ));
+                    }
+                }
+                if !strict && rx_stream_data.unmapped_policy == UnmappedPolicy::Drop {
+                    continue;
+                }
+                for (i, entry) in tx_entries.iter().enumerate() {
+                    let mapped = rx_stream_data.object_entries.iter().any(|(j, _)| *j == i);
+                    if !mapped && !rx_stream_data.ignored.contains(entry) {
+                        problems.push(format!(
+                            "stream {stream_name}: entry {entry} is neither mapped nor ignored"
+                        ));
+                    }
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(errors::ConfigError::UnmappedStreamEntry(problems.join("\n")))
+        }
+    }
+
+    pub fn resolve_type(
+        defined_types: &Vec<TypeRef>,
+        type_name: &str,
+    ) -> errors::Result<ConfigRef<Type>> {
+        type_resolution::resolve_type_with(type_name, &|name| {
+            defined_types
+                .iter()
+                .find(|ty| matches!(ty as &Type, Type::Struct { name: n, .. } | Type::Enum { name: n, .. } if n == name))
+                .cloned()
+        })
+    }
+
+    /// resolves many type strings at once, every distinct string is
+    /// resolved only once (see TypeResolver).
+    pub fn resolve_types(defined_types: &[TypeRef], type_names: &[&str]) -> errors::Result<Vec<TypeRef>> {
+        TypeResolver::new(defined_types).resolve_all(type_names)
+    }
+
+    /// resolves a type string against the types that are defined so far,
+    /// e.g. to validate the input of a front end before building.
+    pub fn resolve_type_str(&self, type_name: &str) -> errors::Result<TypeRef> {
+        let types = Self::build_types(&self.0.borrow().types.borrow())?;
+        TypeResolver::new(&types).resolve(type_name)
+    }
+
+    /// size of a type in bits, before the types are built. None if the
+    /// type (or one of its attributes) isn't defined yet.
+    pub(crate) fn type_bit_len(&self, type_name: &str) -> Option<usize> {
+        if let Ok(ty) = Self::resolve_type(&vec![], type_name) {
+            return Some(ty.size() as usize);
+        }
+        let type_builder = self
+            .0
+            .borrow()
+            .types
+            .borrow()
+            .iter()
+            .find(|ty| match ty {
+                TypeBuilder::Enum(enum_builder) => enum_builder.0.borrow().name == type_name,
+                TypeBuilder::Struct(struct_builder) => struct_builder.0.borrow().name == type_name,
+            })
+            .cloned()?;
+        match type_builder {
+            TypeBuilder::Enum(enum_builder) => {
+                let mut max_entry = 0;
+                for (i, (_, opt_value)) in enum_builder.0.borrow().entries.iter().enumerate() {
+                    max_entry = match opt_value {
+                        Some(explicit_value) => max_entry.max(*explicit_value),
+                        None if i == 0 => max_entry,
+                        None => max_entry + 1,
+                    };
+                }
+                Some(if max_entry == 0 {
+                    1
+                } else {
+                    (max_entry as f64).log2().floor() as usize + 1
+                })
+            }
+            TypeBuilder::Struct(struct_builder) => {
+                let attributes = struct_builder.0.borrow().attributes.clone();
+                attributes
+                    .iter()
+                    .map(|(_, attribute_type)| self.type_bit_len(attribute_type))
+                    .sum()
             }
         }
-        return Err(errors::ConfigError::InvalidType(format!(
-            "failed to resolve type : {type_name:?}"
-        )));
     }
 
     fn topo_sort_types(types: &Vec<TypeRef>) -> Vec<TypeRef> {
@@ -357,6 +1227,7 @@ impl NetworkBuilder {
                     description: _,
                     attribs,
                     visibility: _,
+                    annotations: _,
                 } => {
                     for (_, attrib_type) in attribs {
                         match types.iter().position(|t| t == attrib_type) {
@@ -477,56 +1348,40 @@ impl NetworkBuilder {
             .collect())
     }
 
-    pub fn build(self) -> errors::Result<NetworkRef> {
-        // Generate Heartbeat messages!
-        let enum_node_id = self.define_enum("node_id");
-        let mut node_id = 0;
-        for node_builder in self.0.borrow().nodes.borrow().iter() {
-            let node_name = node_builder.0.borrow().name.clone();
-            enum_node_id.add_entry(&node_name, Some(node_id))?;
-            node_id += 1;
-        }
-        let heartbeat_message = self.create_message("heartbeat", Some(Duration::from_millis(100)));
-        heartbeat_message.__assign_to_heartbeat();
-        heartbeat_message.set_any_std_id(MessagePriority::SuperLow);
-        let heartbeat_message_format = heartbeat_message.make_type_format();
-        heartbeat_message_format.add_type("node_id", "node_id");
-        for node_builder in self.0.borrow().nodes.borrow().iter() {
-            node_builder.add_tx_message(&heartbeat_message);
-            node_builder.add_rx_message(&heartbeat_message);
-        }
-
-        if self.0.borrow().buses.borrow().is_empty() {
-            // ensure that there is always at least one bus defined!
-            self.create_bus("can0", None);
-            #[cfg(feature = "logging_info")]
-            println!("[CANZERO-CONFIG::build] Implicitly added can0 as the only bus");
-        }
-        let builder = self.0.borrow();
-
-        #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::build] Building buses");
-        let buses: Vec<BusRef> = builder
+    /// messages on the buses of the node, nodes without explicit
+    /// buses are connected to all buses.
+    fn messages_on_node_buses(messages: &[MessageBuilder], node_data: &NodeData) -> Vec<MessageBuilder> {
+        let bus_names: Vec<String> = node_data
             .buses
-            .borrow()
             .iter()
-            .map(|bus_builder| {
-                let bus_data = bus_builder.0.borrow();
-                make_config_ref(config::bus::Bus::new(
-                    &bus_data.name,
-                    bus_data.id,
-                    bus_data.baudrate,
-                ))
-            })
+            .chain(node_data.fallback_bus.iter())
+            .map(|bus| bus.0.borrow().name.clone())
             .collect();
+        messages
+            .iter()
+            .filter(|message| {
+                let message_data = message.0.borrow();
+                bus_names.is_empty()
+                    || message_data
+                        .bus
+                        .iter()
+                        .chain(message_data.fallback_bus.iter())
+                        .any(|bus| bus_names.contains(&bus.0.borrow().name))
+            })
+            .cloned()
+            .collect()
+    }
 
+    /// resolves the types in topological order.
+    fn build_types(type_builders: &Vec<TypeBuilder>) -> errors::Result<Vec<TypeRef>> {
         // sort types in topological order!
-        let type_builders = Self::topo_sort_type_builders(&builder.types.borrow())?;
+        let type_builders = Self::topo_sort_type_builders(type_builders)?;
 
         // define types.
         #[cfg(feature = "logging_info")]
         println!("[CANZERO-CONFIG::build] Building Types");
         let mut types = vec![];
+        let mut type_resolver = TypeResolver::default();
         for type_builder in type_builders.iter() {
             let type_ref: TypeRef = match type_builder {
                 TypeBuilder::Enum(enum_builder) => {
@@ -560,6 +1415,7 @@ impl NetworkBuilder {
                         description: enum_data.description.clone(),
                         entries,
                         visibility: enum_data.visibility.clone(),
+                        annotations: enum_data.annotations.clone(),
                     })
                 }
                 TypeBuilder::Struct(struct_builder) => {
@@ -571,7 +1427,7 @@ impl NetworkBuilder {
                         // This creates the restiction that the types
                         // are not defined recursivly which is probably
                         // a good restriction
-                        let ty = Self::resolve_type(&types, type_name)?;
+                        let ty = type_resolver.resolve(type_name)?;
                         attribs.push((name.clone(), ty));
                     }
                     make_config_ref(Type::Struct {
@@ -579,23 +1435,223 @@ impl NetworkBuilder {
                         description: struct_data.description.clone(),
                         attribs,
                         visibility: struct_data.visibility.clone(),
+                        annotations: struct_data.annotations.clone(),
                     })
                 }
             };
+            type_resolver.define(&type_ref);
             types.push(type_ref);
         }
+        Ok(types)
+    }
+
+    /// the builder is left unchanged, it can be modified and built again.
+    pub fn build(&self) -> errors::Result<NetworkRef> {
+        // build generates messages, types and ids within the builder.
+        let snapshot = self.snapshot();
+        let network = self.build_in_place();
+        self.restore(&snapshot);
+        network
+    }
+
+    fn build_in_place(&self) -> errors::Result<NetworkRef> {
+        // before build generates streams, commands and messages.
+        let user_defined_names = naming::user_defined_names(&self);
+        let stream_merging = self.0.borrow().stream_merging;
+        if let Some(max_bytes) = stream_merging {
+            stream_merging::merge_streams(self, max_bytes);
+        }
+        // Generate Heartbeat messages!
+        let enum_node_id = self.define_enum("node_id");
+        let mut node_id = 0;
+        for node_builder in self.0.borrow().nodes.borrow().iter() {
+            let node_name = node_builder.0.borrow().name.clone();
+            enum_node_id.add_entry(&node_name, Some(node_id))?;
+            node_id += 1;
+        }
+        let heartbeat_message = self.create_message_with_interval("heartbeat", Duration::from_millis(100));
+        heartbeat_message.__assign_to_heartbeat();
+        heartbeat_message.set_any_std_id(MessagePriority::SuperLow);
+        let heartbeat_message_format = heartbeat_message.make_type_format();
+        heartbeat_message_format.add_type("node_id", "node_id");
+        for node_builder in self.0.borrow().nodes.borrow().iter() {
+            node_builder.add_tx_message(&heartbeat_message);
+            node_builder.add_rx_message(&heartbeat_message);
+        }
+        if self.0.borrow().uds {
+            self.create_uds_messages()?;
+        }
+        self.create_fault_registry()?;
+        self.create_command_tokens()?;
+        chunked_read::create_chunked_reads(self)?;
+        self.wire_masters();
+        self.subscribe_mirror_master()?;
+        self.silence_passive_nodes();
+        if self.0.borrow().orphan_policy == OrphanPolicy::Prune {
+            #[cfg(feature = "logging_info")]
+            for name in self.orphan_messages() {
+                println!("[CANZERO-CONFIG::build] Pruning orphan message {name}");
+            }
+            self.0
+                .borrow()
+                .messages
+                .borrow_mut()
+                .retain(|message| !Self::is_orphan(message));
+        }
+        let lin_buses = self.0.borrow().lin_buses.clone();
+        for lin_bus in &lin_buses {
+            lin_bus::check_lin_bus(lin_bus)?;
+            lin_bus::create_gateway_messages(lin_bus)?;
+        }
+        let time_triggered_schedules = self.0.borrow().time_triggered_schedules.clone();
+        for schedule in &time_triggered_schedules {
+            time_triggered::check_schedule(schedule, &self)?;
+        }
+        let nodes = self.0.borrow().nodes.borrow().clone();
+        for node in &nodes {
+            let snapshot_stream = node.0.borrow().snapshot_stream.clone();
+            if let Some(snapshot_stream) = snapshot_stream {
+                snapshot_stream::create_snapshot_format(&snapshot_stream)?;
+            }
+        }
+        let deadline_priorities = self.0.borrow().deadline_priorities;
+        if let Some(thresholds) = deadline_priorities {
+            deadline_priorities::assign_deadline_priorities(self, &thresholds);
+        }
+
+        if self.0.borrow().buses.borrow().is_empty() {
+            // ensure that there is always at least one bus defined!
+            self.create_bus("can0", None);
+            #[cfg(feature = "logging_info")]
+            println!("[CANZERO-CONFIG::build] Implicitly added can0 as the only bus");
+        }
+        let builder = self.0.borrow();
+
+        #[cfg(feature = "logging_info")]
+        println!("[CANZERO-CONFIG::build] Building buses");
+        let buses: Vec<BusRef> = builder
+            .buses
+            .borrow()
+            .iter()
+            .map(|bus_builder| {
+                let bus_data = bus_builder.0.borrow();
+                make_config_ref(config::bus::Bus::new(
+                    &bus_data.name,
+                    bus_data.id,
+                    bus_data.baudrate,
+                    bus_data.padding.unwrap_or(builder.frame_padding),
+                    BusTopology::new(bus_data.max_nodes, bus_data.max_stub_length, bus_data.segments.clone()),
+                    bus_data.mode,
+                ))
+            })
+            .collect();
+        bus_quota::validate_quotas(&builder.bus_quotas, &buses)?;
+
+        let type_fingerprint = builder
+            .build_cache
+            .as_ref()
+            .map(|_| build_cache::type_fingerprint(&builder.types.borrow()));
+        let cached_types = builder
+            .build_cache
+            .as_ref()
+            .zip(type_fingerprint.as_ref())
+            .and_then(|(build_cache, fingerprint)| build_cache.borrow_mut().types(fingerprint));
+        let mut types = match cached_types {
+            Some(types) => types,
+            None => {
+                let types = Self::build_types(&builder.types.borrow())?;
+                if let Some((build_cache, fingerprint)) = builder.build_cache.as_ref().zip(type_fingerprint) {
+                    build_cache.borrow_mut().store_types(fingerprint, types.clone());
+                }
+                types
+            }
+        };
+        let type_resolver = TypeResolver::new(&types);
+
+        Self::check_command_arguments(&builder.nodes.borrow(), &types)?;
+        Self::check_stream_mappings(&builder.nodes.borrow(), builder.strict_stream_mappings)?;
 
         let tmp_buses = builder.buses.borrow().clone();
         let tmp_messages = builder.messages.borrow().clone();
+        let mut bus_pin_problems = Self::check_bus_pins(&tmp_messages);
+        let mirror_master = builder.mirror_target.as_ref().map(|(master, _)| master.clone());
+        let hidden_message_wiring_problems = Self::check_hidden_message_wiring(
+            &tmp_messages,
+            &builder.nodes.borrow(),
+            mirror_master.as_deref(),
+            &builder.masters,
+        );
         // we have to drop builder before we assign ids, because the following
         // function might require a mutable reference to self for assigning ids
         // and buses!
         let nodes = builder.nodes.borrow().clone();
+        let reserved_id_ranges = builder.reserved_id_ranges.clone();
+        let receiver_set_folding = builder.receiver_set_folding;
+        let resolution_seed = builder.resolution_seed;
+        let frame_time_model = builder.frame_time_model;
+        let frame_padding = builder.frame_padding;
+        let bus_balancer = builder.bus_balancer.clone();
+        let build_cache = builder.build_cache.clone();
+        let artifact_cache = builder.artifact_cache.clone();
         drop(builder);
+        // nodes that receive all messages are not part of any receiver set.
+        for message in &tmp_messages {
+            message
+                .0
+                .borrow_mut()
+                .receivers
+                .retain(|rx| !rx.0.borrow().receive_all);
+        }
         #[cfg(feature = "logging_info")]
         println!("[CANZERO-CONFIG::build] Resolving message ids and bus assignments");
-        resolve_ids_filters_and_buses(&tmp_buses, &tmp_messages, &nodes, &types)?;
+        let fingerprint = artifact_cache.as_ref().map(|_| {
+            let settings = format!(
+                "{reserved_id_ranges:?} {receiver_set_folding:?} {resolution_seed:?} {frame_time_model:?} {frame_padding:?} {bus_balancer:?}"
+            );
+            artifact_cache::resolution_fingerprint(&tmp_messages, &nodes, &tmp_buses, &types, &settings)
+        });
+        let cached_resolution = artifact_cache.as_ref().zip(fingerprint.as_ref()).and_then(|(dir, fingerprint)| {
+            artifact_cache::load_resolution(dir, fingerprint, &tmp_messages, &nodes, &tmp_buses)
+        });
+        let (filter_banks, id_layout) = match cached_resolution {
+            Some(resolution) => resolution,
+            None => {
+                let (filter_banks, id_layout) = resolve_ids_filters_and_buses(
+                    &tmp_buses,
+                    &tmp_messages,
+                    &nodes,
+                    &types,
+                    &reserved_id_ranges,
+                    receiver_set_folding,
+                    resolution_seed,
+                    &frame_time_model,
+                    frame_padding,
+                    bus_balancer.as_ref(),
+                    build_cache.as_ref(),
+                )?;
+                if let Some((dir, fingerprint)) = artifact_cache.as_ref().zip(fingerprint.as_ref()) {
+                    artifact_cache::store_resolution(dir, fingerprint, &tmp_messages, &filter_banks, &id_layout)?;
+                }
+                (filter_banks, id_layout)
+            }
+        };
+        // audits that the resolution kept every pinned message on its bus.
+        for message in &tmp_messages {
+            let message_data = message.0.borrow();
+            let Some(pin) = message_data.bus_pins.last() else {
+                continue;
+            };
+            let bus = message_data.bus.as_ref().map(|bus| bus.0.borrow().name.clone());
+            if bus.as_ref() != Some(pin) {
+                bus_pin_problems.push(format!(
+                    "message {} is pinned to the bus {pin}, but was moved to {}",
+                    message_data.name,
+                    bus.unwrap_or_default()
+                ));
+            }
+        }
         let builder = self.0.borrow();
+        let constants = builder.constants.clone();
 
         #[cfg(feature = "logging_info")]
         println!("[CANZERO-CONFIG::build] Building messages");
@@ -614,7 +1670,10 @@ impl NetworkBuilder {
                     let mut offset: usize = 0;
                     let signal_format_data = signal_format_builder.0.borrow();
                     let mut signals = vec![];
-                    for signal_data in signal_format_data.0.iter() {
+                    for (signal_data, start_bit) in signal_format_data.0.iter() {
+                        if let Some(start_bit) = start_bit {
+                            offset = *start_bit;
+                        }
                         signals.push(make_config_ref(Signal {
                             name: format!("{}_{}", message_data.name, signal_data.name),
                             offset,
@@ -636,15 +1695,19 @@ impl NetworkBuilder {
                         offset: &mut usize,
                         prefix: &str,
                         signals: &mut Vec<SignalRef>,
+                        annotations: &BTreeMap<String, String>,
                     ) -> TypeSignalEncoding {
                         match ty as &Type {
                             Type::Primitive(signal_type) => {
-                                let signal = make_config_ref(Signal::new(
-                                    &format!("{prefix}_{name}"),
-                                    None,
-                                    signal_type.clone(),
-                                    *offset,
-                                ));
+                                let signal = make_config_ref(Signal {
+                                    annotations: annotations.clone(),
+                                    ..Signal::new(
+                                        &format!("{prefix}_{name}"),
+                                        None,
+                                        signal_type.clone(),
+                                        *offset,
+                                    )
+                                });
                                 signals.push(signal.clone());
                                 *offset += signal.size() as usize;
                                 TypeSignalEncoding::Primitive(PrimitiveSignalEncoding::new(
@@ -658,6 +1721,7 @@ impl NetworkBuilder {
                                 description: _,
                                 attribs,
                                 visibility: _,
+                                annotations: _,
                             } => {
                                 let mut attributes = vec![];
                                 for (attrib_name, attrib_type) in attribs {
@@ -667,6 +1731,7 @@ impl NetworkBuilder {
                                         offset,
                                         &format!("{prefix}_{struct_name}"),
                                         signals,
+                                        annotations,
                                     ));
                                 }
                                 TypeSignalEncoding::Composite(CompositeSignalEncoding::new(
@@ -681,14 +1746,18 @@ impl NetworkBuilder {
                                 size,
                                 entries: _,
                                 visibility: _,
+                                annotations: _,
                             } => {
                                 let size = *size;
-                                let signal = make_config_ref(Signal::new(
-                                    &format!("{prefix}_{enum_name}"),
-                                    None,
-                                    SignalType::UnsignedInt { size },
-                                    *offset,
-                                ));
+                                let signal = make_config_ref(Signal {
+                                    annotations: annotations.clone(),
+                                    ..Signal::new(
+                                        &format!("{prefix}_{enum_name}"),
+                                        None,
+                                        SignalType::UnsignedInt { size },
+                                        *offset,
+                                    )
+                                });
                                 signals.push(signal.clone());
                                 *offset += signal.size() as usize;
                                 TypeSignalEncoding::Primitive(PrimitiveSignalEncoding::new(
@@ -701,15 +1770,46 @@ impl NetworkBuilder {
                         }
                     }
 
-                    for (type_name, var_name) in &type_format_data.0 {
-                        let type_ref = Self::resolve_type(&types, type_name)?;
-                        attributes.push(build_attribute(
-                            &type_ref,
-                            var_name,
-                            &mut offset,
-                            &format!("value_name"),
-                            &mut signals,
-                        ));
+                    let mut reserved_count = 0;
+                    for entry in &type_format_data.0 {
+                        match entry {
+                            MessageTypeFormatEntry::Type(type_name, var_name)
+                            | MessageTypeFormatEntry::PlacedType(type_name, var_name, _) => {
+                                if let MessageTypeFormatEntry::PlacedType(_, _, start_bit) = entry {
+                                    offset = *start_bit;
+                                }
+                                let type_ref = type_resolver.resolve(type_name)?;
+                                // annotations of stream entries apply to all signals of the entry.
+                                let annotations: BTreeMap<String, String> = match &message_data.usage {
+                                    MessageBuilderUsage::Stream(stream) => stream
+                                        .0
+                                        .borrow()
+                                        .entry_annotations
+                                        .iter()
+                                        .filter(|(entry, _, _)| entry == var_name)
+                                        .map(|(_, key, value)| (key.clone(), value.clone()))
+                                        .collect(),
+                                    _ => BTreeMap::new(),
+                                };
+                                attributes.push(build_attribute(
+                                    &type_ref,
+                                    var_name,
+                                    &mut offset,
+                                    &format!("value_name"),
+                                    &mut signals,
+                                    &annotations,
+                                ));
+                            }
+                            MessageTypeFormatEntry::Reserved(bits) => {
+                                signals.push(make_config_ref(Signal {
+                                    name: format!("{}_reserved{reserved_count}", message_data.name),
+                                    offset,
+                                    ..Signal::create_reserved("", *bits)
+                                }));
+                                reserved_count += 1;
+                                offset += *bits as usize;
+                            }
+                        }
                     }
                     let encoding = MessageEncoding::new(attributes);
 
@@ -718,18 +1818,155 @@ impl NetworkBuilder {
                 MessageFormat::Empty => (vec![], None),
             };
 
+            let mut sorted_signals: Vec<&SignalRef> = signals.iter().collect();
+            sorted_signals.sort_by_key(|signal| signal.byte_offset());
+            for (i, signal) in sorted_signals.iter().enumerate() {
+                let end = signal.byte_offset() + signal.size() as usize;
+                if let Some(other) = sorted_signals[i + 1..]
+                    .iter()
+                    .find(|other| other.byte_offset() < end)
+                {
+                    return Err(errors::ConfigError::OverlappingSignals(format!(
+                        "signals {} (bits {}..{end}) and {} (bits {}..{}) of message {} overlap",
+                        signal.name(),
+                        signal.byte_offset(),
+                        other.name(),
+                        other.byte_offset(),
+                        other.byte_offset() + other.size() as usize,
+                        message_data.name
+                    )));
+                }
+            }
+
+            let mut signals = signals;
             let mut max_bit = 0;
             for signal in &signals {
                 let signal_max_bit = signal.byte_offset() + signal.size() as usize;
                 max_bit = max_bit.max(signal_max_bit);
             }
-            let dlc = ((max_bit + 8 - 1) / 8) as u8;
+            let authentication = message_data.cmac.map(|(key_id, mac_len)| {
+                let offset = max_bit.div_ceil(8) * 8;
+                let signal = make_config_ref(Signal::new(
+                    &format!("{}_cmac", message_data.name),
+                    Some("truncated AES-CMAC of the frame"),
+                    SignalType::UnsignedInt { size: mac_len * 8 },
+                    offset,
+                ));
+                signals.push(signal.clone());
+                max_bit = offset + mac_len as usize * 8;
+                MessageAuthentication::new(key_id, mac_len, signal)
+            });
+            let bytes = max_bit.div_ceil(8);
 
             let bus = buses
                 .iter()
                 .find(|bus| bus.id() == message_data.bus.clone().unwrap().0.borrow().id)
                 .unwrap()
                 .clone();
+            let fallback_bus = message_builder.resolve_fallback_bus().map(|fallback_bus| {
+                buses
+                    .iter()
+                    .find(|bus| bus.id() == fallback_bus.0.borrow().id)
+                    .unwrap()
+                    .clone()
+            });
+
+            // the mac must fit into the frame next to the signals.
+            if authentication.is_some() && !bus.is_xl() && bytes > 8 {
+                return Err(errors::ConfigError::InvalidMessageAuthentication(format!(
+                    "{} has {bytes} bytes including its mac, but CAN frames of bus {} are limited to 8 bytes",
+                    message_data.name,
+                    bus.name()
+                )));
+            }
+
+            let resolution_info = match &message_data.resolution {
+                Some(resolution) => MessageResolutionInfo::new(
+                    bus.clone(),
+                    Some(resolution.setcode),
+                    resolution.setcode_len,
+                    resolution.requested_priority,
+                    resolution.priority_bucket,
+                    resolution.position,
+                ),
+                None => MessageResolutionInfo::new(bus.clone(), None, 0, None, None, None),
+            };
+            let (dlc, xl_frame) = if bus.is_xl() {
+                let invalid = |msg: String| Err(errors::ConfigError::InvalidCanXl(msg));
+                if id.ide() {
+                    return invalid(format!(
+                        "{} is on the xl bus {}, but CAN XL frames arbitrate with standard ids",
+                        message_data.name,
+                        bus.name()
+                    ));
+                }
+                if bytes > can_xl::MAX_XL_PAYLOAD {
+                    return invalid(format!(
+                        "{} has {bytes} bytes, but CAN XL frames are limited to {} bytes",
+                        message_data.name,
+                        can_xl::MAX_XL_PAYLOAD
+                    ));
+                }
+                let xl_frame = XlFrame::new(
+                    message_data.sdu_type.unwrap_or_default(),
+                    message_data.acceptance_field.unwrap_or(id.as_u32()),
+                    bytes.max(1) as u16,
+                );
+                (bytes.min(u8::MAX as usize) as u8, Some(xl_frame))
+            } else {
+                if message_data.sdu_type.is_some() || message_data.acceptance_field.is_some() {
+                    return Err(errors::ConfigError::InvalidCanXl(format!(
+                        "{} has CAN XL metadata, but bus {} is not in xl mode",
+                        message_data.name,
+                        bus.name()
+                    )));
+                }
+                (bytes as u8, None)
+            };
+
+            let remote_request = if message_data.remote_requestable {
+                let invalid = |msg: String| Err(errors::ConfigError::InvalidRemoteRequest(msg));
+                if bus.is_xl() || frame_time_model.format != FrameFormat::Classic {
+                    return invalid(format!(
+                        "{} is remote requestable, but only classic CAN frames have a rtr bit",
+                        message_data.name
+                    ));
+                }
+                if message_data.transmitters.is_empty() {
+                    return invalid(format!(
+                        "{} is remote requestable, but has no transmitter that answers",
+                        message_data.name
+                    ));
+                }
+                for requester in &message_data.remote_requesters {
+                    if !builder.nodes.borrow().iter().any(|node| &node.0.borrow().name == requester) {
+                        return invalid(format!(
+                            "{requester} requests {}, but is not a node of the network",
+                            message_data.name
+                        ));
+                    }
+                    if message_data.transmitters.iter().any(|tx| &tx.0.borrow().name == requester) {
+                        return invalid(format!(
+                            "{requester} transmits {} and can't request it",
+                            message_data.name
+                        ));
+                    }
+                }
+                Some(RemoteRequest::new(
+                    message_data.remote_requesters.clone(),
+                    message_data.remote_response_latency,
+                ))
+            } else {
+                if !message_data.remote_requesters.is_empty()
+                    || message_data.remote_response_latency.is_some()
+                {
+                    return Err(errors::ConfigError::InvalidRemoteRequest(format!(
+                        "{} has remote requesters or a response latency, but is not remote requestable",
+                        message_data.name
+                    )));
+                }
+                None
+            };
 
             messages.push(make_config_ref(Message::new(
                 message_data.name.clone(),
@@ -739,9 +1976,33 @@ impl NetworkBuilder {
                 signals,
                 message_data.visibility.clone(),
                 dlc,
-                bus,
+                bus.clone(),
+                fallback_bus,
+                resolution_info,
+                message_data.version.clone(),
+                message_data
+                    .deprecation
+                    .clone()
+                    .map(|(since, note)| MessageDeprecation::new(since, note)),
+                message_data.processing_deadline,
+                message_data.deadline,
+                authentication,
+                message_data
+                    .burst
+                    .map(|(max_frames, window)| MessageBurst::new(max_frames, window)),
+                MessageDocumentation::new(
+                    message_data.author.clone(),
+                    message_data.requirement.clone(),
+                    message_data.revision_note.clone(),
+                ),
+                message_data.annotations.clone(),
+                xl_frame,
+                remote_request,
             )));
         }
+        // the order of the builders depends on when messages are generated,
+        // sorting by name keeps generated code stable between definitions.
+        messages.sort_by(|a, b| a.name().cmp(b.name()));
         let get_resp_message = messages
             .iter()
             .find(|m| m.name() == builder.get_resp_message.get().unwrap().0.borrow().name)
@@ -767,6 +2028,15 @@ impl NetworkBuilder {
             .clone();
         set_req_message.__set_usage(MessageUsage::SetReq);
 
+        // has to happen before the streams are built, because
+        // it modifies the intervals of the stream builders.
+        bus_quota::degrade_elastic_streams(
+            &builder.bus_quotas,
+            &messages,
+            &builder.messages.borrow(),
+            &frame_time_model,
+        );
+
         pub fn rec_type_acc(node_types: &mut Vec<TypeRef>, encoding: &TypeSignalEncoding) {
             match encoding {
                 TypeSignalEncoding::Composite(composite) => {
@@ -784,6 +2054,7 @@ impl NetworkBuilder {
                         description: _,
                         attribs: _,
                         visibility: _,
+                        annotations: _,
                     } => panic!("not a primitive"),
                     Type::Enum {
                         name: _,
@@ -791,6 +2062,7 @@ impl NetworkBuilder {
                         size: _,
                         entries: _,
                         visibility: _,
+                        annotations: _,
                     } => {
                         if !node_types.contains(primitive.ty()) {
                             node_types.push(primitive.ty().clone());
@@ -816,8 +2088,16 @@ impl NetworkBuilder {
                 "[CANZERO-CONFIG::build] Collecting all messages received by node {}",
                 &node_data.name
             );
+            let mut rx_message_builders = if node_data.receive_all {
+                Self::messages_on_node_buses(&builder.messages.borrow(), &node_data)
+            } else {
+                node_data.rx_messages.clone()
+            };
+            // like the messages of the network, the messages (and therefore
+            // the types) of the nodes are sorted by name.
+            rx_message_builders.sort_by_key(|message| message.0.borrow().name.clone());
             let mut rx_messages = vec![];
-            for rx_message_builder in &node_data.rx_messages {
+            for rx_message_builder in &rx_message_builders {
                 let message_ref = messages
                     .iter()
                     .find(|m| m.name() == rx_message_builder.0.borrow().name)
@@ -838,8 +2118,10 @@ impl NetworkBuilder {
                 "[CANZERO-CONFIG::build] Collecting all messages transmitted by node {}",
                 &node_data.name
             );
+            let mut tx_message_builders = node_data.tx_messages.clone();
+            tx_message_builders.sort_by_key(|message| message.0.borrow().name.clone());
             let mut tx_messages = vec![];
-            for tx_message_builder in &node_data.tx_messages {
+            for tx_message_builder in &tx_message_builders {
                 let message_ref = messages
                     .iter()
                     .find(|m| m.name() == tx_message_builder.0.borrow().name)
@@ -880,6 +2162,12 @@ impl NetworkBuilder {
                     rx_message.clone(),
                     command_data.visibility.clone(),
                     command_data.expected_interval.clone(),
+                    command_data.burst,
+                    command_data.timeout,
+                    command_data.max_in_flight,
+                    command_data.token.clone(),
+                    command_data.errors.clone(),
+                    command_data.annotations.clone(),
                 ));
                 rx_message.__set_usage(MessageUsage::CommandResp(command_ref.clone()));
                 tx_message.__set_usage(MessageUsage::CommandReq(command_ref.clone()));
@@ -896,7 +2184,7 @@ impl NetworkBuilder {
             let mut id_acc = 0;
             for object_entry_builder in &node_builder.0.borrow().object_entries {
                 let object_entry_data = object_entry_builder.0.borrow();
-                let ty = Self::resolve_type(&mut types, &object_entry_data.ty)?;
+                let ty = type_resolver.resolve(&object_entry_data.ty)?;
                 fn rec_add_type(node_types: &mut Vec<TypeRef>, ty: &TypeRef) {
                     match ty as &Type {
                         Type::Primitive(_) => (),
@@ -905,6 +2193,7 @@ impl NetworkBuilder {
                             description: _,
                             attribs,
                             visibility: _,
+                            annotations: _,
                         } => {
                             if !node_types.contains(ty) {
                                 node_types.push(ty.clone());
@@ -919,15 +2208,59 @@ impl NetworkBuilder {
                             size: _,
                             entries: _,
                             visibility: _,
+                            annotations: _,
                         } => {
                             if !node_types.contains(ty) {
                                 node_types.push(ty.clone());
                             }
                         }
-                        Type::Array { len: _, ty: _ } => todo!(),
+                        Type::Array { len: _, ty } => rec_add_type(node_types, ty),
                     };
                 }
                 rec_add_type(&mut node_types, &ty);
+                let bounds = match &object_entry_data.bounds {
+                    Some((min, max)) => {
+                        let resolve_bound = |bound: &str| match bound.parse::<f64>() {
+                            Ok(value) => Ok(ObjectEntryBound::Value(value)),
+                            Err(_) => match constants.iter().find(|c| c.name() == bound) {
+                                Some(constant) => Ok(ObjectEntryBound::Constant(constant.clone())),
+                                None => Err(errors::ConfigError::UndefinedConstant(format!(
+                                    "bound {bound} of object entry {}::{} is neither a number nor a constant",
+                                    node_data.name, object_entry_data.name
+                                ))),
+                            },
+                        };
+                        let bounds = ObjectEntryBounds::new(resolve_bound(min)?, resolve_bound(max)?);
+                        if bounds.min().value() > bounds.max().value() {
+                            return Err(errors::ConfigError::InvalidRange(format!(
+                                "bounds of object entry {}::{} are empty ({min} > {max})",
+                                node_data.name, object_entry_data.name
+                            )));
+                        }
+                        Some(bounds)
+                    }
+                    None => None,
+                };
+                let display = match &object_entry_data.display {
+                    Some((factor, offset, unit)) => {
+                        if !factor.is_finite() || *factor == 0.0 || !offset.is_finite() {
+                            return Err(errors::ConfigError::InvalidDisplayConversion(format!(
+                                "display conversion of object entry {}::{} (factor {factor}, offset {offset}) is not invertible",
+                                node_data.name, object_entry_data.name
+                            )));
+                        }
+                        Some(ObjectEntryDisplay::new(*factor, *offset, unit))
+                    }
+                    None => None,
+                };
+                let aggregation = object_entry_data.aggregation.map(|policy| {
+                    let sources = object_entry_data
+                        .sources
+                        .iter()
+                        .map(|(tx_node, stream, tx_oe)| AggregationSource::new(tx_node, stream, tx_oe))
+                        .collect();
+                    StreamAggregation::new(policy, sources)
+                });
                 let id = id_acc;
                 id_acc += 1;
                 object_entries.push(make_config_ref(ObjectEntry::new(
@@ -938,7 +2271,22 @@ impl NetworkBuilder {
                     ty,
                     object_entry_data.access.clone(),
                     object_entry_data.visibility.clone(),
+                    bounds,
+                    object_entry_data.persistent,
+                    display,
+                    aggregation,
                 )));
+                let chunked_read = object_entry_data.chunked_read.as_ref().map(|(response, index_bits, elements_per_frame)| {
+                    let message = |name: &str| {
+                        messages
+                            .iter()
+                            .find(|m| m.name() == name)
+                            .expect("chunked read message was not added to the network")
+                            .clone()
+                    };
+                    ChunkedRead::new(message("chunk_read_req"), message(response), *index_bits, *elements_per_frame)
+                });
+                object_entries.last().unwrap().__set_chunked_read(chunked_read);
             }
 
             #[cfg(feature = "logging_info")]
@@ -957,6 +2305,7 @@ impl NetworkBuilder {
                     .expect("stream message was not added to the network")
                     .clone();
                 let mut mappings = vec![];
+                let mut delta_encodings = vec![];
                 for oe_builder in &stream_data.object_entries {
                     let oe_data = oe_builder.0.borrow();
                     let oe = object_entries
@@ -964,6 +2313,19 @@ impl NetworkBuilder {
                         .find(|oe| oe.name() == oe_data.name)
                         .expect("stream object entry wasn't added to the node")
                         .clone();
+                    let delta_entry = stream_data
+                        .delta_entries
+                        .iter()
+                        .find(|(name, _, _)| name == &oe_data.name);
+                    delta_encodings.push(match delta_entry {
+                        Some((_, width, rollover)) => Some(Self::delta_encoding(
+                            &stream_data.name,
+                            &oe,
+                            *width,
+                            *rollover,
+                        )?),
+                        None => None,
+                    });
                     mappings.push(Some(oe));
                 }
 
@@ -974,6 +2336,10 @@ impl NetworkBuilder {
                     message.clone(),
                     stream_data.visbility.clone(),
                     stream_data.interval,
+                    delta_encodings,
+                    stream_data.merged.clone(),
+                    None,
+                    stream_data.annotations.clone(),
                 ));
                 message.__set_usage(MessageUsage::Stream(stream_ref.clone()));
                 tx_streams.push(stream_ref);
@@ -991,6 +2357,13 @@ impl NetworkBuilder {
             );
             let node_types = Self::topo_sort_types(&node_types);
 
+            let fallback_bus = node_data.fallback_bus.as_ref().map(|fallback_bus| {
+                buses
+                    .iter()
+                    .find(|bus| bus.id() == fallback_bus.0.borrow().id)
+                    .unwrap()
+                    .clone()
+            });
             let buses = node_data
                 .buses
                 .iter()
@@ -1013,6 +2386,37 @@ impl NetworkBuilder {
                 "[CANZERO-CONFIG::build] Successfully build transmitting part of node {}",
                 node_data.name
             );
+            let mut faults = vec![];
+            for (fault, severity) in &node_data.faults {
+                let code = types
+                    .iter()
+                    .find_map(|ty| match ty as &Type {
+                        Type::Enum {
+                            name,
+                            description: _,
+                            size: _,
+                            entries,
+                            visibility: _,
+                            annotations: _,
+                        } if name == "fault_code" => entries
+                            .iter()
+                            .find(|(entry, _)| *entry == format!("{}_{fault}", node_data.name))
+                            .map(|(_, code)| *code),
+                        _ => None,
+                    })
+                    .expect("fault codes are defined at the start of the build");
+                let object_entry = object_entries
+                    .iter()
+                    .find(|oe| oe.name() == format!("fault_{fault}"))
+                    .expect("fault object entries are defined at the start of the build")
+                    .clone();
+                faults.push(make_config_ref(Fault::new(
+                    fault.clone(),
+                    code,
+                    *severity,
+                    object_entry,
+                )));
+            }
             nodes.push(RefCell::new(Node::new(
                 node_data.name.clone(),
                 node_data.description.clone(),
@@ -1026,6 +2430,10 @@ impl NetworkBuilder {
                 tx_messages,
                 object_entries,
                 buses,
+                fallback_bus,
+                faults,
+                node_data.receive_all,
+                node_data.annotations.clone(),
             )));
         }
 
@@ -1082,6 +2490,17 @@ impl NetworkBuilder {
                     .find(|s| s.name() == tx_stream_data.name)
                     .unwrap()
                     .clone();
+                if let Some(timeout) = rx_stream_data.timeout {
+                    if timeout < *tx_stream.max_interval() {
+                        return Err(errors::ConfigError::InvalidReceiveStream(format!(
+                            "timeout {timeout:?} of {}::{} received by {} is shorter than the max interval {:?} of the stream",
+                            tx_node_data.name,
+                            tx_stream_data.name,
+                            node_data.name,
+                            tx_stream.max_interval()
+                        )));
+                    }
+                }
 
                 let mut builder_mapping = rx_stream_data.object_entries.clone();
                 builder_mapping.sort_by(|(i1, _), (i2, _)| {
@@ -1093,7 +2512,7 @@ impl NetworkBuilder {
                         Ordering::Greater
                     }
                 });
-                let oe_count = builder_mapping.len();
+                let oe_count = tx_stream.mapping().len();
                 let mut mappings = vec![];
                 let mut j = 0;
                 let rx_node_data = rx_stream_data.rx_node.0.borrow();
@@ -1103,7 +2522,7 @@ impl NetworkBuilder {
                     .unwrap()
                     .borrow();
                 for i in 0..oe_count {
-                    if builder_mapping[j].0 == i {
+                    if j < builder_mapping.len() && builder_mapping[j].0 == i {
                         // search for object entry in rx_node
                         let oe = rx_node
                             .object_entries()
@@ -1130,6 +2549,10 @@ impl NetworkBuilder {
                         tx_stream.message().clone(),
                         rx_stream_data.visibility.clone(),
                         *tx_stream.interval(),
+                        tx_stream.delta_encodings().clone(),
+                        tx_stream.merged_streams().clone(),
+                        rx_stream_data.timeout,
+                        tx_stream.annotations().clone(),
                     )));
             }
         }
@@ -1150,6 +2573,26 @@ impl NetworkBuilder {
             }
         }
 
+        for node in &nodes {
+            let mut software_filters: Vec<config::MessageRef> = filter_banks
+                .iter()
+                .find(|bank| bank.node().0.borrow().name == node.name())
+                .map(|bank| {
+                    bank.software_filters()
+                        .iter()
+                        .filter_map(|filter| {
+                            messages
+                                .iter()
+                                .find(|m| m.id().as_u32() == filter.id() && m.id().ide() == filter.ide())
+                                .cloned()
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            software_filters.sort_by(|a, b| a.name().cmp(b.name()));
+            node.__set_software_filters(software_filters);
+        }
+
         #[cfg(feature = "logging_info")]
         println!("[CANZERO-CONFIG::build] Finalizing usage of all messages");
         // set usage for all messages!
@@ -1181,12 +2624,65 @@ impl NetworkBuilder {
             }
         }
 
+        let supervision_matrix =
+            supervision::supervision_matrix(&nodes, &builder.nodes.borrow(), builder.heartbeat_timeout)?;
+        for (node, supervisions) in nodes.iter().zip(supervision_matrix) {
+            node.__set_supervisions(supervisions);
+        }
+        Self::check_listen_only(&nodes, &builder.nodes.borrow(), &buses)?;
+        topology::validate_topology(&buses, &nodes)?;
+        for (node, node_builder) in nodes.iter().zip(builder.nodes.borrow().iter()) {
+            let snapshot_stream = match &node_builder.0.borrow().snapshot_stream {
+                Some(snapshot_stream) => Some(make_config_ref(snapshot_stream::build_snapshot_stream(
+                    snapshot_stream,
+                    node,
+                    &messages,
+                )?)),
+                None => None,
+            };
+            node.__set_snapshot_stream(snapshot_stream);
+        }
+        for (node, node_builder) in nodes.iter().zip(builder.nodes.borrow().iter()) {
+            let mut init_sequence = vec![];
+            for name in &node_builder.0.borrow().init_sequence {
+                let Some(command) = node.commands().iter().find(|command| command.name() == name) else {
+                    return Err(errors::ConfigError::InvalidInitSequence(format!(
+                        "the init sequence of {} calls {name}, which is not a command of the node",
+                        node.name()
+                    )));
+                };
+                if !command.tx_message().signals().is_empty() {
+                    return Err(errors::ConfigError::InvalidInitSequence(format!(
+                        "the init sequence of {} calls {name}, but only commands without arguments can be called",
+                        node.name()
+                    )));
+                }
+                init_sequence.push(command.clone());
+            }
+            node.__set_init_sequence(init_sequence);
+        }
+
         let heartbeat_message = messages
             .iter()
             .find(|message| message.name() == "heartbeat")
             .expect("heartbeat message was not defined")
             .clone();
 
+        let unused_types: Vec<String> = type_usage::type_usage(&types, &messages, &nodes)
+            .iter()
+            .filter(|usage| !usage.is_used())
+            .map(|usage| usage.ty().name())
+            .filter(|name| !BUILTIN_TYPES.contains(&name.as_str()))
+            .collect();
+        let unused_type_policy = self.0.borrow().unused_type_policy;
+        if unused_type_policy == UnusedTypePolicy::Prune {
+            #[cfg(feature = "logging_info")]
+            for name in &unused_types {
+                println!("[CANZERO-CONFIG::build] Pruning unused type {name}");
+            }
+            types.retain(|ty| !unused_types.contains(&ty.name()));
+        }
+
         #[cfg(feature = "logging_info")]
         println!("[CANZERO-CONFIG::build] Successfully build configuration");
         let network_ref = make_config_ref(Network::new(
@@ -1200,13 +2696,25 @@ impl NetworkBuilder {
             set_resp_message,
             heartbeat_message,
             buses,
+            constants,
+            frame_time_model,
         ));
 
         // SEMANTIC CHECKS!
+        let mut diagnostics = self.0.borrow().diagnostics.clone();
+        let external_messages: Vec<String> = self
+            .0
+            .borrow()
+            .messages
+            .borrow()
+            .iter()
+            .filter(|message| message.0.borrow().external)
+            .map(|message| message.0.borrow().name.clone())
+            .collect();
         
         // check that all names are valid c/c++ variables
-        let valid_c_var = Regex::new(r"^[a-zA-Z_]+[a-zA-Z0-9_]*$").unwrap();
-        let is_c_keyword = Regex::new(r"^(restrict|alignas|alignof|and|and_eq|asm|atomic_cancel|atomic_commit|auto|bitand|bitor|bool|break|case|catch|char|char8_t|char16_t|char32_t|class|compl|concept|const|consteval|constexpr|constinit|const_cast|continue|co_await|co_return|co_yield|decltype|default|delete|do|double|dynamic_cast|else|enum|explicit|export|extern|false|float|for|friend|goto|if|inline|int|long|mutable|namespace|new|noexpect|not|not_eq|nullptr|operator|or|or_eq|private|protected|public|reflexpr|register|reinterpret_cast|require|return|short|signed|sizeof|static|static_assert|static_cast|struct|switch|synchronized|template|this|thread_local|throw|true|try|typedef|typeid|typename|union|unsigned|using|virtual|void|volatile|wchar_t|while|xor|xor_eq)$").unwrap();
+        let valid_c_var = Regex::new(VALID_C_VAR).unwrap();
+        let is_c_keyword = Regex::new(C_KEYWORDS).unwrap();
         for node in network_ref.nodes() {
             let node_name = node.name();
             if !valid_c_var.is_match(node_name) || is_c_keyword.is_match(node_name) {
@@ -1239,6 +2747,75 @@ impl NetworkBuilder {
             }
         }
 
+        let naming_rules = self.0.borrow().naming_rules.clone();
+        for violation in naming::check_naming_rules(&naming_rules, &network_ref, &user_defined_names) {
+            diagnostics.emit(DiagnosticKind::NamingConvention, violation);
+        }
+
+        if unused_type_policy == UnusedTypePolicy::Keep {
+            for name in &unused_types {
+                diagnostics.emit(
+                    DiagnosticKind::UnusedType,
+                    format!("type {name} is not used by any message or object entry"),
+                );
+            }
+        }
+
+        // the entries of a stream (and the headroom) have to fit into a single frame.
+        let stream_headroom = self.0.borrow().stream_headroom as usize;
+        for node in network_ref.nodes() {
+            for stream in node.tx_streams() {
+                let message = stream.message();
+                let bits: usize = message
+                    .signals()
+                    .iter()
+                    .map(|signal| signal.size() as usize)
+                    .sum();
+                let bytes = (bits + stream_headroom).div_ceil(8);
+                if bytes > 8 {
+                    let headroom = if stream_headroom == 0 {
+                        String::new()
+                    } else {
+                        format!(" ({stream_headroom} bits of headroom)")
+                    };
+                    diagnostics.emit(
+                        DiagnosticKind::StreamOverflow,
+                        format!(
+                            "stream {}::{} requires {bytes} bytes{headroom}, which is {} bytes more than a frame can carry",
+                            node.name(),
+                            stream.name(),
+                            bytes - 8
+                        ),
+                    );
+                }
+                let received = network_ref
+                    .nodes()
+                    .iter()
+                    .filter(|rx_node| !rx_node.receive_all())
+                    .any(|rx_node| rx_node.rx_messages().iter().any(|m| m.name() == message.name()));
+                if !received {
+                    diagnostics.emit(
+                        DiagnosticKind::StreamWithoutReceivers,
+                        format!(
+                            "stream {}::{} is not received by any node",
+                            node.name(),
+                            stream.name()
+                        ),
+                    );
+                }
+            }
+        }
+        // overflowing streams can't be encoded, stop before the frames are checked.
+        let overflows: Vec<&str> = diagnostics
+            .denied()
+            .iter()
+            .filter(|diagnostic| diagnostic.kind() == DiagnosticKind::StreamOverflow)
+            .map(|diagnostic| diagnostic.message())
+            .collect();
+        if !overflows.is_empty() {
+            return Err(errors::ConfigError::DeniedDiagnostics(overflows.join("\n")));
+        }
+
         for message in network_ref.messages() {
             let dlc = message.dlc();
             let msg_name = message.name();
@@ -1246,14 +2823,305 @@ impl NetworkBuilder {
             if !valid_c_var.is_match(msg_name) || is_c_keyword.is_match(msg_name) {
                 panic!("{msg_name} is not a valid name for a message.");
             }
-            if dlc > 8 {
+            if dlc > 8 && !message.bus().is_xl() {
                 panic!(
                     "All messages have to have a dlc less than 8. \n{msg_name} has dlc = {dlc}."
                 );
             }
 
             if dlc == 0 {
-                println!("WARN: message {msg_name} is empty");
+                diagnostics.emit(
+                    DiagnosticKind::EmptyMessage,
+                    format!("message {msg_name} is empty"),
+                );
+            }
+            if external_messages.iter().any(|name| name == msg_name) {
+                continue;
+            }
+            let received = network_ref
+                .nodes()
+                .iter()
+                .filter(|node| !node.receive_all())
+                .any(|node| node.rx_messages().iter().any(|m| m.name() == msg_name));
+            let transmitted = network_ref
+                .nodes()
+                .iter()
+                .any(|node| node.tx_messages().iter().any(|m| m.name() == msg_name));
+            if !received {
+                diagnostics.emit(
+                    DiagnosticKind::MessageWithoutReceivers,
+                    format!("message {msg_name} is not received by any node"),
+                );
+            } else if !transmitted {
+                diagnostics.emit(
+                    DiagnosticKind::MessageWithoutTransmitter,
+                    format!("message {msg_name} is received but not transmitted by any node"),
+                );
+            }
+        }
+
+        // entries created by the builder and entries that are only meant to be
+        // accessed with get/set requests (constants and parameters) aren't streamed.
+        for node in network_ref.nodes() {
+            let node_builders = builder.nodes.borrow();
+            let node_builder = node_builders
+                .iter()
+                .find(|node_builder| node_builder.0.borrow().name == node.name())
+                .expect("every node is built from a node builder");
+            for object_entry in node.object_entries() {
+                let generated = node_builder
+                    .0
+                    .borrow()
+                    .object_entries
+                    .iter()
+                    .any(|oe| oe.0.borrow().name == object_entry.name() && oe.0.borrow().generated);
+                if generated || !matches!(object_entry.access(), ObjectEntryAccess::Local) {
+                    continue;
+                }
+                let streamed = node.tx_streams().iter().chain(node.rx_streams()).any(|stream| {
+                    stream
+                        .mapping()
+                        .iter()
+                        .flatten()
+                        .any(|oe| oe.node().name() == node.name() && oe.id() == object_entry.id())
+                });
+                if !streamed {
+                    diagnostics.emit(
+                        DiagnosticKind::ObjectEntryNotStreamed,
+                        format!(
+                            "object entry {}::{} is not part of any stream",
+                            node.name(),
+                            object_entry.name()
+                        ),
+                    );
+                }
+            }
+        }
+
+        // entries mapped by streams have to be labelled with the same unit.
+        for node in network_ref.nodes() {
+            for rx_stream in node.rx_streams() {
+                let MessageUsage::Stream(tx_stream) = rx_stream.message().usage() else {
+                    continue;
+                };
+                for (tx_oe, rx_oe) in std::iter::zip(tx_stream.mapping(), rx_stream.mapping()) {
+                    let (Some(tx_oe), Some(rx_oe)) = (tx_oe, rx_oe) else {
+                        continue;
+                    };
+                    let (Some(tx_unit), Some(rx_unit)) = (tx_oe.unit(), rx_oe.unit()) else {
+                        continue;
+                    };
+                    if tx_unit.trim() != rx_unit.trim() {
+                        diagnostics.emit(
+                            DiagnosticKind::UnitMismatch,
+                            format!(
+                                "stream {}::{} maps {} [{tx_unit}] to {}::{} [{rx_unit}]",
+                                tx_oe.node().name(),
+                                tx_stream.name(),
+                                tx_oe.name(),
+                                node.name(),
+                                rx_oe.name()
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+
+        // overloaded buses fail with a capacity error if bus load diagnostics are denied.
+        let overloaded: Vec<(String, f64)> = network_ref
+            .buses()
+            .iter()
+            .map(|bus| (bus.name().to_owned(), network_ref.bus_load(bus)))
+            .filter(|(_, load)| *load > 1.0)
+            .collect();
+        if !overloaded.is_empty() && diagnostics.severity(DiagnosticKind::BusLoad) == Severity::Deny {
+            return Err(errors::ConfigError::Capacity(errors::CapacityError::BusLoad(overloaded)));
+        }
+        for bus in network_ref.buses() {
+            let load = network_ref.bus_load(bus);
+            if load > 1.0 {
+                let overload = errors::CapacityError::BusLoad(vec![(bus.name().to_owned(), load)]);
+                diagnostics.emit(DiagnosticKind::BusLoad, overload.to_string());
+            } else if load > BUS_LOAD_WARNING_THRESHOLD {
+                diagnostics.emit(
+                    DiagnosticKind::BusLoad,
+                    format!(
+                        "expected load of bus {} is {:.1}% (more than {:.0}%)",
+                        bus.name(),
+                        load * 100.0,
+                        BUS_LOAD_WARNING_THRESHOLD * 100.0
+                    ),
+                );
+            }
+        }
+
+        for quota in &self.0.borrow().bus_quotas {
+            let load = bus_quota::tag_load(
+                quota,
+                network_ref.messages(),
+                &self.0.borrow().messages.borrow(),
+                &frame_time_model,
+            );
+            if load > quota.max_load {
+                diagnostics.emit(
+                    DiagnosticKind::BusQuota,
+                    format!(
+                        "expected load of {} on bus {} is {:.1}% (quota {:.0}%)",
+                        quota.tag,
+                        quota.bus,
+                        load * 100.0,
+                        quota.max_load * 100.0
+                    ),
+                );
+            }
+        }
+
+        // a response with a lower priority than its request can be
+        // delayed by the traffic on the bus until the caller times out.
+        for node in network_ref.nodes() {
+            for command in node.commands() {
+                let Some(timeout) = command.timeout() else {
+                    continue;
+                };
+                let req = command.tx_message();
+                let resp = command.rx_message();
+                let round_trip = simulator::worst_case_response_time(&network_ref, req)
+                    .zip(simulator::worst_case_response_time(&network_ref, resp))
+                    .map(|(req_time, resp_time)| req_time + resp_time);
+                let round_trip = match round_trip {
+                    Some(round_trip) if round_trip <= timeout => continue,
+                    Some(round_trip) => format!("{round_trip:?}"),
+                    None => "unbounded".to_owned(),
+                };
+                let inversion = if resp.id().as_u32() > req.id().as_u32() {
+                    format!(
+                        ", the response ({}) has a lower priority than the request ({})",
+                        resp.id(),
+                        req.id()
+                    )
+                } else {
+                    String::new()
+                };
+                diagnostics.emit(
+                    DiagnosticKind::CommandTimeout,
+                    format!(
+                        "worst-case round trip of command {}::{} is {round_trip} (timeout {timeout:?}){inversion}",
+                        node.name(),
+                        command.name()
+                    ),
+                );
+            }
+        }
+
+        for message in network_ref.messages() {
+            let Some(deadline) = message.deadline() else {
+                continue;
+            };
+            match simulator::worst_case_response_time(&network_ref, message) {
+                Some(response_time) if response_time <= *deadline => (),
+                response_time => diagnostics.emit(
+                    DiagnosticKind::DeadlineMiss,
+                    format!(
+                        "worst-case response time of {} is {} (deadline {deadline:?})",
+                        message.name(),
+                        response_time.map_or("unbounded".to_owned(), |time| format!("{time:?}"))
+                    ),
+                ),
+            }
+        }
+
+        for problem in bus_pin_problems {
+            diagnostics.emit(DiagnosticKind::BusPin, problem);
+        }
+        for problem in hidden_message_wiring_problems {
+            diagnostics.emit(DiagnosticKind::HiddenMessageWiring, problem);
+        }
+
+        // the resolver may move messages into other buckets, which must not
+        // change the arbitration order of the requested priorities.
+        for violation in assignment::network_priority_inversions(&network_ref) {
+            if let AssignmentViolation::PriorityInversion { bus, higher, lower } = violation {
+                let id = |name: &str| *network_ref.messages().iter().find(|m| m.name() == name).unwrap().id();
+                let bus = network_ref.buses().iter().find(|b| b.id() == bus).unwrap();
+                diagnostics.emit(
+                    DiagnosticKind::PriorityInversion,
+                    format!(
+                        "{higher} ({}) requested a higher priority than {lower} ({}), but loses the arbitration on bus {}",
+                        id(&higher),
+                        id(&lower),
+                        bus.name()
+                    ),
+                );
+            }
+        }
+
+        // a pipelining caller may have max_in_flight requests pending, which
+        // the callee answers back to back. The responses have to be sent
+        // within the timeout (or the interval) of the command, otherwise the
+        // caller drops them.
+        for node in network_ref.nodes() {
+            for command in node.commands().iter().filter(|command| command.is_pipelined()) {
+                let window = command.timeout().unwrap_or(*command.expected_interval());
+                let resp = command.rx_message();
+                let response_time =
+                    simulator::worst_case_burst_response_time(&network_ref, resp, command.max_in_flight());
+                let response_time = match response_time {
+                    Some(response_time) if response_time <= window => continue,
+                    Some(response_time) => format!("{response_time:?}"),
+                    None => "unbounded".to_owned(),
+                };
+                diagnostics.emit(
+                    DiagnosticKind::CommandPipelining,
+                    format!(
+                        "{} pipelined responses of command {}::{} ({}) take {response_time} (window {window:?})",
+                        command.max_in_flight(),
+                        node.name(),
+                        command.name(),
+                        resp.id()
+                    ),
+                );
+            }
+        }
+
+        // messages with a fallback bus keep their id on the fallback bus,
+        // which requires that the id is not used on the fallback bus.
+        for message in network_ref.messages() {
+            let Some(fallback_bus) = message.fallback_bus() else {
+                continue;
+            };
+            let msg_name = message.name();
+            if fallback_bus.id() == message.bus().id() {
+                return Err(errors::ConfigError::InvalidFallbackBus(format!(
+                    "fallback bus of {msg_name} is the same as the primary bus {}",
+                    fallback_bus.name()
+                )));
+            }
+            let collision = network_ref.messages().iter().find(|other| {
+                other.name() != msg_name
+                    && other.id() == message.id()
+                    && (other.bus().id() == fallback_bus.id()
+                        || other.fallback_bus().map(|b| b.id()) == Some(fallback_bus.id()))
+            });
+            if let Some(other) = collision {
+                return Err(errors::ConfigError::InvalidFallbackBus(format!(
+                    "{msg_name} can't fall back to {} because {} uses the same id ({})",
+                    fallback_bus.name(),
+                    other.name(),
+                    message.id()
+                )));
+            }
+        }
+
+        let semver = Regex::new(r"^[0-9]+\.[0-9]+\.[0-9]+$").unwrap();
+        for message in network_ref.messages() {
+            if let Some(version) = message.version() {
+                if !semver.is_match(version) {
+                    return Err(errors::ConfigError::InvalidVersion(format!(
+                        "version {version} of {} is not of the form major.minor.patch",
+                        message.name()
+                    )));
+                }
             }
         }
 
@@ -1265,6 +3133,7 @@ impl NetworkBuilder {
                     description : _,
                     attribs,
                     visibility : _,
+                    annotations : _,
                 } => {
                     if !valid_c_var.is_match(name) || c_keyword.is_match(name) {
                         panic!("{name} is not a valid name for a struct");
@@ -1282,6 +3151,7 @@ impl NetworkBuilder {
                     size : _,
                     entries : _,
                     visibility : _,
+                    annotations : _,
                 } => {
                     if !valid_c_var.is_match(name) || c_keyword.is_match(name) {
                         panic!("{name} is not a valid name for a struct");
@@ -1295,6 +3165,98 @@ impl NetworkBuilder {
             check_ty(ty, &valid_c_var, &is_c_keyword);
         }
 
+        #[cfg(feature = "logging_info")]
+        for diagnostic in diagnostics.diagnostics() {
+            if diagnostic.severity() == Severity::Warn {
+                println!("{diagnostic}");
+            }
+        }
+        let denied = diagnostics.denied();
+        if !denied.is_empty() {
+            return Err(errors::ConfigError::DeniedDiagnostics(
+                denied
+                    .iter()
+                    .map(|diagnostic| diagnostic.message())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ));
+        }
+        network_ref.__set_diagnostics(diagnostics.diagnostics().clone());
+        if let Some((master, port)) = &self.0.borrow().mirror_target {
+            let mut streams = vec![];
+            for node_builder in self.0.borrow().nodes.borrow().iter() {
+                let node_data = node_builder.0.borrow();
+                let node = network_ref
+                    .nodes()
+                    .iter()
+                    .find(|node| node.name() == node_data.name)
+                    .expect("node was not added to the network");
+                for stream_builder in &node_data.tx_streams {
+                    let stream_data = stream_builder.0.borrow();
+                    if !stream_data.mirrored {
+                        continue;
+                    }
+                    let stream = node
+                        .tx_streams()
+                        .iter()
+                        .find(|stream| stream.name() == stream_data.name)
+                        .expect("stream was not added to the node");
+                    streams.push((node_data.name.clone(), stream.clone()));
+                }
+            }
+            network_ref.__set_mirror(MirrorTarget::new(master.clone(), *port, streams));
+        }
+        network_ref.__set_time_epoch(self.0.borrow().time_epoch);
+        for bus in network_ref.buses() {
+            bus.__set_id_utilization(config::id_utilization::id_regions(
+                id_layout.setcode_len,
+                &id_layout.bucket_sizes,
+                bus,
+                network_ref.messages(),
+            ));
+        }
+        let network_data = self.0.borrow();
+        let or_off = |value: Option<String>| value.unwrap_or_else(|| "off".to_owned());
+        let resolver = format!(
+            "{:?}, receiver set folding {}, seed {}",
+            network_data.bus_balancer,
+            or_off(network_data.receiver_set_folding.map(|cost| cost.to_string())),
+            or_off(network_data.resolution_seed.map(|seed| seed.to_string())),
+        );
+        network_ref.__set_provenance(Provenance::new(
+            network_data.source_revision.clone(),
+            network_data.input_files.clone(),
+            resolver,
+        ));
+        drop(network_data);
+        network_ref.__set_masters(
+            self.0
+                .borrow()
+                .masters
+                .iter()
+                .map(|master| {
+                    network_ref
+                        .nodes()
+                        .iter()
+                        .find(|node| node.name() == master)
+                        .expect("master was not added to the network")
+                        .clone()
+                })
+                .collect(),
+        );
+        network_ref.__set_lin_buses(
+            lin_buses
+                .iter()
+                .map(|lin_bus| lin_bus::build_lin_bus(lin_bus, network_ref.messages()))
+                .collect(),
+        );
+        network_ref.__set_time_triggered_schedules(
+            time_triggered_schedules
+                .iter()
+                .map(|schedule| time_triggered::build_schedule(schedule, &network_ref))
+                .collect::<errors::Result<Vec<_>>>()?,
+        );
+
         Ok(network_ref)
     }
 }
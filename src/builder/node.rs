@@ -1,13 +1,13 @@
-use std::time::Duration;
+use std::{ops::Range, rc::Rc, time::Duration};
 
-use crate::config::ObjectEntryAccess;
+use crate::{config::{Annotations, FaultSeverity, ObjectEntryAccess}, errors};
 
-use super::{stream_builder::{ReceiveStreamBuilder, StreamBuilder}, ObjectEntryBuilder, MessageBuilder, NetworkBuilder, CommandBuilder, BuilderRef, MessagePriority, make_builder_ref, bus::BusBuilder};
+use super::{snapshot_stream::SnapshotStreamBuilder, stream_builder::{ReceiveStreamBuilder, StreamBuilder}, ObjectEntryBuilder, MessageBuilder, NetworkBuilder, CommandBuilder, BuilderRef, MessagePriority, make_builder_ref, bus::BusBuilder};
 
 
 #[derive(Debug, Clone)]
 pub struct NodeBuilder(pub BuilderRef<NodeData>);
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NodeData {
     pub name: String,
     pub description: Option<String>,
@@ -20,6 +20,19 @@ pub struct NodeData {
     pub tx_streams: Vec<StreamBuilder>,
     pub rx_streams: Vec<ReceiveStreamBuilder>,
     pub buses : Vec<BusBuilder>,
+    pub fallback_bus : Option<BusBuilder>,
+    // (name, severity)
+    pub faults : Vec<(String, FaultSeverity)>,
+    // receives every message on its buses (e.g. a data logger).
+    pub receive_all : bool,
+    // buses the node never transmits on (e.g. a passive logger).
+    pub listen_only : Vec<String>,
+    // (node, timeout) of explicit heartbeat supervisions.
+    pub supervisions : Vec<(String, Option<Duration>)>,
+    pub snapshot_stream : Option<SnapshotStreamBuilder>,
+    // names of the commands, which the master calls in order on startup.
+    pub init_sequence : Vec<String>,
+    pub annotations : Annotations,
 }
 
 
@@ -39,6 +52,14 @@ impl NodeBuilder {
             tx_streams: vec![],
             rx_streams: vec![],
             buses : vec![],
+            fallback_bus : None,
+            faults : vec![],
+            receive_all : false,
+            listen_only : vec![],
+            supervisions : vec![],
+            snapshot_stream : None,
+            init_sequence : vec![],
+            annotations : Annotations::new(),
         }));
         node_builder.add_rx_message(&network_builder._get_req_message());
         node_builder.add_tx_message(&network_builder._get_resp_message());
@@ -48,10 +69,12 @@ impl NodeBuilder {
         let config_hash = node_builder.create_object_entry("config_hash", "u64");
         config_hash.add_description("Hash of the local network configuration");
         config_hash.set_access(ObjectEntryAccess::Const);
+        config_hash.mark_generated();
 
         let build_time = node_builder.create_object_entry("build_time", "date_time");
         build_time.add_description("Time the node was last build from source");
         build_time.set_access(ObjectEntryAccess::Const);
+        build_time.mark_generated();
 
         node_builder
     }
@@ -72,10 +95,77 @@ impl NodeBuilder {
             }
         }
     }
+    // messages that are only transmitted by this node inherit the fallback bus
+    // unless they define their own.
+    pub fn set_fallback_bus(&self, bus_name : &str) -> BusBuilder {
+        let mut node_data = self.0.borrow_mut();
+        let network_data = node_data.network_builder.0.borrow_mut();
+        let bus = network_data.buses.borrow().iter().find(|bus| bus.0.borrow().name == bus_name).cloned();
+        drop(network_data);
+        let bus = match bus {
+            Some(bus) => bus,
+            None => node_data.network_builder.create_bus(bus_name, None),
+        };
+        node_data.fallback_bus = Some(bus.clone());
+        bus
+    }
     pub fn add_description(&self, description: &str) {
         let mut node_data = self.0.borrow_mut();
         node_data.description = Some(description.to_owned());
     }
+    /// key value metadata for downstream tools, exported to json and dbc.
+    pub fn annotate(&self, key: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .annotations
+            .insert(key.to_owned(), value.to_owned());
+    }
+    /// the node receives every message on its buses (e.g. a data logger)
+    /// with an open filter. It is not part of any receiver set, such that
+    /// it doesn't increase the number of receiver sets or filters of the
+    /// other nodes.
+    pub fn receive_all(&self) {
+        self.0.borrow_mut().receive_all = true;
+    }
+    /// the node never transmits on the bus (e.g. a passive logger or a
+    /// debugging probe). A node that is listen only on all of its buses
+    /// doesn't send heartbeats and doesn't answer get and set requests.
+    /// Messages transmitted by the node on the bus are rejected by the build.
+    pub fn set_listen_only(&self, bus_name : &str) -> BusBuilder {
+        let assigned = self.0.borrow().buses.iter().find(|bus| bus.0.borrow().name == bus_name).cloned();
+        let bus = match assigned {
+            Some(bus) => bus,
+            None => self.assign_bus(bus_name),
+        };
+        let mut node_data = self.0.borrow_mut();
+        if !node_data.listen_only.iter().any(|name| name == bus_name) {
+            node_data.listen_only.push(bus_name.to_owned());
+        }
+        bus
+    }
+    // listen only on all buses of the node, such a node never transmits.
+    pub(crate) fn is_passive(&self) -> bool {
+        let node_data = self.0.borrow();
+        !node_data.listen_only.is_empty()
+            && node_data
+                .buses
+                .iter()
+                .all(|bus| node_data.listen_only.contains(&bus.0.borrow().name))
+    }
+    /// the node monitors the heartbeat of the other node, even if it doesn't
+    /// receive any of its messages. None uses the timeout of the network
+    /// (NetworkBuilder::set_heartbeat_timeout).
+    pub fn supervise(&self, node_name : &str, timeout : Option<Duration>) {
+        let mut node_data = self.0.borrow_mut();
+        node_data.supervisions.retain(|(name, _)| name != node_name);
+        node_data.supervisions.push((node_name.to_owned(), timeout));
+    }
+    /// commands of the node, which the master calls in the given order
+    /// to initialize the node (e.g. configure_filters then enable_output).
+    /// Replaces a previous sequence, only commands without arguments can be part of it.
+    pub fn init_sequence(&self, commands : &[&str]) {
+        self.0.borrow_mut().init_sequence = commands.iter().map(|command| (*command).to_owned()).collect();
+    }
     pub fn add_tx_message(&self, message_builder: &MessageBuilder) {
         let node_name = self.0.borrow().name.clone();
         if !message_builder.0.borrow().transmitters.iter().any(|n| &n.0.borrow().name == &node_name) {
@@ -128,6 +218,35 @@ impl NodeBuilder {
         node_data.object_entries.push(object_entry_builder.clone());
         object_entry_builder
     }
+    /// creates an object entry for every i in range, whose name is the
+    /// pattern with {} replaced by i, e.g. ("cell_voltage_{}", 0..96, "d16<0..5>").
+    pub fn create_object_entries(&self, pattern: &str, range: Range<usize>, ty: &str) -> Vec<ObjectEntryBuilder> {
+        expand_pattern(pattern, range)
+            .iter()
+            .map(|name| self.create_object_entry(name, ty))
+            .collect()
+    }
+    /// defines a fault of the node. All faults of the network get a
+    /// unique code (fault_code enum) and every node with faults streams
+    /// their state (fault_status) and can be asked to clear them (clear_fault).
+    pub fn define_fault(&self, name: &str, severity: FaultSeverity) -> errors::Result<()> {
+        let mut node_data = self.0.borrow_mut();
+        if node_data.faults.iter().any(|(fault, _)| fault == name) {
+            return Err(errors::ConfigError::DuplicatedFault(format!(
+                "fault {}::{name} is already defined",
+                node_data.name
+            )));
+        }
+        node_data.faults.push((name.to_owned(), severity));
+        Ok(())
+    }
+    pub(crate) fn owns_object_entry(&self, object_entry: &ObjectEntryBuilder) -> bool {
+        self.0
+            .borrow()
+            .object_entries
+            .iter()
+            .any(|oe| Rc::ptr_eq(&oe.0, &object_entry.0))
+    }
     pub fn create_stream(&self, name: &str) -> StreamBuilder {
         #[cfg(feature = "logging_info")]
         println!("[CANZERO-CONFIG::construct] Require Stream {}::{name}", self.0.borrow().name);
@@ -141,14 +260,63 @@ impl NodeBuilder {
         stream_builder
     }
 
-    pub fn receive_stream(&self, tx_node_name: &str, tx_stream_name: &str) -> ReceiveStreamBuilder {
+    /// logs the object entries round-robin with a single low priority
+    /// message ({node}_snapshot), one entry every interval. A node has at
+    /// most one snapshot stream, further calls add entries to it and
+    /// replace the interval.
+    pub fn create_snapshot_stream(&self, entries: &[&str], interval: Duration) -> SnapshotStreamBuilder {
+        let existing = self.0.borrow().snapshot_stream.clone();
+        let snapshot_stream = match existing {
+            Some(snapshot_stream) => {
+                snapshot_stream.set_interval(interval);
+                snapshot_stream
+            }
+            None => {
+                let snapshot_stream = SnapshotStreamBuilder::new(self, interval);
+                self.0.borrow_mut().snapshot_stream = Some(snapshot_stream.clone());
+                snapshot_stream
+            }
+        };
+        for entry in entries {
+            snapshot_stream.add_entry(entry);
+        }
+        snapshot_stream
+    }
+
+    /// receives a tx stream of another node. The tx node and stream are created if
+    /// they don't exist yet, such that they can be defined later on.
+    pub fn receive_stream(
+        &self,
+        tx_node_name: &str,
+        tx_stream_name: &str,
+    ) -> errors::Result<ReceiveStreamBuilder> {
+        self.receive_stream_impl(tx_node_name, tx_stream_name, false)
+    }
+    /// like receive_stream, but fails if the tx node or stream are not defined yet.
+    pub fn receive_stream_strict(
+        &self,
+        tx_node_name: &str,
+        tx_stream_name: &str,
+    ) -> errors::Result<ReceiveStreamBuilder> {
+        self.receive_stream_impl(tx_node_name, tx_stream_name, true)
+    }
+    fn receive_stream_impl(
+        &self,
+        tx_node_name: &str,
+        tx_stream_name: &str,
+        strict: bool,
+    ) -> errors::Result<ReceiveStreamBuilder> {
         let node_data = self.0.borrow();
+        let rx_node_name = node_data.name.clone();
+        let network_builder = node_data.network_builder.clone();
+        drop(node_data);
         #[cfg(feature = "logging_info")]
-        println!("[CANZERO-CONFIG::construct] Creating dependencies of receive stream {tx_node_name}::{tx_stream_name} -> {}", node_data.name);
-        if tx_node_name == node_data.name {
-            panic!("can't receive local stream");
+        println!("[CANZERO-CONFIG::construct] Creating dependencies of receive stream {tx_node_name}::{tx_stream_name} -> {rx_node_name}");
+        if tx_node_name == rx_node_name {
+            return Err(errors::ConfigError::InvalidReceiveStream(format!(
+                "{rx_node_name} can't receive its own stream {tx_stream_name}"
+            )));
         }
-        let network_builder = &node_data.network_builder;
         let tx_node_opt = network_builder
             .0
             .borrow()
@@ -159,6 +327,11 @@ impl NodeBuilder {
             .cloned();
         let tx_node = match tx_node_opt {
             Some(tx_node) => tx_node,
+            None if strict => {
+                return Err(errors::ConfigError::InvalidReceiveStream(format!(
+                    "{rx_node_name} receives {tx_node_name}::{tx_stream_name}, but there is no node {tx_node_name}"
+                )))
+            }
             None => network_builder.create_node(tx_node_name),
         };
         let tx_node_data = tx_node.0.borrow();
@@ -167,10 +340,14 @@ impl NodeBuilder {
             .iter()
             .find(|s| s.0.borrow().name == tx_stream_name)
             .cloned();
-        drop(node_data);
         drop(tx_node_data);
         let tx_stream = match tx_stream_opt {
             Some(tx_stream) => tx_stream,
+            None if strict => {
+                return Err(errors::ConfigError::InvalidReceiveStream(format!(
+                    "{rx_node_name} receives {tx_node_name}::{tx_stream_name}, but {tx_node_name} has no stream {tx_stream_name}"
+                )))
+            }
             None => tx_node.create_stream(tx_stream_name),
         };
 
@@ -183,6 +360,14 @@ impl NodeBuilder {
         node_data.rx_streams.push(rx_stream_builder.clone());
 
 
-        rx_stream_builder
+        Ok(rx_stream_builder)
     }
 }
+
+pub(crate) fn expand_pattern(pattern: &str, range: Range<usize>) -> Vec<String> {
+    assert!(
+        pattern.contains("{}"),
+        "pattern {pattern:?} doesn't contain a placeholder {{}}"
+    );
+    range.map(|i| pattern.replace("{}", &i.to_string())).collect()
+}
@@ -1,11 +1,11 @@
-use crate::config::{ObjectEntryAccess, Visibility};
+use crate::config::{AggregationPolicy, ObjectEntryAccess, Visibility};
 
 use super::{make_builder_ref, BuilderRef};
 
 
 #[derive(Debug, Clone)]
 pub struct ObjectEntryBuilder(pub BuilderRef<ObjectEntryData>);
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ObjectEntryData {
     pub name: String,
     pub description: Option<String>,
@@ -13,6 +13,19 @@ pub struct ObjectEntryData {
     pub ty: String,
     pub access: ObjectEntryAccess,
     pub visibility: Visibility,
+    // (min, max), either literals or names of network constants.
+    pub bounds: Option<(String, String)>,
+    // value has to survive a reboot (stored in NVM).
+    pub persistent: bool,
+    // (factor, offset, unit) of the displayed value.
+    pub display: Option<(f64, f64, String)>,
+    pub aggregation: Option<AggregationPolicy>,
+    // (tx node, stream, tx entry) of every receive stream mapping into this entry.
+    pub sources: Vec<(String, String, String)>,
+    // (response message, index bits, elements per frame), see builder::chunked_read.
+    pub chunked_read: Option<(String, u8, usize)>,
+    // created by the builder itself (e.g. config_hash), not by the user.
+    pub generated: bool,
 }
 
 
@@ -27,6 +40,13 @@ impl ObjectEntryBuilder {
             unit: None,
             access: ObjectEntryAccess::Global,
             visibility: Visibility::Global,
+            bounds: None,
+            persistent: false,
+            display: None,
+            aggregation: None,
+            sources: vec![],
+            chunked_read: None,
+            generated: false,
         }))
     }
     pub fn hide(&self) {
@@ -41,9 +61,41 @@ impl ObjectEntryBuilder {
         let mut data = self.0.borrow_mut();
         data.access = access;
     }
+    pub(crate) fn mark_generated(&self) {
+        self.0.borrow_mut().generated = true;
+    }
     pub fn add_unit(&self, unit: &str) {
         let mut data = self.0.borrow_mut();
         data.unit = Some(unit.to_owned());
     }
+    /// min and max are either numbers or names of constants defined
+    /// with NetworkBuilder::define_constant.
+    pub fn set_bounds(&self, min: &str, max: &str) {
+        let mut data = self.0.borrow_mut();
+        data.bounds = Some((min.to_owned(), max.to_owned()));
+    }
+    /// marks the entry as a parameter, whose value is stored
+    /// in non volatile memory and survives a reboot of the node.
+    pub fn persistent(&self) {
+        let mut data = self.0.borrow_mut();
+        data.persistent = true;
+    }
+    /// allows mapping the entry from several receive streams (e.g. redundant
+    /// sensors), the policy arbitrates between them. The priority of a source
+    /// is the order of the map calls, the first mapping has the highest.
+    pub fn aggregate(&self, policy: AggregationPolicy) {
+        self.0.borrow_mut().aggregation = Some(policy);
+    }
+    /// the value is displayed as value * factor in the given unit (e.g.
+    /// set_display(1.0 / 3.6, "m/s") for a value in km/h). The encoding
+    /// on the wire and the unit of the entry are not affected.
+    pub fn set_display(&self, factor: f64, unit: &str) {
+        self.set_display_with_offset(factor, 0.0, unit);
+    }
+    /// the value is displayed as value * factor + offset in the given unit.
+    pub fn set_display_with_offset(&self, factor: f64, offset: f64, unit: &str) {
+        let mut data = self.0.borrow_mut();
+        data.display = Some((factor, offset, unit.to_owned()));
+    }
 }
 
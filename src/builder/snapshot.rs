@@ -0,0 +1,263 @@
+use std::{collections::BTreeSet, rc::Rc};
+
+use super::{
+    lin_bus::LinBusBuilder,
+    stream_builder::{ReceiveStreamBuilder, StreamBuilder},
+    BuilderRef, CommandBuilder, MessageBuilder, MessageFormat, NetworkBuilder, NodeBuilder,
+    TypeBuilder,
+};
+
+// A snapshot saves the data of every builder, that is reachable from the
+// network builder. Builders are shared handles, therefore restoring writes
+// the saved data back into the same handles: handles obtained before the
+// snapshot stay valid, builders created afterwards are no longer part of
+// the network.
+
+trait SavedBuilder {
+    fn restore(&self);
+}
+
+struct Saved<T: Clone> {
+    builder: BuilderRef<T>,
+    data: T,
+}
+
+impl<T: Clone> SavedBuilder for Saved<T> {
+    fn restore(&self) {
+        *self.builder.borrow_mut() = self.data.clone();
+    }
+}
+
+/// state of a NetworkBuilder, see NetworkBuilder::snapshot.
+pub struct NetworkSnapshot {
+    network: *const (),
+    saved: Vec<Box<dyn SavedBuilder>>,
+}
+
+impl std::fmt::Debug for NetworkSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NetworkSnapshot")
+            .field("builders", &self.saved.len())
+            .finish()
+    }
+}
+
+impl NetworkSnapshot {
+    /// number of saved builders.
+    pub fn len(&self) -> usize {
+        self.saved.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.saved.is_empty()
+    }
+}
+
+struct Collector {
+    visited: BTreeSet<*const ()>,
+    saved: Vec<Box<dyn SavedBuilder>>,
+}
+
+impl Collector {
+    /// false if the builder was saved before.
+    fn save<T: Clone + 'static>(&mut self, builder: &BuilderRef<T>) -> bool {
+        if !self.visited.insert(Rc::as_ptr(builder) as *const ()) {
+            return false;
+        }
+        self.saved.push(Box::new(Saved {
+            builder: builder.clone(),
+            data: builder.borrow().clone(),
+        }));
+        true
+    }
+
+    fn network(&mut self, network_builder: &NetworkBuilder) {
+        if !self.save(&network_builder.0) {
+            return;
+        }
+        let network_data = network_builder.0.borrow();
+        self.save(&network_data.messages);
+        self.save(&network_data.types);
+        self.save(&network_data.nodes);
+        self.save(&network_data.buses);
+        for message in network_data.messages.borrow().iter() {
+            self.message(message);
+        }
+        for message in [
+            &network_data.get_req_message,
+            &network_data.get_resp_message,
+            &network_data.set_req_message,
+            &network_data.set_resp_message,
+        ]
+        .into_iter()
+        .flat_map(|message| message.get())
+        {
+            self.message(message);
+        }
+        for ty in network_data.types.borrow().iter() {
+            match ty {
+                TypeBuilder::Enum(enum_builder) => self.save(&enum_builder.0),
+                TypeBuilder::Struct(struct_builder) => self.save(&struct_builder.0),
+            };
+        }
+        for node in network_data.nodes.borrow().iter() {
+            self.node(node);
+        }
+        for bus in network_data.buses.borrow().iter() {
+            self.save(&bus.0);
+        }
+        for lin_bus in &network_data.lin_buses {
+            self.lin_bus(lin_bus);
+        }
+        for schedule in &network_data.time_triggered_schedules {
+            if self.save(&schedule.0) {
+                for message in &schedule.0.borrow().messages {
+                    self.message(message);
+                }
+            }
+        }
+    }
+
+    fn message(&mut self, message: &MessageBuilder) {
+        if !self.save(&message.0) {
+            return;
+        }
+        let message_data = message.0.borrow();
+        match &message_data.format {
+            MessageFormat::Signals(format) => {
+                self.save(&format.0);
+            }
+            MessageFormat::Types(format) => {
+                self.save(&format.0);
+            }
+            MessageFormat::Empty => (),
+        }
+        for node in message_data.receivers.iter().chain(&message_data.transmitters) {
+            self.node(node);
+        }
+        for bus in message_data.bus.iter().chain(&message_data.fallback_bus) {
+            self.save(&bus.0);
+        }
+        match &message_data.usage {
+            super::message_builder::MessageBuilderUsage::Stream(stream) => self.stream(stream),
+            super::message_builder::MessageBuilderUsage::CommandReq(command)
+            | super::message_builder::MessageBuilderUsage::CommandResp(command) => {
+                self.command(command)
+            }
+            _ => (),
+        }
+    }
+
+    fn node(&mut self, node: &NodeBuilder) {
+        if !self.save(&node.0) {
+            return;
+        }
+        let node_data = node.0.borrow();
+        for command in node_data.commands.iter().chain(&node_data.extern_commands) {
+            self.command(command);
+        }
+        for message in node_data.rx_messages.iter().chain(&node_data.tx_messages) {
+            self.message(message);
+        }
+        for object_entry in &node_data.object_entries {
+            self.save(&object_entry.0);
+        }
+        for stream in &node_data.tx_streams {
+            self.stream(stream);
+        }
+        for stream in &node_data.rx_streams {
+            self.receive_stream(stream);
+        }
+        for bus in node_data.buses.iter().chain(&node_data.fallback_bus) {
+            self.save(&bus.0);
+        }
+        if let Some(snapshot_stream) = &node_data.snapshot_stream {
+            if self.save(&snapshot_stream.0) {
+                self.message(&snapshot_stream.0.borrow().message);
+            }
+        }
+    }
+
+    fn stream(&mut self, stream: &StreamBuilder) {
+        if !self.save(&stream.0) {
+            return;
+        }
+        let stream_data = stream.0.borrow();
+        self.save(&stream_data.format.0);
+        for object_entry in &stream_data.object_entries {
+            self.save(&object_entry.0);
+        }
+        self.message(&stream_data.message);
+        self.node(&stream_data.tx_node);
+    }
+
+    fn receive_stream(&mut self, stream: &ReceiveStreamBuilder) {
+        if !self.save(&stream.0) {
+            return;
+        }
+        let stream_data = stream.0.borrow();
+        for (_, object_entry) in &stream_data.object_entries {
+            self.save(&object_entry.0);
+        }
+        self.stream(&stream_data.stream_builder);
+        self.node(&stream_data.rx_node);
+    }
+
+    fn command(&mut self, command: &CommandBuilder) {
+        if !self.save(&command.0) {
+            return;
+        }
+        let command_data = command.0.borrow();
+        self.save(&command_data.call_message_format.0);
+        self.message(&command_data.call_message);
+        self.message(&command_data.resp_message);
+        self.node(&command_data.tx_node);
+    }
+
+    fn lin_bus(&mut self, lin_bus: &LinBusBuilder) {
+        if !self.save(&lin_bus.0) {
+            return;
+        }
+        let lin_bus_data = lin_bus.0.borrow();
+        for frame in &lin_bus_data.frames {
+            if self.save(&frame.0) {
+                let frame_data = frame.0.borrow();
+                self.save(&frame_data.format.0);
+                if let Some(message) = &frame_data.gateway_message {
+                    self.message(message);
+                }
+            }
+        }
+        for schedule_table in &lin_bus_data.schedule_tables {
+            self.save(&schedule_table.0);
+        }
+    }
+}
+
+impl NetworkBuilder {
+    /// saves the state of the network builder and all builders it contains,
+    /// e.g. to implement undo in an interactive editor. See restore.
+    pub fn snapshot(&self) -> NetworkSnapshot {
+        let mut collector = Collector {
+            visited: BTreeSet::new(),
+            saved: vec![],
+        };
+        collector.network(self);
+        NetworkSnapshot {
+            network: Rc::as_ptr(&self.0) as *const (),
+            saved: collector.saved,
+        }
+    }
+    /// resets the network builder to the state of the snapshot. Handles to
+    /// builders that existed at the time of the snapshot stay valid, builders
+    /// created afterwards are removed from the network. A snapshot can be
+    /// restored any number of times, but only into the network it was taken of.
+    pub fn restore(&self, snapshot: &NetworkSnapshot) {
+        assert!(
+            std::ptr::eq(Rc::as_ptr(&self.0) as *const (), snapshot.network),
+            "the snapshot was taken of another network builder"
+        );
+        for saved in &snapshot.saved {
+            saved.restore();
+        }
+    }
+}
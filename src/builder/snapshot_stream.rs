@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use crate::{
+    config::{snapshot_stream::SnapshotStream, MessageRef, NodeRef},
+    errors::{self, Result},
+};
+
+use super::{make_builder_ref, message_builder::MessageBuilderUsage, BuilderRef, MessageBuilder, MessagePriority, NodeBuilder};
+
+// A snapshot stream logs many slow changing object entries with the
+// bandwidth of a single low priority message. Every frame carries the index
+// of one entry followed by its raw value, the entries are sent round-robin.
+
+#[derive(Debug, Clone)]
+pub struct SnapshotStreamBuilder(pub BuilderRef<SnapshotStreamData>);
+#[derive(Debug, Clone)]
+pub struct SnapshotStreamData {
+    pub tx_node: NodeBuilder,
+    pub message: MessageBuilder,
+    // names of the object entries in the order of the rotation.
+    pub entries: Vec<String>,
+    // time between two frames.
+    pub interval: Duration,
+}
+
+impl SnapshotStreamBuilder {
+    pub(crate) fn new(tx_node: &NodeBuilder, interval: Duration) -> SnapshotStreamBuilder {
+        let node_data = tx_node.0.borrow();
+        let message = node_data
+            .network_builder
+            .create_message_with_interval(&format!("{}_snapshot", node_data.name), interval);
+        drop(node_data);
+        tx_node.add_tx_message(&message);
+        message.hide();
+        message.set_any_std_id(MessagePriority::Low);
+        SnapshotStreamBuilder(make_builder_ref(SnapshotStreamData {
+            tx_node: tx_node.clone(),
+            message,
+            entries: vec![],
+            interval,
+        }))
+    }
+    /// appends the object entry to the rotation, adding an entry
+    /// twice has no effect.
+    pub fn add_entry(&self, name: &str) {
+        let mut snapshot_data = self.0.borrow_mut();
+        if !snapshot_data.entries.iter().any(|entry| entry == name) {
+            snapshot_data.entries.push(name.to_owned());
+        }
+    }
+    /// time between two frames, every entry is sent once
+    /// every interval * number of entries.
+    pub fn set_interval(&self, interval: Duration) {
+        assert!(!interval.is_zero(), "interval of a snapshot stream can't be zero");
+        self.0.borrow_mut().interval = interval;
+    }
+    pub fn add_receiver(&self, name: &str) {
+        self.0.borrow().message.add_receiver(name);
+    }
+}
+
+/// bits of the index of a rotation with entries entries.
+fn index_bits(entries: usize) -> u8 {
+    (usize::BITS - entries.saturating_sub(1).leading_zeros()).max(1) as u8
+}
+
+/// defines the layout (index, value) and interval of the message.
+pub(crate) fn create_snapshot_format(snapshot: &SnapshotStreamBuilder) -> Result<()> {
+    let snapshot_data = snapshot.0.borrow();
+    let node_data = snapshot_data.tx_node.0.borrow();
+    if snapshot_data.entries.is_empty() {
+        return Err(errors::ConfigError::InvalidSnapshotStream(format!(
+            "snapshot stream of {} has no entries",
+            node_data.name
+        )));
+    }
+    if let Some(entry) = snapshot_data
+        .entries
+        .iter()
+        .find(|entry| !node_data.object_entries.iter().any(|oe| &oe.0.borrow().name == *entry))
+    {
+        return Err(errors::ConfigError::InvalidSnapshotStream(format!(
+            "snapshot stream of {} contains {entry}, which is not an object entry of {}",
+            node_data.name, node_data.name
+        )));
+    }
+    let index_bits = index_bits(snapshot_data.entries.len());
+    let message = &snapshot_data.message;
+    message.0.borrow_mut().usage = MessageBuilderUsage::External {
+        interval: Some(snapshot_data.interval),
+    };
+    let format = message.make_type_format();
+    format.add_type(&format!("u{index_bits}"), "index");
+    format.add_type(&format!("u{}", 64 - index_bits), "value");
+    Ok(())
+}
+
+/// the config of the snapshot stream, fails if the value of an entry
+/// doesn't fit into the value signal.
+pub(crate) fn build_snapshot_stream(
+    snapshot: &SnapshotStreamBuilder,
+    node: &NodeRef,
+    messages: &[MessageRef],
+) -> Result<SnapshotStream> {
+    let snapshot_data = snapshot.0.borrow();
+    let message_name = &snapshot_data.message.0.borrow().name;
+    let message = messages
+        .iter()
+        .find(|m| m.name() == message_name.as_str())
+        .expect("message of the snapshot stream was not added to the network")
+        .clone();
+    let index_bits = index_bits(snapshot_data.entries.len());
+    let value_bits = 64 - index_bits as u32;
+    let mut entries = vec![];
+    for name in &snapshot_data.entries {
+        let entry = node
+            .object_entries()
+            .iter()
+            .find(|oe| oe.name() == name)
+            .expect("entries of snapshot streams are checked before build")
+            .clone();
+        if entry.ty().size() > value_bits {
+            return Err(errors::ConfigError::InvalidSnapshotStream(format!(
+                "{}::{name} has {} bits, but the snapshot stream has {value_bits} bits per value",
+                node.name(),
+                entry.ty().size()
+            )));
+        }
+        entries.push(entry);
+    }
+    Ok(SnapshotStream::new(message, entries, snapshot_data.interval, index_bits))
+}
@@ -1,15 +1,15 @@
-use std::time::Duration;
+use std::{ops::Range, time::Duration};
 
-use crate::config::Visibility;
+use crate::config::{signal::ON_RECEIVE, stream::DeltaRollover, Annotations, Visibility};
 
 use super::{
-    make_builder_ref, BuilderRef, MessageBuilder, MessagePriority, MessageTypeFormatBuilder,
-    NodeBuilder, ObjectEntryBuilder,
+    make_builder_ref, node::expand_pattern, BuilderRef, MessageBuilder, MessagePriority,
+    MessageTypeFormatBuilder, NodeBuilder, ObjectEntryBuilder,
 };
 
 #[derive(Debug, Clone)]
 pub struct StreamBuilder(pub BuilderRef<StreamData>);
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StreamData {
     pub name: String,
     pub description: Option<String>,
@@ -19,16 +19,42 @@ pub struct StreamData {
     pub object_entries: Vec<ObjectEntryBuilder>,
     pub visbility: Visibility,
     pub interval: (Duration, Duration),
+    // (object entry name, width, rollover)
+    pub delta_entries: Vec<(String, u8, DeltaRollover)>,
+    // interval the stream can be slowed down to, to meet bus quotas.
+    pub elastic_interval: Option<(Duration, Duration)>,
+    pub mirrored: bool,
+    // names of the streams that were merged into this one.
+    pub merged: Vec<String>,
+    // (object entry name, key, value), copied to the signals of the entry.
+    pub entry_annotations: Vec<(String, String, String)>,
+    pub annotations: Annotations,
+}
+
+/// how entries of the tx stream that are neither mapped
+/// nor ignored by a receive stream are handled by build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnmappedPolicy {
+    /// the entries are silently dropped by the receiver.
+    #[default]
+    Drop,
+    /// build fails with ConfigError::UnmappedStreamEntry.
+    Error,
 }
 
 #[derive(Debug, Clone)]
 pub struct ReceiveStreamBuilder(pub BuilderRef<ReceiveStreamData>);
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReceiveStreamData {
     pub stream_builder: StreamBuilder,
     pub rx_node: NodeBuilder,
     pub object_entries: Vec<(usize, ObjectEntryBuilder)>,
     pub visibility: Visibility,
+    // names of the tx entries that are intentionally not mapped.
+    pub ignored: Vec<String>,
+    pub unmapped_policy: UnmappedPolicy,
+    // how stale the received values may become.
+    pub timeout: Option<Duration>,
 }
 
 impl StreamBuilder {
@@ -38,12 +64,11 @@ impl StreamBuilder {
         let node_data = node_builder.0.borrow();
         let message = node_data.network_builder.create_message(
             &format!("{}_stream_{name}", node_builder.0.borrow().name),
-            None,
         );
         drop(node_data);
         node_builder.add_tx_message(&message);
         message.hide();
-        message.set_any_std_id(MessagePriority::Normal);
+        message.set_any_std_id(MessagePriority::default());
         let format = message.make_type_format();
 
         let new = StreamBuilder(make_builder_ref(StreamData {
@@ -55,6 +80,12 @@ impl StreamBuilder {
             object_entries: vec![],
             visbility: Visibility::Global,
             interval: (Duration::from_millis(50), Duration::from_millis(500)),
+            delta_entries: vec![],
+            elastic_interval: None,
+            mirrored: false,
+            merged: vec![],
+            entry_annotations: vec![],
+            annotations: Annotations::new(),
         }));
         message.__assign_to_stream(&new);
         new
@@ -65,6 +96,21 @@ impl StreamBuilder {
         assert!(min.as_micros() <= max.as_micros());
         self.0.borrow_mut().interval = (min, max);
     }
+    /// interval that the stream may be slowed down to,
+    /// if a bus quota of one of its tags is exceeded.
+    pub fn set_elastic_interval(&self, min: Duration, max: Duration) {
+        assert!(min.as_micros() <= max.as_micros());
+        self.0.borrow_mut().elastic_interval = Some((min, max));
+    }
+    /// the master of the mirror target (NetworkBuilder::set_mirror_target)
+    /// receives the stream and mirrors it onto UDP.
+    pub fn mirror(&self) {
+        self.0.borrow_mut().mirrored = true;
+    }
+    /// see MessageBuilder::add_tag.
+    pub fn add_tag(&self, tag: &str) {
+        self.0.borrow().message.add_tag(tag);
+    }
     pub fn hide(&self) {
         let mut stream_data = self.0.borrow_mut();
         stream_data.visbility = Visibility::Static;
@@ -73,7 +119,30 @@ impl StreamBuilder {
         let mut stream_data = self.0.borrow_mut();
         stream_data.description = Some(description.to_owned());
     }
+    /// key value metadata for downstream tools, exported to json and dbc.
+    pub fn annotate(&self, key: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .annotations
+            .insert(key.to_owned(), value.to_owned());
+    }
     pub fn add_entry(&self, name: &str) {
+        self.add_entry_with_type(name, None);
+    }
+    /// adds an entry, which is transmitted as the difference of its raw value
+    /// since the last frame in a signed signal of the given width,
+    /// e.g. for high rate entries that change slowly.
+    pub fn add_delta_entry(&self, name: &str, width: u8, rollover: DeltaRollover) {
+        if self.0.borrow().object_entries.iter().any(|oe| oe.0.borrow().name == name) {
+            return;
+        }
+        self.add_entry_with_type(name, Some(format!("i{width}")));
+        self.0
+            .borrow_mut()
+            .delta_entries
+            .push((name.to_owned(), width, rollover));
+    }
+    fn add_entry_with_type(&self, name: &str, signal_type: Option<String>) {
         let mut stream_data = self.0.borrow_mut();
         // CHECK if entry already exists
         if stream_data.object_entries.iter().any(|oe| &oe.0.borrow().name == name) {
@@ -97,7 +166,128 @@ impl StreamBuilder {
         // .unwrap_or_else(|| node.create_object_entry(name, "u1"));
         stream_data.object_entries.push(oe.clone());
         let oe_data = oe.0.borrow();
-        stream_data.format.add_type(&oe_data.ty, &oe_data.name);
+        let ty = signal_type.unwrap_or_else(|| oe_data.ty.clone());
+        stream_data.format.add_type(&ty, &oe_data.name);
+    }
+    /// annotates the signals that encode the entry, e.g. with hints for
+    /// firmware code generators. The entry has to be part of the stream.
+    pub fn annotate_entry(&self, entry: &str, key: &str, value: &str) {
+        if !self.contains_entry(entry) {
+            let stream_name = &self.0.borrow().name;
+            panic!("Failed to annotate stream entry. Stream {stream_name} doesn't contain {entry}");
+        }
+        let mut stream_data = self.0.borrow_mut();
+        stream_data.entry_annotations.retain(|(e, k, _)| e != entry || k != key);
+        stream_data
+            .entry_annotations
+            .push((entry.to_owned(), key.to_owned(), value.to_owned()));
+    }
+    /// handler that receivers of the stream call when the entry is received,
+    /// see Signal::on_receive.
+    pub fn set_entry_on_receive(&self, entry: &str, handler: &str) {
+        self.annotate_entry(entry, ON_RECEIVE, handler);
+    }
+    /// same as add_entry, but takes the handle of the object entry,
+    /// which has to belong to the transmitting node of the stream.
+    pub fn add_entry_builder(&self, object_entry: &ObjectEntryBuilder) {
+        let tx_node = self.0.borrow().tx_node.clone();
+        if !tx_node.owns_object_entry(object_entry) {
+            let node_name = &tx_node.0.borrow().name;
+            let name = &object_entry.0.borrow().name;
+            panic!("Failed to create stream entry. Object entry {name} does not belong to node {node_name}");
+        }
+        let name = object_entry.0.borrow().name.clone();
+        self.add_entry(&name);
+    }
+    /// adds the entries pattern.replace("{}", i) for all i in range, see
+    /// NodeBuilder::create_object_entries. Entries that don't fit into the frame
+    /// (minus the stream headroom) are added to the continuation streams
+    /// {name}_1, {name}_2, ..., which are configured like this stream.
+    /// Returns all streams that entries were added to, starting with this one.
+    pub fn add_entries(&self, pattern: &str, range: Range<usize>) -> Vec<StreamBuilder> {
+        let network_builder = self.0.borrow().tx_node.0.borrow().network_builder.clone();
+        let capacity = 64 - (network_builder.0.borrow().stream_headroom as usize).min(64);
+        let mut streams = vec![self.clone()];
+        let mut current = self.clone();
+        for name in expand_pattern(pattern, range) {
+            if streams.iter().any(|stream| stream.contains_entry(&name)) {
+                continue;
+            }
+            let oe_bits = self.entry_type_bit_len(&name);
+            loop {
+                let bits = current.bit_len();
+                let fits = current
+                    .0
+                    .borrow()
+                    .message
+                    .authenticated_bit_len(bits + oe_bits)
+                    <= capacity;
+                // entries that exceed an empty frame are reported as StreamOverflow by build.
+                if fits || bits == 0 {
+                    break;
+                }
+                current = self.continuation(streams.len());
+                if !streams.iter().any(|stream| stream.0.borrow().name == current.0.borrow().name) {
+                    streams.push(current.clone());
+                }
+            }
+            current.add_entry(&name);
+        }
+        streams
+    }
+    pub(crate) fn contains_entry(&self, name: &str) -> bool {
+        self.0.borrow().object_entries.iter().any(|oe| oe.0.borrow().name == name)
+    }
+    pub(crate) fn entry_type_bit_len(&self, name: &str) -> usize {
+        let node = self.0.borrow().tx_node.clone();
+        let node_data = node.0.borrow();
+        let Some(oe) = node_data.object_entries.iter().find(|oe| oe.0.borrow().name == name) else {
+            let node_name = &node_data.name;
+            panic!("Failed to create stream entry. Object entry {node_name}:{name} does not exist");
+        };
+        let ty = oe.0.borrow().ty.clone();
+        match node_data.network_builder.type_bit_len(&ty) {
+            Some(bits) => bits,
+            None => panic!("Failed to create stream entry. Size of type {ty} of {name} is unknown, define it before adding the entry"),
+        }
+    }
+    /// bits of the entries of the stream.
+    pub(crate) fn bit_len(&self) -> usize {
+        let stream_data = self.0.borrow();
+        stream_data
+            .object_entries
+            .iter()
+            .map(|oe| {
+                let name = oe.0.borrow().name.clone();
+                match stream_data.delta_entries.iter().find(|(entry, _, _)| entry == &name) {
+                    Some((_, width, _)) => *width as usize,
+                    None => self.entry_type_bit_len(&name),
+                }
+            })
+            .sum()
+    }
+    /// the k-th continuation stream of this stream.
+    fn continuation(&self, k: usize) -> StreamBuilder {
+        let stream_data = self.0.borrow();
+        let continuation = stream_data
+            .tx_node
+            .create_stream(&format!("{}_{k}", stream_data.name));
+        let mut continuation_data = continuation.0.borrow_mut();
+        continuation_data.description = stream_data.description.clone();
+        continuation_data.visbility = stream_data.visbility.clone();
+        continuation_data.interval = stream_data.interval;
+        continuation_data.elastic_interval = stream_data.elastic_interval;
+        continuation_data.mirrored = stream_data.mirrored;
+        continuation_data.annotations = stream_data.annotations.clone();
+        let message_data = stream_data.message.0.borrow();
+        let mut continuation_message_data = continuation_data.message.0.borrow_mut();
+        continuation_message_data.id = message_data.id.clone();
+        continuation_message_data.tags = message_data.tags.clone();
+        continuation_message_data.cmac = message_data.cmac;
+        continuation_message_data.deadline = message_data.deadline;
+        drop(continuation_message_data);
+        drop(continuation_data);
+        continuation
     }
     pub fn set_priority(&self, priority: MessagePriority) {
         self.0.borrow().message.set_any_std_id(priority);
@@ -105,6 +295,10 @@ impl StreamBuilder {
     pub fn set_priority_with_extended_id(&self, priority: MessagePriority) {
         self.0.borrow().message.set_any_ext_id(priority);
     }
+    /// see MessageBuilder::set_deadline.
+    pub fn set_deadline(&self, deadline: Duration) {
+        self.0.borrow().message.set_deadline(deadline);
+    }
 }
 
 impl ReceiveStreamBuilder {
@@ -118,12 +312,30 @@ impl ReceiveStreamBuilder {
             rx_node,
             object_entries: vec![],
             visibility: Visibility::Global,
+            ignored: vec![],
+            unmapped_policy: UnmappedPolicy::default(),
+            timeout: None,
         }))
     }
+    /// marks an entry of the tx stream as intentionally not received.
+    pub fn ignore(&self, from: &str) {
+        let mut rx_stream_data = self.0.borrow_mut();
+        if !rx_stream_data.ignored.iter().any(|name| name == from) {
+            rx_stream_data.ignored.push(from.to_owned());
+        }
+    }
+    pub fn set_unmapped_policy(&self, policy: UnmappedPolicy) {
+        self.0.borrow_mut().unmapped_policy = policy;
+    }
     pub fn hide(&self) {
         let mut rx_stream_data = self.0.borrow_mut();
         rx_stream_data.visibility = Visibility::Static;
     }
+    /// the received values are stale if no frame was received for the timeout,
+    /// has to be at least the max interval of the tx stream.
+    pub fn set_timeout(&self, timeout: Duration) {
+        self.0.borrow_mut().timeout = Some(timeout);
+    }
     pub fn map(&self, from: &str, to: &str) {
         // resolve from
         let tx_stream_builder = self.0.borrow().stream_builder.clone();
@@ -196,9 +408,35 @@ impl ReceiveStreamBuilder {
                     .position(|oe| &oe.0.borrow().name == &tx_oe_name).expect("Mapping should really really exist by now xD.")
             }
         };
+        let source = {
+            let tx_stream_data = tx_stream_builder.0.borrow();
+            let tx_node_name = tx_stream_data.tx_node.0.borrow().name.clone();
+            (tx_node_name, tx_stream_data.name.clone(), tx_oe_name)
+        };
+        if rx_oe.0.borrow().sources.contains(&source) {
+            // mapping the same entries again doesn't add a source.
+            return;
+        }
+        rx_oe.0.borrow_mut().sources.push(source);
         self.0
             .borrow_mut()
             .object_entries
             .push((tx_oe_map_position, rx_oe));
     }
+    /// same as map, but takes the handles of the object entries,
+    /// from has to belong to the transmitting and to to the receiving node.
+    pub fn map_builder(&self, from: &ObjectEntryBuilder, to: &ObjectEntryBuilder) {
+        let tx_node = self.0.borrow().stream_builder.0.borrow().tx_node.clone();
+        let rx_node = self.0.borrow().rx_node.clone();
+        for (node, object_entry) in [(&tx_node, from), (&rx_node, to)] {
+            if !node.owns_object_entry(object_entry) {
+                let node_name = &node.0.borrow().name;
+                let name = &object_entry.0.borrow().name;
+                panic!("Failed to map stream entry. Object entry {name} does not belong to node {node_name}");
+            }
+        }
+        let from = from.0.borrow().name.clone();
+        let to = to.0.borrow().name.clone();
+        self.map(&from, &to);
+    }
 }
@@ -0,0 +1,271 @@
+use std::rc::Rc;
+
+use super::{
+    message_builder::MessageIdTemplate,
+    stream_builder::{ReceiveStreamBuilder, StreamBuilder},
+    MessageBuilder, NetworkBuilder, NodeBuilder,
+};
+
+// Merges small streams of the same node into a single stream, which reduces
+// the number of ids and filters (see Network::optimization_hints for the same
+// analysis on built messages). Streams are merged if they have the same
+// receivers, compatible intervals and message options and the merged entries
+// fit into the threshold. The first stream (in the order of definition) keeps
+// its name and absorbs the entries of the other one, receive streams are
+// merged the same way. Stream::merged_streams lists the absorbed streams.
+
+// the longer max interval of merged streams is at most twice the shorter one.
+const MAX_MERGE_INTERVAL_RATIO: u32 = 2;
+
+fn rx_nodes(stream: &StreamBuilder, nodes: &[NodeBuilder]) -> Vec<String> {
+    let mut names: Vec<String> = nodes
+        .iter()
+        .filter(|node| receive_stream(node, stream).is_some())
+        .map(|node| node.0.borrow().name.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+fn receive_stream(node: &NodeBuilder, stream: &StreamBuilder) -> Option<ReceiveStreamBuilder> {
+    node.0
+        .borrow()
+        .rx_streams
+        .iter()
+        .find(|rx_stream| Rc::ptr_eq(&rx_stream.0.borrow().stream_builder.0, &stream.0))
+        .cloned()
+}
+
+fn has_any_id(message: &MessageBuilder) -> bool {
+    matches!(
+        message.0.borrow().id,
+        MessageIdTemplate::AnyStd(_) | MessageIdTemplate::AnyExt(_) | MessageIdTemplate::AnyAny(_)
+    )
+}
+
+/// bits of the entries of a and the entries of b that are not part of a.
+fn merged_bit_len(a: &StreamBuilder, b: &StreamBuilder) -> usize {
+    let b_data = b.0.borrow();
+    a.bit_len()
+        + b_data
+            .object_entries
+            .iter()
+            .map(|oe| oe.0.borrow().name.clone())
+            .filter(|name| !a.contains_entry(name))
+            .map(|name| {
+                match b_data.delta_entries.iter().find(|(entry, _, _)| entry == &name) {
+                    Some((_, width, _)) => *width as usize,
+                    None => b.entry_type_bit_len(&name),
+                }
+            })
+            .sum::<usize>()
+}
+
+fn is_mergeable(
+    network_builder: &NetworkBuilder,
+    a: &StreamBuilder,
+    b: &StreamBuilder,
+    max_bytes: u8,
+) -> bool {
+    let network_data = network_builder.0.borrow();
+    let (a_data, b_data) = (a.0.borrow(), b.0.borrow());
+    let (a_message, b_message) = (a_data.message.0.borrow(), b_data.message.0.borrow());
+    let scheduled = |message: &MessageBuilder| {
+        network_data.time_triggered_schedules.iter().any(|schedule| {
+            schedule
+                .0
+                .borrow()
+                .messages
+                .iter()
+                .any(|m| Rc::ptr_eq(&m.0, &message.0))
+        })
+    };
+    let bus_name = |message: &MessageBuilder| {
+        message.0.borrow().bus.as_ref().map(|bus| bus.0.borrow().name.clone())
+    };
+    let fallback_bus_name = |message: &MessageBuilder| {
+        message.0.borrow().fallback_bus.as_ref().map(|bus| bus.0.borrow().name.clone())
+    };
+    let (max_a, max_b) = (a_data.interval.1, b_data.interval.1);
+    let compatible = a_data.mirrored == b_data.mirrored
+        && a_data.visbility == b_data.visbility
+        && a_data.elastic_interval.is_none()
+        && b_data.elastic_interval.is_none()
+        && max_a.max(max_b) <= max_a.min(max_b) * MAX_MERGE_INTERVAL_RATIO
+        && a_message.tags == b_message.tags
+        && a_message.cmac.is_none()
+        && b_message.cmac.is_none()
+        && matches!(
+            (&a_message.id, &b_message.id),
+            (MessageIdTemplate::AnyExt(_), MessageIdTemplate::AnyExt(_))
+                | (
+                    MessageIdTemplate::AnyStd(_) | MessageIdTemplate::AnyAny(_),
+                    MessageIdTemplate::AnyStd(_) | MessageIdTemplate::AnyAny(_)
+                )
+        );
+    drop((a_message, b_message));
+    let (a_message, b_message) = (a_data.message.clone(), b_data.message.clone());
+    drop((a_data, b_data));
+    if !compatible
+        || !has_any_id(&a_message)
+        || !has_any_id(&b_message)
+        || bus_name(&a_message) != bus_name(&b_message)
+        || fallback_bus_name(&a_message) != fallback_bus_name(&b_message)
+        || scheduled(&a_message)
+        || scheduled(&b_message)
+    {
+        return false;
+    }
+    let nodes = network_data.nodes.borrow().clone();
+    drop(network_data);
+    let receivers = rx_nodes(a, &nodes);
+    !receivers.is_empty()
+        && receivers == rx_nodes(b, &nodes)
+        && merged_bit_len(a, b) <= max_bytes as usize * 8
+}
+
+fn priority(message: &MessageBuilder) -> Option<u32> {
+    match &message.0.borrow().id {
+        MessageIdTemplate::AnyStd(priority)
+        | MessageIdTemplate::AnyExt(priority)
+        | MessageIdTemplate::AnyAny(priority) => Some(priority.to_u32()),
+        MessageIdTemplate::StdId(_) | MessageIdTemplate::ExtId(_) => None,
+    }
+}
+
+/// moves the entries and receivers of b into a and removes b.
+fn merge(network_builder: &NetworkBuilder, tx_node: &NodeBuilder, a: &StreamBuilder, b: &StreamBuilder) {
+    let b_data = b.0.borrow().clone();
+    for oe in &b_data.object_entries {
+        let name = oe.0.borrow().name.clone();
+        match b_data.delta_entries.iter().find(|(entry, _, _)| entry == &name) {
+            Some((_, width, rollover)) => a.add_delta_entry(&name, *width, *rollover),
+            None => a.add_entry(&name),
+        }
+    }
+    let a_message = a.0.borrow().message.clone();
+    if priority(&b_data.message) < priority(&a_message) {
+        let b_id = b_data.message.0.borrow().id.clone();
+        a_message.0.borrow_mut().id = b_id;
+    }
+    // the merged frame has to meet the shorter deadline.
+    let b_deadline = b_data.message.0.borrow().deadline;
+    let mut a_message_data = a_message.0.borrow_mut();
+    a_message_data.deadline = match (a_message_data.deadline, b_deadline) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    };
+    drop(a_message_data);
+    {
+        let mut a_data = a.0.borrow_mut();
+        a_data.interval = (
+            a_data.interval.0.min(b_data.interval.0),
+            a_data.interval.1.min(b_data.interval.1),
+        );
+        a_data.merged.push(b_data.name.clone());
+        a_data.merged.extend(b_data.merged.iter().cloned());
+        for (key, value) in &b_data.annotations {
+            a_data.annotations.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+        for annotation in &b_data.entry_annotations {
+            if !a_data.entry_annotations.iter().any(|(e, k, _)| *e == annotation.0 && *k == annotation.1) {
+                a_data.entry_annotations.push(annotation.clone());
+            }
+        }
+    }
+
+    let nodes = network_builder.0.borrow().nodes.borrow().clone();
+    for node in &nodes {
+        let (Some(rx_a), Some(rx_b)) = (receive_stream(node, a), receive_stream(node, b)) else {
+            continue;
+        };
+        let rx_b_data = rx_b.0.borrow().clone();
+        let a_entries: Vec<String> = a
+            .0
+            .borrow()
+            .object_entries
+            .iter()
+            .map(|oe| oe.0.borrow().name.clone())
+            .collect();
+        let mut rx_a_data = rx_a.0.borrow_mut();
+        for (index, rx_oe) in rx_b_data.object_entries {
+            // aggregation sources refer to the stream by name.
+            let tx_node_name = tx_node.0.borrow().name.clone();
+            let a_name = a.0.borrow().name.clone();
+            for (node, stream, _) in rx_oe.0.borrow_mut().sources.iter_mut() {
+                if *node == tx_node_name && *stream == b_data.name {
+                    *stream = a_name.clone();
+                }
+            }
+            let name = b_data.object_entries[index].0.borrow().name.clone();
+            let position = a_entries
+                .iter()
+                .position(|entry| entry == &name)
+                .expect("the entries of b were added to a");
+            if !rx_a_data.object_entries.iter().any(|(i, _)| *i == position) {
+                rx_a_data.object_entries.push((position, rx_oe));
+            }
+        }
+        for ignored in rx_b_data.ignored {
+            if !rx_a_data.ignored.contains(&ignored) {
+                rx_a_data.ignored.push(ignored);
+            }
+        }
+        drop(rx_a_data);
+        let mut node_data = node.0.borrow_mut();
+        node_data.rx_streams.retain(|rx_stream| !Rc::ptr_eq(&rx_stream.0, &rx_b.0));
+        node_data
+            .rx_messages
+            .retain(|message| !Rc::ptr_eq(&message.0, &b_data.message.0));
+    }
+    let mut tx_node_data = tx_node.0.borrow_mut();
+    tx_node_data.tx_streams.retain(|stream| !Rc::ptr_eq(&stream.0, &b.0));
+    tx_node_data
+        .tx_messages
+        .retain(|message| !Rc::ptr_eq(&message.0, &b_data.message.0));
+    drop(tx_node_data);
+    network_builder
+        .0
+        .borrow()
+        .messages
+        .borrow_mut()
+        .retain(|message| !Rc::ptr_eq(&message.0, &b_data.message.0));
+}
+
+/// merges the streams of every node until no more streams can be merged.
+pub(crate) fn merge_streams(network_builder: &NetworkBuilder, max_bytes: u8) {
+    let nodes = network_builder.0.borrow().nodes.borrow().clone();
+    for node in &nodes {
+        loop {
+            let streams = node.0.borrow().tx_streams.clone();
+            let pair = streams.iter().enumerate().find_map(|(i, a)| {
+                streams[i + 1..]
+                    .iter()
+                    .find(|b| is_mergeable(network_builder, a, b, max_bytes))
+                    .map(|b| (a.clone(), b.clone()))
+            });
+            match pair {
+                Some((a, b)) => {
+                    #[cfg(feature = "logging_info")]
+                    println!(
+                        "[CANZERO-CONFIG::build] Merging stream {} into {}",
+                        b.0.borrow().name,
+                        a.0.borrow().name
+                    );
+                    merge(network_builder, node, &a, &b);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl NetworkBuilder {
+    /// merges streams of the same node with the same receivers and compatible
+    /// intervals, if the merged entries fit into max_bytes (1..=8) bytes.
+    /// Every merge saves an id and a filter on every receiver.
+    pub fn enable_stream_merging(&self, max_bytes: u8) {
+        assert!((1..=8).contains(&max_bytes), "merged streams have to fit into a frame");
+        self.0.borrow_mut().stream_merging = Some(max_bytes);
+    }
+}
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use crate::{
+    config::{message::MessageUsage, node::Supervision, MessageRef, NodeRef},
+    errors::{self, Result},
+};
+
+use super::NodeBuilder;
+
+// Heartbeat supervision matrix: a node monitors the heartbeat of every node
+// it depends on, which are the transmitters of the messages it receives
+// (streams, commands and external messages). Heartbeats and the get/set
+// protocol are sent between all nodes and don't count as dependency.
+// Supervisions added with NodeBuilder::supervise override the timeout or add
+// nodes that aren't a dependency.
+
+// heartbeats are sent every 100ms, a node is considered lost after 3 missing ones.
+pub const DEFAULT_HEARTBEAT_TIMEOUT: Duration = Duration::from_millis(300);
+
+fn is_dependency(message: &MessageRef) -> bool {
+    message.name() != "heartbeat"
+        && !matches!(
+            message.usage(),
+            MessageUsage::GetReq | MessageUsage::GetResp | MessageUsage::SetReq | MessageUsage::SetResp
+        )
+}
+
+/// supervisions of every node, in the order of nodes.
+pub(crate) fn supervision_matrix(
+    nodes: &[NodeRef],
+    node_builders: &[NodeBuilder],
+    default_timeout: Duration,
+) -> Result<Vec<Vec<Supervision>>> {
+    let mut matrix = vec![];
+    for node in nodes {
+        let node_builder = node_builders
+            .iter()
+            .find(|builder| builder.0.borrow().name == node.name())
+            .expect("node was not created by a builder");
+        let node_data = node_builder.0.borrow();
+        let mut supervised: Vec<(String, Duration)> = vec![];
+        // a node that receives everything doesn't depend on every node.
+        if !node_data.receive_all {
+            for message in node.rx_messages().iter().filter(|message| is_dependency(message)) {
+                for transmitter in nodes.iter().filter(|tx_node| {
+                    tx_node.name() != node.name()
+                        && tx_node.tx_messages().iter().any(|m| m.name() == message.name())
+                }) {
+                    if !supervised.iter().any(|(name, _)| name == transmitter.name()) {
+                        supervised.push((transmitter.name().to_owned(), default_timeout));
+                    }
+                }
+            }
+        }
+        for (name, timeout) in &node_data.supervisions {
+            let passive = node_builders
+                .iter()
+                .any(|builder| &builder.0.borrow().name == name && builder.is_passive());
+            if name == node.name() || passive || !nodes.iter().any(|other| other.name() == name) {
+                return Err(errors::ConfigError::InvalidSupervision(format!(
+                    "{} can't supervise the heartbeat of {name}, which is {}",
+                    node.name(),
+                    if name == node.name() {
+                        "the node itself"
+                    } else if passive {
+                        "listen only and doesn't send heartbeats"
+                    } else {
+                        "not part of the network"
+                    }
+                )));
+            }
+            let timeout = timeout.unwrap_or(default_timeout);
+            match supervised.iter_mut().find(|(other, _)| other == name) {
+                Some(supervision) => supervision.1 = timeout,
+                None => supervised.push((name.clone(), timeout)),
+            }
+        }
+        // in the order of nodes, such that the matrix is stable.
+        let supervisions = nodes
+            .iter()
+            .filter_map(|other| {
+                supervised
+                    .iter()
+                    .find(|(name, _)| name == other.name())
+                    .map(|(name, timeout)| Supervision::new(name.clone(), *timeout))
+            })
+            .collect();
+        matrix.push(supervisions);
+    }
+    Ok(matrix)
+}
@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use crate::{
+    config::{
+        time_triggered::{TimeTriggeredSchedule, TimeWindow},
+        Network,
+    },
+    errors::{self, Result},
+    simulator,
+};
+
+use super::{make_builder_ref, BuilderRef, MessageBuilder, NetworkBuilder};
+
+#[derive(Debug, Clone)]
+pub struct TimeTriggeredBuilder(pub BuilderRef<TimeTriggeredData>);
+#[derive(Debug, Clone)]
+pub struct TimeTriggeredData {
+    pub bus: String,
+    pub basic_cycle: Duration,
+    pub cycle_count: u32,
+    pub messages: Vec<MessageBuilder>,
+}
+
+impl TimeTriggeredBuilder {
+    pub fn new(bus: &str, basic_cycle: Duration, cycle_count: u32) -> Self {
+        TimeTriggeredBuilder(make_builder_ref(TimeTriggeredData {
+            bus: bus.to_owned(),
+            basic_cycle,
+            cycle_count,
+            messages: vec![],
+        }))
+    }
+    /// the message is assigned to the bus of the schedule and gets an
+    /// exclusive window, which is repeated according to its interval.
+    pub fn add_message(&self, message: &MessageBuilder) {
+        let mut schedule_data = self.0.borrow_mut();
+        if schedule_data
+            .messages
+            .iter()
+            .any(|m| m.0.borrow().name == message.0.borrow().name)
+        {
+            return;
+        }
+        message.assign_bus(&schedule_data.bus);
+        schedule_data.messages.push(message.clone());
+    }
+}
+
+pub(crate) fn check_schedule(schedule: &TimeTriggeredBuilder, network_builder: &NetworkBuilder) -> Result<()> {
+    let schedule_data = schedule.0.borrow();
+    let bus_name = &schedule_data.bus;
+    let invalid = |msg: String| Err(errors::ConfigError::InvalidTimeTriggeredSchedule(msg));
+    if schedule_data.basic_cycle.is_zero() {
+        return invalid(format!("basic cycle of bus {bus_name} is zero"));
+    }
+    if !schedule_data.cycle_count.is_power_of_two() {
+        return invalid(format!(
+            "bus {bus_name} has {} basic cycles per matrix cycle, which is not a power of two",
+            schedule_data.cycle_count
+        ));
+    }
+    let schedules = network_builder.0.borrow().time_triggered_schedules.clone();
+    if schedules
+        .iter()
+        .filter(|other| &other.0.borrow().bus == bus_name)
+        .count()
+        > 1
+    {
+        return invalid(format!("bus {bus_name} has more than one time-triggered schedule"));
+    }
+    Ok(())
+}
+
+/// every message is sent in the earliest window, which is free in all
+/// basic cycles that it is sent in. Frequent messages are placed first,
+/// messages with the same repeat in the order of their priority (id).
+pub(crate) fn build_schedule(
+    schedule: &TimeTriggeredBuilder,
+    network: &Network,
+) -> Result<TimeTriggeredSchedule> {
+    let schedule_data = schedule.0.borrow();
+    let bus_name = &schedule_data.bus;
+    let invalid = |msg: String| Err(errors::ConfigError::InvalidTimeTriggeredSchedule(msg));
+    let bus = network
+        .buses()
+        .iter()
+        .find(|bus| bus.name() == bus_name)
+        .expect("the bus of the schedule was not added to the network")
+        .clone();
+    let basic_cycle = schedule_data.basic_cycle;
+    let cycle_count = schedule_data.cycle_count;
+
+    // (message, repeat, length)
+    let mut requests = vec![];
+    for message_builder in &schedule_data.messages {
+        let name = &message_builder.0.borrow().name;
+        let message = network
+            .messages()
+            .iter()
+            .find(|m| m.name() == name)
+            .expect("message of the schedule was not added to the network");
+        if message.bus().id() != bus.id() {
+            return invalid(format!(
+                "{name} is time-triggered on bus {bus_name}, but was assigned to bus {}",
+                message.bus().name()
+            ));
+        }
+        let interval = simulator::message_interval(message);
+        if interval < basic_cycle {
+            return invalid(format!(
+                "interval of {name} ({interval:?}) is shorter than the basic cycle of bus {bus_name} ({basic_cycle:?})"
+            ));
+        }
+        let mut repeat = 1;
+        while repeat * 2 <= cycle_count && basic_cycle * repeat * 2 <= interval {
+            repeat *= 2;
+        }
+        let length = bus.frame_time(network.frame_time_model(), message);
+        requests.push((message.clone(), repeat, length));
+    }
+    requests.sort_by_key(|(message, repeat, _)| (*repeat, message.id().as_u32()));
+
+    // occupied (start, end) of every basic cycle.
+    let mut occupied: Vec<Vec<(Duration, Duration)>> = vec![vec![]; cycle_count as usize];
+    let mut windows = vec![];
+    for (message, repeat, length) in requests {
+        let (first_cycle, offset) = (0..repeat)
+            .map(|first_cycle| {
+                let cycles: Vec<usize> = (first_cycle..cycle_count)
+                    .step_by(repeat as usize)
+                    .map(|cycle| cycle as usize)
+                    .collect();
+                let mut offset = Duration::ZERO;
+                while let Some(end) = cycles
+                    .iter()
+                    .flat_map(|cycle| occupied[*cycle].iter())
+                    .filter(|(start, end)| *start < offset + length && offset < *end)
+                    .map(|(_, end)| *end)
+                    .max()
+                {
+                    offset = end;
+                }
+                (first_cycle, offset)
+            })
+            .min_by_key(|(_, offset)| *offset)
+            .expect("repeat is at least 1");
+        if offset + length > basic_cycle {
+            return invalid(format!(
+                "window of {} ({length:?}) doesn't fit into the basic cycle of bus {bus_name} ({basic_cycle:?})",
+                message.name()
+            ));
+        }
+        for cycle in (first_cycle..cycle_count).step_by(repeat as usize) {
+            occupied[cycle as usize].push((offset, offset + length));
+        }
+        windows.push(TimeWindow::new(message, first_cycle, repeat, offset, length));
+    }
+    windows.sort_by_key(|window| (*window.offset(), window.first_cycle()));
+    Ok(TimeTriggeredSchedule::new(
+        bus,
+        basic_cycle,
+        cycle_count,
+        windows,
+    ))
+}
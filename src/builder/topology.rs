@@ -0,0 +1,41 @@
+use crate::{
+    config::{bus::{connected_nodes, BusRef}, NodeRef},
+    errors::{self, Result},
+};
+
+// Validates the physical layer metadata of the buses against the software
+// topology, such that both are maintained in the network definition. Buses
+// without limits are not checked.
+
+pub(crate) fn validate_topology(buses: &[BusRef], nodes: &[NodeRef]) -> Result<()> {
+    for bus in buses {
+        let topology = bus.topology();
+        if let Some(max_nodes) = topology.max_nodes() {
+            let connected = connected_nodes(nodes, bus);
+            if connected.len() > max_nodes as usize {
+                let names: Vec<&str> = connected.iter().map(|node| node.name()).collect();
+                return Err(errors::ConfigError::InvalidTopology(format!(
+                    "bus {} supports {max_nodes} nodes, but {} nodes are connected ({})",
+                    bus.name(),
+                    connected.len(),
+                    names.join(", ")
+                )));
+            }
+        }
+        if let Some(max_stub_length) = topology.max_stub_length() {
+            if let Some(stub) = topology
+                .segments()
+                .iter()
+                .find(|segment| segment.is_stub() && segment.length() > max_stub_length)
+            {
+                return Err(errors::ConfigError::InvalidTopology(format!(
+                    "stub {} of bus {} is {}m long, but stubs are limited to {max_stub_length}m",
+                    stub.description(),
+                    bus.name(),
+                    stub.length()
+                )));
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::{config::Visibility, errors};
+use crate::{config::{Annotations, Visibility}, errors};
 
 use super::{make_builder_ref, BuilderRef};
 
@@ -6,22 +6,24 @@ use super::{make_builder_ref, BuilderRef};
 
 #[derive(Clone, Debug)]
 pub struct EnumBuilder(pub BuilderRef<EnumData>);
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EnumData {
     pub name: String,
     pub description: Option<String>,
     pub entries: Vec<(String, Option<u64>)>,
     pub visibility: Visibility,
+    pub annotations: Annotations,
 }
 
 #[derive(Debug, Clone)]
 pub struct StructBuilder(pub BuilderRef<StructData>);
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StructData {
     pub name: String,
     pub description: Option<String>,
     pub attributes: Vec<(String, String)>,
     pub visibility: Visibility,
+    pub annotations: Annotations,
 }
 
 #[derive(Debug, Clone)]
@@ -39,12 +41,20 @@ impl EnumBuilder {
             description: None,
             entries: vec![],
             visibility: Visibility::Global,
+            annotations: Annotations::new(),
         }))
     }
     pub fn add_description(&self, description: &str) {
         let mut enum_data = self.0.borrow_mut();
         enum_data.description = Some(description.to_owned());
     }
+    /// key value metadata for downstream tools, exported to json and dbc.
+    pub fn annotate(&self, key: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .annotations
+            .insert(key.to_owned(), value.to_owned());
+    }
     pub fn add_entry(&self, name: &str, value: Option<u64>) -> errors::Result<()> {
         let mut enum_data = self.0.borrow_mut();
         if enum_data.entries.iter().any(|a| a.0 == name) {
@@ -68,12 +78,20 @@ impl StructBuilder {
             description: None,
             attributes: vec![],
             visibility: Visibility::Global,
+            annotations: Annotations::new(),
         }))
     }
     pub fn add_description(&self, description: &str) {
         let mut struct_data = self.0.borrow_mut();
         struct_data.description = Some(description.to_owned());
     }
+    /// key value metadata for downstream tools, exported to json and dbc.
+    pub fn annotate(&self, key: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .annotations
+            .insert(key.to_owned(), value.to_owned());
+    }
     pub fn add_attribute(&self, name: &str, ty: &str) -> errors::Result<()> {
         let mut struct_data = self.0.borrow_mut();
         if struct_data.attributes.iter().any(|a| a.0 == name) {
@@ -0,0 +1,120 @@
+use std::{cell::RefCell, collections::HashMap, sync::LazyLock};
+
+use regex::Regex;
+
+use crate::{
+    config::{make_config_ref, SignalType, TimeKind, TimeUnit, Type, TypeRef},
+    errors::{self, Result},
+};
+
+// Type strings (u8, i12, d16<0..10>, t32<ms>, Foo[4], struct and enum names) are
+// resolved for every attribute, message entry and object entry of the
+// network. The regexes are compiled once and a TypeResolver caches the
+// resolved types by type string, such that large type libraries with
+// thousands of object entries resolve every distinct string only once.
+
+static INT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^i(?<size>[0-9]{1,2})$"#).unwrap());
+static UINT_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"^u(?<size>[0-9]{1,2})$"#).unwrap());
+static DEC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^d(?<size>[0-9]{1,2})<(?<min>[+-]?([0-9]*[.])?[0-9]+)\.\.(?<max>[+-]?([0-9]*[.])?[0-9]+)>$").unwrap()
+});
+static TIME_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^(?<kind>t|dt)(?<size>[0-9]{1,2})<(?<unit>us|ms|s)>$"#).unwrap());
+static ARRAY_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(?<type>[a-zA-Z][a-zA-Z0-9_]*(<[+-]?([0-9]*[.])?[0-9]+\.\.[+-]?([0-9]*[.])?[0-9]+>|<(us|ms|s)>)?)\[(?<len>[0-9]+)\]$"#)
+        .unwrap()
+});
+
+fn named_type(ty: &TypeRef) -> Option<&str> {
+    match ty as &Type {
+        Type::Struct { name, .. } | Type::Enum { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// resolves the type string, defined types are looked up with lookup.
+pub(crate) fn resolve_type_with(
+    type_name: &str,
+    lookup: &dyn Fn(&str) -> Option<TypeRef>,
+) -> Result<TypeRef> {
+    if let Some(cap) = INT_REGEX.captures(type_name) {
+        let size = cap["size"].parse::<u8>().unwrap();
+        if size > 0 && size <= 64 {
+            return Ok(make_config_ref(Type::Primitive(SignalType::SignedInt { size })));
+        }
+    }
+    if let Some(cap) = UINT_REGEX.captures(type_name) {
+        let size = cap["size"].parse::<u8>().unwrap();
+        if size > 0 && size <= 64 {
+            return Ok(make_config_ref(Type::Primitive(SignalType::UnsignedInt { size })));
+        }
+    }
+    if let Some(cap) = DEC_REGEX.captures(type_name) {
+        let size = cap["size"].parse::<u8>().unwrap();
+        let min = cap["min"].parse::<f64>().unwrap();
+        let max = cap["max"].parse::<f64>().unwrap();
+        if min >= max {
+            return Err(errors::ConfigError::InvalidRange(
+                "invalid decimal range min has to be less than max".to_owned(),
+            ));
+        }
+        let range = max - min;
+        let scale = range / ((0xFFFFFFFFFFFFFFFF_u64 >> (64 - size)) as f64);
+        let offset = min;
+        if size <= 64 {
+            return Ok(make_config_ref(Type::Primitive(SignalType::Decimal { size, offset, scale })));
+        }
+    }
+    if let Some(cap) = TIME_REGEX.captures(type_name) {
+        let size = cap["size"].parse::<u8>().unwrap();
+        if size > 0 && size <= 64 {
+            let unit = TimeUnit::from_suffix(&cap["unit"]).unwrap();
+            let kind = if &cap["kind"] == "t" { TimeKind::Timestamp } else { TimeKind::Duration };
+            return Ok(make_config_ref(Type::Primitive(SignalType::Time { size, unit, kind })));
+        }
+    }
+    if let Some(cap) = ARRAY_REGEX.captures(type_name) {
+        let len = cap["len"].parse::<usize>().unwrap();
+        let ty = resolve_type_with(&cap["type"], lookup)?;
+        return Ok(make_config_ref(Type::Array { len, ty }));
+    }
+    lookup(type_name).ok_or_else(|| {
+        errors::ConfigError::InvalidType(format!("failed to resolve type : {type_name:?}"))
+    })
+}
+
+/// resolves type strings against a set of defined types (structs and
+/// enums), every distinct type string is resolved only once.
+#[derive(Debug, Default)]
+pub struct TypeResolver {
+    defined: HashMap<String, TypeRef>,
+    cache: RefCell<HashMap<String, TypeRef>>,
+}
+
+impl TypeResolver {
+    pub fn new(defined_types: &[TypeRef]) -> TypeResolver {
+        let mut resolver = TypeResolver::default();
+        for ty in defined_types {
+            resolver.define(ty);
+        }
+        resolver
+    }
+    /// adds a struct or enum, the first definition of a name wins.
+    pub fn define(&mut self, ty: &TypeRef) {
+        if let Some(name) = named_type(ty) {
+            self.defined.entry(name.to_owned()).or_insert_with(|| ty.clone());
+        }
+    }
+    pub fn resolve(&self, type_name: &str) -> Result<TypeRef> {
+        if let Some(ty) = self.cache.borrow().get(type_name) {
+            return Ok(ty.clone());
+        }
+        let ty = resolve_type_with(type_name, &|name| self.defined.get(name).cloned())?;
+        self.cache.borrow_mut().insert(type_name.to_owned(), ty.clone());
+        Ok(ty)
+    }
+    /// resolves all type strings, fails on the first that can't be resolved.
+    pub fn resolve_all(&self, type_names: &[&str]) -> Result<Vec<TypeRef>> {
+        type_names.iter().map(|type_name| self.resolve(type_name)).collect()
+    }
+}
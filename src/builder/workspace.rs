@@ -0,0 +1,128 @@
+use std::rc::Rc;
+
+use crate::{
+    config::{make_config_ref, workspace::Workspace, workspace::WorkspaceRef},
+    errors::{self, Result},
+};
+
+use super::{
+    make_builder_ref, BuilderRef, EnumBuilder, NetworkBuilder, NodeBuilder, StructBuilder,
+    TypeBuilder,
+};
+
+#[derive(Debug, Clone)]
+pub struct WorkspaceBuilder(pub BuilderRef<WorkspaceData>);
+#[derive(Debug)]
+pub struct WorkspaceData {
+    pub networks: Vec<(String, NetworkBuilder)>,
+    // defined in every network of the workspace.
+    pub types: Vec<TypeBuilder>,
+}
+
+fn type_name(type_builder: &TypeBuilder) -> String {
+    match type_builder {
+        TypeBuilder::Enum(enum_builder) => enum_builder.0.borrow().name.clone(),
+        TypeBuilder::Struct(struct_builder) => struct_builder.0.borrow().name.clone(),
+    }
+}
+
+fn same_type(a: &TypeBuilder, b: &TypeBuilder) -> bool {
+    match (a, b) {
+        (TypeBuilder::Enum(a), TypeBuilder::Enum(b)) => Rc::ptr_eq(&a.0, &b.0),
+        (TypeBuilder::Struct(a), TypeBuilder::Struct(b)) => Rc::ptr_eq(&a.0, &b.0),
+        _ => false,
+    }
+}
+
+impl Default for WorkspaceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkspaceBuilder {
+    pub fn new() -> WorkspaceBuilder {
+        WorkspaceBuilder(make_builder_ref(WorkspaceData {
+            networks: vec![],
+            types: vec![],
+        }))
+    }
+    /// returns the network with the name, if it already exists.
+    pub fn create_network(&self, name: &str) -> NetworkBuilder {
+        let mut workspace_data = self.0.borrow_mut();
+        if let Some((_, network)) = workspace_data.networks.iter().find(|(n, _)| n == name) {
+            return network.clone();
+        }
+        let network = NetworkBuilder::new();
+        network
+            .0
+            .borrow()
+            .types
+            .borrow_mut()
+            .extend(workspace_data.types.iter().cloned());
+        workspace_data.networks.push((name.to_owned(), network.clone()));
+        network
+    }
+    fn share_type(&self, type_builder: TypeBuilder) {
+        let mut workspace_data = self.0.borrow_mut();
+        for (_, network) in &workspace_data.networks {
+            network.0.borrow().types.borrow_mut().push(type_builder.clone());
+        }
+        workspace_data.types.push(type_builder);
+    }
+    /// defines the enum in all networks of the workspace.
+    pub fn define_enum(&self, name: &str) -> EnumBuilder {
+        let enum_builder = EnumBuilder::new(name);
+        self.share_type(TypeBuilder::Enum(enum_builder.clone()));
+        enum_builder
+    }
+    /// defines the struct in all networks of the workspace.
+    pub fn define_struct(&self, name: &str) -> StructBuilder {
+        let struct_builder = StructBuilder::new(name);
+        self.share_type(TypeBuilder::Struct(struct_builder.clone()));
+        struct_builder
+    }
+    /// creates the node in all listed networks, e.g. a node
+    /// that forwards frames between two networks.
+    pub fn create_gateway(&self, name: &str, networks: &[&str]) -> Vec<NodeBuilder> {
+        networks
+            .iter()
+            .map(|network_name| {
+                let network = self
+                    .0
+                    .borrow()
+                    .networks
+                    .iter()
+                    .find(|(n, _)| n == network_name)
+                    .map(|(_, network)| network.clone())
+                    .unwrap_or_else(|| {
+                        panic!("Failed to create gateway {name}. Network {network_name} does not exist")
+                    });
+                network.create_node(name)
+            })
+            .collect()
+    }
+    pub fn build(self) -> Result<WorkspaceRef> {
+        let workspace_data = self.0.borrow();
+        // a type defined by a single network with the name of a shared
+        // type would silently replace the shared definition.
+        for (network_name, network) in &workspace_data.networks {
+            for type_builder in network.0.borrow().types.borrow().iter() {
+                let conflict = workspace_data.types.iter().any(|shared| {
+                    type_name(shared) == type_name(type_builder) && !same_type(shared, type_builder)
+                });
+                if conflict {
+                    return Err(errors::ConfigError::InvalidWorkspace(format!(
+                        "network {network_name} redefines the shared type {}",
+                        type_name(type_builder)
+                    )));
+                }
+            }
+        }
+        let mut networks = vec![];
+        for (network_name, network) in &workspace_data.networks {
+            networks.push((network_name.clone(), network.build()?));
+        }
+        Ok(make_config_ref(Workspace::new(networks)))
+    }
+}
@@ -0,0 +1,48 @@
+use std::{hash::Hasher, path::Path};
+
+use crate::{config::Network, errors};
+
+use super::{
+    json::Json,
+    serialize::{network_json, resolution_json},
+};
+
+// A bundle is a single json document that allows to trace a firmware
+// back to the exact network definition it was generated from.
+// The checksum is the seahash of the network, resolution and dbc
+// sections in their compact (single line) json representation.
+
+pub const BUNDLE_FORMAT_VERSION: u64 = 1;
+
+fn bundle_checksum(sections: &[&Json]) -> u64 {
+    let mut hasher = seahash::SeaHasher::new();
+    for section in sections {
+        hasher.write(section.to_compact_string().as_bytes());
+    }
+    hasher.finish()
+}
+
+impl Network {
+    /// Writes the serialized network, the dbc export, the resolution report,
+    /// the network hash and the build time into a single json file.
+    pub fn export_bundle(&self, path: impl AsRef<Path>) -> errors::Result<()> {
+        std::fs::write(path, self.bundle())?;
+        Ok(())
+    }
+    pub fn bundle(&self) -> String {
+        let network = network_json(self);
+        let resolution = resolution_json(self);
+        let dbc = Json::String(self.generate_dbc());
+        let checksum = bundle_checksum(&[&network, &resolution, &dbc]);
+        Json::object([
+            ("format_version", Json::uint(BUNDLE_FORMAT_VERSION)),
+            ("build_time", Json::String(self.build_time().to_rfc3339())),
+            ("hash", Json::String(format!("{:#X}", self.portable_hash()))),
+            ("checksum", Json::String(format!("{checksum:#X}"))),
+            ("network", network),
+            ("resolution", resolution),
+            ("dbc", dbc),
+        ])
+        .to_string()
+    }
+}
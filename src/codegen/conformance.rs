@@ -0,0 +1,92 @@
+use crate::config::{
+    conformance::{ConformanceFrame, ConformanceKind, ConformanceStep, ConformanceValue},
+    Network,
+};
+
+use super::json::Json;
+
+// Serializes the conformance scenarios (see config::conformance) into json,
+// such that firmware test harnesses can replay them without this crate.
+// Payloads and values are hex strings, byte 0 first.
+
+fn hex(bytes: &[u8]) -> Json {
+    Json::String(bytes.iter().map(|byte| format!("{byte:02X}")).collect())
+}
+
+fn value_json(object_entry: &str, value: &ConformanceValue) -> Json {
+    Json::object([
+        ("object_entry", Json::string(object_entry)),
+        ("bits", Json::uint(value.bits() as u64)),
+        ("value", hex(value.bytes())),
+    ])
+}
+
+fn frame_json(frame: &ConformanceFrame) -> Json {
+    Json::object([
+        ("message", Json::string(frame.message())),
+        ("id", Json::uint(frame.id().as_u32() as u64)),
+        ("ide", Json::Bool(frame.id().ide())),
+        ("bus", Json::string(frame.bus())),
+        (
+            "fields",
+            Json::Object(
+                frame
+                    .fields()
+                    .iter()
+                    .map(|(path, value)| (path.clone(), Json::uint(*value)))
+                    .collect(),
+            ),
+        ),
+        ("data", hex(frame.data())),
+    ])
+}
+
+fn step_json(step: &ConformanceStep) -> Json {
+    match step {
+        ConformanceStep::Assume { object_entry, value } => Json::object([
+            ("step", Json::string("assume")),
+            ("entry", value_json(object_entry, value)),
+        ]),
+        ConformanceStep::Send(frame) => Json::object([
+            ("step", Json::string("send")),
+            ("frame", frame_json(frame)),
+        ]),
+        ConformanceStep::Expect { frame, timeout } => Json::object([
+            ("step", Json::string("expect")),
+            ("frame", frame_json(frame)),
+            (
+                "timeout_us",
+                timeout.map_or(Json::Null, |timeout| Json::uint(timeout.as_micros() as u64)),
+            ),
+        ]),
+        ConformanceStep::Verify { object_entry, value } => Json::object([
+            ("step", Json::string("verify")),
+            ("entry", value_json(object_entry, value)),
+        ]),
+    }
+}
+
+impl Network {
+    /// the conformance scenarios of all nodes as a json array.
+    pub fn generate_conformance_vectors(&self) -> String {
+        let scenarios = self
+            .conformance_scenarios()
+            .iter()
+            .map(|scenario| {
+                let kind = match scenario.kind() {
+                    ConformanceKind::Get => "get",
+                    ConformanceKind::Set => "set",
+                    ConformanceKind::SetError => "set_error",
+                    ConformanceKind::Command => "command",
+                };
+                Json::object([
+                    ("name", Json::string(scenario.name())),
+                    ("kind", Json::string(kind)),
+                    ("node", Json::string(scenario.node())),
+                    ("steps", Json::Array(scenario.steps().iter().map(step_json).collect())),
+                ])
+            })
+            .collect();
+        Json::Array(scenarios).to_string()
+    }
+}
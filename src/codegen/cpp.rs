@@ -0,0 +1,518 @@
+use std::fmt::{self, Write};
+
+use crate::config::{Message, Network, SignalType, TimeKind, TimeUnit, Type};
+
+use super::{
+    composite_types_in_dependency_order, container_bits, fits_u64, message_fields, reserved_mask,
+    reserved_signals, signal_leaves, signal_mask, MessageField,
+};
+
+// Generates a single C++ header with enum classes and structs for all types,
+// a struct with pack/unpack functions for every message and a constexpr id table.
+// Frames are handled as 8 byte buffers (detail::frame_buffer<8>, a std::span
+// in C++20 and a reference to a std::array in C++17), CAN XL
+// frames with more than 8 bytes as buffers of payload_len bytes, which are
+// packed bit by bit. Signals are little endian bitfields.
+
+const NAMESPACE: &str = "canzero";
+
+fn cpp_signal_type(signal_type: &SignalType) -> String {
+    match signal_type {
+        SignalType::UnsignedInt { size } => format!("uint{}_t", container_bits(*size)),
+        SignalType::SignedInt { size } => format!("int{}_t", container_bits(*size)),
+        SignalType::Decimal {
+            size,
+            offset: _,
+            scale: _,
+        } => {
+            if *size <= 24 {
+                "float".to_owned()
+            } else {
+                "double".to_owned()
+            }
+        }
+        SignalType::Time { size, unit, kind: _ } => {
+            let period = match unit {
+                TimeUnit::Micros => "std::micro",
+                TimeUnit::Millis => "std::milli",
+                TimeUnit::Secs => "std::ratio<1>",
+            };
+            format!("std::chrono::duration<uint{}_t, {period}>", container_bits(*size))
+        }
+    }
+}
+
+fn cpp_type(ty: &Type) -> String {
+    match ty {
+        Type::Primitive(signal_type) => cpp_signal_type(signal_type),
+        Type::Struct {
+            name,
+            description: _,
+            attribs: _,
+            visibility: _,
+            annotations: _,
+        } => format!("{NAMESPACE}::{name}"),
+        Type::Enum {
+            name,
+            description: _,
+            size: _,
+            entries: _,
+            visibility: _,
+            annotations: _,
+        } => format!("{NAMESPACE}::{name}"),
+        Type::Array { len, ty } => format!("std::array<{}, {len}>", cpp_type(ty)),
+    }
+}
+
+fn write_description(f: &mut String, indent: &str, description: Option<&str>) -> fmt::Result {
+    if let Some(description) = description {
+        for line in description.lines() {
+            writeln!(f, "{indent}/// {line}")?;
+        }
+    }
+    Ok(())
+}
+
+fn write_types(f: &mut String, network: &Network) -> fmt::Result {
+    for ty in composite_types_in_dependency_order(network.types()) {
+        match &ty as &Type {
+            Type::Enum {
+                name,
+                description,
+                size,
+                entries,
+                visibility: _,
+                annotations: _,
+            } => {
+                write_description(f, "", description.as_deref())?;
+                writeln!(f, "enum class {name} : uint{}_t {{", container_bits(*size))?;
+                for (entry_name, entry_value) in entries {
+                    writeln!(f, "  {entry_name} = {entry_value},")?;
+                }
+                writeln!(f, "}};")?;
+                writeln!(f)?;
+            }
+            Type::Struct {
+                name,
+                description,
+                attribs,
+                visibility: _,
+                annotations: _,
+            } => {
+                write_description(f, "", description.as_deref())?;
+                writeln!(f, "struct {name} {{")?;
+                for (attrib_name, attrib_type) in attribs {
+                    writeln!(f, "  {} {attrib_name};", cpp_type(attrib_type))?;
+                }
+                writeln!(f, "}};")?;
+                writeln!(f)?;
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn write_message(f: &mut String, message: &Message) -> fmt::Result {
+    let name = message.name();
+    write_description(f, "", message.description())?;
+    match message.deprecation() {
+        Some(deprecation) => writeln!(
+            f,
+            "struct [[deprecated(\"since {}: {}\")]] {name} {{",
+            deprecation.since(),
+            deprecation.note().replace('"', "'")
+        )?,
+        None => writeln!(f, "struct {name} {{")?,
+    }
+    writeln!(f, "  static constexpr uint32_t id = {:#X};", message.id().as_u32())?;
+    writeln!(f, "  static constexpr bool ide = {};", message.id().ide())?;
+    // the dlc of XL messages doesn't hold the payload length.
+    let len_type = match message.xl_frame() {
+        Some(_) => {
+            writeln!(f, "  static constexpr uint16_t payload_len = {};", message.payload_len())?;
+            "uint16_t"
+        }
+        None => {
+            writeln!(f, "  static constexpr uint8_t dlc = {};", message.dlc())?;
+            "uint8_t"
+        }
+    };
+    writeln!(f, "  static constexpr uint32_t bus_id = {};", message.bus().id())?;
+    if let Some(fill) = message.bus().padding().fill_byte() {
+        writeln!(
+            f,
+            "  static constexpr {len_type} tx_len = {};",
+            message.bus().padding().transmitted_len(message.payload_len())
+        )?;
+        writeln!(f, "  static constexpr uint8_t padding = {fill:#04X};")?;
+    }
+    if let Some(deadline) = message.processing_deadline() {
+        writeln!(
+            f,
+            "  static constexpr uint32_t processing_deadline_us = {};",
+            deadline.as_micros()
+        )?;
+    }
+    if let Some(authentication) = message.authentication() {
+        writeln!(
+            f,
+            "  static constexpr uint32_t cmac_key_id = {};",
+            authentication.key_id()
+        )?;
+        writeln!(
+            f,
+            "  static constexpr uint8_t cmac_len = {};",
+            authentication.mac_len()
+        )?;
+    }
+    let wide = !fits_u64(message);
+    let frame_len = if wide { message.payload_len() } else { 8 };
+    if !wide {
+        writeln!(
+            f,
+            "  static constexpr uint64_t reserved_mask = {:#X}ull;",
+            reserved_mask(message)
+        )?;
+    }
+    writeln!(f)?;
+    for field in message_fields(message) {
+        match field {
+            MessageField::Typed(field_name, ty) => {
+                writeln!(f, "  {} {field_name};", cpp_type(&ty))?
+            }
+            MessageField::Raw(field_name, signal_type) => {
+                writeln!(f, "  {} {field_name};", cpp_signal_type(&signal_type))?
+            }
+        }
+    }
+    let leaves = signal_leaves(message);
+    let unused = if leaves.is_empty() { "[[maybe_unused]] " } else { "" };
+
+    writeln!(f)?;
+    writeln!(
+        f,
+        "  static {name} unpack({unused}detail::frame_view<{frame_len}> data) {{"
+    )?;
+    writeln!(f, "    {name} msg{{}};")?;
+    if !leaves.is_empty() && !wide {
+        writeln!(f, "    const uint64_t frame = detail::load_frame(data);")?;
+    }
+    for leaf in &leaves {
+        let path = leaf.path.join(".");
+        let signal = &leaf.signal;
+        let raw = if wide {
+            format!(
+                "detail::load_bits(data, {}, {})",
+                signal.byte_offset(),
+                signal.size()
+            )
+        } else {
+            format!(
+                "((frame >> {}) & {:#X}ull)",
+                signal.byte_offset(),
+                signal_mask(signal.ty())
+            )
+        };
+        match (&leaf.enum_type, signal.ty()) {
+            (Some(enum_type), _) => {
+                writeln!(f, "    msg.{path} = static_cast<{}>({raw});", cpp_type(enum_type))?
+            }
+            (None, SignalType::UnsignedInt { size: _ }) => writeln!(
+                f,
+                "    msg.{path} = static_cast<{}>({raw});",
+                cpp_signal_type(signal.ty())
+            )?,
+            (None, SignalType::SignedInt { size }) => writeln!(
+                f,
+                "    msg.{path} = static_cast<{}>(detail::sign_extend({raw}, {size}));",
+                cpp_signal_type(signal.ty())
+            )?,
+            (
+                None,
+                SignalType::Decimal {
+                    size: _,
+                    offset,
+                    scale,
+                },
+            ) => writeln!(
+                f,
+                "    msg.{path} = static_cast<{}>({raw} * {scale:?} + {offset:?});",
+                cpp_signal_type(signal.ty())
+            )?,
+            (None, SignalType::Time { .. }) => {
+                writeln!(f, "    msg.{path} = {}({raw});", cpp_signal_type(signal.ty()))?
+            }
+        }
+    }
+    writeln!(f, "    return msg;")?;
+    writeln!(f, "  }}")?;
+
+    writeln!(f)?;
+    writeln!(f, "  void pack(detail::frame_buffer<{frame_len}> data) const {{")?;
+    if wide {
+        writeln!(f, "    std::fill(data.begin(), data.end(), 0);")?;
+    } else {
+        writeln!(f, "    uint64_t frame = 0;")?;
+    }
+    for leaf in &leaves {
+        let path = leaf.path.join(".");
+        let signal = &leaf.signal;
+        let mask = signal_mask(signal.ty());
+        let value = match (&leaf.enum_type, signal.ty()) {
+            (Some(_), _) | (None, SignalType::UnsignedInt { size: _ }) => {
+                format!("(static_cast<uint64_t>(this->{path}) & {mask:#X}ull)")
+            }
+            (None, SignalType::SignedInt { size: _ }) => format!(
+                "(static_cast<uint64_t>(static_cast<int64_t>(this->{path})) & {mask:#X}ull)"
+            ),
+            (
+                None,
+                SignalType::Decimal {
+                    size: _,
+                    offset: value_offset,
+                    scale,
+                },
+            ) => format!(
+                "detail::pack_decimal(this->{path}, {value_offset:?}, {scale:?}, {mask:#X}ull)"
+            ),
+            // timestamps wrap around, durations saturate.
+            (None, SignalType::Time { kind: TimeKind::Timestamp, .. }) => {
+                format!("(static_cast<uint64_t>(this->{path}.count()) & {mask:#X}ull)")
+            }
+            (None, SignalType::Time { kind: TimeKind::Duration, .. }) => {
+                format!("std::min<uint64_t>(this->{path}.count(), {mask:#X}ull)")
+            }
+        };
+        if wide {
+            writeln!(
+                f,
+                "    detail::store_bits(data, {}, {}, {value});",
+                signal.byte_offset(),
+                signal.size()
+            )?;
+        } else {
+            writeln!(f, "    frame |= {value} << {};", signal.byte_offset())?;
+        }
+    }
+    if !wide {
+        writeln!(f, "    detail::store_frame(frame, data);")?;
+    }
+    writeln!(f, "  }}")?;
+    writeln!(f)?;
+    writeln!(f, "  /// reserved bits have to be zero, otherwise the frame was")?;
+    writeln!(f, "  /// encoded with a newer layout of this message.")?;
+    if wide {
+        let reserved: Vec<String> = reserved_signals(message)
+            .map(|signal| {
+                format!(
+                    "detail::load_bits(data, {}, {}) == 0",
+                    signal.byte_offset(),
+                    signal.size()
+                )
+            })
+            .collect();
+        let unused = if reserved.is_empty() { "[[maybe_unused]] " } else { "" };
+        writeln!(
+            f,
+            "  static bool reserved_bits_zero({unused}detail::frame_view<{frame_len}> data) {{"
+        )?;
+        if reserved.is_empty() {
+            writeln!(f, "    return true;")?;
+        } else {
+            writeln!(f, "    return {};", reserved.join(" && "))?;
+        }
+    } else {
+        writeln!(
+            f,
+            "  static bool reserved_bits_zero(detail::frame_view<8> data) {{"
+        )?;
+        writeln!(
+            f,
+            "    return (detail::load_frame(data) & reserved_mask) == 0;"
+        )?;
+    }
+    writeln!(f, "  }}")?;
+    writeln!(f, "}};")?;
+    writeln!(f)?;
+    Ok(())
+}
+
+fn write_header(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "// generated by canzero-config, do not edit.")?;
+    writeln!(f, "// network hash : {:#X}", network.portable_hash())?;
+    writeln!(f, "// provenance : {}", network.provenance())?;
+    writeln!(f, "#pragma once")?;
+    writeln!(f)?;
+    writeln!(f, "#include <algorithm>")?;
+    writeln!(f, "#include <array>")?;
+    writeln!(f, "#include <chrono>")?;
+    writeln!(f, "#include <cstddef>")?;
+    writeln!(f, "#include <cstdint>")?;
+    writeln!(f, "#if __cplusplus >= 202002L")?;
+    writeln!(f, "#include <span>")?;
+    writeln!(f, "#endif")?;
+    writeln!(f)?;
+    writeln!(f, "namespace {NAMESPACE} {{")?;
+    writeln!(f)?;
+    writeln!(f, "namespace detail {{")?;
+    writeln!(f, "// buffers of encoded frames.")?;
+    writeln!(f, "#if __cplusplus >= 202002L")?;
+    writeln!(f, "template <std::size_t N> using frame_view = std::span<const uint8_t, N>;")?;
+    writeln!(f, "template <std::size_t N> using frame_buffer = std::span<uint8_t, N>;")?;
+    writeln!(f, "#else")?;
+    writeln!(f, "template <std::size_t N> using frame_view = const std::array<uint8_t, N>&;")?;
+    writeln!(f, "template <std::size_t N> using frame_buffer = std::array<uint8_t, N>&;")?;
+    writeln!(f, "#endif")?;
+    writeln!(
+        f,
+        "inline uint64_t load_frame(frame_view<8> data) {{"
+    )?;
+    writeln!(f, "  uint64_t frame = 0;")?;
+    writeln!(f, "  for (std::size_t i = 0; i < 8; ++i) {{")?;
+    writeln!(f, "    frame |= static_cast<uint64_t>(data[i]) << (8 * i);")?;
+    writeln!(f, "  }}")?;
+    writeln!(f, "  return frame;")?;
+    writeln!(f, "}}")?;
+    writeln!(
+        f,
+        "inline void store_frame(uint64_t frame, frame_buffer<8> data) {{"
+    )?;
+    writeln!(f, "  for (std::size_t i = 0; i < 8; ++i) {{")?;
+    writeln!(f, "    data[i] = static_cast<uint8_t>(frame >> (8 * i));")?;
+    writeln!(f, "  }}")?;
+    writeln!(f, "}}")?;
+    writeln!(f, "template <std::size_t N>")?;
+    writeln!(
+        f,
+        "inline uint64_t load_bits(frame_view<N> data, std::size_t offset, uint8_t size) {{"
+    )?;
+    writeln!(f, "  uint64_t value = 0;")?;
+    writeln!(f, "  for (uint8_t i = 0; i < size; ++i) {{")?;
+    writeln!(f, "    const std::size_t bit = offset + i;")?;
+    writeln!(
+        f,
+        "    value |= static_cast<uint64_t>((data[bit / 8] >> (bit % 8)) & 1u) << i;"
+    )?;
+    writeln!(f, "  }}")?;
+    writeln!(f, "  return value;")?;
+    writeln!(f, "}}")?;
+    writeln!(f, "template <std::size_t N>")?;
+    writeln!(
+        f,
+        "inline void store_bits(frame_buffer<N> data, std::size_t offset, uint8_t size, uint64_t value) {{"
+    )?;
+    writeln!(f, "  for (uint8_t i = 0; i < size; ++i) {{")?;
+    writeln!(f, "    const std::size_t bit = offset + i;")?;
+    writeln!(
+        f,
+        "    data[bit / 8] |= static_cast<uint8_t>(((value >> i) & 1u) << (bit % 8));"
+    )?;
+    writeln!(f, "  }}")?;
+    writeln!(f, "}}")?;
+    writeln!(f, "inline int64_t sign_extend(uint64_t raw, uint8_t size) {{")?;
+    writeln!(f, "  const uint64_t sign = static_cast<uint64_t>(1) << (size - 1);")?;
+    writeln!(f, "  return static_cast<int64_t>((raw ^ sign) - sign);")?;
+    writeln!(f, "}}")?;
+    writeln!(
+        f,
+        "inline uint64_t pack_decimal(double value, double offset, double scale, uint64_t mask) {{"
+    )?;
+    writeln!(f, "  const double raw = (value - offset) / scale + 0.5;")?;
+    writeln!(f, "  if (raw <= 0.0) return 0;")?;
+    writeln!(f, "  if (raw >= static_cast<double>(mask)) return mask;")?;
+    writeln!(f, "  return static_cast<uint64_t>(raw);")?;
+    writeln!(f, "}}")?;
+    writeln!(f, "}} // namespace detail")?;
+    writeln!(f)?;
+
+    if !network.constants().is_empty() {
+        writeln!(f, "namespace constants {{")?;
+        for constant in network.constants() {
+            writeln!(
+                f,
+                "inline constexpr double {} = {:?}; // {}",
+                constant.name(),
+                constant.value(),
+                constant.unit()
+            )?;
+        }
+        writeln!(f, "}} // namespace constants")?;
+        writeln!(f)?;
+    }
+
+    write_types(f, network)?;
+
+    // object entry ids (od index) that the nvm layer of a node stores.
+    let persistent_nodes: Vec<_> = network
+        .nodes()
+        .iter()
+        .filter(|node| node.object_entries().iter().any(|oe| oe.persistent()))
+        .collect();
+    if !persistent_nodes.is_empty() {
+        writeln!(f, "namespace persistent {{")?;
+        for node in persistent_nodes {
+            let ids: Vec<String> = node
+                .persistent_object_entries()
+                .iter()
+                .map(|oe| oe.id().to_string())
+                .collect();
+            writeln!(
+                f,
+                "inline constexpr std::array<uint32_t, {}> {}_object_entries = {{{}}};",
+                ids.len(),
+                node.name(),
+                ids.join(", ")
+            )?;
+        }
+        writeln!(f, "}} // namespace persistent")?;
+        writeln!(f)?;
+    }
+
+    writeln!(f, "namespace messages {{")?;
+    writeln!(f)?;
+    for message in network.messages() {
+        write_message(f, message)?;
+    }
+    writeln!(f, "}} // namespace messages")?;
+    writeln!(f)?;
+
+    writeln!(f, "struct message_info {{")?;
+    writeln!(f, "  const char* name;")?;
+    writeln!(f, "  uint32_t id;")?;
+    writeln!(f, "  bool ide;")?;
+    writeln!(f, "  uint16_t payload_len;")?;
+    writeln!(f, "  uint32_t bus_id;")?;
+    writeln!(f, "}};")?;
+    writeln!(f)?;
+    writeln!(
+        f,
+        "inline constexpr std::array<message_info, {}> message_table = {{{{",
+        network.messages().len()
+    )?;
+    for message in network.messages() {
+        writeln!(
+            f,
+            "  {{\"{}\", {:#X}, {}, {}, {}}},",
+            message.name(),
+            message.id().as_u32(),
+            message.id().ide(),
+            message.payload_len(),
+            message.bus().id()
+        )?;
+    }
+    writeln!(f, "}}}};")?;
+    writeln!(f)?;
+    writeln!(f, "}} // namespace {NAMESPACE}")?;
+    Ok(())
+}
+
+impl Network {
+    /// Generates a C++ header (C++17, frames are passed as std::span in C++20)
+    /// with type safe enums and structs and pack/unpack functions for all messages.
+    pub fn generate_cpp(&self) -> String {
+        let mut header = String::new();
+        write_header(&mut header, self).expect("writing into a String can't fail");
+        header
+    }
+}
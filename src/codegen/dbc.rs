@@ -0,0 +1,277 @@
+use std::fmt::{self, Write};
+
+use crate::config::{
+    message::MessageUsage, Annotations, Message, Network, SignalType, TypeSignalEncoding,
+};
+
+use super::signal_mask;
+
+// Generates a dbc file of the network, signals are exported as
+// little endian (intel) signals at their bit offset. Annotations of
+// nodes, messages and signals are exported as string attributes,
+// remote requestable messages (RTR) as RemoteRequest* attributes.
+
+const NO_NODE: &str = "Vector__XXX";
+
+fn transmitter_name<'a>(network: &'a Network, message: &Message) -> &'a str {
+    network
+        .nodes()
+        .iter()
+        .find(|node| node.tx_messages().iter().any(|m| m.name() == message.name()))
+        .map(|node| node.name())
+        .unwrap_or(NO_NODE)
+}
+
+fn receiver_names(network: &Network, message: &Message) -> String {
+    let receivers: Vec<&str> = network
+        .nodes()
+        .iter()
+        .filter(|node| node.rx_messages().iter().any(|m| m.name() == message.name()))
+        .map(|node| node.name())
+        .collect();
+    if receivers.is_empty() {
+        NO_NODE.to_owned()
+    } else {
+        receivers.join(",")
+    }
+}
+
+fn collect_signal_names(encoding: &TypeSignalEncoding, names: &mut Vec<String>) {
+    match encoding {
+        TypeSignalEncoding::Composite(composite) => {
+            for attribute in composite.attributes() {
+                collect_signal_names(attribute, names);
+            }
+        }
+        TypeSignalEncoding::Primitive(primitive) => {
+            names.push(primitive.signal().name().to_owned())
+        }
+    }
+}
+
+/// (signal name, unit) of stream messages, the signals inherit
+/// the unit of the object entry they encode.
+fn signal_units(message: &Message) -> Vec<(String, String)> {
+    let (MessageUsage::Stream(stream), Some(encoding)) = (message.usage(), message.encoding())
+    else {
+        return vec![];
+    };
+    let mut units = vec![];
+    for (attribute, object_entry) in std::iter::zip(encoding.attributes(), stream.mapping()) {
+        let Some(unit) = object_entry.as_ref().and_then(|oe| oe.unit()) else {
+            continue;
+        };
+        let mut names = vec![];
+        collect_signal_names(attribute, &mut names);
+        units.extend(names.into_iter().map(|name| (name, unit.to_owned())));
+    }
+    units
+}
+
+// extended ids are marked with the msb.
+fn dbc_id(message: &Message) -> u32 {
+    if message.id().ide() {
+        message.id().as_u32() | 0x80000000
+    } else {
+        message.id().as_u32()
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('"', "'")
+}
+
+/// description followed by a line per documentation field.
+fn message_comment(message: &Message) -> Option<String> {
+    let documentation = message.documentation();
+    let lines: Vec<String> = message
+        .description()
+        .map(str::to_owned)
+        .into_iter()
+        .chain(documentation.author().map(|author| format!("author: {author}")))
+        .chain(documentation.requirement().map(|requirement| format!("requirement: {requirement}")))
+        .chain(documentation.revision_note().map(|note| format!("revision: {note}")))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// (object type, key) of every annotation, each key is defined once
+/// per object type.
+fn attribute_definitions(network: &Network) -> Vec<(&'static str, String)> {
+    let mut definitions = vec![];
+    let mut define = |object_type: &'static str, annotations: &Annotations| {
+        for key in annotations.keys() {
+            if !definitions.iter().any(|(t, k)| *t == object_type && k == key) {
+                definitions.push((object_type, key.clone()));
+            }
+        }
+    };
+    for node in network.nodes() {
+        define("BU_", node.annotations());
+    }
+    for message in network.messages() {
+        define("BO_", message.annotations());
+        for signal in message.signals() {
+            define("SG_", signal.annotations());
+        }
+    }
+    definitions
+}
+
+/// dbc has no notion of remote frames, remote requestable messages are
+/// marked with attributes, the latency is in microseconds.
+fn write_remote_request_definitions(f: &mut String, network: &Network) -> fmt::Result {
+    if !network.messages().iter().any(|message| message.remote_request().is_some()) {
+        return Ok(());
+    }
+    writeln!(f, "BA_DEF_ BO_ \"RemoteRequestable\" INT 0 1;")?;
+    writeln!(f, "BA_DEF_ BO_ \"RemoteRequesters\" STRING ;")?;
+    writeln!(f, "BA_DEF_ BO_ \"RemoteResponseLatency\" INT 0 2147483647;")?;
+    writeln!(f, "BA_DEF_DEF_ \"RemoteRequestable\" 0;")?;
+    writeln!(f, "BA_DEF_DEF_ \"RemoteRequesters\" \"\";")?;
+    writeln!(f, "BA_DEF_DEF_ \"RemoteResponseLatency\" 0;")
+}
+
+fn write_remote_requests(f: &mut String, message: &Message) -> fmt::Result {
+    let Some(remote_request) = message.remote_request() else {
+        return Ok(());
+    };
+    let dbc_id = dbc_id(message);
+    writeln!(f, "BA_ \"RemoteRequestable\" BO_ {dbc_id} 1;")?;
+    if !remote_request.requesters().is_empty() {
+        writeln!(
+            f,
+            "BA_ \"RemoteRequesters\" BO_ {dbc_id} \"{}\";",
+            remote_request.requesters().join(",")
+        )?;
+    }
+    if let Some(latency) = remote_request.response_latency() {
+        writeln!(f, "BA_ \"RemoteResponseLatency\" BO_ {dbc_id} {};", latency.as_micros())?;
+    }
+    Ok(())
+}
+
+fn write_attributes(f: &mut String, network: &Network) -> fmt::Result {
+    let definitions = attribute_definitions(network);
+    for (object_type, key) in &definitions {
+        writeln!(f, "BA_DEF_ {object_type} \"{}\" STRING ;", escape(key))?;
+    }
+    let mut keys: Vec<&String> = definitions.iter().map(|(_, key)| key).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        writeln!(f, "BA_DEF_DEF_ \"{}\" \"\";", escape(key))?;
+    }
+    write_remote_request_definitions(f, network)?;
+    for node in network.nodes() {
+        for (key, value) in node.annotations() {
+            writeln!(f, "BA_ \"{}\" BU_ {} \"{}\";", escape(key), node.name(), escape(value))?;
+        }
+    }
+    for message in network.messages() {
+        write_remote_requests(f, message)?;
+        let dbc_id = dbc_id(message);
+        for (key, value) in message.annotations() {
+            writeln!(f, "BA_ \"{}\" BO_ {dbc_id} \"{}\";", escape(key), escape(value))?;
+        }
+        for signal in message.signals() {
+            for (key, value) in signal.annotations() {
+                writeln!(
+                    f,
+                    "BA_ \"{}\" SG_ {dbc_id} {} \"{}\";",
+                    escape(key),
+                    signal.name(),
+                    escape(value)
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_dbc(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "VERSION \"{:#X}\"", network.portable_hash())?;
+    writeln!(f)?;
+    writeln!(f, "NS_ :")?;
+    writeln!(f)?;
+    writeln!(f, "BS_:")?;
+    writeln!(f)?;
+    write!(f, "BU_:")?;
+    for node in network.nodes() {
+        write!(f, " {}", node.name())?;
+    }
+    writeln!(f)?;
+    writeln!(f)?;
+
+    for message in network.messages() {
+        let dbc_id = dbc_id(message);
+        writeln!(
+            f,
+            "BO_ {dbc_id} {}: {} {}",
+            message.name(),
+            message.payload_len(),
+            transmitter_name(network, message)
+        )?;
+        let receivers = receiver_names(network, message);
+        let units = signal_units(message);
+        for signal in message.signals() {
+            let (sign, min, max) = match signal.ty() {
+                SignalType::UnsignedInt { size: _ } => {
+                    ("+", 0.0, signal_mask(signal.ty()) as f64)
+                }
+                SignalType::SignedInt { size: _ } => {
+                    let half = (signal_mask(signal.ty()) >> 1) as f64;
+                    ("-", -half - 1.0, half)
+                }
+                SignalType::Decimal {
+                    size: _,
+                    offset,
+                    scale,
+                } => (
+                    "+",
+                    *offset,
+                    offset + scale * signal_mask(signal.ty()) as f64,
+                ),
+                SignalType::Time { .. } => ("+", 0.0, signal.scale() * signal_mask(signal.ty()) as f64),
+            };
+            // decoded time values are seconds.
+            let default_unit = match signal.ty() {
+                SignalType::Time { .. } => "s".to_owned(),
+                _ => String::new(),
+            };
+            let unit = units
+                .iter()
+                .find(|(name, _)| name == signal.name())
+                .map(|(_, unit)| escape(unit))
+                .unwrap_or(default_unit);
+            writeln!(
+                f,
+                " SG_ {} : {}|{}@1{sign} ({},{}) [{min}|{max}] \"{unit}\" {receivers}",
+                signal.name(),
+                signal.byte_offset(),
+                signal.size(),
+                signal.scale(),
+                signal.offset(),
+            )?;
+        }
+        writeln!(f)?;
+    }
+
+    writeln!(f, "CM_ \"{}\";", escape(&network.provenance().to_string()))?;
+    for message in network.messages() {
+        if let Some(comment) = message_comment(message) {
+            let dbc_id = dbc_id(message);
+            writeln!(f, "CM_ BO_ {dbc_id} \"{}\";", escape(&comment))?;
+        }
+    }
+    write_attributes(f, network)
+}
+
+impl Network {
+    /// Generates a dbc file, e.g. for bus analysis tools.
+    pub fn generate_dbc(&self) -> String {
+        let mut dbc = String::new();
+        write_dbc(&mut dbc, self).expect("writing into a String can't fail");
+        dbc
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::{self, Write};
+
+use crate::config::Network;
+
+// Generates a flat list of named constants for every message id, node id,
+// object entry index and command of the network, as a C header of #defines
+// or as a Rust module of consts. Intended for handwritten firmware that
+// can't consume the full generated codecs yet but shouldn't hardcode ids.
+
+const PREFIX: &str = "CANZERO";
+
+enum Define {
+    Value {
+        name: String,
+        value: String,
+        rust_type: &'static str,
+    },
+    /// refers to another define, e.g. the request id of a command.
+    Alias {
+        name: String,
+        target: String,
+        rust_type: &'static str,
+    },
+}
+
+fn define_name(parts: &[&str]) -> String {
+    let mut name = PREFIX.to_owned();
+    for part in parts {
+        name.push('_');
+        name.push_str(&part.to_uppercase());
+    }
+    name
+}
+
+fn message_id_name(message: &str) -> String {
+    define_name(&[message, "id"])
+}
+
+fn defines(network: &Network) -> Vec<(String, Vec<Define>)> {
+    let mut sections = vec![];
+
+    let nodes = network
+        .nodes()
+        .iter()
+        .map(|node| Define::Value {
+            name: define_name(&[node.name(), "node_id"]),
+            value: node.id().to_string(),
+            rust_type: "u8",
+        })
+        .collect();
+    sections.push(("node ids".to_owned(), nodes));
+
+    let mut messages = vec![];
+    for message in network.messages() {
+        messages.push(Define::Value {
+            name: message_id_name(message.name()),
+            value: format!("{:#X}", message.id().as_u32()),
+            rust_type: "u32",
+        });
+        messages.push(Define::Value {
+            name: define_name(&[message.name(), "ide"]),
+            value: (message.id().ide() as u8).to_string(),
+            rust_type: "u8",
+        });
+    }
+    sections.push(("message ids".to_owned(), messages));
+
+    let mut object_entries = vec![];
+    for node in network.nodes() {
+        for object_entry in node.object_entries() {
+            object_entries.push(Define::Value {
+                name: define_name(&[node.name(), object_entry.name(), "oe_index"]),
+                value: object_entry.id().to_string(),
+                rust_type: "u32",
+            });
+        }
+    }
+    sections.push(("object entry indices".to_owned(), object_entries));
+
+    let mut commands = vec![];
+    for node in network.nodes() {
+        for command in node.commands() {
+            commands.push(Define::Alias {
+                name: define_name(&[node.name(), command.name(), "cmd_req_id"]),
+                target: message_id_name(command.tx_message().name()),
+                rust_type: "u32",
+            });
+            commands.push(Define::Alias {
+                name: define_name(&[node.name(), command.name(), "cmd_resp_id"]),
+                target: message_id_name(command.rx_message().name()),
+                rust_type: "u32",
+            });
+        }
+    }
+    sections.push(("command ids".to_owned(), commands));
+
+    sections
+}
+
+fn write_c_defines(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "// generated by canzero-config, do not edit.")?;
+    writeln!(f, "// network hash : {:#X}", network.portable_hash())?;
+    writeln!(f, "#pragma once")?;
+    for (section, defines) in defines(network) {
+        writeln!(f)?;
+        writeln!(f, "// {section}")?;
+        for define in defines {
+            match define {
+                Define::Value { name, value, rust_type: _ } => {
+                    writeln!(f, "#define {name} {value}")?
+                }
+                Define::Alias { name, target, rust_type: _ } => {
+                    writeln!(f, "#define {name} {target}")?
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_rust_defines(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "// generated by canzero-config, do not edit.")?;
+    writeln!(f, "// network hash : {:#X}", network.portable_hash())?;
+    for (section, defines) in defines(network) {
+        writeln!(f)?;
+        writeln!(f, "// {section}")?;
+        for define in defines {
+            match define {
+                Define::Value { name, value, rust_type } => {
+                    writeln!(f, "pub const {name}: {rust_type} = {value};")?
+                }
+                Define::Alias { name, target, rust_type } => {
+                    writeln!(f, "pub const {name}: {rust_type} = {target};")?
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a C header with a #define for the id of every node and
+    /// message, the index of every object entry and the request and response
+    /// ids of every command (e.g. CANZERO_SECU_CPU_TEMP_OE_INDEX).
+    pub fn generate_c_defines(&self) -> String {
+        let mut header = String::new();
+        write_c_defines(&mut header, self).expect("writing into a String can't fail");
+        header
+    }
+
+    /// Same constants as generate_c_defines, as a no_std Rust module of consts.
+    pub fn generate_rust_defines(&self) -> String {
+        let mut module = String::new();
+        write_rust_defines(&mut module, self).expect("writing into a String can't fail");
+        module
+    }
+}
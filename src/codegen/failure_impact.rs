@@ -0,0 +1,65 @@
+use std::fmt::{self, Write};
+
+use crate::config::{FailureImpact, ImpactedItem, Network};
+
+// Generates the single node failure matrix as a markdown table for the
+// failure mode analysis. Each row is a failed node, each column a node that
+// keeps running and the cells list what the running node loses.
+
+fn lost_by<'a>(items: &'a [ImpactedItem], node: &str) -> Vec<&'a str> {
+    items
+        .iter()
+        .filter(|item| item.receivers().iter().any(|receiver| receiver == node))
+        .map(|item| item.name())
+        .collect()
+}
+
+fn cell(impact: &FailureImpact, node: &str) -> String {
+    let groups = [
+        ("streams", lost_by(impact.streams(), node)),
+        ("commands", lost_by(impact.commands(), node)),
+        ("object entries", lost_by(impact.object_entries(), node)),
+    ];
+    groups
+        .iter()
+        .filter(|(_, names)| !names.is_empty())
+        .map(|(kind, names)| format!("{kind}: {}", names.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn write_matrix(f: &mut String, network: &Network) -> fmt::Result {
+    write!(f, "| failed node |")?;
+    for node in network.nodes() {
+        write!(f, " {} |", node.name())?;
+    }
+    writeln!(f)?;
+    write!(f, "|---|")?;
+    for _ in network.nodes() {
+        write!(f, "---|")?;
+    }
+    writeln!(f)?;
+    for impact in network.failure_impact() {
+        write!(f, "| {} |", impact.node())?;
+        for other in network.nodes() {
+            if other.name() == impact.node() {
+                write!(f, " - |")?;
+            } else {
+                write!(f, " {} |", cell(&impact, other.name()))?;
+            }
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a markdown table of the single node failure impact, rows
+    /// are the failed nodes, columns the nodes losing streams, commands or
+    /// object entries (see Network::failure_impact).
+    pub fn generate_failure_impact_matrix(&self) -> String {
+        let mut markdown = String::new();
+        write_matrix(&mut markdown, self).expect("writing into a String can't fail");
+        markdown
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::{self, Write};
+
+use crate::config::{MessageRef, Network};
+
+// Generates a csv map of the id space of every bus. Each row is either a
+// message or a free range between two messages, ordered by id, which is
+// also the arbitration order (lower ids win).
+
+const STD_ID_MAX: u32 = 0x7FF;
+const EXT_ID_MAX: u32 = 0x1FFF_FFFF;
+
+const HEADER: &str = "bus,ide,first_id,last_id,kind,message,setcode,setcode_len,priority_bucket,position";
+
+fn optional(v: Option<u32>) -> String {
+    v.map(|v| v.to_string()).unwrap_or_default()
+}
+
+fn write_free(f: &mut String, bus: &str, ide: &str, first: u32, last: u32) -> fmt::Result {
+    writeln!(f, "{bus},{ide},{first:#X},{last:#X},free,,,,,")
+}
+
+fn write_id_space(
+    f: &mut String,
+    bus: &str,
+    ide: bool,
+    max_id: u32,
+    messages: &[&MessageRef],
+) -> fmt::Result {
+    let ide_name = if ide { "ext" } else { "std" };
+    let mut next_free = 0;
+    for message in messages {
+        let id = message.id().as_u32();
+        if id > next_free {
+            write_free(f, bus, ide_name, next_free, id - 1)?;
+        }
+        let info = message.resolution_info();
+        writeln!(
+            f,
+            "{bus},{ide_name},{id:#X},{id:#X},message,{},{},{},{},{}",
+            message.name(),
+            optional(info.setcode()),
+            info.setcode_len(),
+            optional(info.priority_bucket()),
+            optional(info.position()),
+        )?;
+        next_free = id + 1;
+    }
+    if next_free <= max_id {
+        write_free(f, bus, ide_name, next_free, max_id)?;
+    }
+    Ok(())
+}
+
+fn write_id_map(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "{HEADER}")?;
+    for bus in network.buses() {
+        let mut messages: Vec<&MessageRef> = network
+            .messages()
+            .iter()
+            .filter(|message| message.bus().id() == bus.id())
+            .collect();
+        messages.sort_by_key(|message| message.id().as_u32());
+        let (ext, std): (Vec<&MessageRef>, Vec<&MessageRef>) =
+            messages.into_iter().partition(|message| message.id().ide());
+        write_id_space(f, bus.name(), false, STD_ID_MAX, &std)?;
+        // the extended id space is only listed if it is used at all.
+        if !ext.is_empty() {
+            write_id_space(f, bus.name(), true, EXT_ID_MAX, &ext)?;
+        }
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a csv map of the occupied and free id ranges of every bus,
+    /// messages are annotated with their setcode, priority bucket and position
+    /// (see Message::resolution_info).
+    pub fn id_map_csv(&self) -> String {
+        let mut csv = String::new();
+        write_id_map(&mut csv, self).expect("writing into a String can't fail");
+        csv
+    }
+}
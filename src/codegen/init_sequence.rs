@@ -0,0 +1,72 @@
+use std::fmt::{self, Write};
+
+use crate::config::{Message, Network};
+
+// Generates a C header for the startup code of the master, with a table of
+// the commands it calls on every node (NodeBuilder::init_sequence). The
+// master sends the request of a step and only continues with the next step
+// after the response arrived or the timeout expired. Nodes are initialized
+// in the order of Network::nodes.
+
+const STEP_TYPE: &str = "\
+typedef struct {
+  const char* command;
+  uint8_t node_id;
+  uint32_t req_id;
+  uint8_t req_ide;
+  uint32_t resp_id;
+  uint8_t resp_ide;
+  // 0 if the command has no timeout.
+  uint32_t timeout_us;
+} canzero_init_step;
+";
+
+fn id(message: &Message) -> String {
+    format!("{:#X}, {}", message.id().as_u32(), message.id().ide() as u8)
+}
+
+fn write_init_sequence(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "// generated by canzero-config, do not edit.")?;
+    writeln!(f, "// network hash : {:#X}", network.portable_hash())?;
+    writeln!(f, "#pragma once")?;
+    writeln!(f, "#include <stdint.h>")?;
+    writeln!(f)?;
+    write!(f, "{STEP_TYPE}")?;
+    for node in network.nodes() {
+        if node.init_sequence().is_empty() {
+            continue;
+        }
+        let name = node.name().to_uppercase();
+        writeln!(f)?;
+        writeln!(f, "static const canzero_init_step CANZERO_{name}_INIT_SEQUENCE[] = {{")?;
+        for command in node.init_sequence() {
+            writeln!(
+                f,
+                "  {{ \"{}\", {}, {}, {}, {} }},",
+                command.name(),
+                node.id(),
+                id(command.tx_message()),
+                id(command.rx_message()),
+                command.timeout().map(|timeout| timeout.as_micros()).unwrap_or(0)
+            )?;
+        }
+        writeln!(f, "}};")?;
+        writeln!(
+            f,
+            "#define CANZERO_{name}_INIT_SEQUENCE_LEN {}",
+            node.init_sequence().len()
+        )?;
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a C header with the init sequence of every node as a table
+    /// of command steps (e.g. CANZERO_SECU_INIT_SEQUENCE), which the startup
+    /// code of the master executes in order.
+    pub fn generate_init_sequence(&self) -> String {
+        let mut header = String::new();
+        write_init_sequence(&mut header, self).expect("writing into a String can't fail");
+        header
+    }
+}
@@ -0,0 +1,142 @@
+use std::fmt::{self, Display, Write};
+
+// Minimal json document model, used by all generators that emit json.
+
+#[derive(Debug, Clone)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    // already formatted number.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn string(s: &str) -> Json {
+        Json::String(s.to_owned())
+    }
+    pub fn uint(v: u64) -> Json {
+        Json::Number(v.to_string())
+    }
+    pub fn int(v: i64) -> Json {
+        Json::Number(v.to_string())
+    }
+    pub fn float(v: f64) -> Json {
+        if v.is_finite() {
+            Json::Number(format!("{v:?}"))
+        } else {
+            Json::Null
+        }
+    }
+    pub fn object<const N: usize>(entries: [(&str, Json); N]) -> Json {
+        Json::Object(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        )
+    }
+
+    /// json without any whitespace.
+    pub fn to_compact_string(&self) -> String {
+        let mut compact = String::new();
+        self.write_compact(&mut compact)
+            .expect("writing into a String can't fail");
+        compact
+    }
+
+    fn write_compact(&self, f: &mut impl Write) -> fmt::Result {
+        match self {
+            Json::Array(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    value.write_compact(f)?;
+                }
+                write!(f, "]")
+            }
+            Json::Object(entries) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ",")?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":")?;
+                    value.write_compact(f)?;
+                }
+                write!(f, "}}")
+            }
+            other => other.write_pretty(f, 0),
+        }
+    }
+
+    fn write_pretty(&self, f: &mut impl Write, indent: usize) -> fmt::Result {
+        let pad = "  ".repeat(indent + 1);
+        let end_pad = "  ".repeat(indent);
+        match self {
+            Json::Null => write!(f, "null"),
+            Json::Bool(b) => write!(f, "{b}"),
+            Json::Number(n) => write!(f, "{n}"),
+            Json::String(s) => write_escaped(f, s),
+            Json::Array(values) => {
+                if values.is_empty() {
+                    return write!(f, "[]");
+                }
+                writeln!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    write!(f, "{pad}")?;
+                    value.write_pretty(f, indent + 1)?;
+                    if i + 1 != values.len() {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+                write!(f, "{end_pad}]")
+            }
+            Json::Object(entries) => {
+                if entries.is_empty() {
+                    return write!(f, "{{}}");
+                }
+                writeln!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    write!(f, "{pad}")?;
+                    write_escaped(f, key)?;
+                    write!(f, ": ")?;
+                    value.write_pretty(f, indent + 1)?;
+                    if i + 1 != entries.len() {
+                        write!(f, ",")?;
+                    }
+                    writeln!(f)?;
+                }
+                write!(f, "{end_pad}}}")
+            }
+        }
+    }
+}
+
+fn write_escaped(f: &mut impl Write, s: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_pretty(f, 0)
+    }
+}
@@ -0,0 +1,179 @@
+use crate::config::{Message, SignalRef, SignalType, Type, TypeRef, TypeSignalEncoding};
+
+mod bundle;
+mod conformance;
+mod cpp;
+mod dbc;
+mod defines;
+mod failure_impact;
+mod id_map;
+mod init_sequence;
+mod json;
+mod proto;
+mod python;
+mod rust;
+mod serialize;
+mod supervision;
+mod time_windows;
+mod topology;
+mod typescript;
+
+// Helpers shared between the code generation targets.
+
+/// all struct and enum types that are referenced by the given types,
+/// in an order where each type is listed after its dependencies.
+fn composite_types_in_dependency_order(types: &[TypeRef]) -> Vec<TypeRef> {
+    fn visit(ty: &TypeRef, ordered: &mut Vec<TypeRef>) {
+        match ty as &Type {
+            Type::Primitive(_) => (),
+            Type::Array { len: _, ty } => visit(ty, ordered),
+            Type::Struct {
+                name: _,
+                description: _,
+                attribs,
+                visibility: _,
+                annotations: _,
+            } => {
+                for (_, attrib_ty) in attribs {
+                    visit(attrib_ty, ordered);
+                }
+                if !ordered.iter().any(|t| t.name() == ty.name()) {
+                    ordered.push(ty.clone());
+                }
+            }
+            Type::Enum {
+                name: _,
+                description: _,
+                size: _,
+                entries: _,
+                visibility: _,
+                annotations: _,
+            } => {
+                if !ordered.iter().any(|t| t.name() == ty.name()) {
+                    ordered.push(ty.clone());
+                }
+            }
+        }
+    }
+    let mut ordered = vec![];
+    for ty in types {
+        visit(ty, &mut ordered);
+    }
+    ordered
+}
+
+/// smallest power of two bit width (at least 8) that can hold size bits.
+fn container_bits(size: u8) -> u8 {
+    match size {
+        0..=8 => 8,
+        9..=16 => 16,
+        17..=32 => 32,
+        _ => 64,
+    }
+}
+
+fn signal_mask(signal_type: &SignalType) -> u64 {
+    u64::MAX.checked_shr(64 - signal_type.size() as u32).unwrap_or(0)
+}
+
+/// the payload fits into a single u64. Larger payloads (CAN XL frames)
+/// are packed bit by bit into a byte buffer of payload_len bytes.
+fn fits_u64(message: &Message) -> bool {
+    message.payload_len() <= 8
+}
+
+/// bits of the frame that are reserved and have to be zero,
+/// only defined for messages that fit into a u64.
+fn reserved_mask(message: &Message) -> u64 {
+    debug_assert!(fits_u64(message));
+    reserved_signals(message).fold(0, |mask, signal| {
+        mask | (signal_mask(signal.ty()) << signal.byte_offset())
+    })
+}
+
+fn reserved_signals(message: &Message) -> impl Iterator<Item = &SignalRef> {
+    message.signals().iter().filter(|signal| signal.is_reserved())
+}
+
+/// top level field of a decoded message.
+/// Messages with an encoding map to their types, all other
+/// messages (e.g. imported from a dbc) map to their raw signals.
+enum MessageField {
+    Typed(String, TypeRef),
+    Raw(String, SignalType),
+}
+
+fn message_fields(message: &Message) -> Vec<MessageField> {
+    match message.encoding() {
+        Some(encoding) => encoding
+            .attributes()
+            .iter()
+            .map(|attrib| MessageField::Typed(attrib.name().to_owned(), attrib.ty().clone()))
+            .collect(),
+        None => message
+            .signals()
+            .iter()
+            .filter(|signal| !signal.is_reserved())
+            .map(|signal| MessageField::Raw(signal.name().to_owned(), signal.ty().clone()))
+            .collect(),
+    }
+}
+
+/// a signal of a message together with the path of the
+/// decoded value it is mapped to.
+struct SignalLeaf {
+    path: Vec<String>,
+    signal: SignalRef,
+    enum_type: Option<TypeRef>,
+}
+
+fn signal_leaves(message: &Message) -> Vec<SignalLeaf> {
+    fn visit(encoding: &TypeSignalEncoding, path: &mut Vec<String>, leaves: &mut Vec<SignalLeaf>) {
+        path.push(encoding.name().to_owned());
+        match encoding {
+            TypeSignalEncoding::Composite(composite) => {
+                for attrib in composite.attributes() {
+                    visit(attrib, path, leaves);
+                }
+            }
+            TypeSignalEncoding::Primitive(primitive) => {
+                let enum_type = match primitive.ty() as &Type {
+                    Type::Enum {
+                        name: _,
+                        description: _,
+                        size: _,
+                        entries: _,
+                        visibility: _,
+                        annotations: _,
+                    } => Some(primitive.ty().clone()),
+                    _ => None,
+                };
+                leaves.push(SignalLeaf {
+                    path: path.clone(),
+                    signal: primitive.signal().clone(),
+                    enum_type,
+                });
+            }
+        }
+        path.pop();
+    }
+    match message.encoding() {
+        Some(encoding) => {
+            let mut leaves = vec![];
+            for attrib in encoding.attributes() {
+                visit(attrib, &mut vec![], &mut leaves);
+            }
+            leaves
+        }
+        None => message
+            .signals()
+            .iter()
+            .filter(|signal| !signal.is_reserved())
+            .map(|signal| SignalLeaf {
+                path: vec![signal.name().to_owned()],
+                signal: signal.clone(),
+                enum_type: None,
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt::{self, Write};
+
+use crate::config::{mirror::MirrorTarget, Network, SignalSign, SignalType, Type, TypeRef};
+
+use super::{signal_leaves, SignalLeaf};
+
+// Generates a protobuf (proto3) schema for the streams of the mirror target
+// and the table that maps the signals of the CAN frames onto the fields of
+// the protobuf messages, which is used by the gateway firmware of the master.
+// Every mirrored stream becomes a message, the datagrams are MirrorFrames.
+
+const MAPPING_HEADER: &str =
+    "proto_message,field_number,field,node,stream,message,bus,ide,id,signal,byte_offset,size,sign,scale,offset";
+
+fn proto_message_name(node: &str, stream: &str) -> String {
+    format!("{node}_{stream}")
+}
+
+fn proto_field_name(leaf: &SignalLeaf) -> String {
+    leaf.path.join("_")
+}
+
+fn proto_scalar(signal_type: &SignalType) -> &'static str {
+    match signal_type {
+        SignalType::UnsignedInt { size } if *size <= 32 => "uint32",
+        SignalType::UnsignedInt { size: _ } => "uint64",
+        SignalType::SignedInt { size } if *size <= 32 => "sint32",
+        SignalType::SignedInt { size: _ } => "sint64",
+        SignalType::Decimal {
+            size: _,
+            offset: _,
+            scale: _,
+        } => "double",
+        // raw ticks of the unit.
+        SignalType::Time { size, .. } if *size <= 32 => "uint32",
+        SignalType::Time { .. } => "uint64",
+    }
+}
+
+fn proto_field_type(leaf: &SignalLeaf) -> String {
+    match &leaf.enum_type {
+        Some(enum_type) => enum_type.name(),
+        None => proto_scalar(leaf.signal.ty()).to_owned(),
+    }
+}
+
+/// all enum types that are used by a mirrored stream.
+fn mirrored_enums(mirror: &MirrorTarget) -> Vec<TypeRef> {
+    let mut enums: Vec<TypeRef> = vec![];
+    for (_, stream) in mirror.streams() {
+        for leaf in signal_leaves(stream.message()) {
+            if let Some(enum_type) = leaf.enum_type {
+                if !enums.iter().any(|ty| ty.name() == enum_type.name()) {
+                    enums.push(enum_type);
+                }
+            }
+        }
+    }
+    enums
+}
+
+fn write_enum(f: &mut String, ty: &Type) -> fmt::Result {
+    let Type::Enum {
+        name,
+        description,
+        size: _,
+        entries,
+        visibility: _,
+        annotations: _,
+    } = ty
+    else {
+        return Ok(());
+    };
+    if let Some(description) = description {
+        writeln!(f, "// {description}")?;
+    }
+    writeln!(f, "enum {name} {{")?;
+    // proto3 enums have to start with a zero value.
+    if !entries.iter().any(|(_, value)| *value == 0) {
+        writeln!(f, "  {name}_UNSPECIFIED = 0;")?;
+    }
+    for (entry, value) in entries {
+        writeln!(f, "  {name}_{entry} = {value};")?;
+    }
+    writeln!(f, "}}")?;
+    writeln!(f)
+}
+
+fn write_proto(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "// Generated by canzero-config, do not edit.")?;
+    writeln!(f, "syntax = \"proto3\";")?;
+    writeln!(f)?;
+    writeln!(f, "package canzero.mirror;")?;
+    writeln!(f)?;
+    let Some(mirror) = network.mirror() else {
+        return Ok(());
+    };
+    for ty in mirrored_enums(mirror) {
+        write_enum(f, &ty)?;
+    }
+    for (node, stream) in mirror.streams() {
+        let message = stream.message();
+        writeln!(
+            f,
+            "// stream {node}::{}, can message {} ({:#X})",
+            stream.name(),
+            message.name(),
+            message.id().as_u32()
+        )?;
+        writeln!(f, "message {} {{", proto_message_name(node, stream.name()))?;
+        for (i, leaf) in signal_leaves(message).iter().enumerate() {
+            writeln!(
+                f,
+                "  {} {} = {};",
+                proto_field_type(leaf),
+                proto_field_name(leaf),
+                i + 1
+            )?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+    }
+    writeln!(f, "// payload of a single UDP datagram sent by {}.", mirror.master())?;
+    writeln!(f, "message MirrorFrame {{")?;
+    writeln!(f, "  uint64 timestamp_us = 1;")?;
+    writeln!(f, "  oneof stream {{")?;
+    for (i, (node, stream)) in mirror.streams().iter().enumerate() {
+        let name = proto_message_name(node, stream.name());
+        writeln!(f, "    {name} {name} = {};", i + 2)?;
+    }
+    writeln!(f, "  }}")?;
+    writeln!(f, "}}")
+}
+
+fn write_mapping(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "{MAPPING_HEADER}")?;
+    let Some(mirror) = network.mirror() else {
+        return Ok(());
+    };
+    for (node, stream) in mirror.streams() {
+        let message = stream.message();
+        let ide = if message.id().ide() { "ext" } else { "std" };
+        for (i, leaf) in signal_leaves(message).iter().enumerate() {
+            let signal = &leaf.signal;
+            let sign = match signal.sign() {
+                SignalSign::Signed => "signed",
+                SignalSign::Unsigned => "unsigned",
+            };
+            writeln!(
+                f,
+                "{},{},{},{node},{},{},{},{ide},{:#X},{},{},{},{sign},{},{}",
+                proto_message_name(node, stream.name()),
+                i + 1,
+                proto_field_name(leaf),
+                stream.name(),
+                message.name(),
+                message.bus().name(),
+                message.id().as_u32(),
+                signal.name(),
+                signal.byte_offset(),
+                signal.size(),
+                signal.scale(),
+                signal.offset(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a proto3 schema with a message for every stream of
+    /// the mirror target (see NetworkBuilder::set_mirror_target).
+    /// Without a mirror target the schema doesn't contain any messages.
+    pub fn generate_proto(&self) -> String {
+        let mut proto = String::new();
+        write_proto(&mut proto, self).expect("writing into a String can't fail");
+        proto
+    }
+    /// Generates a csv table, which maps every signal of the mirrored
+    /// streams to the field of the protobuf message (see Network::generate_proto).
+    pub fn mirror_mapping_csv(&self) -> String {
+        let mut csv = String::new();
+        write_mapping(&mut csv, self).expect("writing into a String can't fail");
+        csv
+    }
+}
@@ -0,0 +1,373 @@
+use std::fmt::{self, Write};
+
+use crate::config::{Message, Network, SignalType, TimeKind, Type};
+
+use super::{
+    composite_types_in_dependency_order, fits_u64, message_fields, reserved_mask,
+    reserved_signals, signal_leaves, signal_mask, MessageField,
+};
+
+// Generates a python module with IntEnums and dataclasses for all types
+// and a dataclass with encode/decode functions for every message.
+// Encoded frames are little endian bitfields of payload_len bytes
+// (the dlc for classic and FD frames), python ints have no size limit.
+
+fn python_signal_type(signal_type: &SignalType) -> &'static str {
+    match signal_type {
+        SignalType::UnsignedInt { size: _ } | SignalType::SignedInt { size: _ } => "int",
+        SignalType::Decimal {
+            size: _,
+            offset: _,
+            scale: _,
+        } => "float",
+        // seconds.
+        SignalType::Time { .. } => "float",
+    }
+}
+
+fn python_signal_default(signal_type: &SignalType) -> String {
+    match signal_type {
+        SignalType::UnsignedInt { size: _ } | SignalType::SignedInt { size: _ } => "0".to_owned(),
+        SignalType::Decimal {
+            size: _,
+            offset,
+            scale: _,
+        } => format!("{offset:?}"),
+        SignalType::Time { .. } => "0.0".to_owned(),
+    }
+}
+
+fn python_type(ty: &Type) -> String {
+    match ty {
+        Type::Primitive(signal_type) => python_signal_type(signal_type).to_owned(),
+        Type::Struct {
+            name,
+            description: _,
+            attribs: _,
+            visibility: _,
+            annotations: _,
+        } => name.to_owned(),
+        Type::Enum {
+            name,
+            description: _,
+            size: _,
+            entries: _,
+            visibility: _,
+            annotations: _,
+        } => name.to_owned(),
+        Type::Array { len: _, ty } => format!("list[{}]", python_type(ty)),
+    }
+}
+
+/// default value of a dataclass field of the given type.
+fn python_default(ty: &Type) -> String {
+    match ty {
+        Type::Primitive(signal_type) => python_signal_default(signal_type),
+        Type::Struct {
+            name,
+            description: _,
+            attribs: _,
+            visibility: _,
+            annotations: _,
+        } => format!("field(default_factory={name})"),
+        Type::Enum {
+            name,
+            description: _,
+            size: _,
+            entries,
+            visibility: _,
+            annotations: _,
+        } => match entries.first() {
+            Some((entry_name, _)) => format!("{name}.{entry_name}"),
+            None => "None".to_owned(),
+        },
+        Type::Array { len, ty } => {
+            let element = match ty as &Type {
+                Type::Struct {
+                    name,
+                    description: _,
+                    attribs: _,
+                    visibility: _,
+                    annotations: _,
+                } => format!("{name}()"),
+                _ => python_default(ty),
+            };
+            format!("field(default_factory=lambda: [{element} for _ in range({len})])")
+        }
+    }
+}
+
+fn write_docstring(f: &mut String, indent: &str, description: Option<&str>) -> fmt::Result {
+    if let Some(description) = description {
+        writeln!(f, "{indent}\"\"\"{}\"\"\"", description.replace("\"\"\"", "'''"))?;
+    }
+    Ok(())
+}
+
+fn write_types(f: &mut String, network: &Network) -> fmt::Result {
+    for ty in composite_types_in_dependency_order(network.types()) {
+        match &ty as &Type {
+            Type::Enum {
+                name,
+                description,
+                size: _,
+                entries,
+                visibility: _,
+                annotations: _,
+            } => {
+                writeln!(f, "class {name}(IntEnum):")?;
+                write_docstring(f, "    ", description.as_deref())?;
+                for (entry_name, entry_value) in entries {
+                    writeln!(f, "    {entry_name} = {entry_value}")?;
+                }
+                if entries.is_empty() && description.is_none() {
+                    writeln!(f, "    pass")?;
+                }
+                writeln!(f)?;
+                writeln!(f)?;
+            }
+            Type::Struct {
+                name,
+                description,
+                attribs,
+                visibility: _,
+                annotations: _,
+            } => {
+                writeln!(f, "@dataclass")?;
+                writeln!(f, "class {name}:")?;
+                write_docstring(f, "    ", description.as_deref())?;
+                for (attrib_name, attrib_type) in attribs {
+                    writeln!(
+                        f,
+                        "    {attrib_name}: {} = {}",
+                        python_type(attrib_type),
+                        python_default(attrib_type)
+                    )?;
+                }
+                if attribs.is_empty() && description.is_none() {
+                    writeln!(f, "    pass")?;
+                }
+                writeln!(f)?;
+                writeln!(f)?;
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn write_message(f: &mut String, message: &Message) -> fmt::Result {
+    let name = message.name();
+    writeln!(f, "@dataclass")?;
+    writeln!(f, "class {name}:")?;
+    write_docstring(f, "    ", message.description())?;
+    writeln!(f, "    ID = {:#X}", message.id().as_u32())?;
+    writeln!(
+        f,
+        "    IDE = {}",
+        if message.id().ide() { "True" } else { "False" }
+    )?;
+    match message.xl_frame() {
+        Some(_) => writeln!(f, "    PAYLOAD_LEN = {}", message.payload_len())?,
+        None => writeln!(f, "    DLC = {}", message.dlc())?,
+    }
+    writeln!(f, "    BUS = \"{}\"", message.bus().name())?;
+    writeln!(f)?;
+    for field in message_fields(message) {
+        match field {
+            MessageField::Typed(field_name, ty) => writeln!(
+                f,
+                "    {field_name}: {} = {}",
+                python_type(&ty),
+                python_default(&ty)
+            )?,
+            MessageField::Raw(field_name, signal_type) => writeln!(
+                f,
+                "    {field_name}: {} = {}",
+                python_signal_type(&signal_type),
+                python_signal_default(&signal_type)
+            )?,
+        }
+    }
+    let leaves = signal_leaves(message);
+
+    writeln!(f)?;
+    writeln!(f, "    @classmethod")?;
+    writeln!(f, "    def decode(cls, data: bytes) -> \"{name}\":")?;
+    writeln!(f, "        frame = int.from_bytes(bytes(data), \"little\")")?;
+    let reserved = if fits_u64(message) {
+        Some(reserved_mask(message))
+            .filter(|mask| *mask != 0)
+            .map(|mask| format!("{mask:#X}"))
+    } else {
+        let masks: Vec<String> = reserved_signals(message)
+            .map(|signal| format!("({:#X} << {})", signal_mask(signal.ty()), signal.byte_offset()))
+            .collect();
+        (!masks.is_empty()).then(|| format!("({})", masks.join(" | ")))
+    };
+    if let Some(reserved) = reserved {
+        writeln!(f, "        if frame & {reserved}:")?;
+        writeln!(
+            f,
+            "            raise ValueError(\"reserved bits of {name} are not zero\")"
+        )?;
+    }
+    writeln!(f, "        msg = cls()")?;
+    for leaf in &leaves {
+        let path = leaf.path.join(".");
+        let signal = &leaf.signal;
+        let raw = format!(
+            "((frame >> {}) & {:#X})",
+            signal.byte_offset(),
+            signal_mask(signal.ty())
+        );
+        match (&leaf.enum_type, signal.ty()) {
+            (Some(enum_type), _) => {
+                writeln!(f, "        msg.{path} = {}({raw})", python_type(enum_type))?
+            }
+            (None, SignalType::UnsignedInt { size: _ }) => {
+                writeln!(f, "        msg.{path} = {raw}")?
+            }
+            (None, SignalType::SignedInt { size }) => {
+                writeln!(f, "        msg.{path} = _sign_extend({raw}, {size})")?
+            }
+            (
+                None,
+                SignalType::Decimal {
+                    size: _,
+                    offset,
+                    scale,
+                },
+            ) => writeln!(f, "        msg.{path} = {raw} * {scale:?} + {offset:?}")?,
+            (None, SignalType::Time { unit, .. }) => writeln!(f, "        msg.{path} = {raw} * {:?}", unit.secs())?,
+        }
+    }
+    writeln!(f, "        return msg")?;
+
+    writeln!(f)?;
+    writeln!(f, "    def encode(self) -> bytes:")?;
+    writeln!(f, "        frame = 0")?;
+    for leaf in &leaves {
+        let path = leaf.path.join(".");
+        let signal = &leaf.signal;
+        let offset = signal.byte_offset();
+        let mask = signal_mask(signal.ty());
+        match signal.ty() {
+            SignalType::UnsignedInt { size: _ } | SignalType::SignedInt { size: _ } => {
+                writeln!(f, "        frame |= (int(self.{path}) & {mask:#X}) << {offset}")?
+            }
+            SignalType::Decimal {
+                size: _,
+                offset: value_offset,
+                scale,
+            } => writeln!(
+                f,
+                "        frame |= _pack_decimal(self.{path}, {value_offset:?}, {scale:?}, {mask:#X}) << {offset}"
+            )?,
+            // timestamps wrap around, durations saturate.
+            SignalType::Time { unit, kind: TimeKind::Timestamp, .. } => writeln!(
+                f,
+                "        frame |= (round(self.{path} / {:?}) & {mask:#X}) << {offset}",
+                unit.secs()
+            )?,
+            SignalType::Time { unit, kind: TimeKind::Duration, .. } => writeln!(
+                f,
+                "        frame |= _pack_decimal(self.{path}, 0.0, {:?}, {mask:#X}) << {offset}",
+                unit.secs()
+            )?,
+        }
+    }
+    writeln!(
+        f,
+        "        return frame.to_bytes({}, \"little\")",
+        message.payload_len()
+    )?;
+    writeln!(f)?;
+    writeln!(f)?;
+    Ok(())
+}
+
+fn write_module(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "# generated by canzero-config, do not edit.")?;
+    writeln!(f, "from dataclasses import dataclass, field")?;
+    writeln!(f, "from enum import IntEnum")?;
+    writeln!(f)?;
+    writeln!(f, "NETWORK_HASH = {:#X}", network.portable_hash())?;
+    writeln!(f, "PROVENANCE = {:?}", network.provenance().to_string())?;
+    writeln!(f)?;
+    writeln!(f, "BUSES = {{")?;
+    for bus in network.buses() {
+        writeln!(
+            f,
+            "    \"{}\": {{\"id\": {}, \"baudrate\": {}}},",
+            bus.name(),
+            bus.id(),
+            bus.baudrate()
+        )?;
+    }
+    writeln!(f, "}}")?;
+    writeln!(f)?;
+    for constant in network.constants() {
+        writeln!(
+            f,
+            "{} = {:?}  # {}",
+            constant.name(),
+            constant.value(),
+            constant.unit()
+        )?;
+    }
+    if !network.constants().is_empty() {
+        writeln!(f)?;
+        writeln!(f)?;
+    }
+    writeln!(f)?;
+    writeln!(f, "def _sign_extend(raw: int, size: int) -> int:")?;
+    writeln!(f, "    sign = 1 << (size - 1)")?;
+    writeln!(f, "    return (raw ^ sign) - sign")?;
+    writeln!(f)?;
+    writeln!(f)?;
+    writeln!(
+        f,
+        "def _pack_decimal(value: float, offset: float, scale: float, mask: int) -> int:"
+    )?;
+    writeln!(f, "    raw = int((value - offset) / scale + 0.5)")?;
+    writeln!(f, "    return min(max(raw, 0), mask)")?;
+    writeln!(f)?;
+    writeln!(f)?;
+
+    write_types(f, network)?;
+
+    for message in network.messages() {
+        write_message(f, message)?;
+    }
+
+    writeln!(f, "MESSAGES = [")?;
+    for message in network.messages() {
+        writeln!(f, "    {},", message.name())?;
+    }
+    writeln!(f, "]")?;
+    writeln!(f)?;
+    writeln!(f, "_MESSAGES_BY_ID = {{(m.ID, m.IDE): m for m in MESSAGES}}")?;
+    writeln!(f)?;
+    writeln!(f)?;
+    writeln!(f, "def decode(can_id: int, ide: bool, data: bytes):")?;
+    writeln!(
+        f,
+        "    \"\"\"decodes a frame, returns None if the id is not part of the network.\"\"\""
+    )?;
+    writeln!(f, "    message = _MESSAGES_BY_ID.get((can_id, ide))")?;
+    writeln!(f, "    if message is None:")?;
+    writeln!(f, "        return None")?;
+    writeln!(f, "    return message.decode(data)")?;
+    Ok(())
+}
+
+impl Network {
+    /// Generates a python module with dataclasses for all types and messages
+    /// and encode/decode functions for all messages.
+    pub fn generate_python(&self) -> String {
+        let mut module = String::new();
+        write_module(&mut module, self).expect("writing into a String can't fail");
+        module
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    builder::hardware_filters,
+    config::{Message, MessageId, Node},
+};
+
+// Generates a no_std Rust module with the ids, payload lengths and hardware filters of a
+// single node, such that firmware written in Rust doesn't have to hardcode
+// ids or depend on this crate. The module only contains plain types and
+// consts, decoding the payload is left to the firmware.
+
+const PRELUDE: &str = "\
+/// identifier of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Id {
+    Standard(u16),
+    Extended(u32),
+}
+
+/// acceptance filter, accepts the frames with (frame id & mask) == (id & mask).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Filter {
+    pub id: u32,
+    pub mask: u32,
+    pub ide: bool,
+}
+
+impl Filter {
+    pub const fn accepts(&self, id: Id) -> bool {
+        match id {
+            Id::Standard(id) => !self.ide && (id as u32 ^ self.id) & self.mask == 0,
+            Id::Extended(id) => self.ide && (id ^ self.id) & self.mask == 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub id: Id,
+    /// payload bytes, up to 2048 for CAN XL frames.
+    pub payload_len: u16,
+    /// id of the bus.
+    pub bus: u32,
+}
+";
+
+fn rust_id(id: &MessageId) -> String {
+    match id {
+        MessageId::StandardId(id) => format!("Id::Standard({id:#X})"),
+        MessageId::ExtendedId(id) => format!("Id::Extended({id:#X})"),
+    }
+}
+
+fn const_name(message: &Message) -> String {
+    message.name().to_uppercase()
+}
+
+fn write_messages(f: &mut String, module: &str, messages: &[&Message]) -> fmt::Result {
+    writeln!(f)?;
+    writeln!(f, "pub mod {module} {{")?;
+    writeln!(f, "    use super::{{Id, Message}};")?;
+    for message in messages {
+        writeln!(
+            f,
+            "    pub const {}: Message = Message {{ id: {}, payload_len: {}, bus: {} }};",
+            const_name(message),
+            rust_id(message.id()),
+            message.payload_len(),
+            message.bus().id()
+        )?;
+    }
+    writeln!(f, "}}")
+}
+
+fn write_tables(f: &mut String, node: &Node) -> fmt::Result {
+    writeln!(f, "// generated by canzero-config, do not edit.")?;
+    writeln!(f, "// ids and filters of the node {}.", node.name())?;
+    writeln!(f)?;
+    write!(f, "{PRELUDE}")?;
+    writeln!(f)?;
+    writeln!(f, "pub const NODE_ID: u8 = {};", node.id())?;
+    let tx: Vec<&Message> = node.tx_messages().iter().map(|m| m as &Message).collect();
+    let rx: Vec<&Message> = node.rx_messages().iter().map(|m| m as &Message).collect();
+    write_messages(f, "tx", &tx)?;
+    write_messages(f, "rx", &rx)?;
+    writeln!(f)?;
+    writeln!(f, "pub const RX_IDS: [Id; {}] = [", rx.len())?;
+    for message in &rx {
+        writeln!(f, "    rx::{}.id,", const_name(message))?;
+    }
+    writeln!(f, "];")?;
+    let filters = hardware_filters(node);
+    writeln!(f)?;
+    writeln!(f, "pub const FILTERS: [Filter; {}] = [", filters.len())?;
+    for filter in &filters {
+        writeln!(
+            f,
+            "    Filter {{ id: {:#X}, mask: {:#X}, ide: {} }},",
+            filter.id(),
+            filter.mask(),
+            filter.ide()
+        )?;
+    }
+    writeln!(f, "];")
+}
+
+impl Node {
+    /// Generates a no_std Rust module with the ids and payload lengths of the tx and rx
+    /// messages (modules tx and rx), the ids of all rx messages (RX_IDS) and
+    /// the hardware filters (FILTERS) of the node.
+    pub fn generate_rust_tables(&self) -> String {
+        let mut module = String::new();
+        write_tables(&mut module, self).expect("writing into a String can't fail");
+        module
+    }
+}
@@ -0,0 +1,714 @@
+use crate::diagnostics::{Diagnostic, Severity};
+use crate::config::{
+    bus::BusMode, message::MessageUsage, object_entry::ChunkedRead, provenance::Provenance, AggregationPolicy, StreamAggregation, stream::DeltaRollover, Annotations, MessageRef, Network, ObjectEntryAccess, ObjectEntryBound, SignalRef,
+    SignalType, TimeKind, Type, TypeRef, TypeSignalEncoding,
+};
+
+use super::json::Json;
+
+// Serializes the built network into json.
+// Objects are referenced by name (messages, types) or id (buses).
+
+fn signal_type_json(signal_type: &SignalType) -> Json {
+    match signal_type {
+        SignalType::UnsignedInt { size } => Json::object([
+            ("kind", Json::string("unsigned")),
+            ("size", Json::uint(*size as u64)),
+        ]),
+        SignalType::SignedInt { size } => Json::object([
+            ("kind", Json::string("signed")),
+            ("size", Json::uint(*size as u64)),
+        ]),
+        SignalType::Decimal {
+            size,
+            offset,
+            scale,
+        } => Json::object([
+            ("kind", Json::string("decimal")),
+            ("size", Json::uint(*size as u64)),
+            ("offset", Json::float(*offset)),
+            ("scale", Json::float(*scale)),
+        ]),
+        SignalType::Time { size, unit, kind } => Json::object([
+            (
+                "kind",
+                Json::string(match kind {
+                    TimeKind::Timestamp => "timestamp",
+                    TimeKind::Duration => "duration",
+                }),
+            ),
+            ("size", Json::uint(*size as u64)),
+            ("unit", Json::string(unit.suffix())),
+        ]),
+    }
+}
+
+fn optional_string(s: Option<&str>) -> Json {
+    match s {
+        Some(s) => Json::string(s),
+        None => Json::Null,
+    }
+}
+
+fn annotations_json(annotations: &Annotations) -> Json {
+    Json::Object(
+        annotations
+            .iter()
+            .map(|(key, value)| (key.clone(), Json::string(value)))
+            .collect(),
+    )
+}
+
+fn type_json(ty: &TypeRef) -> Json {
+    match ty as &Type {
+        Type::Primitive(signal_type) => Json::object([
+            ("name", Json::String(ty.name())),
+            ("primitive", signal_type_json(signal_type)),
+        ]),
+        Type::Struct {
+            name,
+            description,
+            attribs,
+            visibility: _,
+            annotations,
+        } => Json::object([
+            ("name", Json::string(name)),
+            ("description", optional_string(description.as_deref())),
+            ("annotations", annotations_json(annotations)),
+            (
+                "attributes",
+                Json::Array(
+                    attribs
+                        .iter()
+                        .map(|(attrib_name, attrib_ty)| {
+                            Json::object([
+                                ("name", Json::string(attrib_name)),
+                                ("type", Json::String(attrib_ty.name())),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ]),
+        Type::Enum {
+            name,
+            description,
+            size,
+            entries,
+            visibility: _,
+            annotations,
+        } => Json::object([
+            ("name", Json::string(name)),
+            ("description", optional_string(description.as_deref())),
+            ("annotations", annotations_json(annotations)),
+            ("size", Json::uint(*size as u64)),
+            (
+                "entries",
+                Json::Array(
+                    entries
+                        .iter()
+                        .map(|(entry_name, value)| {
+                            Json::object([
+                                ("name", Json::string(entry_name)),
+                                ("value", Json::uint(*value)),
+                            ])
+                        })
+                        .collect(),
+                ),
+            ),
+        ]),
+        Type::Array { len, ty: element } => Json::object([
+            ("name", Json::String(ty.name())),
+            ("len", Json::uint(*len as u64)),
+            ("type", Json::String(element.name())),
+        ]),
+    }
+}
+
+fn bound_json(bound: Option<&ObjectEntryBound>) -> Json {
+    match bound {
+        Some(ObjectEntryBound::Value(value)) => Json::float(*value),
+        Some(ObjectEntryBound::Constant(constant)) => Json::object([
+            ("constant", Json::string(constant.name())),
+            ("value", Json::float(constant.value())),
+        ]),
+        None => Json::Null,
+    }
+}
+
+fn chunked_read_json(chunked_read: Option<&ChunkedRead>) -> Json {
+    let Some(chunked_read) = chunked_read else {
+        return Json::Null;
+    };
+    Json::object([
+        ("request", Json::string(chunked_read.request().name())),
+        ("response", Json::string(chunked_read.response().name())),
+        ("index_bits", Json::uint(chunked_read.index_bits() as u64)),
+        ("elements_per_frame", Json::uint(chunked_read.elements_per_frame() as u64)),
+    ])
+}
+
+fn aggregation_json(aggregation: Option<&StreamAggregation>) -> Json {
+    let Some(aggregation) = aggregation else {
+        return Json::Null;
+    };
+    let policy = match aggregation.policy() {
+        AggregationPolicy::First => "first",
+        AggregationPolicy::Newest => "newest",
+        AggregationPolicy::Priority => "priority",
+    };
+    Json::object([
+        ("policy", Json::string(policy)),
+        (
+            "sources",
+            Json::Array(
+                aggregation
+                    .sources()
+                    .iter()
+                    .map(|source| {
+                        Json::object([
+                            ("node", Json::string(source.node())),
+                            ("stream", Json::string(source.stream())),
+                            ("object_entry", Json::string(source.object_entry())),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+    ])
+}
+
+fn signal_json(signal: &SignalRef) -> Json {
+    Json::object([
+        ("name", Json::string(signal.name())),
+        ("description", optional_string(signal.description())),
+        ("offset", Json::uint(signal.byte_offset() as u64)),
+        ("type", signal_type_json(signal.ty())),
+        ("reserved", Json::Bool(signal.is_reserved())),
+        ("annotations", annotations_json(signal.annotations())),
+    ])
+}
+
+fn encoding_json(encoding: &TypeSignalEncoding) -> Json {
+    match encoding {
+        TypeSignalEncoding::Composite(composite) => Json::object([
+            ("name", Json::string(composite.name())),
+            ("type", Json::String(composite.ty().name())),
+            (
+                "attributes",
+                Json::Array(composite.attributes().iter().map(encoding_json).collect()),
+            ),
+        ]),
+        TypeSignalEncoding::Primitive(primitive) => Json::object([
+            ("name", Json::string(primitive.name())),
+            ("type", Json::String(primitive.ty().name())),
+            ("signal", Json::string(primitive.signal().name())),
+        ]),
+    }
+}
+
+fn usage_json(message: &MessageRef) -> Json {
+    match message.usage() {
+        MessageUsage::Stream(stream) => Json::object([
+            ("kind", Json::string("stream")),
+            ("stream", Json::string(stream.name())),
+            ("annotations", annotations_json(stream.annotations())),
+            (
+                "delta_encodings",
+                Json::Array(
+                    stream
+                        .delta_encodings()
+                        .iter()
+                        .map(|delta| match delta {
+                            Some(delta) => Json::object([
+                                ("width", Json::uint(delta.width() as u64)),
+                                ("reference_width", Json::uint(delta.reference_width() as u64)),
+                                (
+                                    "rollover",
+                                    Json::string(match delta.rollover() {
+                                        DeltaRollover::Saturate => "saturate",
+                                        DeltaRollover::Wrap => "wrap",
+                                    }),
+                                ),
+                            ]),
+                            None => Json::Null,
+                        })
+                        .collect(),
+                ),
+            ),
+            (
+                "merged_streams",
+                Json::Array(stream.merged_streams().iter().map(|name| Json::string(name)).collect()),
+            ),
+        ]),
+        MessageUsage::CommandReq(command) => Json::object([
+            ("kind", Json::string("command_req")),
+            ("command", Json::string(command.name())),
+            ("max_in_flight", Json::uint(command.max_in_flight() as u64)),
+            ("annotations", annotations_json(command.annotations())),
+        ]),
+        MessageUsage::CommandResp(command) => Json::object([
+            ("kind", Json::string("command_resp")),
+            ("command", Json::string(command.name())),
+            ("max_in_flight", Json::uint(command.max_in_flight() as u64)),
+            (
+                "errors",
+                Json::Array(
+                    command
+                        .errors()
+                        .iter()
+                        .map(|(name, code)| {
+                            Json::object([("name", Json::string(name)), ("code", Json::uint(*code))])
+                        })
+                        .collect(),
+                ),
+            ),
+            ("annotations", annotations_json(command.annotations())),
+        ]),
+        MessageUsage::GetResp => Json::object([("kind", Json::string("get_resp"))]),
+        MessageUsage::GetReq => Json::object([("kind", Json::string("get_req"))]),
+        MessageUsage::SetResp => Json::object([("kind", Json::string("set_resp"))]),
+        MessageUsage::SetReq => Json::object([("kind", Json::string("set_req"))]),
+        MessageUsage::Heartbeat => Json::object([("kind", Json::string("heartbeat"))]),
+        MessageUsage::External { interval } => Json::object([
+            ("kind", Json::string("external")),
+            ("interval_us", Json::uint(interval.as_micros() as u64)),
+        ]),
+    }
+}
+
+fn message_json(message: &MessageRef) -> Json {
+    Json::object([
+        ("name", Json::string(message.name())),
+        ("description", optional_string(message.description())),
+        ("id", Json::uint(message.id().as_u32() as u64)),
+        ("ide", Json::Bool(message.id().ide())),
+        ("dlc", Json::uint(message.dlc() as u64)),
+        ("bus", Json::uint(message.bus().id() as u64)),
+        (
+            "xl",
+            match message.xl_frame() {
+                Some(xl_frame) => Json::object([
+                    ("sdu_type", Json::uint(xl_frame.sdu_type().to_u8() as u64)),
+                    ("acceptance_field", Json::uint(xl_frame.acceptance_field() as u64)),
+                    ("payload_len", Json::uint(xl_frame.payload_len() as u64)),
+                ]),
+                None => Json::Null,
+            },
+        ),
+        (
+            "fallback_bus",
+            match message.fallback_bus() {
+                Some(bus) => Json::uint(bus.id() as u64),
+                None => Json::Null,
+            },
+        ),
+        ("version", optional_string(message.version())),
+        (
+            "deprecation",
+            match message.deprecation() {
+                Some(deprecation) => Json::object([
+                    ("since", Json::string(deprecation.since())),
+                    ("note", Json::string(deprecation.note())),
+                ]),
+                None => Json::Null,
+            },
+        ),
+        (
+            "processing_deadline_us",
+            match message.processing_deadline() {
+                Some(deadline) => Json::uint(deadline.as_micros() as u64),
+                None => Json::Null,
+            },
+        ),
+        (
+            "deadline_us",
+            match message.deadline() {
+                Some(deadline) => Json::uint(deadline.as_micros() as u64),
+                None => Json::Null,
+            },
+        ),
+        (
+            "authentication",
+            match message.authentication() {
+                Some(authentication) => Json::object([
+                    ("key_id", Json::uint(authentication.key_id() as u64)),
+                    ("mac_len", Json::uint(authentication.mac_len() as u64)),
+                    ("signal", Json::string(authentication.signal().name())),
+                ]),
+                None => Json::Null,
+            },
+        ),
+        (
+            "burst",
+            match message.burst() {
+                Some(burst) => Json::object([
+                    ("max_frames", Json::uint(burst.max_frames() as u64)),
+                    ("window_us", Json::uint(burst.window().as_micros() as u64)),
+                ]),
+                None => Json::Null,
+            },
+        ),
+        (
+            "documentation",
+            Json::object([
+                ("author", optional_string(message.documentation().author())),
+                ("requirement", optional_string(message.documentation().requirement())),
+                ("revision_note", optional_string(message.documentation().revision_note())),
+            ]),
+        ),
+        ("annotations", annotations_json(message.annotations())),
+        ("usage", usage_json(message)),
+        (
+            "signals",
+            Json::Array(message.signals().iter().map(signal_json).collect()),
+        ),
+        (
+            "encoding",
+            match message.encoding() {
+                Some(encoding) => {
+                    Json::Array(encoding.attributes().iter().map(encoding_json).collect())
+                }
+                None => Json::Null,
+            },
+        ),
+    ])
+}
+
+/// the resolver placement of every message, see Message::resolution_info.
+pub(crate) fn resolution_json(network: &Network) -> Json {
+    Json::Array(
+        network
+            .messages()
+            .iter()
+            .map(|message| {
+                let info = message.resolution_info();
+                let optional_uint = |v: Option<u32>| match v {
+                    Some(v) => Json::uint(v as u64),
+                    None => Json::Null,
+                };
+                Json::object([
+                    ("message", Json::string(message.name())),
+                    ("bus", Json::uint(info.bus().id() as u64)),
+                    ("setcode", optional_uint(info.setcode())),
+                    ("setcode_len", Json::uint(info.setcode_len() as u64)),
+                    ("requested_priority", optional_uint(info.requested_priority())),
+                    ("priority_bucket", optional_uint(info.priority_bucket())),
+                    ("position", optional_uint(info.position())),
+                ])
+            })
+            .collect(),
+    )
+}
+
+fn provenance_json(provenance: &Provenance) -> Json {
+    Json::object([
+        ("crate_version", Json::string(provenance.crate_version())),
+        (
+            "source_revision",
+            provenance.source_revision().map_or(Json::Null, Json::string),
+        ),
+        (
+            "input_files",
+            Json::Array(
+                provenance
+                    .input_files()
+                    .iter()
+                    .map(|input_file| {
+                        Json::object([
+                            ("path", Json::string(input_file.path())),
+                            ("hash", Json::String(format!("{:#X}", input_file.hash()))),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        ("resolver", Json::string(provenance.resolver())),
+    ])
+}
+
+pub(crate) fn network_json(network: &Network) -> Json {
+    let buses = network
+        .buses()
+        .iter()
+        .map(|bus| {
+            Json::object([
+                ("name", Json::string(bus.name())),
+                ("id", Json::uint(bus.id() as u64)),
+                ("baudrate", Json::uint(bus.baudrate() as u64)),
+                (
+                    "mode",
+                    match bus.mode() {
+                        BusMode::Can => Json::object([("kind", Json::string("can"))]),
+                        BusMode::Xl { data_baudrate } => Json::object([
+                            ("kind", Json::string("xl")),
+                            ("data_baudrate", Json::uint(data_baudrate as u64)),
+                        ]),
+                    },
+                ),
+                (
+                    "padding",
+                    match bus.padding().fill_byte() {
+                        Some(fill) => Json::uint(fill as u64),
+                        None => Json::Null,
+                    },
+                ),
+                (
+                    "topology",
+                    Json::object([
+                        (
+                            "max_nodes",
+                            match bus.topology().max_nodes() {
+                                Some(max_nodes) => Json::uint(max_nodes as u64),
+                                None => Json::Null,
+                            },
+                        ),
+                        (
+                            "max_stub_length_m",
+                            match bus.topology().max_stub_length() {
+                                Some(length) => Json::float(length),
+                                None => Json::Null,
+                            },
+                        ),
+                        (
+                            "segments",
+                            Json::Array(
+                                bus.topology()
+                                    .segments()
+                                    .iter()
+                                    .map(|segment| {
+                                        Json::object([
+                                            ("description", Json::string(segment.description())),
+                                            ("length_m", Json::float(segment.length())),
+                                            ("stub", Json::Bool(segment.is_stub())),
+                                        ])
+                                    })
+                                    .collect(),
+                            ),
+                        ),
+                    ]),
+                ),
+            ])
+        })
+        .collect();
+    let nodes = network
+        .nodes()
+        .iter()
+        .map(|node| {
+            let names = |messages: &Vec<MessageRef>| {
+                Json::Array(messages.iter().map(|m| Json::string(m.name())).collect())
+            };
+            let object_entries = node
+                .object_entries()
+                .iter()
+                .map(|object_entry| {
+                    let access = match object_entry.access() {
+                        ObjectEntryAccess::Const => "const",
+                        ObjectEntryAccess::Local => "local",
+                        ObjectEntryAccess::Global => "global",
+                    };
+                    Json::object([
+                        ("name", Json::string(object_entry.name())),
+                        ("description", optional_string(object_entry.description())),
+                        ("id", Json::uint(object_entry.id() as u64)),
+                        ("type", Json::String(object_entry.ty().name())),
+                        ("access", Json::string(access)),
+                        ("unit", optional_string(object_entry.unit())),
+                        ("min", bound_json(object_entry.bounds().map(|b| b.min()))),
+                        ("max", bound_json(object_entry.bounds().map(|b| b.max()))),
+                        ("persistent", Json::Bool(object_entry.persistent())),
+                        (
+                            "display",
+                            match object_entry.display() {
+                                Some(display) => Json::object([
+                                    ("factor", Json::float(display.factor())),
+                                    ("offset", Json::float(display.offset())),
+                                    ("unit", Json::string(display.unit())),
+                                ]),
+                                None => Json::Null,
+                            },
+                        ),
+                        ("aggregation", aggregation_json(object_entry.aggregation())),
+                        ("chunked_read", chunked_read_json(object_entry.chunked_read())),
+                    ])
+                })
+                .collect();
+            Json::object([
+                ("name", Json::string(node.name())),
+                (
+                    "description",
+                    optional_string(node.description().map(|d| d.as_str())),
+                ),
+                ("id", Json::uint(node.id() as u64)),
+                ("annotations", annotations_json(node.annotations())),
+                (
+                    "buses",
+                    Json::Array(
+                        node.buses()
+                            .iter()
+                            .map(|bus| Json::uint(bus.id() as u64))
+                            .collect(),
+                    ),
+                ),
+                ("tx_messages", names(node.tx_messages())),
+                ("rx_messages", names(node.rx_messages())),
+                (
+                    "rx_streams",
+                    Json::Array(
+                        node.rx_streams()
+                            .iter()
+                            .map(|stream| {
+                                Json::object([
+                                    ("message", Json::string(stream.message().name())),
+                                    (
+                                        "timeout_us",
+                                        match stream.timeout() {
+                                            Some(timeout) => Json::uint(timeout.as_micros() as u64),
+                                            None => Json::Null,
+                                        },
+                                    ),
+                                ])
+                            })
+                            .collect(),
+                    ),
+                ),
+                ("receive_all", Json::Bool(node.receive_all())),
+                (
+                    "listen_only",
+                    Json::Array(
+                        node.listen_only()
+                            .iter()
+                            .map(|bus| Json::uint(bus.id() as u64))
+                            .collect(),
+                    ),
+                ),
+                ("software_filters", names(node.software_filters())),
+                (
+                    "snapshot_stream",
+                    match node.snapshot_stream() {
+                        Some(snapshot_stream) => Json::object([
+                            ("message", Json::string(snapshot_stream.message().name())),
+                            ("interval_us", Json::uint(snapshot_stream.interval().as_micros() as u64)),
+                            ("index_bits", Json::uint(snapshot_stream.index_bits() as u64)),
+                            (
+                                "entries",
+                                Json::Array(
+                                    snapshot_stream
+                                        .entries()
+                                        .iter()
+                                        .map(|entry| Json::string(entry.name()))
+                                        .collect(),
+                                ),
+                            ),
+                        ]),
+                        None => Json::Null,
+                    },
+                ),
+                (
+                    "supervisions",
+                    Json::Array(
+                        node.supervisions()
+                            .iter()
+                            .map(|supervision| {
+                                Json::object([
+                                    ("node", Json::string(supervision.node())),
+                                    ("timeout_ms", Json::uint(supervision.timeout().as_millis() as u64)),
+                                ])
+                            })
+                            .collect(),
+                    ),
+                ),
+                (
+                    "init_sequence",
+                    Json::Array(
+                        node.init_sequence()
+                            .iter()
+                            .map(|command| Json::string(command.name()))
+                            .collect(),
+                    ),
+                ),
+                ("object_entries", Json::Array(object_entries)),
+                (
+                    "faults",
+                    Json::Array(
+                        node.faults()
+                            .iter()
+                            .map(|fault| {
+                                Json::object([
+                                    ("name", Json::string(fault.name())),
+                                    ("code", Json::uint(fault.code())),
+                                    (
+                                        "severity",
+                                        Json::String(format!("{:?}", fault.severity())),
+                                    ),
+                                    ("object_entry", Json::string(fault.object_entry().name())),
+                                ])
+                            })
+                            .collect(),
+                    ),
+                ),
+            ])
+        })
+        .collect();
+
+    Json::object([
+        ("build_time", Json::String(network.build_time().to_rfc3339())),
+        ("hash", Json::String(format!("{:#X}", network.portable_hash()))),
+        ("provenance", provenance_json(network.provenance())),
+        ("time_epoch", Json::String(network.time_epoch().to_string())),
+        (
+            "masters",
+            Json::Array(network.masters().iter().map(|master| Json::string(master.name())).collect()),
+        ),
+        ("buses", Json::Array(buses)),
+        (
+            "constants",
+            Json::Array(
+                network
+                    .constants()
+                    .iter()
+                    .map(|constant| {
+                        Json::object([
+                            ("name", Json::string(constant.name())),
+                            ("value", Json::float(constant.value())),
+                            ("unit", Json::string(constant.unit())),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+        (
+            "types",
+            Json::Array(network.types().iter().map(type_json).collect()),
+        ),
+        (
+            "messages",
+            Json::Array(network.messages().iter().map(message_json).collect()),
+        ),
+        ("nodes", Json::Array(nodes)),
+    ])
+}
+
+fn diagnostic_json(diagnostic: &Diagnostic) -> Json {
+    let severity = match diagnostic.severity() {
+        Severity::Allow => "allow",
+        Severity::Warn => "warn",
+        Severity::Deny => "deny",
+    };
+    Json::object([
+        ("kind", Json::string(&format!("{:?}", diagnostic.kind()))),
+        ("severity", Json::string(severity)),
+        ("message", Json::string(diagnostic.message())),
+    ])
+}
+
+impl Network {
+    /// Serializes the network into json.
+    pub fn to_json(&self) -> String {
+        network_json(self).to_string()
+    }
+    /// Serializes the diagnostics of the build into a json array, for lint tools.
+    pub fn diagnostics_to_json(&self) -> String {
+        Json::Array(self.diagnostics().iter().map(diagnostic_json).collect()).to_string()
+    }
+}
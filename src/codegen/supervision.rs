@@ -0,0 +1,42 @@
+use std::fmt::{self, Write};
+
+use crate::config::Network;
+
+// Generates the heartbeat supervision matrix as a markdown table for the
+// documentation. Each row is a supervising node, each column a supervised
+// node and the cells contain the timeout.
+
+fn write_matrix(f: &mut String, network: &Network) -> fmt::Result {
+    write!(f, "| supervisor |")?;
+    for node in network.nodes() {
+        write!(f, " {} |", node.name())?;
+    }
+    writeln!(f)?;
+    write!(f, "|---|")?;
+    for _ in network.nodes() {
+        write!(f, "---|")?;
+    }
+    writeln!(f)?;
+    for node in network.nodes() {
+        write!(f, "| {} |", node.name())?;
+        for other in network.nodes() {
+            match node.supervisions().iter().find(|s| s.node() == other.name()) {
+                Some(supervision) => write!(f, " {}ms |", supervision.timeout().as_millis())?,
+                None if other.name() == node.name() => write!(f, " - |")?,
+                None => write!(f, "  |")?,
+            }
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a markdown table of the heartbeat supervisions, rows are the
+    /// supervising nodes, columns the supervised nodes (see Node::supervisions).
+    pub fn generate_supervision_matrix(&self) -> String {
+        let mut markdown = String::new();
+        write_matrix(&mut markdown, self).expect("writing into a String can't fail");
+        markdown
+    }
+}
@@ -0,0 +1,51 @@
+use std::fmt::{self, Write};
+
+use crate::config::Network;
+
+// Generates the window table of a node for all time-triggered schedules,
+// which the node needs to transmit and expect the messages of its windows.
+// Offsets and lengths are in nanoseconds relative to the basic cycle.
+
+const HEADER: &str = "bus,basic_cycle_ns,cycle_count,message,ide,id,direction,first_cycle,repeat,offset_ns,length_ns";
+
+fn write_window_table(f: &mut String, network: &Network, node_name: &str) -> fmt::Result {
+    writeln!(f, "{HEADER}")?;
+    let Some(node) = network.nodes().iter().find(|node| node.name() == node_name) else {
+        return Ok(());
+    };
+    for schedule in network.time_triggered_schedules() {
+        for window in schedule.windows_of(network, node_name) {
+            let message = window.message();
+            let direction = if node.tx_messages().iter().any(|m| m.name() == message.name()) {
+                "tx"
+            } else {
+                "rx"
+            };
+            writeln!(
+                f,
+                "{},{},{},{},{},{:#X},{direction},{},{},{},{}",
+                schedule.bus().name(),
+                schedule.basic_cycle().as_nanos(),
+                schedule.cycle_count(),
+                message.name(),
+                if message.id().ide() { "ext" } else { "std" },
+                message.id().as_u32(),
+                window.first_cycle(),
+                window.repeat(),
+                window.offset().as_nanos(),
+                window.length().as_nanos(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a csv table of the time windows (see config::time_triggered)
+    /// of all messages that the node transmits or receives, ordered by offset.
+    pub fn time_window_table_csv(&self, node_name: &str) -> String {
+        let mut csv = String::new();
+        write_window_table(&mut csv, self, node_name).expect("writing into a String can't fail");
+        csv
+    }
+}
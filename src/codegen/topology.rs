@@ -0,0 +1,58 @@
+use std::fmt::{self, Write};
+
+use crate::config::Network;
+
+// Generates the physical topology of the buses as markdown for the
+// documentation: a summary table of all buses followed by the cable
+// segments of every bus that has segments.
+
+fn limit<T: fmt::Display>(limit: Option<T>, unit: &str) -> String {
+    match limit {
+        Some(limit) => format!("{limit}{unit}"),
+        None => "-".to_owned(),
+    }
+}
+
+fn write_report(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "| bus | baudrate | nodes | max nodes | trunk | max stub |")?;
+    writeln!(f, "|---|---|---|---|---|---|")?;
+    for bus in network.buses() {
+        let topology = bus.topology();
+        writeln!(
+            f,
+            "| {} | {} | {} | {} | {}m | {} |",
+            bus.name(),
+            bus.baudrate(),
+            network.bus_nodes(bus).len(),
+            limit(topology.max_nodes(), ""),
+            topology.trunk_length(),
+            limit(topology.max_stub_length(), "m"),
+        )?;
+    }
+    for bus in network.buses() {
+        let segments = bus.topology().segments();
+        if segments.is_empty() {
+            continue;
+        }
+        writeln!(f)?;
+        writeln!(f, "### {}", bus.name())?;
+        writeln!(f)?;
+        writeln!(f, "| segment | kind | length |")?;
+        writeln!(f, "|---|---|---|")?;
+        for segment in segments {
+            let kind = if segment.is_stub() { "stub" } else { "trunk" };
+            writeln!(f, "| {} | {kind} | {}m |", segment.description(), segment.length())?;
+        }
+    }
+    Ok(())
+}
+
+impl Network {
+    /// Generates a markdown report of the node counts, limits and cable
+    /// segments of the buses (see BusBuilder::set_max_nodes).
+    pub fn generate_topology_report(&self) -> String {
+        let mut markdown = String::new();
+        write_report(&mut markdown, self).expect("writing into a String can't fail");
+        markdown
+    }
+}
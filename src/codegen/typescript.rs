@@ -0,0 +1,375 @@
+use std::fmt::{self, Write};
+
+use crate::config::{Network, SignalType, Type, TypeRef};
+
+use super::{
+    composite_types_in_dependency_order, json::Json, message_fields, signal_mask, MessageField,
+};
+
+// Generates typescript declarations for the decoded values of all messages
+// and object entries and a json schema that describes the same values.
+// Enums are represented by their numeric value.
+
+fn all_types(network: &Network) -> Vec<TypeRef> {
+    let mut types = network.types().clone();
+    for node in network.nodes() {
+        for object_entry in node.object_entries() {
+            types.push(object_entry.ty().clone());
+        }
+    }
+    composite_types_in_dependency_order(&types)
+}
+
+fn ts_type(ty: &Type) -> String {
+    match ty {
+        Type::Primitive(_) => "number".to_owned(),
+        Type::Struct {
+            name,
+            description: _,
+            attribs: _,
+            visibility: _,
+            annotations: _,
+        } => name.to_owned(),
+        Type::Enum {
+            name,
+            description: _,
+            size: _,
+            entries: _,
+            visibility: _,
+            annotations: _,
+        } => name.to_owned(),
+        Type::Array { len: _, ty } => format!("{}[]", ts_type(ty)),
+    }
+}
+
+fn write_doc(f: &mut String, indent: &str, description: Option<&str>) -> fmt::Result {
+    if let Some(description) = description {
+        writeln!(f, "{indent}/** {} */", description.replace("*/", "* /"))?;
+    }
+    Ok(())
+}
+
+fn write_typescript(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "// generated by canzero-config, do not edit.")?;
+    writeln!(
+        f,
+        "export const NETWORK_HASH = \"{:#X}\";",
+        network.portable_hash()
+    )?;
+    writeln!(
+        f,
+        "export const PROVENANCE = {:?};",
+        network.provenance().to_string()
+    )?;
+    writeln!(f)?;
+    for constant in network.constants() {
+        writeln!(f, "/** {} */", constant.unit())?;
+        writeln!(
+            f,
+            "export const {} = {:?};",
+            constant.name(),
+            constant.value()
+        )?;
+    }
+    if !network.constants().is_empty() {
+        writeln!(f)?;
+    }
+    for ty in all_types(network) {
+        match &ty as &Type {
+            Type::Enum {
+                name,
+                description,
+                size: _,
+                entries,
+                visibility: _,
+                annotations: _,
+            } => {
+                write_doc(f, "", description.as_deref())?;
+                writeln!(f, "export enum {name} {{")?;
+                for (entry_name, entry_value) in entries {
+                    writeln!(f, "  {entry_name} = {entry_value},")?;
+                }
+                writeln!(f, "}}")?;
+                writeln!(f)?;
+            }
+            Type::Struct {
+                name,
+                description,
+                attribs,
+                visibility: _,
+                annotations: _,
+            } => {
+                write_doc(f, "", description.as_deref())?;
+                writeln!(f, "export interface {name} {{")?;
+                for (attrib_name, attrib_type) in attribs {
+                    writeln!(f, "  {attrib_name}: {};", ts_type(attrib_type))?;
+                }
+                writeln!(f, "}}")?;
+                writeln!(f)?;
+            }
+            _ => (),
+        }
+    }
+
+    for message in network.messages() {
+        write_doc(f, "", message.description())?;
+        if let Some(deprecation) = message.deprecation() {
+            let doc = format!("@deprecated since {}: {}", deprecation.since(), deprecation.note());
+            write_doc(f, "", Some(&doc))?;
+        }
+        writeln!(f, "export interface {} {{", message.name())?;
+        for field in message_fields(message) {
+            match field {
+                MessageField::Typed(field_name, ty) => {
+                    writeln!(f, "  {field_name}: {};", ts_type(&ty))?
+                }
+                MessageField::Raw(field_name, _) => writeln!(f, "  {field_name}: number;")?,
+            }
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+    }
+
+    for node in network.nodes() {
+        writeln!(f, "export interface {}_object_entries {{", node.name())?;
+        for object_entry in node.object_entries() {
+            let display = object_entry.display().map(|display| {
+                format!(
+                    "displayed as value * {:?} + {:?} [{}]",
+                    display.factor(),
+                    display.offset(),
+                    display.unit()
+                )
+            });
+            let unit = match (object_entry.unit().map(|unit| format!("[{unit}]")), display) {
+                (Some(unit), Some(display)) => Some(format!("{unit}, {display}")),
+                (unit, display) => unit.or(display),
+            };
+            let doc = match (object_entry.description(), unit) {
+                (Some(description), Some(unit)) => Some(format!("{description} {unit}")),
+                (Some(description), None) => Some(description.to_owned()),
+                (None, unit) => unit,
+            };
+            write_doc(f, "  ", doc.as_deref())?;
+            writeln!(f, "  {}: {};", object_entry.name(), ts_type(object_entry.ty()))?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f)?;
+    }
+
+    writeln!(f, "export const MESSAGE_IDS = {{")?;
+    for message in network.messages() {
+        writeln!(
+            f,
+            "  {}: {{ id: {:#X}, ide: {}, bus: \"{}\" }},",
+            message.name(),
+            message.id().as_u32(),
+            message.id().ide(),
+            message.bus().name()
+        )?;
+    }
+    writeln!(f, "}} as const;")?;
+    Ok(())
+}
+
+fn signal_schema(signal_type: &SignalType) -> Json {
+    let mask = signal_mask(signal_type);
+    match signal_type {
+        SignalType::UnsignedInt { size: _ } => Json::object([
+            ("type", Json::string("integer")),
+            ("minimum", Json::uint(0)),
+            ("maximum", Json::uint(mask)),
+        ]),
+        SignalType::SignedInt { size: _ } => Json::object([
+            ("type", Json::string("integer")),
+            ("minimum", Json::int(-((mask >> 1) as i64) - 1)),
+            ("maximum", Json::int((mask >> 1) as i64)),
+        ]),
+        SignalType::Decimal {
+            size: _,
+            offset,
+            scale,
+        } => Json::object([
+            ("type", Json::string("number")),
+            ("minimum", Json::float(*offset)),
+            ("maximum", Json::float(offset + scale * mask as f64)),
+        ]),
+        // seconds, like the values of decimals.
+        SignalType::Time { .. } => Json::object([
+            ("type", Json::string("number")),
+            ("minimum", Json::float(0.0)),
+            ("maximum", Json::float(signal_type.scale() * mask as f64)),
+        ]),
+    }
+}
+
+fn type_schema(ty: &Type) -> Json {
+    match ty {
+        Type::Primitive(signal_type) => signal_schema(signal_type),
+        Type::Struct {
+            name,
+            description: _,
+            attribs: _,
+            visibility: _,
+            annotations: _,
+        }
+        | Type::Enum {
+            name,
+            description: _,
+            size: _,
+            entries: _,
+            visibility: _,
+            annotations: _,
+        } => Json::object([("$ref", Json::String(format!("#/definitions/{name}")))]),
+        Type::Array { len, ty } => Json::object([
+            ("type", Json::string("array")),
+            ("items", type_schema(ty)),
+            ("minItems", Json::uint(*len as u64)),
+            ("maxItems", Json::uint(*len as u64)),
+        ]),
+    }
+}
+
+fn object_schema(properties: Vec<(String, Json)>) -> Json {
+    let required = properties
+        .iter()
+        .map(|(name, _)| Json::string(name))
+        .collect();
+    Json::object([
+        ("type", Json::string("object")),
+        ("properties", Json::Object(properties)),
+        ("required", Json::Array(required)),
+        ("additionalProperties", Json::Bool(false)),
+    ])
+}
+
+fn json_schema(network: &Network) -> Json {
+    let mut definitions = vec![];
+    for ty in all_types(network) {
+        match &ty as &Type {
+            Type::Enum {
+                name,
+                description: _,
+                size: _,
+                entries,
+                visibility: _,
+                annotations: _,
+            } => definitions.push((
+                name.to_owned(),
+                Json::object([
+                    ("type", Json::string("integer")),
+                    (
+                        "enum",
+                        Json::Array(entries.iter().map(|(_, value)| Json::uint(*value)).collect()),
+                    ),
+                ]),
+            )),
+            Type::Struct {
+                name,
+                description: _,
+                attribs,
+                visibility: _,
+                annotations: _,
+            } => definitions.push((
+                name.to_owned(),
+                object_schema(
+                    attribs
+                        .iter()
+                        .map(|(attrib_name, attrib_type)| {
+                            (attrib_name.to_owned(), type_schema(attrib_type))
+                        })
+                        .collect(),
+                ),
+            )),
+            _ => (),
+        }
+    }
+
+    let messages = network
+        .messages()
+        .iter()
+        .map(|message| {
+            let properties = message_fields(message)
+                .into_iter()
+                .map(|field| match field {
+                    MessageField::Typed(field_name, ty) => (field_name, type_schema(&ty)),
+                    MessageField::Raw(field_name, signal_type) => {
+                        (field_name, signal_schema(&signal_type))
+                    }
+                })
+                .collect();
+            (message.name().to_owned(), object_schema(properties))
+        })
+        .collect();
+
+    let object_entries = network
+        .nodes()
+        .iter()
+        .map(|node| {
+            let properties = node
+                .object_entries()
+                .iter()
+                .map(|object_entry| {
+                    let mut schema = type_schema(object_entry.ty());
+                    if let (Json::Object(entries), Some(display)) =
+                        (&mut schema, object_entry.display())
+                    {
+                        // custom keyword, ignored by validators.
+                        entries.push((
+                            "x-display".to_owned(),
+                            Json::object([
+                                ("factor", Json::float(display.factor())),
+                                ("offset", Json::float(display.offset())),
+                                ("unit", Json::string(display.unit())),
+                            ]),
+                        ));
+                    }
+                    (object_entry.name().to_owned(), schema)
+                })
+                .collect();
+            (node.name().to_owned(), object_schema(properties))
+        })
+        .collect();
+
+    Json::object([
+        (
+            "$schema",
+            Json::string("http://json-schema.org/draft-07/schema#"),
+        ),
+        ("title", Json::string("canzero network")),
+        ("definitions", Json::Object(definitions)),
+        ("type", Json::string("object")),
+        (
+            "properties",
+            Json::object([
+                (
+                    "messages",
+                    Json::object([
+                        ("type", Json::string("object")),
+                        ("properties", Json::Object(messages)),
+                    ]),
+                ),
+                (
+                    "object_entries",
+                    Json::object([
+                        ("type", Json::string("object")),
+                        ("properties", Json::Object(object_entries)),
+                    ]),
+                ),
+            ]),
+        ),
+    ])
+}
+
+impl Network {
+    /// Generates typescript declarations for decoded messages and object entries.
+    pub fn generate_typescript(&self) -> String {
+        let mut declarations = String::new();
+        write_typescript(&mut declarations, self).expect("writing into a String can't fail");
+        declarations
+    }
+    /// Generates a json schema (draft-07) of the decoded messages and object entries.
+    pub fn generate_json_schema(&self) -> String {
+        json_schema(self).to_string()
+    }
+}
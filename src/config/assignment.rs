@@ -0,0 +1,173 @@
+use crate::{builder::resolve_filters_only, simulator::arbitration_key};
+
+use super::{Message, MessageId, Network, NodeFilters};
+
+// The assignment is the result of the id resolution: the id and bus of every
+// message and the acceptance filters of every node. verify checks the
+// invariants that the firmware relies on, independent of how the resolver
+// produced the assignment. The test_util feature adds perturbations of
+// assignments, to check that the verification catches corrupted assignments.
+
+#[derive(Debug, Clone)]
+pub struct AssignedMessage {
+    pub(crate) name: String,
+    pub(crate) id: MessageId,
+    pub(crate) bus: u32,
+    pub(crate) priority: Option<u32>,
+    // names of the nodes that receive the message.
+    pub(crate) receivers: Vec<String>,
+}
+
+impl AssignedMessage {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn id(&self) -> &MessageId {
+        &self.id
+    }
+    /// id of the bus.
+    pub fn bus(&self) -> u32 {
+        self.bus
+    }
+    /// requested priority bucket, None for fixed ids
+    /// (see MessageResolutionInfo::requested_priority).
+    pub fn priority(&self) -> Option<u32> {
+        self.priority
+    }
+    pub fn receivers(&self) -> &Vec<String> {
+        &self.receivers
+    }
+    fn of(message: &Message, receivers: Vec<String>) -> AssignedMessage {
+        AssignedMessage {
+            name: message.name().to_owned(),
+            id: *message.id(),
+            bus: message.bus().id(),
+            priority: message.resolution_info().requested_priority(),
+            receivers,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Assignment {
+    pub(crate) messages: Vec<AssignedMessage>,
+    pub(crate) filters: Vec<NodeFilters>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignmentViolation {
+    /// two messages with the same id on the same bus.
+    DuplicatedId {
+        bus: u32,
+        id: MessageId,
+        messages: (String, String),
+    },
+    /// the filters of a receiver don't accept the message.
+    RejectedMessage { node: String, message: String },
+    /// a message that requested a higher priority loses the arbitration
+    /// against a message with a lower priority on the same bus.
+    PriorityInversion { bus: u32, higher: String, lower: String },
+}
+
+// pairs of messages on the same bus, where the message with the higher
+// requested priority (lower bucket) has the lower arbitration priority.
+fn priority_inversions(messages: &[AssignedMessage]) -> Vec<AssignmentViolation> {
+    let mut violations = vec![];
+    for a in messages {
+        let Some(a_priority) = a.priority else {
+            continue;
+        };
+        for b in messages {
+            if a.bus != b.bus {
+                continue;
+            }
+            let Some(b_priority) = b.priority else {
+                continue;
+            };
+            if a_priority < b_priority && arbitration_key(&a.id) > arbitration_key(&b.id) {
+                violations.push(AssignmentViolation::PriorityInversion {
+                    bus: a.bus,
+                    higher: a.name.clone(),
+                    lower: b.name.clone(),
+                });
+            }
+        }
+    }
+    violations
+}
+
+/// priority inversions of the messages of the network, without computing
+/// the filters of the assignment (see AssignmentViolation::PriorityInversion).
+pub(crate) fn network_priority_inversions(network: &Network) -> Vec<AssignmentViolation> {
+    let messages: Vec<AssignedMessage> = network
+        .messages()
+        .iter()
+        .map(|message| AssignedMessage::of(message, vec![]))
+        .collect();
+    priority_inversions(&messages)
+}
+
+impl Assignment {
+    /// the assignment of the built network, filters are recomputed from
+    /// the assigned ids (see resolve_filters_only).
+    pub fn of(network: &Network) -> Assignment {
+        let messages = network
+            .messages()
+            .iter()
+            .map(|message| {
+                let receivers = network
+                    .nodes()
+                    .iter()
+                    .filter(|node| node.rx_messages().iter().any(|m| m.name() == message.name()))
+                    .map(|node| node.name().to_owned())
+                    .collect();
+                AssignedMessage::of(message, receivers)
+            })
+            .collect();
+        Assignment {
+            messages,
+            filters: resolve_filters_only(network),
+        }
+    }
+    pub fn messages(&self) -> &Vec<AssignedMessage> {
+        &self.messages
+    }
+    pub fn filters(&self) -> &Vec<NodeFilters> {
+        &self.filters
+    }
+    /// all violated invariants, empty if the assignment is valid.
+    pub fn verify(&self) -> Vec<AssignmentViolation> {
+        let mut violations = vec![];
+        for (i, a) in self.messages.iter().enumerate() {
+            for b in &self.messages[i + 1..] {
+                if a.bus == b.bus && a.id == b.id {
+                    violations.push(AssignmentViolation::DuplicatedId {
+                        bus: a.bus,
+                        id: a.id,
+                        messages: (a.name.clone(), b.name.clone()),
+                    });
+                }
+            }
+        }
+        for node_filters in &self.filters {
+            let node = node_filters.node().name();
+            for message in self.messages.iter().filter(|m| m.receivers.iter().any(|r| r == node)) {
+                if !node_filters.filters().iter().any(|filter| filter.accepts_id(&message.id)) {
+                    violations.push(AssignmentViolation::RejectedMessage {
+                        node: node.to_owned(),
+                        message: message.name.clone(),
+                    });
+                }
+            }
+        }
+        violations.extend(priority_inversions(&self.messages));
+        violations
+    }
+}
+
+impl Network {
+    /// verifies the assignment of ids and filters, see Assignment::verify.
+    pub fn verify_assignment(&self) -> Vec<AssignmentViolation> {
+        Assignment::of(self).verify()
+    }
+}
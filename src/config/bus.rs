@@ -1,27 +1,157 @@
-use std::hash::Hash;
+use std::{hash::Hash, sync::OnceLock, time::Duration};
 
-use super::ConfigRef;
+use crate::simulator;
+
+use super::{can_xl, id_utilization::IdRegion, ConfigRef, FrameTimeModel, Message, Network, NodeRef};
 
 
 
 pub type BusRef = ConfigRef<Bus>;
 
+/// how frames are filled up on the bus. All nodes on a bus have to agree on
+/// the padding, otherwise checksums over the whole frame don't match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FramePadding {
+    /// frames are sent with the dlc of the message.
+    #[default]
+    Exact,
+    /// frames are padded to 8 bytes, unused bytes are set to fill (e.g. 0x00 or 0xAA).
+    PadTo8 { fill: u8 },
+}
+
+impl FramePadding {
+    /// number of payload bytes, that are transmitted for a message with the given dlc.
+    pub fn transmitted_len(&self, dlc: usize) -> usize {
+        match self {
+            FramePadding::Exact => dlc,
+            FramePadding::PadTo8 { fill: _ } => dlc.max(8),
+        }
+    }
+    pub fn fill_byte(&self) -> Option<u8> {
+        match self {
+            FramePadding::Exact => None,
+            FramePadding::PadTo8 { fill } => Some(*fill),
+        }
+    }
+}
+
+/// frame format of the bus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BusMode {
+    /// classic CAN or CAN FD, see Network::frame_time_model.
+    #[default]
+    Can,
+    /// CAN XL, the data phase is transmitted with the data baudrate
+    /// (experimental, see config::can_xl).
+    Xl { data_baudrate: u32 },
+}
+
+/// a piece of the cable of a bus, lengths are in meters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CableSegment {
+    description : String,
+    length : f64,
+    stub : bool,
+}
+
+impl CableSegment {
+    /// part of the main line between the terminations.
+    pub fn trunk(description : &str, length : f64) -> Self {
+        Self {
+            description : description.to_owned(),
+            length,
+            stub : false,
+        }
+    }
+    /// branch from the trunk to a node.
+    pub fn stub(description : &str, length : f64) -> Self {
+        Self {
+            description : description.to_owned(),
+            length,
+            stub : true,
+        }
+    }
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+    pub fn length(&self) -> f64 {
+        self.length
+    }
+    pub fn is_stub(&self) -> bool {
+        self.stub
+    }
+}
+
+/// physical layer limits and wiring of a bus, the limits are
+/// validated by build. Not part of the network hash.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BusTopology {
+    max_nodes : Option<u32>,
+    max_stub_length : Option<f64>,
+    segments : Vec<CableSegment>,
+}
+
+impl BusTopology {
+    pub fn new(max_nodes : Option<u32>, max_stub_length : Option<f64>, segments : Vec<CableSegment>) -> Self {
+        Self {
+            max_nodes,
+            max_stub_length,
+            segments,
+        }
+    }
+    /// max number of transceivers on the bus.
+    pub fn max_nodes(&self) -> Option<u32> {
+        self.max_nodes
+    }
+    pub fn max_stub_length(&self) -> Option<f64> {
+        self.max_stub_length
+    }
+    pub fn segments(&self) -> &Vec<CableSegment> {
+        &self.segments
+    }
+    /// length of the trunk between the terminations.
+    pub fn trunk_length(&self) -> f64 {
+        self.segments.iter().filter(|s| !s.is_stub()).map(|s| s.length()).sum()
+    }
+    /// total cable length, including stubs.
+    pub fn cable_length(&self) -> f64 {
+        self.segments.iter().map(|s| s.length()).sum()
+    }
+}
+
 #[derive(Debug)]
 
 pub struct Bus {
     id : u32,
     baudrate : u32,
     name : String,
+    padding : FramePadding,
+    topology : BusTopology,
+    mode : BusMode,
+    id_utilization : OnceLock<Vec<IdRegion>>,
 }
 
 impl Bus {
-    pub fn new(name : &str, id : u32, baudrate : u32) -> Self{
+    pub fn new(name : &str, id : u32, baudrate : u32, padding : FramePadding, topology : BusTopology, mode : BusMode) -> Self{
         Self {
             id,
             baudrate,
             name : name.to_owned(),
+            padding,
+            topology,
+            mode,
+            id_utilization : OnceLock::new(),
         }
     }
+    pub fn mode(&self) -> BusMode {
+        self.mode
+    }
+    pub fn is_xl(&self) -> bool {
+        matches!(self.mode, BusMode::Xl { .. })
+    }
+    pub fn topology(&self) -> &BusTopology {
+        &self.topology
+    }
     pub fn id(&self) -> u32 {
         self.id
     }
@@ -31,8 +161,84 @@ impl Bus {
     pub fn name(&self) -> &str {
         &self.name
     }
+    pub fn padding(&self) -> FramePadding {
+        self.padding
+    }
+    /// the priority regions of the id space and how many of their ids are
+    /// used by messages on this bus. Empty if no id was resolved.
+    pub fn id_utilization(&self) -> &[IdRegion] {
+        match self.id_utilization.get() {
+            Some(regions) => regions,
+            None => &[],
+        }
+    }
+    pub fn __set_id_utilization(&self, regions : Vec<IdRegion>) {
+        self.id_utilization.set(regions).expect("__set_id_utilization can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// payload bytes of a frame with the given dlc on this bus.
+    pub fn transmitted_len(&self, dlc: u8) -> usize {
+        self.padding.transmitted_len(dlc as usize)
+    }
+    /// worst-case transmission time of a frame of the message on this bus,
+    /// frames on CAN buses follow the frame time model of the network.
+    pub fn frame_time(&self, frame_time_model: &FrameTimeModel, message: &Message) -> Duration {
+        match self.mode {
+            BusMode::Can => frame_time_model.frame_time(
+                message.id().ide(),
+                self.transmitted_len(message.dlc()),
+                self.baudrate,
+            ),
+            BusMode::Xl { data_baudrate } => {
+                can_xl::xl_frame_time(message.payload_len(), self.baudrate, data_baudrate)
+            }
+        }
+    }
+    /// fraction of the bus, that a frame of the message sent every interval occupies.
+    pub fn load(&self, frame_time_model: &FrameTimeModel, message: &Message, interval: Duration) -> f64 {
+        match self.mode {
+            BusMode::Can => {
+                frame_time_model.load(message.id().ide(), self.transmitted_len(message.dlc()), interval)
+                    / self.baudrate as f64
+            }
+            BusMode::Xl { data_baudrate: _ } => {
+                self.frame_time(frame_time_model, message).as_secs_f64() / interval.as_secs_f64()
+            }
+        }
+    }
+}
+
+
+/// nodes that are connected to the bus, nodes without explicit
+/// buses are connected to all buses.
+pub(crate) fn connected_nodes<'a>(nodes: &'a [NodeRef], bus: &Bus) -> Vec<&'a NodeRef> {
+    nodes
+        .iter()
+        .filter(|node| {
+            (node.buses().is_empty() && node.fallback_bus().is_none())
+                || node.buses().iter().chain(node.fallback_bus()).any(|b| b.id() == bus.id())
+        })
+        .collect()
 }
 
+impl Network {
+    /// nodes that are connected to the bus (including fallback buses).
+    pub fn bus_nodes(&self, bus: &Bus) -> Vec<&NodeRef> {
+        connected_nodes(self.nodes(), bus)
+    }
+    /// expected load of the bus (0..1) by the worst-case release
+    /// of all messages that are placed on it.
+    pub fn bus_load(&self, bus: &Bus) -> f64 {
+        self.messages()
+            .iter()
+            .filter(|message| message.bus().id() == bus.id())
+            .map(|message| {
+                // bursts of event driven messages are expected in every window.
+                let (period, frames) = simulator::worst_case_release(message);
+                frames as f64 * bus.load(self.frame_time_model(), message, period)
+            })
+            .sum()
+    }
+}
 
 impl Hash for Bus {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -41,5 +247,11 @@ impl Hash for Bus {
         for b in self.name.bytes() {
             state.write_u8(b);
         }
+        if let Some(fill) = self.padding.fill_byte() {
+            state.write_u8(fill);
+        }
+        if let BusMode::Xl { data_baudrate } = self.mode {
+            state.write_u32(data_baudrate);
+        }
     }
 }
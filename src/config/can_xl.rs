@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+// Experimental support of CAN XL (ISO 11898-1:2024) buses. Frames on a bus in
+// XL mode carry up to 2048 bytes, arbitrate with an 11 bit priority id (the
+// standard id assigned by the resolver) and are addressed with a 32 bit
+// acceptance field, which defaults to the priority id. Buses in the default
+// mode (classic CAN or CAN FD) are not affected.
+
+/// max payload of a CAN XL frame in bytes.
+pub const MAX_XL_PAYLOAD: usize = 2048;
+
+/// service data unit type, which tells the receiver how to interpret the payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SduType {
+    /// the payload is defined by the acceptance field (like classic messages).
+    #[default]
+    ContentBasedAddressing,
+    /// the payload is a tunneled classic or FD frame.
+    ClassicFdTunneling,
+    /// the payload is an IEEE 802.3 (ethernet) frame.
+    Ethernet,
+    Other(u8),
+}
+
+impl SduType {
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            SduType::ContentBasedAddressing => 0x01,
+            SduType::ClassicFdTunneling => 0x03,
+            SduType::Ethernet => 0x05,
+            SduType::Other(sdu_type) => *sdu_type,
+        }
+    }
+}
+
+/// metadata of a message on a bus in XL mode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct XlFrame {
+    sdu_type: SduType,
+    acceptance_field: u32,
+    payload_len: u16,
+}
+
+impl XlFrame {
+    pub fn new(sdu_type: SduType, acceptance_field: u32, payload_len: u16) -> Self {
+        Self {
+            sdu_type,
+            acceptance_field,
+            payload_len,
+        }
+    }
+    pub fn sdu_type(&self) -> SduType {
+        self.sdu_type
+    }
+    pub fn acceptance_field(&self) -> u32 {
+        self.acceptance_field
+    }
+    /// payload bytes (1..=2048).
+    pub fn payload_len(&self) -> usize {
+        self.payload_len as usize
+    }
+}
+
+// SOF, priority id, RRS, IDE, FDF, XLF, resXL and ADH.
+const ARBITRATION_BITS: u64 = 18;
+// ADS and DAS sequences around the data phase.
+const SWITCH_BITS: u64 = 6;
+// ACK slot, ACK delimiter, EOF and interframe space.
+const TAIL_BITS: u64 = 1 + 1 + 7 + 3;
+// SDT, SEC, DLC, SBC, PCRC, VCID, AF, FCRC and FCP.
+const DATA_HEADER_BITS: u64 = 8 + 1 + 11 + 3 + 13 + 8 + 32 + 32 + 4;
+
+/// (bits transmitted with the nominal bitrate, bits transmitted with the
+/// data bitrate) in the worst case: dynamic stuffing in the arbitration
+/// phase and a fixed stuff bit after every 10 bits of the data phase.
+pub fn xl_frame_bits(payload_len: usize) -> (u64, u64) {
+    let nominal = ARBITRATION_BITS + (ARBITRATION_BITS - 1) / 4 + SWITCH_BITS + TAIL_BITS;
+    let data = DATA_HEADER_BITS + 8 * payload_len.clamp(1, MAX_XL_PAYLOAD) as u64;
+    (nominal, data + data / 10)
+}
+
+pub fn xl_frame_time(payload_len: usize, baudrate: u32, data_baudrate: u32) -> Duration {
+    let (nominal, data) = xl_frame_bits(payload_len);
+    Duration::from_secs_f64(nominal as f64 / baudrate as f64 + data as f64 / data_baudrate as f64)
+}
@@ -1,6 +1,6 @@
 use std::{hash::Hash, time::Duration};
 
-use super::{ConfigRef, MessageRef, Visibility, Message};
+use super::{Annotations, ConfigRef, MessageRef, Network, NodeRef, Visibility, Message};
 
 
 pub type CommandRef = ConfigRef<Command>;
@@ -13,6 +13,13 @@ pub struct Command {
     rx_message: MessageRef,
     visibility: Visibility,
     expected_interval : Duration,
+    burst : u32,
+    timeout : Option<Duration>,
+    max_in_flight : u32,
+    token : Option<String>,
+    // (name, code) of the errors in addition to Success and Error.
+    errors : Vec<(String, u64)>,
+    annotations : Annotations,
 }
 
 impl Hash for Command {
@@ -23,6 +30,26 @@ impl Hash for Command {
         self.visibility.hash(state);
         let us =  self.expected_interval().as_micros();
         state.write_u128(us);
+        if self.burst != 1 {
+            state.write_u32(self.burst);
+        }
+        if let Some(timeout) = self.timeout {
+            state.write_u128(timeout.as_micros());
+        }
+        if self.max_in_flight != 1 {
+            state.write_u32(self.max_in_flight);
+        }
+        if let Some(token) = &self.token {
+            for b in token.bytes() {
+                state.write_u8(b);
+            }
+        }
+        for (name, code) in &self.errors {
+            for b in name.bytes() {
+                state.write_u8(b);
+            }
+            state.write_u64(*code);
+        }
     }
 }
 
@@ -32,22 +59,67 @@ impl Command {
                tx_message : MessageRef,
                rx_message : MessageRef,
                visibility : Visibility, 
-               expected_interval : Duration) -> Self {
+               expected_interval : Duration,
+               burst : u32,
+               timeout : Option<Duration>,
+               max_in_flight : u32,
+               token : Option<String>,
+               errors : Vec<(String, u64)>,
+               annotations : Annotations) -> Self {
         Self{
             name,
             description,
             tx_message,
             rx_message,
             visibility,
-            expected_interval
+            expected_interval,
+            burst,
+            timeout,
+            max_in_flight,
+            token,
+            errors,
+            annotations,
         }
     }
+    pub fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
     pub fn visibility(&self) -> &Visibility {
         &self.visibility
     }
     pub fn expected_interval(&self) -> &Duration {
         &self.expected_interval
     }
+    /// number of back to back calls every expected_interval.
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+    /// mean time between two req (or resp) frames of the command.
+    pub fn frame_interval(&self) -> Duration {
+        self.expected_interval / self.burst
+    }
+    /// max time between sending the request and receiving the response.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+    /// number of requests a caller may send before the first
+    /// response arrives, 1 if the command isn't pipelined.
+    pub fn max_in_flight(&self) -> u32 {
+        self.max_in_flight
+    }
+    pub fn is_pipelined(&self) -> bool {
+        self.max_in_flight > 1
+    }
+    /// group of the arbitration token, which the caller has to own
+    /// ({token}_token_request) before the command is executed.
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+    /// (name, code) of the error codes of the response, that were added
+    /// with CommandBuilder::add_error. Success (0) and Error (1) aren't listed.
+    pub fn errors(&self) -> &[(String, u64)] {
+        &self.errors
+    }
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -64,3 +136,56 @@ impl Command {
         &self.rx_message
     }
 }
+
+// Cross references between commands and nodes. A command is executed by the
+// node that defines it (Node::commands) and called by the nodes, which list
+// it as extern command (Node::extern_commands).
+impl Network {
+    /// (executing node, command) of all commands that the node calls,
+    /// empty if there is no node with the name.
+    pub fn commands_callable_by(&self, node: &str) -> Vec<(NodeRef, CommandRef)> {
+        let Some(caller) = self.nodes().iter().find(|n| n.name() == node) else {
+            return vec![];
+        };
+        caller
+            .extern_commands()
+            .iter()
+            .filter_map(|(owner, command)| {
+                self.nodes()
+                    .iter()
+                    .find(|n| n.name() == owner)
+                    .map(|owner| (owner.clone(), command.clone()))
+            })
+            .collect()
+    }
+    /// the node that executes the command.
+    pub fn command_owner(&self, command: &Command) -> Option<&NodeRef> {
+        self.nodes()
+            .iter()
+            .find(|node| node.commands().iter().any(|c| std::ptr::eq(c as &Command, command)))
+    }
+    /// all nodes that call the command.
+    pub fn command_callers(&self, command: &Command) -> Vec<NodeRef> {
+        self.nodes()
+            .iter()
+            .filter(|node| {
+                node.extern_commands()
+                    .iter()
+                    .any(|(_, c)| std::ptr::eq(c as &Command, command))
+            })
+            .cloned()
+            .collect()
+    }
+    /// (executing node, command) of a command request or response message.
+    pub fn command_of_message(&self, message: &Message) -> Option<(NodeRef, CommandRef)> {
+        self.nodes().iter().find_map(|node| {
+            node.commands()
+                .iter()
+                .find(|command| {
+                    command.tx_message().name() == message.name()
+                        || command.rx_message().name() == message.name()
+                })
+                .map(|command| (node.clone(), command.clone()))
+        })
+    }
+}
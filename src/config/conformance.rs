@@ -0,0 +1,374 @@
+use std::time::Duration;
+
+use super::{Message, MessageId, Network, NodeRef, ObjectEntry, SignalType, Type, TypeSignalEncoding};
+
+// End-to-end scenarios of the protocols that build generates (get, set and
+// commands), such that node firmware can be regression-tested against the
+// frames of this config. Frames are encoded with the layout of their message,
+// fields are addressed by their path in the message encoding (header.od_index).
+//
+// Object entry values are transferred as 32-bit words of data: the leaves of
+// the value are packed LSB first in the order of their definition and
+// fragmented into frames, the first frame sets sof, the last eof and toggle
+// alternates starting at 0. A set is answered once, after the frame with eof.
+// The tester acts as the client with the id of the first master
+// (NetworkBuilder::set_master) or, without masters, the id after the last node.
+
+/// raw value of an object entry, LSB first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceValue {
+    bits: u32,
+    bytes: Vec<u8>,
+}
+
+impl ConformanceValue {
+    fn zero(bits: u32) -> Self {
+        Self {
+            bits,
+            bytes: vec![0; bits.div_ceil(8) as usize],
+        }
+    }
+    // values are built by pushing their leaves.
+    fn with_capacity(bits: u32) -> Self {
+        Self {
+            bits: 0,
+            bytes: vec![0; bits.div_ceil(8) as usize],
+        }
+    }
+    fn push(&mut self, value: u64, size: u32) {
+        for i in 0..size {
+            if (value >> i) & 1 == 1 {
+                let bit = self.bits + i;
+                self.bytes[(bit / 8) as usize] |= 1 << (bit % 8);
+            }
+        }
+        self.bits += size;
+    }
+    fn word(&self, index: usize) -> u64 {
+        (0..4)
+            .filter_map(|byte| self.bytes.get(index * 4 + byte))
+            .enumerate()
+            .fold(0, |word, (byte, value)| word | (*value as u64) << (byte * 8))
+    }
+    /// number of 32-bit words (frames) the value is transferred in.
+    pub fn words(&self) -> usize {
+        (self.bits as usize).div_ceil(32).max(1)
+    }
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+    pub fn bytes(&self) -> &Vec<u8> {
+        &self.bytes
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConformanceFrame {
+    message: String,
+    id: MessageId,
+    bus: String,
+    // (path, raw value) of all fields that are not zero.
+    fields: Vec<(String, u64)>,
+    data: Vec<u8>,
+}
+
+impl ConformanceFrame {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    pub fn id(&self) -> &MessageId {
+        &self.id
+    }
+    pub fn bus(&self) -> &str {
+        &self.bus
+    }
+    /// the raw values of the fields of the frame, fields that
+    /// are not listed are zero.
+    pub fn fields(&self) -> &Vec<(String, u64)> {
+        &self.fields
+    }
+    /// the payload of the frame.
+    pub fn data(&self) -> &Vec<u8> {
+        &self.data
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConformanceStep {
+    /// the node under test holds the value in the object entry.
+    Assume { object_entry: String, value: ConformanceValue },
+    /// the tester transmits the frame.
+    Send(ConformanceFrame),
+    /// the node under test has to transmit the frame, within the timeout if there is one.
+    Expect { frame: ConformanceFrame, timeout: Option<Duration> },
+    /// the object entry holds the value after the scenario.
+    Verify { object_entry: String, value: ConformanceValue },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConformanceKind {
+    Get,
+    Set,
+    /// set of an entry that can't be written remotely.
+    SetError,
+    Command,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConformanceScenario {
+    name: String,
+    kind: ConformanceKind,
+    node: String,
+    steps: Vec<ConformanceStep>,
+}
+
+impl ConformanceScenario {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn kind(&self) -> ConformanceKind {
+        self.kind
+    }
+    /// the node under test.
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+    pub fn steps(&self) -> &Vec<ConformanceStep> {
+        &self.steps
+    }
+}
+
+fn find_leaf<'a>(encodings: &'a [TypeSignalEncoding], path: &[&str]) -> Option<&'a TypeSignalEncoding> {
+    let (first, rest) = path.split_first()?;
+    let encoding = encodings.iter().find(|encoding| encoding.name() == *first)?;
+    match (encoding, rest) {
+        (TypeSignalEncoding::Primitive(_), []) => Some(encoding),
+        (TypeSignalEncoding::Composite(composite), rest) => find_leaf(composite.attributes(), rest),
+        _ => None,
+    }
+}
+
+fn encode_frame(message: &Message, fields: Vec<(&str, u64)>) -> ConformanceFrame {
+    let attributes = message.encoding().map(|encoding| &encoding.attributes()[..]).unwrap_or(&[]);
+    let mut data = vec![0u8; message.payload_len()];
+    for (path, value) in &fields {
+        let Some(TypeSignalEncoding::Primitive(leaf)) = find_leaf(attributes, &path.split('.').collect::<Vec<_>>())
+        else {
+            panic!("message {} has no field {path}", message.name());
+        };
+        let signal = leaf.signal();
+        for i in 0..signal.size() as usize {
+            if (value >> i) & 1 == 1 {
+                let bit = signal.byte_offset() + i;
+                data[bit / 8] |= 1 << (bit % 8);
+            }
+        }
+    }
+    ConformanceFrame {
+        message: message.name().to_owned(),
+        id: *message.id(),
+        bus: message.bus().name().to_owned(),
+        fields: fields
+            .into_iter()
+            .filter(|(_, value)| *value != 0)
+            .map(|(path, value)| (path.to_owned(), value))
+            .collect(),
+        data,
+    }
+}
+
+/// value of the enum entry (e.g. the Success of an erno) of the field.
+fn enum_value(message: &Message, path: &str, entry: &str) -> u64 {
+    let attributes = message.encoding().map(|encoding| &encoding.attributes()[..]).unwrap_or(&[]);
+    let leaf = find_leaf(attributes, &path.split('.').collect::<Vec<_>>());
+    match leaf.map(|leaf| leaf.ty() as &Type) {
+        Some(Type::Enum { entries, .. }) => entries
+            .iter()
+            .find(|(name, _)| name == entry)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("{path} of {} has no entry {entry}", message.name())),
+        _ => panic!("{path} of {} is not an enum", message.name()),
+    }
+}
+
+// alternating bits, such that swapped bytes or shifted fields are detected.
+fn test_pattern(ty: &Type, value: &mut ConformanceValue) {
+    match ty {
+        Type::Primitive(signal_type) => {
+            let size = signal_type.size() as u32;
+            value.push(0x5555_5555_5555_5555 & (u64::MAX >> (64 - size)), size);
+        }
+        Type::Struct { attribs, .. } => {
+            for (_, attrib_ty) in attribs {
+                test_pattern(attrib_ty, value);
+            }
+        }
+        Type::Enum { size, entries, .. } => {
+            value.push(entries.last().map(|(_, value)| *value).unwrap_or(0), *size as u32);
+        }
+        Type::Array { len, ty } => {
+            for _ in 0..*len {
+                test_pattern(ty, value);
+            }
+        }
+    }
+}
+
+fn test_value(object_entry: &ObjectEntry) -> ConformanceValue {
+    let mut value = ConformanceValue::with_capacity(object_entry.ty().size());
+    match (object_entry.ty() as &Type, object_entry.bounds()) {
+        // the pattern might violate the bounds, the lower bound doesn't.
+        (
+            Type::Primitive(
+                signal_type @ (SignalType::SignedInt { .. } | SignalType::UnsignedInt { .. } | SignalType::Decimal { .. }),
+            ),
+            Some(bounds),
+        ) => {
+            let raw = ((bounds.min().value() - signal_type.offset()) / signal_type.scale()).round() as i64;
+            let size = signal_type.size() as u32;
+            value.push(raw as u64 & (u64::MAX >> (64 - size)), size);
+        }
+        _ => test_pattern(object_entry.ty(), &mut value),
+    }
+    value
+}
+
+struct Scenarios<'a> {
+    network: &'a Network,
+    client_id: u64,
+}
+
+impl Scenarios<'_> {
+    fn header(&self, node: &NodeRef, object_entry: &ObjectEntry) -> Vec<(&'static str, u64)> {
+        vec![
+            ("header.od_index", object_entry.id() as u64),
+            ("header.client_id", self.client_id),
+            ("header.server_id", node.id() as u64),
+        ]
+    }
+    // the frames of a fragmented transfer (get_resp and set_req).
+    fn fragments(
+        &self,
+        message: &Message,
+        node: &NodeRef,
+        object_entry: &ObjectEntry,
+        value: &ConformanceValue,
+    ) -> Vec<ConformanceFrame> {
+        let words = value.words();
+        (0..words)
+            .map(|i| {
+                let mut fields = vec![
+                    ("header.sof", (i == 0) as u64),
+                    ("header.eof", (i == words - 1) as u64),
+                    ("header.toggle", (i % 2) as u64),
+                ];
+                fields.extend(self.header(node, object_entry));
+                fields.push(("data", value.word(i)));
+                encode_frame(message, fields)
+            })
+            .collect()
+    }
+    fn get(&self, node: &NodeRef, object_entry: &ObjectEntry) -> ConformanceScenario {
+        let value = test_value(object_entry);
+        let mut steps = vec![
+            ConformanceStep::Assume {
+                object_entry: object_entry.name().to_owned(),
+                value: value.clone(),
+            },
+            ConformanceStep::Send(encode_frame(self.network.get_req_message(), self.header(node, object_entry))),
+        ];
+        for frame in self.fragments(self.network.get_resp_message(), node, object_entry, &value) {
+            steps.push(ConformanceStep::Expect { frame, timeout: None });
+        }
+        ConformanceScenario {
+            name: format!("get {}::{}", node.name(), object_entry.name()),
+            kind: ConformanceKind::Get,
+            node: node.name().to_owned(),
+            steps,
+        }
+    }
+    fn set(&self, node: &NodeRef, object_entry: &ObjectEntry, kind: ConformanceKind) -> ConformanceScenario {
+        let initial = ConformanceValue::zero(object_entry.ty().size());
+        let value = test_value(object_entry);
+        let set_resp = self.network.set_resp_message();
+        let (erno, result) = match kind {
+            ConformanceKind::SetError => ("Error", initial.clone()),
+            _ => ("Success", value.clone()),
+        };
+        let mut steps = vec![ConformanceStep::Assume {
+            object_entry: object_entry.name().to_owned(),
+            value: initial,
+        }];
+        for frame in self.fragments(self.network.set_req_message(), node, object_entry, &value) {
+            steps.push(ConformanceStep::Send(frame));
+        }
+        let mut fields = self.header(node, object_entry);
+        fields.push(("header.erno", enum_value(set_resp, "header.erno", erno)));
+        steps.push(ConformanceStep::Expect {
+            frame: encode_frame(set_resp, fields),
+            timeout: None,
+        });
+        steps.push(ConformanceStep::Verify {
+            object_entry: object_entry.name().to_owned(),
+            value: result,
+        });
+        let name = match kind {
+            ConformanceKind::SetError => "set error",
+            _ => "set",
+        };
+        ConformanceScenario {
+            name: format!("{name} {}::{}", node.name(), object_entry.name()),
+            kind,
+            node: node.name().to_owned(),
+            steps,
+        }
+    }
+}
+
+impl Network {
+    /// get, set and command scenarios of all nodes: a get of every object
+    /// entry, a set of every globally writable entry, a failing set of the
+    /// first constant entry and a call of every command (with arguments
+    /// that are all zero), which has to be answered within its timeout.
+    pub fn conformance_scenarios(&self) -> Vec<ConformanceScenario> {
+        let client_id = match self.masters().first() {
+            Some(master) => master.id() as u64,
+            None => self.nodes().iter().map(|node| node.id() as u64 + 1).max().unwrap_or(0),
+        };
+        let scenarios = Scenarios { network: self, client_id };
+        let mut result = vec![];
+        // passive nodes don't answer get and set requests.
+        for node in self.nodes().iter().filter(|node| !node.passive()) {
+            for object_entry in node.object_entries() {
+                result.push(scenarios.get(node, object_entry));
+                if matches!(object_entry.access(), super::ObjectEntryAccess::Global) {
+                    result.push(scenarios.set(node, object_entry, ConformanceKind::Set));
+                }
+            }
+            if let Some(object_entry) = node
+                .object_entries()
+                .iter()
+                .find(|object_entry| matches!(object_entry.access(), super::ObjectEntryAccess::Const))
+            {
+                result.push(scenarios.set(node, object_entry, ConformanceKind::SetError));
+            }
+            for command in node.commands() {
+                let resp = command.rx_message();
+                let success = enum_value(resp, "erno", "Success");
+                result.push(ConformanceScenario {
+                    name: format!("command {}::{}", node.name(), command.name()),
+                    kind: ConformanceKind::Command,
+                    node: node.name().to_owned(),
+                    steps: vec![
+                        ConformanceStep::Send(encode_frame(command.tx_message(), vec![])),
+                        ConformanceStep::Expect {
+                            frame: encode_frame(resp, vec![("erno", success)]),
+                            timeout: command.timeout(),
+                        },
+                    ],
+                });
+            }
+        }
+        result
+    }
+}
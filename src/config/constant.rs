@@ -0,0 +1,41 @@
+use std::hash::Hash;
+
+use super::ConfigRef;
+
+pub type ConstantRef = ConfigRef<Constant>;
+
+/// named scalar defined once for the whole network, e.g. a limit
+/// that is used as a bound of object entries and in the firmware.
+#[derive(Debug)]
+pub struct Constant {
+    name: String,
+    value: f64,
+    unit: String,
+}
+
+impl Hash for Constant {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        state.write_u64(self.value.to_bits());
+        for b in self.unit.bytes() {
+            state.write_u8(b);
+        }
+    }
+}
+
+impl Constant {
+    pub fn new(name: String, value: f64, unit: String) -> Self {
+        Self { name, value, unit }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+}
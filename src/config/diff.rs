@@ -0,0 +1,76 @@
+use super::{
+    layout::{layout_violations, MessageLayout},
+    MessageRef, Network,
+};
+
+// Compares the messages of two networks by name.
+
+#[derive(Debug)]
+pub enum MessageChange {
+    Added(MessageRef),
+    Removed(MessageRef),
+    Modified { old: MessageRef, new: MessageRef },
+}
+
+impl MessageChange {
+    /// removing a message that was not deprecated before breaks
+    /// every deployed node that still depends on it, modifications
+    /// break it if they violate the layout rules (see layout_violations).
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            MessageChange::Added(_) => false,
+            MessageChange::Removed(message) => message.deprecation().is_none(),
+            MessageChange::Modified { old, new } => {
+                !layout_violations(&MessageLayout::of(old), &MessageLayout::of(new)).is_empty()
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NetworkDiff {
+    changes: Vec<MessageChange>,
+}
+
+impl NetworkDiff {
+    pub fn changes(&self) -> &Vec<MessageChange> {
+        &self.changes
+    }
+    pub fn breaking_changes(&self) -> Vec<&MessageChange> {
+        self.changes.iter().filter(|c| c.is_breaking()).collect()
+    }
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|c| c.is_breaking())
+    }
+}
+
+impl Network {
+    /// changes from self (old) to other (new).
+    pub fn diff(&self, other: &Network) -> NetworkDiff {
+        let mut changes = vec![];
+        for old in self.messages() {
+            match other.messages().iter().find(|new| new.name() == old.name()) {
+                Some(new) => {
+                    if old.id() != new.id()
+                        || old.payload_len() != new.payload_len()
+                        || old.bus().id() != new.bus().id()
+                        || old.version() != new.version()
+                        || MessageLayout::of(old).signals() != MessageLayout::of(new).signals()
+                    {
+                        changes.push(MessageChange::Modified {
+                            old: old.clone(),
+                            new: new.clone(),
+                        });
+                    }
+                }
+                None => changes.push(MessageChange::Removed(old.clone())),
+            }
+        }
+        for new in other.messages() {
+            if !self.messages().iter().any(|old| old.name() == new.name()) {
+                changes.push(MessageChange::Added(new.clone()));
+            }
+        }
+        NetworkDiff { changes }
+    }
+}
@@ -0,0 +1,156 @@
+use super::{MessageRef, Network, NodeRef};
+
+// Assertions on the contents of a built network, meant for tests.
+// All assertions panic with a readable message instead of returning errors.
+
+pub struct MessageExpectation<'a> {
+    network: &'a Network,
+    message: MessageRef,
+}
+
+impl<'a> MessageExpectation<'a> {
+    pub fn message(&self) -> &MessageRef {
+        &self.message
+    }
+    #[track_caller]
+    pub fn with_dlc(self, dlc: u8) -> Self {
+        assert!(
+            self.message.dlc() == dlc,
+            "expected message {} to have dlc {dlc}, but it has dlc {}",
+            self.message.name(),
+            self.message.dlc()
+        );
+        self
+    }
+    #[track_caller]
+    pub fn with_id(self, id: u32) -> Self {
+        assert!(
+            self.message.id().as_u32() == id,
+            "expected message {} to have id {id:#X}, but it has id {}",
+            self.message.name(),
+            self.message.id()
+        );
+        self
+    }
+    #[track_caller]
+    pub fn with_bus(self, bus_name: &str) -> Self {
+        assert!(
+            self.message.bus().name() == bus_name,
+            "expected message {} to be on bus {bus_name}, but it is on bus {}",
+            self.message.name(),
+            self.message.bus().name()
+        );
+        self
+    }
+    #[track_caller]
+    pub fn with_signal(self, signal_name: &str) -> Self {
+        let signal_names: Vec<&str> = self.message.signals().iter().map(|s| s.name()).collect();
+        assert!(
+            signal_names.contains(&signal_name),
+            "expected message {} to contain signal {signal_name}, signals are {signal_names:?}",
+            self.message.name()
+        );
+        self
+    }
+    #[track_caller]
+    pub fn with_receiver(self, node_name: &str) -> Self {
+        let receivers = self.nodes_with(|node| node.rx_messages());
+        assert!(
+            receivers.contains(&node_name),
+            "expected message {} to be received by {node_name}, receivers are {receivers:?}",
+            self.message.name()
+        );
+        self
+    }
+    #[track_caller]
+    pub fn with_transmitter(self, node_name: &str) -> Self {
+        let transmitters = self.nodes_with(|node| node.tx_messages());
+        assert!(
+            transmitters.contains(&node_name),
+            "expected message {} to be transmitted by {node_name}, transmitters are {transmitters:?}",
+            self.message.name()
+        );
+        self
+    }
+
+    fn nodes_with(&self, messages: impl Fn(&NodeRef) -> &Vec<MessageRef>) -> Vec<&'a str> {
+        self.network
+            .nodes()
+            .iter()
+            .filter(|node| {
+                messages(node)
+                    .iter()
+                    .any(|m| m.name() == self.message.name())
+            })
+            .map(|node| node.name())
+            .collect()
+    }
+}
+
+pub struct NodeExpectation {
+    node: NodeRef,
+}
+
+impl NodeExpectation {
+    pub fn node(&self) -> &NodeRef {
+        &self.node
+    }
+    #[track_caller]
+    pub fn with_object_entry(self, name: &str) -> Self {
+        let names: Vec<&str> = self.node.object_entries().iter().map(|oe| oe.name()).collect();
+        assert!(
+            names.contains(&name),
+            "expected node {} to define object entry {name}, object entries are {names:?}",
+            self.node.name()
+        );
+        self
+    }
+    #[track_caller]
+    pub fn with_tx_stream(self, name: &str) -> Self {
+        let names: Vec<&str> = self.node.tx_streams().iter().map(|s| s.name()).collect();
+        assert!(
+            names.contains(&name),
+            "expected node {} to transmit stream {name}, tx streams are {names:?}",
+            self.node.name()
+        );
+        self
+    }
+    #[track_caller]
+    pub fn with_command(self, name: &str) -> Self {
+        let names: Vec<&str> = self.node.commands().iter().map(|c| c.name()).collect();
+        assert!(
+            names.contains(&name),
+            "expected node {} to define command {name}, commands are {names:?}",
+            self.node.name()
+        );
+        self
+    }
+}
+
+impl Network {
+    /// panics if the network doesn't contain a message with the given name.
+    #[track_caller]
+    pub fn expect_message(&self, name: &str) -> MessageExpectation<'_> {
+        match self.messages().iter().find(|m| m.name() == name) {
+            Some(message) => MessageExpectation {
+                network: self,
+                message: message.clone(),
+            },
+            None => {
+                let names: Vec<&str> = self.messages().iter().map(|m| m.name()).collect();
+                panic!("expected network to contain message {name}, messages are {names:?}")
+            }
+        }
+    }
+    /// panics if the network doesn't contain a node with the given name.
+    #[track_caller]
+    pub fn expect_node(&self, name: &str) -> NodeExpectation {
+        match self.nodes().iter().find(|n| n.name() == name) {
+            Some(node) => NodeExpectation { node: node.clone() },
+            None => {
+                let names: Vec<&str> = self.nodes().iter().map(|n| n.name()).collect();
+                panic!("expected network to contain node {name}, nodes are {names:?}")
+            }
+        }
+    }
+}
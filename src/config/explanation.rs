@@ -0,0 +1,159 @@
+use std::fmt::Display;
+
+use crate::simulator;
+
+use super::{bus::connected_nodes, Message, MessageId, MessageResolutionInfo, Network};
+
+// Reconstructs the decisions of the id resolver for a single message from
+// the built network: the receiver set, which determines the setcode (the
+// low bits of the id), the bus with the loads of all buses the message could
+// have been placed on, and the priority bucket and position, which make up
+// the high bits of the id (lower ids win the arbitration).
+
+#[derive(Debug, Clone)]
+pub struct AssignmentExplanation {
+    message: String,
+    id: MessageId,
+    transmitters: Vec<String>,
+    receivers: Vec<String>,
+    resolution_info: MessageResolutionInfo,
+    // (bus, load) of every bus that all transmitters and receivers are connected to.
+    candidate_buses: Vec<(String, f64)>,
+    message_load: f64,
+}
+
+impl AssignmentExplanation {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+    pub fn id(&self) -> &MessageId {
+        &self.id
+    }
+    pub fn transmitters(&self) -> &Vec<String> {
+        &self.transmitters
+    }
+    /// the receiver set of the message, messages with the same
+    /// receivers share a setcode.
+    pub fn receivers(&self) -> &Vec<String> {
+        &self.receivers
+    }
+    pub fn resolution_info(&self) -> &MessageResolutionInfo {
+        &self.resolution_info
+    }
+    /// (bus, load) of the buses that all transmitters and receivers are
+    /// connected to, the load (0..1) includes all messages on the bus.
+    pub fn candidate_buses(&self) -> &Vec<(String, f64)> {
+        &self.candidate_buses
+    }
+    /// share of the load (0..1) of the chosen bus caused by the message.
+    pub fn message_load(&self) -> f64 {
+        self.message_load
+    }
+    /// true if the id was fixed by the configuration.
+    pub fn is_fixed(&self) -> bool {
+        self.resolution_info.requested_priority().is_none()
+    }
+}
+
+fn optional(value: Option<u32>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_else(|| "-".to_owned())
+}
+
+fn node_list(nodes: &[String]) -> String {
+    if nodes.is_empty() {
+        "none".to_owned()
+    } else {
+        nodes.join(", ")
+    }
+}
+
+impl Display for AssignmentExplanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let info = &self.resolution_info;
+        let ide = if self.id.ide() { "ext" } else { "std" };
+        writeln!(f, "message {} : id {:#X} ({ide})", self.message, self.id.as_u32())?;
+        writeln!(f, "  transmitters : {}", node_list(&self.transmitters))?;
+        writeln!(f, "  receivers : {}", node_list(&self.receivers))?;
+        let candidates: Vec<String> = self
+            .candidate_buses
+            .iter()
+            .map(|(bus, load)| format!("{bus} {:.1}%", load * 100.0))
+            .collect();
+        writeln!(
+            f,
+            "  bus : {} (message load {:.2}%, candidates : {})",
+            info.bus().name(),
+            self.message_load * 100.0,
+            node_list(&candidates)
+        )?;
+        if self.is_fixed() {
+            return writeln!(f, "  fixed id, not resolved");
+        }
+        match info.setcode() {
+            Some(setcode) => writeln!(
+                f,
+                "  setcode : {setcode:#b} ({} bits)",
+                info.setcode_len()
+            )?,
+            None => writeln!(f, "  setcode : - (not part of a receiver set)")?,
+        }
+        writeln!(
+            f,
+            "  priority bucket : {} (requested {})",
+            optional(info.priority_bucket()),
+            optional(info.requested_priority())
+        )?;
+        writeln!(f, "  position : {}", optional(info.position()))
+    }
+}
+
+fn explain(network: &Network, message: &Message) -> AssignmentExplanation {
+    let transmitters: Vec<String> = network
+        .nodes()
+        .iter()
+        .filter(|node| node.tx_messages().iter().any(|m| m.name() == message.name()))
+        .map(|node| node.name().to_owned())
+        .collect();
+    let receivers: Vec<String> = network
+        .nodes()
+        .iter()
+        .filter(|node| node.rx_messages().iter().any(|m| m.name() == message.name()))
+        .map(|node| node.name().to_owned())
+        .collect();
+    let candidate_buses = network
+        .buses()
+        .iter()
+        .filter(|bus| {
+            let connected = connected_nodes(network.nodes(), bus);
+            transmitters
+                .iter()
+                .chain(&receivers)
+                .all(|name| connected.iter().any(|node| node.name() == name))
+        })
+        .map(|bus| (bus.name().to_owned(), network.bus_load(bus)))
+        .collect();
+    let (period, frames) = simulator::worst_case_release(message);
+    let message_load =
+        frames as f64 * message.bus().load(network.frame_time_model(), message, period);
+    AssignmentExplanation {
+        message: message.name().to_owned(),
+        id: *message.id(),
+        transmitters,
+        receivers,
+        resolution_info: message.resolution_info().clone(),
+        candidate_buses,
+        message_load,
+    }
+}
+
+impl Network {
+    /// the decisions of the id resolver that led to the id of the message,
+    /// None if there is no message with the name. Display gives a
+    /// structured text.
+    pub fn explain_assignment(&self, message_name: &str) -> Option<AssignmentExplanation> {
+        self.messages()
+            .iter()
+            .find(|message| message.name() == message_name)
+            .map(|message| explain(self, message))
+    }
+}
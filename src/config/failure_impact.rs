@@ -0,0 +1,154 @@
+use super::{message::MessageUsage, Network, Node};
+
+// What the rest of the network loses if a single node drops off the bus.
+// Derived from the cross references between nodes: the streams the node
+// transmits are lost for their receivers, its commands for their callers and
+// its object entries for the nodes that receive them through a stream.
+// Entries that are only accessed with get/set requests are reachable by any
+// client and therefore listed without receivers.
+
+/// something that the failed node provides, with the nodes depending on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImpactedItem {
+    name: String,
+    receivers: Vec<String>,
+}
+
+impl ImpactedItem {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// names of the nodes that lose the item.
+    pub fn receivers(&self) -> &Vec<String> {
+        &self.receivers
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureImpact {
+    node: String,
+    streams: Vec<ImpactedItem>,
+    commands: Vec<ImpactedItem>,
+    object_entries: Vec<ImpactedItem>,
+}
+
+impl FailureImpact {
+    /// name of the failed node.
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+    /// streams transmitted by the node and the nodes receiving them.
+    pub fn streams(&self) -> &Vec<ImpactedItem> {
+        &self.streams
+    }
+    /// commands executed by the node and the nodes calling them.
+    pub fn commands(&self) -> &Vec<ImpactedItem> {
+        &self.commands
+    }
+    /// object entries of the node and the nodes receiving them in a stream.
+    pub fn object_entries(&self) -> &Vec<ImpactedItem> {
+        &self.object_entries
+    }
+    /// all nodes that lose at least one item, sorted by name.
+    pub fn affected_nodes(&self) -> Vec<String> {
+        let mut nodes: Vec<String> = self
+            .streams
+            .iter()
+            .chain(&self.commands)
+            .chain(&self.object_entries)
+            .flat_map(|item| item.receivers.iter().cloned())
+            .collect();
+        nodes.sort();
+        nodes.dedup();
+        nodes
+    }
+}
+
+fn push_unique(names: &mut Vec<String>, name: &str) {
+    if !names.iter().any(|n| n == name) {
+        names.push(name.to_owned());
+    }
+}
+
+fn failure_impact(network: &Network, node: &Node) -> FailureImpact {
+    let streams = node
+        .tx_streams()
+        .iter()
+        .map(|stream| {
+            let mut receivers = vec![];
+            for other in network.nodes() {
+                if other
+                    .rx_streams()
+                    .iter()
+                    .any(|rx_stream| rx_stream.message().name() == stream.message().name())
+                {
+                    push_unique(&mut receivers, other.name());
+                }
+            }
+            ImpactedItem {
+                name: stream.name().to_owned(),
+                receivers,
+            }
+        })
+        .collect();
+
+    let commands = node
+        .commands()
+        .iter()
+        .map(|command| ImpactedItem {
+            name: command.name().to_owned(),
+            receivers: network
+                .command_callers(command)
+                .iter()
+                .map(|caller| caller.name().to_owned())
+                .collect(),
+        })
+        .collect();
+
+    let object_entries = node
+        .object_entries()
+        .iter()
+        .map(|object_entry| {
+            let mut receivers = vec![];
+            for other in network.nodes() {
+                for rx_stream in other.rx_streams() {
+                    let MessageUsage::Stream(tx_stream) = rx_stream.message().usage() else {
+                        continue;
+                    };
+                    let received = std::iter::zip(tx_stream.mapping(), rx_stream.mapping())
+                        .any(|(tx_oe, rx_oe)| {
+                            rx_oe.is_some()
+                                && tx_oe.as_ref().is_some_and(|tx_oe| {
+                                    tx_oe.node().name() == node.name()
+                                        && tx_oe.name() == object_entry.name()
+                                })
+                        });
+                    if received {
+                        push_unique(&mut receivers, other.name());
+                    }
+                }
+            }
+            ImpactedItem {
+                name: object_entry.name().to_owned(),
+                receivers,
+            }
+        })
+        .collect();
+
+    FailureImpact {
+        node: node.name().to_owned(),
+        streams,
+        commands,
+        object_entries,
+    }
+}
+
+impl Network {
+    /// impact of the failure of every single node, in the order of nodes.
+    pub fn failure_impact(&self) -> Vec<FailureImpact> {
+        self.nodes()
+            .iter()
+            .map(|node| failure_impact(self, node))
+            .collect()
+    }
+}
@@ -0,0 +1,62 @@
+use std::hash::Hash;
+
+use super::{ConfigRef, ObjectEntryRef};
+
+pub type FaultRef = ConfigRef<Fault>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FaultSeverity {
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+/// Fault reported by a node. The code is unique within the network
+/// (entry of the fault_code enum), the state of the fault is the
+/// object entry, which is part of the fault_status stream of the node.
+#[derive(Debug)]
+pub struct Fault {
+    name: String,
+    code: u64,
+    severity: FaultSeverity,
+    object_entry: ObjectEntryRef,
+}
+
+impl Hash for Fault {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for b in self.name.bytes() {
+            state.write_u8(b);
+        }
+        state.write_u64(self.code);
+        state.write_u8(self.severity as u8);
+    }
+}
+
+impl Fault {
+    pub fn new(
+        name: String,
+        code: u64,
+        severity: FaultSeverity,
+        object_entry: ObjectEntryRef,
+    ) -> Self {
+        Self {
+            name,
+            code,
+            severity,
+            object_entry,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn code(&self) -> u64 {
+        self.code
+    }
+    pub fn severity(&self) -> FaultSeverity {
+        self.severity
+    }
+    pub fn object_entry(&self) -> &ObjectEntryRef {
+        &self.object_entry
+    }
+}
@@ -0,0 +1,67 @@
+use super::{MessageId, MessageRef, NodeRef};
+
+/// hardware acceptance filter, a frame passes if its id
+/// matches the filter id in all bits of the mask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Filter {
+    id: u32,
+    mask: u32,
+    ide: bool,
+}
+
+impl Filter {
+    pub fn new(id: u32, mask: u32, ide: bool) -> Self {
+        Self {
+            id: id & mask,
+            mask,
+            ide,
+        }
+    }
+    /// filter that only accepts the given id.
+    pub fn exact(id: u32, ide: bool) -> Self {
+        Self::new(id, 0xFFFFFFFFu32, ide)
+    }
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+    pub fn ide(&self) -> bool {
+        self.ide
+    }
+    pub fn accepts(&self, id: u32, ide: bool) -> bool {
+        self.ide == ide && (id & self.mask) == self.id
+    }
+    pub fn accepts_id(&self, id: &MessageId) -> bool {
+        self.accepts(id.as_u32(), id.ide())
+    }
+}
+
+/// filter configuration of a single node.
+#[derive(Debug, Clone)]
+pub struct NodeFilters {
+    node: NodeRef,
+    filters: Vec<Filter>,
+    software_filters: Vec<MessageRef>,
+}
+
+impl NodeFilters {
+    pub fn new(node: NodeRef, filters: Vec<Filter>, software_filters: Vec<MessageRef>) -> Self {
+        Self {
+            node,
+            filters,
+            software_filters,
+        }
+    }
+    pub fn node(&self) -> &NodeRef {
+        &self.node
+    }
+    pub fn filters(&self) -> &Vec<Filter> {
+        &self.filters
+    }
+    /// messages that pass the filters, but are not received by the node.
+    pub fn software_filters(&self) -> &Vec<MessageRef> {
+        &self.software_filters
+    }
+}
@@ -0,0 +1,265 @@
+use crate::errors::{self, Result};
+
+use super::{Filter, NodeFilters};
+
+// Register values of the acceptance filters of common CAN controllers. The
+// filters of a node are translated one to one, exact filters (full mask) are
+// packed in pairs into the list / dual id modes of the controllers. Frames
+// that don't match any filter have to be rejected by the global configuration
+// of the controller, which is not part of the export.
+
+const STD_MASK: u32 = 0x7FF;
+const EXT_MASK: u32 = 0x1FFF_FFFF;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardwareTarget {
+    /// STM32 bxCAN with 14 filter banks in 32 bit scale.
+    BxCan,
+    /// Bosch M_CAN / STM32 FDCAN with 28 standard and 8 extended filter elements.
+    Fdcan,
+    /// Microchip MCP2515 with 2 masks (RXM0, RXM1) and 6 filters (RXF0-RXF5).
+    Mcp2515,
+}
+
+impl HardwareTarget {
+    fn name(&self) -> &'static str {
+        match self {
+            HardwareTarget::BxCan => "bxCAN",
+            HardwareTarget::Fdcan => "FDCAN",
+            HardwareTarget::Mcp2515 => "MCP2515",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BxCanFilterMode {
+    /// FR1 is the id, FR2 the mask.
+    Mask,
+    /// FR1 and FR2 are both exact ids.
+    List,
+}
+
+/// a bxCAN filter bank in 32 bit scale (CAN_FxR1, CAN_FxR2), the bits
+/// are STID[31:21], EXID[20:3], IDE[2], RTR[1].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BxCanFilterBank {
+    pub mode: BxCanFilterMode,
+    pub fr1: u32,
+    pub fr2: u32,
+}
+
+/// FDCAN filter elements as they are written into the message RAM, all
+/// elements store matching frames in Rx FIFO 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FdcanFilterElements {
+    pub standard: Vec<u32>,
+    pub extended: Vec<[u32; 2]>,
+}
+
+/// MCP2515 mask and filter registers as (SIDH, SIDL, EID8, EID0).
+/// RXM0 applies to RXF0-RXF1 (RXB0), RXM1 to RXF2-RXF5 (RXB1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mcp2515Registers {
+    pub masks: [[u8; 4]; 2],
+    pub filters: [[u8; 4]; 6],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterRegisters {
+    BxCan(Vec<BxCanFilterBank>),
+    Fdcan(FdcanFilterElements),
+    Mcp2515(Mcp2515Registers),
+}
+
+fn id_width(ide: bool) -> u32 {
+    if ide {
+        EXT_MASK
+    } else {
+        STD_MASK
+    }
+}
+
+/// mask that is limited to the bits of the id.
+fn filter_mask(filter: &Filter) -> u32 {
+    filter.mask() & id_width(filter.ide())
+}
+
+fn is_exact(filter: &Filter) -> bool {
+    filter_mask(filter) == id_width(filter.ide())
+}
+
+fn bxcan_id(id: u32, ide: bool) -> u32 {
+    if ide {
+        (id << 3) | 0x4
+    } else {
+        id << 21
+    }
+}
+
+fn bxcan_banks(filters: &[Filter]) -> Vec<BxCanFilterBank> {
+    let mut banks: Vec<BxCanFilterBank> = filters
+        .iter()
+        .filter(|filter| !is_exact(filter))
+        .map(|filter| BxCanFilterBank {
+            mode: BxCanFilterMode::Mask,
+            fr1: bxcan_id(filter.id(), filter.ide()),
+            // the IDE bit always has to match.
+            fr2: bxcan_id(filter_mask(filter), filter.ide()) | 0x4,
+        })
+        .collect();
+    let exact: Vec<u32> = filters
+        .iter()
+        .filter(|filter| is_exact(filter))
+        .map(|filter| bxcan_id(filter.id(), filter.ide()))
+        .collect();
+    for pair in exact.chunks(2) {
+        banks.push(BxCanFilterBank {
+            mode: BxCanFilterMode::List,
+            fr1: pair[0],
+            fr2: *pair.last().expect("chunks are never empty"),
+        });
+    }
+    banks
+}
+
+fn fdcan_elements(filters: &[Filter]) -> FdcanFilterElements {
+    // SFT / EFT: 1 = dual id, 2 = classic (id, mask). SFEC / EFEC 1 = Rx FIFO 0.
+    let standard_element = |sft: u32, id1: u32, id2: u32| (sft << 30) | (1 << 27) | (id1 << 16) | id2;
+    let extended_element = |eft: u32, id1: u32, id2: u32| [(1 << 29) | id1, (eft << 30) | id2];
+    let mut elements = FdcanFilterElements {
+        standard: vec![],
+        extended: vec![],
+    };
+    for ide in [false, true] {
+        let (classic, exact): (Vec<&Filter>, Vec<&Filter>) = filters
+            .iter()
+            .filter(|filter| filter.ide() == ide)
+            .partition(|filter| !is_exact(filter));
+        let classic = classic
+            .iter()
+            .map(|filter| (2, filter.id(), filter_mask(filter)));
+        let dual = exact.chunks(2).map(|pair| {
+            (1, pair[0].id(), pair.last().expect("chunks are never empty").id())
+        });
+        for (ty, id1, id2) in classic.chain(dual) {
+            if ide {
+                elements.extended.push(extended_element(ty, id1, id2));
+            } else {
+                elements.standard.push(standard_element(ty, id1, id2));
+            }
+        }
+    }
+    elements
+}
+
+/// (SIDH, SIDL, EID8, EID0), the EXIDE bit is only set for filters.
+fn mcp2515_register(id: u32, ide: bool, exide: bool) -> [u8; 4] {
+    let (sid, eid) = if ide { (id >> 18, id & 0x3FFFF) } else { (id, 0) };
+    [
+        (sid >> 3) as u8,
+        (((sid & 0x7) << 5) as u8) | (if exide { 0x08 } else { 0 }) | ((eid >> 16) & 0x3) as u8,
+        ((eid >> 8) & 0xFF) as u8,
+        (eid & 0xFF) as u8,
+    ]
+}
+
+fn mcp2515_registers(node: &str, filters: &[Filter]) -> Result<Mcp2515Registers> {
+    let invalid = |msg: String| Err(errors::ConfigError::InvalidFilterExport(msg));
+    if filters.is_empty() {
+        return invalid(format!("node {node} has no filters"));
+    }
+    // filters can only share a mask register if their masks are equal.
+    let mut groups: Vec<([u8; 4], Vec<[u8; 4]>)> = vec![];
+    for filter in filters {
+        let mask = mcp2515_register(filter_mask(filter), filter.ide(), false);
+        let register = mcp2515_register(filter.id(), filter.ide(), filter.ide());
+        match groups.iter_mut().find(|(group_mask, _)| *group_mask == mask) {
+            Some((_, registers)) => registers.push(register),
+            None => groups.push((mask, vec![register])),
+        }
+    }
+    // the smaller group is assigned to RXB0 with only 2 filters.
+    groups.sort_by_key(|(_, registers)| registers.len());
+    let (rxb0, rxb1) = match groups.as_slice() {
+        [single] => (single, single),
+        [small, large] => (small, large),
+        _ => {
+            return invalid(format!(
+                "the filters of node {node} require {} different masks, but the MCP2515 only has 2",
+                groups.len()
+            ))
+        }
+    };
+    let fits = if groups.len() == 1 {
+        rxb0.1.len() <= 6
+    } else {
+        rxb0.1.len() <= 2 && rxb1.1.len() <= 4
+    };
+    if !fits {
+        return invalid(format!(
+            "the filters of node {node} don't fit into the filters of the MCP2515"
+        ));
+    }
+    let (rxb0_filters, rxb1_filters) = if groups.len() == 1 {
+        let split = rxb0.1.len().min(2);
+        (&rxb0.1[..split], &rxb0.1[split..])
+    } else {
+        (&rxb0.1[..], &rxb1.1[..])
+    };
+    // unused filters repeat a filter of the same buffer, which doesn't
+    // accept additional frames.
+    let fill = |registers: &[[u8; 4]], fallback: &[[u8; 4]], count: usize| -> Vec<[u8; 4]> {
+        let registers = if registers.is_empty() { fallback } else { registers };
+        (0..count).map(|i| registers[i.min(registers.len() - 1)]).collect()
+    };
+    let rxb0_registers = fill(rxb0_filters, rxb1_filters, 2);
+    let rxb1_registers = fill(rxb1_filters, rxb0_filters, 4);
+    let mut registers = Mcp2515Registers {
+        masks: [rxb0.0, rxb1.0],
+        filters: [[0; 4]; 6],
+    };
+    for (slot, register) in registers
+        .filters
+        .iter_mut()
+        .zip(rxb0_registers.into_iter().chain(rxb1_registers))
+    {
+        *slot = register;
+    }
+    Ok(registers)
+}
+
+impl NodeFilters {
+    /// register values of the hardware filters for the controller, fails
+    /// if the filters don't fit into the filters of the controller.
+    pub fn export(&self, target: HardwareTarget) -> Result<FilterRegisters> {
+        let node = self.node().name();
+        let too_many = |kind: &str, count: usize, capacity: usize| {
+            Err(errors::ConfigError::InvalidFilterExport(format!(
+                "the filters of node {node} require {count} {kind}, but the {} only has {capacity}",
+                target.name()
+            )))
+        };
+        match target {
+            HardwareTarget::BxCan => {
+                let banks = bxcan_banks(self.filters());
+                if banks.len() > 14 {
+                    return too_many("filter banks", banks.len(), 14);
+                }
+                Ok(FilterRegisters::BxCan(banks))
+            }
+            HardwareTarget::Fdcan => {
+                let elements = fdcan_elements(self.filters());
+                if elements.standard.len() > 28 {
+                    return too_many("standard filter elements", elements.standard.len(), 28);
+                }
+                if elements.extended.len() > 8 {
+                    return too_many("extended filter elements", elements.extended.len(), 8);
+                }
+                Ok(FilterRegisters::Fdcan(elements))
+            }
+            HardwareTarget::Mcp2515 => {
+                Ok(FilterRegisters::Mcp2515(mcp2515_registers(node, self.filters())?))
+            }
+        }
+    }
+}
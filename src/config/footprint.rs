@@ -0,0 +1,154 @@
+use std::fmt::Display;
+
+use super::{MessageRef, Node, Type};
+
+// Estimates the static memory of the generated firmware tables of a node.
+// The numbers are rough upper bounds of the generated code, they are only
+// meant to catch early that a config no longer fits a small mcu.
+
+/// costs in bytes of the parts of the generated firmware.
+#[derive(Debug, Clone)]
+pub struct CostModel {
+    /// runtime (can driver, scheduler, heartbeat) that every node contains.
+    pub base_ram: usize,
+    pub base_flash: usize,
+    /// frame buffer of a rx or tx message (id, dlc and data).
+    pub message_ram: usize,
+    /// dispatch table entry and pack/unpack function of a message.
+    pub message_flash: usize,
+    /// code to pack or unpack a single signal.
+    pub signal_flash: usize,
+    /// get/set handler table entry of an object entry.
+    pub object_entry_flash: usize,
+    /// scheduling state of a stream.
+    pub stream_ram: usize,
+    pub stream_flash: usize,
+    /// handler or call stub of a command.
+    pub command_ram: usize,
+    pub command_flash: usize,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            base_ram: 512,
+            base_flash: 4096,
+            message_ram: 16,
+            message_flash: 48,
+            signal_flash: 12,
+            object_entry_flash: 24,
+            stream_ram: 8,
+            stream_flash: 32,
+            command_ram: 8,
+            command_flash: 96,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Footprint {
+    ram: usize,
+    flash: usize,
+}
+
+impl Footprint {
+    pub fn ram(&self) -> usize {
+        self.ram
+    }
+    pub fn flash(&self) -> usize {
+        self.flash
+    }
+    pub fn fits(&self, ram: usize, flash: usize) -> bool {
+        self.ram <= ram && self.flash <= flash
+    }
+}
+
+impl Display for Footprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ram: {} bytes, flash: {} bytes", self.ram, self.flash)
+    }
+}
+
+fn container_bytes(size: u8) -> usize {
+    match size {
+        0..=8 => 1,
+        9..=16 => 2,
+        17..=32 => 4,
+        _ => 8,
+    }
+}
+
+/// bytes of the variable that stores a value of the type.
+fn storage_bytes(ty: &Type) -> usize {
+    match ty {
+        Type::Primitive(signal_type) => match signal_type {
+            super::SignalType::Decimal {
+                size: _,
+                offset: _,
+                scale: _,
+            } => 4,
+            _ => container_bytes(signal_type.size()),
+        },
+        Type::Struct {
+            name: _,
+            description: _,
+            attribs,
+            visibility: _,
+            annotations: _,
+        } => attribs.iter().map(|(_, ty)| storage_bytes(ty)).sum(),
+        Type::Enum {
+            name: _,
+            description: _,
+            size,
+            entries: _,
+            visibility: _,
+            annotations: _,
+        } => container_bytes(*size),
+        Type::Array { len, ty } => *len * storage_bytes(ty),
+    }
+}
+
+fn messages_flash(messages: &[MessageRef], model: &CostModel) -> usize {
+    messages
+        .iter()
+        .map(|message| {
+            let signals = message
+                .signals()
+                .iter()
+                .filter(|signal| !signal.is_reserved())
+                .count();
+            model.message_flash + signals * model.signal_flash
+        })
+        .sum()
+}
+
+impl Node {
+    /// estimated static memory of the generated firmware of the node,
+    /// see CostModel for the default costs.
+    pub fn footprint_estimate(&self) -> Footprint {
+        self.footprint_estimate_with(&CostModel::default())
+    }
+    pub fn footprint_estimate_with(&self, model: &CostModel) -> Footprint {
+        let messages = self.rx_messages().len() + self.tx_messages().len();
+        let streams = self.rx_streams().len() + self.tx_streams().len();
+        let commands = self.commands().len() + self.extern_commands().len();
+        let object_entries_ram: usize = self
+            .object_entries()
+            .iter()
+            .map(|object_entry| storage_bytes(object_entry.ty()))
+            .sum();
+
+        let ram = model.base_ram
+            + messages * model.message_ram
+            + streams * model.stream_ram
+            + commands * model.command_ram
+            + object_entries_ram;
+        let flash = model.base_flash
+            + messages_flash(self.rx_messages(), model)
+            + messages_flash(self.tx_messages(), model)
+            + streams * model.stream_flash
+            + commands * model.command_flash
+            + self.object_entries().len() * model.object_entry_flash;
+        Footprint { ram, flash }
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Duration;
+
+// Worst-case transmission time of a single frame, used for bus load
+// estimations. Frame layouts follow ISO 11898-1, the interframe space
+// (3 bits) is counted as part of the frame.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitStuffing {
+    /// a stuff bit after every 4 bits of the stuffed fields,
+    /// which is the worst case of the bit stuffing rule.
+    WorstCase,
+    /// only the fixed stuff bits of CAN FD are counted.
+    Ignored,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    Classic,
+    Fd,
+    /// CAN FD with bit rate switching, the data phase is transmitted
+    /// with bitrate_ratio times the nominal bitrate.
+    FdBrs { bitrate_ratio: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTimeModel {
+    pub format: FrameFormat,
+    pub stuffing: BitStuffing,
+}
+
+impl Default for FrameTimeModel {
+    fn default() -> Self {
+        Self {
+            format: FrameFormat::Classic,
+            stuffing: BitStuffing::WorstCase,
+        }
+    }
+}
+
+// CRC delimiter, ACK slot, ACK delimiter, EOF and interframe space.
+const TAIL_BITS: u64 = 1 + 1 + 1 + 7 + 3;
+
+const FD_PAYLOAD_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+impl FrameTimeModel {
+    /// payload length that is actually transmitted for the given number of bytes,
+    /// classic frames are limited to 8 bytes and CAN FD rounds up to the next valid length.
+    pub fn payload_len(&self, bytes: usize) -> usize {
+        match self.format {
+            FrameFormat::Classic => bytes.min(8),
+            FrameFormat::Fd | FrameFormat::FdBrs { bitrate_ratio: _ } => FD_PAYLOAD_LENGTHS
+                .iter()
+                .copied()
+                .find(|len| *len >= bytes)
+                .unwrap_or(64),
+        }
+    }
+    fn stuff_bits(&self, stuffed_bits: u64) -> u64 {
+        match self.stuffing {
+            BitStuffing::WorstCase => (stuffed_bits - 1) / 4,
+            BitStuffing::Ignored => 0,
+        }
+    }
+    /// (bits transmitted with the nominal bitrate, bits transmitted with the data bitrate).
+    fn phase_bits(&self, ide: bool, bytes: usize) -> (u64, u64) {
+        let data_bits = 8 * self.payload_len(bytes) as u64;
+        match self.format {
+            FrameFormat::Classic => {
+                // SOF, arbitration, control, data and CRC fields are stuffed.
+                let stuffed = if ide { 54 } else { 34 } + data_bits;
+                (stuffed + self.stuff_bits(stuffed) + TAIL_BITS, 0)
+            }
+            FrameFormat::Fd | FrameFormat::FdBrs { bitrate_ratio: _ } => {
+                // SOF, arbitration, FDF, res and BRS.
+                let arbitration = if ide { 36 } else { 17 };
+                // ESI, DLC and data.
+                let data = 5 + data_bits;
+                let stuff = self.stuff_bits(arbitration + data);
+                let arbitration_stuff = self.stuff_bits(arbitration);
+                // stuff count and CRC with a fixed stuff bit every 4 bits.
+                let crc: u64 = if data_bits <= 16 * 8 { 17 } else { 21 };
+                let fixed = 4 + crc + (4 + crc).div_ceil(4);
+                let nominal = arbitration + arbitration_stuff + TAIL_BITS;
+                let data_phase = data + (stuff - arbitration_stuff) + fixed;
+                match self.format {
+                    FrameFormat::FdBrs { bitrate_ratio: _ } => (nominal, data_phase),
+                    _ => (nominal + data_phase, 0),
+                }
+            }
+        }
+    }
+    /// number of bits of a frame with the given payload.
+    pub fn frame_bits(&self, ide: bool, bytes: usize) -> u64 {
+        let (nominal, data) = self.phase_bits(ide, bytes);
+        nominal + data
+    }
+    /// duration of a frame in nominal bit times, which differs from
+    /// frame_bits if the data phase is transmitted at a higher bitrate.
+    pub fn nominal_bit_times(&self, ide: bool, bytes: usize) -> f64 {
+        let (nominal, data) = self.phase_bits(ide, bytes);
+        match self.format {
+            FrameFormat::FdBrs { bitrate_ratio } => {
+                nominal as f64 + data as f64 / bitrate_ratio.max(1) as f64
+            }
+            _ => (nominal + data) as f64,
+        }
+    }
+    pub fn frame_time(&self, ide: bool, bytes: usize, baudrate: u32) -> Duration {
+        Duration::from_secs_f64(self.nominal_bit_times(ide, bytes) / baudrate as f64)
+    }
+    /// nominal bit times per second, that a frame sent every interval
+    /// occupies on the bus. Divided by the baudrate this is the bus load.
+    pub fn load(&self, ide: bool, bytes: usize, interval: Duration) -> f64 {
+        self.nominal_bit_times(ide, bytes) / interval.as_secs_f64()
+    }
+}
@@ -0,0 +1,122 @@
+use std::ops::Range;
+
+use crate::builder::MessagePriority;
+
+use super::{Bus, MessageRef, Network};
+
+// Headroom of the id space. The resolver splits the priority bits of the id
+// (id = priority << setcode_len | setcode) into one region per priority
+// bucket, the last region extends up to the next power of two. Every region
+// is a contiguous range of ids. Ids are unique across all buses, therefore
+// all buses share the same regions and the utilization of a bus only counts
+// the messages on the bus.
+
+/// the ids of a single priority bucket and how many of them are in use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdRegion {
+    priority: MessagePriority,
+    ids: Range<u32>,
+    used: usize,
+}
+
+impl IdRegion {
+    pub fn priority(&self) -> MessagePriority {
+        self.priority
+    }
+    /// ids that the resolver allocates for the priority bucket.
+    pub fn ids(&self) -> &Range<u32> {
+        &self.ids
+    }
+    pub fn allocatable(&self) -> usize {
+        self.ids.len()
+    }
+    /// number of messages of the bus with an id in the region,
+    /// including fixed ids.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+    /// fraction (0..1) of the allocatable ids in use.
+    pub fn utilization(&self) -> f64 {
+        if self.ids.is_empty() {
+            0.0
+        } else {
+            self.used as f64 / self.ids.len() as f64
+        }
+    }
+}
+
+pub(crate) fn id_regions(
+    setcode_len: u32,
+    bucket_sizes: &[usize],
+    bus: &Bus,
+    messages: &[MessageRef],
+) -> Vec<IdRegion> {
+    let total: usize = bucket_sizes.iter().sum();
+    let priority_end = total.next_power_of_two() as u32;
+    let mut start = 0u32;
+    bucket_sizes
+        .iter()
+        .enumerate()
+        .map(|(bucket, size)| {
+            let end = if bucket + 1 == bucket_sizes.len() {
+                priority_end
+            } else {
+                start + *size as u32
+            };
+            let ids = (start << setcode_len)..(end << setcode_len);
+            start = end;
+            let used = messages
+                .iter()
+                .filter(|message| message.bus().id() == bus.id())
+                .filter(|message| ids.contains(&message.id().as_u32()))
+                .count();
+            IdRegion {
+                priority: MessagePriority::from_u32(bucket as u32),
+                ids,
+                used,
+            }
+        })
+        .collect()
+}
+
+/// number of messages per priority bucket, in which the resolver placed them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityHistogram {
+    buckets: Vec<(MessagePriority, usize)>,
+    fixed: usize,
+}
+
+impl PriorityHistogram {
+    /// (priority, message count) of every bucket, highest priority first.
+    pub fn buckets(&self) -> &Vec<(MessagePriority, usize)> {
+        &self.buckets
+    }
+    pub fn count(&self, priority: MessagePriority) -> usize {
+        self.buckets
+            .iter()
+            .find(|(p, _)| *p == priority)
+            .map(|(_, count)| *count)
+            .unwrap_or(0)
+    }
+    /// messages with a fixed id, which aren't placed in a bucket.
+    pub fn fixed(&self) -> usize {
+        self.fixed
+    }
+}
+
+impl Network {
+    /// messages per priority bucket over all buses. A message may land in a
+    /// higher bucket than requested if its own bucket is full.
+    pub fn priority_histogram(&self) -> PriorityHistogram {
+        let mut buckets: Vec<(MessagePriority, usize)> =
+            MessagePriority::ALL.iter().map(|priority| (*priority, 0)).collect();
+        let mut fixed = 0;
+        for message in self.messages() {
+            match message.resolution_info().priority_bucket() {
+                Some(bucket) => buckets[MessagePriority::from_u32(bucket).to_u32() as usize].1 += 1,
+                None => fixed += 1,
+            }
+        }
+        PriorityHistogram { buckets, fixed }
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::{self, Write};
+
+use crate::errors::{self, Result};
+
+use super::{Message, Network, SignalType, TimeKind, TimeUnit};
+
+// Layout evolution checks. Deployed nodes keep decoding messages with the
+// layout they were built with, therefore a message may only grow by
+// appending signals (or by using bits that were reserved before). Existing
+// signals keep their offset and type. A message opts out of the rules by
+// bumping the major version of its layout (see MessageBuilder::set_version).
+// The layouts of a deployed network are stored as a lock file (see
+// Network::layout_lock), such that CI can check against it without
+// rebuilding the old network.
+
+const LOCK_HEADER: &str = "# canzero layout lock";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalLayout {
+    name: String,
+    offset: usize,
+    ty: SignalType,
+    reserved: bool,
+}
+
+impl SignalLayout {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// offset in bits.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    pub fn ty(&self) -> &SignalType {
+        &self.ty
+    }
+    pub fn reserved(&self) -> bool {
+        self.reserved
+    }
+    fn end(&self) -> usize {
+        self.offset + self.ty.size() as usize
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessageLayout {
+    name: String,
+    version: Option<String>,
+    signals: Vec<SignalLayout>,
+}
+
+impl MessageLayout {
+    pub fn of(message: &Message) -> MessageLayout {
+        MessageLayout {
+            name: message.name().to_owned(),
+            version: message.version().map(str::to_owned),
+            signals: message
+                .signals()
+                .iter()
+                .map(|signal| SignalLayout {
+                    name: signal.name().to_owned(),
+                    offset: signal.byte_offset(),
+                    ty: signal.ty().clone(),
+                    reserved: signal.is_reserved(),
+                })
+                .collect(),
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+    pub fn signals(&self) -> &Vec<SignalLayout> {
+        &self.signals
+    }
+    fn major_version(&self) -> Option<u64> {
+        self.version.as_ref()?.split('.').next()?.parse().ok()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutViolation {
+    /// a signal of the previous layout doesn't exist anymore.
+    RemovedSignal { message: String, signal: String },
+    /// the offset or the type of a signal changed.
+    ChangedSignal { message: String, signal: String },
+    /// a new signal isn't appended and doesn't replace reserved bits.
+    InsertedSignal { message: String, signal: String },
+}
+
+/// violations of the layout rules from the previous to the next layout of a
+/// message, empty if the major version of the message was bumped.
+pub fn layout_violations(previous: &MessageLayout, next: &MessageLayout) -> Vec<LayoutViolation> {
+    if let (Some(previous_major), Some(next_major)) = (previous.major_version(), next.major_version()) {
+        if next_major > previous_major {
+            return vec![];
+        }
+    }
+    let mut violations = vec![];
+    let used: Vec<&SignalLayout> = previous.signals.iter().filter(|signal| !signal.reserved).collect();
+    let end = used.iter().map(|signal| signal.end()).max().unwrap_or(0);
+    for old in &used {
+        match next.signals.iter().find(|new| new.name == old.name && !new.reserved) {
+            Some(new) if new.offset == old.offset && new.ty == old.ty => (),
+            Some(_) => violations.push(LayoutViolation::ChangedSignal {
+                message: next.name.clone(),
+                signal: old.name.clone(),
+            }),
+            None => violations.push(LayoutViolation::RemovedSignal {
+                message: next.name.clone(),
+                signal: old.name.clone(),
+            }),
+        }
+    }
+    for new in next.signals.iter().filter(|signal| !signal.reserved) {
+        if used.iter().any(|old| old.name == new.name) {
+            continue;
+        }
+        let in_reserved = previous
+            .signals
+            .iter()
+            .any(|old| old.reserved && old.offset <= new.offset && new.end() <= old.end());
+        if new.offset < end && !in_reserved {
+            violations.push(LayoutViolation::InsertedSignal {
+                message: next.name.clone(),
+                signal: new.name.clone(),
+            });
+        }
+    }
+    violations
+}
+
+fn type_token(ty: &SignalType) -> String {
+    match ty {
+        SignalType::UnsignedInt { size } => format!("u{size}"),
+        SignalType::SignedInt { size } => format!("i{size}"),
+        SignalType::Decimal { size, offset, scale } => format!("d{size}:{offset:?}:{scale:?}"),
+        SignalType::Time { size, unit, kind } => format!("{}{size}<{}>", kind.prefix(), unit.suffix()),
+    }
+}
+
+fn parse_type(token: &str) -> Option<SignalType> {
+    if let Some((ty, unit)) = token.strip_suffix('>').and_then(|token| token.split_once('<')) {
+        let (kind, size) = match ty.strip_prefix("dt") {
+            Some(size) => (TimeKind::Duration, size),
+            None => (TimeKind::Timestamp, ty.strip_prefix('t')?),
+        };
+        return Some(SignalType::Time {
+            size: size.parse().ok()?,
+            unit: TimeUnit::from_suffix(unit)?,
+            kind,
+        });
+    }
+    let (kind, rest) = token.split_at_checked(1)?;
+    match kind {
+        "u" => Some(SignalType::UnsignedInt { size: rest.parse().ok()? }),
+        "i" => Some(SignalType::SignedInt { size: rest.parse().ok()? }),
+        "d" => {
+            let mut parts = rest.split(':');
+            let ty = SignalType::Decimal {
+                size: parts.next()?.parse().ok()?,
+                offset: parts.next()?.parse().ok()?,
+                scale: parts.next()?.parse().ok()?,
+            };
+            parts.next().is_none().then_some(ty)
+        }
+        _ => None,
+    }
+}
+
+/// parses the layouts of a lock file (see Network::layout_lock).
+pub fn parse_layout_lock(lock: &str) -> Result<Vec<MessageLayout>> {
+    let mut layouts: Vec<MessageLayout> = vec![];
+    for (i, line) in lock.lines().enumerate() {
+        let invalid = || errors::ConfigError::InvalidLayoutLock(format!("line {}: {line}", i + 1));
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            [] => (),
+            [comment, ..] if comment.starts_with('#') => (),
+            ["message", name, version] => layouts.push(MessageLayout {
+                name: name.to_string(),
+                version: (*version != "-").then(|| version.to_string()),
+                signals: vec![],
+            }),
+            [kind @ ("signal" | "reserved"), name, offset, ty] => {
+                let signal = SignalLayout {
+                    name: name.to_string(),
+                    offset: offset.parse().map_err(|_| invalid())?,
+                    ty: parse_type(ty).ok_or_else(invalid)?,
+                    reserved: *kind == "reserved",
+                };
+                layouts.last_mut().ok_or_else(invalid)?.signals.push(signal);
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(layouts)
+}
+
+fn write_lock(f: &mut String, network: &Network) -> fmt::Result {
+    writeln!(f, "{LOCK_HEADER}")?;
+    for message in network.messages() {
+        let layout = MessageLayout::of(message);
+        writeln!(f, "message {} {}", layout.name, layout.version().unwrap_or("-"))?;
+        for signal in &layout.signals {
+            let kind = if signal.reserved { "reserved" } else { "signal" };
+            writeln!(f, "{kind} {} {} {}", signal.name, signal.offset, type_token(&signal.ty))?;
+        }
+    }
+    Ok(())
+}
+
+impl Network {
+    /// the layouts of all messages in a line based format, that is meant
+    /// to be committed next to the firmware of a deployed network.
+    pub fn layout_lock(&self) -> String {
+        let mut lock = String::new();
+        write_lock(&mut lock, self).expect("writing into a String can't fail");
+        lock
+    }
+    /// violations of the layout rules by the messages of self, compared to
+    /// the previous layouts. Messages that were added or removed are not
+    /// checked (see Network::diff).
+    pub fn check_layouts(&self, previous: &[MessageLayout]) -> Vec<LayoutViolation> {
+        let mut violations = vec![];
+        for message in self.messages() {
+            if let Some(old) = previous.iter().find(|old| old.name == message.name()) {
+                violations.extend(layout_violations(old, &MessageLayout::of(message)));
+            }
+        }
+        violations
+    }
+    /// see check_layouts.
+    pub fn check_layouts_against(&self, previous: &Network) -> Vec<LayoutViolation> {
+        let layouts: Vec<MessageLayout> = previous.messages().iter().map(|m| MessageLayout::of(m)).collect();
+        self.check_layouts(&layouts)
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use super::{ConfigRef, MessageRef, SignalRef};
+
+// LIN clusters (ISO 17987) are single master buses, the master sends
+// the header of every frame according to the active schedule table and
+// the publisher of the frame (master or slave) responds with the data.
+
+pub type LinBusRef = ConfigRef<LinBus>;
+pub type LinFrameRef = ConfigRef<LinFrame>;
+
+#[derive(Debug)]
+pub struct LinBus {
+    name: String,
+    baudrate: u32,
+    master: String,
+    slaves: Vec<String>,
+    frames: Vec<LinFrameRef>,
+    schedule_tables: Vec<LinScheduleTable>,
+}
+
+impl LinBus {
+    pub fn new(
+        name: String,
+        baudrate: u32,
+        master: String,
+        slaves: Vec<String>,
+        frames: Vec<LinFrameRef>,
+        schedule_tables: Vec<LinScheduleTable>,
+    ) -> Self {
+        Self {
+            name,
+            baudrate,
+            master,
+            slaves,
+            frames,
+            schedule_tables,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn baudrate(&self) -> u32 {
+        self.baudrate
+    }
+    /// name of the master node, which is also the gateway to CAN.
+    pub fn master(&self) -> &str {
+        &self.master
+    }
+    pub fn slaves(&self) -> &Vec<String> {
+        &self.slaves
+    }
+    pub fn frames(&self) -> &Vec<LinFrameRef> {
+        &self.frames
+    }
+    pub fn schedule_tables(&self) -> &Vec<LinScheduleTable> {
+        &self.schedule_tables
+    }
+}
+
+#[derive(Debug)]
+pub struct LinFrame {
+    name: String,
+    id: u8,
+    publisher: String,
+    subscribers: Vec<String>,
+    signals: Vec<SignalRef>,
+    dlc: u8,
+    gateway_message: Option<MessageRef>,
+}
+
+impl LinFrame {
+    pub fn new(
+        name: String,
+        id: u8,
+        publisher: String,
+        subscribers: Vec<String>,
+        signals: Vec<SignalRef>,
+        dlc: u8,
+        gateway_message: Option<MessageRef>,
+    ) -> Self {
+        Self {
+            name,
+            id,
+            publisher,
+            subscribers,
+            signals,
+            dlc,
+            gateway_message,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// frame identifier (0..=0x3F).
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+    /// identifier with the two parity bits, as transmitted in the header.
+    pub fn protected_id(&self) -> u8 {
+        protected_id(self.id)
+    }
+    pub fn publisher(&self) -> &str {
+        &self.publisher
+    }
+    pub fn subscribers(&self) -> &Vec<String> {
+        &self.subscribers
+    }
+    pub fn signals(&self) -> &Vec<SignalRef> {
+        &self.signals
+    }
+    /// number of data bytes (1..=8).
+    pub fn dlc(&self) -> u8 {
+        self.dlc
+    }
+    /// CAN message to which the master forwards the frame.
+    pub fn gateway_message(&self) -> Option<&MessageRef> {
+        self.gateway_message.as_ref()
+    }
+}
+
+pub fn protected_id(id: u8) -> u8 {
+    let bit = |i: u8| (id >> i) & 1;
+    let p0 = bit(0) ^ bit(1) ^ bit(2) ^ bit(4);
+    let p1 = !(bit(1) ^ bit(3) ^ bit(4) ^ bit(5)) & 1;
+    (id & 0x3F) | (p0 << 6) | (p1 << 7)
+}
+
+/// nominal time of a frame with the given number of data bytes:
+/// break, sync and protected id (34 bits) and the data and checksum bytes (10 bits each).
+/// The specification allows frames to take up to 40% longer.
+pub fn frame_time(dlc: u8, baudrate: u32) -> Duration {
+    let bits = 34 + 10 * (dlc as u64 + 1);
+    Duration::from_secs_f64(bits as f64 / baudrate as f64)
+}
+
+#[derive(Debug, Clone)]
+pub struct LinScheduleTable {
+    name: String,
+    // (frame name, delay until the next slot).
+    slots: Vec<(String, Duration)>,
+}
+
+impl LinScheduleTable {
+    pub fn new(name: String, slots: Vec<(String, Duration)>) -> Self {
+        Self { name, slots }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    pub fn slots(&self) -> &Vec<(String, Duration)> {
+        &self.slots
+    }
+    pub fn cycle_time(&self) -> Duration {
+        self.slots.iter().map(|(_, delay)| *delay).sum()
+    }
+}
@@ -1,6 +1,6 @@
 use std::{fmt::Display, hash::Hash, sync::OnceLock, time::Duration};
 
-use super::{ConfigRef, MessageEncoding, SignalRef, Visibility, bus::BusRef, stream::StreamRef, CommandRef};
+use super::{Annotations, ConfigRef, MessageEncoding, SignalRef, Visibility, bus::BusRef, can_xl::XlFrame, stream::StreamRef, CommandRef};
 
 
 #[derive(Debug)]
@@ -55,6 +55,189 @@ impl MessageId {
 
 pub type MessageRef = ConfigRef<Message>;
 
+#[derive(Debug, Clone)]
+pub struct MessageDeprecation {
+    since: String,
+    note: String,
+}
+
+impl MessageDeprecation {
+    pub fn new(since: String, note: String) -> Self {
+        Self { since, note }
+    }
+    pub fn since(&self) -> &str {
+        &self.since
+    }
+    pub fn note(&self) -> &str {
+        &self.note
+    }
+}
+
+/// the frame carries a truncated AES-CMAC (signal) after the payload.
+#[derive(Debug, Clone)]
+pub struct MessageAuthentication {
+    key_id: u32,
+    mac_len: u8,
+    signal: SignalRef,
+}
+
+impl MessageAuthentication {
+    pub fn new(key_id: u32, mac_len: u8, signal: SignalRef) -> Self {
+        Self {
+            key_id,
+            mac_len,
+            signal,
+        }
+    }
+    /// key of the security modules of the transmitter and the receivers.
+    pub fn key_id(&self) -> u32 {
+        self.key_id
+    }
+    /// length of the mac in bytes.
+    pub fn mac_len(&self) -> u8 {
+        self.mac_len
+    }
+    /// signal of the mac, which is also part of Message::signals.
+    pub fn signal(&self) -> &SignalRef {
+        &self.signal
+    }
+}
+
+/// the transmitter answers remote frames (RTR) with the id of the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRequest {
+    requesters: Vec<String>,
+    response_latency: Option<Duration>,
+}
+
+impl RemoteRequest {
+    pub fn new(requesters: Vec<String>, response_latency: Option<Duration>) -> Self {
+        Self {
+            requesters,
+            response_latency,
+        }
+    }
+    /// names of the nodes that may send the remote frame.
+    pub fn requesters(&self) -> &Vec<String> {
+        &self.requesters
+    }
+    /// expected time between the remote frame and the answer.
+    pub fn response_latency(&self) -> Option<&Duration> {
+        self.response_latency.as_ref()
+    }
+}
+
+/// up to max_frames frames of an event driven message within the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MessageBurst {
+    max_frames: u32,
+    window: Duration,
+}
+
+impl MessageBurst {
+    pub fn new(max_frames: u32, window: Duration) -> Self {
+        Self { max_frames, window }
+    }
+    pub fn max_frames(&self) -> u32 {
+        self.max_frames
+    }
+    pub fn window(&self) -> &Duration {
+        &self.window
+    }
+}
+
+/// traceability of a message (see MessageBuilder::set_author,
+/// set_requirement and set_revision_note).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageDocumentation {
+    author: Option<String>,
+    requirement: Option<String>,
+    revision_note: Option<String>,
+}
+
+impl MessageDocumentation {
+    pub fn new(
+        author: Option<String>,
+        requirement: Option<String>,
+        revision_note: Option<String>,
+    ) -> Self {
+        Self {
+            author,
+            requirement,
+            revision_note,
+        }
+    }
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+    /// ticket or requirement (id or link) the message implements.
+    pub fn requirement(&self) -> Option<&str> {
+        self.requirement.as_deref()
+    }
+    pub fn revision_note(&self) -> Option<&str> {
+        self.revision_note.as_deref()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.author.is_none() && self.requirement.is_none() && self.revision_note.is_none()
+    }
+}
+
+/// Describes where the id resolver placed a message.
+#[derive(Debug, Clone)]
+pub struct MessageResolutionInfo {
+    bus: BusRef,
+    setcode: Option<u32>,
+    setcode_len: u32,
+    requested_priority: Option<u32>,
+    priority_bucket: Option<u32>,
+    position: Option<u32>,
+}
+
+impl MessageResolutionInfo {
+    pub fn new(
+        bus: BusRef,
+        setcode: Option<u32>,
+        setcode_len: u32,
+        requested_priority: Option<u32>,
+        priority_bucket: Option<u32>,
+        position: Option<u32>,
+    ) -> Self {
+        Self {
+            bus,
+            setcode,
+            setcode_len,
+            requested_priority,
+            priority_bucket,
+            position,
+        }
+    }
+    pub fn bus(&self) -> &BusRef {
+        &self.bus
+    }
+    /// None if the message was not part of any receive set
+    /// (e.g. a fixed id without receivers).
+    pub fn setcode(&self) -> Option<u32> {
+        self.setcode
+    }
+    pub fn setcode_len(&self) -> u32 {
+        self.setcode_len
+    }
+    /// bucket of the priority requested by the message (see
+    /// MessagePriority::to_u32). None for fixed ids.
+    pub fn requested_priority(&self) -> Option<u32> {
+        self.requested_priority
+    }
+    /// bucket the message actually landed in, which can be a higher
+    /// priority bucket than requested. None for fixed ids.
+    pub fn priority_bucket(&self) -> Option<u32> {
+        self.priority_bucket
+    }
+    /// position within the priority bucket. None for fixed ids.
+    pub fn position(&self) -> Option<u32> {
+        self.position
+    }
+}
+
 #[derive(Debug)]
 pub struct Message {
     name: String,
@@ -65,6 +248,18 @@ pub struct Message {
     visibility: Visibility,
     dlc : u8,
     bus : BusRef,
+    fallback_bus : Option<BusRef>,
+    resolution_info : MessageResolutionInfo,
+    version : Option<String>,
+    deprecation : Option<MessageDeprecation>,
+    processing_deadline : Option<Duration>,
+    deadline : Option<Duration>,
+    authentication : Option<MessageAuthentication>,
+    burst : Option<MessageBurst>,
+    documentation : MessageDocumentation,
+    annotations : Annotations,
+    xl_frame : Option<XlFrame>,
+    remote_request : Option<RemoteRequest>,
     usage : OnceLock<MessageUsage>,
 }
 
@@ -89,6 +284,22 @@ impl Hash for Message {
         self.visibility.hash(state);
         state.write_u8(self.dlc);
         state.write_u32(self.bus.id());
+        if let Some(fallback_bus) = &self.fallback_bus {
+            state.write_u32(fallback_bus.id());
+        }
+        if let Some(authentication) = &self.authentication {
+            state.write_u32(authentication.key_id);
+            state.write_u8(authentication.mac_len);
+        }
+        if let Some(xl_frame) = &self.xl_frame {
+            xl_frame.hash(state);
+        }
+        if let Some(remote_request) = &self.remote_request {
+            for requester in &remote_request.requesters {
+                state.write(requester.as_bytes());
+            }
+            remote_request.response_latency.hash(state);
+        }
     }
 }
 
@@ -100,7 +311,19 @@ impl Message {
                encoding : Option<MessageEncoding>,
                signals : Vec<SignalRef>,
                visibility : Visibility, dlc : u8,
-               bus : BusRef) -> Self {
+               bus : BusRef,
+               fallback_bus : Option<BusRef>,
+               resolution_info : MessageResolutionInfo,
+               version : Option<String>,
+               deprecation : Option<MessageDeprecation>,
+               processing_deadline : Option<Duration>,
+               deadline : Option<Duration>,
+               authentication : Option<MessageAuthentication>,
+               burst : Option<MessageBurst>,
+               documentation : MessageDocumentation,
+               annotations : Annotations,
+               xl_frame : Option<XlFrame>,
+               remote_request : Option<RemoteRequest>) -> Self {
         Self {
             name,
             description,
@@ -110,6 +333,18 @@ impl Message {
             visibility,
             dlc,
             bus,
+            fallback_bus,
+            resolution_info,
+            version,
+            deprecation,
+            processing_deadline,
+            deadline,
+            authentication,
+            burst,
+            documentation,
+            annotations,
+            xl_frame,
+            remote_request,
             usage : OnceLock::new(),
         }
     }
@@ -141,15 +376,71 @@ impl Message {
     pub fn signals(&self) -> &Vec<SignalRef> {
         &self.signals
     }
+    /// payload bytes of classic and FD frames, saturates for CAN XL
+    /// frames with more than 255 bytes (see payload_len).
     pub fn dlc(&self) -> u8 { 
         self.dlc
     }
+    /// payload bytes of the frame.
+    pub fn payload_len(&self) -> usize {
+        match &self.xl_frame {
+            Some(xl_frame) => xl_frame.payload_len(),
+            None => self.dlc as usize,
+        }
+    }
+    /// Some for messages on buses in XL mode.
+    pub fn xl_frame(&self) -> Option<&XlFrame> {
+        self.xl_frame.as_ref()
+    }
+    /// Some if the message can be polled with remote frames (RTR).
+    pub fn remote_request(&self) -> Option<&RemoteRequest> {
+        self.remote_request.as_ref()
+    }
     pub fn visibility(&self) -> &Visibility {
         &self.visibility
     }
     pub fn bus(&self) -> &BusRef {
         &self.bus
     }
+    /// bus the message is transmitted on if the primary bus fails.
+    /// The message keeps its id on the fallback bus.
+    pub fn fallback_bus(&self) -> Option<&BusRef> {
+        self.fallback_bus.as_ref()
+    }
+    pub fn resolution_info(&self) -> &MessageResolutionInfo {
+        &self.resolution_info
+    }
+    /// semantic version (major.minor.patch) of the message layout.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+    pub fn deprecation(&self) -> Option<&MessageDeprecation> {
+        self.deprecation.as_ref()
+    }
+    /// max time between the reception of the message and its processing,
+    /// short deadlines have to be handled in the isr instead of the main loop.
+    pub fn processing_deadline(&self) -> Option<&Duration> {
+        self.processing_deadline.as_ref()
+    }
+    /// max time between the release of a frame and the end of its
+    /// transmission (see MessageBuilder::set_deadline).
+    pub fn deadline(&self) -> Option<&Duration> {
+        self.deadline.as_ref()
+    }
+    /// see MessageBuilder::enable_cmac.
+    pub fn authentication(&self) -> Option<&MessageAuthentication> {
+        self.authentication.as_ref()
+    }
+    /// see MessageBuilder::set_burst.
+    pub fn burst(&self) -> Option<&MessageBurst> {
+        self.burst.as_ref()
+    }
+    pub fn documentation(&self) -> &MessageDocumentation {
+        &self.documentation
+    }
+    pub fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
 }
 
 
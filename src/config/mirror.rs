@@ -0,0 +1,32 @@
+use super::stream::StreamRef;
+
+/// streams that the master mirrors onto UDP as protobuf messages
+/// (see Network::generate_proto and Network::mirror_mapping_csv).
+#[derive(Debug)]
+pub struct MirrorTarget {
+    master: String,
+    port: u16,
+    // (tx node, stream)
+    streams: Vec<(String, StreamRef)>,
+}
+
+impl MirrorTarget {
+    pub fn new(master: String, port: u16, streams: Vec<(String, StreamRef)>) -> Self {
+        Self {
+            master,
+            port,
+            streams,
+        }
+    }
+    /// node that receives the streams on CAN and sends them over UDP.
+    pub fn master(&self) -> &str {
+        &self.master
+    }
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+    /// mirrored streams with the name of their transmitting node.
+    pub fn streams(&self) -> &Vec<(String, StreamRef)> {
+        &self.streams
+    }
+}
@@ -0,0 +1,229 @@
+use std::{f64::consts::PI, time::Duration};
+
+use super::{Network, ObjectEntry, ObjectEntryAccess, SignalType, Type};
+
+// Generates synthetic time series for the object entries of the network,
+// such that HIL setups and UIs can run without the hardware. The ranges come
+// from the types and bounds of the object entries and the rates from the
+// streams that transmit them, therefore mocked values always fit the frames
+// of the config. The series are deterministic functions of the time.
+
+// periods of the waveforms in multiples of the stream interval.
+const SINE_PERIOD_INTERVALS: u32 = 200;
+const STEP_DWELL_INTERVALS: u32 = 100;
+// object entries without a stream are only read on request.
+const UNSTREAMED_PERIOD: Duration = Duration::from_secs(10);
+
+/// how a mock value evolves over time.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockWaveform {
+    /// sine between min and max, phase is a fraction of the period.
+    Sine { period: Duration, phase: f64 },
+    /// steps through the values (e.g. the entries of an enum),
+    /// each value is held for the dwell time.
+    Steps { values: Vec<f64>, dwell: Duration },
+    Constant(f64),
+}
+
+/// generator of a primitive value of an object entry.
+#[derive(Debug, Clone)]
+pub struct MockValue {
+    node: String,
+    object_entry: String,
+    path: String,
+    min: f64,
+    max: f64,
+    integer: bool,
+    interval: Option<Duration>,
+    waveform: MockWaveform,
+}
+
+impl MockValue {
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+    pub fn object_entry(&self) -> &str {
+        &self.object_entry
+    }
+    /// path of the value within the object entry, attributes of structs are
+    /// separated by '.' and elements of arrays are indexed with [i]
+    /// (e.g. "state.cells[3]"). Equals the name for primitive object entries.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+    /// integer and enum values are rounded.
+    pub fn is_integer(&self) -> bool {
+        self.integer
+    }
+    /// shortest interval of the streams that transmit the object entry,
+    /// None if it's not streamed.
+    pub fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+    pub fn waveform(&self) -> &MockWaveform {
+        &self.waveform
+    }
+    /// value at the time since the start of the simulation, within min..=max.
+    pub fn sample(&self, t: Duration) -> f64 {
+        let value = match &self.waveform {
+            MockWaveform::Sine { period, phase } => {
+                let x = t.as_secs_f64() / period.as_secs_f64() + phase;
+                self.min + (self.max - self.min) * (0.5 + 0.5 * (2.0 * PI * x).sin())
+            }
+            MockWaveform::Steps { values, dwell } => {
+                let step = (t.as_nanos() / dwell.as_nanos().max(1)) as usize;
+                values[step % values.len()]
+            }
+            MockWaveform::Constant(value) => *value,
+        };
+        let value = if self.integer { value.round() } else { value };
+        value.clamp(self.min, self.max)
+    }
+    /// samples at the interval (or every 100ms if not streamed) for the duration.
+    pub fn series(&self, duration: Duration) -> Vec<(Duration, f64)> {
+        let step = self.interval.unwrap_or(Duration::from_millis(100));
+        let mut series = vec![];
+        let mut t = Duration::ZERO;
+        while t < duration {
+            series.push((t, self.sample(t)));
+            t += step;
+        }
+        series
+    }
+}
+
+// range of the decoded values of a primitive type.
+fn signal_range(ty: &SignalType) -> (f64, f64, bool) {
+    let size = ty.size() as i32;
+    match ty {
+        SignalType::UnsignedInt { size: _ } => (0.0, 2f64.powi(size) - 1.0, true),
+        SignalType::SignedInt { size: _ } => (-(2f64.powi(size - 1)), 2f64.powi(size - 1) - 1.0, true),
+        SignalType::Decimal { size: _, offset, scale } => {
+            (*offset, offset + scale * (2f64.powi(size) - 1.0), false)
+        }
+        SignalType::Time { size: _, unit, kind: _ } => (0.0, unit.secs() * (2f64.powi(size) - 1.0), false),
+    }
+}
+
+// stable phase in 0..1, such that values don't move in lockstep.
+fn phase(node: &str, path: &str) -> f64 {
+    let hash = seahash::hash(format!("{node}::{path}").as_bytes());
+    (hash % 1000) as f64 / 1000.0
+}
+
+struct MockContext<'a> {
+    node: &'a str,
+    object_entry: &'a ObjectEntry,
+    interval: Option<Duration>,
+}
+
+impl MockContext<'_> {
+    fn value(&self, path: String, min: f64, max: f64, integer: bool, waveform: MockWaveform) -> MockValue {
+        MockValue {
+            node: self.node.to_owned(),
+            object_entry: self.object_entry.name().to_owned(),
+            path,
+            min,
+            max,
+            integer,
+            interval: self.interval,
+            waveform,
+        }
+    }
+
+    fn period(&self, intervals: u32) -> Duration {
+        self.interval.map(|interval| interval * intervals).unwrap_or(UNSTREAMED_PERIOD)
+    }
+
+    fn collect(&self, ty: &Type, path: String, values: &mut Vec<MockValue>) {
+        let constant = matches!(self.object_entry.access(), ObjectEntryAccess::Const);
+        match ty {
+            Type::Primitive(signal_type) => {
+                let (mut min, mut max, integer) = signal_range(signal_type);
+                // bounds only apply to primitive object entries.
+                if path == self.object_entry.name() {
+                    if let Some(bounds) = self.object_entry.bounds() {
+                        min = bounds.min().value().clamp(min, max);
+                        max = bounds.max().value().clamp(min, max);
+                    }
+                }
+                let waveform = if constant {
+                    let mid = (min + max) / 2.0;
+                    MockWaveform::Constant(if integer { mid.floor() } else { mid })
+                } else {
+                    MockWaveform::Sine {
+                        period: self.period(SINE_PERIOD_INTERVALS),
+                        phase: phase(self.node, &path),
+                    }
+                };
+                values.push(self.value(path, min, max, integer, waveform));
+            }
+            Type::Enum { entries, .. } => {
+                let mut entries: Vec<f64> = entries.iter().map(|(_, value)| *value as f64).collect();
+                if entries.is_empty() {
+                    entries.push(0.0);
+                }
+                let min = entries.iter().cloned().fold(f64::INFINITY, f64::min);
+                let max = entries.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let waveform = if constant {
+                    MockWaveform::Constant(entries[0])
+                } else {
+                    MockWaveform::Steps {
+                        values: entries,
+                        dwell: self.period(STEP_DWELL_INTERVALS),
+                    }
+                };
+                values.push(self.value(path, min, max, true, waveform));
+            }
+            Type::Struct { attribs, .. } => {
+                for (name, ty) in attribs {
+                    self.collect(ty, format!("{path}.{name}"), values);
+                }
+            }
+            Type::Array { len, ty } => {
+                for i in 0..*len {
+                    self.collect(ty, format!("{path}[{i}]"), values);
+                }
+            }
+        }
+    }
+}
+
+impl Network {
+    /// generators of synthetic values for every primitive value of every
+    /// object entry, in the order of the nodes and their object entries.
+    /// Const object entries keep a constant value, enums step through
+    /// their entries and all other values follow a sine within their range.
+    pub fn mock_values(&self) -> Vec<MockValue> {
+        let mut values = vec![];
+        for node in self.nodes() {
+            for object_entry in node.object_entries() {
+                let interval = node
+                    .tx_streams()
+                    .iter()
+                    .filter(|stream| {
+                        stream
+                            .mapping()
+                            .iter()
+                            .flatten()
+                            .any(|oe| oe.name() == object_entry.name())
+                    })
+                    .map(|stream| *stream.min_interval())
+                    .min();
+                let context = MockContext {
+                    node: node.name(),
+                    object_entry,
+                    interval,
+                };
+                context.collect(object_entry.ty(), object_entry.name().to_owned(), &mut values);
+            }
+        }
+        values
+    }
+}
@@ -1,42 +1,163 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
+// The public model of a built network. Every public type of the submodules
+// is re-exported here, such that downstream generators can import all types
+// from config:: and don't depend on the module layout.
+
+pub use self::assignment::Assignment;
+pub use self::assignment::AssignmentViolation;
+pub use self::assignment::AssignedMessage;
 pub use self::command::Command;
 pub use self::command::CommandRef;
+pub use self::bus::Bus;
+pub use self::bus::BusRef;
+pub use self::bus::BusMode;
+pub use self::bus::BusTopology;
+pub use self::bus::CableSegment;
+pub use self::bus::FramePadding;
+pub use self::can_xl::SduType;
+pub use self::can_xl::XlFrame;
+pub use self::conformance::ConformanceFrame;
+pub use self::conformance::ConformanceKind;
+pub use self::conformance::ConformanceScenario;
+pub use self::conformance::ConformanceStep;
+pub use self::conformance::ConformanceValue;
+pub use self::constant::Constant;
+pub use self::constant::ConstantRef;
 pub use self::encoding::MessageEncoding;
 pub use self::encoding::TypeSignalEncoding;
+pub use self::encoding::CompositeSignalEncoding;
+pub use self::encoding::PrimitiveSignalEncoding;
+pub use self::expect::MessageExpectation;
+pub use self::expect::NodeExpectation;
+pub use self::explanation::AssignmentExplanation;
 pub use self::message::MessageId;
 pub use self::message::Message;
 pub use self::message::MessageRef;
+pub use self::message::MessageDeprecation;
+pub use self::message::MessageBurst;
+pub use self::message::MessageDocumentation;
+pub use self::message::MessageResolutionInfo;
+pub use self::message::MessageUsage;
+pub use self::message::MessageAuthentication;
+pub use self::message::RemoteRequest;
 pub use self::network::Network;
 pub use self::network::NetworkRef;
+pub use self::diff::NetworkDiff;
+pub use self::diff::MessageChange;
+pub use self::failure_impact::FailureImpact;
+pub use self::failure_impact::ImpactedItem;
+pub use self::fault::Fault;
+pub use self::fault::FaultRef;
+pub use self::fault::FaultSeverity;
+pub use self::filter::Filter;
+pub use self::filter::NodeFilters;
+pub use self::filter_export::FilterRegisters;
+pub use self::filter_export::HardwareTarget;
+pub use self::filter_export::BxCanFilterBank;
+pub use self::filter_export::BxCanFilterMode;
+pub use self::filter_export::FdcanFilterElements;
+pub use self::filter_export::Mcp2515Registers;
+pub use self::frame_time::BitStuffing;
+pub use self::frame_time::FrameFormat;
+pub use self::frame_time::FrameTimeModel;
+pub use self::footprint::CostModel;
+pub use self::footprint::Footprint;
+pub use self::id_utilization::IdRegion;
+pub use self::id_utilization::PriorityHistogram;
+pub use self::layout::LayoutViolation;
+pub use self::layout::MessageLayout;
+pub use self::layout::SignalLayout;
+pub use self::lin_bus::LinBus;
+pub use self::lin_bus::LinBusRef;
+pub use self::lin_bus::LinFrame;
+pub use self::lin_bus::LinFrameRef;
+pub use self::lin_bus::LinScheduleTable;
+pub use self::mirror::MirrorTarget;
+pub use self::mock::MockValue;
+pub use self::mock::MockWaveform;
 pub use self::node::Node;
 pub use self::node::NodeRef;
+pub use self::node::Supervision;
 pub use self::object_entry::ObjectEntryAccess;
+pub use self::optimization::OptimizationHint;
+pub use self::object_entry::ObjectEntryBound;
+pub use self::object_entry::ObjectEntryBounds;
+pub use self::object_entry::ObjectEntryDisplay;
 pub use self::object_entry::ObjectEntry;
 pub use self::object_entry::ObjectEntryRef;
+pub use self::object_entry::AggregationPolicy;
+pub use self::object_entry::AggregationSource;
+pub use self::object_entry::StreamAggregation;
+pub use self::object_entry::ChunkedRead;
+pub use self::provenance::InputFile;
+pub use self::provenance::Provenance;
 pub use self::signal::SignalSign;
 pub use self::signal::SignalType;
+pub use self::signal::TimeEpoch;
+pub use self::signal::TimeKind;
+pub use self::signal::TimeUnit;
 pub use self::signal::SignalRef;
 pub use self::signal::ValueTable;
 pub use self::signal::ValueTableRef;
+pub use self::signal::Signal;
+pub use self::snapshot_stream::SnapshotStream;
+pub use self::snapshot_stream::SnapshotStreamRef;
+pub use self::stream::Stream;
+pub use self::stream::StreamRef;
+pub use self::stream::DeltaEncoding;
+pub use self::stream::DeltaRollover;
+pub use self::time_triggered::TimeTriggeredSchedule;
+pub use self::time_triggered::TimeWindow;
 pub use self::types::Type;
 pub use self::types::TypeRef;
+pub use self::type_usage::TypeUsage;
 pub use self::visibility::Visibility;
+pub use self::workspace::Workspace;
+pub use self::workspace::WorkspaceRef;
 
+pub mod assignment;
 pub mod command;
+pub mod constant;
 pub mod encoding;
 pub mod message;
 pub mod network;
 pub mod node;
 pub mod object_entry;
 pub mod signal;
+pub mod snapshot_stream;
 pub mod stream;
 pub mod types;
+pub mod type_usage;
 pub mod visibility;
 pub mod bus;
+pub mod can_xl;
+pub mod diff;
+pub mod expect;
+pub mod explanation;
+pub mod failure_impact;
+pub mod fault;
+pub mod filter;
+pub mod filter_export;
+pub mod footprint;
+pub mod id_utilization;
+pub mod frame_time;
+pub mod conformance;
+pub mod layout;
+pub mod lin_bus;
+pub mod mirror;
+pub mod mock;
+pub mod optimization;
+pub mod provenance;
+pub mod time_triggered;
+pub mod workspace;
 
 pub type ConfigRef<T> = Arc<T>;
 
+/// free form key value metadata of downstream tools, which is exported
+/// but not part of the network hash.
+pub type Annotations = BTreeMap<String, String>;
+
 pub fn make_config_ref<T>(value: T) -> ConfigRef<T> {
     Arc::new(value)
 }
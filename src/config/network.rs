@@ -1,6 +1,8 @@
-use std::{fmt::Display, hash::{self, Hash, Hasher}};
+use std::{fmt::Display, hash::{self, Hash, Hasher}, sync::OnceLock};
 
-use super::{ConfigRef, NodeRef, MessageRef, TypeRef, Type, SignalType, bus::BusRef};
+use crate::diagnostics::Diagnostic;
+
+use super::{ConfigRef, NodeRef, MessageRef, TypeRef, Type, SignalType, TimeEpoch, bus::BusRef, ConstantRef, FrameTimeModel, lin_bus::LinBusRef, mirror::MirrorTarget, provenance::Provenance, time_triggered::TimeTriggeredSchedule};
 
 
 pub type NetworkRef = ConfigRef<Network>;
@@ -17,6 +19,15 @@ pub struct Network {
     set_req_message : MessageRef,
     heartbeat_message : MessageRef,
     buses : Vec<BusRef>,
+    constants : Vec<ConstantRef>,
+    frame_time_model : FrameTimeModel,
+    diagnostics : OnceLock<Vec<Diagnostic>>,
+    lin_buses : OnceLock<Vec<LinBusRef>>,
+    mirror : OnceLock<MirrorTarget>,
+    time_triggered_schedules : OnceLock<Vec<TimeTriggeredSchedule>>,
+    time_epoch : OnceLock<TimeEpoch>,
+    masters : OnceLock<Vec<NodeRef>>,
+    provenance : OnceLock<Provenance>,
 }
 
 impl hash::Hash for Network {
@@ -39,6 +50,9 @@ impl Network {
         for b in &self.buses {
             b.hash(&mut hasher);
         }
+        for c in &self.constants {
+            c.hash(&mut hasher);
+        }
         hasher.finish()
     }
 }
@@ -55,6 +69,8 @@ impl Network {
         set_resp_message : MessageRef,
         heartbeat_message : MessageRef,
         buses : Vec<BusRef>,
+        constants : Vec<ConstantRef>,
+        frame_time_model : FrameTimeModel,
     ) -> Network {
         Network {
             types,
@@ -66,21 +82,106 @@ impl Network {
             set_req_message,
             set_resp_message,
             heartbeat_message,
-            buses
+            buses,
+            constants,
+            frame_time_model,
+            diagnostics : OnceLock::new(),
+            lin_buses : OnceLock::new(),
+            mirror : OnceLock::new(),
+            time_triggered_schedules : OnceLock::new(),
+            time_epoch : OnceLock::new(),
+            masters : OnceLock::new(),
+            provenance : OnceLock::new(),
+        }
+    }
+    /// warnings emitted while building the network.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self.diagnostics.get() {
+            Some(diagnostics) => diagnostics,
+            None => &[],
+        }
+    }
+    pub fn __set_diagnostics(&self, diagnostics : Vec<Diagnostic>) {
+        self.diagnostics.set(diagnostics).expect("__set_diagnostics can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// LIN clusters, whose masters are nodes of the network.
+    pub fn lin_buses(&self) -> &[LinBusRef] {
+        match self.lin_buses.get() {
+            Some(lin_buses) => lin_buses,
+            None => &[],
+        }
+    }
+    pub fn __set_lin_buses(&self, lin_buses : Vec<LinBusRef>) {
+        self.lin_buses.set(lin_buses).expect("__set_lin_buses can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// streams mirrored onto UDP, if the network has a mirror target.
+    pub fn mirror(&self) -> Option<&MirrorTarget> {
+        self.mirror.get()
+    }
+    pub fn __set_mirror(&self, mirror : MirrorTarget) {
+        self.mirror.set(mirror).expect("__set_mirror can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// time-triggered schedules of the buses that have one.
+    pub fn time_triggered_schedules(&self) -> &[TimeTriggeredSchedule] {
+        match self.time_triggered_schedules.get() {
+            Some(schedules) => schedules,
+            None => &[],
+        }
+    }
+    pub fn __set_time_triggered_schedules(&self, schedules : Vec<TimeTriggeredSchedule>) {
+        self.time_triggered_schedules.set(schedules).expect("__set_time_triggered_schedules can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// reference point of all timestamp signals (t32<ms>).
+    pub fn time_epoch(&self) -> TimeEpoch {
+        self.time_epoch.get().copied().unwrap_or_default()
+    }
+    pub fn __set_time_epoch(&self, time_epoch : TimeEpoch) {
+        self.time_epoch.set(time_epoch).expect("__set_time_epoch can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// control stations (NetworkBuilder::set_master), which receive all
+    /// streams and can call every command.
+    pub fn masters(&self) -> &[NodeRef] {
+        match self.masters.get() {
+            Some(masters) => masters,
+            None => &[],
         }
     }
+    pub fn __set_masters(&self, masters : Vec<NodeRef>) {
+        self.masters.set(masters).expect("__set_masters can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// generator, sources and resolver the network was built with.
+    pub fn provenance(&self) -> &Provenance {
+        self.provenance.get().expect("the provenance is set when building the network")
+    }
+    pub fn __set_provenance(&self, provenance : Provenance) {
+        self.provenance.set(provenance).expect("__set_provenance can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    /// in the order of definition.
     pub fn buses(&self) -> &Vec<BusRef> {
         &self.buses
     }
+    /// frame layout and stuffing used to estimate bus loads.
+    pub fn frame_time_model(&self) -> &FrameTimeModel {
+        &self.frame_time_model
+    }
+    /// in the order of definition.
+    pub fn constants(&self) -> &Vec<ConstantRef> {
+        &self.constants
+    }
+    /// in the order of definition, which is the order of the node ids.
     pub fn nodes(&self) -> &Vec<NodeRef> {
         &self.nodes
     }
+    /// sorted by name, independent of the order in which messages are
+    /// defined or generated (for streams and commands) by build.
     pub fn messages(&self) -> &Vec<MessageRef> {
         &self.messages
     }
     pub fn build_time(&self) -> &chrono::DateTime<chrono::Local> {
         &self.build_time
     }
+    /// in the order of definition, but every type is listed after
+    /// the types of its attributes.
     pub fn types(&self) -> &Vec<TypeRef> {
         &self.types
     }
@@ -115,11 +216,20 @@ impl Display for Network {
         let s5 = format!("{s4}{s1}");
         writeln!(f, "Network:")?;
         writeln!(f, "{s1}build_time : {}", self.build_time)?;
+        if let Some(provenance) = self.provenance.get() {
+            writeln!(f, "{s1}provenance : {provenance}")?;
+        }
         writeln!(f, "{s1}busses:")?;
         for bus in &self.buses {
             writeln!(f, "{s2}{}", bus.id())?;
             writeln!(f, "{s3}baudrate : {}", bus.baudrate())?;
         }
+        if !self.constants.is_empty() {
+            writeln!(f, "{s1}constants:")?;
+            for constant in &self.constants {
+                writeln!(f, "{s2}{} = {} {}", constant.name(), constant.value(), constant.unit())?;
+            }
+        }
         writeln!(f, "{s1}types:")?;
         for ty in &self.types {
             let vis = match ty as &Type {
@@ -129,6 +239,7 @@ impl Display for Network {
                     description: _,
                     attribs: _,
                     visibility,
+                    annotations: _,
                 } => format!("{visibility:?}"),
                 Type::Enum {
                     name: _,
@@ -136,6 +247,7 @@ impl Display for Network {
                     size: _,
                     entries: _,
                     visibility,
+                    annotations: _,
                 } => format!("{visibility:?}"),
                 Type::Array { len: _, ty: _ } => "Static".to_owned(),
             };
@@ -149,6 +261,7 @@ impl Display for Network {
                     description: _,
                     attribs,
                     visibility: _,
+                    annotations: _,
                 } => {
                     writeln!(f, ": (struct)")?;
                     for (attrib_name, attrib_type) in attribs {
@@ -161,6 +274,7 @@ impl Display for Network {
                     size: _,
                     entries,
                     visibility: _,
+                    annotations: _,
                 } => {
                     writeln!(f, ": (enum)")?;
                     for (entry_name, entry_value) in entries {
@@ -179,6 +293,18 @@ impl Display for Network {
                 writeln!(f, "{s3}description = {}", message.description().unwrap())?;
             }
             writeln!(f, "{s3}id = {}", message.id())?;
+            if let Some(fallback_bus) = message.fallback_bus() {
+                writeln!(f, "{s3}fallback_bus = {}", fallback_bus.name())?;
+            }
+            if let Some(version) = message.version() {
+                writeln!(f, "{s3}version = {version}")?;
+            }
+            if let Some(deprecation) = message.deprecation() {
+                writeln!(f, "{s3}deprecated since {} : {}", deprecation.since(), deprecation.note())?;
+            }
+            if let Some(deadline) = message.processing_deadline() {
+                writeln!(f, "{s3}processing_deadline = {deadline:?}")?;
+            }
             if message.encoding().is_some() {
                 let encodings = message.encoding().unwrap();
                 writeln!(f, "{s3}map_to_types:")?;
@@ -197,12 +323,16 @@ impl Display for Network {
                             } => {
                                 write!(f, "d{size}<offset={offset}, scale={scale}>")?;
                             }
+                            SignalType::Time { size, unit, kind } => {
+                                write!(f, "{}{size}<{}>", kind.prefix(), unit.suffix())?;
+                            }
                         },
                         Type::Struct {
                             name,
                             description: _,
                             attribs: _,
                             visibility: _,
+                            annotations: _,
                         } => {
                             write!(f, "{name} (struct)")?;
                         }
@@ -212,6 +342,7 @@ impl Display for Network {
                             size: _,
                             entries: _,
                             visibility: _,
+                            annotations: _,
                         } => {
                             write!(f, "{name} (enum)")?;
                         }
@@ -1,10 +1,30 @@
-use std::hash::Hash;
+use std::{hash::Hash, sync::OnceLock, time::Duration};
 
-use super::{ConfigRef, TypeRef, CommandRef, stream::StreamRef, MessageRef, ObjectEntryRef, bus::BusRef};
+use super::{Annotations, ConfigRef, TypeRef, CommandRef, snapshot_stream::SnapshotStreamRef, stream::StreamRef, MessageRef, ObjectEntryRef, bus::BusRef, FaultRef};
 
 
 pub type NodeRef = ConfigRef<Node>;
 
+/// the heartbeat of the node has to be received within the timeout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Supervision {
+    node: String,
+    timeout: Duration,
+}
+
+impl Supervision {
+    pub fn new(node: String, timeout: Duration) -> Self {
+        Self { node, timeout }
+    }
+    /// name of the supervised node.
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+    pub fn timeout(&self) -> &Duration {
+        &self.timeout
+    }
+}
+
 
 #[derive(Debug)]
 pub struct Node {
@@ -25,6 +45,15 @@ pub struct Node {
 
     object_entries: Vec<ObjectEntryRef>,
     buses : Vec<BusRef>,
+    fallback_bus : Option<BusRef>,
+    faults : Vec<FaultRef>,
+    software_filters : OnceLock<Vec<MessageRef>>,
+    supervisions : OnceLock<Vec<Supervision>>,
+    snapshot_stream : OnceLock<Option<SnapshotStreamRef>>,
+    listen_only : OnceLock<Vec<BusRef>>,
+    init_sequence : OnceLock<Vec<CommandRef>>,
+    receive_all : bool,
+    annotations : Annotations,
 }
 
 impl Hash for Node {
@@ -51,6 +80,12 @@ impl Hash for Node {
         for oe in &self.object_entries {
             oe.hash(state);
         }
+        for fault in &self.faults {
+            fault.hash(state);
+        }
+        if self.receive_all {
+            state.write_u8(1);
+        }
     }
 }
 
@@ -64,7 +99,11 @@ impl Node {
                rx_messages : Vec<MessageRef>,
                tx_messages : Vec<MessageRef>,
                object_entries : Vec<ObjectEntryRef>,
-               buses : Vec<BusRef>)-> Self{
+               buses : Vec<BusRef>,
+               fallback_bus : Option<BusRef>,
+               faults : Vec<FaultRef>,
+               receive_all : bool,
+               annotations : Annotations)-> Self{
         Self {
             name,
             description,
@@ -78,6 +117,15 @@ impl Node {
             tx_messages,
             object_entries,
             buses,
+            fallback_bus,
+            faults,
+            software_filters : OnceLock::new(),
+            supervisions : OnceLock::new(),
+            snapshot_stream : OnceLock::new(),
+            listen_only : OnceLock::new(),
+            init_sequence : OnceLock::new(),
+            receive_all,
+            annotations,
         }
     }
 
@@ -85,33 +133,45 @@ impl Node {
     pub fn name(&self) -> &str {
         &self.name
     }
+    pub fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
     pub fn types(&self) -> &Vec<TypeRef> {
         &self.types
     }
+    /// in the order of definition, commands generated by build
+    /// (e.g. for faults) follow the defined commands.
     pub fn commands(&self) -> &Vec<CommandRef> {
         &self.commands
     }
+    /// (owner, command) in the order the node was added as callee.
     pub fn extern_commands(&self) -> &Vec<(String, CommandRef)> {
         &self.extern_commands
     }
     pub fn extern_commands_mut(&mut self) -> &mut Vec<(String, CommandRef)> {
         &mut self.extern_commands
     }
+    /// in the order of definition.
     pub fn tx_streams(&self) -> &Vec<StreamRef> {
         &self.tx_streams
     }
+    /// in the order of definition (see NodeBuilder::receive_stream).
     pub fn rx_streams(&self) -> &Vec<StreamRef> {
         &self.rx_streams
     }
     pub fn rx_streams_mut(&mut self) -> &mut Vec<StreamRef> {
         &mut self.rx_streams
     }
+    /// sorted by name, like Network::messages.
     pub fn tx_messages(&self) -> &Vec<MessageRef> {
         &self.tx_messages
     }
+    /// sorted by name, like Network::messages.
     pub fn rx_messages(&self) -> &Vec<MessageRef> {
         &self.rx_messages
     }
+    /// the object entries of every node (e.g. config_hash) followed by the
+    /// defined ones in the order of definition, which is the order of the ids.
     pub fn object_entries(&self) -> &Vec<ObjectEntryRef> {
         &self.object_entries
     }
@@ -127,4 +187,70 @@ impl Node {
     pub fn buses(&self) -> &Vec<BusRef> {
         &self.buses
     }
+    pub fn fallback_bus(&self) -> Option<&BusRef> {
+        self.fallback_bus.as_ref()
+    }
+    /// faults defined with NodeBuilder::define_fault.
+    pub fn faults(&self) -> &Vec<FaultRef> {
+        &self.faults
+    }
+    /// object entries, which have to be restored from non volatile memory on startup.
+    pub fn persistent_object_entries(&self) -> Vec<ObjectEntryRef> {
+        self.object_entries
+            .iter()
+            .filter(|object_entry| object_entry.persistent())
+            .cloned()
+            .collect()
+    }
+    /// receives every message on its buses with an open filter, see
+    /// NodeBuilder::receive_all.
+    pub fn receive_all(&self) -> bool {
+        self.receive_all
+    }
+    /// messages that pass the hardware filters of the node, but are
+    /// not received by it and have to be dropped in software (sorted by name).
+    pub fn software_filters(&self) -> &Vec<MessageRef> {
+        self.software_filters.get().expect("software filters are set when building the network")
+    }
+    /// heartbeats the node has to monitor, see builder::supervision.
+    pub fn supervisions(&self) -> &Vec<Supervision> {
+        self.supervisions.get().expect("supervisions are set when building the network")
+    }
+    /// see NodeBuilder::create_snapshot_stream.
+    pub fn snapshot_stream(&self) -> Option<&SnapshotStreamRef> {
+        self.snapshot_stream.get().expect("snapshot streams are set when building the network").as_ref()
+    }
+    /// buses the node never transmits on, see NodeBuilder::set_listen_only.
+    pub fn listen_only(&self) -> &Vec<BusRef> {
+        self.listen_only.get().expect("listen only buses are set when building the network")
+    }
+    pub fn is_listen_only(&self, bus : &BusRef) -> bool {
+        self.listen_only().iter().any(|other| other.id() == bus.id())
+    }
+    /// listen only on all of its buses, the node doesn't transmit at all.
+    pub fn passive(&self) -> bool {
+        !self.listen_only().is_empty() && self.buses.iter().all(|bus| self.is_listen_only(bus))
+    }
+    /// commands the master calls in order on startup, see NodeBuilder::init_sequence.
+    pub fn init_sequence(&self) -> &[CommandRef] {
+        match self.init_sequence.get() {
+            Some(init_sequence) => init_sequence,
+            None => &[],
+        }
+    }
+    pub fn __set_init_sequence(&self, init_sequence : Vec<CommandRef>) {
+        self.init_sequence.set(init_sequence).expect("__set_init_sequence can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    pub fn __set_listen_only(&self, listen_only : Vec<BusRef>) {
+        self.listen_only.set(listen_only).expect("__set_listen_only can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    pub fn __set_snapshot_stream(&self, snapshot_stream : Option<SnapshotStreamRef>) {
+        self.snapshot_stream.set(snapshot_stream).expect("__set_snapshot_stream can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    pub fn __set_supervisions(&self, supervisions : Vec<Supervision>) {
+        self.supervisions.set(supervisions).expect("__set_supervisions can only be called once (when calling NetworkBuilder::build(&self))");
+    }
+    pub fn __set_software_filters(&self, software_filters : Vec<MessageRef>) {
+        self.software_filters.set(software_filters).expect("__set_software_filters can only be called once (when calling NetworkBuilder::build(&self))");
+    }
 }
@@ -1,6 +1,6 @@
 use std::{hash::Hash, sync::OnceLock};
 
-use super::{ConfigRef, TypeRef, Visibility, NodeRef};
+use super::{ConfigRef, TypeRef, Visibility, NodeRef, MessageRef, constant::ConstantRef};
 
 
 pub type ObjectEntryRef = ConfigRef<ObjectEntry>;
@@ -22,6 +22,202 @@ impl Hash for ObjectEntryAccess {
     }
 }
 
+/// bound of an object entry, either a literal or a network constant.
+#[derive(Debug, Clone)]
+pub enum ObjectEntryBound {
+    Value(f64),
+    Constant(ConstantRef),
+}
+
+impl ObjectEntryBound {
+    pub fn value(&self) -> f64 {
+        match self {
+            ObjectEntryBound::Value(value) => *value,
+            ObjectEntryBound::Constant(constant) => constant.value(),
+        }
+    }
+}
+
+impl Hash for ObjectEntryBound {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self {
+            ObjectEntryBound::Value(value) => {
+                state.write_u8(0);
+                state.write_u64(value.to_bits());
+            }
+            ObjectEntryBound::Constant(constant) => {
+                state.write_u8(1);
+                constant.hash(state);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ObjectEntryBounds {
+    min: ObjectEntryBound,
+    max: ObjectEntryBound,
+}
+
+impl ObjectEntryBounds {
+    pub fn new(min: ObjectEntryBound, max: ObjectEntryBound) -> Self {
+        Self { min, max }
+    }
+    pub fn min(&self) -> &ObjectEntryBound {
+        &self.min
+    }
+    pub fn max(&self) -> &ObjectEntryBound {
+        &self.max
+    }
+}
+
+/// conversion of the value of an object entry for display, independent of the
+/// encoding on the wire (e.g. wheel speed in km/h displayed in m/s).
+/// displayed = value * factor + offset
+#[derive(Debug, Clone)]
+pub struct ObjectEntryDisplay {
+    factor: f64,
+    offset: f64,
+    unit: String,
+}
+
+impl ObjectEntryDisplay {
+    pub fn new(factor: f64, offset: f64, unit: &str) -> Self {
+        Self {
+            factor,
+            offset,
+            unit: unit.to_owned(),
+        }
+    }
+    pub fn factor(&self) -> f64 {
+        self.factor
+    }
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+    pub fn unit(&self) -> &str {
+        &self.unit
+    }
+    pub fn apply(&self, value: f64) -> f64 {
+        value * self.factor + self.offset
+    }
+    /// inverse of apply, converts a displayed value back.
+    pub fn revert(&self, displayed: f64) -> f64 {
+        (displayed - self.offset) / self.factor
+    }
+}
+
+impl Hash for ObjectEntryDisplay {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.factor.to_bits());
+        state.write_u64(self.offset.to_bits());
+        for b in self.unit.bytes() {
+            state.write_u8(b);
+        }
+    }
+}
+
+/// how an entry, that is mapped from several tx streams (e.g. redundant
+/// sensors), picks its value (see ObjectEntryBuilder::aggregate).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AggregationPolicy {
+    /// the source that was received first is kept until it times out.
+    First,
+    /// the most recently received value wins.
+    Newest,
+    /// the value of the source with the highest priority, that didn't time out.
+    Priority,
+}
+
+/// tx entry of a stream, that is mapped into an aggregated entry.
+#[derive(Debug, Clone, Hash)]
+pub struct AggregationSource {
+    node: String,
+    stream: String,
+    object_entry: String,
+}
+
+impl AggregationSource {
+    pub fn new(node: &str, stream: &str, object_entry: &str) -> Self {
+        Self {
+            node: node.to_owned(),
+            stream: stream.to_owned(),
+            object_entry: object_entry.to_owned(),
+        }
+    }
+    /// the transmitting node.
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+    pub fn stream(&self) -> &str {
+        &self.stream
+    }
+    /// the entry of the transmitting node.
+    pub fn object_entry(&self) -> &str {
+        &self.object_entry
+    }
+}
+
+#[derive(Debug, Clone, Hash)]
+pub struct StreamAggregation {
+    policy: AggregationPolicy,
+    sources: Vec<AggregationSource>,
+}
+
+impl StreamAggregation {
+    pub fn new(policy: AggregationPolicy, sources: Vec<AggregationSource>) -> Self {
+        Self { policy, sources }
+    }
+    pub fn policy(&self) -> AggregationPolicy {
+        self.policy
+    }
+    /// in the order of the map calls, which is the descending
+    /// priority for AggregationPolicy::Priority.
+    pub fn sources(&self) -> &Vec<AggregationSource> {
+        &self.sources
+    }
+}
+
+/// indexed read of an array entry, that exceeds a frame
+/// (see builder::chunked_read).
+#[derive(Debug, Clone)]
+pub struct ChunkedRead {
+    request: MessageRef,
+    response: MessageRef,
+    index_bits: u8,
+    elements_per_frame: usize,
+}
+
+impl ChunkedRead {
+    pub fn new(request: MessageRef, response: MessageRef, index_bits: u8, elements_per_frame: usize) -> Self {
+        Self {
+            request,
+            response,
+            index_bits,
+            elements_per_frame,
+        }
+    }
+    /// shared by all nodes, addresses the entry like a get request
+    /// and selects count elements starting at start.
+    pub fn request(&self) -> &MessageRef {
+        &self.request
+    }
+    /// client_id, the index of the first element and the elements.
+    pub fn response(&self) -> &MessageRef {
+        &self.response
+    }
+    pub fn index_bits(&self) -> u8 {
+        self.index_bits
+    }
+    pub fn elements_per_frame(&self) -> usize {
+        self.elements_per_frame
+    }
+    /// number of response frames of a read of count elements.
+    pub fn frames(&self, count: usize) -> usize {
+        count.div_ceil(self.elements_per_frame)
+    }
+}
+
 #[derive(Debug)]
 pub struct ObjectEntry {
     name: String,
@@ -31,7 +227,12 @@ pub struct ObjectEntry {
     ty: TypeRef,
     access: ObjectEntryAccess,
     visibility: Visibility,
+    bounds : Option<ObjectEntryBounds>,
+    persistent : bool,
+    display : Option<ObjectEntryDisplay>,
+    aggregation : Option<StreamAggregation>,
     node : OnceLock<NodeRef>,
+    chunked_read : OnceLock<Option<ChunkedRead>>,
 }
 
 impl Hash for ObjectEntry {
@@ -52,6 +253,17 @@ impl Hash for ObjectEntry {
         self.ty.hash(state);
         self.access.hash(state);
         self.visibility.hash(state);
+        if let Some(bounds) = &self.bounds {
+            bounds.min.hash(state);
+            bounds.max.hash(state);
+        }
+        state.write_u8(self.persistent as u8);
+        if let Some(display) = &self.display {
+            display.hash(state);
+        }
+        if let Some(aggregation) = &self.aggregation {
+            aggregation.hash(state);
+        }
     }
 }
 
@@ -61,7 +273,11 @@ impl ObjectEntry {
                id : u32,
                ty : TypeRef,
                access : ObjectEntryAccess,
-               visibility : Visibility) -> Self {
+               visibility : Visibility,
+               bounds : Option<ObjectEntryBounds>,
+               persistent : bool,
+               display : Option<ObjectEntryDisplay>,
+               aggregation : Option<StreamAggregation>) -> Self {
         Self {
             name,
             description,
@@ -70,7 +286,12 @@ impl ObjectEntry {
             ty,
             access,
             visibility,
+            bounds,
+            persistent,
+            display,
+            aggregation,
             node : OnceLock::new(),
+            chunked_read : OnceLock::new(),
         }
     }
     pub fn id(&self) -> u32 {
@@ -97,6 +318,29 @@ impl ObjectEntry {
             None => None,
         }
     }
+    /// valid range of the value, only defined for numeric entries.
+    pub fn bounds(&self) -> Option<&ObjectEntryBounds> {
+        self.bounds.as_ref()
+    }
+    /// value is stored in non volatile memory, see ObjectEntryBuilder::persistent.
+    pub fn persistent(&self) -> bool {
+        self.persistent
+    }
+    /// conversion for displaying the value, see ObjectEntryBuilder::set_display.
+    pub fn display(&self) -> Option<&ObjectEntryDisplay> {
+        self.display.as_ref()
+    }
+    /// arbitration of the sources, if the entry is mapped from several streams.
+    pub fn aggregation(&self) -> Option<&StreamAggregation> {
+        self.aggregation.as_ref()
+    }
+    /// indexed read, if the entry is an array that exceeds a frame.
+    pub fn chunked_read(&self) -> Option<&ChunkedRead> {
+        self.chunked_read.get().and_then(Option::as_ref)
+    }
+    pub fn __set_chunked_read(&self, chunked_read : Option<ChunkedRead>) {
+        self.chunked_read.set(chunked_read).expect("__set_chunked_read can only be called once (when calling NetworkBuilder::build(&self))");
+    }
     pub fn __set_node(&self, node : NodeRef){
         self.node.set(node).expect("can't set the node of a object entry");
     }
@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use crate::simulator;
+
+use super::{message::MessageUsage, Message, MessageRef, Network};
+
+// Hints where the network wastes bandwidth. Every frame carries ~50 bits of
+// overhead, therefore small messages that are sent by the same node to the
+// same receivers are cheaper as a single frame. Merging is only suggested if
+// the intervals are compatible (the merged frame is sent with the shorter
+// interval) and the merged frame occupies the bus less than both frames.
+// Generated messages of the configuration protocol and the heartbeat are
+// not part of the hints, their layout is fixed.
+
+// the longer interval of merged messages is at most twice the shorter one.
+const MAX_MERGE_INTERVAL_RATIO: f64 = 2.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptimizationHint {
+    /// the signals of the message use fewer bits than the payload.
+    UnusedPayload {
+        message: String,
+        used_bits: u32,
+        dlc: u8,
+    },
+    /// both messages fit into a single frame with the same transmitter,
+    /// receivers and bus. saved_load is the reduction of the bus load (0..1).
+    Merge {
+        messages: (String, String),
+        dlc: u8,
+        interval: Duration,
+        saved_load: f64,
+    },
+}
+
+fn is_hinted(message: &Message) -> bool {
+    // the heartbeat is built as an external message, the hints
+    // only model classic and FD frames (dlc <= 64 bytes).
+    message.name() != "heartbeat"
+        && message.xl_frame().is_none()
+        && !matches!(
+        message.usage(),
+        MessageUsage::GetReq
+            | MessageUsage::GetResp
+            | MessageUsage::SetReq
+            | MessageUsage::SetResp
+            | MessageUsage::Heartbeat
+    )
+}
+
+fn used_bits(message: &Message) -> u32 {
+    message.signals().iter().map(|signal| signal.size() as u32).sum()
+}
+
+/// load of the message on its bus (0..1).
+fn load(network: &Network, message: &Message, ide: bool, dlc: u8, interval: Duration) -> f64 {
+    let bus = message.bus();
+    network
+        .frame_time_model()
+        .load(ide, bus.transmitted_len(dlc), interval)
+        / bus.baudrate() as f64
+}
+
+// (bus id, (transmitters, receivers))
+type MergeKey = (u32, (Vec<String>, Vec<String>));
+
+/// (transmitters, receivers) by name, nodes that receive every
+/// message don't restrict merging.
+fn endpoints(network: &Network, message: &Message) -> (Vec<String>, Vec<String>) {
+    let sends = |messages: &Vec<MessageRef>| messages.iter().any(|m| m.name() == message.name());
+    let transmitters = network
+        .nodes()
+        .iter()
+        .filter(|node| sends(node.tx_messages()))
+        .map(|node| node.name().to_owned())
+        .collect();
+    let receivers = network
+        .nodes()
+        .iter()
+        .filter(|node| !node.receive_all() && sends(node.rx_messages()))
+        .map(|node| node.name().to_owned())
+        .collect();
+    (transmitters, receivers)
+}
+
+/// a and b have the same bus, transmitters and receivers.
+fn merge_hint(network: &Network, a: &MessageRef, b: &MessageRef) -> Option<OptimizationHint> {
+    let bits = used_bits(a) + used_bits(b);
+    if bits == 0 || bits > 64 {
+        return None;
+    }
+    let (interval_a, interval_b) = (simulator::message_interval(a), simulator::message_interval(b));
+    let interval = interval_a.min(interval_b);
+    if interval.is_zero()
+        || interval_a.max(interval_b).as_secs_f64() > interval.as_secs_f64() * MAX_MERGE_INTERVAL_RATIO
+    {
+        return None;
+    }
+    let dlc = bits.div_ceil(8) as u8;
+    // the merged frame keeps the id format of the higher priority message.
+    let ide = if a.id().as_u32() <= b.id().as_u32() { a.id().ide() } else { b.id().ide() };
+    let before = load(network, a, a.id().ide(), a.dlc(), interval_a)
+        + load(network, b, b.id().ide(), b.dlc(), interval_b);
+    let saved_load = before - load(network, a, ide, dlc, interval);
+    (saved_load > 0.0).then(|| OptimizationHint::Merge {
+        messages: (a.name().to_owned(), b.name().to_owned()),
+        dlc,
+        interval,
+        saved_load,
+    })
+}
+
+impl Network {
+    /// messages with unused payload bits, followed by merge suggestions
+    /// ordered by the saved bus load. Every message is part of at most
+    /// one suggestion.
+    pub fn optimization_hints(&self) -> Vec<OptimizationHint> {
+        let messages: Vec<&MessageRef> = self.messages().iter().filter(|m| is_hinted(m)).collect();
+        let mut hints: Vec<OptimizationHint> = messages
+            .iter()
+            .filter(|message| used_bits(message) < message.dlc() as u32 * 8)
+            .map(|message| OptimizationHint::UnusedPayload {
+                message: message.name().to_owned(),
+                used_bits: used_bits(message),
+                dlc: message.dlc(),
+            })
+            .collect();
+
+        // only messages with the same bus and endpoints can be merged.
+        let mut groups: Vec<(MergeKey, Vec<&MessageRef>)> = vec![];
+        for message in &messages {
+            let key = (message.bus().id(), endpoints(self, message));
+            match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+                Some((_, group)) => group.push(message),
+                None => groups.push((key, vec![message])),
+            }
+        }
+        let mut merges: Vec<OptimizationHint> = groups
+            .iter()
+            .flat_map(|(_, group)| {
+                group.iter().enumerate().flat_map(move |(i, a)| {
+                    group[i + 1..].iter().filter_map(move |b| merge_hint(self, a, b))
+                })
+            })
+            .collect();
+        merges.sort_by(|a, b| match (a, b) {
+            (
+                OptimizationHint::Merge { saved_load: a, .. },
+                OptimizationHint::Merge { saved_load: b, .. },
+            ) => b.total_cmp(a),
+            _ => std::cmp::Ordering::Equal,
+        });
+        let mut merged: Vec<String> = vec![];
+        for merge in merges {
+            let OptimizationHint::Merge { messages: (a, b), .. } = &merge else {
+                continue;
+            };
+            if merged.contains(a) || merged.contains(b) {
+                continue;
+            }
+            merged.push(a.clone());
+            merged.push(b.clone());
+            hints.push(merge);
+        }
+        hints
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+
+// Provenance of a build, such that a flashed ECU can be traced back to the
+// generator and the sources of its configuration. The revision of the
+// sources is supplied by the caller (see NetworkBuilder::set_source_revision),
+// input files are identified by the seahash of their content.
+
+pub const CRATE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputFile {
+    path: String,
+    hash: u64,
+}
+
+impl InputFile {
+    pub fn new(path: String, hash: u64) -> Self {
+        Self { path, hash }
+    }
+    /// path as it was passed to the builder.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// seahash of the content.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    crate_version: String,
+    source_revision: Option<String>,
+    input_files: Vec<InputFile>,
+    resolver: String,
+}
+
+impl Provenance {
+    pub fn new(source_revision: Option<String>, input_files: Vec<InputFile>, resolver: String) -> Self {
+        Self {
+            crate_version: CRATE_VERSION.to_owned(),
+            source_revision,
+            input_files,
+            resolver,
+        }
+    }
+    /// version of canzero-config that built the network.
+    pub fn crate_version(&self) -> &str {
+        &self.crate_version
+    }
+    /// revision of the config sources, e.g. a git commit.
+    pub fn source_revision(&self) -> Option<&str> {
+        self.source_revision.as_deref()
+    }
+    /// in the order in which they were read.
+    pub fn input_files(&self) -> &[InputFile] {
+        &self.input_files
+    }
+    /// bus balancer, receiver set folding and seed of the id resolution.
+    pub fn resolver(&self) -> &str {
+        &self.resolver
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "canzero-config {}", self.crate_version)?;
+        if let Some(revision) = &self.source_revision {
+            write!(f, ", source revision {revision}")?;
+        }
+        write!(f, ", resolver {}", self.resolver)
+    }
+}
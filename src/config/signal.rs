@@ -1,6 +1,6 @@
-use std::{fmt::Display, hash::Hash};
+use std::{fmt::Display, hash::Hash, time::Duration};
 
-use super::ConfigRef;
+use super::{Annotations, ConfigRef};
 
 
 
@@ -33,6 +33,89 @@ pub enum SignalType {
     UnsignedInt { size: u8 },
     SignedInt { size: u8 },
     Decimal { size: u8, offset: f64, scale: f64 },
+    /// unsigned ticks of the unit, t32<ms> (timestamp) or dt32<ms> (duration).
+    Time { size: u8, unit: TimeUnit, kind: TimeKind },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeUnit {
+    Micros,
+    Millis,
+    Secs,
+}
+
+impl TimeUnit {
+    pub fn from_suffix(suffix: &str) -> Option<TimeUnit> {
+        match suffix {
+            "us" => Some(TimeUnit::Micros),
+            "ms" => Some(TimeUnit::Millis),
+            "s" => Some(TimeUnit::Secs),
+            _ => None,
+        }
+    }
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            TimeUnit::Micros => "us",
+            TimeUnit::Millis => "ms",
+            TimeUnit::Secs => "s",
+        }
+    }
+    /// length of a tick in seconds.
+    pub fn secs(&self) -> f64 {
+        match self {
+            TimeUnit::Micros => 1e-6,
+            TimeUnit::Millis => 1e-3,
+            TimeUnit::Secs => 1.0,
+        }
+    }
+    pub fn ticks(&self, ticks: u64) -> Duration {
+        match self {
+            TimeUnit::Micros => Duration::from_micros(ticks),
+            TimeUnit::Millis => Duration::from_millis(ticks),
+            TimeUnit::Secs => Duration::from_secs(ticks),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeKind {
+    /// ticks since the epoch of the network (see TimeEpoch), wrapping
+    /// around after 2^size ticks.
+    Timestamp,
+    /// ticks between two points in time, saturating at 2^size - 1 ticks.
+    Duration,
+}
+
+impl TimeKind {
+    /// prefix of the type string, t32<ms> or dt32<ms>.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            TimeKind::Timestamp => "t",
+            TimeKind::Duration => "dt",
+        }
+    }
+}
+
+/// reference point of all timestamps of a network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimeEpoch {
+    /// the boot of the transmitting node.
+    #[default]
+    Boot,
+    /// the time of the network, which is synchronized by the master.
+    Network,
+    /// 1970-01-01 00:00:00 UTC.
+    Unix,
+}
+
+impl Display for TimeEpoch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeEpoch::Boot => write!(f, "boot"),
+            TimeEpoch::Network => write!(f, "network"),
+            TimeEpoch::Unix => write!(f, "unix"),
+        }
+    }
 }
 
 impl Hash for SignalType {
@@ -52,6 +135,12 @@ impl Hash for SignalType {
                 ((*offset * 1e4) as u128).hash(state);
                 ((*scale * 1e4) as u128).hash(state);
             }
+            SignalType::Time { size, unit, kind } => {
+                state.write_u8(3);
+                state.write_u128(*size as u128);
+                unit.hash(state);
+                kind.hash(state);
+            }
         }
     }
 }
@@ -66,6 +155,7 @@ impl SignalType {
                 offset,
                 scale: _,
             } => *offset,
+            SignalType::Time { .. } => 0.0,
         }
     }
     pub fn size(&self) -> u8 {
@@ -77,6 +167,7 @@ impl SignalType {
                 offset: _,
                 scale: _,
             } => *size,
+            SignalType::Time { size, .. } => *size,
         }
     }
     pub fn scale(&self) -> f64 {
@@ -88,6 +179,8 @@ impl SignalType {
                 offset: _,
                 scale,
             } => *scale,
+            // decoded time values are seconds.
+            SignalType::Time { unit, .. } => unit.secs(),
         }
     }
     pub fn sign(&self) -> SignalSign {
@@ -99,12 +192,27 @@ impl SignalType {
                 offset: _,
                 scale: _,
             } => SignalSign::Unsigned,
+            SignalType::Time { .. } => SignalSign::Unsigned,
+        }
+    }
+    /// time after which a timestamp wraps around.
+    pub fn rollover(&self) -> Option<Duration> {
+        match self {
+            SignalType::Time {
+                size,
+                unit,
+                kind: TimeKind::Timestamp,
+            } => Some(unit.ticks(1u64.checked_shl(*size as u32).unwrap_or(u64::MAX))),
+            _ => None,
         }
     }
 }
 
 pub type SignalRef = ConfigRef<Signal>;
 
+/// annotation key of receive handlers.
+pub const ON_RECEIVE: &str = "on_receive";
+
 #[derive(Debug, Clone)]
 pub struct Signal {
     pub name: String,
@@ -113,6 +221,10 @@ pub struct Signal {
     pub value_table: Option<ValueTableRef>,
     // refers to the byte offset!
     pub offset: usize,
+    // reserved for future use, always transmitted as zero.
+    pub reserved: bool,
+    // hints for code generators, e.g. on_receive. not part of the hash.
+    pub annotations: Annotations,
 }
 
 impl Hash for Signal {
@@ -138,6 +250,9 @@ impl Hash for Signal {
             None => state.write_u8(1),
         }
         state.write_u128(self.offset as u128);
+        if self.reserved {
+            state.write_u8(2);
+        }
     }
 }
 
@@ -149,6 +264,8 @@ impl Signal {
             ty,
             offset,
             value_table : None,
+            reserved : false,
+            annotations : Annotations::new(),
         }
     }
     pub fn create(name : &str, description : Option<&str>, ty : SignalType) -> Signal {
@@ -158,8 +275,42 @@ impl Signal {
             ty,
             offset : 0,
             value_table : None,
+            reserved : false,
+            annotations : Annotations::new(),
+        }
+    }
+    /// padding that reserves space for future signals.
+    pub fn create_reserved(name : &str, bits : u8) -> Signal {
+        Signal {
+            name : name.to_owned(),
+            description : None,
+            ty : SignalType::UnsignedInt { size: bits },
+            offset : 0,
+            value_table : None,
+            reserved : true,
+            annotations : Annotations::new(),
         }
     }
+    pub fn with_annotation(mut self, key : &str, value : &str) -> Signal {
+        self.annotations.insert(key.to_owned(), value.to_owned());
+        self
+    }
+    /// handler that firmware code generators call when the signal is received.
+    pub fn with_on_receive(self, handler : &str) -> Signal {
+        self.with_annotation(ON_RECEIVE, handler)
+    }
+    pub fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
+    pub fn annotation(&self, key : &str) -> Option<&str> {
+        self.annotations.get(key).map(String::as_str)
+    }
+    pub fn on_receive(&self) -> Option<&str> {
+        self.annotation(ON_RECEIVE)
+    }
+    pub fn is_reserved(&self) -> bool {
+        self.reserved
+    }
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use super::{ConfigRef, MessageRef, ObjectEntryRef};
+
+pub type SnapshotStreamRef = ConfigRef<SnapshotStream>;
+
+/// sends one object entry per frame and cycles through the entries
+/// round-robin. Every frame carries the index of the entry (index_bits)
+/// followed by the raw value of the entry (value_bits), the receiver
+/// demultiplexes the frames with the index into the entries.
+#[derive(Debug)]
+pub struct SnapshotStream {
+    message: MessageRef,
+    entries: Vec<ObjectEntryRef>,
+    interval: Duration,
+    index_bits: u8,
+}
+
+impl SnapshotStream {
+    pub fn new(message: MessageRef, entries: Vec<ObjectEntryRef>, interval: Duration, index_bits: u8) -> Self {
+        Self {
+            message,
+            entries,
+            interval,
+            index_bits,
+        }
+    }
+    pub fn message(&self) -> &MessageRef {
+        &self.message
+    }
+    /// entries in the order of the rotation, the position is the index.
+    pub fn entries(&self) -> &Vec<ObjectEntryRef> {
+        &self.entries
+    }
+    /// entry of a received index, None for invalid indices.
+    pub fn entry(&self, index: u32) -> Option<&ObjectEntryRef> {
+        self.entries.get(index as usize)
+    }
+    /// time between two frames.
+    pub fn interval(&self) -> &Duration {
+        &self.interval
+    }
+    /// time until every entry was sent once.
+    pub fn rotation_period(&self) -> Duration {
+        self.interval * self.entries.len() as u32
+    }
+    pub fn index_bits(&self) -> u8 {
+        self.index_bits
+    }
+    /// bits of the value signal, the raw value of an entry is in the
+    /// lower bits.
+    pub fn value_bits(&self) -> u8 {
+        64 - self.index_bits
+    }
+}
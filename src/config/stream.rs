@@ -1,10 +1,50 @@
 use std::{hash::Hash, time::Duration};
 
-use super::{ConfigRef, ObjectEntryRef, MessageRef, Visibility};
+use super::{Annotations, ConfigRef, ObjectEntryRef, MessageRef, Visibility};
 
 
 pub type StreamRef = ConfigRef<Stream>;
 
+/// what happens if the difference to the last transmitted value
+/// doesn't fit into the width of the delta signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DeltaRollover {
+    /// the delta is clamped, the receiver catches up over the next frames.
+    Saturate,
+    /// the raw value wraps around at the reference width (e.g. counters or angles).
+    Wrap,
+}
+
+/// the signal of a stream entry carries the difference of the raw value
+/// since the last frame instead of the raw value itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeltaEncoding {
+    width: u8,
+    reference_width: u8,
+    rollover: DeltaRollover,
+}
+
+impl DeltaEncoding {
+    pub fn new(width: u8, reference_width: u8, rollover: DeltaRollover) -> Self {
+        Self {
+            width,
+            reference_width,
+            rollover,
+        }
+    }
+    /// bits of the (signed) delta signal.
+    pub fn width(&self) -> u8 {
+        self.width
+    }
+    /// bits of the raw value of the object entry.
+    pub fn reference_width(&self) -> u8 {
+        self.reference_width
+    }
+    pub fn rollover(&self) -> DeltaRollover {
+        self.rollover
+    }
+}
+
 #[derive(Debug)]
 pub struct Stream {
     name: String,
@@ -13,6 +53,10 @@ pub struct Stream {
     message: MessageRef,
     visibility: Visibility,
     interval : (Duration, Duration),
+    delta_encodings : Vec<Option<DeltaEncoding>>,
+    merged_streams : Vec<String>,
+    timeout : Option<Duration>,
+    annotations : Annotations,
 }
 
 impl Hash for Stream {
@@ -36,6 +80,10 @@ impl Hash for Stream {
         let us2 = self.interval.1.as_micros();
         state.write_u128(us1);
         state.write_u128(us2);
+        self.delta_encodings.hash(state);
+        if let Some(timeout) = self.timeout {
+            state.write_u128(timeout.as_micros());
+        }
     }
 }
 
@@ -44,7 +92,11 @@ impl Stream {
                mappings : Vec<Option<ObjectEntryRef>>,
                message : MessageRef,
                visibility : Visibility,
-               interval : (Duration,Duration)) -> Self {
+               interval : (Duration,Duration),
+               delta_encodings : Vec<Option<DeltaEncoding>>,
+               merged_streams : Vec<String>,
+               timeout : Option<Duration>,
+               annotations : Annotations) -> Self {
         Self {
             name,
             description,
@@ -52,8 +104,15 @@ impl Stream {
             message,
             visibility,
             interval,
+            delta_encodings,
+            merged_streams,
+            timeout,
+            annotations,
         }
     }
+    pub fn annotations(&self) -> &Annotations {
+        &self.annotations
+    }
     pub fn min_interval(&self) -> &Duration {
         &self.interval.0
     }
@@ -81,4 +140,19 @@ impl Stream {
     pub fn message(&self) -> &MessageRef {
         &self.message
     }
+    /// delta encoding of every entry of the stream, None for entries
+    /// that are transmitted as they are.
+    pub fn delta_encodings(&self) -> &Vec<Option<DeltaEncoding>> {
+        &self.delta_encodings
+    }
+    /// streams that were merged into this stream by build
+    /// (see NetworkBuilder::enable_stream_merging).
+    pub fn merged_streams(&self) -> &Vec<String> {
+        &self.merged_streams
+    }
+    /// how stale the values of a received stream may become
+    /// (see ReceiveStreamBuilder::set_timeout), None for tx streams.
+    pub fn timeout(&self) -> Option<&Duration> {
+        self.timeout.as_ref()
+    }
 }
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use super::{bus::BusRef, MessageRef, Network};
+
+// Time-triggered schedules (similar to TTCAN) divide the time on a bus into
+// basic cycles. Every time-triggered message owns an exclusive window at a
+// fixed offset within the basic cycle, which bounds its jitter to the
+// synchronization error of the nodes. The time that is not covered by a
+// window is an arbitrating window for all other (event-driven) messages.
+// A matrix cycle consists of cycle_count basic cycles, messages with a
+// longer interval only use their window in every repeat-th basic cycle.
+
+#[derive(Debug)]
+pub struct TimeTriggeredSchedule {
+    bus: BusRef,
+    basic_cycle: Duration,
+    cycle_count: u32,
+    windows: Vec<TimeWindow>,
+}
+
+impl TimeTriggeredSchedule {
+    pub fn new(
+        bus: BusRef,
+        basic_cycle: Duration,
+        cycle_count: u32,
+        windows: Vec<TimeWindow>,
+    ) -> Self {
+        Self {
+            bus,
+            basic_cycle,
+            cycle_count,
+            windows,
+        }
+    }
+    pub fn bus(&self) -> &BusRef {
+        &self.bus
+    }
+    pub fn basic_cycle(&self) -> &Duration {
+        &self.basic_cycle
+    }
+    /// basic cycles per matrix cycle.
+    pub fn cycle_count(&self) -> u32 {
+        self.cycle_count
+    }
+    pub fn matrix_cycle(&self) -> Duration {
+        self.basic_cycle * self.cycle_count
+    }
+    /// windows ordered by their offset.
+    pub fn windows(&self) -> &Vec<TimeWindow> {
+        &self.windows
+    }
+    /// windows of the messages that the node transmits or receives.
+    pub fn windows_of(&self, network: &Network, node_name: &str) -> Vec<&TimeWindow> {
+        let Some(node) = network.nodes().iter().find(|node| node.name() == node_name) else {
+            return vec![];
+        };
+        self.windows
+            .iter()
+            .filter(|window| {
+                let name = window.message().name();
+                node.tx_messages().iter().any(|m| m.name() == name)
+                    || node.rx_messages().iter().any(|m| m.name() == name)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub struct TimeWindow {
+    message: MessageRef,
+    first_cycle: u32,
+    repeat: u32,
+    offset: Duration,
+    length: Duration,
+}
+
+impl TimeWindow {
+    pub fn new(
+        message: MessageRef,
+        first_cycle: u32,
+        repeat: u32,
+        offset: Duration,
+        length: Duration,
+    ) -> Self {
+        Self {
+            message,
+            first_cycle,
+            repeat,
+            offset,
+            length,
+        }
+    }
+    pub fn message(&self) -> &MessageRef {
+        &self.message
+    }
+    /// first basic cycle of the matrix cycle, in which the message is sent.
+    pub fn first_cycle(&self) -> u32 {
+        self.first_cycle
+    }
+    /// the message is sent in every repeat-th basic cycle.
+    pub fn repeat(&self) -> u32 {
+        self.repeat
+    }
+    /// start of the window relative to the start of the basic cycle.
+    pub fn offset(&self) -> &Duration {
+        &self.offset
+    }
+    /// worst-case frame time of the message.
+    pub fn length(&self) -> &Duration {
+        &self.length
+    }
+}
@@ -0,0 +1,121 @@
+use super::{MessageRef, Network, NodeRef, Type, TypeRef};
+
+// Which messages and object entries reference a user defined type (struct or
+// enum). References are transitive, a message carrying a struct also uses
+// the types of its attributes. Types that are not used by any message or
+// object entry only bloat the generated code.
+
+#[derive(Debug, Clone)]
+pub struct TypeUsage {
+    ty: TypeRef,
+    messages: Vec<String>,
+    // node::object_entry
+    object_entries: Vec<String>,
+}
+
+impl TypeUsage {
+    pub fn ty(&self) -> &TypeRef {
+        &self.ty
+    }
+    /// names of the messages whose encoding contains the type.
+    pub fn messages(&self) -> &Vec<String> {
+        &self.messages
+    }
+    /// object entries (node::name) whose value contains the type.
+    pub fn object_entries(&self) -> &Vec<String> {
+        &self.object_entries
+    }
+    pub fn is_used(&self) -> bool {
+        !self.messages.is_empty() || !self.object_entries.is_empty()
+    }
+}
+
+fn collect_type_names(ty: &Type, names: &mut Vec<String>) {
+    match ty {
+        Type::Primitive(_) => (),
+        Type::Struct {
+            name,
+            description: _,
+            attribs,
+            visibility: _,
+            annotations: _,
+        } => {
+            if !names.contains(name) {
+                names.push(name.clone());
+                for (_, attrib_ty) in attribs {
+                    collect_type_names(attrib_ty, names);
+                }
+            }
+        }
+        Type::Enum {
+            name,
+            description: _,
+            size: _,
+            entries: _,
+            visibility: _,
+            annotations: _,
+        } => {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        Type::Array { len: _, ty } => collect_type_names(ty, names),
+    }
+}
+
+/// usage of all structs and enums of types, in the order of types.
+pub(crate) fn type_usage(
+    types: &[TypeRef],
+    messages: &[MessageRef],
+    nodes: &[NodeRef],
+) -> Vec<TypeUsage> {
+    let message_types: Vec<(&str, Vec<String>)> = messages
+        .iter()
+        .map(|message| {
+            let mut names = vec![];
+            if let Some(encoding) = message.encoding() {
+                for attribute in encoding.attributes() {
+                    collect_type_names(attribute.ty(), &mut names);
+                }
+            }
+            (message.name(), names)
+        })
+        .collect();
+    let object_entry_types: Vec<(String, Vec<String>)> = nodes
+        .iter()
+        .flat_map(|node| {
+            node.object_entries().iter().map(|object_entry| {
+                let mut names = vec![];
+                collect_type_names(object_entry.ty(), &mut names);
+                (format!("{}::{}", node.name(), object_entry.name()), names)
+            })
+        })
+        .collect();
+    types
+        .iter()
+        .filter(|ty| matches!(ty as &Type, Type::Struct { .. } | Type::Enum { .. }))
+        .map(|ty| {
+            let name = ty.name();
+            TypeUsage {
+                ty: ty.clone(),
+                messages: message_types
+                    .iter()
+                    .filter(|(_, names)| names.contains(&name))
+                    .map(|(message, _)| message.to_string())
+                    .collect(),
+                object_entries: object_entry_types
+                    .iter()
+                    .filter(|(_, names)| names.contains(&name))
+                    .map(|(object_entry, _)| object_entry.clone())
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+impl Network {
+    /// usage of every struct and enum of the network.
+    pub fn type_usage(&self) -> Vec<TypeUsage> {
+        type_usage(self.types(), self.messages(), self.nodes())
+    }
+}
@@ -1,9 +1,11 @@
 use std::hash::Hash;
 
-use super::{ConfigRef, SignalType, Visibility};
+use super::{Annotations, ConfigRef, SignalType, Visibility};
 
 pub type TypeRef = ConfigRef<Type>;
 
+static NO_ANNOTATIONS: Annotations = Annotations::new();
+
 #[derive(Debug, PartialEq)]
 pub enum Type {
     Primitive(SignalType),
@@ -12,6 +14,7 @@ pub enum Type {
         description: Option<String>,
         attribs: Vec<(String, TypeRef)>,
         visibility: Visibility,
+        annotations: Annotations,
     },
     Enum {
         name: String,
@@ -19,6 +22,7 @@ pub enum Type {
         size: u8,
         entries: Vec<(String, u64)>,
         visibility: Visibility,
+        annotations: Annotations,
     },
     Array {
         len: usize,
@@ -33,7 +37,7 @@ impl Hash for Type  {
                 state.write_u8(0);
                 signal.hash(state);
             }
-            Type::Struct { name, description, attribs, visibility } => {
+            Type::Struct { name, description, attribs, visibility, annotations: _ } => {
                 state.write_u8(1);
                 for b in name.bytes() {
                     state.write_u8(b);
@@ -55,7 +59,7 @@ impl Hash for Type  {
                 }
                 visibility.hash(state);
             },
-            Type::Enum { name, description, size, entries, visibility } => {
+            Type::Enum { name, description, size, entries, visibility, annotations: _ } => {
                 state.write_u8(2);
                 for b in name.bytes() {
                     state.write_u8(b);
@@ -104,12 +108,16 @@ impl Type {
                 } => {
                     return format!("d{size}<offset={offset}, scale={scale}>");
                 }
+                SignalType::Time { size, unit, kind } => {
+                    return format!("{}{size}<{}>", kind.prefix(), unit.suffix());
+                }
             },
             Type::Struct {
                 name,
                 description: _,
                 attribs: _,
                 visibility: _,
+                annotations: _,
             } => name.to_owned(),
             Type::Enum {
                 name,
@@ -117,10 +125,18 @@ impl Type {
                 size: _,
                 entries: _,
                 visibility: _,
+                annotations: _,
             } => name.to_owned(),
             Type::Array { len, ty } => format!("{}[{len}]", ty.name()),
         }
     }
+    /// annotations of structs and enums, primitives and arrays have none.
+    pub fn annotations(&self) -> &Annotations {
+        match &self {
+            Type::Struct { annotations, .. } | Type::Enum { annotations, .. } => annotations,
+            Type::Primitive(_) | Type::Array { .. } => &NO_ANNOTATIONS,
+        }
+    }
     pub fn size(&self) -> u32 {
         match &self {
             Type::Primitive(signal_type) => signal_type.size() as u32,
@@ -129,6 +145,7 @@ impl Type {
                 description: _,
                 attribs,
                 visibility: _,
+                annotations: _,
             } => attribs.iter().map(|(_, attrib_ty)| attrib_ty.size()).sum(),
             Type::Enum {
                 name: _,
@@ -136,6 +153,7 @@ impl Type {
                 size,
                 entries: _,
                 visibility: _,
+                annotations: _,
             } => *size as u32,
             Type::Array { len, ty } => ty.size() * *len as u32,
         }
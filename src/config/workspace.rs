@@ -0,0 +1,40 @@
+use super::{ConfigRef, NetworkRef};
+
+pub type WorkspaceRef = ConfigRef<Workspace>;
+
+/// independent networks that were built together, sharing the
+/// type definitions of the workspace and their gateway nodes.
+#[derive(Debug)]
+pub struct Workspace {
+    networks: Vec<(String, NetworkRef)>,
+}
+
+impl Workspace {
+    pub fn new(networks: Vec<(String, NetworkRef)>) -> Self {
+        Self { networks }
+    }
+    pub fn networks(&self) -> &Vec<(String, NetworkRef)> {
+        &self.networks
+    }
+    pub fn network(&self, name: &str) -> Option<&NetworkRef> {
+        self.networks
+            .iter()
+            .find(|(network_name, _)| network_name == name)
+            .map(|(_, network)| network)
+    }
+    /// nodes that are part of more than one network,
+    /// with the names of the networks that they are part of.
+    pub fn gateways(&self) -> Vec<(String, Vec<String>)> {
+        let mut gateways: Vec<(String, Vec<String>)> = vec![];
+        for (network_name, network) in &self.networks {
+            for node in network.nodes() {
+                match gateways.iter_mut().find(|(name, _)| name == node.name()) {
+                    Some((_, networks)) => networks.push(network_name.clone()),
+                    None => gateways.push((node.name().to_owned(), vec![network_name.clone()])),
+                }
+            }
+        }
+        gateways.retain(|(_, networks)| networks.len() > 1);
+        gateways
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Display;
+
+// Diagnostics are emitted by the checks at the end of NetworkBuilder::build.
+// Unlike errors they don't stop the build unless their severity is Deny.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    EmptyMessage,
+    MessageWithoutReceivers,
+    MessageWithoutTransmitter,
+    ObjectEntryNotStreamed,
+    BusLoad,
+    BusQuota,
+    BusPin,
+    HiddenMessageWiring,
+    CommandTimeout,
+    CommandPipelining,
+    DeadlineMiss,
+    PriorityInversion,
+    UnitMismatch,
+    StreamOverflow,
+    StreamWithoutReceivers,
+    UnusedType,
+    NamingConvention,
+}
+
+impl DiagnosticKind {
+    fn default_severity(&self) -> Severity {
+        match self {
+            // the frame of an overflowing stream can't be built.
+            DiagnosticKind::StreamOverflow => Severity::Deny,
+            // pinned messages are placed by hand, a violated pin is a bug.
+            DiagnosticKind::BusPin => Severity::Deny,
+            // the resolver relies on the wiring of streams and commands.
+            DiagnosticKind::HiddenMessageWiring => Severity::Deny,
+            _ => Severity::Warn,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Allow,
+    Warn,
+    Deny,
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    kind: DiagnosticKind,
+    severity: Severity,
+    message: String,
+}
+
+impl Diagnostic {
+    pub fn kind(&self) -> DiagnosticKind {
+        self.kind
+    }
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.severity {
+            Severity::Allow => write!(f, "ALLOW: {}", self.message),
+            Severity::Warn => write!(f, "WARN: {}", self.message),
+            Severity::Deny => write!(f, "ERROR: {}", self.message),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    severities: Vec<(DiagnosticKind, Severity)>,
+    deny_warnings: bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn set_severity(&mut self, kind: DiagnosticKind, severity: Severity) {
+        self.severities.retain(|(k, _)| *k != kind);
+        self.severities.push((kind, severity));
+    }
+    /// turns all warnings into errors.
+    pub fn deny_warnings(&mut self) {
+        self.deny_warnings = true;
+    }
+    pub fn severity(&self, kind: DiagnosticKind) -> Severity {
+        let severity = self
+            .severities
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, severity)| *severity)
+            .unwrap_or(kind.default_severity());
+        if self.deny_warnings && severity == Severity::Warn {
+            Severity::Deny
+        } else {
+            severity
+        }
+    }
+    /// allowed diagnostics are dropped.
+    pub fn emit(&mut self, kind: DiagnosticKind, message: String) {
+        let severity = self.severity(kind);
+        if severity == Severity::Allow {
+            return;
+        }
+        self.diagnostics.push(Diagnostic {
+            kind,
+            severity,
+            message,
+        });
+    }
+    pub fn diagnostics(&self) -> &Vec<Diagnostic> {
+        &self.diagnostics
+    }
+    pub fn denied(&self) -> Vec<&Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Deny)
+            .collect()
+    }
+}
@@ -6,16 +6,110 @@ pub enum ConfigError {
     InvalidRange(String),
     InvalidType(String),
     DuplicatedSignal(String),
+    OverlappingSignals(String),
     DuplicatedEnumEntry(String),
     DuplicatedStructAttribute(String),
     UndefinedType(String),
+    UndefinedConstant(String),
+    DuplicatedConstant(String),
+    InvalidConstant(String),
+    DuplicatedFault(String),
     InvalidDecimalDefinition(String),
     FailedToResolveId,
     NoBusAvaiable,
+    InvalidFallbackBus(String),
+    InvalidVersion(String),
+    InvalidCommandArguments(String),
+    InvalidDeltaEncoding(String),
+    InvalidUdsConfiguration(String),
+    InvalidImport(String),
+    InvalidBusQuota(String),
+    UnmappedStreamEntry(String),
+    ConflictingStreamMapping(String),
+    InvalidWorkspace(String),
+    InvalidLinConfiguration(String),
+    InvalidMirror(String),
+    InvalidTimeTriggeredSchedule(String),
+    InvalidReceiveStream(String),
+    InvalidCallee(String),
+    InvalidDisplayConversion(String),
+    InvalidNamingRule(String),
+    InvalidSupervision(String),
+    ListenOnlyTransmission(String),
+    InvalidFilterExport(String),
+    InvalidTopology(String),
+    InvalidFaultInjection(String),
+    InvalidSnapshotStream(String),
+    InvalidCanXl(String),
+    InvalidRemoteRequest(String),
+    InvalidInitSequence(String),
+    InvalidBusBalancing(String),
+    InvalidLayoutLock(String),
+    InvalidMessageAuthentication(String),
+    DeniedDiagnostics(String),
+    Capacity(CapacityError),
     Io(std::io::Error),
     CanDbc(String),
 }
 
+/// the network doesn't fit into the id space or the bandwidth of its buses.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CapacityError {
+    /// the receiver sets need more setcodes than the ids leave
+    /// next to the priority bits.
+    IdSpace {
+        needed_setcodes: usize,
+        available_setcodes: usize,
+        priority_bits: u32,
+        id_bits: u32,
+    },
+    /// (bus, expected load) of every bus whose load exceeds 100%.
+    BusLoad(Vec<(String, f64)>),
+}
+
+impl CapacityError {
+    /// changes to the configuration that reduce the required capacity.
+    pub fn mitigations(&self) -> Vec<&'static str> {
+        match self {
+            CapacityError::IdSpace { .. } => vec![
+                "assign fixed extended ids to messages with rare receivers (MessageBuilder::set_ext_id)",
+                "fold receiver sets (NetworkBuilder::fold_receiver_sets)",
+                "merge streams with the same receivers (NetworkBuilder::enable_stream_merging)",
+            ],
+            CapacityError::BusLoad(_) => vec![
+                "add a bus",
+                "increase the baudrate of the bus",
+                "merge small streams (NetworkBuilder::enable_stream_merging)",
+                "increase the intervals of streams and messages",
+            ],
+        }
+    }
+}
+
+impl std::fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CapacityError::IdSpace {
+                needed_setcodes,
+                available_setcodes,
+                priority_bits,
+                id_bits,
+            } => write!(
+                f,
+                "the receiver sets need {needed_setcodes} setcodes, but {id_bits} bit ids with {priority_bits} priority bits only provide {available_setcodes}"
+            )?,
+            CapacityError::BusLoad(loads) => {
+                let loads: Vec<String> = loads
+                    .iter()
+                    .map(|(bus, load)| format!("{bus} {:.1}% (+{:.1}%)", load * 100.0, (load - 1.0) * 100.0))
+                    .collect();
+                write!(f, "the expected load exceeds the bandwidth of {}", loads.join(", "))?
+            }
+        }
+        write!(f, ", try to {}", self.mitigations().join(" or "))
+    }
+}
+
 impl From<std::io::Error> for ConfigError {
     fn from(value: std::io::Error) -> Self {
         ConfigError::Io(value)
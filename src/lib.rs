@@ -1,5 +1,10 @@
-
 pub mod errors;
+pub mod diagnostics;
 pub mod config;
 pub mod builder;
-
+pub mod simulator;
+mod codegen;
+#[cfg(feature = "macros")]
+mod macros;
+#[cfg(feature = "test-util")]
+pub mod test_util;
@@ -0,0 +1,322 @@
+// Declarative syntax for network configurations, every item expands to the
+// corresponding builder call. Unknown items are rejected by the macro
+// ("no rules expected the token ..."), with the span of the offending token.
+//
+// network! {
+//     bus can0: 1_000_000;
+//     node secu {
+//         bus: can0;
+//         object_entry position: "d16<0..10>" { unit: "m"; persistent; }
+//         stream state {
+//             interval: Duration::from_millis(10), Duration::from_millis(50);
+//             entries: [position];
+//         }
+//         command reset { argument level: "u8"; callees: [becu]; }
+//     }
+//     node becu {
+//         receive_stream secu.state { map: position => secu_position; }
+//     }
+//     message pdu_state (Duration::from_millis(100)) {
+//         bus: can0;
+//         std_id: 0x100;
+//         receivers: [becu];
+//         signals { state: SignalType::UnsignedInt { size: 8 }; }
+//     }
+// }
+
+/// builds a NetworkBuilder from a declarative description of buses,
+/// nodes and messages, evaluates to Result<NetworkBuilder>.
+#[macro_export]
+macro_rules! network {
+    ($($items:tt)*) => {
+        (|| -> $crate::errors::Result<$crate::builder::NetworkBuilder> {
+            let network_builder = $crate::builder::NetworkBuilder::new();
+            $crate::__network_items!(network_builder; $($items)*);
+            Ok(network_builder)
+        })()
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __network_items {
+    ($nb:ident;) => {};
+    ($nb:ident; bus $name:ident : $baudrate:expr; $($rest:tt)*) => {
+        $nb.create_bus(stringify!($name), Some($baudrate));
+        $crate::__network_items!($nb; $($rest)*);
+    };
+    ($nb:ident; bus $name:ident; $($rest:tt)*) => {
+        $nb.create_bus(stringify!($name), None);
+        $crate::__network_items!($nb; $($rest)*);
+    };
+    ($nb:ident; node $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        {
+            let node = $nb.create_node(stringify!($name));
+            $crate::__node_items!(node; $($body)*);
+        }
+        $crate::__network_items!($nb; $($rest)*);
+    };
+    ($nb:ident; message $name:ident ($interval:expr) { $($body:tt)* } $($rest:tt)*) => {
+        {
+            let message = $nb.create_message_with_interval(stringify!($name), $interval);
+            $crate::__message_items!(message; $($body)*);
+        }
+        $crate::__network_items!($nb; $($rest)*);
+    };
+    ($nb:ident; message $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        {
+            let message = $nb.create_message(stringify!($name));
+            $crate::__message_items!(message; $($body)*);
+        }
+        $crate::__network_items!($nb; $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __node_items {
+    ($node:ident;) => {};
+    ($node:ident; bus: $bus:ident; $($rest:tt)*) => {
+        $node.assign_bus(stringify!($bus));
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; fallback_bus: $bus:ident; $($rest:tt)*) => {
+        $node.set_fallback_bus(stringify!($bus));
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; description: $description:expr; $($rest:tt)*) => {
+        $node.add_description($description);
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; fault $name:ident : $severity:ident; $($rest:tt)*) => {
+        $node.define_fault(stringify!($name), $crate::config::FaultSeverity::$severity)?;
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; object_entry $name:ident : $ty:literal; $($rest:tt)*) => {
+        $node.create_object_entry(stringify!($name), $ty);
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; object_entry $name:ident : $ty:literal { $($body:tt)* } $($rest:tt)*) => {
+        {
+            let object_entry = $node.create_object_entry(stringify!($name), $ty);
+            $crate::__object_entry_items!(object_entry; $($body)*);
+        }
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; stream $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        {
+            let stream = $node.create_stream(stringify!($name));
+            $crate::__stream_items!(stream; $($body)*);
+        }
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; receive_stream $tx_node:ident . $stream:ident; $($rest:tt)*) => {
+        $node.receive_stream(stringify!($tx_node), stringify!($stream))?;
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; receive_stream $tx_node:ident . $stream:ident { $(map: $from:ident => $to:ident;)* } $($rest:tt)*) => {
+        {
+            let rx_stream = $node.receive_stream(stringify!($tx_node), stringify!($stream))?;
+            $(rx_stream.map(stringify!($from), stringify!($to));)*
+        }
+        $crate::__node_items!($node; $($rest)*);
+    };
+    ($node:ident; command $name:ident { $($body:tt)* } $($rest:tt)*) => {
+        {
+            let command = $node.create_command(stringify!($name), None);
+            $crate::__command_items!(command; $($body)*);
+        }
+        $crate::__node_items!($node; $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __object_entry_items {
+    ($oe:ident;) => {};
+    ($oe:ident; description: $description:expr; $($rest:tt)*) => {
+        $oe.add_description($description);
+        $crate::__object_entry_items!($oe; $($rest)*);
+    };
+    ($oe:ident; unit: $unit:expr; $($rest:tt)*) => {
+        $oe.add_unit($unit);
+        $crate::__object_entry_items!($oe; $($rest)*);
+    };
+    ($oe:ident; bounds: $min:expr, $max:expr; $($rest:tt)*) => {
+        $oe.set_bounds($min, $max);
+        $crate::__object_entry_items!($oe; $($rest)*);
+    };
+    ($oe:ident; access: $access:ident; $($rest:tt)*) => {
+        $oe.set_access($crate::config::ObjectEntryAccess::$access);
+        $crate::__object_entry_items!($oe; $($rest)*);
+    };
+    ($oe:ident; persistent; $($rest:tt)*) => {
+        $oe.persistent();
+        $crate::__object_entry_items!($oe; $($rest)*);
+    };
+    ($oe:ident; hide; $($rest:tt)*) => {
+        $oe.hide();
+        $crate::__object_entry_items!($oe; $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stream_items {
+    ($stream:ident;) => {};
+    ($stream:ident; description: $description:expr; $($rest:tt)*) => {
+        $stream.add_description($description);
+        $crate::__stream_items!($stream; $($rest)*);
+    };
+    ($stream:ident; interval: $min:expr, $max:expr; $($rest:tt)*) => {
+        $stream.set_interval($min, $max);
+        $crate::__stream_items!($stream; $($rest)*);
+    };
+    ($stream:ident; priority: $priority:ident; $($rest:tt)*) => {
+        $stream.set_priority($crate::builder::MessagePriority::$priority);
+        $crate::__stream_items!($stream; $($rest)*);
+    };
+    ($stream:ident; entries: [$($entry:ident),* $(,)?]; $($rest:tt)*) => {
+        $($stream.add_entry(stringify!($entry));)*
+        $crate::__stream_items!($stream; $($rest)*);
+    };
+    ($stream:ident; delta_entry $name:ident : $width:expr, $rollover:ident; $($rest:tt)*) => {
+        $stream.add_delta_entry(
+            stringify!($name),
+            $width,
+            $crate::config::stream::DeltaRollover::$rollover,
+        );
+        $crate::__stream_items!($stream; $($rest)*);
+    };
+    ($stream:ident; hide; $($rest:tt)*) => {
+        $stream.hide();
+        $crate::__stream_items!($stream; $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __command_items {
+    ($command:ident;) => {};
+    ($command:ident; description: $description:expr; $($rest:tt)*) => {
+        $command.add_description($description);
+        $crate::__command_items!($command; $($rest)*);
+    };
+    ($command:ident; interval: $interval:expr; $($rest:tt)*) => {
+        $command.expected_interval($interval);
+        $crate::__command_items!($command; $($rest)*);
+    };
+    ($command:ident; rate: $rate:expr; $($rest:tt)*) => {
+        $command.set_expected_rate($rate);
+        $crate::__command_items!($command; $($rest)*);
+    };
+    ($command:ident; burst: $calls:expr; $($rest:tt)*) => {
+        $command.set_burst($calls);
+        $crate::__command_items!($command; $($rest)*);
+    };
+    ($command:ident; priority: $priority:ident; $($rest:tt)*) => {
+        $command.set_priority($crate::builder::MessagePriority::$priority);
+        $crate::__command_items!($command; $($rest)*);
+    };
+    ($command:ident; argument $name:ident : $ty:literal; $($rest:tt)*) => {
+        $command.add_argument(stringify!($name), $ty);
+        $crate::__command_items!($command; $($rest)*);
+    };
+    ($command:ident; callees: [$($callee:ident),* $(,)?]; $($rest:tt)*) => {
+        $($command.add_callee(stringify!($callee))?;)*
+        $crate::__command_items!($command; $($rest)*);
+    };
+    ($command:ident; hide; $($rest:tt)*) => {
+        $command.hide();
+        $crate::__command_items!($command; $($rest)*);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __message_items {
+    ($message:ident;) => {};
+    ($message:ident; bus: $bus:ident; $($rest:tt)*) => {
+        $message.assign_bus(stringify!($bus));
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; fallback_bus: $bus:ident; $($rest:tt)*) => {
+        $message.set_fallback_bus(stringify!($bus));
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; description: $description:expr; $($rest:tt)*) => {
+        $message.add_description($description);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; std_id: $id:expr; $($rest:tt)*) => {
+        $message.set_std_id($id);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; ext_id: $id:expr; $($rest:tt)*) => {
+        $message.set_ext_id($id);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; priority: $priority:ident; $($rest:tt)*) => {
+        $message.set_any_std_id($crate::builder::MessagePriority::$priority);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; ext_priority: $priority:ident; $($rest:tt)*) => {
+        $message.set_any_ext_id($crate::builder::MessagePriority::$priority);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; version: $version:expr; $($rest:tt)*) => {
+        $message.set_version($version);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; author: $author:expr; $($rest:tt)*) => {
+        $message.set_author($author);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; requirement: $requirement:expr; $($rest:tt)*) => {
+        $message.set_requirement($requirement);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; revision_note: $note:expr; $($rest:tt)*) => {
+        $message.set_revision_note($note);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; receivers: [$($node:ident),* $(,)?]; $($rest:tt)*) => {
+        $($message.add_receiver(stringify!($node));)*
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; transmitters: [$($node:ident),* $(,)?]; $($rest:tt)*) => {
+        $($message.add_transmitter(stringify!($node));)*
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; burst: $max_frames:expr, $window:expr; $($rest:tt)*) => {
+        $message.set_burst($max_frames, $window);
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; external; $($rest:tt)*) => {
+        $message.mark_external();
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; hide; $($rest:tt)*) => {
+        $message.hide();
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; signals { $($name:ident : $ty:expr;)* } $($rest:tt)*) => {
+        {
+            let signal_format = $message.make_signal_format();
+            $(signal_format.add_signal($crate::config::signal::Signal::new(
+                stringify!($name),
+                None,
+                $ty,
+                0,
+            ))?;)*
+        }
+        $crate::__message_items!($message; $($rest)*);
+    };
+    ($message:ident; types { $($name:ident : $ty:literal;)* } $($rest:tt)*) => {
+        {
+            let type_format = $message.make_type_format();
+            $(type_format.add_type($ty, stringify!($name));)*
+        }
+        $crate::__message_items!($message; $($rest)*);
+    };
+}
@@ -0,0 +1,379 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{
+    builder::{hardware_filters, on_node_buses, MessagePriority},
+    config::{
+        bus::BusRef, message::MessageUsage, FrameTimeModel, Message, MessageId, MessageRef, Network,
+        NodeRef,
+    },
+};
+
+// Simulates the cyclic traffic of a built network.
+// All messages are released synchronously at t=0 (critical instant) and then
+// periodically with their expected interval. Frames are arbitrated like on
+// a real CAN bus (lowest id wins, std before ext on the same base id) and are
+// never preempted once the transmission started. Messages with bursts release
+// a full burst at the start of every burst window, if that is more frequent
+// than their interval (worst case).
+
+pub struct SimulationOptions {
+    pub duration: Duration,
+}
+
+impl Default for SimulationOptions {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(1),
+        }
+    }
+}
+
+pub struct MessageReport {
+    message: MessageRef,
+    frame_count: usize,
+    overrun_count: usize,
+    worst_queuing_delay: Duration,
+}
+
+impl MessageReport {
+    pub fn message(&self) -> &MessageRef {
+        &self.message
+    }
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+    /// number of times a frame was released before the previous
+    /// frame of the same message was transmitted.
+    pub fn overrun_count(&self) -> usize {
+        self.overrun_count
+    }
+    /// time between the release of a frame and the start of its transmission.
+    pub fn worst_queuing_delay(&self) -> &Duration {
+        &self.worst_queuing_delay
+    }
+}
+
+pub struct BusReport {
+    bus: BusRef,
+    load: f64,
+    frame_count: usize,
+    messages: Vec<MessageReport>,
+}
+
+impl BusReport {
+    pub fn bus(&self) -> &BusRef {
+        &self.bus
+    }
+    /// fraction of the simulated time the bus was busy.
+    pub fn load(&self) -> f64 {
+        self.load
+    }
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+    /// sorted by arbitration priority (highest priority first).
+    pub fn messages(&self) -> &Vec<MessageReport> {
+        &self.messages
+    }
+    pub fn worst_queuing_delay(&self) -> Duration {
+        self.messages
+            .iter()
+            .map(|m| m.worst_queuing_delay)
+            .max()
+            .unwrap_or_default()
+    }
+    /// worst queuing delay of the messages of each priority bucket they
+    /// landed in (highest priority first), buckets without messages on the
+    /// bus and messages with fixed ids are left out.
+    pub fn worst_queuing_delay_by_priority(&self) -> Vec<(MessagePriority, Duration)> {
+        MessagePriority::ALL
+            .iter()
+            .filter_map(|priority| {
+                self.messages
+                    .iter()
+                    .filter(|m| m.message.resolution_info().priority_bucket() == Some(priority.to_u32()))
+                    .map(|m| m.worst_queuing_delay)
+                    .max()
+                    .map(|delay| (*priority, delay))
+            })
+            .collect()
+    }
+}
+
+pub struct FilterReport {
+    node: NodeRef,
+    hit_count: usize,
+    rejected: Vec<MessageRef>,
+}
+
+impl FilterReport {
+    pub fn node(&self) -> &NodeRef {
+        &self.node
+    }
+    /// number of frames on the buses of the node, that passed
+    /// its hardware filters (including software filtered frames).
+    pub fn hit_count(&self) -> usize {
+        self.hit_count
+    }
+    /// rx messages of the node that its hardware filters reject,
+    /// empty for a consistent filter configuration.
+    pub fn rejected(&self) -> &Vec<MessageRef> {
+        &self.rejected
+    }
+}
+
+pub struct SimulationReport {
+    duration: Duration,
+    buses: Vec<BusReport>,
+    filters: Vec<FilterReport>,
+}
+
+impl SimulationReport {
+    pub fn duration(&self) -> &Duration {
+        &self.duration
+    }
+    pub fn buses(&self) -> &Vec<BusReport> {
+        &self.buses
+    }
+    pub fn filters(&self) -> &Vec<FilterReport> {
+        &self.filters
+    }
+    pub fn message(&self, name: &str) -> Option<&MessageReport> {
+        self.buses
+            .iter()
+            .flat_map(|bus| bus.messages.iter())
+            .find(|m| m.message.name() == name)
+    }
+}
+
+pub fn message_interval(message: &Message) -> Duration {
+    match message.usage() {
+        MessageUsage::Stream(stream) => *stream.max_interval(),
+        MessageUsage::CommandReq(command) | MessageUsage::CommandResp(command) => {
+            command.frame_interval()
+        }
+        MessageUsage::GetResp
+        | MessageUsage::GetReq
+        | MessageUsage::SetResp
+        | MessageUsage::SetReq
+        | MessageUsage::Heartbeat => Duration::from_millis(100),
+        MessageUsage::External { interval } => *interval,
+    }
+}
+
+/// (period, frames) of the worst-case release pattern: the message releases
+/// frames back to back every period. Without a burst (or if the burst is
+/// less frequent than the interval) a single frame is released per interval.
+pub fn worst_case_release(message: &Message) -> (Duration, u32) {
+    let interval = message_interval(message);
+    match message.burst() {
+        Some(burst)
+            if burst.max_frames() as f64 / burst.window().as_secs_f64()
+                > 1.0 / interval.as_secs_f64() =>
+        {
+            (*burst.window(), burst.max_frames())
+        }
+        _ => (interval, 1),
+    }
+}
+
+/// worst case length of a classic frame, see FrameTimeModel.
+pub fn frame_bits(id: &MessageId, dlc: u8) -> u64 {
+    FrameTimeModel::default().frame_bits(id.ide(), dlc as usize)
+}
+
+// lower keys win the arbitration.
+pub(crate) fn arbitration_key(id: &MessageId) -> u64 {
+    match id {
+        MessageId::StandardId(id) => (*id as u64) << 19,
+        MessageId::ExtendedId(id) => {
+            let id = *id as u64;
+            ((id >> 18) << 19) | (1 << 18) | (id & 0x3FFFF)
+        }
+    }
+}
+
+/// worst-case time between the release of a frame and the end of its
+/// transmission, from the response time analysis of Davis et al. (2007)
+/// for a single instance: blocking by the longest lower priority frame
+/// plus the interference of all higher priority frames on the bus.
+/// For messages with bursts it's the response time of the last frame of a
+/// burst, see worst_case_release.
+/// None if the load of the frames with a higher priority is at least 1,
+/// in which case the frame might never be transmitted.
+pub fn worst_case_response_time(network: &Network, message: &Message) -> Option<Duration> {
+    let (_, own_frames) = worst_case_release(message);
+    response_time(network, message, own_frames)
+}
+
+/// worst-case response time of the last of frames, that are released
+/// back to back (e.g. the responses of a pipelined command).
+pub fn worst_case_burst_response_time(network: &Network, message: &Message, frames: u32) -> Option<Duration> {
+    response_time(network, message, frames.max(1))
+}
+
+fn response_time(network: &Network, message: &Message, own_frames: u32) -> Option<Duration> {
+    let bus = message.bus();
+    let key = arbitration_key(message.id());
+    let frame_time = |m: &Message| bus.frame_time(network.frame_time_model(), m).as_secs_f64();
+    let on_bus = network
+        .messages()
+        .iter()
+        .filter(|m| m.bus().id() == bus.id() && m.name() != message.name());
+    let blocking = on_bus
+        .clone()
+        .filter(|m| arbitration_key(m.id()) > key)
+        .map(|m| frame_time(m))
+        .fold(0.0, f64::max);
+    // (frame time, period, frames per period)
+    let higher_priority: Vec<(f64, f64, f64)> = on_bus
+        .filter(|m| arbitration_key(m.id()) < key)
+        .map(|m| {
+            let (period, frames) = worst_case_release(m);
+            (frame_time(m), period.as_secs_f64(), frames as f64)
+        })
+        .collect();
+    let load: f64 = higher_priority.iter().map(|(c, t, n)| n * c / t).sum();
+    if load >= 1.0 {
+        return None;
+    }
+    // the previous frames of the own burst are sent first.
+    let blocking = blocking + (own_frames - 1) as f64 * frame_time(message);
+    let bit_time = 1.0 / bus.baudrate() as f64;
+    let mut queuing_delay = blocking;
+    loop {
+        let next = blocking
+            + higher_priority
+                .iter()
+                .map(|(c, t, n)| ((queuing_delay + bit_time) / t).ceil() * n * c)
+                .sum::<f64>();
+        if next <= queuing_delay {
+            break;
+        }
+        queuing_delay = next;
+    }
+    Some(Duration::from_secs_f64(queuing_delay + frame_time(message)))
+}
+
+struct SimulatedMessage {
+    key: u64,
+    period_ns: u64,
+    frames_per_release: u32,
+    tx_time_ns: u64,
+    next_release_ns: u64,
+    // release times of the frames, that wait for transmission.
+    pending: VecDeque<u64>,
+    report: MessageReport,
+}
+
+fn simulate_bus(network: &Network, bus: &BusRef, duration_ns: u64) -> BusReport {
+    let mut messages: Vec<SimulatedMessage> = network
+        .messages()
+        .iter()
+        .filter(|m| m.bus().id() == bus.id())
+        .map(|m| {
+            let (period, frames_per_release) = worst_case_release(m);
+            SimulatedMessage {
+                key: arbitration_key(m.id()),
+                period_ns: (period.as_nanos() as u64).max(1),
+                frames_per_release,
+                tx_time_ns: bus.frame_time(network.frame_time_model(), m).as_nanos() as u64,
+                next_release_ns: 0,
+                pending: VecDeque::new(),
+                report: MessageReport {
+                    message: m.clone(),
+                    frame_count: 0,
+                    overrun_count: 0,
+                    worst_queuing_delay: Duration::ZERO,
+                },
+            }
+        })
+        .collect();
+    messages.sort_by_key(|m| m.key);
+
+    let mut t = 0u64;
+    let mut busy_ns = 0u64;
+    let mut frame_count = 0usize;
+    while t < duration_ns {
+        for msg in messages.iter_mut() {
+            while msg.next_release_ns <= t {
+                if !msg.pending.is_empty() {
+                    msg.report.overrun_count += 1;
+                }
+                // frames of an overrun release are merged with the pending ones.
+                while msg.pending.len() < msg.frames_per_release as usize {
+                    msg.pending.push_back(msg.next_release_ns);
+                }
+                msg.next_release_ns += msg.period_ns;
+            }
+        }
+        // messages are sorted by arbitration key, the first pending frame wins.
+        match messages.iter_mut().find(|m| !m.pending.is_empty()) {
+            Some(msg) => {
+                let released = msg.pending.pop_front().unwrap();
+                let delay = Duration::from_nanos(t - released);
+                msg.report.worst_queuing_delay = msg.report.worst_queuing_delay.max(delay);
+                msg.report.frame_count += 1;
+                frame_count += 1;
+                let end = (t + msg.tx_time_ns).min(duration_ns);
+                busy_ns += end - t;
+                t += msg.tx_time_ns;
+            }
+            None => {
+                t = messages
+                    .iter()
+                    .map(|m| m.next_release_ns)
+                    .min()
+                    .unwrap_or(duration_ns);
+            }
+        }
+    }
+
+    BusReport {
+        bus: bus.clone(),
+        load: busy_ns as f64 / duration_ns as f64,
+        frame_count,
+        messages: messages.into_iter().map(|m| m.report).collect(),
+    }
+}
+
+pub fn simulate(network: &Network, options: &SimulationOptions) -> SimulationReport {
+    let duration_ns = (options.duration.as_nanos() as u64).max(1);
+    let buses: Vec<BusReport> = network
+        .buses()
+        .iter()
+        .map(|bus| simulate_bus(network, bus, duration_ns))
+        .collect();
+
+    let filters = network
+        .nodes()
+        .iter()
+        .map(|node| {
+            let filters = hardware_filters(node);
+            let accepts = |message: &MessageRef| filters.iter().any(|f| f.accepts_id(message.id()));
+            let hit_count = buses
+                .iter()
+                .flat_map(|bus| bus.messages.iter())
+                .filter(|m| on_node_buses(node, &m.message) && accepts(&m.message))
+                .map(|m| m.frame_count)
+                .sum();
+            let rejected = node
+                .rx_messages()
+                .iter()
+                .filter(|message| !accepts(message))
+                .cloned()
+                .collect();
+            FilterReport {
+                node: node.clone(),
+                hit_count,
+                rejected,
+            }
+        })
+        .collect();
+
+    SimulationReport {
+        duration: options.duration,
+        buses,
+        filters,
+    }
+}
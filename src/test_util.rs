@@ -0,0 +1,93 @@
+use crate::{
+    config::{assignment::Assignment, NodeFilters},
+    errors::{self, Result},
+};
+
+// Fault injection for the verification of assignments (see
+// Assignment::verify). Perturbations model realistic corruption of a
+// generated configuration, e.g. a stale id table or a filter bank that
+// wasn't written. Only available with the test-util feature, for the tests
+// of this crate and HIL tests of firmware that embeds a configuration.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Perturbation {
+    /// swaps the ids of two messages (by name).
+    SwapIds(String, String),
+    /// removes the filter with the index from the filters of the node.
+    DropFilter { node: String, index: usize },
+}
+
+fn message_index(assignment: &Assignment, name: &str) -> Result<usize> {
+    assignment
+        .messages
+        .iter()
+        .position(|message| message.name() == name)
+        .ok_or_else(|| {
+            errors::ConfigError::InvalidFaultInjection(format!("message {name} is not part of the assignment"))
+        })
+}
+
+impl Assignment {
+    /// applies the perturbation in place, fails if it refers to messages,
+    /// nodes or filters that don't exist.
+    pub fn perturb(&mut self, perturbation: &Perturbation) -> Result<()> {
+        match perturbation {
+            Perturbation::SwapIds(a, b) => {
+                let (a, b) = (message_index(self, a)?, message_index(self, b)?);
+                let id = self.messages[a].id;
+                self.messages[a].id = self.messages[b].id;
+                self.messages[b].id = id;
+            }
+            Perturbation::DropFilter { node, index } => {
+                let node_filters = self
+                    .filters
+                    .iter_mut()
+                    .find(|node_filters| node_filters.node().name() == node)
+                    .ok_or_else(|| {
+                        errors::ConfigError::InvalidFaultInjection(format!("node {node} is not part of the assignment"))
+                    })?;
+                if *index >= node_filters.filters().len() {
+                    return Err(errors::ConfigError::InvalidFaultInjection(format!(
+                        "node {node} has {} filters, can't drop filter {index}",
+                        node_filters.filters().len()
+                    )));
+                }
+                let mut filters = node_filters.filters().clone();
+                filters.remove(*index);
+                *node_filters = NodeFilters::new(
+                    node_filters.node().clone(),
+                    filters,
+                    node_filters.software_filters().clone(),
+                );
+            }
+        }
+        Ok(())
+    }
+    /// copy of the assignment with the perturbation applied.
+    pub fn perturbed(&self, perturbation: &Perturbation) -> Result<Assignment> {
+        let mut assignment = self.clone();
+        assignment.perturb(perturbation)?;
+        Ok(assignment)
+    }
+    /// every single perturbation of the assignment: all swaps of two messages
+    /// on the same bus with different ids and all dropped filters.
+    pub fn perturbations(&self) -> Vec<Perturbation> {
+        let mut perturbations = vec![];
+        for (i, a) in self.messages.iter().enumerate() {
+            for b in &self.messages[i + 1..] {
+                if a.bus() == b.bus() && a.id() != b.id() {
+                    perturbations.push(Perturbation::SwapIds(a.name().to_owned(), b.name().to_owned()));
+                }
+            }
+        }
+        for node_filters in &self.filters {
+            for index in 0..node_filters.filters().len() {
+                perturbations.push(Perturbation::DropFilter {
+                    node: node_filters.node().name().to_owned(),
+                    index,
+                });
+            }
+        }
+        perturbations
+    }
+}
@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{message::MessageUsage, NetworkRef},
+};
+
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let state = network_builder.define_enum("motor_state");
+    state.add_entry("idle", None).unwrap();
+    state.add_entry("running", None).unwrap();
+    state.annotate("owner", "drive-team");
+    let limits = network_builder.define_struct("limits");
+    limits.add_attribute("min", "u8").unwrap();
+    limits.add_attribute("max", "u8").unwrap();
+    limits.annotate("owner", "safety");
+
+    let motor = network_builder.create_node("motor");
+    motor.annotate("ecu", "stm32g4");
+    let master = network_builder.create_node("master");
+    motor.create_object_entry("state", "motor_state");
+    master.create_object_entry("motor_state", "motor_state");
+
+    let stream = motor.create_stream("state");
+    stream.set_interval(Duration::from_millis(10), Duration::from_millis(100));
+    stream.add_entry("state");
+    stream.annotate("logging", "always");
+    master.receive_stream("motor", "state").unwrap().map("state", "motor_state");
+
+    let command = motor.create_command("reset", None);
+    command.add_callee("master").unwrap();
+    command.annotate("safety", "requires_standstill");
+
+    let message = network_builder.create_message_with_interval("limits", Duration::from_millis(50));
+    message.add_transmitter("master");
+    message.add_receiver("motor");
+    message.make_type_format().add_type("limits", "value");
+    message.annotate("owner", "safety");
+
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn annotations_are_preserved_by_build() {
+    let network = build_network();
+    let motor = network.nodes().iter().find(|n| n.name() == "motor").unwrap();
+    assert_eq!(motor.annotations().get("ecu").map(String::as_str), Some("stm32g4"));
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert!(master.annotations().is_empty());
+
+    let stream = motor.tx_streams().iter().find(|s| s.name() == "state").unwrap();
+    assert_eq!(stream.annotations().get("logging").map(String::as_str), Some("always"));
+    let rx_stream = master.rx_streams().iter().find(|s| s.name() == "state").unwrap();
+    assert_eq!(rx_stream.annotations(), stream.annotations());
+
+    let command = motor.commands().iter().find(|c| c.name() == "reset").unwrap();
+    assert_eq!(
+        command.annotations().get("safety").map(String::as_str),
+        Some("requires_standstill")
+    );
+
+    let message = network.messages().iter().find(|m| m.name() == "limits").unwrap();
+    assert_eq!(message.annotations().get("owner").map(String::as_str), Some("safety"));
+    assert!(matches!(message.usage(), MessageUsage::External { .. }));
+
+    for (ty, owner) in [("motor_state", "drive-team"), ("limits", "safety")] {
+        let ty = network.types().iter().find(|t| t.name() == ty).unwrap();
+        assert_eq!(ty.annotations().get("owner").map(String::as_str), Some(owner));
+    }
+}
+
+#[test]
+fn annotations_are_exported_to_json() {
+    let json = build_network().to_json();
+    assert!(json.contains("\"ecu\": \"stm32g4\""));
+    assert!(json.contains("\"logging\": \"always\""));
+    assert!(json.contains("\"safety\": \"requires_standstill\""));
+    assert!(json.contains("\"owner\": \"drive-team\""));
+}
+
+#[test]
+fn annotations_are_exported_as_dbc_attributes() {
+    let network = build_network();
+    let dbc = network.generate_dbc();
+    assert!(dbc.contains("BA_DEF_ BU_ \"ecu\" STRING ;"));
+    assert!(dbc.contains("BA_DEF_ BO_ \"owner\" STRING ;"));
+    assert!(dbc.contains("BA_DEF_DEF_ \"owner\" \"\";"));
+    assert!(dbc.contains("BA_ \"ecu\" BU_ motor \"stm32g4\";"));
+    let message = network.messages().iter().find(|m| m.name() == "limits").unwrap();
+    assert!(dbc.contains(&format!("BA_ \"owner\" BO_ {} \"safety\";", message.id().as_u32())));
+}
+
+#[test]
+fn annotations_are_not_part_of_the_hash() {
+    let annotated_builder = NetworkBuilder::new();
+    annotated_builder.create_bus("can0", None);
+    annotated_builder.create_node("motor").annotate("ecu", "stm32g4");
+    let plain_builder = NetworkBuilder::new();
+    plain_builder.create_bus("can0", None);
+    plain_builder.create_node("motor");
+    assert_eq!(
+        annotated_builder.build().unwrap().portable_hash(),
+        plain_builder.build().unwrap().portable_hash()
+    );
+}
@@ -0,0 +1,117 @@
+use std::path::PathBuf;
+
+use canzero_config::{
+    builder::{bus_balancing::PinnedBalancer, MessagePriority, NetworkBuilder},
+    config::NetworkRef,
+};
+
+fn cache_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("canzero_artifact_cache_test_{name}"));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn network_builder(receivers: &[&str]) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    for node in ["becu", "secu", "master"] {
+        network_builder.create_node(node);
+    }
+    for i in 0..8 {
+        let message = network_builder.create_message(&format!("status{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_transmitter("becu");
+        for receiver in receivers {
+            message.add_receiver(receiver);
+        }
+    }
+    network_builder
+}
+
+// the build time is the only difference between two builds of the same network.
+fn json(network: &NetworkRef) -> String {
+    network
+        .to_json()
+        .lines()
+        .filter(|line| !line.contains("\"build_time\""))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn message_id(network: &NetworkRef, name: &str) -> u32 {
+    network
+        .messages()
+        .iter()
+        .find(|message| message.name() == name)
+        .unwrap()
+        .id()
+        .as_u32()
+}
+
+#[test]
+fn cached_builds_equal_fresh_builds() {
+    let dir = cache_dir("equal");
+    let fresh = network_builder(&["secu"]).build().unwrap();
+    let stored = network_builder(&["secu"]).build_with_cache(&dir).unwrap();
+    assert!(dir.join("canzero-resolution.cache").exists());
+    let reused = network_builder(&["secu"]).build_with_cache(&dir).unwrap();
+    assert_eq!(json(&fresh), json(&stored));
+    assert_eq!(json(&fresh), json(&reused));
+}
+
+#[test]
+fn unchanged_builders_reuse_the_stored_assignment() {
+    let dir = cache_dir("reuse");
+    let network = network_builder(&["secu"]).build_with_cache(&dir).unwrap();
+    let id = message_id(&network, "status0");
+    // the cache is trusted, an edited id shows that the resolution was skipped.
+    let cache_file = dir.join("canzero-resolution.cache");
+    let cache = std::fs::read_to_string(&cache_file).unwrap();
+    let edited = cache.replace(&format!("message status0 std {id} "), "message status0 std 2000 ");
+    assert_ne!(cache, edited);
+    std::fs::write(&cache_file, edited).unwrap();
+    let network_builder = network_builder(&["secu"]);
+    network_builder.create_node("secu").create_object_entry("pressure", "u16");
+    let network = network_builder.build_with_cache(&dir).unwrap();
+    assert_eq!(message_id(&network, "status0"), 2000);
+}
+
+#[test]
+fn changed_inputs_invalidate_the_cache() {
+    let dir = cache_dir("invalidate");
+    network_builder(&["secu"]).build_with_cache(&dir).unwrap();
+    let cache = std::fs::read_to_string(dir.join("canzero-resolution.cache")).unwrap();
+    let network = network_builder(&["secu", "master"]).build_with_cache(&dir).unwrap();
+    let fresh = network_builder(&["secu", "master"]).build().unwrap();
+    assert_eq!(json(&network), json(&fresh));
+    assert_ne!(std::fs::read_to_string(dir.join("canzero-resolution.cache")).unwrap(), cache);
+    std::fs::write(dir.join("canzero-resolution.cache"), "# canzero resolution cache\ngarbage").unwrap();
+    let network = network_builder(&["secu", "master"]).build_with_cache(&dir).unwrap();
+    assert_eq!(json(&network), json(&fresh));
+}
+
+fn fingerprint(dir: &std::path::Path) -> String {
+    let cache = std::fs::read_to_string(dir.join("canzero-resolution.cache")).unwrap();
+    cache.lines().find(|line| line.starts_with("fingerprint ")).unwrap().to_owned()
+}
+
+#[test]
+fn pinned_balancer_fingerprint_is_stable() {
+    let pins = [("becu", "can0"), ("secu", "can1"), ("master", "can0"), ("tcu", "can1")];
+    let mut fingerprints = vec![];
+    for (i, order) in [[0, 1, 2, 3], [3, 2, 1, 0], [2, 0, 3, 1]].iter().enumerate() {
+        let network_builder = network_builder(&["secu"]);
+        let mut balancer = PinnedBalancer::new();
+        for index in order {
+            let (node, bus) = pins[*index];
+            balancer = balancer.pin(node, bus);
+        }
+        network_builder.set_bus_balancer(balancer);
+        let dir = cache_dir(&format!("pinned_balancer_{i}"));
+        network_builder.build_with_cache(&dir).unwrap();
+        fingerprints.push(fingerprint(&dir));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+    assert!(fingerprints.windows(2).all(|pair| pair[0] == pair[1]));
+}
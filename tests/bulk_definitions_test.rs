@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+#[test]
+fn object_entries_are_created_from_a_pattern() {
+    let network_builder = NetworkBuilder::new();
+    let bms = network_builder.create_node("bms");
+    let cells = bms.create_object_entries("cell_voltage_{}", 0..96, "d16<0..5>");
+    assert_eq!(cells.len(), 96);
+    assert_eq!(cells[0].0.borrow().name, "cell_voltage_0");
+    assert_eq!(cells[95].0.borrow().name, "cell_voltage_95");
+    // entries that exist already are reused.
+    let again = bms.create_object_entries("cell_voltage_{}", 90..100, "d16<0..5>");
+    assert!(std::rc::Rc::ptr_eq(&again[0].0, &cells[90].0));
+    let network = network_builder.build().unwrap();
+    let bms = network.nodes().iter().find(|n| n.name() == "bms").unwrap();
+    assert_eq!(
+        bms.object_entries()
+            .iter()
+            .filter(|oe| oe.name().starts_with("cell_voltage_"))
+            .count(),
+        100
+    );
+}
+
+#[test]
+fn stream_entries_are_split_into_continuation_streams() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entries("cell_voltage_{}", 0..10, "d16<0..5>");
+    let stream = bms.create_stream("cells");
+    stream.set_interval(Duration::from_millis(10), Duration::from_millis(100));
+    stream.set_priority(MessagePriority::Low);
+    stream.add_tag("bms");
+    let streams = stream.add_entries("cell_voltage_{}", 0..10);
+    let names: Vec<String> = streams.iter().map(|s| s.0.borrow().name.clone()).collect();
+    assert_eq!(names, vec!["cells", "cells_1", "cells_2"]);
+    for stream in &streams {
+        master.receive_stream("bms", &stream.0.borrow().name).unwrap();
+    }
+    let network = network_builder.build().unwrap();
+    let bms = network.nodes().iter().find(|n| n.name() == "bms").unwrap();
+    let entry_counts: Vec<usize> = ["cells", "cells_1", "cells_2"]
+        .iter()
+        .map(|name| {
+            let stream = bms.tx_streams().iter().find(|s| s.name() == *name).unwrap();
+            assert_eq!(stream.message().dlc() as usize, stream.mapping().len() * 2);
+            stream.mapping().len()
+        })
+        .collect();
+    assert_eq!(entry_counts, vec![4, 4, 2]);
+    let first = bms.tx_streams().iter().find(|s| s.name() == "cells").unwrap();
+    let last = bms.tx_streams().iter().find(|s| s.name() == "cells_2").unwrap();
+    assert_eq!(first.max_interval(), last.max_interval());
+}
+
+#[test]
+fn headroom_and_existing_entries_are_respected() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.set_stream_headroom(16);
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entries("temperature_{}", 0..7, "u8");
+    let stream = bms.create_stream("temperatures");
+    stream.add_entry("temperature_0");
+    // 48 bits remain for the entries, temperature_0 isn't added twice.
+    let streams = stream.add_entries("temperature_{}", 0..7);
+    assert_eq!(streams.len(), 2);
+    assert_eq!(streams[0].0.borrow().object_entries.len(), 6);
+    assert_eq!(streams[1].0.borrow().object_entries.len(), 1);
+    // adding more entries continues in the last continuation stream.
+    bms.create_object_entries("pressure_{}", 0..2, "u16");
+    let streams = stream.add_entries("pressure_{}", 0..2);
+    let names: Vec<String> = streams.iter().map(|s| s.0.borrow().name.clone()).collect();
+    assert_eq!(names, vec!["temperatures", "temperatures_1"]);
+    assert_eq!(streams[1].0.borrow().object_entries.len(), 3);
+}
+
+#[test]
+fn user_defined_types_are_sized_before_build() {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("pump_state");
+    for entry in ["Off", "Starting", "Running", "Stopping", "Error"] {
+        state.add_entry(entry, None).unwrap();
+    }
+    let pump = network_builder.define_struct("pump");
+    pump.add_attribute("state", "pump_state").unwrap();
+    pump.add_attribute("pressure", "u13").unwrap();
+    let cooling = network_builder.create_node("cooling");
+    cooling.create_object_entries("pump_{}", 0..5, "pump");
+    // 3 + 13 bits per pump, 4 pumps per frame.
+    let streams = cooling.create_stream("pumps").add_entries("pump_{}", 0..5);
+    assert_eq!(streams.len(), 2);
+    assert_eq!(streams[0].0.borrow().object_entries.len(), 4);
+}
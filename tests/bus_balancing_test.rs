@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{
+        bus_balancing::{BalancingBus, BalancingMessage, BusBalancer, GreedyBalancer, LocalityBalancer, PinnedBalancer},
+        NetworkBuilder,
+    },
+    config::Network,
+    errors::{ConfigError, Result},
+};
+
+// a sensor and an actuator that exchange fast streams, next to a
+// few unrelated nodes that only talk to the master.
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    let master = network_builder.create_node("master");
+    master.assign_bus("can0");
+    master.assign_bus("can1");
+    let sensor = network_builder.create_node("sensor");
+    sensor.create_object_entry("position", "u32");
+    let position = sensor.create_stream("position");
+    position.add_entry("position");
+    position.set_interval(Duration::from_millis(1), Duration::from_millis(1));
+    let actuator = network_builder.create_node("actuator");
+    actuator.create_object_entry("torque", "u16");
+    let torque = actuator.create_stream("torque");
+    torque.add_entry("torque");
+    torque.set_interval(Duration::from_millis(2), Duration::from_millis(2));
+    actuator.receive_stream("sensor", "position").unwrap();
+    sensor.receive_stream("actuator", "torque").unwrap();
+    for name in ["bms", "pdu", "cooling"] {
+        let node = network_builder.create_node(name);
+        node.create_object_entry("state", "u8");
+        let state = node.create_stream("state");
+        state.add_entry("state");
+        state.set_interval(Duration::from_millis(5), Duration::from_millis(5));
+        master.receive_stream(name, "state").unwrap();
+    }
+    network_builder
+}
+
+fn stream_bus(network: &Network, node: &str, stream: &str) -> String {
+    let node = network.nodes().iter().find(|n| n.name() == node).unwrap();
+    let stream = node.tx_streams().iter().find(|s| s.name() == stream).unwrap();
+    stream.message().bus().name().to_owned()
+}
+
+fn buses(network: &Network) -> Vec<(String, String)> {
+    network
+        .messages()
+        .iter()
+        .map(|message| (message.name().to_owned(), message.bus().name().to_owned()))
+        .collect()
+}
+
+#[test]
+fn greedy_balancer_is_the_default() {
+    let default = network_builder().build().unwrap();
+    let network_builder = network_builder();
+    network_builder.set_bus_balancer(GreedyBalancer);
+    let greedy = network_builder.build().unwrap();
+    assert_eq!(buses(&default), buses(&greedy));
+}
+
+#[test]
+fn locality_balancer_keeps_coupled_nodes_on_one_bus() {
+    let network_builder = network_builder();
+    network_builder.set_bus_balancer(LocalityBalancer::new());
+    let network = network_builder.build().unwrap();
+    assert_eq!(
+        stream_bus(&network, "sensor", "position"),
+        stream_bus(&network, "actuator", "torque")
+    );
+}
+
+#[test]
+fn pinned_nodes_keep_their_messages_on_their_bus() {
+    let network_builder = network_builder();
+    network_builder.set_bus_balancer(PinnedBalancer::new().pin("sensor", "can1").pin("actuator", "can1"));
+    let network = network_builder.build().unwrap();
+    let sensor = network.nodes().iter().find(|n| n.name() == "sensor").unwrap();
+    assert!(sensor.tx_messages().iter().all(|message| message.bus().name() == "can1"));
+    assert_eq!(stream_bus(&network, "actuator", "torque"), "can1");
+    // the remaining messages are balanced onto both buses.
+    assert!(network.messages().iter().any(|message| message.bus().name() == "can0"));
+}
+
+#[test]
+fn pins_to_unknown_buses_are_rejected() {
+    let network_builder = network_builder();
+    network_builder.set_bus_balancer(PinnedBalancer::new().pin("sensor", "can7"));
+    let Err(ConfigError::InvalidBusBalancing(msg)) = network_builder.build() else {
+        panic!("expected an invalid bus balancing");
+    };
+    assert!(msg.contains("can7"), "{msg}");
+}
+
+#[derive(Debug)]
+struct FallbackBalancer;
+
+impl BusBalancer for FallbackBalancer {
+    fn balance(&self, _buses: &[BalancingBus], messages: &[BalancingMessage]) -> Result<Vec<usize>> {
+        Ok(messages.iter().map(|message| message.fallback_bus().unwrap_or(0)).collect())
+    }
+}
+
+#[test]
+fn balancers_are_validated() {
+    let network_builder = network_builder();
+    let gateway = network_builder.create_node("gateway");
+    gateway.set_fallback_bus("can0");
+    gateway.create_object_entry("status", "u8");
+    gateway.create_stream("status").add_entry("status");
+    network_builder.set_bus_balancer(FallbackBalancer);
+    let Err(ConfigError::InvalidBusBalancing(msg)) = network_builder.build() else {
+        panic!("expected an invalid bus balancing");
+    };
+    assert!(msg.contains("fallback bus can0"), "{msg}");
+}
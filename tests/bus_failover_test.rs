@@ -0,0 +1,62 @@
+use canzero_config::{builder::MessagePriority, errors::ConfigError};
+
+#[test]
+fn bus_failover_message_keeps_primary_bus() {
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    network_builder.create_node("becu");
+    for i in 0..8 {
+        let message = network_builder.create_message(&format!("brake_msg_{i}"));
+        message.set_any_std_id(MessagePriority::Realtime);
+        message.add_receiver("becu");
+        message.set_fallback_bus("can1");
+    }
+    let network = network_builder.build().unwrap();
+    for i in 0..8 {
+        let message = network
+            .messages()
+            .iter()
+            .find(|m| m.name() == format!("brake_msg_{i}"))
+            .unwrap();
+        let fallback_bus = message.fallback_bus().expect("fallback bus was not built");
+        assert_eq!(fallback_bus.name(), "can1");
+        assert_eq!(message.bus().name(), "can0");
+    }
+}
+
+#[test]
+fn bus_failover_inherited_from_node() {
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.set_fallback_bus("can1");
+    becu.create_object_entry("pressure", "u16");
+    let stream = becu.create_stream("pressure");
+    stream.add_entry("pressure");
+    let network = network_builder.build().unwrap();
+
+    let becu = network.nodes().iter().find(|n| n.name() == "becu").unwrap();
+    assert_eq!(becu.fallback_bus().unwrap().name(), "can1");
+    let stream_message = becu.tx_streams()[0].message();
+    assert_eq!(stream_message.fallback_bus().unwrap().name(), "can1");
+    assert_eq!(stream_message.bus().name(), "can0");
+}
+
+#[test]
+fn bus_failover_id_collision() {
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    let a = network_builder.create_message("a");
+    a.set_std_id(0x10);
+    a.assign_bus("can0");
+    a.set_fallback_bus("can1");
+    let b = network_builder.create_message("b");
+    b.set_std_id(0x10);
+    b.assign_bus("can1");
+    match network_builder.build() {
+        Err(ConfigError::InvalidFallbackBus(_)) => (),
+        other => panic!("expected a fallback bus collision, got {other:?}"),
+    }
+}
@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{
+        bus_balancing::{BusBalancer, GreedyBalancer, LocalityBalancer, PinnedBalancer},
+        MessageBuilder, NetworkBuilder,
+    },
+    diagnostics::{DiagnosticKind, Severity},
+    errors::ConfigError,
+};
+
+// heavy messages that the balancers would spread over both buses,
+// every third message is pinned to can1.
+fn network_builder() -> (NetworkBuilder, Vec<MessageBuilder>) {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    network_builder.create_node("sensor");
+    network_builder.create_node("actuator");
+    let mut messages = vec![];
+    for i in 0..12 {
+        let message = network_builder.create_message_with_interval(&format!("msg_{i}"), Duration::from_millis(1 + i));
+        message.add_transmitter("sensor");
+        message.add_receiver("actuator");
+        if i % 3 == 0 {
+            message.assign_bus("can1");
+        }
+        messages.push(message);
+    }
+    (network_builder, messages)
+}
+
+fn denied_bus_pins(network_builder: &NetworkBuilder) -> String {
+    match network_builder.build() {
+        Err(ConfigError::DeniedDiagnostics(msg)) => msg,
+        Err(err) => panic!("expected a denied bus pin, got {err:?}"),
+        Ok(_) => panic!("expected a denied bus pin"),
+    }
+}
+
+fn assert_pins_hold(bus_balancer: impl BusBalancer + 'static) {
+    let (network_builder, _) = network_builder();
+    network_builder.set_bus_balancer(bus_balancer);
+    let network = network_builder.build().unwrap();
+    for i in (0..12).step_by(3) {
+        let message = network.messages().iter().find(|m| m.name() == format!("msg_{i}")).unwrap();
+        assert_eq!(message.bus().name(), "can1", "msg_{i} was moved");
+    }
+    assert!(network.messages().iter().any(|m| m.bus().name() == "can0"));
+}
+
+#[test]
+fn pinned_messages_never_move() {
+    assert_pins_hold(GreedyBalancer);
+    assert_pins_hold(LocalityBalancer::new());
+    assert_pins_hold(PinnedBalancer::new().pin("sensor", "can0"));
+}
+
+#[test]
+fn conflicting_pins_are_denied() {
+    let (network_builder, messages) = network_builder();
+    messages[0].assign_bus("can0");
+    let msg = denied_bus_pins(&network_builder);
+    assert!(msg.contains("msg_0 is pinned to the buses can1 and can0"), "{msg}");
+}
+
+#[test]
+fn time_triggered_schedules_conflict_with_pins() {
+    let (network_builder, messages) = network_builder();
+    let schedule = network_builder.create_time_triggered_schedule("can0", Duration::from_millis(1), 1);
+    schedule.add_message(&messages[3]);
+    let msg = denied_bus_pins(&network_builder);
+    assert!(msg.contains("msg_3 is pinned to the buses can1 and can0"), "{msg}");
+}
+
+#[test]
+fn pins_to_unconnected_buses_are_denied() {
+    let (network_builder, _) = network_builder();
+    network_builder.create_node("sensor").assign_bus("can0");
+    let msg = denied_bus_pins(&network_builder);
+    assert!(msg.contains("its transmitter sensor is not connected to it"), "{msg}");
+}
+
+#[test]
+fn bus_pin_diagnostics_can_be_downgraded() {
+    let (network_builder, messages) = network_builder();
+    messages[0].assign_bus("can0");
+    network_builder.set_diagnostic_severity(DiagnosticKind::BusPin, Severity::Warn);
+    let network = network_builder.build().unwrap();
+    assert!(network.diagnostics().iter().any(|d| d.kind() == DiagnosticKind::BusPin));
+    // the last pin wins.
+    let message = network.messages().iter().find(|m| m.name() == "msg_0").unwrap();
+    assert_eq!(message.bus().name(), "can0");
+}
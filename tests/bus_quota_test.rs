@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    diagnostics::DiagnosticKind,
+    errors::ConfigError,
+};
+
+fn network_builder(elastic: bool) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125_000));
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u32");
+    becu.create_object_entry("temperature", "u32");
+    let debug = becu.create_stream("debug");
+    debug.add_entry("pressure");
+    debug.add_entry("temperature");
+    // 8 bytes every ms is more than the whole bus.
+    debug.set_interval(Duration::from_millis(1), Duration::from_millis(1));
+    debug.add_tag("telemetry");
+    if elastic {
+        debug.set_elastic_interval(Duration::from_millis(10), Duration::from_millis(10));
+    }
+    let secu = network_builder.create_node("secu");
+    secu.receive_stream("becu", "debug").unwrap();
+    network_builder.set_bus_quota("telemetry", "can0", 0.3);
+    network_builder
+}
+
+fn quota_violations(network_builder: NetworkBuilder) -> usize {
+    let network = network_builder.build().unwrap();
+    network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::BusQuota && d.message().contains("telemetry"))
+        .count()
+}
+
+#[test]
+fn quota_violations_are_reported() {
+    assert_eq!(quota_violations(network_builder(false)), 1);
+}
+
+#[test]
+fn elastic_streams_are_degraded_to_meet_the_quota() {
+    let network = network_builder(true).build().unwrap();
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::BusQuota));
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    let debug = becu.tx_streams().iter().find(|stream| stream.name() == "debug").unwrap();
+    assert_eq!(*debug.max_interval(), Duration::from_millis(10));
+}
+
+#[test]
+fn streams_within_the_quota_keep_their_interval() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u32");
+    let stream = becu.create_stream("pressure");
+    stream.add_entry("pressure");
+    stream.add_tag("telemetry");
+    stream.set_elastic_interval(Duration::from_secs(1), Duration::from_secs(5));
+    network_builder.set_bus_quota("telemetry", "can0", 0.3);
+    let network = network_builder.build().unwrap();
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    assert_eq!(*becu.tx_streams()[0].max_interval(), Duration::from_millis(500));
+}
+
+#[test]
+fn invalid_quotas_are_rejected() {
+    let network_builder = network_builder(false);
+    network_builder.set_bus_quota("telemetry", "can7", 0.3);
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidBusQuota(_))
+    ));
+    let network_builder = self::network_builder(false);
+    network_builder.set_bus_quota("control", "can0", 1.5);
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidBusQuota(_))
+    ));
+}
@@ -0,0 +1,216 @@
+use canzero_config::{
+    builder::{MessageBuilder, MessagePriority, NetworkBuilder},
+    config::can_xl::{self, SduType},
+    errors::ConfigError,
+};
+
+mod common;
+
+fn xl_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let xl0 = network_builder.create_bus("xl0", Some(500_000));
+    xl0.set_xl_mode(10_000_000);
+    network_builder.create_node("vcu").assign_bus("xl0");
+    network_builder.create_node("inverter").assign_bus("xl0");
+    let status = network_builder.create_message("status");
+    status.assign_bus("can0");
+    status.make_type_format().add_type("u16", "state");
+    network_builder
+}
+
+fn create_xl_message(network_builder: &NetworkBuilder, name: &str, words: usize) -> MessageBuilder {
+    let message = network_builder.create_message(name);
+    message.assign_bus("xl0");
+    message.set_any_std_id(MessagePriority::Normal);
+    message.add_transmitter("vcu");
+    message.add_receiver("inverter");
+    let format = message.make_type_format();
+    for i in 0..words {
+        format.add_type("u64", &format!("word_{i}"));
+    }
+    message
+}
+
+#[test]
+fn xl_frames_carry_large_payloads() {
+    let network_builder = xl_network();
+    create_xl_message(&network_builder, "torque_map", 40);
+    let network = network_builder.build().unwrap();
+    let xl0 = network.buses().iter().find(|bus| bus.name() == "xl0").unwrap();
+    assert!(xl0.is_xl());
+
+    let torque_map = network.expect_message("torque_map");
+    let torque_map = torque_map.message();
+    assert_eq!(torque_map.payload_len(), 320);
+    let xl_frame = torque_map.xl_frame().unwrap();
+    assert_eq!(xl_frame.sdu_type(), SduType::ContentBasedAddressing);
+    assert_eq!(xl_frame.sdu_type().to_u8(), 0x01);
+    assert_eq!(xl_frame.acceptance_field(), torque_map.id().as_u32());
+    assert!(!torque_map.id().ide());
+    assert_eq!(
+        xl0.frame_time(network.frame_time_model(), torque_map),
+        can_xl::xl_frame_time(320, 500_000, 10_000_000)
+    );
+
+    let json = network.to_json();
+    assert!(json.contains("\"data_baudrate\": 10000000"), "{json}");
+    assert!(json.contains("\"payload_len\": 320"), "{json}");
+}
+
+#[test]
+fn can_buses_are_not_affected() {
+    let network_builder = xl_network();
+    let network = network_builder.build().unwrap();
+    let can0 = network.buses().iter().find(|bus| bus.name() == "can0").unwrap();
+    assert!(!can0.is_xl());
+    let status = network.expect_message("status");
+    let status = status.message();
+    assert!(status.xl_frame().is_none());
+    assert_eq!(status.payload_len(), status.dlc() as usize);
+    assert_eq!(
+        can0.frame_time(network.frame_time_model(), status),
+        network
+            .frame_time_model()
+            .frame_time(status.id().ide(), status.dlc() as usize, can0.baudrate())
+    );
+}
+
+#[test]
+fn xl_metadata_is_part_of_the_config() {
+    let network_builder = xl_network();
+    let tunnel = create_xl_message(&network_builder, "tunnel", 1);
+    tunnel.set_sdu_type(SduType::ClassicFdTunneling);
+    tunnel.set_acceptance_field(0xCAFE);
+    let network = network_builder.build().unwrap();
+    let tunnel = network.expect_message("tunnel");
+    let xl_frame = tunnel.message().xl_frame().unwrap();
+    assert_eq!(xl_frame.sdu_type().to_u8(), 0x03);
+    assert_eq!(xl_frame.acceptance_field(), 0xCAFE);
+    assert_eq!(xl_frame.payload_len(), 8);
+}
+
+#[test]
+fn xl_limits_are_validated() {
+    let network_builder = xl_network();
+    create_xl_message(&network_builder, "too_large", 257);
+    let Err(ConfigError::InvalidCanXl(msg)) = network_builder.build() else {
+        panic!("expected an invalid CAN XL message");
+    };
+    assert!(msg.contains("too_large"), "{msg}");
+
+    let network_builder = xl_network();
+    let extended = network_builder.create_message("extended");
+    extended.assign_bus("xl0");
+    extended.set_ext_id(0x1234);
+    assert!(matches!(network_builder.build(), Err(ConfigError::InvalidCanXl(_))));
+
+    let network_builder = xl_network();
+    let classic = network_builder.create_message("classic");
+    classic.assign_bus("can0");
+    classic.set_sdu_type(SduType::Ethernet);
+    assert!(matches!(network_builder.build(), Err(ConfigError::InvalidCanXl(_))));
+}
+
+fn wide_network() -> NetworkBuilder {
+    let network_builder = xl_network();
+    let setpoint = network_builder.create_message("setpoint");
+    setpoint.assign_bus("xl0");
+    setpoint.set_any_std_id(MessagePriority::Normal);
+    setpoint.add_transmitter("vcu");
+    setpoint.add_receiver("inverter");
+    let format = setpoint.make_type_format();
+    format.add_type("u64", "word_0");
+    format.add_type("i32", "velocity");
+    network_builder
+}
+
+#[test]
+fn xl_payload_len_in_generated_code() {
+    let network = wide_network().build().unwrap();
+    let setpoint = network.expect_message("setpoint");
+    let setpoint = setpoint.message();
+    assert_eq!(setpoint.payload_len(), 12);
+    let id = setpoint.id().as_u32();
+
+    let dbc = network.generate_dbc();
+    assert!(dbc.contains(&format!("BO_ {id} setpoint: 12 vcu\n")), "{dbc}");
+    let vcu = network.nodes().iter().find(|node| node.name() == "vcu").unwrap();
+    assert!(vcu.generate_rust_tables().contains(&format!(
+        "pub const SETPOINT: Message = Message {{ id: Id::Standard({id:#X}), payload_len: 12, bus: 1 }};"
+    )));
+    let header = network.generate_cpp();
+    assert!(header.contains("  static constexpr uint16_t payload_len = 12;\n"));
+    assert!(header.contains("  void pack(detail::frame_buffer<12> data) const {\n"));
+    assert!(header.contains(&format!("  {{\"setpoint\", {id:#X}, false, 12, 1}},\n")));
+    assert!(network.generate_python().contains("    PAYLOAD_LEN = 12\n"));
+}
+
+#[test]
+fn xl_payload_cpp_compiles() {
+    let Ok(compiler) = std::process::Command::new("g++").arg("--version").output() else {
+        // no c++ compiler available.
+        return;
+    };
+    if !compiler.status.success() {
+        return;
+    }
+    let dir = std::env::temp_dir().join("canzero_can_xl_cpp_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("canzero.hpp"), wide_network().build().unwrap().generate_cpp()).unwrap();
+    let source = dir.join("main.cpp");
+    std::fs::write(
+        &source,
+        r#"#include "canzero.hpp"
+int main() {
+  canzero::messages::setpoint msg{};
+  msg.word_0 = 0x0123456789ABCDEFull;
+  msg.velocity = -42;
+  std::array<uint8_t, 12> data{};
+  msg.pack(data);
+  const auto decoded = canzero::messages::setpoint::unpack(data);
+  return decoded.word_0 == msg.word_0 && decoded.velocity == -42
+      && canzero::messages::setpoint::reserved_bits_zero(data) ? 0 : 1;
+}
+"#,
+    )
+    .unwrap();
+    let binary = dir.join("main");
+    for standard in ["-std=c++17", "-std=c++20"] {
+        let status = std::process::Command::new("g++")
+            .args([standard, "-Wall", "-Wextra", "-Werror", "-o"])
+            .arg(&binary)
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success(), "{standard}");
+        assert!(std::process::Command::new(&binary).status().unwrap().success());
+    }
+}
+
+#[test]
+fn xl_payload_python_roundtrip() {
+    if !common::python3_available("xl_payload_python_roundtrip") {
+        return;
+    }
+    let dir = std::env::temp_dir().join("canzero_can_xl_python_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("canzero.py"), wide_network().build().unwrap().generate_python()).unwrap();
+    let script = r#"
+import canzero
+msg = canzero.setpoint()
+msg.word_0 = 0x0123456789ABCDEF
+msg.velocity = -42
+data = msg.encode()
+assert len(data) == canzero.setpoint.PAYLOAD_LEN
+decoded = canzero.setpoint.decode(data)
+assert decoded.word_0 == 0x0123456789ABCDEF
+assert decoded.velocity == -42
+"#;
+    let status = std::process::Command::new("python3")
+        .args(["-c", script])
+        .current_dir(&dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,191 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    diagnostics::{DiagnosticKind, Severity},
+    errors::{CapacityError, ConfigError},
+};
+
+// a message for every receiver set of 3 out of 18 nodes, which needs
+// more setcodes than 11 bit ids provide.
+fn receiver_set_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let n = 18;
+    for i in 0..n {
+        network_builder.create_node(&format!("node{i}"));
+    }
+    let mut k = 0;
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                let message = network_builder.create_message(&format!("msg{k}"));
+                k += 1;
+                message.set_any_std_id(MessagePriority::Normal);
+                for receiver in [a, b, c] {
+                    message.add_receiver(&format!("node{receiver}"));
+                }
+            }
+        }
+    }
+    network_builder
+}
+
+#[test]
+fn too_many_receiver_sets_for_std_ids() {
+    let Err(ConfigError::Capacity(error)) = receiver_set_network().build() else {
+        panic!("expected a capacity error");
+    };
+    let CapacityError::IdSpace {
+        needed_setcodes,
+        available_setcodes,
+        priority_bits,
+        id_bits,
+    } = error
+    else {
+        panic!("expected an id space error, got {error:?}");
+    };
+    assert_eq!(id_bits, 11);
+    assert!(needed_setcodes > available_setcodes);
+    assert_eq!(available_setcodes, 1 << (id_bits - priority_bits));
+    assert!(error.to_string().contains(&format!("need {needed_setcodes} setcodes")));
+    assert!(error.to_string().contains("fold_receiver_sets"));
+}
+
+#[test]
+fn folded_receiver_sets_fit_into_std_ids() {
+    let network_builder = receiver_set_network();
+    network_builder.fold_receiver_sets(usize::MAX);
+    let network = network_builder.build().unwrap();
+    assert!(network.messages().iter().all(|m| m.id().ide() || m.id().as_u32() <= 0x7FF));
+}
+
+#[test]
+fn reserved_std_ids_leave_no_id_for_the_sets() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    network_builder.reserve_std_id_range(0..0x800);
+    let message = network_builder.create_message("msg");
+    message.set_any_std_id(MessagePriority::Normal);
+    message.add_receiver("secu");
+    let Err(ConfigError::Capacity(CapacityError::IdSpace { id_bits, .. })) = network_builder.build()
+    else {
+        panic!("expected an id space error");
+    };
+    assert_eq!(id_bits, 11);
+}
+
+// the uds range takes ids of the sets, which have to be assigned around it.
+fn uds_network(message_count: usize) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("becu");
+    network_builder.create_node("secu");
+    network_builder.enable_uds();
+    for i in 0..message_count {
+        let message = network_builder.create_message(&format!("msg{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_receiver("becu");
+    }
+    network_builder
+}
+
+#[test]
+fn uds_ids_are_assigned_around() {
+    let network = uds_network(1400).build().unwrap();
+    assert!(network
+        .messages()
+        .iter()
+        .filter(|m| m.name().starts_with("msg"))
+        .all(|m| !(0x7E0..0x7F0).contains(&m.id().as_u32())));
+}
+
+#[test]
+fn uds_ids_exceed_the_id_space() {
+    let Err(ConfigError::Capacity(error)) = uds_network(1500).build() else {
+        panic!("expected a capacity error");
+    };
+    assert!(matches!(error, CapacityError::IdSpace { id_bits: 11, .. }));
+}
+
+// fixed ids received by secu fill the id space, which leaves
+// 16 std ids for the sets of becu.
+fn fixed_ids_network(message_count: usize) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("becu");
+    network_builder.create_node("secu");
+    for id in 0..0x7F0 {
+        let message = network_builder.create_message(&format!("fixed{id}"));
+        message.set_std_id(id);
+        message.add_receiver("secu");
+    }
+    for i in 0..message_count {
+        let message = network_builder.create_message(&format!("msg{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_receiver("becu");
+    }
+    network_builder
+}
+
+#[test]
+fn fixed_ids_fill_the_id_space() {
+    let Err(ConfigError::Capacity(error)) = fixed_ids_network(40).build() else {
+        panic!("expected a capacity error");
+    };
+    assert!(matches!(error, CapacityError::IdSpace { id_bits: 11, .. }));
+}
+
+#[test]
+fn sets_are_assigned_around_fixed_ids() {
+    let network = fixed_ids_network(4).build().unwrap();
+    let mut ids: Vec<u32> = network
+        .messages()
+        .iter()
+        .filter(|m| !m.id().ide())
+        .map(|m| m.id().as_u32())
+        .collect();
+    assert!(ids.iter().all(|id| *id <= 0x7FF));
+    let count = ids.len();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), count);
+}
+
+fn overloaded_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125_000));
+    network_builder.create_node("becu");
+    network_builder.create_node("secu");
+    let flood = network_builder.create_message_with_interval("flood", Duration::from_micros(100));
+    flood.set_std_id(0x10);
+    flood.add_transmitter("becu");
+    flood.add_receiver("secu");
+    network_builder
+}
+
+#[test]
+fn overloaded_bus_is_a_warning() {
+    let network = overloaded_network().build().unwrap();
+    let diagnostic = network
+        .diagnostics()
+        .iter()
+        .find(|d| d.kind() == DiagnosticKind::BusLoad)
+        .unwrap();
+    assert!(diagnostic.message().contains("exceeds the bandwidth of can0"));
+    assert!(diagnostic.message().contains("add a bus"));
+}
+
+#[test]
+fn overloaded_bus_with_denied_bus_load() {
+    let network_builder = overloaded_network();
+    network_builder.set_diagnostic_severity(DiagnosticKind::BusLoad, Severity::Deny);
+    let Err(ConfigError::Capacity(error)) = network_builder.build() else {
+        panic!("expected a capacity error");
+    };
+    let CapacityError::BusLoad(loads) = &error else {
+        panic!("expected a bus load error, got {error:?}");
+    };
+    assert_eq!(loads.len(), 1);
+    assert_eq!(loads[0].0, "can0");
+    assert!(loads[0].1 > 1.0);
+    assert!(error.mitigations().contains(&"add a bus"));
+}
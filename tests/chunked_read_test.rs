@@ -0,0 +1,80 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{NetworkRef, ObjectEntryRef},
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entry("cell_voltages", "u16[48]");
+    bms.create_object_entry("temperatures", "i8[8]");
+    bms.create_object_entry("cell_states", "cell_state[128]");
+    let cell_state = network_builder.define_enum("cell_state");
+    cell_state.add_entry("Ok", None).unwrap();
+    cell_state.add_entry("Balancing", None).unwrap();
+    cell_state.add_entry("Fault", None).unwrap();
+    network_builder.create_node("secu").create_object_entry("speed", "u16");
+    network_builder.set_master("master");
+    network_builder.build().unwrap()
+}
+
+fn object_entry(network: &NetworkRef, node: &str, name: &str) -> ObjectEntryRef {
+    let node = network.nodes().iter().find(|n| n.name() == node).unwrap();
+    node.object_entries().iter().find(|oe| oe.name() == name).cloned().unwrap()
+}
+
+#[test]
+fn arrays_exceeding_a_frame_are_read_in_chunks() {
+    let network = network();
+    let cell_voltages = object_entry(&network, "bms", "cell_voltages");
+    let chunked_read = cell_voltages.chunked_read().expect("u16[48] exceeds a frame");
+    // 64 bits - 8 bits client id - 6 bits index (0..48) fit three u16.
+    assert_eq!(chunked_read.index_bits(), 6);
+    assert_eq!(chunked_read.elements_per_frame(), 3);
+    assert_eq!(chunked_read.frames(48), 16);
+    assert_eq!(chunked_read.frames(4), 2);
+    assert_eq!(chunked_read.request().name(), "chunk_read_req");
+    let response = chunked_read.response();
+    assert_eq!(response.name(), "bms_cell_voltages_chunk_resp");
+    let attributes: Vec<&str> = response
+        .encoding()
+        .unwrap()
+        .attributes()
+        .iter()
+        .map(|attribute| attribute.name())
+        .collect();
+    assert_eq!(attributes, vec!["client_id", "index", "element0", "element1", "element2"]);
+
+    // 2 bit enum elements with a 7 bit index.
+    let cell_states = object_entry(&network, "bms", "cell_states");
+    assert_eq!(cell_states.chunked_read().unwrap().elements_per_frame(), 24);
+}
+
+#[test]
+fn both_sides_know_the_messages() {
+    let network = network();
+    let bms = network.nodes().iter().find(|n| n.name() == "bms").unwrap();
+    assert!(bms.rx_messages().iter().any(|m| m.name() == "chunk_read_req"));
+    assert!(bms.tx_messages().iter().any(|m| m.name() == "bms_cell_voltages_chunk_resp"));
+    let secu = network.nodes().iter().find(|n| n.name() == "secu").unwrap();
+    assert!(!secu.rx_messages().iter().any(|m| m.name() == "chunk_read_req"));
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert!(master.rx_messages().iter().any(|m| m.name() == "bms_cell_voltages_chunk_resp"));
+    let request = network.messages().iter().find(|m| m.name() == "chunk_read_req").unwrap();
+    assert!(request.dlc() <= 8);
+    assert!(network
+        .to_json()
+        .contains("\"response\": \"bms_cell_voltages_chunk_resp\""));
+}
+
+#[test]
+fn arrays_within_a_frame_use_the_get_protocol() {
+    let network = network();
+    assert!(object_entry(&network, "bms", "temperatures").chunked_read().is_none());
+    assert!(object_entry(&network, "secu", "speed").chunked_read().is_none());
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu").create_object_entry("speed", "u16");
+    let network = network_builder.build().unwrap();
+    assert!(!network.messages().iter().any(|m| m.name() == "chunk_read_req"));
+}
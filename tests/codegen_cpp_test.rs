@@ -0,0 +1,44 @@
+mod common;
+
+#[test]
+fn codegen_cpp_contains_types_and_messages() {
+    let network = common::codegen_network();
+    let header = network.generate_cpp();
+    assert!(header.contains("enum class brake_state : uint8_t {"));
+    assert!(header.contains("struct vec3 {"));
+    // vec3 has to be declared before it is used in a message.
+    assert!(header.find("struct vec3 {").unwrap() < header.find("namespace messages").unwrap());
+    for message in network.messages() {
+        assert!(header.contains(&format!("struct {} {{", message.name())));
+        assert!(header.contains(&format!("{{\"{}\", {:#X},", message.name(), message.id().as_u32())));
+    }
+    assert!(header.contains("detail::sign_extend("));
+    assert!(header.contains("detail::pack_decimal("));
+    assert!(header.contains("namespace persistent {"));
+    assert!(header.contains("becu_object_entries = {"));
+}
+
+#[test]
+fn codegen_cpp_compiles() {
+    let Ok(compiler) = std::process::Command::new("g++").arg("--version").output() else {
+        // no c++ compiler available.
+        return;
+    };
+    if !compiler.status.success() {
+        return;
+    }
+    let dir = std::env::temp_dir().join("canzero_codegen_cpp_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("canzero.hpp"), common::codegen_network().generate_cpp()).unwrap();
+    let source = dir.join("main.cpp");
+    std::fs::write(&source, "#include \"canzero.hpp\"\nint main() { return 0; }\n").unwrap();
+    // C++17 passes frames as std::array, C++20 as std::span.
+    for standard in ["-std=c++17", "-std=c++20"] {
+        let status = std::process::Command::new("g++")
+            .args([standard, "-fsyntax-only", "-Wall", "-Wextra", "-Werror"])
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success(), "{standard}");
+    }
+}
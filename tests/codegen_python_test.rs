@@ -0,0 +1,41 @@
+mod common;
+
+#[test]
+fn codegen_python_contains_types_and_messages() {
+    let network = common::codegen_network();
+    let module = network.generate_python();
+    assert!(module.contains("class brake_state(IntEnum):"));
+    assert!(module.contains("class vec3:"));
+    for message in network.messages() {
+        assert!(module.contains(&format!("class {}:", message.name())));
+    }
+}
+
+#[test]
+fn codegen_python_roundtrip() {
+    if !common::python3_available("codegen_python_roundtrip") {
+        return;
+    }
+    let dir = std::env::temp_dir().join("canzero_codegen_python_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("canzero.py"), common::codegen_network().generate_python()).unwrap();
+    let script = r#"
+import canzero
+msg = canzero.becu_stream_brake_stream()
+msg.state = canzero.brake_state.engaged
+msg.acceleration = canzero.vec3(x=-3.25, y=-1000, z=9)
+data = msg.encode()
+assert len(data) == canzero.becu_stream_brake_stream.DLC
+decoded = canzero.decode(msg.ID, msg.IDE, data)
+assert decoded.state == canzero.brake_state.engaged
+assert abs(decoded.acceleration.x + 3.25) < 1e-3
+assert decoded.acceleration.y == -1000
+assert decoded.acceleration.z == 9
+"#;
+    let status = std::process::Command::new("python3")
+        .args(["-c", script])
+        .current_dir(&dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,60 @@
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef};
+
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    let stream = becu.create_stream("brake_stream");
+    stream.add_entry("pressure");
+    let secu = network_builder.create_node("secu");
+    secu.receive_stream("becu", "brake_stream").unwrap();
+    let legacy = network_builder.create_message("legacy");
+    legacy.set_ext_id(0x1234);
+    legacy.add_transmitter("becu");
+    legacy.add_receiver("secu");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn rust_tables_contain_ids_and_filters() {
+    let network = build_network();
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    let tables = secu.generate_rust_tables();
+    assert!(tables.contains(&format!("pub const NODE_ID: u8 = {};", secu.id())));
+    for message in secu.rx_messages() {
+        assert!(tables.contains(&format!("    rx::{}.id,", message.name().to_uppercase())));
+    }
+    assert!(tables.contains(&format!("pub const RX_IDS: [Id; {}] = [", secu.rx_messages().len())));
+    assert!(tables.contains("pub const LEGACY: Message = Message { id: Id::Extended(0x1234), payload_len: 0, bus: 0 };"));
+    let stream = network.messages().iter().find(|m| m.name() == "becu_stream_brake_stream").unwrap();
+    assert!(tables.contains(&format!(
+        "pub const BECU_STREAM_BRAKE_STREAM: Message = Message {{ id: Id::Standard({:#X}), payload_len: 2, bus: 0 }};",
+        stream.id().as_u32()
+    )));
+    assert!(tables.contains("Filter { id: 0x1234, mask: 0xFFFFFFFF, ide: true },"));
+}
+
+#[test]
+fn rust_tables_compile_without_std() {
+    let Ok(compiler) = std::process::Command::new("rustc").arg("--version").output() else {
+        return;
+    };
+    if !compiler.status.success() {
+        return;
+    }
+    let network = build_network();
+    let dir = std::env::temp_dir().join("canzero_codegen_rust_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    for node in network.nodes() {
+        let source = dir.join(format!("{}.rs", node.name()));
+        std::fs::write(&source, format!("#![no_std]\n{}", node.generate_rust_tables())).unwrap();
+        let status = std::process::Command::new("rustc")
+            .args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata", "-D", "warnings", "--out-dir"])
+            .arg(&dir)
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success(), "tables of {} don't compile", node.name());
+    }
+}
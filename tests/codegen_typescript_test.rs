@@ -0,0 +1,28 @@
+mod common;
+
+#[test]
+fn codegen_typescript_declarations() {
+    let network = common::codegen_network();
+    let declarations = network.generate_typescript();
+    assert!(declarations.contains("export enum brake_state {"));
+    assert!(declarations.contains("export interface vec3 {"));
+    assert!(declarations.contains("export interface becu_object_entries {"));
+    assert!(declarations.contains("  acceleration: vec3;"));
+    for message in network.messages() {
+        assert!(declarations.contains(&format!("export interface {} {{", message.name())));
+    }
+}
+
+#[test]
+fn codegen_json_schema_is_valid_json() {
+    let schema = common::codegen_network().generate_json_schema();
+    assert!(schema.contains("\"$ref\": \"#/definitions/vec3\""));
+    if !common::python3_available("codegen_json_schema_is_valid_json") {
+        return;
+    }
+    let output = std::process::Command::new("python3")
+        .args(["-c", "import json,sys; json.loads(sys.argv[1])", &schema])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
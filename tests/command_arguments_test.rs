@@ -0,0 +1,48 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+fn invalid_arguments(network_builder: NetworkBuilder) -> String {
+    match network_builder.build() {
+        Err(ConfigError::InvalidCommandArguments(msg)) => msg,
+        other => panic!("expected invalid command arguments, got {other:?}"),
+    }
+}
+
+#[test]
+fn command_arguments_valid() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    let command = master.create_command("calibrate", None);
+    command.add_argument("offset", "i32");
+    command.add_argument("gain", "u31");
+    command.add_callee("becu").unwrap();
+    network_builder.build().unwrap();
+}
+
+#[test]
+fn command_arguments_too_large() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    let command = master.create_command("calibrate", None);
+    command.add_argument("offset", "i32");
+    command.add_argument("gain", "u64");
+    let msg = invalid_arguments(network_builder);
+    assert!(msg.contains("master::calibrate"));
+    assert!(msg.contains("96 bits"));
+}
+
+#[test]
+fn command_arguments_invalid_names() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    let command = master.create_command("calibrate", None);
+    command.add_argument("gain", "u8");
+    command.add_argument("gain", "u8");
+    command.add_argument("1st", "u8");
+    command.add_argument("class", "u8");
+    command.add_argument("mode", "undefined_enum");
+    let msg = invalid_arguments(network_builder);
+    assert!(msg.contains("duplicated argument gain"));
+    assert!(msg.contains("\"1st\" is not a valid identifier"));
+    assert!(msg.contains("\"class\" is not a valid identifier"));
+    assert!(msg.contains("undefined type undefined_enum"));
+}
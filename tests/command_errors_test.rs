@@ -0,0 +1,71 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{NetworkRef, Type},
+    errors::ConfigError,
+};
+
+fn erno_type(network: &NetworkRef, message: &str) -> (String, Vec<(String, u64)>) {
+    let message = network.messages().iter().find(|m| m.name() == message).unwrap();
+    let erno = message
+        .encoding()
+        .unwrap()
+        .attributes()
+        .iter()
+        .find(|attribute| attribute.name() == "erno")
+        .unwrap();
+    match erno.ty() as &Type {
+        Type::Enum { name, entries, .. } => (name.clone(), entries.clone()),
+        _ => panic!("erno is not an enum"),
+    }
+}
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    let engage = becu.create_command("engage", None);
+    engage.add_error("InvalidState", 2).unwrap();
+    engage.add_error("PressureLow", 3).unwrap();
+    becu.create_command("reset", None);
+    network_builder
+}
+
+#[test]
+fn commands_with_errors_respond_with_their_own_erno() {
+    let network = network_builder().build().unwrap();
+    let (name, entries) = erno_type(&network, "becu_engage_command_resp");
+    assert_eq!(name, "becu_engage_erno");
+    let entries: Vec<(&str, u64)> = entries.iter().map(|(name, code)| (name.as_str(), *code)).collect();
+    assert_eq!(
+        entries,
+        vec![("Success", 0), ("Error", 1), ("InvalidState", 2), ("PressureLow", 3)]
+    );
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    let engage = becu.commands().iter().find(|command| command.name() == "engage").unwrap();
+    assert_eq!(
+        engage.errors(),
+        &[("InvalidState".to_owned(), 2), ("PressureLow".to_owned(), 3)]
+    );
+    assert!(network.to_json().contains("\"name\": \"PressureLow\""));
+    assert!(network.generate_python().contains("class becu_engage_erno(IntEnum):"));
+    assert!(network.generate_python().contains("    PressureLow = 3"));
+}
+
+#[test]
+fn commands_without_errors_keep_the_shared_erno() {
+    let network = network_builder().build().unwrap();
+    assert_eq!(erno_type(&network, "becu_reset_command_resp").0, "command_resp_erno");
+}
+
+#[test]
+fn colliding_errors_are_rejected() {
+    let network_builder = NetworkBuilder::new();
+    let command = network_builder.create_node("becu").create_command("engage", None);
+    for (name, code) in [("Success", 5), ("Busy", 1)] {
+        assert!(matches!(command.add_error(name, code), Err(ConfigError::DuplicatedEnumEntry(_))));
+    }
+    command.add_error("Busy", 2).unwrap();
+    assert!(matches!(command.add_error("Busy", 3), Err(ConfigError::DuplicatedEnumEntry(_))));
+    assert!(matches!(command.add_error("Locked", 2), Err(ConfigError::DuplicatedEnumEntry(_))));
+    network_builder.build().unwrap();
+}
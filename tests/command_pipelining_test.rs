@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{CommandRef, NetworkRef},
+    diagnostics::DiagnosticKind,
+    simulator,
+};
+
+fn network(max_in_flight: Option<u32>) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125_000));
+    let becu = network_builder.create_node("becu");
+    let secu = network_builder.create_node("secu");
+    // 2 frames of ~1ms every 5ms on the bus, which both have a
+    // higher priority than the command response.
+    for i in 0..2 {
+        becu.create_object_entry(&format!("value_{i}"), "u64");
+        let stream = becu.create_stream(&format!("stream_{i}"));
+        stream.add_entry(&format!("value_{i}"));
+        stream.set_interval(Duration::from_millis(5), Duration::from_millis(5));
+        stream.set_priority(MessagePriority::Normal);
+        secu.receive_stream("becu", &format!("stream_{i}")).unwrap();
+    }
+    let command = secu.create_command("upload", Some(Duration::from_millis(100)));
+    command.add_callee("becu").unwrap();
+    command.set_timeout(Duration::from_millis(20));
+    if let Some(max_in_flight) = max_in_flight {
+        command.set_max_in_flight(max_in_flight);
+    }
+    network_builder.build().unwrap()
+}
+
+fn upload(network: &NetworkRef) -> CommandRef {
+    network
+        .nodes()
+        .iter()
+        .find(|node| node.name() == "secu")
+        .unwrap()
+        .commands()
+        .iter()
+        .find(|command| command.name() == "upload")
+        .unwrap()
+        .clone()
+}
+
+fn pipelining_diagnostics(network: &NetworkRef) -> Vec<String> {
+    network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::CommandPipelining)
+        .map(|d| d.message().to_owned())
+        .collect()
+}
+
+#[test]
+fn commands_are_not_pipelined_by_default() {
+    let network = network(None);
+    let upload = upload(&network);
+    assert_eq!(upload.max_in_flight(), 1);
+    assert!(!upload.is_pipelined());
+    assert!(pipelining_diagnostics(&network).is_empty());
+}
+
+#[test]
+fn max_in_flight_is_part_of_the_config() {
+    let network = network(Some(2));
+    let upload = upload(&network);
+    assert_eq!(upload.max_in_flight(), 2);
+    assert!(upload.is_pipelined());
+    assert!(pipelining_diagnostics(&network).is_empty());
+    // both sides of the command see the same limit.
+    let json = network.to_json();
+    assert_eq!(json.matches("\"max_in_flight\": 2").count(), 2, "{json}");
+    assert_ne!(network.portable_hash(), self::network(None).portable_hash());
+}
+
+#[test]
+fn unsustainable_pipelining_is_reported() {
+    let network = network(Some(64));
+    let diagnostics = pipelining_diagnostics(&network);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].contains("secu::upload"), "{}", diagnostics[0]);
+
+    let upload = upload(&network);
+    let single = simulator::worst_case_burst_response_time(&network, upload.rx_message(), 1).unwrap();
+    assert_eq!(Some(single), simulator::worst_case_response_time(&network, upload.rx_message()));
+    let pipelined = simulator::worst_case_burst_response_time(&network, upload.rx_message(), 64);
+    assert!(pipelined.is_none_or(|pipelined| pipelined > Duration::from_millis(20)));
+}
+
+#[test]
+#[should_panic]
+fn max_in_flight_has_to_be_positive() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    master.create_command("reset", None).set_max_in_flight(0);
+}
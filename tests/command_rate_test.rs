@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{CommandBuilder, NetworkBuilder},
+    config::NetworkRef,
+    diagnostics::DiagnosticKind,
+    simulator,
+};
+
+fn network(configure: impl Fn(&CommandBuilder)) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125_000));
+    let master = network_builder.create_node("master");
+    network_builder.create_node("pod");
+    let command = master.create_command("ping", None);
+    command.add_argument("payload", "u64");
+    command.add_callee("pod").unwrap();
+    configure(&command);
+    network_builder.build().unwrap()
+}
+
+fn command_interval(network: &NetworkRef) -> Duration {
+    let req = network
+        .messages()
+        .iter()
+        .find(|m| m.name() == "master_ping_command_req")
+        .unwrap();
+    simulator::message_interval(req)
+}
+
+fn bus_load_warnings(network: &NetworkRef) -> usize {
+    network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::BusLoad)
+        .count()
+}
+
+#[test]
+fn interval_of_create_command_is_respected() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    master.create_command("reset", Some(Duration::from_millis(250)));
+    let network = network_builder.build().unwrap();
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    let reset = master.commands().iter().find(|c| c.name() == "reset").unwrap();
+    assert_eq!(*reset.expected_interval(), Duration::from_millis(250));
+    assert_eq!(reset.burst(), 1);
+}
+
+#[test]
+fn rate_and_burst_define_the_frame_interval() {
+    let network = network(|command| {
+        command.set_expected_rate(10.0);
+        command.set_burst(5);
+    });
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    let ping = master.commands().iter().find(|c| c.name() == "ping").unwrap();
+    assert_eq!(*ping.expected_interval(), Duration::from_millis(100));
+    assert_eq!(ping.burst(), 5);
+    assert_eq!(ping.frame_interval(), Duration::from_millis(20));
+    assert_eq!(command_interval(&network), Duration::from_millis(20));
+}
+
+#[test]
+fn rate_is_part_of_the_bus_load() {
+    // ~1 call per minute doesn't load the bus.
+    let rare = network(|command| command.set_expected_rate(1.0 / 60.0));
+    assert_eq!(command_interval(&rare), Duration::from_secs(60));
+    assert_eq!(bus_load_warnings(&rare), 0);
+    // bursts of 20 calls every 10ms exceed the capacity of a 125 kbit/s bus.
+    let bursty = network(|command| {
+        command.set_expected_rate(100.0);
+        command.set_burst(20);
+    });
+    assert_eq!(bus_load_warnings(&bursty), 1);
+    assert_ne!(rare.portable_hash(), bursty.portable_hash());
+}
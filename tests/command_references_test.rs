@@ -0,0 +1,66 @@
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("master");
+    let bms = network_builder.create_node("bms");
+    let pdu = network_builder.create_node("pdu");
+    let reset = bms.create_command("reset", None);
+    reset.add_callee("master").unwrap();
+    reset.add_callee("pdu").unwrap();
+    let shutdown = pdu.create_command("shutdown", None);
+    shutdown.add_argument("delay", "u16");
+    shutdown.add_callee("master").unwrap();
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn commands_callable_by_node() {
+    let network = network();
+    let callable: Vec<(String, String)> = network
+        .commands_callable_by("master")
+        .iter()
+        .map(|(owner, command)| (owner.name().to_owned(), command.name().to_owned()))
+        .collect();
+    assert_eq!(
+        callable,
+        vec![
+            ("bms".to_owned(), "reset".to_owned()),
+            ("pdu".to_owned(), "shutdown".to_owned())
+        ]
+    );
+    assert_eq!(network.commands_callable_by("bms").len(), 0);
+    assert!(network.commands_callable_by("imu").is_empty());
+}
+
+#[test]
+fn owner_and_callers_of_command() {
+    let network = network();
+    let bms = network.nodes().iter().find(|n| n.name() == "bms").unwrap();
+    let reset = bms.commands().iter().find(|c| c.name() == "reset").unwrap();
+    assert_eq!(network.command_owner(reset).unwrap().name(), "bms");
+    let callers: Vec<String> = network
+        .command_callers(reset)
+        .iter()
+        .map(|n| n.name().to_owned())
+        .collect();
+    assert_eq!(callers, vec!["master".to_owned(), "pdu".to_owned()]);
+}
+
+#[test]
+fn command_of_message() {
+    let network = network();
+    for (message, owner, command) in [
+        ("pdu_shutdown_command_req", "pdu", "shutdown"),
+        ("pdu_shutdown_command_resp", "pdu", "shutdown"),
+        ("bms_reset_command_req", "bms", "reset"),
+    ] {
+        let message = network.messages().iter().find(|m| m.name() == message).unwrap();
+        let (node, cmd) = network.command_of_message(message).unwrap();
+        assert_eq!(node.name(), owner);
+        assert_eq!(cmd.name(), command);
+    }
+    let heartbeat = network.heartbeat_message();
+    assert!(network.command_of_message(heartbeat).is_none());
+}
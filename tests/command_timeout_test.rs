@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::NetworkRef,
+    diagnostics::DiagnosticKind,
+    simulator,
+};
+
+fn network(timeout: Duration) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125_000));
+    let becu = network_builder.create_node("becu");
+    let secu = network_builder.create_node("secu");
+    // 4 frames of ~1ms every 5ms on the bus, which all have a
+    // higher priority than the command response.
+    for i in 0..4 {
+        becu.create_object_entry(&format!("value_{i}"), "u64");
+        let stream = becu.create_stream(&format!("stream_{i}"));
+        stream.add_entry(&format!("value_{i}"));
+        stream.set_interval(Duration::from_millis(5), Duration::from_millis(5));
+        stream.set_priority(MessagePriority::Normal);
+        secu.receive_stream("becu", &format!("stream_{i}")).unwrap();
+    }
+    let command = secu.create_command("reset", Some(Duration::from_millis(100)));
+    command.add_callee("becu").unwrap();
+    command.set_timeout(timeout);
+    network_builder.build().unwrap()
+}
+
+fn timeout_diagnostics(network: &NetworkRef) -> Vec<String> {
+    network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::CommandTimeout)
+        .map(|d| d.message().to_owned())
+        .collect()
+}
+
+#[test]
+fn round_trip_exceeding_the_timeout_is_reported() {
+    let network = network(Duration::from_millis(5));
+    let diagnostics = timeout_diagnostics(&network);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].contains("secu::reset"));
+    assert!(diagnostics[0].contains("lower priority"));
+
+    let command = &network
+        .nodes()
+        .iter()
+        .find(|node| node.name() == "secu")
+        .unwrap()
+        .commands()[0];
+    assert_eq!(command.timeout(), Some(Duration::from_millis(5)));
+    let req = simulator::worst_case_response_time(&network, command.tx_message()).unwrap();
+    let resp = simulator::worst_case_response_time(&network, command.rx_message()).unwrap();
+    assert!(resp > req);
+    assert!(req + resp > Duration::from_millis(5));
+}
+
+#[test]
+fn round_trip_within_the_timeout_is_accepted() {
+    let network = network(Duration::from_millis(100));
+    assert!(timeout_diagnostics(&network).is_empty());
+}
+
+#[test]
+fn response_time_of_a_single_frame_is_its_frame_time() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(500_000));
+    let message = network_builder.create_message_with_interval("single", Duration::from_millis(10));
+    message.set_std_id(0x0);
+    let network = network_builder.build().unwrap();
+    let single = network
+        .messages()
+        .iter()
+        .find(|message| message.name() == "single")
+        .unwrap();
+    let response_time = simulator::worst_case_response_time(&network, single).unwrap();
+    // every other message of the network (heartbeat, get/set) has a lower
+    // priority, so the frame is at most blocked by the longest possible frame.
+    let frame_time = network.frame_time_model().frame_time(false, single.dlc() as usize, 500_000);
+    assert!(response_time >= frame_time);
+    assert!(response_time <= frame_time + network.frame_time_model().frame_time(true, 8, 500_000));
+}
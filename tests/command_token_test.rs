@@ -0,0 +1,68 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn token_commands_and_owner_are_generated() {
+    let network_builder = NetworkBuilder::new();
+    let becu = network_builder.create_node("becu");
+    network_builder.create_node("secu");
+    network_builder.create_node("pdu");
+    let calibrate = becu.create_command("calibrate", None);
+    calibrate.require_token("calibration");
+    calibrate.add_callee("secu").unwrap();
+    let zero = becu.create_command("zero", None);
+    zero.require_token("calibration");
+    zero.add_callee("pdu").unwrap();
+    let reset = becu.create_command("reset", None);
+    reset.add_callee("pdu").unwrap();
+    let network = network_builder.build().unwrap();
+
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    let command = |name: &str| {
+        becu.commands()
+            .iter()
+            .find(|command| command.name() == name)
+            .unwrap_or_else(|| panic!("command {name} is missing"))
+            .clone()
+    };
+    assert_eq!(command("calibrate").token(), Some("calibration"));
+    assert_eq!(command("zero").token(), Some("calibration"));
+    assert_eq!(command("reset").token(), None);
+    for name in ["calibration_token_request", "calibration_token_release"] {
+        assert_eq!(command(name).token(), None);
+    }
+    assert_eq!(
+        becu.commands()
+            .iter()
+            .filter(|command| command.name().ends_with("_token_request"))
+            .count(),
+        1
+    );
+    assert!(becu
+        .object_entries()
+        .iter()
+        .any(|oe| oe.name() == "calibration_token_owner"));
+
+    // all callers of a command of the group can request the token.
+    for caller in ["secu", "pdu"] {
+        let node = network.nodes().iter().find(|node| node.name() == caller).unwrap();
+        for name in ["calibration_token_request", "calibration_token_release"] {
+            assert!(node
+                .extern_commands()
+                .iter()
+                .any(|(_, command)| command.name() == name));
+        }
+    }
+}
+
+#[test]
+fn nodes_without_token_groups_are_unchanged() {
+    let network_builder = NetworkBuilder::new();
+    let becu = network_builder.create_node("becu");
+    becu.create_command("reset", None).add_callee("secu").unwrap();
+    let network = network_builder.build().unwrap();
+    assert!(network
+        .nodes()
+        .iter()
+        .flat_map(|node| node.commands())
+        .all(|command| !command.name().contains("token")));
+}
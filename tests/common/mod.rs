@@ -0,0 +1,52 @@
+// helpers shared by the integration tests, a test file includes
+// them with `mod common;` and only uses some of them.
+#![allow(dead_code)]
+
+/// true if a python3 interpreter can be run. Tests that need one
+/// return early otherwise, the skip is logged to stderr.
+pub fn python3_available(test: &str) -> bool {
+    match std::process::Command::new("python3").arg("--version").output() {
+        Ok(output) if output.status.success() => true,
+        _ => {
+            eprintln!("skipping {test}: no python3 interpreter available");
+            false
+        }
+    }
+}
+
+/// network of the codegen tests, an enum and a struct that are
+/// transmitted by a stream of becu.
+pub fn codegen_network() -> canzero_config::config::NetworkRef {
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder
+        .define_constant("MAX_BRAKE_PRESSURE", 180.0, "bar")
+        .unwrap();
+    let state = network_builder.define_enum("brake_state");
+    state.add_entry("idle", Some(0)).unwrap();
+    state.add_entry("engaged", Some(1)).unwrap();
+    let vec3 = network_builder.define_struct("vec3");
+    vec3.add_attribute("x", "d16<-10..10>").unwrap();
+    vec3.add_attribute("y", "i12").unwrap();
+    vec3.add_attribute("z", "u4").unwrap();
+
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("state", "brake_state");
+    let acceleration = becu.create_object_entry("acceleration", "vec3");
+    acceleration.add_unit("m/s^2");
+    becu.create_object_entry("brake_offset", "d16<-1..1>").persistent();
+    let stream = becu.create_stream("brake_stream");
+    stream.add_entry("state");
+    stream.add_entry("acceleration");
+    let network = network_builder.build().unwrap();
+    network
+        .expect_node("becu")
+        .with_object_entry("acceleration")
+        .with_tx_stream("brake_stream");
+    network
+        .expect_message("becu_stream_brake_stream")
+        .with_dlc(5)
+        .with_transmitter("becu")
+        .with_bus("can0");
+    network
+}
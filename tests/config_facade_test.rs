@@ -0,0 +1,26 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{Bus, BusRef, MessageUsage, Provenance, Signal, Stream, StreamRef, TypeUsage},
+};
+
+#[test]
+fn config_types_are_reexported() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    becu.create_stream("state").add_entry("pressure");
+    network_builder.create_node("secu").receive_stream("becu", "state").unwrap();
+    let network = network_builder.build().unwrap();
+
+    let bus: &BusRef = &network.buses()[0];
+    let _: &Bus = bus;
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    let stream: &StreamRef = &becu.tx_streams()[0];
+    let _: &Stream = stream;
+    let signal: &Signal = &stream.message().signals()[0];
+    assert_eq!(signal.size(), 16);
+    assert!(matches!(stream.message().usage(), MessageUsage::Stream(_)));
+    let _: &Provenance = network.provenance();
+    let _: Vec<TypeUsage> = network.type_usage();
+}
@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{
+        conformance::{ConformanceFrame, ConformanceKind, ConformanceScenario, ConformanceStep},
+        NetworkRef,
+    },
+};
+
+mod common;
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let position = network_builder.define_struct("position");
+    position.add_attribute("x", "u32").unwrap();
+    position.add_attribute("y", "u16").unwrap();
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    becu.create_object_entry("position", "position");
+    becu.create_command("reset", None).set_timeout(Duration::from_millis(20));
+    network_builder.set_master("master");
+    network_builder.build().unwrap()
+}
+
+fn scenario<'a>(scenarios: &'a [ConformanceScenario], name: &str) -> &'a ConformanceScenario {
+    scenarios
+        .iter()
+        .find(|scenario| scenario.name() == name)
+        .unwrap_or_else(|| panic!("no scenario {name}"))
+}
+
+fn field(frame: &ConformanceFrame, path: &str) -> u64 {
+    frame
+        .fields()
+        .iter()
+        .find(|(field, _)| field == path)
+        .map(|(_, value)| *value)
+        .unwrap_or(0)
+}
+
+fn expected_frames(scenario: &ConformanceScenario) -> Vec<&ConformanceFrame> {
+    scenario
+        .steps()
+        .iter()
+        .filter_map(|step| match step {
+            ConformanceStep::Expect { frame, .. } => Some(frame),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn get_requests_are_answered_with_the_value() {
+    let network = network();
+    let scenarios = network.conformance_scenarios();
+    let get = scenario(&scenarios, "get becu::pressure");
+    assert_eq!(get.kind(), ConformanceKind::Get);
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    let master = network.masters()[0].clone();
+    let pressure = becu.object_entries().iter().find(|oe| oe.name() == "pressure").unwrap();
+    let ConformanceStep::Send(req) = &get.steps()[1] else {
+        panic!("the tester sends the request");
+    };
+    assert_eq!(req.message(), "get_req");
+    let raw = pressure.id() as u64 | (master.id() as u64) << 13 | (becu.id() as u64) << 21;
+    assert_eq!(req.data(), &raw.to_le_bytes()[..4].to_vec());
+    let resp = expected_frames(get);
+    assert_eq!(resp.len(), 1);
+    assert_eq!(field(resp[0], "header.sof"), 1);
+    assert_eq!(field(resp[0], "header.eof"), 1);
+    assert_eq!(field(resp[0], "header.toggle"), 0);
+    assert_eq!(field(resp[0], "data"), 0x5555);
+    assert_eq!(&resp[0].data()[4..], &[0x55, 0x55, 0, 0]);
+}
+
+#[test]
+fn large_values_are_fragmented() {
+    let scenarios = network().conformance_scenarios();
+    let set = scenario(&scenarios, "set becu::position");
+    let fragments: Vec<&ConformanceFrame> = set
+        .steps()
+        .iter()
+        .filter_map(|step| match step {
+            ConformanceStep::Send(frame) => Some(frame),
+            _ => None,
+        })
+        .collect();
+    let headers: Vec<(u64, u64, u64)> = fragments
+        .iter()
+        .map(|frame| (field(frame, "header.sof"), field(frame, "header.eof"), field(frame, "header.toggle")))
+        .collect();
+    assert_eq!(headers, vec![(1, 0, 0), (0, 1, 1)]);
+    assert_eq!(field(fragments[0], "data"), 0x5555_5555);
+    assert_eq!(field(fragments[1], "data"), 0x5555);
+    let resp = expected_frames(set);
+    assert_eq!(field(resp[0], "header.erno"), 0);
+    let Some(ConformanceStep::Verify { value, .. }) = set.steps().last() else {
+        panic!("the value is verified after the set");
+    };
+    assert_eq!(value.bits(), 48);
+    assert_eq!(value.words(), 2);
+}
+
+#[test]
+fn sets_of_constant_entries_fail() {
+    let scenarios = network().conformance_scenarios();
+    let set_error = scenario(&scenarios, "set error becu::config_hash");
+    assert_eq!(set_error.kind(), ConformanceKind::SetError);
+    assert_eq!(field(expected_frames(set_error)[0], "header.erno"), 1);
+    let (ConformanceStep::Assume { value: before, .. }, Some(ConformanceStep::Verify { value: after, .. })) =
+        (&set_error.steps()[0], set_error.steps().last())
+    else {
+        panic!("the value is assumed and verified");
+    };
+    assert_eq!(before, after);
+    assert!(!scenarios.iter().any(|scenario| scenario.name() == "set becu::config_hash"));
+}
+
+#[test]
+fn commands_are_answered_within_their_timeout() {
+    let scenarios = network().conformance_scenarios();
+    let command = scenario(&scenarios, "command becu::reset");
+    let ConformanceStep::Expect { frame, timeout } = &command.steps()[1] else {
+        panic!("the node answers the command");
+    };
+    assert_eq!(frame.message(), "becu_reset_command_resp");
+    assert_eq!(*timeout, Some(Duration::from_millis(20)));
+    let json = network().generate_conformance_vectors();
+    assert!(json.contains("\"timeout_us\": 20000"));
+    assert!(json.contains("\"kind\": \"set_error\""));
+}
+
+#[test]
+fn frames_decode_with_the_generated_python() {
+    if !common::python3_available("frames_decode_with_the_generated_python") {
+        return;
+    }
+    let network = network();
+    let dir = std::env::temp_dir().join("canzero_conformance_vectors_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("canzero.py"), network.generate_python()).unwrap();
+    let mut script = String::from("import canzero\n");
+    for scenario in network.conformance_scenarios() {
+        for step in scenario.steps() {
+            let frame = match step {
+                ConformanceStep::Send(frame) | ConformanceStep::Expect { frame, .. } => frame,
+                _ => continue,
+            };
+            let data: Vec<String> = frame.data().iter().map(|byte| byte.to_string()).collect();
+            script.push_str(&format!(
+                "msg = canzero.decode({}, {}, bytes([{}]))\n",
+                frame.id().as_u32(),
+                if frame.id().ide() { "True" } else { "False" },
+                data.join(", ")
+            ));
+            for (path, value) in frame.fields() {
+                script.push_str(&format!("assert int(msg.{path}) == {value}, ({:?}, msg)\n", frame.message()));
+            }
+        }
+    }
+    let status = std::process::Command::new("python3")
+        .args(["-c", &script])
+        .current_dir(&dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
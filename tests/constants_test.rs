@@ -0,0 +1,92 @@
+use canzero_config::{builder::NetworkBuilder, config::ObjectEntryBound, errors::ConfigError};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder
+        .define_constant("MAX_BRAKE_PRESSURE", 180.0, "bar")
+        .unwrap();
+    let becu = network_builder.create_node("becu");
+    let pressure = becu.create_object_entry("brake_pressure", "d16<0..200>");
+    pressure.set_bounds("0", "MAX_BRAKE_PRESSURE");
+    network_builder
+}
+
+#[test]
+fn constants_as_object_entry_bounds() {
+    let network = network_builder().build().unwrap();
+    assert_eq!(network.constants().len(), 1);
+    let constant = &network.constants()[0];
+    assert_eq!(constant.name(), "MAX_BRAKE_PRESSURE");
+    assert_eq!(constant.unit(), "bar");
+
+    let becu = network.nodes().iter().find(|n| n.name() == "becu").unwrap();
+    let pressure = becu
+        .object_entries()
+        .iter()
+        .find(|oe| oe.name() == "brake_pressure")
+        .unwrap();
+    let bounds = pressure.bounds().unwrap();
+    assert!(matches!(bounds.min(), ObjectEntryBound::Value(v) if *v == 0.0));
+    match bounds.max() {
+        ObjectEntryBound::Constant(constant) => assert_eq!(constant.name(), "MAX_BRAKE_PRESSURE"),
+        other => panic!("expected a constant bound, got {other:?}"),
+    }
+    assert_eq!(bounds.max().value(), 180.0);
+}
+
+#[test]
+fn constants_are_exported() {
+    let network = network_builder().build().unwrap();
+    assert!(network
+        .generate_cpp()
+        .contains("inline constexpr double MAX_BRAKE_PRESSURE = 180.0; // bar"));
+    assert!(network
+        .generate_python()
+        .contains("MAX_BRAKE_PRESSURE = 180.0  # bar"));
+    assert!(network
+        .generate_typescript()
+        .contains("export const MAX_BRAKE_PRESSURE = 180.0;"));
+    let json = network.to_json();
+    assert!(json.contains("\"constant\": \"MAX_BRAKE_PRESSURE\""));
+}
+
+#[test]
+fn constants_undefined_or_duplicated() {
+    let network_builder = network_builder();
+    assert!(matches!(
+        network_builder.define_constant("MAX_BRAKE_PRESSURE", 1.0, "bar"),
+        Err(ConfigError::DuplicatedConstant(_))
+    ));
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("temperature", "u8")
+        .set_bounds("MIN_TEMPERATURE", "100");
+    match network_builder.build() {
+        Err(ConfigError::UndefinedConstant(msg)) => assert!(msg.contains("MIN_TEMPERATURE")),
+        other => panic!("expected an undefined constant, got {other:?}"),
+    }
+}
+
+#[test]
+fn constants_empty_bounds() {
+    let network_builder = network_builder();
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("temperature", "u8")
+        .set_bounds("MAX_BRAKE_PRESSURE", "100");
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidRange(_))
+    ));
+}
+
+#[test]
+fn constants_invalid_names() {
+    let network_builder = NetworkBuilder::new();
+    for name in ["2PI", "max-speed", "", "static"] {
+        assert!(matches!(
+            network_builder.define_constant(name, 1.0, "1"),
+            Err(ConfigError::InvalidConstant(_))
+        ));
+    }
+    network_builder.define_constant("MAX_SPEED", 120.0, "m/s").unwrap();
+    assert_eq!(network_builder.build().unwrap().constants().len(), 1);
+}
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessageBuilder, NetworkBuilder},
+    config::{message::MessageUsage, NetworkRef},
+};
+
+fn interval(network: &NetworkRef, name: &str) -> Duration {
+    let message = network.messages().iter().find(|message| message.name() == name).unwrap();
+    match message.usage() {
+        MessageUsage::External { interval } => *interval,
+        _ => panic!("{name} is not an external message"),
+    }
+}
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    network_builder.create_node("secu");
+    network_builder
+}
+
+fn wire(message: &MessageBuilder) {
+    message.add_transmitter("becu");
+    message.add_receiver("secu");
+}
+
+#[test]
+fn messages_without_interval_use_the_default() {
+    let network_builder = network_builder();
+    wire(&network_builder.create_message("status"));
+    let network = network_builder.build().unwrap();
+    assert_eq!(interval(&network, "status"), Duration::from_secs(60));
+}
+
+#[test]
+fn intervals_can_be_given_on_creation_or_later() {
+    let network_builder = network_builder();
+    wire(&network_builder.create_message_with_interval("created", Duration::from_millis(10)));
+    wire(
+        &network_builder
+            .create_message("chained")
+            .expect_interval(Duration::from_millis(10)),
+    );
+    let network = network_builder.build().unwrap();
+    assert_eq!(interval(&network, "created"), Duration::from_millis(10));
+    assert_eq!(interval(&network, "chained"), Duration::from_millis(10));
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{
+        deadline_priorities::{deadline_priority, DEFAULT_DEADLINE_THRESHOLDS},
+        MessagePriority, NetworkBuilder,
+    },
+    config::NetworkRef,
+    diagnostics::DiagnosticKind,
+};
+
+fn network(deadline_priorities: bool) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let sensor = network_builder.create_node("sensor");
+    let master = network_builder.create_node("master");
+    for (name, priority, deadline) in [
+        // hand-assigned priorities, that contradict the deadlines.
+        ("brake", MessagePriority::Low, Some(Duration::from_micros(800))),
+        ("position", MessagePriority::Realtime, Some(Duration::from_millis(10))),
+        ("temperature", MessagePriority::High, None),
+    ] {
+        sensor.create_object_entry(name, "u16");
+        let stream = sensor.create_stream(name);
+        stream.add_entry(name);
+        stream.set_priority(priority);
+        if let Some(deadline) = deadline {
+            stream.set_deadline(deadline);
+        }
+        master.receive_stream("sensor", name).unwrap();
+    }
+    let fixed = network_builder.create_message("fixed");
+    fixed.set_std_id(0x7F0);
+    fixed.set_deadline(Duration::from_micros(500));
+    fixed.add_transmitter("master");
+    fixed.add_receiver("sensor");
+    if deadline_priorities {
+        network_builder.enable_deadline_priorities(DEFAULT_DEADLINE_THRESHOLDS);
+    }
+    network_builder.build().unwrap()
+}
+
+fn requested_priority(network: &NetworkRef, name: &str) -> Option<u32> {
+    let message = network.messages().iter().find(|m| m.name() == name).unwrap();
+    message.resolution_info().requested_priority()
+}
+
+fn id(network: &NetworkRef, name: &str) -> u32 {
+    network.messages().iter().find(|m| m.name() == name).unwrap().id().as_u32()
+}
+
+#[test]
+fn deadlines_map_to_buckets() {
+    let priority = |us| deadline_priority(Duration::from_micros(us), &DEFAULT_DEADLINE_THRESHOLDS);
+    assert_eq!(priority(200), MessagePriority::Realtime);
+    assert_eq!(priority(1000), MessagePriority::Realtime);
+    assert_eq!(priority(1001), MessagePriority::High);
+    assert_eq!(priority(20_000), MessagePriority::Normal);
+    assert_eq!(priority(50_000), MessagePriority::Low);
+    assert_eq!(priority(1_000_000), MessagePriority::SuperLow);
+}
+
+#[test]
+fn requested_priorities_are_kept_by_default() {
+    let network = network(false);
+    assert_eq!(requested_priority(&network, "sensor_stream_brake"), Some(3));
+    assert_eq!(requested_priority(&network, "sensor_stream_position"), Some(0));
+}
+
+#[test]
+fn deadlines_select_the_priorities() {
+    let network = network(true);
+    assert_eq!(requested_priority(&network, "sensor_stream_brake"), Some(0));
+    assert_eq!(requested_priority(&network, "sensor_stream_position"), Some(2));
+    // messages without a deadline and fixed ids are untouched.
+    assert_eq!(requested_priority(&network, "sensor_stream_temperature"), Some(1));
+    assert_eq!(id(&network, "fixed"), 0x7F0);
+    assert!(id(&network, "sensor_stream_brake") < id(&network, "sensor_stream_position"));
+    assert!(network.verify_assignment().is_empty());
+}
+
+#[test]
+fn missed_deadlines_are_reported() {
+    let network = network(true);
+    let misses: Vec<&str> = network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::DeadlineMiss)
+        .map(|d| d.message())
+        .collect();
+    // the fixed id loses the arbitration against every stream.
+    assert_eq!(misses.len(), 1, "{misses:?}");
+    assert!(misses[0].contains("fixed"), "{misses:?}");
+    assert!(network.to_json().contains("\"deadline_us\": 800"));
+}
@@ -0,0 +1,88 @@
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef};
+
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("cpu_temp", "u8");
+    secu.create_command("reset", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    let legacy = network_builder.create_message("legacy");
+    legacy.set_ext_id(0x1234);
+    legacy.add_transmitter("becu");
+    legacy.add_receiver("secu");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn c_defines_contain_ids_and_indices() {
+    let network = build_network();
+    let header = network.generate_c_defines();
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    let cpu_temp = secu
+        .object_entries()
+        .iter()
+        .find(|oe| oe.name() == "cpu_temp")
+        .unwrap();
+    assert!(header.contains(&format!("#define CANZERO_SECU_NODE_ID {}\n", secu.id())));
+    assert!(header.contains(&format!(
+        "#define CANZERO_SECU_CPU_TEMP_OE_INDEX {}\n",
+        cpu_temp.id()
+    )));
+    assert!(header.contains("#define CANZERO_LEGACY_ID 0x1234\n"));
+    assert!(header.contains("#define CANZERO_LEGACY_IDE 1\n"));
+    let reset = &secu.commands()[0];
+    assert!(header.contains(&format!(
+        "#define CANZERO_SECU_RESET_COMMAND_REQ_ID {:#X}\n",
+        reset.tx_message().id().as_u32()
+    )));
+    assert!(header.contains(
+        "#define CANZERO_SECU_RESET_CMD_REQ_ID CANZERO_SECU_RESET_COMMAND_REQ_ID\n"
+    ));
+    assert!(header.contains(
+        "#define CANZERO_SECU_RESET_CMD_RESP_ID CANZERO_SECU_RESET_COMMAND_RESP_ID\n"
+    ));
+    for message in network.messages() {
+        assert!(header.contains(&format!(
+            "#define CANZERO_{}_ID {:#X}\n",
+            message.name().to_uppercase(),
+            message.id().as_u32()
+        )));
+    }
+}
+
+#[test]
+fn rust_defines_mirror_c_defines() {
+    let network = build_network();
+    let header = network.generate_c_defines();
+    let module = network.generate_rust_defines();
+    let count = |s: &str, prefix: &str| s.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(count(&header, "#define "), count(&module, "pub const "));
+    assert!(module.contains("pub const CANZERO_LEGACY_ID: u32 = 0x1234;"));
+    assert!(module.contains(
+        "pub const CANZERO_SECU_RESET_CMD_REQ_ID: u32 = CANZERO_SECU_RESET_COMMAND_REQ_ID;"
+    ));
+}
+
+#[test]
+fn rust_defines_compile_without_std() {
+    let Ok(compiler) = std::process::Command::new("rustc").arg("--version").output() else {
+        return;
+    };
+    if !compiler.status.success() {
+        return;
+    }
+    let network = build_network();
+    let dir = std::env::temp_dir().join("canzero_defines_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let source = dir.join("defines.rs");
+    std::fs::write(&source, format!("#![no_std]\n{}", network.generate_rust_defines())).unwrap();
+    let status = std::process::Command::new("rustc")
+        .args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata", "-D", "warnings", "--out-dir"])
+        .arg(&dir)
+        .arg(&source)
+        .status()
+        .unwrap();
+    assert!(status.success(), "defines don't compile");
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::stream::{DeltaEncoding, DeltaRollover},
+    errors::ConfigError,
+};
+
+fn imu_network(width: u8) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let imu = network_builder.create_node("imu");
+    let master = network_builder.create_node("master");
+    imu.create_object_entry("acceleration", "d16<-20..20>");
+    imu.create_object_entry("heading", "u16");
+    imu.create_object_entry("state", "u2");
+    master.create_object_entry("acceleration", "d16<-20..20>");
+    master.create_object_entry("heading", "u16");
+    let stream = imu.create_stream("motion");
+    stream.set_interval(Duration::from_millis(1), Duration::from_millis(1));
+    stream.add_delta_entry("acceleration", width, DeltaRollover::Saturate);
+    stream.add_delta_entry("heading", width, DeltaRollover::Wrap);
+    stream.add_entry("state");
+    let rx_stream = master.receive_stream("imu", "motion").unwrap();
+    rx_stream.map("acceleration", "acceleration");
+    rx_stream.map("heading", "heading");
+    network_builder
+}
+
+#[test]
+fn delta_entries_use_narrow_signals() {
+    let network = imu_network(6).build().unwrap();
+    let imu = network.expect_node("imu");
+    let stream = imu.node().tx_streams().iter().find(|s| s.name() == "motion").unwrap();
+    assert_eq!(
+        stream.delta_encodings(),
+        &vec![
+            Some(DeltaEncoding::new(6, 16, DeltaRollover::Saturate)),
+            Some(DeltaEncoding::new(6, 16, DeltaRollover::Wrap)),
+            None,
+        ]
+    );
+    // 2 * 6 bits of deltas + 2 bits of state instead of 34 bits.
+    network.expect_message("imu_stream_motion").with_dlc(2);
+
+    // the receiver has to know how to decode the deltas.
+    let master = network.expect_node("master");
+    let rx_stream = master.node().rx_streams().iter().find(|s| s.name() == "motion").unwrap();
+    assert_eq!(rx_stream.delta_encodings(), stream.delta_encodings());
+    assert!(network.to_json().contains("\"rollover\": \"wrap\""));
+}
+
+#[test]
+fn delta_width_has_to_be_narrower_than_the_entry() {
+    for width in [1, 16, 20] {
+        match imu_network(width).build() {
+            Err(ConfigError::InvalidDeltaEncoding(_)) => (),
+            other => panic!("expected InvalidDeltaEncoding for width {width}, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::ObjectEntryAccess,
+    diagnostics::{DiagnosticKind, Severity},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    // written by the node itself, but never streamed.
+    becu.create_object_entry("unused", "u8").set_access(ObjectEntryAccess::Local);
+    // parameters are accessed with get/set requests.
+    becu.create_object_entry("gain", "u8");
+    let stream = becu.create_stream("pressure");
+    stream.add_entry("pressure");
+    let master = network_builder.create_node("master");
+    master.create_object_entry("pressure", "u16");
+    master.receive_stream("becu", "pressure").unwrap().map("pressure", "pressure");
+    let orphan = network_builder.create_message("orphan");
+    orphan.set_std_id(0x10);
+    network_builder
+}
+
+#[test]
+fn diagnostics_are_collected() {
+    let network = network_builder().build().unwrap();
+    let diagnostics = network.diagnostics();
+    assert!(diagnostics.iter().any(|d| d.kind() == DiagnosticKind::EmptyMessage
+        && d.message().contains("orphan")));
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::MessageWithoutReceivers
+            && d.message().contains("orphan")));
+    assert!(diagnostics
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::ObjectEntryNotStreamed
+            && d.message().contains("becu::unused")));
+    assert!(!diagnostics
+        .iter()
+        .any(|d| d.message().contains("becu::pressure")));
+    assert!(!diagnostics.iter().any(|d| d.message().contains("becu::gain")));
+    assert!(!diagnostics.iter().any(|d| d.message().contains("::config_hash")
+        || d.message().contains("::build_time")));
+    assert!(diagnostics.iter().all(|d| d.severity() == Severity::Warn));
+}
+
+#[test]
+fn diagnostics_allowed_are_dropped() {
+    let network_builder = network_builder();
+    network_builder.set_diagnostic_severity(DiagnosticKind::EmptyMessage, Severity::Allow);
+    let network = network_builder.build().unwrap();
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::EmptyMessage));
+}
+
+#[test]
+fn diagnostics_deny_warnings() {
+    let network_builder = network_builder();
+    network_builder.deny_warnings();
+    match network_builder.build() {
+        Err(ConfigError::DeniedDiagnostics(msg)) => assert!(msg.contains("orphan")),
+        other => panic!("expected denied diagnostics, got {other:?}"),
+    }
+}
+
+#[test]
+fn deny_warnings_accepts_a_clean_network() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16").set_access(ObjectEntryAccess::Local);
+    becu.create_object_entry("gain", "u8");
+    let stream = becu.create_stream("pressure");
+    stream.add_entry("pressure");
+    let master = network_builder.create_node("master");
+    master.create_object_entry("pressure", "u16");
+    master.receive_stream("becu", "pressure").unwrap().map("pressure", "pressure");
+    network_builder.deny_warnings();
+    let network = network_builder.build().unwrap();
+    assert!(network.diagnostics().is_empty());
+}
+
+#[test]
+fn diagnostics_bus_load() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125000));
+    let becu = network_builder.create_node("becu");
+    for i in 0..20 {
+        becu.create_object_entry(&format!("value{i}"), "u64");
+        let stream = becu.create_stream(&format!("stream{i}"));
+        stream.add_entry(&format!("value{i}"));
+        stream.set_interval(
+            std::time::Duration::from_millis(1),
+            std::time::Duration::from_millis(1),
+        );
+    }
+    let network = network_builder.build().unwrap();
+    assert!(network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::BusLoad));
+}
@@ -0,0 +1,50 @@
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef};
+
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("position", "u32");
+    let stream = secu.create_stream("realtime");
+    stream.add_entry("position");
+    let master = network_builder.create_node("master");
+    master.receive_stream("secu", "realtime").unwrap().map("position", "position");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn expect_message_and_node() {
+    let network = build_network();
+    network
+        .expect_message("secu_stream_realtime")
+        .with_dlc(4)
+        .with_bus("can0")
+        .with_transmitter("secu")
+        .with_receiver("master");
+    network
+        .expect_node("secu")
+        .with_object_entry("position")
+        .with_tx_stream("realtime");
+}
+
+#[test]
+#[should_panic(expected = "expected message secu_stream_realtime to be received by secu, receivers are [\"master\"]")]
+fn expect_message_wrong_receiver() {
+    build_network()
+        .expect_message("secu_stream_realtime")
+        .with_receiver("secu");
+}
+
+#[test]
+#[should_panic(expected = "expected network to contain message missing")]
+fn expect_message_missing() {
+    build_network().expect_message("missing");
+}
+
+#[test]
+#[should_panic(expected = "expected node master to define object entry speed")]
+fn expect_node_missing_object_entry() {
+    build_network()
+        .expect_node("master")
+        .with_object_entry("speed");
+}
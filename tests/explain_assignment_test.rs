@@ -0,0 +1,54 @@
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+#[test]
+fn explanation_of_a_resolved_message() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    network_builder.create_node("becu");
+    let secu = network_builder.create_node("secu");
+    secu.assign_bus("can1");
+    let message = network_builder.create_message("brake");
+    message.set_any_std_id(MessagePriority::High);
+    message.add_transmitter("becu");
+    message.add_receiver("secu");
+    let network = network_builder.build().unwrap();
+
+    let explanation = network.explain_assignment("brake").unwrap();
+    let brake = network.messages().iter().find(|m| m.name() == "brake").unwrap();
+    assert_eq!(explanation.id(), brake.id());
+    assert_eq!(explanation.transmitters(), &vec!["becu".to_owned()]);
+    assert_eq!(explanation.receivers(), &vec!["secu".to_owned()]);
+    assert!(!explanation.is_fixed());
+    // secu is only connected to can1.
+    assert_eq!(explanation.candidate_buses().len(), 1);
+    assert_eq!(explanation.candidate_buses()[0].0, "can1");
+    assert_eq!(explanation.resolution_info().bus().name(), "can1");
+    assert!(explanation.message_load() > 0.0);
+    assert!(explanation.candidate_buses()[0].1 >= explanation.message_load());
+
+    let text = explanation.to_string();
+    let info = brake.resolution_info();
+    assert!(text.starts_with(&format!("message brake : id {:#X} (std)\n", brake.id().as_u32())));
+    assert!(text.contains("  receivers : secu\n"));
+    assert!(text.contains(&format!(
+        "  setcode : {:#b} ({} bits)\n",
+        info.setcode().unwrap(),
+        info.setcode_len()
+    )));
+    assert!(text.contains(&format!("  position : {}\n", info.position().unwrap())));
+}
+
+#[test]
+fn explanation_of_a_fixed_id() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("becu");
+    let fixed = network_builder.create_message("fixed");
+    fixed.set_std_id(0x7F0);
+    fixed.add_receiver("becu");
+    let network = network_builder.build().unwrap();
+    let explanation = network.explain_assignment("fixed").unwrap();
+    assert!(explanation.is_fixed());
+    assert!(explanation.to_string().contains("  fixed id, not resolved\n"));
+    assert!(network.explain_assignment("unknown").is_none());
+}
@@ -0,0 +1,66 @@
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+mod common;
+
+fn build_network() -> canzero_config::config::NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.add_description("brake \"control\" unit");
+    becu.create_object_entry("pressure", "d16<0..100>");
+    let stream = becu.create_stream("pressure");
+    stream.add_entry("pressure");
+    let status = network_builder.create_message("status");
+    status.set_any_std_id(MessagePriority::Low);
+    status.add_receiver("becu");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn export_bundle_contains_all_sections() {
+    let network = build_network();
+    let dir = std::env::temp_dir().join("canzero_export_bundle_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("bundle.json");
+    network.export_bundle(&path).unwrap();
+    let bundle = std::fs::read_to_string(&path).unwrap();
+    assert!(bundle.contains(&format!("\"hash\": \"{:#X}\"", network.portable_hash())));
+    assert!(bundle.contains(&network.build_time().to_rfc3339()));
+    for section in ["\"network\"", "\"resolution\"", "\"dbc\"", "\"checksum\""] {
+        assert!(bundle.contains(section), "missing section {section}");
+    }
+    // the bundle is deterministic.
+    assert_eq!(bundle, network.bundle());
+
+    if !common::python3_available("export_bundle_contains_all_sections") {
+        return;
+    }
+    let output = std::process::Command::new("python3")
+        .args(["-c", "import json,sys; json.load(open(sys.argv[1]))"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn export_dbc_can_be_parsed() {
+    let network = build_network();
+    let dbc = network.generate_dbc();
+    let parsed = can_dbc::DBC::from_slice(dbc.as_bytes()).unwrap();
+    assert_eq!(parsed.messages().len(), network.messages().len());
+    for message in network.messages() {
+        let parsed_message = parsed
+            .messages()
+            .iter()
+            .find(|m| m.message_name() == message.name())
+            .unwrap();
+        assert_eq!(parsed_message.message_id().0 & 0x1FFFFFFF, message.id().as_u32());
+        assert_eq!(*parsed_message.message_size(), message.dlc() as u64);
+        assert_eq!(parsed_message.signals().len(), message.signals().len());
+        for (parsed_signal, signal) in parsed_message.signals().iter().zip(message.signals()) {
+            assert_eq!(*parsed_signal.start_bit(), signal.byte_offset() as u64);
+            assert_eq!(*parsed_signal.signal_size(), signal.size() as u64);
+        }
+    }
+}
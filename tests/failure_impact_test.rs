@@ -0,0 +1,70 @@
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef};
+
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    becu.create_object_entry("debug", "u8");
+    let stream = becu.create_stream("state");
+    stream.add_entry("pressure");
+    stream.add_entry("debug");
+    let engage = becu.create_command("engage", None);
+    engage.add_callee("master").unwrap();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("becu_pressure", "u16");
+    let rx_stream = secu.receive_stream("becu", "state").unwrap();
+    rx_stream.map("pressure", "becu_pressure");
+    network_builder.create_node("master").receive_stream("becu", "state").unwrap();
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn failure_of_a_node_reports_lost_items() {
+    let network = build_network();
+    let impacts = network.failure_impact();
+    assert_eq!(impacts.len(), network.nodes().len());
+    let becu = impacts.iter().find(|impact| impact.node() == "becu").unwrap();
+
+    let state = becu.streams().iter().find(|s| s.name() == "state").unwrap();
+    assert_eq!(state.receivers(), &vec!["master".to_owned(), "secu".to_owned()]);
+
+    let engage = becu.commands().iter().find(|c| c.name() == "engage").unwrap();
+    assert_eq!(engage.receivers(), &vec!["master".to_owned()]);
+
+    let pressure = becu
+        .object_entries()
+        .iter()
+        .find(|oe| oe.name() == "pressure")
+        .unwrap();
+    assert_eq!(pressure.receivers(), &vec!["secu".to_owned()]);
+    // only available with get requests.
+    let debug = becu.object_entries().iter().find(|oe| oe.name() == "debug").unwrap();
+    assert!(debug.receivers().is_empty());
+
+    assert_eq!(becu.affected_nodes(), vec!["master".to_owned(), "secu".to_owned()]);
+}
+
+#[test]
+fn failure_of_a_leaf_node_has_no_impact() {
+    let network = build_network();
+    let secu = network
+        .failure_impact()
+        .into_iter()
+        .find(|impact| impact.node() == "secu")
+        .unwrap();
+    assert!(secu.affected_nodes().is_empty());
+}
+
+#[test]
+fn failure_impact_matrix() {
+    let network = build_network();
+    let matrix = network.generate_failure_impact_matrix();
+    let row = matrix.lines().find(|line| line.starts_with("| becu |")).unwrap();
+    let columns: Vec<&str> = matrix.lines().next().unwrap().split('|').map(str::trim).collect();
+    let cells: Vec<&str> = row.split('|').map(str::trim).collect();
+    let cell = |node: &str| cells[columns.iter().position(|c| *c == node).unwrap()];
+    assert_eq!(cell("becu"), "-");
+    assert_eq!(cell("secu"), "streams: state; object entries: pressure");
+    assert_eq!(cell("master"), "streams: state; commands: engage");
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "test-util")]
+
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{Assignment, AssignmentViolation, NetworkRef},
+    errors::ConfigError,
+    test_util::Perturbation,
+};
+
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let bms = network_builder.create_node("bms");
+    let master = network_builder.create_node("master");
+    let pdu = network_builder.create_node("pdu");
+    bms.create_object_entry("voltage", "u16");
+    bms.create_object_entry("current", "u16");
+    master.create_object_entry("bms_voltage", "u16");
+    pdu.create_object_entry("bms_current", "u16");
+    for (stream, entry, rx_node, rx_entry, priority) in [
+        ("voltage", "voltage", &master, "bms_voltage", MessagePriority::Realtime),
+        ("current", "current", &pdu, "bms_current", MessagePriority::Low),
+    ] {
+        let stream_builder = bms.create_stream(stream);
+        stream_builder.set_priority(priority);
+        stream_builder.set_interval(Duration::from_millis(10), Duration::from_millis(100));
+        stream_builder.add_entry(entry);
+        rx_node.receive_stream("bms", stream).unwrap().map(entry, rx_entry);
+    }
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn built_assignment_is_valid() {
+    let network = build_network();
+    assert_eq!(network.verify_assignment(), vec![]);
+}
+
+#[test]
+fn swapped_ids_are_detected() {
+    let network = build_network();
+    let assignment = Assignment::of(&network);
+    let perturbed = assignment
+        .perturbed(&Perturbation::SwapIds(
+            "bms_stream_voltage".to_owned(),
+            "bms_stream_current".to_owned(),
+        ))
+        .unwrap();
+    let violations = perturbed.verify();
+    assert!(violations.contains(&AssignmentViolation::RejectedMessage {
+        node: "master".to_owned(),
+        message: "bms_stream_voltage".to_owned(),
+    }), "{violations:?}");
+}
+
+#[test]
+fn swapped_priorities_are_detected() {
+    let network = build_network();
+    let perturbed = Assignment::of(&network)
+        .perturbed(&Perturbation::SwapIds(
+            "bms_stream_voltage".to_owned(),
+            "bms_stream_current".to_owned(),
+        ))
+        .unwrap();
+    let violations = perturbed.verify();
+    assert!(violations.iter().any(|violation| matches!(
+        violation,
+        AssignmentViolation::PriorityInversion { higher, lower, .. }
+            if higher == "bms_stream_voltage" && lower == "bms_stream_current"
+    )), "{violations:?}");
+}
+
+#[test]
+fn dropped_filters_are_detected() {
+    let network = build_network();
+    let mut assignment = Assignment::of(&network);
+    let filters = assignment
+        .filters()
+        .iter()
+        .find(|filters| filters.node().name() == "master")
+        .unwrap()
+        .filters()
+        .len();
+    for index in (0..filters).rev() {
+        assignment
+            .perturb(&Perturbation::DropFilter { node: "master".to_owned(), index })
+            .unwrap();
+    }
+    assert!(assignment.verify().iter().any(|violation| matches!(
+        violation,
+        AssignmentViolation::RejectedMessage { node, .. } if node == "master"
+    )));
+}
+
+#[test]
+fn invalid_perturbations_fail() {
+    let mut assignment = Assignment::of(&build_network());
+    let result = assignment.perturb(&Perturbation::SwapIds("unknown".to_owned(), "heartbeat".to_owned()));
+    assert!(matches!(result, Err(ConfigError::InvalidFaultInjection(_))));
+    let result = assignment.perturb(&Perturbation::DropFilter { node: "master".to_owned(), index: 1000 });
+    assert!(matches!(result, Err(ConfigError::InvalidFaultInjection(_))));
+}
+
+#[test]
+fn perturbations_enumerate_swaps_and_filters() {
+    let assignment = Assignment::of(&build_network());
+    let perturbations = assignment.perturbations();
+    assert!(perturbations.contains(&Perturbation::SwapIds(
+        "bms_stream_voltage".to_owned(),
+        "bms_stream_current".to_owned(),
+    )) || perturbations.contains(&Perturbation::SwapIds(
+        "bms_stream_current".to_owned(),
+        "bms_stream_voltage".to_owned(),
+    )));
+    assert!(perturbations.iter().any(|p| matches!(p, Perturbation::DropFilter { node, .. } if node == "pdu")));
+}
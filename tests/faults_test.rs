@@ -0,0 +1,81 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{FaultSeverity, Type},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.define_fault("overcurrent", FaultSeverity::Critical)
+        .unwrap();
+    becu.define_fault("overtemperature", FaultSeverity::Warning)
+        .unwrap();
+    let pdu = network_builder.create_node("pdu");
+    pdu.define_fault("overcurrent", FaultSeverity::Error).unwrap();
+    network_builder.create_node("master");
+    network_builder
+}
+
+#[test]
+fn faults_have_unique_codes() {
+    let network = network_builder().build().unwrap();
+    let mut codes = vec![];
+    for node in network.nodes() {
+        for fault in node.faults() {
+            assert!(!codes.contains(&fault.code()));
+            codes.push(fault.code());
+        }
+    }
+    assert_eq!(codes.len(), 3);
+
+    let becu = network.nodes().iter().find(|n| n.name() == "becu").unwrap();
+    let overcurrent = &becu.faults()[0];
+    assert_eq!(overcurrent.name(), "overcurrent");
+    assert_eq!(overcurrent.severity(), FaultSeverity::Critical);
+    assert_eq!(overcurrent.object_entry().name(), "fault_overcurrent");
+
+    let fault_code = network
+        .types()
+        .iter()
+        .find(|ty| ty.name() == "fault_code")
+        .unwrap();
+    match fault_code as &Type {
+        Type::Enum { entries, .. } => {
+            assert!(entries
+                .iter()
+                .any(|(name, code)| name == "becu_overcurrent" && *code == overcurrent.code()));
+            assert_eq!(entries.len(), 3);
+        }
+        other => panic!("expected fault_code to be an enum, got {other:?}"),
+    }
+}
+
+#[test]
+fn faults_status_stream_and_clear_command() {
+    let network = network_builder().build().unwrap();
+    let becu = network.nodes().iter().find(|n| n.name() == "becu").unwrap();
+    let stream = becu
+        .tx_streams()
+        .iter()
+        .find(|s| s.name() == "fault_status")
+        .unwrap();
+    let mapped: Vec<&str> = stream.mapping().iter().flatten().map(|oe| oe.name()).collect();
+    assert_eq!(mapped, vec!["fault_overcurrent", "fault_overtemperature"]);
+    assert!(becu.commands().iter().any(|c| c.name() == "clear_fault"));
+
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    assert!(master.faults().is_empty());
+    assert!(!master.tx_streams().iter().any(|s| s.name() == "fault_status"));
+}
+
+#[test]
+fn faults_duplicated() {
+    let network_builder = network_builder();
+    let becu = network_builder.create_node("becu");
+    assert!(matches!(
+        becu.define_fault("overcurrent", FaultSeverity::Info),
+        Err(ConfigError::DuplicatedFault(_))
+    ));
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{resolve_filters_only, MessagePriority, NetworkBuilder},
+    config::{
+        filter_export::{BxCanFilterBank, BxCanFilterMode},
+        Filter, FilterRegisters, HardwareTarget, NetworkRef, NodeFilters,
+    },
+    errors::ConfigError,
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    for node in ["master", "bms", "pdu"] {
+        network_builder.create_node(node);
+    }
+    for (name, receivers) in [
+        ("bms_state", &["master"][..]),
+        ("pdu_state", &["master", "bms"][..]),
+        ("master_state", &["bms", "pdu"][..]),
+    ] {
+        let message = network_builder.create_message_with_interval(name, Duration::from_millis(10));
+        message.set_any_std_id(MessagePriority::Normal);
+        for rx in receivers {
+            message.add_receiver(rx);
+        }
+        message.make_type_format().add_type("u8", "value");
+    }
+    let fixed = network_builder.create_message("fixed_ext");
+    fixed.set_ext_id(0x1234567);
+    fixed.add_receiver("master");
+    network_builder.build().unwrap()
+}
+
+fn node_filters(network: &NetworkRef, filters: Vec<Filter>) -> NodeFilters {
+    NodeFilters::new(network.nodes()[0].clone(), filters, vec![])
+}
+
+fn bxcan_accepts(bank: &BxCanFilterBank, id: u32, ide: bool) -> bool {
+    let register = if ide { (id << 3) | 0x4 } else { id << 21 };
+    match bank.mode {
+        BxCanFilterMode::Mask => register & bank.fr2 == bank.fr1 & bank.fr2,
+        BxCanFilterMode::List => register == bank.fr1 || register == bank.fr2,
+    }
+}
+
+#[test]
+fn bxcan_banks_accept_the_received_messages() {
+    let network = network();
+    for node_filters in resolve_filters_only(&network) {
+        let FilterRegisters::BxCan(banks) = node_filters.export(HardwareTarget::BxCan).unwrap() else {
+            panic!("expected bxCAN banks");
+        };
+        for message in node_filters.node().rx_messages() {
+            assert!(
+                banks
+                    .iter()
+                    .any(|bank| bxcan_accepts(bank, message.id().as_u32(), message.id().ide())),
+                "{} is not accepted by the banks of {}",
+                message.name(),
+                node_filters.node().name()
+            );
+        }
+    }
+}
+
+#[test]
+fn bxcan_register_layout() {
+    let network = network();
+    let filters = node_filters(
+        &network,
+        vec![
+            Filter::new(0x100, 0x700, false),
+            Filter::exact(0x123, false),
+            Filter::exact(0x1234567, true),
+        ],
+    );
+    let FilterRegisters::BxCan(banks) = filters.export(HardwareTarget::BxCan).unwrap() else {
+        panic!("expected bxCAN banks");
+    };
+    assert_eq!(
+        banks,
+        vec![
+            BxCanFilterBank {
+                mode: BxCanFilterMode::Mask,
+                fr1: 0x100 << 21,
+                fr2: (0x700 << 21) | 0x4,
+            },
+            BxCanFilterBank {
+                mode: BxCanFilterMode::List,
+                fr1: 0x123 << 21,
+                fr2: (0x1234567 << 3) | 0x4,
+            },
+        ]
+    );
+}
+
+#[test]
+fn fdcan_filter_elements() {
+    let network = network();
+    let filters = node_filters(
+        &network,
+        vec![
+            Filter::new(0x100, 0x700, false),
+            Filter::exact(0x123, false),
+            Filter::exact(0x124, false),
+            Filter::exact(0x1234567, true),
+        ],
+    );
+    let FilterRegisters::Fdcan(elements) = filters.export(HardwareTarget::Fdcan).unwrap() else {
+        panic!("expected FDCAN elements");
+    };
+    assert_eq!(
+        elements.standard,
+        vec![
+            (2 << 30) | (1 << 27) | (0x100 << 16) | 0x700,
+            (1 << 30) | (1 << 27) | (0x123 << 16) | 0x124,
+        ]
+    );
+    assert_eq!(
+        elements.extended,
+        vec![[(1 << 29) | 0x1234567, (1 << 30) | 0x1234567]]
+    );
+}
+
+#[test]
+fn mcp2515_registers() {
+    let network = network();
+    let filters = node_filters(
+        &network,
+        vec![
+            Filter::new(0x100, 0x700, false),
+            Filter::exact(0x123, false),
+            Filter::exact(0x124, false),
+        ],
+    );
+    let FilterRegisters::Mcp2515(registers) = filters.export(HardwareTarget::Mcp2515).unwrap() else {
+        panic!("expected MCP2515 registers");
+    };
+    // the setcode filter has its own mask in RXB0.
+    assert_eq!(registers.masks[0], [0xE0, 0x00, 0, 0]);
+    assert_eq!(registers.filters[0], [0x20, 0x00, 0, 0]);
+    assert_eq!(registers.filters[1], [0x20, 0x00, 0, 0]);
+    assert_eq!(registers.masks[1], [0xFF, 0xE0, 0, 0]);
+    assert_eq!(registers.filters[2], [0x24, 0x60, 0, 0]);
+    assert_eq!(registers.filters[3], [0x24, 0x80, 0, 0]);
+    assert_eq!(registers.filters[5], [0x24, 0x80, 0, 0]);
+
+    let ext = node_filters(&network, vec![Filter::exact(0x1234567, true)]);
+    let FilterRegisters::Mcp2515(registers) = ext.export(HardwareTarget::Mcp2515).unwrap() else {
+        panic!("expected MCP2515 registers");
+    };
+    // sid = 0x48, eid = 0x34567
+    assert_eq!(registers.filters[0], [0x09, 0x08 | 0x03, 0x45, 0x67]);
+    assert_eq!(registers.masks[0], [0xFF, 0xE3, 0xFF, 0xFF]);
+}
+
+#[test]
+fn filters_that_dont_fit_are_rejected() {
+    let network = network();
+    let masks = node_filters(
+        &network,
+        vec![
+            Filter::new(0x100, 0x700, false),
+            Filter::new(0x100, 0x780, false),
+            Filter::new(0x100, 0x7C0, false),
+        ],
+    );
+    assert!(matches!(
+        masks.export(HardwareTarget::Mcp2515),
+        Err(ConfigError::InvalidFilterExport(_))
+    ));
+    let many = node_filters(
+        &network,
+        (0..20).map(|i| Filter::new(i << 4, 0x7F0, false)).collect(),
+    );
+    assert!(matches!(
+        many.export(HardwareTarget::BxCan),
+        Err(ConfigError::InvalidFilterExport(_))
+    ));
+    assert!(many.export(HardwareTarget::Fdcan).is_ok());
+}
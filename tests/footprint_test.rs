@@ -0,0 +1,70 @@
+use canzero_config::{builder::NetworkBuilder, config::CostModel};
+
+fn zero_cost_model() -> CostModel {
+    CostModel {
+        base_ram: 0,
+        base_flash: 0,
+        message_ram: 0,
+        message_flash: 0,
+        signal_flash: 0,
+        object_entry_flash: 0,
+        stream_ram: 0,
+        stream_flash: 0,
+        command_ram: 0,
+        command_flash: 0,
+    }
+}
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let sensor = network_builder.create_node("sensor");
+    sensor.create_object_entry("pressure", "u16");
+    sensor.create_object_entry("temperature", "d10<0..100>");
+    let stream = sensor.create_stream("state");
+    stream.add_entry("pressure");
+    stream.add_entry("temperature");
+    let master = network_builder.create_node("master");
+    master.receive_stream("sensor", "state").unwrap();
+    network_builder
+}
+
+#[test]
+fn footprint_object_entries_ram() {
+    let network = network_builder().build().unwrap();
+    let sensor = network.nodes().iter().find(|n| n.name() == "sensor").unwrap();
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    let model = zero_cost_model();
+    let sensor_ram = sensor.footprint_estimate_with(&model).ram();
+    let master_ram = master.footprint_estimate_with(&model).ram();
+    // u16 + d10 (float) on top of the builtin object entries of every node.
+    assert_eq!(sensor_ram - master_ram, 2 + 4);
+}
+
+#[test]
+fn footprint_grows_with_messages() {
+    let model = CostModel {
+        message_ram: 16,
+        ..zero_cost_model()
+    };
+    let network = network_builder().build().unwrap();
+    for node in network.nodes() {
+        let messages = node.rx_messages().len() + node.tx_messages().len();
+        let without = node.footprint_estimate_with(&zero_cost_model()).ram();
+        assert_eq!(
+            node.footprint_estimate_with(&model).ram(),
+            without + 16 * messages
+        );
+    }
+}
+
+#[test]
+fn footprint_default_fits_small_mcu() {
+    let network = network_builder().build().unwrap();
+    for node in network.nodes() {
+        let footprint = node.footprint_estimate();
+        assert!(footprint.flash() > footprint.ram());
+        assert!(footprint.fits(16 * 1024, 64 * 1024), "{footprint}");
+        assert!(!footprint.fits(64, 64));
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{bus::FramePadding, NetworkRef},
+    simulator::{self, SimulationOptions},
+};
+
+fn short_frame_network(padding: Option<FramePadding>, can1_padding: Option<FramePadding>) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(500_000));
+    let can1 = network_builder.create_bus("can1", Some(500_000));
+    if let Some(padding) = padding {
+        network_builder.set_frame_padding(padding);
+    }
+    if let Some(padding) = can1_padding {
+        can1.padding(padding);
+    }
+    network_builder.create_node("master");
+    network_builder.create_node("bms");
+    for (name, bus) in [("state", "can0"), ("alarm", "can1")] {
+        let message = network_builder.create_message_with_interval(name, Duration::from_millis(1));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.assign_bus(bus);
+        message.add_transmitter("bms");
+        message.add_receiver("master");
+        message.make_type_format().add_type("u8", "value");
+    }
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn exact_dlc_is_the_default() {
+    let network = short_frame_network(None, None);
+    for bus in network.buses() {
+        assert_eq!(bus.padding(), FramePadding::Exact);
+        assert_eq!(bus.transmitted_len(1), 1);
+    }
+    let cpp = network.generate_cpp();
+    assert!(!cpp.contains("padding"));
+    assert!(network.to_json().contains("\"padding\": null"));
+}
+
+#[test]
+fn buses_override_the_network_padding() {
+    let network = short_frame_network(
+        Some(FramePadding::PadTo8 { fill: 0xAA }),
+        Some(FramePadding::PadTo8 { fill: 0x00 }),
+    );
+    let can0 = network.buses().iter().find(|bus| bus.name() == "can0").unwrap();
+    let can1 = network.buses().iter().find(|bus| bus.name() == "can1").unwrap();
+    assert_eq!(can0.padding(), FramePadding::PadTo8 { fill: 0xAA });
+    assert_eq!(can1.padding(), FramePadding::PadTo8 { fill: 0x00 });
+    assert_eq!(can0.transmitted_len(1), 8);
+    // the dlc of the message isn't changed by the padding.
+    let state = network.messages().iter().find(|m| m.name() == "state").unwrap();
+    assert_eq!(state.dlc(), 1);
+
+    let cpp = network.generate_cpp();
+    assert!(cpp.contains("static constexpr uint8_t tx_len = 8;"));
+    assert!(cpp.contains("static constexpr uint8_t padding = 0xAA;"));
+    assert!(cpp.contains("static constexpr uint8_t padding = 0x00;"));
+}
+
+#[test]
+fn padded_frames_increase_the_bus_load() {
+    let exact = short_frame_network(None, None);
+    let padded = short_frame_network(None, Some(FramePadding::PadTo8 { fill: 0xAA }));
+    let options = SimulationOptions {
+        duration: Duration::from_secs(1),
+    };
+    let exact_report = simulator::simulate(&exact, &options);
+    let padded_report = simulator::simulate(&padded, &options);
+    let load = |report: &simulator::SimulationReport, bus: &str| {
+        report
+            .buses()
+            .iter()
+            .find(|b| b.bus().name() == bus)
+            .unwrap()
+            .load()
+    };
+    assert_eq!(load(&exact_report, "can0"), load(&padded_report, "can0"));
+    assert!(load(&exact_report, "can1") < load(&padded_report, "can1"));
+    // padding is part of the wire format and therefore of the hash.
+    assert_ne!(exact.portable_hash(), padded.portable_hash());
+    assert!(padded.to_json().contains("\"padding\": 170"));
+}
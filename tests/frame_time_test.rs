@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{BitStuffing, FrameFormat, FrameTimeModel},
+    simulator::{self, SimulationOptions},
+};
+
+fn model(format: FrameFormat, stuffing: BitStuffing) -> FrameTimeModel {
+    FrameTimeModel { format, stuffing }
+}
+
+#[test]
+fn classic_worst_case_reference_values() {
+    // worst case frame lengths including the interframe space,
+    // see Davis et al. "Controller Area Network (CAN) schedulability analysis".
+    let classic = FrameTimeModel::default();
+    assert_eq!(classic.frame_bits(false, 8), 135);
+    assert_eq!(classic.frame_bits(true, 8), 160);
+    assert_eq!(classic.frame_bits(false, 0), 55);
+    assert_eq!(classic.frame_bits(true, 0), 80);
+    let unstuffed = model(FrameFormat::Classic, BitStuffing::Ignored);
+    assert_eq!(unstuffed.frame_bits(false, 8), 111);
+    assert_eq!(unstuffed.frame_bits(true, 8), 131);
+    // classic frames carry at most 8 bytes.
+    assert_eq!(classic.frame_bits(false, 12), 135);
+    // 500 kbit/s
+    assert_eq!(classic.frame_time(false, 8, 500_000), Duration::from_micros(270));
+}
+
+#[test]
+fn fd_frame_layout() {
+    let fd = model(FrameFormat::Fd, BitStuffing::Ignored);
+    // 17 arbitration, 5 ESI and DLC, 4 stuff count, 17 CRC,
+    // 6 fixed stuff bits and 13 tail bits.
+    assert_eq!(fd.frame_bits(false, 0), 62);
+    assert_eq!(fd.frame_bits(true, 0), 62 + 19);
+    // payloads are rounded up to the next valid length.
+    assert_eq!(fd.payload_len(9), 12);
+    assert_eq!(fd.payload_len(33), 48);
+    assert_eq!(fd.frame_bits(false, 9), fd.frame_bits(false, 12));
+    // payloads longer than 16 bytes use a CRC of 21 bits with one more fixed stuff bit.
+    assert_eq!(fd.frame_bits(false, 20) - fd.frame_bits(false, 16), 4 * 8 + 4 + 1);
+    let fd_stuffed = model(FrameFormat::Fd, BitStuffing::WorstCase);
+    assert!(fd_stuffed.frame_bits(false, 64) > fd.frame_bits(false, 64));
+}
+
+#[test]
+fn bitrate_switching_shortens_the_data_phase() {
+    let fd = model(FrameFormat::Fd, BitStuffing::WorstCase);
+    let brs1 = model(FrameFormat::FdBrs { bitrate_ratio: 1 }, BitStuffing::WorstCase);
+    let brs4 = model(FrameFormat::FdBrs { bitrate_ratio: 4 }, BitStuffing::WorstCase);
+    for bytes in [0, 8, 16, 64] {
+        assert_eq!(brs1.frame_bits(false, bytes), fd.frame_bits(false, bytes));
+        assert_eq!(brs1.nominal_bit_times(false, bytes), fd.nominal_bit_times(false, bytes));
+        assert!(brs4.nominal_bit_times(false, bytes) < fd.nominal_bit_times(false, bytes));
+        assert!(brs4.nominal_bit_times(false, bytes) > 30.0);
+    }
+}
+
+#[test]
+fn network_uses_the_configured_model() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(500_000));
+    let brs = model(FrameFormat::FdBrs { bitrate_ratio: 4 }, BitStuffing::WorstCase);
+    network_builder.set_frame_time_model(brs);
+    let network = network_builder.build().unwrap();
+    assert_eq!(*network.frame_time_model(), brs);
+
+    let classic_network = NetworkBuilder::new();
+    classic_network.create_bus("can0", Some(500_000));
+    let classic_network = classic_network.build().unwrap();
+    let options = SimulationOptions {
+        duration: Duration::from_secs(1),
+    };
+    let brs_load = simulator::simulate(&network, &options).buses()[0].load();
+    let classic_load = simulator::simulate(&classic_network, &options).buses()[0].load();
+    assert!(brs_load < classic_load, "{brs_load} >= {classic_load}");
+}
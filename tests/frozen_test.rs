@@ -0,0 +1,97 @@
+use std::{sync::Arc, thread, time::Duration};
+
+use canzero_config::builder::{
+    frozen::{FrozenMessageUsage, FrozenNetwork},
+    MessagePriority, NetworkBuilder,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let master = network_builder.create_node("master");
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entry("voltage", "u16");
+    let stream = bms.create_stream("state");
+    stream.add_entry("voltage");
+    master.receive_stream("bms", "state").unwrap();
+    let command = master.create_command("reset", None);
+    command.add_callee("bms").unwrap();
+    let message = network_builder.create_message_with_interval("master_state", Duration::from_millis(10));
+    message.set_any_std_id(MessagePriority::Normal);
+    message.add_transmitter("master");
+    message.add_receiver("bms");
+    message.make_type_format().add_type("u8", "state");
+    network_builder
+}
+
+#[test]
+fn frozen_network_mirrors_the_builders() {
+    let network_builder = network_builder();
+    let frozen = network_builder.freeze();
+    let master = frozen.node("master").unwrap();
+    let bms = frozen.node("bms").unwrap();
+    assert_eq!(master.commands(), &vec!["reset".to_owned()]);
+    assert_eq!(bms.extern_commands(), &vec!["reset".to_owned()]);
+    assert_eq!(bms.tx_streams(), &vec!["state".to_owned()]);
+    assert_eq!(master.rx_streams(), &vec!["state".to_owned()]);
+    assert!(bms.object_entries().contains(&"voltage".to_owned()));
+
+    let message = frozen.message("master_state").unwrap();
+    assert_eq!(message.transmitters(), &vec!["master".to_owned()]);
+    assert_eq!(message.receivers(), &vec!["bms".to_owned()]);
+    assert_eq!(message.attributes(), &vec!["state".to_owned()]);
+    assert_eq!(
+        message.usage(),
+        &FrozenMessageUsage::External {
+            interval: Some(Duration::from_millis(10))
+        }
+    );
+    assert!(frozen
+        .messages()
+        .iter()
+        .any(|m| m.usage() == &FrozenMessageUsage::Stream("state".to_owned())));
+    assert_eq!(frozen.bus("can0").unwrap().baudrate(), 1_000_000);
+}
+
+#[test]
+fn frozen_network_is_independent_of_later_edits() {
+    let network_builder = network_builder();
+    let frozen = network_builder.freeze();
+    network_builder.create_node("imu");
+    network_builder.create_node("master").create_object_entry("speed", "u16");
+    assert!(frozen.node("imu").is_none());
+    assert!(!frozen
+        .node("master")
+        .unwrap()
+        .object_entries()
+        .contains(&"speed".to_owned()));
+    // freezing doesn't change the network.
+    assert!(network_builder.build().is_ok());
+}
+
+#[test]
+fn frozen_network_can_be_traversed_concurrently() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<FrozenNetwork>();
+
+    let frozen = Arc::new(network_builder().freeze());
+    let counts: Vec<usize> = thread::scope(|scope| {
+        let handles: Vec<_> = frozen
+            .nodes()
+            .iter()
+            .map(|node| {
+                let frozen = frozen.clone();
+                scope.spawn(move || {
+                    node.rx_messages()
+                        .iter()
+                        .filter(|name| frozen.message(name).is_some())
+                        .count()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    for (node, count) in frozen.nodes().iter().zip(counts) {
+        assert_eq!(count, node.rx_messages().len());
+    }
+}
@@ -0,0 +1,86 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    diagnostics::{DiagnosticKind, Severity},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    let stream = becu.create_stream("brake_stream");
+    stream.add_entry("pressure");
+    becu.create_command("reset", None);
+    let secu = network_builder.create_node("secu");
+    secu.receive_stream("becu", "brake_stream").unwrap();
+    network_builder.create_node("telemetry");
+    network_builder
+}
+
+fn denied_wiring(network_builder: &NetworkBuilder) -> String {
+    match network_builder.build() {
+        Err(ConfigError::DeniedDiagnostics(msg)) => msg,
+        Err(err) => panic!("expected denied wiring, got {err:?}"),
+        Ok(_) => panic!("expected denied wiring"),
+    }
+}
+
+#[test]
+fn generated_wiring_is_accepted() {
+    let network_builder = network_builder();
+    let secu = network_builder.create_node("secu");
+    let reset = network_builder
+        .create_node("becu")
+        .0
+        .borrow()
+        .commands
+        .iter()
+        .find(|command| command.0.borrow().name == "reset")
+        .cloned()
+        .unwrap();
+    secu.add_extern_command(&reset);
+    let network = network_builder.build().unwrap();
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::HiddenMessageWiring));
+}
+
+#[test]
+fn foreign_stream_receivers_are_denied() {
+    let network_builder = network_builder();
+    let stream_message = network_builder.create_node("becu").0.borrow().tx_streams[0]
+        .0
+        .borrow()
+        .message
+        .clone();
+    stream_message.add_receiver("telemetry");
+    let msg = denied_wiring(&network_builder);
+    assert!(
+        msg.contains("telemetry was added as a receiver of the message becu_stream_brake_stream, which belongs to the stream becu::brake_stream"),
+        "{msg}"
+    );
+}
+
+#[test]
+fn foreign_command_transmitters_are_reported() {
+    let network_builder = network_builder();
+    network_builder.set_diagnostic_severity(DiagnosticKind::HiddenMessageWiring, Severity::Warn);
+    let call_message = network_builder.create_node("becu").0.borrow().commands[0]
+        .0
+        .borrow()
+        .call_message
+        .clone();
+    call_message.add_transmitter("telemetry");
+    let network = network_builder.build().unwrap();
+    let problems: Vec<&str> = network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::HiddenMessageWiring)
+        .map(|d| d.message())
+        .collect();
+    assert_eq!(problems.len(), 1, "{problems:?}");
+    assert!(problems[0].starts_with("telemetry was added as a transmitter"), "{problems:?}");
+    assert!(problems[0].ends_with("which belongs to the command becu::reset"), "{problems:?}");
+}
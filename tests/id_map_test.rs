@@ -0,0 +1,57 @@
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+#[test]
+fn id_map_covers_the_std_id_space_of_every_bus() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    network_builder.create_node("becu");
+    for i in 0..3 {
+        let message = network_builder.create_message(&format!("msg_{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_receiver("becu");
+    }
+    let fixed = network_builder.create_message("fixed");
+    fixed.set_std_id(0x700);
+    fixed.add_receiver("becu");
+    let extended = network_builder.create_message("extended");
+    extended.set_ext_id(0x1000);
+    extended.add_receiver("becu");
+    let network = network_builder.build().unwrap();
+
+    let id_map = network.id_map_csv();
+    let mut lines = id_map.lines();
+    assert!(lines.next().unwrap().starts_with("bus,ide,first_id,last_id,kind,message"));
+    let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert!(rows.iter().all(|row| row.len() == 10));
+
+    for bus in network.buses() {
+        let std_rows: Vec<&Vec<&str>> = rows
+            .iter()
+            .filter(|row| row[0] == bus.name() && row[1] == "std")
+            .collect();
+        // the rows are contiguous and cover the whole std id space.
+        let mut next = 0;
+        for row in &std_rows {
+            let first = u32::from_str_radix(row[2].trim_start_matches("0x"), 16).unwrap();
+            let last = u32::from_str_radix(row[3].trim_start_matches("0x"), 16).unwrap();
+            assert_eq!(first, next);
+            assert!(last >= first);
+            next = last + 1;
+        }
+        assert_eq!(next, 0x800);
+    }
+
+    for message in network.messages() {
+        let row = rows
+            .iter()
+            .find(|row| row[4] == "message" && row[5] == message.name())
+            .unwrap_or_else(|| panic!("message {} is missing", message.name()));
+        assert_eq!(row[0], message.bus().name());
+        assert_eq!(row[2], format!("{:#X}", message.id().as_u32()));
+    }
+    let fixed_row = rows.iter().find(|row| row[5] == "fixed").unwrap();
+    assert_eq!(fixed_row[8], "");
+    let extended_row = rows.iter().find(|row| row[5] == "extended").unwrap();
+    assert_eq!(extended_row[1], "ext");
+}
@@ -0,0 +1,81 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{signal::Signal, SignalType},
+};
+
+fn other_pod() -> canzero_config::config::NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    network_builder.create_node("pdu");
+    let shared = network_builder.create_message("pdu_state");
+    shared.assign_bus("can0");
+    shared.set_std_id(0x100);
+    shared.add_receiver("pdu");
+    shared
+        .make_signal_format()
+        .add_signal(Signal::new("state", None, SignalType::UnsignedInt { size: 8 }, 0))
+        .unwrap();
+    let private = network_builder.create_message("pdu_private");
+    private.assign_bus("can1");
+    private.set_std_id(0x101);
+    private.add_receiver("pdu");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn imported_messages_keep_their_ids() {
+    let other = other_pod();
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    for i in 0..4 {
+        let message = network_builder.create_message(&format!("msg_{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_receiver("becu");
+    }
+    network_builder.import_network(&other, true).unwrap();
+    let network = network_builder.build().unwrap();
+
+    let imported = network
+        .messages()
+        .iter()
+        .find(|message| message.name() == "external_pdu_state")
+        .unwrap();
+    assert_eq!(imported.id().as_u32(), 0x100);
+    assert!(!imported.id().ide());
+    assert_eq!(imported.bus().name(), "can0");
+    assert_eq!(imported.signals().len(), 1);
+    // messages on buses that the networks don't share are not imported.
+    assert!(!network
+        .messages()
+        .iter()
+        .any(|message| message.name() == "external_pdu_private"));
+    // no other message is assigned to the imported id.
+    assert_eq!(
+        network
+            .messages()
+            .iter()
+            .filter(|message| message.bus().name() == "can0"
+                && !message.id().ide()
+                && message.id().as_u32() == 0x100)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn conflicting_imports_are_rejected() {
+    let other = other_pod();
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let fixed = network_builder.create_message("fixed");
+    fixed.assign_bus("can0");
+    fixed.set_std_id(0x100);
+    assert!(network_builder.import_network(&other, true).is_err());
+
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_message("pdu_state");
+    assert!(network_builder.import_network(&other, false).is_err());
+}
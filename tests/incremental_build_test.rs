@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use canzero_config::builder::{
+    build_cache::BuildCacheStats, MessagePriority, NetworkBuilder,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_node("master");
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entry("cell_voltage", "u16");
+    let state = network_builder.define_enum("bms_state");
+    state.add_entry("Idle", None).unwrap();
+    state.add_entry("Running", None).unwrap();
+    let message = network_builder.create_message_with_interval("bms_status", Duration::from_millis(10));
+    message.set_any_std_id(MessagePriority::High);
+    message.add_transmitter("bms");
+    message.add_receiver("master");
+    message.make_type_format().add_type("bms_state", "state");
+    network_builder
+}
+
+#[test]
+fn build_leaves_the_builder_unchanged() {
+    let network_builder = network_builder();
+    let message_count = network_builder.0.borrow().messages.borrow().len();
+    let first = network_builder.build().unwrap();
+    assert_eq!(network_builder.0.borrow().messages.borrow().len(), message_count);
+    let second = network_builder.build().unwrap();
+    assert_eq!(first.portable_hash(), second.portable_hash());
+    assert_eq!(first.messages().len(), second.messages().len());
+
+    // changes after a build are part of the next build.
+    network_builder.create_node("pdu");
+    let third = network_builder.build().unwrap();
+    assert_eq!(third.nodes().len(), first.nodes().len() + 1);
+}
+
+#[test]
+fn unchanged_stages_are_reused() {
+    let network_builder = network_builder();
+    assert_eq!(network_builder.build_cache_stats(), None);
+    let reference = network_builder.build().unwrap();
+    network_builder.set_incremental(true);
+    network_builder.build().unwrap();
+    let cached = network_builder.build().unwrap();
+    assert_eq!(
+        network_builder.build_cache_stats(),
+        Some(BuildCacheStats {
+            type_hits: 1,
+            type_misses: 1,
+            receive_set_hits: 1,
+            receive_set_misses: 1,
+        })
+    );
+    assert_eq!(reference.portable_hash(), cached.portable_hash());
+}
+
+#[test]
+fn changed_inputs_invalidate_their_stage() {
+    let network_builder = network_builder();
+    network_builder.set_incremental(true);
+    network_builder.build().unwrap();
+
+    // a new receiver changes the receive sets, but not the types.
+    let message = network_builder.create_message("bms_alarm");
+    message.set_any_std_id(MessagePriority::Normal);
+    message.add_transmitter("bms");
+    message.add_receiver("master");
+    let network = network_builder.build().unwrap();
+    let stats = network_builder.build_cache_stats().unwrap();
+    assert_eq!((stats.type_hits, stats.receive_set_misses), (1, 2));
+    assert!(network.messages().iter().any(|m| m.name() == "bms_alarm"));
+
+    network_builder
+        .define_enum("bms_state_v2")
+        .add_entry("Idle", None)
+        .unwrap();
+    let network = network_builder.build().unwrap();
+    let stats = network_builder.build_cache_stats().unwrap();
+    assert_eq!(stats.type_misses, 2);
+    assert!(network.types().iter().any(|ty| ty.name() == "bms_state_v2"));
+
+    network_builder.set_incremental(false);
+    assert_eq!(network_builder.build_cache_stats(), None);
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let secu = network_builder.create_node("secu");
+    secu.create_command("configure_filters", None);
+    let enable_output = secu.create_command("enable_output", None);
+    enable_output.set_timeout(Duration::from_millis(5));
+    let set_gain = secu.create_command("set_gain", None);
+    set_gain.add_argument("gain", "u8");
+    secu.init_sequence(&["configure_filters", "enable_output"]);
+    network_builder.create_node("becu");
+    network_builder
+}
+
+#[test]
+fn init_sequence_in_config() {
+    let network = network_builder().build().unwrap();
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    let steps: Vec<&str> = secu.init_sequence().iter().map(|command| command.name()).collect();
+    assert_eq!(steps, vec!["configure_filters", "enable_output"]);
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    assert!(becu.init_sequence().is_empty());
+    assert!(network
+        .to_json()
+        .contains("\"init_sequence\": [\n        \"configure_filters\",\n        \"enable_output\"\n      ]"));
+}
+
+#[test]
+fn init_sequence_header() {
+    let network = network_builder().build().unwrap();
+    let header = network.generate_init_sequence();
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    let step = |name: &str, timeout_us: u32| {
+        let command = secu.commands().iter().find(|command| command.name() == name).unwrap();
+        format!(
+            "  {{ \"{name}\", {}, {:#X}, 0, {:#X}, 0, {timeout_us} }},\n",
+            secu.id(),
+            command.tx_message().id().as_u32(),
+            command.rx_message().id().as_u32()
+        )
+    };
+    let configure = header.find(&step("configure_filters", 0)).unwrap();
+    let enable = header.find(&step("enable_output", 5000)).unwrap();
+    assert!(configure < enable);
+    assert!(header.contains("static const canzero_init_step CANZERO_SECU_INIT_SEQUENCE[] = {\n"));
+    assert!(header.contains("#define CANZERO_SECU_INIT_SEQUENCE_LEN 2\n"));
+    assert!(!header.contains("CANZERO_BECU_INIT_SEQUENCE"));
+}
+
+#[test]
+fn invalid_init_sequences() {
+    let builder = network_builder();
+    builder.create_node("becu").init_sequence(&["configure_filters"]);
+    assert!(matches!(builder.build(), Err(ConfigError::InvalidInitSequence(_))));
+
+    let builder = network_builder();
+    builder.create_node("secu").init_sequence(&["set_gain"]);
+    assert!(matches!(builder.build(), Err(ConfigError::InvalidInitSequence(_))));
+}
@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef};
+
+// the same network, the streams, commands and messages of the nodes are
+// defined in the given order.
+fn build_network(reversed: bool) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let motor = network_builder.create_node("motor");
+    let master = network_builder.create_node("master");
+    for name in ["velocity", "current", "acceleration"] {
+        motor.create_object_entry(name, "u16");
+        master.create_object_entry(&format!("motor_{name}"), "u16");
+    }
+    let mut streams = vec!["velocity", "current", "acceleration"];
+    let mut commands = vec!["start", "stop", "calibrate"];
+    let mut messages = vec!["heartbeat", "alive", "sync"];
+    if reversed {
+        streams.reverse();
+        commands.reverse();
+        messages.reverse();
+    }
+    for name in streams {
+        let stream = motor.create_stream(name);
+        stream.set_interval(Duration::from_millis(10), Duration::from_millis(100));
+        stream.add_entry(name);
+        master
+            .receive_stream("motor", name)
+            .unwrap()
+            .map(name, &format!("motor_{name}"));
+    }
+    for name in commands {
+        let command = master.create_command(name, None);
+        command.add_callee("motor").unwrap();
+    }
+    for name in messages {
+        let message = network_builder.create_message_with_interval(name, Duration::from_millis(100));
+        message.add_transmitter("master");
+        message.add_receiver("motor");
+    }
+    network_builder.build().unwrap()
+}
+
+fn message_names(network: &NetworkRef) -> Vec<String> {
+    network.messages().iter().map(|m| m.name().to_owned()).collect()
+}
+
+#[test]
+fn messages_are_sorted_by_name() {
+    let network = build_network(false);
+    let names = message_names(&network);
+    let mut sorted = names.clone();
+    sorted.sort();
+    assert_eq!(names, sorted);
+    for node in network.nodes() {
+        for messages in [node.rx_messages(), node.tx_messages()] {
+            let names: Vec<&str> = messages.iter().map(|m| m.name()).collect();
+            let mut sorted = names.clone();
+            sorted.sort();
+            assert_eq!(names, sorted, "messages of {}", node.name());
+        }
+    }
+}
+
+#[test]
+fn message_order_is_independent_of_definition_order() {
+    let a = build_network(false);
+    let b = build_network(true);
+    assert_eq!(message_names(&a), message_names(&b));
+    for (a, b) in a.messages().iter().zip(b.messages()) {
+        assert_eq!(a.id(), b.id(), "id of {}", a.name());
+    }
+    for (a, b) in a.nodes().iter().zip(b.nodes()) {
+        let names = |messages: &Vec<_>| -> Vec<String> {
+            messages
+                .iter()
+                .map(|m: &canzero_config::config::MessageRef| m.name().to_owned())
+                .collect()
+        };
+        assert_eq!(names(a.rx_messages()), names(b.rx_messages()));
+        assert_eq!(names(a.tx_messages()), names(b.tx_messages()));
+    }
+}
+
+#[test]
+fn definitions_keep_their_order() {
+    let network = build_network(true);
+    let nodes: Vec<&str> = network.nodes().iter().map(|node| node.name()).collect();
+    assert_eq!(nodes, vec!["motor", "master"]);
+    let motor = &network.nodes()[0];
+    let entries: Vec<&str> = motor.object_entries().iter().map(|oe| oe.name()).collect();
+    // the object entries that every node has (e.g. config_hash) come first.
+    assert!(entries.ends_with(&["velocity", "current", "acceleration"]), "{entries:?}");
+    for (id, oe) in motor.object_entries().iter().enumerate() {
+        assert_eq!(oe.id() as usize, id);
+    }
+    let streams: Vec<&str> = motor.tx_streams().iter().map(|s| s.name()).collect();
+    assert_eq!(streams, vec!["acceleration", "current", "velocity"]);
+    let master = &network.nodes()[1];
+    let commands: Vec<&str> = master.commands().iter().map(|c| c.name()).collect();
+    assert_eq!(&commands[..3], &["calibrate", "stop", "start"]);
+}
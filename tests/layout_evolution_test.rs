@@ -0,0 +1,167 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{
+        layout::{parse_layout_lock, LayoutViolation},
+        signal::{Signal, SignalType},
+        NetworkRef,
+    },
+    errors::ConfigError,
+};
+
+enum Field {
+    Signal(&'static str, SignalType),
+    SignalAt(&'static str, SignalType, usize),
+    Reserved(u8),
+}
+
+fn u(size: u8) -> SignalType {
+    SignalType::UnsignedInt { size }
+}
+
+fn network(fields: &[Field], version: Option<&str>) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    network_builder.create_node("secu");
+    let status = network_builder.create_message("status");
+    status.add_transmitter("becu");
+    status.add_receiver("secu");
+    if let Some(version) = version {
+        status.set_version(version);
+    }
+    let format = status.make_signal_format();
+    for field in fields {
+        match field {
+            Field::Signal(name, ty) => format.add_signal(Signal::new(name, None, ty.clone(), 0)).unwrap(),
+            Field::SignalAt(name, ty, start_bit) => format
+                .add_signal_at(Signal::new(name, None, ty.clone(), 0), *start_bit)
+                .unwrap(),
+            Field::Reserved(bits) => format.add_reserved(*bits),
+        }
+    }
+    network_builder.build().unwrap()
+}
+
+fn deployed() -> NetworkRef {
+    network(
+        &[Field::Signal("state", u(8)), Field::Reserved(8), Field::Signal("pressure", u(16))],
+        Some("1.0.0"),
+    )
+}
+
+fn violated_signals(violations: &[LayoutViolation]) -> Vec<String> {
+    violations
+        .iter()
+        .map(|violation| match violation {
+            LayoutViolation::RemovedSignal { signal, .. }
+            | LayoutViolation::ChangedSignal { signal, .. }
+            | LayoutViolation::InsertedSignal { signal, .. } => signal.clone(),
+        })
+        .collect()
+}
+
+#[test]
+fn appended_signals_are_compatible() {
+    let next = network(
+        &[
+            Field::Signal("state", u(8)),
+            Field::Reserved(8),
+            Field::Signal("pressure", u(16)),
+            Field::Signal("temperature", u(8)),
+        ],
+        Some("1.1.0"),
+    );
+    assert!(next.check_layouts_against(&deployed()).is_empty());
+    assert!(!deployed().diff(&next).is_breaking());
+}
+
+#[test]
+fn reserved_bits_can_be_used() {
+    let next = network(
+        &[
+            Field::Signal("state", u(8)),
+            Field::Signal("flags", u(4)),
+            Field::SignalAt("pressure", u(16), 16),
+        ],
+        Some("1.1.0"),
+    );
+    assert!(next.check_layouts_against(&deployed()).is_empty());
+}
+
+#[test]
+fn changed_and_removed_signals_are_violations() {
+    let resized = network(
+        &[Field::Signal("state", u(8)), Field::Reserved(8), Field::Signal("pressure", u(12))],
+        Some("1.1.0"),
+    );
+    let violations = resized.check_layouts_against(&deployed());
+    assert_eq!(
+        violations,
+        vec![LayoutViolation::ChangedSignal {
+            message: "status".to_owned(),
+            signal: "status_pressure".to_owned()
+        }]
+    );
+    let retyped = network(
+        &[
+            Field::Signal("state", u(8)),
+            Field::Reserved(8),
+            Field::Signal("pressure", SignalType::SignedInt { size: 16 }),
+        ],
+        Some("1.1.0"),
+    );
+    assert_eq!(violated_signals(&retyped.check_layouts_against(&deployed())), vec!["status_pressure"]);
+    let removed = network(&[Field::Signal("state", u(8))], Some("1.1.0"));
+    let violations = removed.check_layouts_against(&deployed());
+    assert!(matches!(&violations[..], [LayoutViolation::RemovedSignal { signal, .. }] if signal == "status_pressure"));
+    assert!(deployed().diff(&removed).is_breaking());
+}
+
+#[test]
+fn inserted_signals_are_violations() {
+    let next = network(
+        &[
+            Field::Signal("state", u(8)),
+            Field::Reserved(8),
+            Field::Signal("mode", u(4)),
+            Field::Signal("pressure", u(16)),
+        ],
+        Some("1.1.0"),
+    );
+    let signals = violated_signals(&next.check_layouts_against(&deployed()));
+    assert_eq!(signals, vec!["status_pressure", "status_mode"]);
+}
+
+#[test]
+fn major_versions_allow_breaking_layouts() {
+    let next = network(&[Field::Signal("pressure", u(32))], Some("2.0.0"));
+    assert!(next.check_layouts_against(&deployed()).is_empty());
+    let unversioned = network(&[Field::Signal("pressure", u(32))], None);
+    assert!(!unversioned.check_layouts_against(&deployed()).is_empty());
+}
+
+#[test]
+fn lock_files_round_trip() {
+    let deployed = deployed();
+    let lock = deployed.layout_lock();
+    assert!(lock.starts_with("# canzero layout lock\n"));
+    assert!(lock.contains("message status 1.0.0\n"));
+    assert!(lock.contains("reserved status_reserved0 8 u8\n"));
+    let layouts = parse_layout_lock(&lock).unwrap();
+    assert_eq!(layouts.len(), deployed.messages().len());
+    assert!(deployed.check_layouts(&layouts).is_empty());
+    let removed = network(&[Field::Signal("state", u(8))], Some("1.1.0"));
+    assert_eq!(violated_signals(&removed.check_layouts(&layouts)), vec!["status_pressure"]);
+}
+
+#[test]
+fn invalid_lock_files_are_rejected() {
+    let Err(ConfigError::InvalidLayoutLock(line)) = parse_layout_lock("signal state 0 u8") else {
+        panic!("signal without a message");
+    };
+    assert!(line.starts_with("line 1"));
+    assert!(matches!(
+        parse_layout_lock("message status -\nsignal state 0 f8"),
+        Err(ConfigError::InvalidLayoutLock(_))
+    ));
+}
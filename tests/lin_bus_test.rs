@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{lin_bus::protected_id, signal::Signal, SignalType},
+    errors::ConfigError,
+    simulator,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_node("secu");
+    let lin0 = network_builder.create_lin_bus("lin0", None);
+    lin0.set_master("becu");
+    lin0.add_slave("buttons");
+
+    let buttons_state = lin0.create_frame("buttons_state", 0x10, "buttons");
+    buttons_state.add_subscriber("becu");
+    let format = buttons_state.signal_format();
+    format
+        .add_signal(Signal::new("pressed", None, SignalType::UnsignedInt { size: 4 }, 0))
+        .unwrap();
+    format
+        .add_signal(Signal::new("emergency", None, SignalType::UnsignedInt { size: 1 }, 0))
+        .unwrap();
+    let gateway_message = buttons_state.forward_to_can();
+    gateway_message.add_receiver("secu");
+
+    let leds = lin0.create_frame("leds", 0x11, "becu");
+    leds.add_subscriber("buttons");
+    leds.signal_format()
+        .add_signal(Signal::new("brightness", None, SignalType::UnsignedInt { size: 8 }, 0))
+        .unwrap();
+
+    let schedule = lin0.create_schedule_table("normal");
+    schedule.add_slot("buttons_state", Duration::from_millis(10));
+    schedule.add_slot("leds", Duration::from_millis(10));
+    network_builder
+}
+
+#[test]
+fn protected_ids_have_parity_bits() {
+    assert_eq!(protected_id(0x10), 0x50);
+    assert_eq!(protected_id(0x3C), 0x3C);
+    assert_eq!(protected_id(0x00), 0x80);
+}
+
+#[test]
+fn lin_buses_are_part_of_the_network() {
+    let network = network_builder().build().unwrap();
+    assert_eq!(network.lin_buses().len(), 1);
+    let lin0 = &network.lin_buses()[0];
+    assert_eq!(lin0.name(), "lin0");
+    assert_eq!(lin0.baudrate(), 19200);
+    assert_eq!(lin0.master(), "becu");
+    assert_eq!(lin0.slaves(), &vec!["buttons".to_owned()]);
+    assert_eq!(lin0.schedule_tables()[0].cycle_time(), Duration::from_millis(20));
+
+    let buttons_state = &lin0.frames()[0];
+    assert_eq!(buttons_state.protected_id(), 0x50);
+    assert_eq!(buttons_state.dlc(), 1);
+    assert_eq!(buttons_state.signals()[1].name(), "buttons_state_emergency");
+    assert_eq!(buttons_state.signals()[1].byte_offset(), 4);
+    assert!(lin0.frames()[1].gateway_message().is_none());
+
+    // the master forwards the frame to CAN at the rate of the schedule.
+    let gateway_message = buttons_state.gateway_message().unwrap();
+    assert_eq!(gateway_message.name(), "lin0_buttons_state");
+    assert_eq!(gateway_message.signals().len(), 2);
+    assert_eq!(simulator::message_interval(gateway_message), Duration::from_millis(20));
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    assert!(becu
+        .tx_messages()
+        .iter()
+        .any(|message| message.name() == "lin0_buttons_state"));
+}
+
+#[test]
+fn invalid_lin_configurations_are_rejected() {
+    let invalid = |network_builder: NetworkBuilder| {
+        matches!(
+            network_builder.build(),
+            Err(ConfigError::InvalidLinConfiguration(_))
+        )
+    };
+
+    let network_builder = network_builder();
+    network_builder.create_lin_bus("lin1", None);
+    assert!(invalid(network_builder));
+
+    let network_builder = self::network_builder();
+    let lin0 = network_builder.create_lin_bus("lin0", None);
+    lin0.create_frame("invalid_id", 0x40, "becu")
+        .signal_format()
+        .add_signal(Signal::new("x", None, SignalType::UnsignedInt { size: 8 }, 0))
+        .unwrap();
+    assert!(invalid(network_builder));
+
+    let network_builder = self::network_builder();
+    let lin0 = network_builder.create_lin_bus("lin0", None);
+    lin0.create_frame("unknown_publisher", 0x12, "seats")
+        .signal_format()
+        .add_signal(Signal::new("x", None, SignalType::UnsignedInt { size: 8 }, 0))
+        .unwrap();
+    assert!(invalid(network_builder));
+
+    // a frame with one data byte takes ~2.8ms at 19200 baud.
+    let network_builder = self::network_builder();
+    let lin0 = network_builder.create_lin_bus("lin0", None);
+    lin0.create_schedule_table("fast")
+        .add_slot("leds", Duration::from_millis(1));
+    assert!(invalid(network_builder));
+}
@@ -0,0 +1,92 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{NetworkRef, NodeRef},
+    errors::ConfigError,
+};
+
+fn node(network: &NetworkRef, name: &str) -> NodeRef {
+    network.nodes().iter().find(|node| node.name() == name).cloned().unwrap()
+}
+
+fn sends(node: &NodeRef, message: &str) -> bool {
+    node.tx_messages().iter().any(|m| m.name() == message)
+}
+
+fn receives(node: &NodeRef, message: &str) -> bool {
+    node.rx_messages().iter().any(|m| m.name() == message)
+}
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    let becu = network_builder.create_node("becu");
+    becu.assign_bus("can0");
+    becu.create_object_entry("pressure", "u16");
+    becu.create_stream("brake_stream").add_entry("pressure");
+    network_builder
+}
+
+#[test]
+fn passive_nodes_never_transmit() {
+    let network_builder = network_builder();
+    let logger = network_builder.create_node("logger");
+    logger.set_listen_only("can0");
+    let network = network_builder.build().unwrap();
+    let logger = node(&network, "logger");
+    assert!(logger.passive());
+    assert_eq!(logger.listen_only().len(), 1);
+    assert!(logger.tx_messages().is_empty());
+    for message in ["get_req", "set_req"] {
+        assert!(!receives(&logger, message));
+    }
+    let becu = node(&network, "becu");
+    assert!(sends(&becu, "get_resp") && sends(&becu, "heartbeat"));
+    assert!(!network
+        .conformance_scenarios()
+        .iter()
+        .any(|scenario| scenario.node() == "logger"));
+    assert!(network.to_json().contains("\"listen_only\": [\n        0\n      ]"));
+}
+
+#[test]
+fn transmissions_on_listen_only_buses_are_rejected() {
+    let network_builder = network_builder();
+    let probe = network_builder.create_node("probe");
+    probe.set_listen_only("can0");
+    let message = network_builder.create_message("probe_status");
+    message.assign_bus("can0");
+    message.add_transmitter("probe");
+    message.add_receiver("becu");
+    match network_builder.build() {
+        Err(ConfigError::ListenOnlyTransmission(error)) => {
+            assert!(error.contains("probe_status") && error.contains("can0"), "{error}")
+        }
+        other => panic!("expected a listen only transmission, got {other:?}"),
+    }
+}
+
+#[test]
+fn nodes_can_transmit_on_their_other_buses() {
+    let network_builder = network_builder();
+    let gateway = network_builder.create_node("gateway");
+    gateway.assign_bus("can1");
+    gateway.set_listen_only("can0");
+    let message = network_builder.create_message("gateway_status");
+    message.assign_bus("can1");
+    message.add_transmitter("gateway");
+    message.add_receiver("becu");
+    let network = network_builder.build().unwrap();
+    let gateway = node(&network, "gateway");
+    assert!(!gateway.passive());
+    assert!(sends(&gateway, "gateway_status") && sends(&gateway, "get_resp"));
+    assert!(receives(&gateway, "get_req"));
+}
+
+#[test]
+fn passive_nodes_can_not_be_supervised() {
+    let network_builder = network_builder();
+    network_builder.create_node("logger").set_listen_only("can0");
+    network_builder.create_node("becu").supervise("logger", None);
+    assert!(matches!(network_builder.build(), Err(ConfigError::InvalidSupervision(_))));
+}
@@ -0,0 +1,79 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{NetworkRef, NodeRef},
+    diagnostics::DiagnosticKind,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    becu.create_stream("brake_stream").add_entry("pressure");
+    becu.create_command("reset", None);
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("speed", "u16");
+    secu.create_stream("speed_stream").add_entry("speed");
+    secu.create_command("arm", None);
+    network_builder
+}
+
+fn node(network: &NetworkRef, name: &str) -> NodeRef {
+    network.nodes().iter().find(|node| node.name() == name).cloned().unwrap()
+}
+
+#[test]
+fn masters_receive_all_streams_and_call_all_commands() {
+    let network_builder = network_builder();
+    network_builder.set_master("master");
+    let network = network_builder.build().unwrap();
+    let master = node(&network, "master");
+    assert!(master.receive_all());
+    let mut commands: Vec<&str> = master.extern_commands().iter().map(|(_, command)| command.name()).collect();
+    commands.sort();
+    assert_eq!(commands, vec!["arm", "reset"]);
+    for stream_message in ["becu_stream_brake_stream", "secu_stream_speed_stream"] {
+        assert!(
+            master.rx_messages().iter().any(|message| message.name() == stream_message),
+            "master doesn't receive {stream_message}"
+        );
+    }
+    let masters: Vec<&str> = network.masters().iter().map(|master| master.name()).collect();
+    assert_eq!(masters, vec!["master"]);
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::HiddenMessageWiring));
+    assert!(network.to_json().contains("\"masters\": [\n    \"master\"\n  ]"));
+}
+
+#[test]
+fn manual_wiring_of_masters_is_not_duplicated() {
+    let network_builder = network_builder();
+    let master = network_builder.create_node("master");
+    let reset = network_builder.create_node("becu").0.borrow().commands[0].clone();
+    master.add_extern_command(&reset);
+    master.receive_stream("becu", "brake_stream").unwrap().map("pressure", "becu_pressure");
+    network_builder.set_master("master");
+    network_builder.set_master("master");
+    let network = network_builder.build().unwrap();
+    let master = node(&network, "master");
+    assert_eq!(master.extern_commands().iter().filter(|(_, command)| command.name() == "reset").count(), 1);
+    assert_eq!(
+        master
+            .rx_messages()
+            .iter()
+            .filter(|message| message.name() == "becu_stream_brake_stream")
+            .count(),
+        1
+    );
+    assert_eq!(network.masters().len(), 1);
+}
+
+#[test]
+fn networks_without_masters_are_unchanged() {
+    let network = network_builder().build().unwrap();
+    assert!(network.masters().is_empty());
+    assert!(!node(&network, "secu").receive_all());
+    assert!(node(&network, "secu").extern_commands().is_empty());
+}
@@ -0,0 +1,77 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+fn actuator_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("master");
+    network_builder.create_node("brake");
+    let brake_command = network_builder.create_message("brake_command");
+    brake_command.set_std_id(0x10);
+    brake_command.add_transmitter("master");
+    brake_command.add_receiver("brake");
+    brake_command.make_type_format().add_type("u12", "pressure");
+    brake_command.enable_cmac(7);
+    let brake_state = network_builder.create_message("brake_state");
+    brake_state.set_std_id(0x11);
+    brake_state.add_transmitter("brake");
+    brake_state.add_receiver("master");
+    brake_state.make_type_format().add_type("u8", "state");
+    brake_state.enable_truncated_cmac(8, 2);
+    network_builder
+}
+
+#[test]
+fn mac_is_appended_byte_aligned() {
+    let network = actuator_network().build().unwrap();
+    let brake_command = network.expect_message("brake_command").with_dlc(6);
+    let authentication = brake_command.message().authentication().unwrap();
+    assert_eq!(authentication.key_id(), 7);
+    assert_eq!(authentication.mac_len(), 4);
+    assert_eq!(authentication.signal().name(), "brake_command_cmac");
+    assert_eq!(authentication.signal().byte_offset(), 16);
+    assert_eq!(authentication.signal().size(), 32);
+    assert!(brake_command
+        .message()
+        .signals()
+        .iter()
+        .any(|signal| signal.name() == "brake_command_cmac"));
+
+    let brake_state = network.expect_message("brake_state").with_dlc(3);
+    assert_eq!(brake_state.message().authentication().unwrap().mac_len(), 2);
+    assert!(network.heartbeat_message().authentication().is_none());
+}
+
+#[test]
+fn key_ids_are_part_of_the_codegen() {
+    let network = actuator_network().build().unwrap();
+    let cpp = network.generate_cpp();
+    assert!(cpp.contains("static constexpr uint32_t cmac_key_id = 7;"));
+    assert!(cpp.contains("static constexpr uint8_t cmac_len = 2;"));
+    assert!(network.to_json().contains("\"key_id\": 7"));
+}
+
+#[test]
+fn mac_has_to_fit_into_the_frame() {
+    let network_builder = actuator_network();
+    let position = network_builder.create_message("position");
+    position.set_std_id(0x12);
+    position.add_transmitter("brake");
+    position.add_receiver("master");
+    position.make_type_format().add_type("u48", "position");
+    position.enable_cmac(7);
+    let Err(ConfigError::InvalidMessageAuthentication(msg)) = network_builder.build() else {
+        panic!("expected a mac that doesn't fit into the frame");
+    };
+    assert!(msg.contains("position has 10 bytes"), "{msg}");
+
+    // 6 bytes of signals leave room for a 2 byte mac.
+    let network_builder = actuator_network();
+    let position = network_builder.create_message("position");
+    position.set_std_id(0x12);
+    position.add_transmitter("brake");
+    position.add_receiver("master");
+    position.make_type_format().add_type("u48", "position");
+    position.enable_truncated_cmac(7, 2);
+    let network = network_builder.build().unwrap();
+    network.expect_message("position").with_dlc(8);
+}
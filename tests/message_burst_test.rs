@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::NetworkRef,
+    diagnostics::DiagnosticKind,
+    simulator::{self, SimulationOptions},
+};
+
+fn brake_network(burst: Option<(u32, Duration)>) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(125_000));
+    network_builder.create_node("master");
+    network_builder.create_node("brakes");
+    let brake_event = network_builder.create_message_with_interval("brake_event", Duration::from_millis(100));
+    brake_event.set_any_std_id(MessagePriority::Realtime);
+    brake_event.add_transmitter("brakes");
+    brake_event.add_receiver("master");
+    brake_event.make_type_format().add_type("u8", "force");
+    if let Some((max_frames, window)) = burst {
+        brake_event.set_burst(max_frames, window);
+    }
+    let state = network_builder.create_message_with_interval("brake_state", Duration::from_millis(50));
+    state.set_any_std_id(MessagePriority::Low);
+    state.add_transmitter("brakes");
+    state.add_receiver("master");
+    state.make_type_format().add_type("u8", "state");
+    network_builder.build().unwrap()
+}
+
+fn message<'a>(network: &'a NetworkRef, name: &str) -> &'a canzero_config::config::MessageRef {
+    network.messages().iter().find(|m| m.name() == name).unwrap()
+}
+
+#[test]
+fn bursts_define_the_worst_case_release() {
+    let network = brake_network(Some((5, Duration::from_millis(10))));
+    let brake_event = message(&network, "brake_event");
+    assert_eq!(brake_event.burst().unwrap().max_frames(), 5);
+    assert_eq!(
+        simulator::worst_case_release(brake_event),
+        (Duration::from_millis(10), 5)
+    );
+    // a burst that is less frequent than the interval doesn't change anything.
+    let network = brake_network(Some((2, Duration::from_secs(1))));
+    assert_eq!(
+        simulator::worst_case_release(message(&network, "brake_event")),
+        (Duration::from_millis(100), 1)
+    );
+    assert!(network.to_json().contains("\"window_us\": 1000000"));
+}
+
+#[test]
+fn bursts_increase_the_simulated_load() {
+    let options = SimulationOptions {
+        duration: Duration::from_secs(1),
+    };
+    let average = simulator::simulate(&brake_network(None), &options);
+    let bursts = simulator::simulate(&brake_network(Some((5, Duration::from_millis(10)))), &options);
+    assert_eq!(average.message("brake_event").unwrap().frame_count(), 10);
+    let brake_event = bursts.message("brake_event").unwrap();
+    assert_eq!(brake_event.frame_count(), 500);
+    assert_eq!(brake_event.overrun_count(), 0);
+    // 490 additional frames of at least 47 bits at 125 kbit/s.
+    assert!(bursts.buses()[0].load() > average.buses()[0].load() + 0.18);
+}
+
+#[test]
+fn bursts_delay_lower_priority_messages() {
+    let average = brake_network(None);
+    let bursts = brake_network(Some((5, Duration::from_millis(10))));
+    let response_time = |network: &NetworkRef, name: &str| {
+        simulator::worst_case_response_time(network, message(network, name)).unwrap()
+    };
+    assert!(response_time(&bursts, "brake_state") > response_time(&average, "brake_state"));
+    // the last frame of a burst waits for the previous ones.
+    assert!(response_time(&bursts, "brake_event") > response_time(&average, "brake_event") * 2);
+}
+
+#[test]
+fn bursts_are_part_of_the_bus_load_diagnostic() {
+    let bus_load = |network: &NetworkRef| {
+        network
+            .diagnostics()
+            .iter()
+            .any(|d| d.kind() == DiagnosticKind::BusLoad)
+    };
+    assert!(!bus_load(&brake_network(None)));
+    assert!(bus_load(&brake_network(Some((8, Duration::from_millis(5))))));
+}
@@ -0,0 +1,92 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    diagnostics::DiagnosticKind,
+    errors::ConfigError,
+};
+
+fn network_builder(external: bool) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    network_builder.create_node("master");
+
+    let unreceived = network_builder.create_message("unreceived");
+    unreceived.set_any_std_id(MessagePriority::Normal);
+    unreceived.add_transmitter("becu");
+
+    let untransmitted = network_builder.create_message("untransmitted");
+    untransmitted.set_any_std_id(MessagePriority::Normal);
+    untransmitted.add_receiver("master");
+    if external {
+        unreceived.mark_external();
+        untransmitted.mark_external();
+    }
+
+    let connected = network_builder.create_message("connected");
+    connected.set_any_std_id(MessagePriority::Normal);
+    connected.add_transmitter("becu");
+    connected.add_receiver("master");
+    network_builder
+}
+
+fn diagnosed(network_builder: NetworkBuilder, kind: DiagnosticKind, name: &str) -> bool {
+    network_builder
+        .build()
+        .unwrap()
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == kind && d.message().contains(name))
+}
+
+#[test]
+fn message_connectivity_unreceived() {
+    assert!(diagnosed(
+        network_builder(false),
+        DiagnosticKind::MessageWithoutReceivers,
+        "unreceived"
+    ));
+}
+
+#[test]
+fn message_connectivity_untransmitted() {
+    assert!(diagnosed(
+        network_builder(false),
+        DiagnosticKind::MessageWithoutTransmitter,
+        "untransmitted"
+    ));
+}
+
+#[test]
+fn message_connectivity_connected() {
+    let network = network_builder(false).build().unwrap();
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| matches!(
+            d.kind(),
+            DiagnosticKind::MessageWithoutReceivers | DiagnosticKind::MessageWithoutTransmitter
+        ) && d.message().contains("connected")));
+}
+
+#[test]
+fn message_connectivity_external_messages_are_allowed() {
+    let network = network_builder(true).build().unwrap();
+    assert!(!network.diagnostics().iter().any(|d| matches!(
+        d.kind(),
+        DiagnosticKind::MessageWithoutReceivers | DiagnosticKind::MessageWithoutTransmitter
+    )));
+}
+
+#[test]
+fn message_connectivity_fallback_is_the_only_bus() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let message = network_builder.create_message("stranded");
+    message.set_any_std_id(MessagePriority::Normal);
+    message.add_transmitter("becu");
+    message.set_fallback_bus("can0");
+    match network_builder.build() {
+        Err(ConfigError::InvalidFallbackBus(msg)) => assert!(msg.contains("stranded")),
+        other => panic!("expected an invalid fallback bus, got {other:?}"),
+    }
+}
@@ -0,0 +1,50 @@
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef};
+
+fn build_network(documented: bool) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("master");
+    let message = network_builder.create_message("pdu_state");
+    message.set_std_id(0x100);
+    message.add_receiver("master");
+    message.add_description("state of the pdu");
+    if documented {
+        message.set_author("jane");
+        message.set_requirement("https://tracker.example/REQ-42");
+        message.set_revision_note("added the error flag");
+    }
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn documentation_carried_into_config() {
+    let network = build_network(true);
+    let message = network.messages().iter().find(|m| m.name() == "pdu_state").unwrap();
+    let documentation = message.documentation();
+    assert_eq!(documentation.author(), Some("jane"));
+    assert_eq!(documentation.requirement(), Some("https://tracker.example/REQ-42"));
+    assert_eq!(documentation.revision_note(), Some("added the error flag"));
+    assert!(!documentation.is_empty());
+
+    let undocumented = build_network(false);
+    let message = undocumented.messages().iter().find(|m| m.name() == "pdu_state").unwrap();
+    assert!(message.documentation().is_empty());
+    // documentation doesn't change the configuration.
+    assert_eq!(network.portable_hash(), undocumented.portable_hash());
+}
+
+#[test]
+fn documentation_exported() {
+    let network = build_network(true);
+    let json = network.to_json();
+    assert!(json.contains("\"author\": \"jane\""));
+    assert!(json.contains("\"requirement\": \"https://tracker.example/REQ-42\""));
+    assert!(json.contains("\"revision_note\": \"added the error flag\""));
+
+    let dbc = network.generate_dbc();
+    assert!(dbc.contains(
+        "CM_ BO_ 256 \"state of the pdu\nauthor: jane\nrequirement: https://tracker.example/REQ-42\nrevision: added the error flag\";"
+    ));
+    let undocumented = build_network(false).generate_dbc();
+    assert!(undocumented.contains("CM_ BO_ 256 \"state of the pdu\";"));
+}
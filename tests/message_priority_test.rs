@@ -0,0 +1,43 @@
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+#[test]
+fn priority_conversions_are_consistent() {
+    assert_eq!(MessagePriority::ALL.len(), MessagePriority::count());
+    for (i, priority) in MessagePriority::ALL.iter().enumerate() {
+        assert_eq!(priority.to_u32(), i as u32);
+        assert_eq!(MessagePriority::from_u32(i as u32), *priority);
+        assert_eq!(MessagePriority::try_from_u32(i as u32), Some(*priority));
+        assert!((priority.to_u32() as usize) < MessagePriority::count());
+    }
+    for i in MessagePriority::count() as u32..MessagePriority::count() as u32 + 4 {
+        assert_eq!(MessagePriority::try_from_u32(i), None);
+        assert_eq!(MessagePriority::from_u32(i), MessagePriority::SuperLow);
+    }
+    assert_eq!(MessagePriority::try_from_u32(u32::MAX), None);
+    // higher priorities get lower ids.
+    for pair in MessagePriority::ALL.windows(2) {
+        assert!(pair[0].min_id() < pair[1].min_id());
+    }
+}
+
+#[test]
+fn default_priority_lands_in_the_normal_bucket() {
+    assert_eq!(MessagePriority::default(), MessagePriority::Normal);
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    for i in 0..4 {
+        let message = network_builder.create_message(&format!("msg_{i}"));
+        message.add_receiver("becu");
+    }
+    let network = network_builder.build().unwrap();
+    for i in 0..4 {
+        let bucket = network
+            .expect_message(&format!("msg_{i}"))
+            .message()
+            .resolution_info()
+            .priority_bucket()
+            .expect("message was not assigned by the resolver");
+        assert!(bucket <= MessagePriority::default().to_u32());
+    }
+}
@@ -3,7 +3,7 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use can_config_rs::{
+use canzero_config::{
     builder::{MessagePriority, NetworkBuilder},
     config::MessageId,
 };
@@ -66,7 +66,7 @@ fn check_builder(det: impl Fn() -> NetworkBuilder) {
 #[test]
 fn message_resolution_empty_config() {
     check_builder(|| {
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
 
         network_builder
     });
@@ -77,14 +77,14 @@ fn perf1(node_count: u32, non_message_count: u32, fixed_message_count: u32, ide:
         let mut node_gen = MessageNameGen::new();
         let mut name_gen = MessageNameGen::new();
 
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
         let mut message_ids_used: HashSet<u32> = HashSet::new();
         for _ in 0..node_count {
             let node_name = node_gen.next();
             network_builder.create_node(&node_name);
             for _ in 0..non_message_count {
                 let name = name_gen.next();
-                let message = network_builder.create_message(&name, None);
+                let message = network_builder.create_message(&name);
                 let mut hasher = DefaultHasher::new();
                 name.hash(&mut hasher);
                 let hash = hasher.finish();
@@ -95,7 +95,7 @@ fn perf1(node_count: u32, non_message_count: u32, fixed_message_count: u32, ide:
             }
             for _ in 0..fixed_message_count {
                 let mut name = name_gen.next();
-                let message = network_builder.create_message(&name, None);
+                let message = network_builder.create_message(&name);
                 message.add_receiver(&node_name);
 
                 let mut hasher = DefaultHasher::new();
@@ -474,3 +474,43 @@ fn message_resolution_002_fixed_1to16x0to200() {
 //         }
 //     }
 // }
+
+#[test]
+fn message_resolution_fixed_ids_outside_of_all_sets() {
+    let network_builder = NetworkBuilder::new();
+    for node in ["a", "b", "c"] {
+        network_builder.create_node(node);
+    }
+    for i in 0..8 {
+        let message = network_builder.create_message(&format!("any_{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_receiver("a");
+    }
+    let network = network_builder.build().unwrap();
+    let assigned_ids: Vec<MessageId> = network
+        .messages()
+        .iter()
+        .filter(|m| m.name().starts_with("any_"))
+        .map(|m| m.id().clone())
+        .collect();
+
+    // no set has the receivers b and c, therefore the fixed messages
+    // can't be part of a set and the other messages have to avoid their ids.
+    for (i, id) in assigned_ids.iter().enumerate() {
+        let message = network_builder.create_message(&format!("fixed_{i}"));
+        message.set_std_id(id.as_u32());
+        message.add_receiver("b");
+        message.add_receiver("c");
+    }
+    let message = network_builder.create_message("fixed_ext");
+    message.set_ext_id(0x18FF50E5);
+    message.add_receiver("b");
+    check_builder(|| network_builder.clone());
+
+    let network = network_builder.build().unwrap();
+    for node_filters in canzero_config::builder::resolve_filters_only(&network) {
+        for message in node_filters.node().rx_messages() {
+            assert!(node_filters.filters().iter().any(|f| f.accepts_id(message.id())));
+        }
+    }
+}
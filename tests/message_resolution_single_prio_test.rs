@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 
-use can_config_rs::{
+use canzero_config::{
     builder::{MessagePriority, NetworkBuilder},
     config::MessageId,
+    errors::{CapacityError, ConfigError},
 };
 
 const CHECK_DET_ITERATIONS: u32 = 3;
@@ -62,29 +63,46 @@ fn check_builder(det: impl Fn() -> NetworkBuilder) {
 #[test]
 fn message_resolution_empty_config() {
     check_builder(|| {
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
 
         network_builder
     });
 }
 
-fn perf1(node_count: u32, message_count: u32, _: Vec<MessagePriority>) {
-    check_builder(|| {
-        let mut node_gen = MessageNameGen::new();
-        let mut name_gen = MessageNameGen::new();
-
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
-        for _ in 0..node_count {
-            let node_name = node_gen.next();
-            network_builder.create_node(&node_name);
-            for _ in 0..message_count {
-                let message = network_builder.create_message(&name_gen.next(), None);
-                message.set_any_std_id(MessagePriority::Normal);
-                message.add_receiver(&node_name);
-            }
+fn perf1_builder(node_count: u32, message_count: u32) -> NetworkBuilder {
+    let mut node_gen = MessageNameGen::new();
+    let mut name_gen = MessageNameGen::new();
+
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    for _ in 0..node_count {
+        let node_name = node_gen.next();
+        network_builder.create_node(&node_name);
+        for _ in 0..message_count {
+            let message = network_builder.create_message(&name_gen.next());
+            message.set_any_std_id(MessagePriority::Normal);
+            message.add_receiver(&node_name);
         }
-        network_builder
-    });
+    }
+    network_builder
+}
+
+fn perf1(node_count: u32, message_count: u32, _: Vec<MessagePriority>) {
+    check_builder(|| perf1_builder(node_count, message_count));
+}
+
+// networks that need more ids than the std id space provides next to the
+// priority bits fail with a capacity error instead of overflowing 11 bits.
+fn perf1_exceeds_capacity(node_count: u32, message_count: u32, _: Vec<MessagePriority>) {
+    match perf1_builder(node_count, message_count).build() {
+        Err(ConfigError::Capacity(CapacityError::IdSpace {
+            needed_setcodes,
+            available_setcodes,
+            priority_bits: _,
+            id_bits: 11,
+        })) => assert!(needed_setcodes > available_setcodes),
+        Err(error) => panic!("expected an id space error, got {error:?}"),
+        Ok(_) => panic!("expected an id space error"),
+    }
 }
 
 #[test]
@@ -106,17 +124,17 @@ fn message_resolution_1x1000_realtime() {
 
 #[test]
 fn message_resolution_1x2000_normal() {
-    perf1(1, 2000, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(1, 2000, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_1x2000_superlow() {
-    perf1(1, 2000, vec![MessagePriority::SuperLow]);
+    perf1_exceeds_capacity(1, 2000, vec![MessagePriority::SuperLow]);
 }
 
 #[test]
 fn message_resolution_1x2000_realtime() {
-    perf1(1, 2000, vec![MessagePriority::Realtime]);
+    perf1_exceeds_capacity(1, 2000, vec![MessagePriority::Realtime]);
 }
 
 #[test]
@@ -136,78 +154,78 @@ fn message_resolution_2x500_realtime() {
 
 #[test]
 fn message_resolution_2x1000_normal() {
-    perf1(2, 1000, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(2, 1000, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_2x1000_superlow() {
-    perf1(2, 1000, vec![MessagePriority::SuperLow]);
+    perf1_exceeds_capacity(2, 1000, vec![MessagePriority::SuperLow]);
 }
 
 #[test]
 fn message_resolution_2x1000_realtime() {
-    perf1(2, 1000, vec![MessagePriority::Realtime]);
+    perf1_exceeds_capacity(2, 1000, vec![MessagePriority::Realtime]);
 }
 
 #[test]
 fn message_resolution_4x500_normal() {
-    perf1(4, 500, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(4, 500, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_4x500_superlow() {
-    perf1(4, 500, vec![MessagePriority::SuperLow]);
+    perf1_exceeds_capacity(4, 500, vec![MessagePriority::SuperLow]);
 }
 
 #[test]
 fn message_resolution_4x500_realtime() {
-    perf1(4, 500, vec![MessagePriority::Realtime]);
+    perf1_exceeds_capacity(4, 500, vec![MessagePriority::Realtime]);
 }
 
 
 #[test]
 fn message_resolution_8x250_normal() {
-    perf1(8, 250, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(8, 250, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_8x250_superlow() {
-    perf1(8, 250, vec![MessagePriority::SuperLow]);
+    perf1_exceeds_capacity(8, 250, vec![MessagePriority::SuperLow]);
 }
 
 #[test]
 fn message_resolution_8x250_realtime() {
-    perf1(8, 250, vec![MessagePriority::Realtime]);
+    perf1_exceeds_capacity(8, 250, vec![MessagePriority::Realtime]);
 }
 
 
 #[test]
 fn message_resolution_16x125_normal() {
-    perf1(16, 125, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(16, 125, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_16x125_superlow() {
-    perf1(16, 125, vec![MessagePriority::SuperLow]);
+    perf1_exceeds_capacity(16, 125, vec![MessagePriority::SuperLow]);
 }
 
 #[test]
 fn message_resolution_16x125_realtime() {
-    perf1(16, 125, vec![MessagePriority::Realtime]);
+    perf1_exceeds_capacity(16, 125, vec![MessagePriority::Realtime]);
 }
 
 #[test]
 fn message_resolution_32x75_normal() {
-    perf1(32, 75, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(32, 75, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_32x75_superlow() {
-    perf1(32, 75, vec![MessagePriority::SuperLow]);
+    perf1_exceeds_capacity(32, 75, vec![MessagePriority::SuperLow]);
 }
 
 #[test]
 fn message_resolution_32x75_realtime() {
-    perf1(32, 75, vec![MessagePriority::Realtime]);
+    perf1_exceeds_capacity(32, 75, vec![MessagePriority::Realtime]);
 }
 
@@ -3,9 +3,10 @@ use std::{
     hash::{Hash, Hasher},
 };
 
-use can_config_rs::{
+use canzero_config::{
     builder::{MessagePriority, NetworkBuilder},
     config::MessageId,
+    errors::{CapacityError, ConfigError},
 };
 
 const CHECK_DET_ITERATIONS: u32 = 3;
@@ -65,35 +66,60 @@ fn check_builder(det: impl Fn() -> NetworkBuilder) {
 #[test]
 fn message_resolution_empty_config() {
     check_builder(|| {
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
+        let network_builder = canzero_config::builder::NetworkBuilder::new();
 
         network_builder
     });
 }
 
-fn perf1(node_count: u32, message_count: u32, priorities: Vec<MessagePriority>) {
-    check_builder(|| {
-        let mut node_gen = MessageNameGen::new();
-        let mut name_gen = MessageNameGen::new();
-
-        let network_builder = can_config_rs::builder::NetworkBuilder::new();
-        for _ in 0..node_count {
-            let node_name = node_gen.next();
-            network_builder.create_node(&node_name);
-            for _ in 0..message_count {
-                let name = name_gen.next();
-                let message = network_builder.create_message(&name_gen.next(), None);
-                let mut hasher = DefaultHasher::new();
-                name.hash(&mut hasher);
-                let hash = hasher.finish();
-                message.set_any_std_id(MessagePriority::from_u32(
-                    (hash as usize % MessagePriority::count()) as u32,
-                ));
-                message.add_receiver(&node_name);
-            }
+fn perf1_builder(node_count: u32, message_count: u32) -> NetworkBuilder {
+    let mut node_gen = MessageNameGen::new();
+    let mut name_gen = MessageNameGen::new();
+
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    for _ in 0..node_count {
+        let node_name = node_gen.next();
+        network_builder.create_node(&node_name);
+        for _ in 0..message_count {
+            let name = name_gen.next();
+            let message = network_builder.create_message(&name_gen.next());
+            let mut hasher = DefaultHasher::new();
+            name.hash(&mut hasher);
+            let hash = hasher.finish();
+            message.set_any_std_id(MessagePriority::from_u32(
+                (hash as usize % MessagePriority::count()) as u32,
+            ));
+            message.add_receiver(&node_name);
         }
-        network_builder
-    });
+    }
+    network_builder
+}
+
+fn perf1(node_count: u32, message_count: u32, _: Vec<MessagePriority>) {
+    check_builder(|| perf1_builder(node_count, message_count));
+}
+
+// networks that need more ids than the std id space provides next to the
+// priority bits fail with a capacity error instead of overflowing 11 bits.
+fn perf1_exceeds_capacity(node_count: u32, message_count: u32, _: Vec<MessagePriority>) {
+    match perf1_builder(node_count, message_count).build() {
+        Err(ConfigError::Capacity(CapacityError::IdSpace {
+            needed_setcodes,
+            available_setcodes,
+            priority_bits: _,
+            id_bits: 11,
+        })) => assert!(needed_setcodes > available_setcodes),
+        Err(error) => panic!("expected an id space error, got {error:?}"),
+        Ok(_) => panic!("expected an id space error"),
+    }
+}
+
+// sweeps reach sizes that don't fit into the std id space.
+fn perf1_sweep(node_count: u32, message_count: u32, priorities: Vec<MessagePriority>) {
+    match perf1_builder(node_count, message_count).build() {
+        Err(ConfigError::Capacity(CapacityError::IdSpace { .. })) => (),
+        _ => perf1(node_count, message_count, priorities),
+    }
 }
 
 #[test]
@@ -103,7 +129,7 @@ fn message_resolution_1x1000_uniform() {
 
 #[test]
 fn message_resolution_1x2000_uniform() {
-    perf1(1, 2000, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(1, 2000, vec![MessagePriority::Normal]);
 }
 
 
@@ -115,27 +141,27 @@ fn message_resolution_2x500_normal() {
 
 #[test]
 fn message_resolution_2x1000_uniform() {
-    perf1(2, 1000, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(2, 1000, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_4x500_uniform() {
-    perf1(4, 500, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(4, 500, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_8x250_uniform() {
-    perf1(8, 250, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(8, 250, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_16x125_uniform() {
-    perf1(16, 125, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(16, 125, vec![MessagePriority::Normal]);
 }
 
 #[test]
 fn message_resolution_32x75_uniform() {
-    perf1(32, 75, vec![MessagePriority::Normal]);
+    perf1_exceeds_capacity(32, 75, vec![MessagePriority::Normal]);
 }
 
 
@@ -144,7 +170,7 @@ fn message_resolution_000_fixed_1to16x0to200() {
     for node_count in 1..16 {
         let max_msg_count = (200f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -159,7 +185,7 @@ fn message_resolution_000_fixed_1to16x0to400() {
     for node_count in 1..16 {
         let max_msg_count = (400f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -173,7 +199,7 @@ fn message_resolution_000_fixed_1to16x0to600() {
     for node_count in 1..16 {
         let max_msg_count = (600f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -187,7 +213,7 @@ fn message_resolution_000_fixed_1to16x0to800() {
     for node_count in 1..16 {
         let max_msg_count = (800f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -201,7 +227,7 @@ fn message_resolution_000_fixed_1to16x0to1000() {
     for node_count in 1..16 {
         let max_msg_count = (1000f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -215,7 +241,7 @@ fn message_resolution_000_fixed_1to16x0to1200() {
     for node_count in 1..16 {
         let max_msg_count = (1200f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -229,7 +255,7 @@ fn message_resolution_000_fixed_1to16x0to1400() {
     for node_count in 1..16 {
         let max_msg_count = (1400f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -243,7 +269,7 @@ fn message_resolution_000_fixed_1to16x0to1600() {
     for node_count in 1..16 {
         let max_msg_count = (1600f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -257,7 +283,7 @@ fn message_resolution_000_fixed_1to16x0to1800() {
     for node_count in 1..16 {
         let max_msg_count = (1800f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -271,7 +297,7 @@ fn message_resolution_000_fixed_1to16x0to2000() {
     for node_count in 1..16 {
         let max_msg_count = (2000f64 / (node_count as f64)) as u32;
         for msg_count in 1..max_msg_count {
-            perf1(
+            perf1_sweep(
                 node_count,
                 msg_count,
                 vec![],
@@ -0,0 +1,66 @@
+use canzero_config::{builder::NetworkBuilder, config::diff::MessageChange, errors::ConfigError};
+
+fn build_network(with_legacy: bool, deprecate_legacy: bool) -> canzero_config::config::NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    let brake = network_builder.create_message("brake");
+    brake.set_std_id(0x10);
+    brake.set_version("1.2.0");
+    if with_legacy {
+        let legacy = network_builder.create_message("legacy_brake");
+        legacy.set_std_id(0x11);
+        legacy.set_version("1.0.0");
+        if deprecate_legacy {
+            legacy.deprecate("1.2.0", "use brake instead");
+        }
+    }
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn versioning_carried_into_config() {
+    let network = build_network(true, true);
+    let legacy = network.messages().iter().find(|m| m.name() == "legacy_brake").unwrap();
+    assert_eq!(legacy.version(), Some("1.0.0"));
+    let deprecation = legacy.deprecation().unwrap();
+    assert_eq!(deprecation.since(), "1.2.0");
+    assert_eq!(deprecation.note(), "use brake instead");
+    let brake = network.messages().iter().find(|m| m.name() == "brake").unwrap();
+    assert!(brake.deprecation().is_none());
+}
+
+#[test]
+fn versioning_removing_deprecated_message_is_not_breaking() {
+    let old = build_network(true, true);
+    let new = build_network(false, false);
+    let diff = old.diff(&new);
+    assert!(diff
+        .changes()
+        .iter()
+        .any(|c| matches!(c, MessageChange::Removed(m) if m.name() == "legacy_brake")));
+    assert!(!diff.is_breaking());
+}
+
+#[test]
+fn versioning_removing_message_is_breaking() {
+    let old = build_network(true, false);
+    let new = build_network(false, false);
+    let diff = old.diff(&new);
+    assert!(diff.is_breaking());
+    assert_eq!(diff.breaking_changes().len(), 1);
+    assert!(!new.diff(&old).is_breaking());
+}
+
+#[test]
+fn versioning_invalid_version() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let brake = network_builder.create_message("brake");
+    brake.set_std_id(0x10);
+    brake.set_version("v1");
+    match network_builder.build() {
+        Err(ConfigError::InvalidVersion(_)) => (),
+        other => panic!("expected an invalid version, got {other:?}"),
+    }
+}
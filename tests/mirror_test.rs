@@ -0,0 +1,82 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+fn telemetry_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    let state = network_builder.define_enum("bms_state");
+    state.add_entry("IDLE", Some(1)).unwrap();
+    state.add_entry("CHARGING", Some(2)).unwrap();
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entry("voltage", "d16<0..800>");
+    bms.create_object_entry("state", "bms_state");
+    bms.create_object_entry("cell_count", "u8");
+    let pack = bms.create_stream("pack");
+    pack.add_entry("voltage");
+    pack.add_entry("state");
+    pack.mirror();
+    let cells = bms.create_stream("cells");
+    cells.add_entry("cell_count");
+    network_builder
+}
+
+#[test]
+fn mirrored_streams_are_received_by_the_master() {
+    let network_builder = telemetry_network();
+    network_builder.set_mirror_target("telemetry", 7000);
+    let network = network_builder.build().unwrap();
+
+    let mirror = network.mirror().unwrap();
+    assert_eq!(mirror.master(), "telemetry");
+    assert_eq!(mirror.port(), 7000);
+    let streams: Vec<(&str, &str)> = mirror
+        .streams()
+        .iter()
+        .map(|(node, stream)| (node.as_str(), stream.name()))
+        .collect();
+    assert_eq!(streams, vec![("bms", "pack")]);
+
+    network
+        .expect_message("bms_stream_pack")
+        .with_receiver("telemetry");
+}
+
+#[test]
+fn proto_schema_and_mapping_cover_the_mirrored_signals() {
+    let network_builder = telemetry_network();
+    network_builder.set_mirror_target("telemetry", 7000);
+    let network = network_builder.build().unwrap();
+
+    let proto = network.generate_proto();
+    assert!(proto.contains("syntax = \"proto3\";"));
+    assert!(proto.contains("enum bms_state {"));
+    assert!(proto.contains("  bms_state_UNSPECIFIED = 0;"));
+    assert!(proto.contains("  bms_state_CHARGING = 2;"));
+    assert!(proto.contains("message bms_pack {"));
+    assert!(proto.contains("  double voltage = 1;"));
+    assert!(proto.contains("  bms_state state = 2;"));
+    assert!(proto.contains("    bms_pack bms_pack = 2;"));
+    assert!(!proto.contains("bms_cells"));
+
+    let mapping = network.mirror_mapping_csv();
+    let mut lines = mapping.lines();
+    assert!(lines.next().unwrap().starts_with("proto_message,field_number,field"));
+    let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert_eq!(rows.len(), 2);
+    let message = network.messages().iter().find(|m| m.name() == "bms_stream_pack").unwrap();
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(row.len(), 15);
+        assert_eq!(row[0], "bms_pack");
+        assert_eq!(row[1], (i + 1).to_string());
+        assert_eq!(row[8], format!("{:#X}", message.id().as_u32()));
+    }
+    assert_eq!(rows[0][2], "voltage");
+    assert_eq!(rows[1][2], "state");
+}
+
+#[test]
+fn mirrored_streams_require_a_mirror_target() {
+    match telemetry_network().build() {
+        Err(ConfigError::InvalidMirror(_)) => (),
+        other => panic!("expected InvalidMirror, got {other:?}"),
+    }
+}
@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{MockValue, MockWaveform, NetworkRef, ObjectEntryAccess},
+};
+
+fn network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let state = network_builder.define_enum("pod_state");
+    state.add_entry("idle", Some(0)).unwrap();
+    state.add_entry("levitating", Some(3)).unwrap();
+    let vec2 = network_builder.define_struct("vec2");
+    vec2.add_attribute("x", "d16<-10..10>").unwrap();
+    vec2.add_attribute("y", "i8").unwrap();
+    let sensor = network_builder.create_node("sensor");
+    sensor.create_object_entry("temperature", "d10<0..100>").set_bounds("20", "80");
+    sensor.create_object_entry("state", "pod_state");
+    sensor.create_object_entry("position", "vec2");
+    sensor
+        .create_object_entry("serial", "u16")
+        .set_access(ObjectEntryAccess::Const);
+    let fast = sensor.create_stream("fast");
+    fast.add_entry("temperature");
+    fast.set_interval(Duration::from_millis(10), Duration::from_millis(20));
+    let slow = sensor.create_stream("slow");
+    slow.add_entry("temperature");
+    slow.add_entry("state");
+    slow.set_interval(Duration::from_millis(100), Duration::from_millis(100));
+    network_builder.create_node("master").receive_stream("sensor", "slow").unwrap();
+    network_builder.create_node("logger").receive_stream("sensor", "fast").unwrap();
+    network_builder.build().unwrap()
+}
+
+fn value<'a>(values: &'a [MockValue], path: &str) -> &'a MockValue {
+    values
+        .iter()
+        .find(|value| value.node() == "sensor" && value.path() == path)
+        .unwrap_or_else(|| panic!("no mock value {path}"))
+}
+
+#[test]
+fn ranges_follow_types_and_bounds() {
+    let values = network().mock_values();
+    let temperature = value(&values, "temperature");
+    assert_eq!((temperature.min(), temperature.max()), (20.0, 80.0));
+    assert!(!temperature.is_integer());
+    let y = value(&values, "position.y");
+    assert_eq!(y.object_entry(), "position");
+    assert_eq!((y.min(), y.max()), (-128.0, 127.0));
+    let x = value(&values, "position.x");
+    assert!((x.min() + 10.0).abs() < 1e-9 && (x.max() - 10.0).abs() < 1e-3);
+}
+
+#[test]
+fn rates_follow_the_fastest_stream() {
+    let values = network().mock_values();
+    assert_eq!(value(&values, "temperature").interval(), Some(Duration::from_millis(10)));
+    assert_eq!(value(&values, "state").interval(), Some(Duration::from_millis(100)));
+    assert_eq!(value(&values, "position.x").interval(), None);
+    let series = value(&values, "temperature").series(Duration::from_secs(1));
+    assert_eq!(series.len(), 100);
+}
+
+#[test]
+fn samples_stay_within_range() {
+    for value in network().mock_values() {
+        for (_, sample) in value.series(Duration::from_secs(30)) {
+            assert!(sample >= value.min() && sample <= value.max(), "{} = {sample}", value.path());
+            if value.is_integer() {
+                assert_eq!(sample, sample.round());
+            }
+        }
+    }
+}
+
+#[test]
+fn enums_step_through_their_entries_and_constants_stay() {
+    let values = network().mock_values();
+    let state = value(&values, "state");
+    let MockWaveform::Steps { values: entries, dwell } = state.waveform() else {
+        panic!("expected steps");
+    };
+    assert_eq!(entries, &vec![0.0, 3.0]);
+    assert_eq!(state.sample(Duration::ZERO), 0.0);
+    assert_eq!(state.sample(*dwell), 3.0);
+    let serial = value(&values, "serial");
+    assert_eq!(serial.sample(Duration::ZERO), serial.sample(Duration::from_secs(7)));
+    // the same config always yields the same series.
+    let again = network().mock_values();
+    let temperature = value(&again, "temperature");
+    assert_eq!(
+        temperature.sample(Duration::from_millis(1234)),
+        value(&values, "temperature").sample(Duration::from_millis(1234))
+    );
+}
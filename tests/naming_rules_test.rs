@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{naming::NamingKind, MessagePriority, NetworkBuilder},
+    diagnostics::DiagnosticKind,
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(500_000));
+    network_builder.create_bus("can1", Some(500_000));
+    network_builder.create_node("master");
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entry("cell_voltage", "u16");
+    bms.create_object_entry("CellTemperature", "u16");
+    for (name, bus) in [("bms_state", "can0"), ("BmsAlarm", "can1")] {
+        let message = network_builder.create_message_with_interval(name, Duration::from_millis(10));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.assign_bus(bus);
+        message.add_transmitter("bms");
+        message.add_receiver("master");
+        message.make_type_format().add_type("u8", "value");
+    }
+    network_builder
+}
+
+fn naming_violations(network_builder: NetworkBuilder) -> Vec<String> {
+    let network = network_builder.build().unwrap();
+    network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::NamingConvention)
+        .map(|d| d.message().to_owned())
+        .collect()
+}
+
+#[test]
+fn messages_are_prefixed_by_their_transmitter() {
+    let network_builder = network_builder();
+    network_builder
+        .add_naming_rule(NamingKind::Message, "^{node}_[a-z0-9_]+$")
+        .unwrap();
+    let violations = naming_violations(network_builder);
+    // generated messages (streams, commands, get/set) are not checked.
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].contains("BmsAlarm"));
+}
+
+#[test]
+fn bus_rules_only_apply_to_messages_of_the_bus() {
+    let network_builder = network_builder();
+    network_builder.add_bus_naming_rule("can0", "^[A-Z]").unwrap();
+    let violations = naming_violations(network_builder);
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].contains("bms_state"));
+    assert!(violations[0].ends_with("of bus can0"));
+}
+
+#[test]
+fn object_entries_are_qualified_by_their_node() {
+    let network_builder = network_builder();
+    network_builder
+        .add_naming_rule(NamingKind::ObjectEntry, "^[a-z][a-z0-9_]*$")
+        .unwrap();
+    let violations = naming_violations(network_builder);
+    assert_eq!(violations.len(), 1);
+    assert!(violations[0].contains("bms::CellTemperature"));
+}
+
+#[test]
+fn invalid_rules_are_rejected() {
+    let network_builder = network_builder();
+    let result = network_builder.add_naming_rule(NamingKind::Node, "^(unclosed");
+    assert!(matches!(result, Err(ConfigError::InvalidNamingRule(_))));
+}
+
+#[test]
+fn violations_are_exported_as_json() {
+    let network_builder = network_builder();
+    network_builder
+        .add_naming_rule(NamingKind::Node, "^[a-z]+$")
+        .unwrap();
+    network_builder.create_node("Pdu");
+    let network = network_builder.build().unwrap();
+    let json = network.diagnostics_to_json();
+    assert!(json.contains("\"kind\": \"NamingConvention\""));
+    assert!(json.contains("\"severity\": \"warn\""));
+    assert!(json.contains("node Pdu violates the naming rule"));
+}
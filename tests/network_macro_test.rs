@@ -0,0 +1,86 @@
+#![cfg(feature = "macros")]
+
+use std::time::Duration;
+
+use canzero_config::{config::SignalType, network};
+
+#[test]
+fn network_macro_expands_to_builder_calls() {
+    let network_builder = network! {
+        bus can0: 1_000_000;
+        bus can1;
+        node secu {
+            bus: can0;
+            description: "sensor ecu";
+            fault overtemperature: Warning;
+            object_entry position: "d16<0..10>" {
+                unit: "m";
+                persistent;
+            }
+            object_entry speed: "u8";
+            stream state {
+                interval: Duration::from_millis(10), Duration::from_millis(50);
+                entries: [position, speed];
+            }
+            command reset {
+                argument level: "u8";
+                rate: 0.5;
+                burst: 2;
+                callees: [becu];
+            }
+        }
+        node becu {
+            bus: can1;
+            object_entry secu_position: "d16<0..10>";
+            receive_stream secu.state {
+                map: position => secu_position;
+            }
+        }
+        message pdu_state (Duration::from_millis(100)) {
+            bus: can0;
+            std_id: 0x100;
+            receivers: [becu];
+            burst: 3, Duration::from_millis(10);
+            requirement: "REQ-42";
+            signals {
+                state: SignalType::UnsignedInt { size: 8 };
+                error: SignalType::UnsignedInt { size: 1 };
+            }
+        }
+    }
+    .unwrap();
+    let network = network_builder.build().unwrap();
+
+    assert_eq!(network.buses().len(), 2);
+    assert_eq!(network.buses()[0].baudrate(), 1_000_000);
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    assert_eq!(secu.description().map(|d| d.as_str()), Some("sensor ecu"));
+    assert!(secu.object_entries().len() >= 2);
+    assert_eq!(secu.persistent_object_entries().len(), 1);
+    assert!(secu.tx_streams().iter().any(|stream| stream.name() == "state"));
+    let reset = secu.commands().iter().find(|command| command.name() == "reset").unwrap();
+    assert_eq!(reset.frame_interval(), Duration::from_secs(1));
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    assert!(becu.rx_streams().iter().any(|stream| stream.name() == "state"));
+
+    let pdu_state = network
+        .messages()
+        .iter()
+        .find(|message| message.name() == "pdu_state")
+        .unwrap();
+    assert_eq!(pdu_state.id().as_u32(), 0x100);
+    assert_eq!(pdu_state.signals().len(), 2);
+    assert_eq!(pdu_state.burst().unwrap().max_frames(), 3);
+    assert_eq!(pdu_state.documentation().requirement(), Some("REQ-42"));
+}
+
+#[test]
+fn network_macro_forwards_builder_errors() {
+    let result = network! {
+        node secu {
+            fault overtemperature: Warning;
+            fault overtemperature: Error;
+        }
+    };
+    assert!(result.is_err());
+}
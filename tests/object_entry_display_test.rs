@@ -0,0 +1,64 @@
+use canzero_config::{builder::NetworkBuilder, config::NetworkRef, errors::ConfigError};
+
+fn wheel_network(factor: f64) -> canzero_config::errors::Result<NetworkRef> {
+    let network_builder = NetworkBuilder::new();
+    let mlu = network_builder.create_node("mlu");
+    let wheel_speed = mlu.create_object_entry("wheel_speed", "u16");
+    wheel_speed.add_unit("km/h");
+    wheel_speed.set_display(factor, "m/s");
+    let temperature = mlu.create_object_entry("temperature", "u16");
+    temperature.add_unit("K");
+    temperature.set_display_with_offset(1.0, -273.15, "°C");
+    mlu.create_object_entry("state", "u8");
+    network_builder.build()
+}
+
+#[test]
+fn display_conversion_is_independent_of_the_unit() {
+    let network = wheel_network(1.0 / 3.6).unwrap();
+    let mlu = network.nodes().iter().find(|n| n.name() == "mlu").unwrap();
+    let entry = |name: &str| {
+        mlu.object_entries()
+            .iter()
+            .find(|oe| oe.name() == name)
+            .unwrap()
+            .clone()
+    };
+    let wheel_speed = entry("wheel_speed");
+    assert_eq!(wheel_speed.unit(), Some("km/h"));
+    let display = wheel_speed.display().unwrap();
+    assert_eq!(display.unit(), "m/s");
+    assert!((display.apply(36.0) - 10.0).abs() < 1e-9);
+    assert!((display.revert(10.0) - 36.0).abs() < 1e-9);
+    let temperature = entry("temperature");
+    assert!((temperature.display().unwrap().apply(273.15)).abs() < 1e-9);
+    assert!(entry("state").display().is_none());
+}
+
+#[test]
+fn display_conversion_is_exported() {
+    let network = wheel_network(0.5).unwrap();
+    let json = network.to_json();
+    assert!(json.contains("\"factor\": 0.5"));
+    assert!(json.contains("\"unit\": \"m/s\""));
+    assert!(json.contains("\"display\": null"));
+    assert!(network.generate_json_schema().contains("\"x-display\""));
+    assert!(network
+        .generate_typescript()
+        .contains("[km/h], displayed as value * 0.5 + 0.0 [m/s]"));
+}
+
+#[test]
+fn display_conversion_changes_the_hash() {
+    let half = wheel_network(0.5).unwrap();
+    let quarter = wheel_network(0.25).unwrap();
+    assert_ne!(half.portable_hash(), quarter.portable_hash());
+}
+
+#[test]
+fn zero_factor_is_rejected() {
+    match wheel_network(0.0) {
+        Err(ConfigError::InvalidDisplayConversion(msg)) => assert!(msg.contains("mlu::wheel_speed")),
+        other => panic!("expected InvalidDisplayConversion, got {:?}", other.err()),
+    }
+}
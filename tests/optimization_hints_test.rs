@@ -0,0 +1,107 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{NetworkRef, OptimizationHint},
+};
+
+fn network(messages: &[(&str, &str, &str, u64, &[u8])]) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(500_000));
+    for (name, tx, rx, interval_ms, sizes) in messages {
+        let message = network_builder.create_message_with_interval(name, Duration::from_millis(*interval_ms));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_transmitter(tx);
+        message.add_receiver(rx);
+        let format = message.make_type_format();
+        for (i, size) in sizes.iter().enumerate() {
+            format.add_type(&format!("u{size}"), &format!("value{i}"));
+        }
+    }
+    network_builder.build().unwrap()
+}
+
+fn merges(network: &NetworkRef) -> Vec<(String, String)> {
+    network
+        .optimization_hints()
+        .into_iter()
+        .filter_map(|hint| match hint {
+            OptimizationHint::Merge { messages, .. } => Some(messages),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn unused_payload_is_reported() {
+    let network = network(&[("bms_state", "bms", "master", 10, &[8, 4])]);
+    let hints = network.optimization_hints();
+    assert!(hints.contains(&OptimizationHint::UnusedPayload {
+        message: "bms_state".to_owned(),
+        used_bits: 12,
+        dlc: 2,
+    }));
+    // the heartbeat and the get/set protocol are not reported.
+    assert!(!hints.iter().any(|hint| matches!(
+        hint,
+        OptimizationHint::UnusedPayload { message, .. } if message == "heartbeat"
+    )));
+}
+
+#[test]
+fn small_messages_with_same_endpoints_are_merged() {
+    let network = network(&[
+        ("bms_voltage", "bms", "master", 10, &[16, 8]),
+        ("bms_current", "bms", "master", 15, &[16]),
+        ("bms_temperature", "bms", "pdu", 10, &[16]),
+    ]);
+    let merges = merges(&network);
+    assert_eq!(merges.len(), 1);
+    let (a, b) = &merges[0];
+    let mut pair = [a.as_str(), b.as_str()];
+    pair.sort();
+    assert_eq!(pair, ["bms_current", "bms_voltage"]);
+    let hint = network
+        .optimization_hints()
+        .into_iter()
+        .find(|hint| matches!(hint, OptimizationHint::Merge { .. }))
+        .unwrap();
+    let OptimizationHint::Merge { dlc, interval, saved_load, .. } = hint else {
+        unreachable!();
+    };
+    assert_eq!(dlc, 5);
+    assert_eq!(interval, Duration::from_millis(10));
+    assert!(saved_load > 0.0);
+}
+
+#[test]
+fn incompatible_messages_are_not_merged() {
+    // the intervals differ too much.
+    assert!(merges(&network(&[
+        ("bms_voltage", "bms", "master", 10, &[16]),
+        ("bms_current", "bms", "master", 100, &[16]),
+    ]))
+    .is_empty());
+    // the merged payload doesn't fit into a frame.
+    assert!(merges(&network(&[
+        ("bms_voltage", "bms", "master", 10, &[32, 16]),
+        ("bms_current", "bms", "master", 10, &[32]),
+    ]))
+    .is_empty());
+    // different transmitters.
+    assert!(merges(&network(&[
+        ("bms_voltage", "bms", "master", 10, &[16]),
+        ("pdu_voltage", "pdu", "master", 10, &[16]),
+    ]))
+    .is_empty());
+}
+
+#[test]
+fn every_message_is_part_of_one_merge() {
+    let network = network(&[
+        ("a", "bms", "master", 10, &[8]),
+        ("b", "bms", "master", 10, &[8]),
+        ("c", "bms", "master", 10, &[8]),
+    ]);
+    assert_eq!(merges(&network).len(), 1);
+}
@@ -0,0 +1,36 @@
+use canzero_config::builder::{network_builder::OrphanPolicy, MessagePriority, NetworkBuilder};
+
+fn network_with_orphan() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    network_builder.create_node("master");
+    let connected = network_builder.create_message("connected");
+    connected.set_any_std_id(MessagePriority::Normal);
+    connected.add_transmitter("becu");
+    connected.add_receiver("master");
+    let orphan = network_builder.create_message("orphan");
+    orphan.set_std_id(0x10);
+    network_builder
+}
+
+#[test]
+fn orphan_messages_are_detected() {
+    let network_builder = network_with_orphan();
+    assert_eq!(network_builder.orphan_messages(), vec!["orphan".to_owned()]);
+    let network = network_builder.build().unwrap();
+    assert!(network.messages().iter().any(|m| m.name() == "orphan"));
+}
+
+#[test]
+fn orphan_messages_can_be_pruned() {
+    let network_builder = network_with_orphan();
+    network_builder.set_orphan_policy(OrphanPolicy::Prune);
+    let network = network_builder.build().unwrap();
+    assert!(!network.messages().iter().any(|m| m.name() == "orphan"));
+    assert!(network.messages().iter().any(|m| m.name() == "connected"));
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.message().contains("orphan")));
+}
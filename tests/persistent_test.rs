@@ -0,0 +1,28 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn persistent_object_entries_are_listed_per_node() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "d16<0..100>");
+    becu.create_object_entry("pressure_offset", "d16<-1..1>").persistent();
+    becu.create_object_entry("calibration_count", "u16").persistent();
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("temperature", "d16<-40..120>");
+    let network = network_builder.build().unwrap();
+
+    let becu = network.expect_node("becu");
+    let persistent = becu.node().persistent_object_entries();
+    let persistent: Vec<&str> = persistent.iter().map(|oe| oe.name()).collect();
+    assert_eq!(persistent, vec!["pressure_offset", "calibration_count"]);
+    let pressure = becu.node().object_entries().iter().find(|oe| oe.name() == "pressure").unwrap();
+    assert!(!pressure.persistent());
+    assert!(network.expect_node("secu").node().persistent_object_entries().is_empty());
+
+    let json = network.to_json();
+    assert!(json.contains("\"persistent\": true"));
+    let header = network.generate_cpp();
+    assert!(header.contains("becu_object_entries"));
+    assert!(!header.contains("secu_object_entries"));
+}
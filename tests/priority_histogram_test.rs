@@ -0,0 +1,102 @@
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+fn network_builder() -> NetworkBuilder {
+    let builder = NetworkBuilder::new();
+    builder.create_bus("can0", None);
+    builder.create_bus("can1", None);
+    builder.create_node("becu");
+    let secu = builder.create_node("secu");
+    secu.assign_bus("can1");
+    for (name, priority) in [
+        ("brake", MessagePriority::Realtime),
+        ("levitation", MessagePriority::High),
+        ("temperature", MessagePriority::Low),
+        ("log", MessagePriority::SuperLow),
+    ] {
+        let message = builder.create_message(name);
+        message.set_any_std_id(priority);
+        message.add_transmitter("becu");
+        message.add_receiver("secu");
+    }
+    let fixed = builder.create_message("fixed");
+    fixed.set_std_id(0x7F0);
+    fixed.add_receiver("becu");
+    builder
+}
+
+#[test]
+fn histogram_counts_every_message() {
+    let network = network_builder().build().unwrap();
+    let histogram = network.priority_histogram();
+    assert_eq!(histogram.buckets().len(), MessagePriority::ALL.len());
+    assert_eq!(histogram.fixed(), 1);
+    let placed: usize = histogram.buckets().iter().map(|(_, count)| count).sum();
+    assert_eq!(placed + histogram.fixed(), network.messages().len());
+    for message in network.messages() {
+        let Some(bucket) = message.resolution_info().priority_bucket() else {
+            continue;
+        };
+        assert!(histogram.count(MessagePriority::from_u32(bucket)) >= 1);
+    }
+}
+
+#[test]
+fn id_regions_cover_the_resolved_ids() {
+    let network = network_builder().build().unwrap();
+    let regions: Vec<_> = network.buses().iter().map(|bus| bus.id_utilization()).collect();
+    let first = regions[0];
+    assert_eq!(first.len(), MessagePriority::ALL.len());
+    // the regions are contiguous and start at id 0.
+    assert_eq!(first[0].ids().start, 0);
+    for pair in first.windows(2) {
+        assert_eq!(pair[0].ids().end, pair[1].ids().start);
+        assert!(pair[0].priority().to_u32() < pair[1].priority().to_u32());
+    }
+    for (bus_regions, bus) in regions.iter().zip(network.buses()) {
+        // all buses share the regions.
+        assert_eq!(
+            bus_regions.iter().map(|region| region.ids()).collect::<Vec<_>>(),
+            first.iter().map(|region| region.ids()).collect::<Vec<_>>()
+        );
+        for region in bus_regions.iter() {
+            let on_bus = network
+                .messages()
+                .iter()
+                .filter(|message| message.bus().id() == bus.id())
+                .filter(|message| region.ids().contains(&message.id().as_u32()))
+                .count();
+            assert_eq!(region.used(), on_bus);
+            assert!(region.utilization() <= 1.0);
+        }
+    }
+    // every resolved message lies in the region of the bucket it landed in.
+    for message in network.messages() {
+        let info = message.resolution_info();
+        let Some(bucket) = info.priority_bucket() else {
+            continue;
+        };
+        let region = message
+            .bus()
+            .id_utilization()
+            .iter()
+            .find(|region| region.ids().contains(&message.id().as_u32()))
+            .unwrap();
+        assert_eq!(region.priority().to_u32(), bucket);
+    }
+    // the fixed id is far outside of the resolved id space.
+    let used: usize = regions.iter().flat_map(|r| r.iter()).map(|r| r.used()).sum();
+    assert_eq!(used + 1, network.messages().len());
+}
+
+#[test]
+fn id_utilization_survives_the_artifact_cache() {
+    let dir = std::env::temp_dir().join("canzero_priority_histogram_test");
+    let _ = std::fs::remove_dir_all(&dir);
+    let resolved = network_builder().build_with_cache(&dir).unwrap();
+    let cached = network_builder().build_with_cache(&dir).unwrap();
+    for (resolved_bus, cached_bus) in resolved.buses().iter().zip(cached.buses()) {
+        assert!(!cached_bus.id_utilization().is_empty());
+        assert_eq!(resolved_bus.id_utilization(), cached_bus.id_utilization());
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::{Assignment, NetworkRef},
+    diagnostics::DiagnosticKind,
+};
+
+const PRIORITIES: [MessagePriority; 4] = [
+    MessagePriority::Realtime,
+    MessagePriority::High,
+    MessagePriority::Normal,
+    MessagePriority::Low,
+];
+
+// streams of every priority between different pairs of nodes,
+// such that they end up in different receive sets.
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let nodes = ["bms", "pdu", "motor", "master"];
+    for name in nodes {
+        network_builder.create_node(name);
+    }
+    for (i, tx) in nodes.iter().enumerate() {
+        let rx = nodes[(i + 1) % nodes.len()];
+        let tx_node = network_builder.create_node(tx);
+        let rx_node = network_builder.create_node(rx);
+        for (p, priority) in PRIORITIES.iter().enumerate() {
+            let entry = format!("value_{p}");
+            tx_node.create_object_entry(&entry, "u16");
+            let stream = tx_node.create_stream(&entry);
+            stream.add_entry(&entry);
+            stream.set_interval(Duration::from_millis(10), Duration::from_millis(100));
+            stream.set_priority(*priority);
+            rx_node.receive_stream(tx, &entry).unwrap();
+        }
+    }
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn requested_priority_is_part_of_the_resolution_info() {
+    let network = build_network();
+    for (p, priority) in PRIORITIES.iter().enumerate() {
+        let message = network.expect_message(&format!("bms_stream_value_{p}"));
+        assert_eq!(message.message().resolution_info().requested_priority(), Some(priority.to_u32()));
+    }
+}
+
+#[test]
+fn resolved_ids_respect_the_requested_priorities() {
+    let network = build_network();
+    let assignment = Assignment::of(&network);
+    assert_eq!(assignment.verify(), vec![]);
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::PriorityInversion));
+    // every realtime message arbitrates before every low message.
+    for realtime in assignment.messages().iter().filter(|m| m.priority() == Some(0)) {
+        for low in assignment.messages().iter().filter(|m| m.priority() == Some(3)) {
+            assert!(realtime.id().as_u32() < low.id().as_u32(), "{} {}", realtime.name(), low.name());
+        }
+    }
+}
+
+#[test]
+fn fixed_ids_have_no_requested_priority() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let message = network_builder.create_message("fixed");
+    message.set_std_id(0x7F0);
+    let network = network_builder.build().unwrap();
+    let fixed = network.expect_message("fixed");
+    assert_eq!(fixed.message().resolution_info().requested_priority(), None);
+}
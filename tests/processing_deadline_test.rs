@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn processing_deadline() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let emergency = network_builder.create_message("emergency");
+    emergency.set_std_id(0x1);
+    emergency.add_transmitter("master");
+    emergency.add_receiver("becu");
+    emergency.set_processing_deadline(Duration::from_micros(250));
+    let telemetry = network_builder.create_message("telemetry");
+    telemetry.set_std_id(0x2);
+    telemetry.add_transmitter("becu");
+    telemetry.add_receiver("master");
+    let network = network_builder.build().unwrap();
+
+    assert_eq!(
+        network
+            .expect_message("emergency")
+            .message()
+            .processing_deadline(),
+        Some(&Duration::from_micros(250))
+    );
+    assert_eq!(
+        network
+            .expect_message("telemetry")
+            .message()
+            .processing_deadline(),
+        None
+    );
+
+    let cpp = network.generate_cpp();
+    assert!(cpp.contains("static constexpr uint32_t processing_deadline_us = 250;"));
+    assert_eq!(cpp.matches("processing_deadline_us").count(), 1);
+    assert!(network.to_json().contains("\"processing_deadline_us\": 250"));
+}
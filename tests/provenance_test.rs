@@ -0,0 +1,85 @@
+use canzero_config::{
+    builder::{bus_balancing::LocalityBalancer, NetworkBuilder},
+    config::provenance::CRATE_VERSION,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu").create_object_entry("pressure", "u16");
+    network_builder
+}
+
+#[test]
+fn provenance_defaults_to_the_generator() {
+    let network = network_builder().build().unwrap();
+    let provenance = network.provenance();
+    assert_eq!(provenance.crate_version(), CRATE_VERSION);
+    assert_eq!(provenance.source_revision(), None);
+    assert!(provenance.input_files().is_empty());
+    assert_eq!(provenance.resolver(), "GreedyBalancer, receiver set folding off, seed off");
+}
+
+#[test]
+fn source_revision_inputs_and_resolver_are_recorded() {
+    let dir = std::env::temp_dir().join("canzero_provenance_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    let input = dir.join("pod.toml");
+    std::fs::write(&input, "pressure = \"u16\"").unwrap();
+    let network_builder = network_builder();
+    network_builder.set_source_revision("3f2a91c");
+    network_builder.add_input_file(&input).unwrap();
+    network_builder.add_input_file(&input).unwrap();
+    network_builder.set_bus_balancer(LocalityBalancer::new());
+    network_builder.set_resolution_seed(7);
+    let network = network_builder.build().unwrap();
+    let provenance = network.provenance();
+    assert_eq!(provenance.source_revision(), Some("3f2a91c"));
+    assert_eq!(provenance.input_files().len(), 1);
+    assert_eq!(provenance.input_files()[0].path(), input.to_string_lossy());
+    assert!(provenance.resolver().starts_with("LocalityBalancer"));
+    assert!(provenance.resolver().ends_with("seed 7"));
+
+    // the hash follows the content of the file.
+    std::fs::write(&input, "pressure = \"u32\"").unwrap();
+    let network_builder = self::network_builder();
+    network_builder.add_input_file(&input).unwrap();
+    let changed = network_builder.build().unwrap();
+    assert_ne!(changed.provenance().input_files()[0].hash(), provenance.input_files()[0].hash());
+    assert!(self::network_builder().add_input_file(dir.join("missing.toml")).is_err());
+}
+
+#[test]
+fn provenance_is_exported() {
+    let network_builder = network_builder();
+    network_builder.set_source_revision("3f2a91c");
+    let network = network_builder.build().unwrap();
+    let json = network.to_json();
+    assert!(json.contains(&format!("\"crate_version\": \"{CRATE_VERSION}\"")));
+    assert!(json.contains("\"source_revision\": \"3f2a91c\""));
+    let summary = network.provenance().to_string();
+    assert!(summary.contains("source revision 3f2a91c"));
+    assert!(network.generate_dbc().contains(&format!("CM_ \"{summary}\";")));
+    assert!(network.generate_python().contains(&format!("PROVENANCE = {summary:?}")));
+    assert!(network.generate_cpp().contains(&format!("// provenance : {summary}")));
+}
+
+#[test]
+fn imported_dbc_files_are_inputs() {
+    let dir = std::env::temp_dir().join("canzero_provenance_test_dbc");
+    std::fs::create_dir_all(&dir).unwrap();
+    let dbc = dir.join("legacy.dbc");
+    std::fs::write(
+        &dbc,
+        "VERSION \"\"\n\nNS_ :\n\nBS_:\n\nBU_: inverter\n\n\
+         BO_ 291 inverter_status: 2 inverter\n \
+         SG_ temperature : 0|16@1+ (1,0) [0|65535] \"\" Vector__XXX\n\n",
+    )
+    .unwrap();
+    let network_builder = NetworkBuilder::new();
+    network_builder.include_dbc("can0", dbc.to_str().unwrap()).unwrap();
+    let network = network_builder.build().unwrap();
+    let input_files = network.provenance().input_files();
+    assert_eq!(input_files.len(), 1);
+    assert_eq!(input_files[0].path(), dbc.to_str().unwrap());
+}
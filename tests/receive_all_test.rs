@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{resolve_filters_only, MessagePriority, NetworkBuilder},
+    config::NetworkRef,
+    diagnostics::DiagnosticKind,
+};
+
+fn network(logger: bool) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    for node in ["master", "bms", "pdu"] {
+        network_builder.create_node(node);
+    }
+    if logger {
+        network_builder.create_node("logger").receive_all();
+    }
+    for (name, receivers) in [
+        ("bms_state", &["master"][..]),
+        ("pdu_state", &["master", "bms"][..]),
+        ("master_state", &["bms", "pdu"][..]),
+    ] {
+        let message = network_builder.create_message_with_interval(name, Duration::from_millis(10));
+        message.set_any_std_id(MessagePriority::Normal);
+        for rx in receivers {
+            message.add_receiver(rx);
+        }
+        message.make_type_format().add_type("u8", "value");
+    }
+    let unreceived = network_builder.create_message("unreceived");
+    unreceived.set_any_std_id(MessagePriority::Low);
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn logger_receives_every_message() {
+    let network = network(true);
+    let logger = network.nodes().iter().find(|n| n.name() == "logger").unwrap();
+    assert!(logger.receive_all());
+    for message in network.messages() {
+        assert!(
+            logger.rx_messages().iter().any(|m| m.name() == message.name()),
+            "{} is not received by the logger",
+            message.name()
+        );
+    }
+    let node_filters = resolve_filters_only(&network);
+    let logger_filters = node_filters.iter().find(|f| f.node().name() == "logger").unwrap();
+    for message in network.messages() {
+        assert!(logger_filters.filters().iter().any(|f| f.accepts_id(message.id())));
+    }
+    assert!(logger_filters.software_filters().is_empty());
+    assert!(logger.software_filters().is_empty());
+}
+
+#[test]
+fn logger_doesnt_change_the_receiver_sets() {
+    let without_logger = network(false);
+    let with_logger = network(true);
+    for message in without_logger.messages() {
+        let other = with_logger
+            .messages()
+            .iter()
+            .find(|m| m.name() == message.name())
+            .unwrap();
+        assert_eq!(message.id(), other.id(), "id of {} changed", message.name());
+    }
+}
+
+#[test]
+fn logger_isnt_a_receiver_for_diagnostics() {
+    let network = network(true);
+    assert!(network.diagnostics().iter().any(|d| {
+        d.kind() == DiagnosticKind::MessageWithoutReceivers && d.message().contains("unreceived")
+    }));
+}
@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, NetworkRef, SignalType},
+};
+
+fn build_network() -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let pid = network_builder.create_node("pid");
+    let sensor = network_builder.create_node("sensor");
+    sensor.create_object_entry("position", "u16");
+    sensor.create_object_entry("velocity", "i16");
+    pid.create_object_entry("position_input", "u16");
+    pid.create_object_entry("velocity_input", "i16");
+
+    let stream = sensor.create_stream("state");
+    stream.set_interval(Duration::from_millis(1), Duration::from_millis(10));
+    stream.add_entry("position");
+    stream.add_entry("velocity");
+    stream.set_entry_on_receive("position", "update_pid_input");
+    stream.annotate_entry("position", "priority", "isr");
+    let rx_stream = pid.receive_stream("sensor", "state").unwrap();
+    rx_stream.map("position", "position_input");
+    rx_stream.map("velocity", "velocity_input");
+
+    let message = network_builder.create_message_with_interval("emergency", Duration::from_millis(50));
+    message.add_transmitter("sensor");
+    message.add_receiver("pid");
+    message
+        .make_signal_format()
+        .add_signal(
+            Signal::new("stop", None, SignalType::UnsignedInt { size: 1 }, 0)
+                .with_on_receive("on_emergency_stop"),
+        )
+        .unwrap();
+
+    network_builder.build().unwrap()
+}
+
+fn signal<'a>(network: &'a NetworkRef, message: &str, signal: &str) -> &'a Signal {
+    network
+        .messages()
+        .iter()
+        .find(|m| m.name() == message)
+        .unwrap()
+        .signals()
+        .iter()
+        .find(|s| s.name().ends_with(signal))
+        .unwrap()
+}
+
+#[test]
+fn stream_entry_handlers_are_copied_to_signals() {
+    let network = build_network();
+    let position = signal(&network, "sensor_stream_state", "position");
+    assert_eq!(position.on_receive(), Some("update_pid_input"));
+    assert_eq!(position.annotation("priority"), Some("isr"));
+    let velocity = signal(&network, "sensor_stream_state", "velocity");
+    assert_eq!(velocity.on_receive(), None);
+    assert!(velocity.annotations().is_empty());
+}
+
+#[test]
+fn signal_handlers_are_preserved() {
+    let network = build_network();
+    assert_eq!(
+        signal(&network, "emergency", "stop").on_receive(),
+        Some("on_emergency_stop")
+    );
+}
+
+#[test]
+fn handlers_are_exported_to_json() {
+    let json = build_network().to_json();
+    assert!(json.contains("\"on_receive\": \"update_pid_input\""));
+    assert!(json.contains("\"on_receive\": \"on_emergency_stop\""));
+}
+
+#[test]
+#[should_panic]
+fn annotating_unknown_entries_panics() {
+    let network_builder = NetworkBuilder::new();
+    let sensor = network_builder.create_node("sensor");
+    sensor.create_object_entry("position", "u16");
+    let stream = sensor.create_stream("state");
+    stream.set_entry_on_receive("position", "update_pid_input");
+}
@@ -0,0 +1,49 @@
+use canzero_config::{builder::NetworkBuilder, errors::ConfigError};
+
+#[test]
+fn receiving_an_own_stream_fails() {
+    let network_builder = NetworkBuilder::new();
+    let becu = network_builder.create_node("becu");
+    becu.create_stream("state");
+    match becu.receive_stream("becu", "state") {
+        Err(ConfigError::InvalidReceiveStream(msg)) => assert!(msg.contains("becu")),
+        other => panic!("expected InvalidReceiveStream, got {other:?}"),
+    }
+}
+
+#[test]
+fn strict_receive_stream_requires_the_tx_stream() {
+    let network_builder = NetworkBuilder::new();
+    let master = network_builder.create_node("master");
+    match master.receive_stream_strict("becu", "state") {
+        Err(ConfigError::InvalidReceiveStream(msg)) => assert!(msg.contains("no node becu")),
+        other => panic!("expected InvalidReceiveStream, got {other:?}"),
+    }
+    let becu = network_builder.create_node("becu");
+    match master.receive_stream_strict("becu", "state") {
+        Err(ConfigError::InvalidReceiveStream(msg)) => assert!(msg.contains("no stream state")),
+        other => panic!("expected InvalidReceiveStream, got {other:?}"),
+    }
+    becu.create_stream("state");
+    assert!(master.receive_stream_strict("becu", "state").is_ok());
+
+    // the lenient variant creates the tx node and stream.
+    master.receive_stream("pdu", "state").unwrap();
+    assert_eq!(network_builder.0.borrow().nodes.borrow().len(), 3);
+}
+
+#[test]
+fn commands_can_not_call_themselves() {
+    let network_builder = NetworkBuilder::new();
+    let becu = network_builder.create_node("becu");
+    let command = becu.create_command("reset", None);
+    match command.add_callee("becu") {
+        Err(ConfigError::InvalidCallee(msg)) => assert!(msg.contains("reset")),
+        other => panic!("expected InvalidCallee, got {other:?}"),
+    }
+    command.add_callee("master").unwrap();
+    command.add_callee("master").unwrap();
+    let network = network_builder.build().unwrap();
+    let master = network.nodes().iter().find(|node| node.name() == "master").unwrap();
+    assert_eq!(master.extern_commands().len(), 1);
+}
@@ -0,0 +1,68 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::NetworkRef,
+};
+
+// one large receiver set and a few sets that lack a single receiver.
+fn build_network(folding: Option<usize>) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    for node in ["a", "b", "c", "d"] {
+        network_builder.create_node(node);
+    }
+    if let Some(max_cost) = folding {
+        network_builder.fold_receiver_sets(max_cost);
+    }
+    let receivers: [&[&str]; 4] = [&["a", "b", "c", "d"], &["a", "b", "c"], &["b", "c", "d"], &["a", "d"]];
+    for (i, receivers) in receivers.iter().enumerate() {
+        let count = if i == 0 { 8 } else { 1 };
+        for j in 0..count {
+            let message = network_builder.create_message(&format!("msg_{i}_{j}"));
+            message.set_any_std_id(MessagePriority::Normal);
+            for rx in receivers.iter() {
+                message.add_receiver(rx);
+            }
+        }
+    }
+    network_builder.build().unwrap()
+}
+
+fn setcode_len(network: &NetworkRef) -> u32 {
+    network
+        .expect_message("msg_0_0")
+        .message()
+        .resolution_info()
+        .setcode_len()
+}
+
+fn setcode(network: &NetworkRef, message: &str) -> Option<u32> {
+    network.expect_message(message).message().resolution_info().setcode()
+}
+
+#[test]
+fn folding_shortens_the_setcode() {
+    let unfolded = build_network(None);
+    let folded = build_network(Some(2));
+    assert!(setcode_len(&folded) < setcode_len(&unfolded));
+    // without folding {a, b, c} and {b, c, d} are different sets.
+    assert_ne!(setcode(&unfolded, "msg_1_0"), setcode(&unfolded, "msg_2_0"));
+    // folded into {a, b, c, d} they share a set.
+    assert_eq!(setcode(&folded, "msg_1_0"), setcode(&folded, "msg_2_0"));
+    // the declared receivers don't change.
+    folded
+        .expect_message("msg_1_0")
+        .with_receiver("a")
+        .with_receiver("b")
+        .with_receiver("c");
+    let d = folded.expect_node("d");
+    assert!(!d.node().rx_messages().iter().any(|m| m.name() == "msg_1_0"));
+}
+
+#[test]
+fn folding_respects_the_cost_threshold() {
+    let unfolded = build_network(None);
+    let free_only = build_network(Some(0));
+    assert_eq!(setcode_len(&free_only), setcode_len(&unfolded));
+    let folded = build_network(Some(1));
+    assert_eq!(setcode(&folded, "msg_1_0"), setcode(&folded, "msg_2_0"));
+}
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{resolve_filters_only, MessagePriority, NetworkBuilder},
+    config::{FrameFormat, FrameTimeModel},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let builder = NetworkBuilder::new();
+    builder.create_node("secu");
+    builder.create_node("pressure_sensor");
+    let pressure = builder.create_message("pressure");
+    pressure.set_any_std_id(MessagePriority::Normal);
+    pressure.add_transmitter("pressure_sensor");
+    pressure.add_receiver("secu");
+    pressure.set_remote_requestable(true);
+    pressure.add_remote_requester("secu");
+    pressure.set_remote_response_latency(Duration::from_millis(2));
+    builder
+}
+
+#[test]
+fn remote_request_in_config_and_filters() {
+    let network = network_builder().build().unwrap();
+    let pressure = network.messages().iter().find(|m| m.name() == "pressure").unwrap();
+    let remote_request = pressure.remote_request().unwrap();
+    assert_eq!(remote_request.requesters(), &vec!["secu".to_owned()]);
+    assert_eq!(remote_request.response_latency(), Some(&Duration::from_millis(2)));
+    assert!(network.messages().iter().filter(|m| m.name() != "pressure").all(|m| m.remote_request().is_none()));
+
+    // the sensor only transmits, but has to accept the remote frames.
+    let filters = resolve_filters_only(&network);
+    let sensor = filters.iter().find(|f| f.node().name() == "pressure_sensor").unwrap();
+    assert!(sensor.filters().iter().any(|filter| filter.accepts_id(pressure.id())));
+    assert!(!sensor.software_filters().iter().any(|m| m.name() == "pressure"));
+    let sensor_node = network.nodes().iter().find(|n| n.name() == "pressure_sensor").unwrap();
+    assert!(!sensor_node.software_filters().iter().any(|m| m.name() == "pressure"));
+}
+
+#[test]
+fn remote_request_in_dbc() {
+    let network = network_builder().build().unwrap();
+    let pressure = network.messages().iter().find(|m| m.name() == "pressure").unwrap();
+    let dbc = network.generate_dbc();
+    let id = pressure.id().as_u32();
+    assert!(dbc.contains("BA_DEF_ BO_ \"RemoteRequestable\" INT 0 1;\n"));
+    assert!(dbc.contains(&format!("BA_ \"RemoteRequestable\" BO_ {id} 1;\n")));
+    assert!(dbc.contains(&format!("BA_ \"RemoteRequesters\" BO_ {id} \"secu\";\n")));
+    assert!(dbc.contains(&format!("BA_ \"RemoteResponseLatency\" BO_ {id} 2000;\n")));
+    can_dbc::DBC::from_slice(dbc.as_bytes()).unwrap();
+
+    let builder = NetworkBuilder::new();
+    builder.create_node("secu");
+    let plain = builder.build().unwrap().generate_dbc();
+    assert!(!plain.contains("RemoteRequestable"));
+}
+
+#[test]
+fn invalid_remote_requests() {
+    let builder = network_builder();
+    let unknown = builder.create_message("unknown_requester");
+    unknown.add_transmitter("secu");
+    unknown.add_receiver("pressure_sensor");
+    unknown.set_remote_requestable(true);
+    unknown.add_remote_requester("becu");
+    assert!(matches!(builder.build(), Err(ConfigError::InvalidRemoteRequest(_))));
+
+    let builder = network_builder();
+    let not_requestable = builder.create_message("not_requestable");
+    not_requestable.add_transmitter("secu");
+    not_requestable.add_receiver("pressure_sensor");
+    not_requestable.add_remote_requester("pressure_sensor");
+    assert!(matches!(builder.build(), Err(ConfigError::InvalidRemoteRequest(_))));
+
+    let builder = network_builder();
+    builder.set_frame_time_model(FrameTimeModel {
+        format: FrameFormat::Fd,
+        ..Default::default()
+    });
+    assert!(matches!(builder.build(), Err(ConfigError::InvalidRemoteRequest(_))));
+}
@@ -0,0 +1,57 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, SignalType},
+};
+
+#[test]
+fn reserved_bits_in_signal_format() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let message = network_builder.create_message("charger_status");
+    message.set_std_id(0x20);
+    let format = message.make_signal_format();
+    format
+        .add_signal(Signal::create("voltage", None, SignalType::UnsignedInt { size: 12 }))
+        .unwrap();
+    format.add_reserved(4);
+    format
+        .add_signal(Signal::create("current", None, SignalType::UnsignedInt { size: 8 }))
+        .unwrap();
+    let network = network_builder.build().unwrap();
+
+    let message = network.messages().iter().find(|m| m.name() == "charger_status").unwrap();
+    assert_eq!(message.dlc(), 3);
+    let current = message.signals().iter().find(|s| s.name() == "charger_status_current").unwrap();
+    assert_eq!(current.byte_offset(), 16);
+    let reserved: Vec<_> = message.signals().iter().filter(|s| s.is_reserved()).collect();
+    assert_eq!(reserved.len(), 1);
+    assert_eq!(reserved[0].byte_offset(), 12);
+    assert_eq!(reserved[0].size(), 4);
+
+    let header = network.generate_cpp();
+    assert!(header.contains("static constexpr uint64_t reserved_mask = 0xF000ull;"));
+    assert!(!header.contains("charger_status_reserved0;"));
+}
+
+#[test]
+fn reserved_bits_in_type_format() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let message = network_builder.create_message("charger_limits");
+    message.set_std_id(0x21);
+    let format = message.make_type_format();
+    format.add_type("u8", "max_voltage");
+    format.add_reserved(8);
+    format.add_type("u8", "max_current");
+    let network = network_builder.build().unwrap();
+
+    let message = network.messages().iter().find(|m| m.name() == "charger_limits").unwrap();
+    assert_eq!(message.dlc(), 3);
+    let encoding = message.encoding().unwrap();
+    assert_eq!(encoding.attributes().len(), 2);
+    let reserved = message.signals().iter().find(|s| s.is_reserved()).unwrap();
+    assert_eq!(reserved.byte_offset(), 8);
+
+    let module = network.generate_python();
+    assert!(module.contains("if frame & 0xFF00:"));
+}
@@ -0,0 +1,55 @@
+use canzero_config::builder::MessagePriority;
+
+#[test]
+fn resolution_info_of_auto_assigned_messages() {
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    for i in 0..4 {
+        let message = network_builder.create_message(&format!("msg_{i}"));
+        message.set_any_std_id(MessagePriority::High);
+        message.add_receiver("becu");
+    }
+    let fixed = network_builder.create_message("fixed");
+    fixed.set_std_id(0x7F0);
+    let network = network_builder.build().unwrap();
+
+    for i in 0..4 {
+        let message = network
+            .messages()
+            .iter()
+            .find(|m| m.name() == format!("msg_{i}"))
+            .unwrap();
+        let info = message.resolution_info();
+        assert_eq!(info.bus().name(), "can0");
+        let setcode = info.setcode().expect("message was not part of a set");
+        let priority = message.id().as_u32() >> info.setcode_len();
+        assert_eq!(message.id().as_u32() & ((1 << info.setcode_len()) - 1), setcode);
+        assert!(info.priority_bucket().unwrap() <= MessagePriority::High.to_u32());
+        assert!(info.position().unwrap() <= priority);
+    }
+    let fixed = network.messages().iter().find(|m| m.name() == "fixed").unwrap();
+    assert_eq!(fixed.resolution_info().priority_bucket(), None);
+    assert_eq!(fixed.resolution_info().position(), None);
+}
+
+#[test]
+fn resolution_info_of_messages_past_the_bucket_layout() {
+    let network_builder = canzero_config::builder::NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("becu");
+    // the reserved range covers the whole layout, which pushes
+    // the message to the ids above it.
+    network_builder.reserve_std_id_range(0..0x40);
+    let message = network_builder.create_message("low");
+    message.set_any_std_id(MessagePriority::Low);
+    message.add_receiver("becu");
+    let network = network_builder.build().unwrap();
+
+    let message = network.messages().iter().find(|m| m.name() == "low").unwrap();
+    let info = message.resolution_info();
+    let priority = message.id().as_u32() >> info.setcode_len();
+    assert!(message.id().as_u32() >= 0x40);
+    assert_eq!(info.priority_bucket(), Some(MessagePriority::SuperLow.to_u32()));
+    assert!(info.position().unwrap() <= priority);
+}
@@ -0,0 +1,75 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::NetworkRef,
+};
+
+fn sensor_network(reversed: bool, seed: Option<u64>) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_bus("can1", None);
+    let mut nodes = vec!["master", "bms", "imu", "motor"];
+    let mut messages: Vec<u32> = (0..24).collect();
+    if reversed {
+        nodes.reverse();
+        messages.reverse();
+    }
+    for node in &nodes {
+        network_builder.create_node(node);
+    }
+    for i in messages {
+        let message = network_builder.create_message(&format!("sensor_{i}"));
+        message.set_any_std_id(if i % 3 == 0 {
+            MessagePriority::High
+        } else {
+            MessagePriority::Normal
+        });
+        for node in &nodes {
+            if i % 2 == 0 || *node != "motor" {
+                message.add_receiver(node);
+            }
+        }
+    }
+    if let Some(seed) = seed {
+        network_builder.set_resolution_seed(seed);
+    }
+    network_builder
+}
+
+fn assignment(network: &NetworkRef) -> Vec<(String, u32, String)> {
+    let mut assignment: Vec<(String, u32, String)> = network
+        .messages()
+        .iter()
+        .map(|message| {
+            (
+                message.name().to_owned(),
+                message.id().as_u32(),
+                message.bus().name().to_owned(),
+            )
+        })
+        .collect();
+    assignment.sort();
+    assignment
+}
+
+#[test]
+fn identical_inputs_yield_identical_assignments() {
+    let first = sensor_network(false, None).build().unwrap();
+    let second = sensor_network(false, None).build().unwrap();
+    assert_eq!(assignment(&first), assignment(&second));
+    // the order in which the builder objects were created doesn't matter.
+    let reversed = sensor_network(true, None).build().unwrap();
+    assert_eq!(assignment(&first), assignment(&reversed));
+}
+
+#[test]
+fn seeded_resolution_is_reproducible() {
+    let unseeded = assignment(&sensor_network(false, None).build().unwrap());
+    let mut differs = false;
+    for seed in 0..8 {
+        let first = assignment(&sensor_network(false, Some(seed)).build().unwrap());
+        let second = assignment(&sensor_network(true, Some(seed)).build().unwrap());
+        assert_eq!(first, second, "seed {seed} is not deterministic");
+        differs |= first != unseeded;
+    }
+    assert!(differs, "the seed doesn't affect the tie-breaking order");
+}
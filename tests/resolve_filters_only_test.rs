@@ -0,0 +1,76 @@
+use canzero_config::builder::{resolve_filters_only, MessagePriority, NetworkBuilder};
+
+#[test]
+fn fixed_ids_get_exact_filters() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("a");
+    network_builder.create_node("b");
+    for (name, id, receivers) in [
+        ("m0", 0x100, &["a"][..]),
+        ("m1", 0x101, &["a"][..]),
+        ("m2", 0x102, &["a", "b"][..]),
+        ("m3", 0x103, &["b"][..]),
+    ] {
+        let message = network_builder.create_message(name);
+        message.set_std_id(id);
+        for rx in receivers {
+            message.add_receiver(rx);
+        }
+    }
+    let network = network_builder.build().unwrap();
+
+    let node_filters = resolve_filters_only(&network);
+    assert_eq!(node_filters.len(), network.nodes().len());
+    let a = node_filters.iter().find(|f| f.node().name() == "a").unwrap();
+    for message in a.node().rx_messages() {
+        assert!(
+            a.filters().iter().any(|f| f.accepts_id(message.id())),
+            "{} is not accepted",
+            message.name()
+        );
+    }
+    // 0x100 and 0x101 only differ in a single bit.
+    let merged = a.filters().iter().find(|f| f.accepts(0x100, false)).unwrap();
+    assert!(merged.accepts(0x101, false));
+    assert!(!merged.accepts(0x102, false));
+    assert!(!merged.accepts(0x100, true));
+    // exact filters don't need software filtering.
+    let fixed_software_filters = a
+        .software_filters()
+        .iter()
+        .filter(|m| m.name().starts_with('m'))
+        .count();
+    assert_eq!(fixed_software_filters, 0);
+}
+
+#[test]
+fn resolved_ids_are_filtered_by_setcode() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.create_node("a");
+    network_builder.create_node("b");
+    for (i, receivers) in [&["a"][..], &["b"][..], &["a", "b"][..]].iter().enumerate() {
+        let message = network_builder.create_message(&format!("msg_{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        for rx in receivers.iter() {
+            message.add_receiver(rx);
+        }
+    }
+    let network = network_builder.build().unwrap();
+
+    for node_filters in resolve_filters_only(&network) {
+        let node = node_filters.node();
+        for message in node.rx_messages() {
+            assert!(node_filters.filters().iter().any(|f| f.accepts_id(message.id())));
+        }
+        for message in network.messages() {
+            let accepted = node_filters.filters().iter().any(|f| f.accepts_id(message.id()));
+            let received = node.rx_messages().iter().any(|m| m.name() == message.name());
+            let dropped = node_filters.software_filters().iter().any(|m| m.name() == message.name());
+            assert_eq!(accepted, received || dropped, "{} on {}", message.name(), node.name());
+        }
+        // the resolver already produced exact receiver sets.
+        assert_eq!(node_filters.software_filters().len(), node.software_filters().len());
+    }
+}
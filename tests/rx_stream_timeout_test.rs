@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{stream_builder::ReceiveStreamBuilder, NetworkBuilder},
+    errors::ConfigError,
+};
+
+fn network_builder() -> (NetworkBuilder, ReceiveStreamBuilder) {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    let stream = becu.create_stream("state");
+    stream.add_entry("pressure");
+    stream.set_interval(Duration::from_millis(10), Duration::from_millis(50));
+    let secu = network_builder.create_node("secu");
+    let rx_stream = secu.receive_stream("becu", "state").unwrap();
+    rx_stream.map("pressure", "becu_pressure");
+    (network_builder, rx_stream)
+}
+
+#[test]
+fn rx_stream_timeout_is_exposed() {
+    let (network_builder, rx_stream) = network_builder();
+    rx_stream.set_timeout(Duration::from_millis(150));
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    assert_eq!(secu.rx_streams()[0].timeout(), Some(&Duration::from_millis(150)));
+    let becu = network.nodes().iter().find(|node| node.name() == "becu").unwrap();
+    assert_eq!(becu.tx_streams()[0].timeout(), None);
+    assert!(network.to_json().contains("\"timeout_us\": 150000"));
+}
+
+#[test]
+fn rx_stream_without_timeout() {
+    let (network_builder, _) = network_builder();
+    let network = network_builder.build().unwrap();
+    let secu = network.nodes().iter().find(|node| node.name() == "secu").unwrap();
+    assert_eq!(secu.rx_streams()[0].timeout(), None);
+}
+
+#[test]
+fn rx_stream_timeout_shorter_than_max_interval() {
+    let (network_builder, rx_stream) = network_builder();
+    rx_stream.set_timeout(Duration::from_millis(20));
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidReceiveStream(_))
+    ));
+}
+
+#[test]
+fn rx_stream_timeout_changes_hash() {
+    let (without, _) = network_builder();
+    let (with, rx_stream) = network_builder();
+    rx_stream.set_timeout(Duration::from_millis(100));
+    let without = without.build().unwrap().portable_hash();
+    let with = with.build().unwrap().portable_hash();
+    assert_ne!(without, with);
+}
@@ -0,0 +1,76 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{signal::Signal, SignalType},
+    errors::ConfigError,
+};
+
+#[test]
+fn signal_placement_explicit_start_bits() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let message = network_builder.create_message("charger_cmd");
+    message.set_std_id(0x30);
+    let format = message.make_signal_format();
+    format
+        .add_signal(Signal::create("enable", None, SignalType::UnsignedInt { size: 1 }))
+        .unwrap();
+    format
+        .add_signal_at(Signal::create("voltage", None, SignalType::UnsignedInt { size: 16 }), 8)
+        .unwrap();
+    // packed directly after voltage.
+    format
+        .add_signal(Signal::create("current", None, SignalType::UnsignedInt { size: 8 }))
+        .unwrap();
+    let network = network_builder.build().unwrap();
+
+    let message = network.messages().iter().find(|m| m.name() == "charger_cmd").unwrap();
+    let offset_of = |name: &str| {
+        message
+            .signals()
+            .iter()
+            .find(|s| s.name() == format!("charger_cmd_{name}"))
+            .unwrap()
+            .byte_offset()
+    };
+    assert_eq!(offset_of("enable"), 0);
+    assert_eq!(offset_of("voltage"), 8);
+    assert_eq!(offset_of("current"), 24);
+    assert_eq!(message.dlc(), 4);
+}
+
+#[test]
+fn signal_placement_explicit_type_start_bits() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let message = network_builder.create_message("charger_limits");
+    message.set_std_id(0x31);
+    let format = message.make_type_format();
+    format.add_type("u8", "max_voltage");
+    format.add_type_at("u16", "max_current", 32);
+    let network = network_builder.build().unwrap();
+    let message = network.messages().iter().find(|m| m.name() == "charger_limits").unwrap();
+    assert_eq!(message.signals()[1].byte_offset(), 32);
+    assert_eq!(message.dlc(), 6);
+}
+
+#[test]
+fn signal_placement_overlap() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let message = network_builder.create_message("charger_cmd");
+    message.set_std_id(0x30);
+    let format = message.make_signal_format();
+    format
+        .add_signal(Signal::create("voltage", None, SignalType::UnsignedInt { size: 16 }))
+        .unwrap();
+    format
+        .add_signal_at(Signal::create("current", None, SignalType::UnsignedInt { size: 8 }), 12)
+        .unwrap();
+    match network_builder.build() {
+        Err(ConfigError::OverlappingSignals(msg)) => {
+            assert!(msg.contains("charger_cmd_voltage"));
+            assert!(msg.contains("charger_cmd_current"));
+        }
+        other => panic!("expected overlapping signals, got {other:?}"),
+    }
+}
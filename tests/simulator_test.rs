@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{resolve_filters_only, MessagePriority, NetworkBuilder},
+    simulator::{self, SimulationOptions},
+};
+
+fn sensor_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(500000));
+    let master = network_builder.create_node("master");
+    let secu = network_builder.create_node("secu");
+    for i in 0..4 {
+        let name = format!("temperature_{i}");
+        secu.create_object_entry(&name, "d16<-40..120>");
+        let stream = secu.create_stream(&format!("temperatures_{i}"));
+        stream.add_entry(&name);
+        stream.set_interval(Duration::from_millis(5), Duration::from_millis(10));
+        stream.set_priority(MessagePriority::from_u32(i));
+        master.receive_stream("secu", &format!("temperatures_{i}")).unwrap();
+    }
+    network_builder
+}
+
+#[test]
+fn simulator_bus_load_matches_frame_count() {
+    let network = sensor_network().build().unwrap();
+    let report = simulator::simulate(
+        &network,
+        &SimulationOptions {
+            duration: Duration::from_secs(1),
+        },
+    );
+    assert_eq!(report.buses().len(), 1);
+    let bus = &report.buses()[0];
+    assert!(bus.load() > 0.0 && bus.load() < 1.0, "load = {}", bus.load());
+    for i in 0..4 {
+        let stream_message = report
+            .message(&format!("secu_stream_temperatures_{i}"))
+            .expect("stream message was not simulated");
+        // released every 10ms for one second.
+        assert_eq!(stream_message.frame_count(), 100);
+        assert_eq!(stream_message.overrun_count(), 0);
+    }
+    let heartbeat = report.message("heartbeat").unwrap();
+    assert_eq!(heartbeat.frame_count(), 10);
+}
+
+#[test]
+fn simulator_queuing_delay_respects_arbitration() {
+    let network = sensor_network().build().unwrap();
+    let report = simulator::simulate(&network, &SimulationOptions::default());
+    let bus = &report.buses()[0];
+    // the message with the lowest id is never delayed by arbitration
+    // at the synchronous release.
+    let first = bus.messages().first().unwrap();
+    assert_eq!(*first.worst_queuing_delay(), Duration::ZERO);
+    assert!(bus.worst_queuing_delay() > Duration::ZERO);
+}
+
+#[test]
+fn simulator_queuing_delay_by_priority() {
+    let network = sensor_network().build().unwrap();
+    let report = simulator::simulate(&network, &SimulationOptions::default());
+    let bus = &report.buses()[0];
+    let by_priority = bus.worst_queuing_delay_by_priority();
+    assert!(!by_priority.is_empty());
+    for (priority, delay) in &by_priority {
+        let worst = bus
+            .messages()
+            .iter()
+            .filter(|m| m.message().resolution_info().priority_bucket() == Some(priority.to_u32()))
+            .map(|m| *m.worst_queuing_delay())
+            .max()
+            .unwrap();
+        assert_eq!(*delay, worst);
+        assert!(*delay <= bus.worst_queuing_delay());
+    }
+    // highest priority first.
+    for pair in by_priority.windows(2) {
+        assert!(pair[0].0.to_u32() < pair[1].0.to_u32());
+    }
+    // every stream landed in a bucket.
+    for i in 0..4 {
+        let stream = report.message(&format!("secu_stream_temperatures_{i}")).unwrap();
+        let bucket = stream.message().resolution_info().priority_bucket().unwrap();
+        assert!(by_priority.iter().any(|(priority, _)| priority.to_u32() == bucket));
+    }
+}
+
+#[test]
+fn simulator_filter_hits() {
+    let network = sensor_network().build().unwrap();
+    let report = simulator::simulate(&network, &SimulationOptions::default());
+    let master = report
+        .filters()
+        .iter()
+        .find(|f| f.node().name() == "master")
+        .unwrap();
+    // 4 streams with 100 frames and 10 heartbeats.
+    assert!(master.hit_count() >= 410);
+    assert!(master.rejected().is_empty());
+}
+
+#[test]
+fn simulator_filter_hits_follow_the_hardware_filters() {
+    let network = sensor_network().build().unwrap();
+    let report = simulator::simulate(&network, &SimulationOptions::default());
+    for node_filters in resolve_filters_only(&network) {
+        let filter_report = report
+            .filters()
+            .iter()
+            .find(|f| f.node().name() == node_filters.node().name())
+            .unwrap();
+        // all frames on the bus that pass a filter, received or not.
+        let passed: usize = report.buses()[0]
+            .messages()
+            .iter()
+            .filter(|m| node_filters.filters().iter().any(|f| f.accepts_id(m.message().id())))
+            .map(|m| m.frame_count())
+            .sum();
+        assert_eq!(filter_report.hit_count(), passed);
+        let received: usize = report.buses()[0]
+            .messages()
+            .iter()
+            .filter(|m| node_filters.node().rx_messages().iter().any(|rx| rx.name() == m.message().name()))
+            .map(|m| m.frame_count())
+            .sum();
+        assert!(filter_report.hit_count() >= received);
+        assert!(filter_report.rejected().is_empty());
+    }
+}
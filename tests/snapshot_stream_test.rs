@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{NetworkBuilder, NodeBuilder},
+    errors::ConfigError,
+};
+
+fn network_builder() -> (NetworkBuilder, NodeBuilder) {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entries("cell_voltage_{}", 0..300, "d16<0..5>");
+    network_builder.create_node("logger").receive_all();
+    (network_builder, bms)
+}
+
+fn cells() -> Vec<String> {
+    (0..300).map(|i| format!("cell_voltage_{i}")).collect()
+}
+
+#[test]
+fn entries_are_sent_round_robin() {
+    let (network_builder, bms) = network_builder();
+    let cells = cells();
+    let cells: Vec<&str> = cells.iter().map(|cell| cell.as_str()).collect();
+    bms.create_snapshot_stream(&cells, Duration::from_millis(10));
+    let network = network_builder.build().unwrap();
+
+    let bms = network.nodes().iter().find(|node| node.name() == "bms").unwrap();
+    let snapshot_stream = bms.snapshot_stream().unwrap();
+    assert_eq!(snapshot_stream.entries().len(), 300);
+    assert_eq!(snapshot_stream.index_bits(), 9);
+    assert_eq!(snapshot_stream.value_bits(), 55);
+    assert_eq!(snapshot_stream.rotation_period(), Duration::from_secs(3));
+    assert_eq!(snapshot_stream.entry(42).unwrap().name(), "cell_voltage_42");
+    assert!(snapshot_stream.entry(300).is_none());
+
+    let message = snapshot_stream.message();
+    assert_eq!(message.name(), "bms_snapshot");
+    assert_eq!(message.dlc(), 8);
+    assert!(bms.tx_messages().iter().any(|m| m.name() == "bms_snapshot"));
+    let json = network.to_json();
+    assert!(json.contains("\"index_bits\": 9"), "{json}");
+
+    let logger = network.nodes().iter().find(|node| node.name() == "logger").unwrap();
+    assert!(logger.snapshot_stream().is_none());
+}
+
+#[test]
+fn snapshot_stream_is_extended_by_further_calls() {
+    let (network_builder, bms) = network_builder();
+    bms.create_snapshot_stream(&["cell_voltage_0", "cell_voltage_1"], Duration::from_millis(10));
+    let snapshot_stream = bms.create_snapshot_stream(&["cell_voltage_1", "cell_voltage_2"], Duration::from_millis(20));
+    snapshot_stream.add_receiver("logger");
+    let network = network_builder.build().unwrap();
+    let bms = network.nodes().iter().find(|node| node.name() == "bms").unwrap();
+    let snapshot_stream = bms.snapshot_stream().unwrap();
+    let entries: Vec<&str> = snapshot_stream.entries().iter().map(|oe| oe.name()).collect();
+    assert_eq!(entries, vec!["cell_voltage_0", "cell_voltage_1", "cell_voltage_2"]);
+    assert_eq!(*snapshot_stream.interval(), Duration::from_millis(20));
+    assert_eq!(snapshot_stream.index_bits(), 2);
+    // the builder is unchanged by build.
+    assert!(network_builder.build().is_ok());
+}
+
+#[test]
+fn unknown_entries_are_rejected() {
+    let (network_builder, bms) = network_builder();
+    bms.create_snapshot_stream(&["cell_temperature_0"], Duration::from_millis(10));
+    let Err(ConfigError::InvalidSnapshotStream(msg)) = network_builder.build() else {
+        panic!("expected an invalid snapshot stream");
+    };
+    assert!(msg.contains("cell_temperature_0"), "{msg}");
+}
+
+#[test]
+fn values_have_to_fit_next_to_the_index() {
+    let (network_builder, bms) = network_builder();
+    bms.create_object_entry("serial_number", "u64");
+    bms.create_snapshot_stream(&["cell_voltage_0", "serial_number"], Duration::from_millis(10));
+    let Err(ConfigError::InvalidSnapshotStream(msg)) = network_builder.build() else {
+        panic!("expected an invalid snapshot stream");
+    };
+    assert!(msg.contains("bms::serial_number"), "{msg}");
+}
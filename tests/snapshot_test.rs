@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use canzero_config::builder::NetworkBuilder;
+
+fn base_network() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let master = network_builder.create_node("master");
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entry("voltage", "u16");
+    let stream = bms.create_stream("state");
+    stream.add_entry("voltage");
+    master.receive_stream("bms", "state").unwrap();
+    network_builder
+}
+
+fn message_names(network_builder: &NetworkBuilder) -> Vec<String> {
+    network_builder
+        .0
+        .borrow()
+        .messages
+        .borrow()
+        .iter()
+        .map(|m| m.0.borrow().name.clone())
+        .collect()
+}
+
+#[test]
+fn restore_reverts_added_builders() {
+    let network_builder = base_network();
+    let before = message_names(&network_builder);
+    let bms = network_builder.create_node("bms");
+    let object_entry_count = bms.0.borrow().object_entries.len();
+    let snapshot = network_builder.snapshot();
+    assert!(!snapshot.is_empty());
+
+    bms.create_object_entry("current", "u16");
+    bms.create_stream("currents").add_entry("current");
+    network_builder.create_node("imu");
+    assert_ne!(message_names(&network_builder), before);
+
+    network_builder.restore(&snapshot);
+    assert_eq!(message_names(&network_builder), before);
+    let nodes: Vec<String> = network_builder
+        .0
+        .borrow()
+        .nodes
+        .borrow()
+        .iter()
+        .map(|n| n.0.borrow().name.clone())
+        .collect();
+    assert_eq!(nodes, vec!["master", "bms"]);
+    // the handle of bms stays valid, but forgot about the new entry and stream.
+    assert_eq!(bms.0.borrow().object_entries.len(), object_entry_count);
+    assert_eq!(bms.0.borrow().tx_streams.len(), 1);
+}
+
+#[test]
+fn restore_reverts_modified_builders() {
+    let network_builder = base_network();
+    let bms = network_builder.create_node("bms");
+    let stream = bms.create_stream("state");
+    let snapshot = network_builder.snapshot();
+    stream.set_interval(Duration::from_millis(1), Duration::from_millis(2));
+    network_builder.restore(&snapshot);
+    assert_eq!(stream.0.borrow().interval.1, Duration::from_millis(500));
+}
+
+#[test]
+fn what_if_builds_can_be_reverted() {
+    let network_builder = base_network();
+    let snapshot = network_builder.snapshot();
+    let reference = network_builder.clone().build().unwrap();
+    network_builder.restore(&snapshot);
+
+    // try a larger network, inspect it and throw it away.
+    let imu = network_builder.create_node("imu");
+    imu.create_object_entry("acceleration", "i16");
+    imu.create_stream("motion").add_entry("acceleration");
+    let what_if = network_builder.clone().build().unwrap();
+    assert!(what_if.nodes().iter().any(|n| n.name() == "imu"));
+    network_builder.restore(&snapshot);
+
+    // building twice from the same state yields the same network.
+    let rebuilt = network_builder.clone().build().unwrap();
+    assert_eq!(rebuilt.portable_hash(), reference.portable_hash());
+    assert!(!rebuilt.nodes().iter().any(|n| n.name() == "imu"));
+}
+
+#[test]
+#[should_panic(expected = "another network builder")]
+fn snapshots_belong_to_their_network() {
+    let snapshot = base_network().snapshot();
+    base_network().restore(&snapshot);
+}
@@ -0,0 +1,62 @@
+use canzero_config::builder::{MessagePriority, NetworkBuilder};
+
+#[test]
+fn folded_messages_are_dropped_in_software() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    for node in ["a", "b", "c"] {
+        network_builder.create_node(node);
+    }
+    network_builder.fold_receiver_sets(4);
+    for i in 0..4 {
+        let message = network_builder.create_message(&format!("all_{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        for rx in ["a", "b", "c"] {
+            message.add_receiver(rx);
+        }
+    }
+    let partial = network_builder.create_message("partial");
+    partial.set_any_std_id(MessagePriority::Normal);
+    partial.add_receiver("a");
+    partial.add_receiver("b");
+    let network = network_builder.build().unwrap();
+
+    let c = network.expect_node("c");
+    assert!(c.node().software_filters().iter().any(|m| m.name() == "partial"));
+    for node in network.nodes() {
+        for message in node.software_filters() {
+            assert!(
+                !node.rx_messages().iter().any(|m| m.name() == message.name()),
+                "{} drops its own rx message {}",
+                node.name(),
+                message.name()
+            );
+        }
+    }
+    assert!(network.to_json().contains("\"software_filters\""));
+}
+
+#[test]
+fn exact_receiver_sets_need_no_software_filters() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    for node in ["a", "b", "c"] {
+        network_builder.create_node(node);
+    }
+    for (i, receivers) in [["a", "b"], ["b", "c"], ["a", "c"]].iter().enumerate() {
+        let message = network_builder.create_message(&format!("msg_{i}"));
+        message.set_any_std_id(MessagePriority::Normal);
+        for rx in receivers {
+            message.add_receiver(rx);
+        }
+    }
+    let network = network_builder.build().unwrap();
+    for node in network.nodes() {
+        assert!(
+            node.software_filters().is_empty(),
+            "{} has software filters {:?}",
+            node.name(),
+            node.software_filters().iter().map(|m| m.name()).collect::<Vec<_>>()
+        );
+    }
+}
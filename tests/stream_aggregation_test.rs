@@ -0,0 +1,74 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{AggregationPolicy, NetworkRef, ObjectEntryRef},
+    errors::ConfigError,
+};
+
+fn network_builder(policy: Option<AggregationPolicy>) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    for sensor in ["sensor_a", "sensor_b"] {
+        let node = network_builder.create_node(sensor);
+        node.create_object_entry("temperature", "d16<0..100>");
+        node.create_stream("temperature_stream").add_entry("temperature");
+    }
+    let master = network_builder.create_node("master");
+    let temperature = master.create_object_entry("temperature", "d16<0..100>");
+    if let Some(policy) = policy {
+        temperature.aggregate(policy);
+    }
+    for sensor in ["sensor_a", "sensor_b"] {
+        let rx_stream = master.receive_stream(sensor, "temperature_stream").unwrap();
+        rx_stream.map("temperature", "temperature");
+    }
+    network_builder
+}
+
+fn master_temperature(network: &NetworkRef) -> ObjectEntryRef {
+    let master = network.nodes().iter().find(|node| node.name() == "master").unwrap();
+    master
+        .object_entries()
+        .iter()
+        .find(|oe| oe.name() == "temperature")
+        .cloned()
+        .unwrap()
+}
+
+#[test]
+fn conflicting_mappings_require_a_policy() {
+    let Err(ConfigError::ConflictingStreamMapping(msg)) = network_builder(None).build() else {
+        panic!("expected a conflicting stream mapping");
+    };
+    assert!(msg.contains("object entry master::temperature is mapped from"), "{msg}");
+    assert!(msg.contains("sensor_a::temperature_stream::temperature, sensor_b::temperature_stream::temperature"), "{msg}");
+}
+
+#[test]
+fn aggregated_sources_are_recorded_in_priority_order() {
+    let network = network_builder(Some(AggregationPolicy::Priority)).build().unwrap();
+    let temperature = master_temperature(&network);
+    let aggregation = temperature.aggregation().unwrap();
+    assert_eq!(aggregation.policy(), AggregationPolicy::Priority);
+    let sources: Vec<&str> = aggregation.sources().iter().map(|source| source.node()).collect();
+    assert_eq!(sources, vec!["sensor_a", "sensor_b"]);
+    assert!(aggregation.sources().iter().all(|source| source.stream() == "temperature_stream"
+        && source.object_entry() == "temperature"));
+    let json = network.to_json();
+    assert!(json.contains("\"policy\": \"priority\""), "{json}");
+}
+
+#[test]
+fn repeated_and_single_mappings_are_not_aggregated() {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let sensor = network_builder.create_node("sensor_a");
+    sensor.create_object_entry("temperature", "d16<0..100>");
+    sensor.create_stream("temperature_stream").add_entry("temperature");
+    let master = network_builder.create_node("master");
+    master.create_object_entry("temperature", "d16<0..100>");
+    let rx_stream = master.receive_stream("sensor_a", "temperature_stream").unwrap();
+    rx_stream.map("temperature", "temperature");
+    rx_stream.map("temperature", "temperature");
+    let network = network_builder.build().unwrap();
+    assert!(master_temperature(&network).aggregation().is_none());
+}
@@ -0,0 +1,56 @@
+use canzero_config::builder::NetworkBuilder;
+
+#[test]
+fn stream_entry_builder_handles() {
+    let network_builder = NetworkBuilder::new();
+    let sensor = network_builder.create_node("sensor");
+    let pressure = sensor.create_object_entry("pressure", "u16");
+    let stream = sensor.create_stream("state");
+    stream.add_entry_builder(&pressure);
+    // mapping the same entry twice is a noop.
+    stream.add_entry_builder(&pressure);
+
+    let master = network_builder.create_node("master");
+    let sensor_pressure = master.create_object_entry("sensor_pressure", "u16");
+    master
+        .receive_stream("sensor", "state").unwrap()
+        .map_builder(&pressure, &sensor_pressure);
+
+    let network = network_builder.build().unwrap();
+    let sensor = network.nodes().iter().find(|n| n.name() == "sensor").unwrap();
+    let tx_stream = &sensor.tx_streams()[0];
+    assert_eq!(tx_stream.mapping().len(), 1);
+    let master = network.nodes().iter().find(|n| n.name() == "master").unwrap();
+    let rx_stream = &master.rx_streams()[0];
+    let mapped: Vec<&str> = rx_stream
+        .mapping()
+        .iter()
+        .flatten()
+        .map(|oe| oe.name())
+        .collect();
+    assert_eq!(mapped, vec!["sensor_pressure"]);
+}
+
+#[test]
+#[should_panic(expected = "does not belong to node sensor")]
+fn stream_entry_builder_of_other_node() {
+    let network_builder = NetworkBuilder::new();
+    let sensor = network_builder.create_node("sensor");
+    let master = network_builder.create_node("master");
+    let foreign = master.create_object_entry("pressure", "u16");
+    sensor.create_object_entry("pressure", "u16");
+    sensor.create_stream("state").add_entry_builder(&foreign);
+}
+
+#[test]
+#[should_panic(expected = "does not belong to node master")]
+fn stream_map_builder_of_other_node() {
+    let network_builder = NetworkBuilder::new();
+    let sensor = network_builder.create_node("sensor");
+    let pressure = sensor.create_object_entry("pressure", "u16");
+    sensor.create_stream("state").add_entry_builder(&pressure);
+    let master = network_builder.create_node("master");
+    master
+        .receive_stream("sensor", "state").unwrap()
+        .map_builder(&pressure, &pressure);
+}
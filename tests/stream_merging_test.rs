@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{stream::Stream, NetworkRef},
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let bms = network_builder.create_node("bms");
+    let master = network_builder.create_node("master");
+    let pdu = network_builder.create_node("pdu");
+    for (name, ty) in [("voltage", "u16"), ("current", "u16"), ("temperature", "u8"), ("soc", "u32")] {
+        bms.create_object_entry(name, ty);
+        master.create_object_entry(&format!("bms_{name}"), ty);
+    }
+    for (stream, entries, interval_ms) in [
+        ("voltage", &["voltage"][..], 100),
+        ("current", &["current"][..], 150),
+        ("temperature", &["temperature"][..], 500),
+        ("soc", &["soc"][..], 100),
+    ] {
+        let stream_builder = bms.create_stream(stream);
+        stream_builder.set_interval(Duration::from_millis(10), Duration::from_millis(interval_ms));
+        for entry in entries {
+            stream_builder.add_entry(entry);
+        }
+        let rx_stream = master.receive_stream("bms", stream).unwrap();
+        for entry in entries {
+            rx_stream.map(entry, &format!("bms_{entry}"));
+        }
+    }
+    // received by another node, therefore not merged with voltage.
+    pdu.receive_stream("bms", "soc").unwrap();
+    network_builder
+}
+
+fn stream<'a>(network: &'a NetworkRef, node: &str, name: &str, rx: bool) -> Option<&'a Stream> {
+    let node = network.nodes().iter().find(|n| n.name() == node).unwrap();
+    let streams = if rx { node.rx_streams() } else { node.tx_streams() };
+    streams.iter().find(|s| s.name() == name).map(|s| s as &Stream)
+}
+
+#[test]
+fn streams_with_same_receivers_are_merged() {
+    let network_builder = network_builder();
+    network_builder.enable_stream_merging(8);
+    let network = network_builder.build().unwrap();
+
+    let voltage = stream(&network, "bms", "voltage", false).unwrap();
+    assert_eq!(voltage.merged_streams(), &vec!["current".to_owned()]);
+    assert_eq!(*voltage.max_interval(), Duration::from_millis(100));
+    let entries: Vec<&str> = voltage.mapping().iter().flatten().map(|oe| oe.name()).collect();
+    assert_eq!(entries, vec!["voltage", "current"]);
+    assert!(stream(&network, "bms", "current", false).is_none());
+    assert!(!network.messages().iter().any(|m| m.name() == "bms_stream_current"));
+    // the interval of temperature isn't compatible and soc has other receivers.
+    assert!(stream(&network, "bms", "temperature", false).is_some());
+    assert!(stream(&network, "bms", "soc", false).is_some());
+
+    let rx_voltage = stream(&network, "master", "voltage", true).unwrap();
+    let mapped: Vec<Option<&str>> = rx_voltage.mapping().iter().map(|oe| oe.as_ref().map(|oe| oe.name())).collect();
+    assert_eq!(mapped, vec![Some("bms_voltage"), Some("bms_current")]);
+    assert!(stream(&network, "master", "current", true).is_none());
+}
+
+#[test]
+fn merging_respects_the_threshold() {
+    let network_builder = network_builder();
+    network_builder.enable_stream_merging(3);
+    let network = network_builder.build().unwrap();
+    assert!(stream(&network, "bms", "current", false).is_some());
+    assert!(stream(&network, "bms", "voltage", false).unwrap().merged_streams().is_empty());
+}
+
+#[test]
+fn merging_is_disabled_by_default() {
+    let network_builder = network_builder();
+    let network = network_builder.build().unwrap();
+    assert!(stream(&network, "bms", "current", false).is_some());
+    // build doesn't change the builders.
+    network_builder.enable_stream_merging(8);
+    let merged = network_builder.build().unwrap();
+    assert!(stream(&merged, "bms", "current", false).is_none());
+    let rebuilt = network_builder.build().unwrap();
+    assert_eq!(stream(&rebuilt, "bms", "voltage", false).unwrap().merged_streams().len(), 1);
+}
+
+#[test]
+fn merged_streams_are_exported() {
+    let network_builder = network_builder();
+    network_builder.enable_stream_merging(8);
+    let json = network_builder.build().unwrap().to_json();
+    assert!(json.contains("\"merged_streams\": [\n          \"current\"\n        ]"));
+}
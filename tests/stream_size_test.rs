@@ -0,0 +1,83 @@
+use canzero_config::{
+    builder::NetworkBuilder,
+    diagnostics::{DiagnosticKind, Severity},
+    errors::ConfigError,
+};
+
+fn bms_network(cells: usize) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let bms = network_builder.create_node("bms");
+    let master = network_builder.create_node("master");
+    let stream = bms.create_stream("cells");
+    for i in 0..cells {
+        let name = format!("cell_{i}");
+        bms.create_object_entry(&name, "u16");
+        master.create_object_entry(&name, "u16");
+        stream.add_entry(&name);
+    }
+    let rx_stream = master.receive_stream("bms", "cells").unwrap();
+    for i in 0..cells {
+        let name = format!("cell_{i}");
+        rx_stream.map(&name, &name);
+    }
+    network_builder
+}
+
+#[test]
+fn overflowing_streams_report_the_missing_bytes() {
+    match bms_network(6).build() {
+        Err(ConfigError::DeniedDiagnostics(msg)) => {
+            assert!(msg.contains("stream bms::cells requires 12 bytes"));
+            assert!(msg.contains("4 bytes more"));
+        }
+        other => panic!("expected DeniedDiagnostics, got {other:?}"),
+    }
+    assert!(bms_network(4).build().is_ok());
+}
+
+#[test]
+fn stream_headroom_is_reserved() {
+    let network_builder = bms_network(4);
+    network_builder.set_stream_headroom(8);
+    match network_builder.build() {
+        Err(ConfigError::DeniedDiagnostics(msg)) => {
+            assert!(msg.contains("requires 9 bytes (8 bits of headroom)"));
+            assert!(msg.contains("1 bytes more"));
+        }
+        other => panic!("expected DeniedDiagnostics, got {other:?}"),
+    }
+
+    // the headroom can be relaxed to a warning, the frame itself still fits.
+    let network_builder = bms_network(4);
+    network_builder.set_stream_headroom(8);
+    network_builder.set_diagnostic_severity(DiagnosticKind::StreamOverflow, Severity::Warn);
+    let network = network_builder.build().unwrap();
+    assert!(network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::StreamOverflow && d.severity() == Severity::Warn));
+}
+
+#[test]
+fn streams_without_receivers_are_reported() {
+    let network_builder = NetworkBuilder::new();
+    let bms = network_builder.create_node("bms");
+    bms.create_object_entry("voltage", "u16");
+    bms.create_stream("pack").add_entry("voltage");
+    let network = network_builder.build().unwrap();
+    let diagnostics: Vec<_> = network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::StreamWithoutReceivers)
+        .collect();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].message(), "stream bms::pack is not received by any node");
+
+    assert!(!bms_network(2)
+        .build()
+        .unwrap()
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::StreamWithoutReceivers));
+}
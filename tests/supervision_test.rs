@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{supervision::DEFAULT_HEARTBEAT_TIMEOUT, MessagePriority, NetworkBuilder},
+    config::{NetworkRef, Supervision},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    for node in ["master", "bms", "pdu", "logger"] {
+        network_builder.create_node(node);
+    }
+    for (name, tx, receivers) in [
+        ("bms_state", "bms", &["master"][..]),
+        ("pdu_state", "pdu", &["master", "bms"][..]),
+        ("master_state", "master", &["bms", "pdu"][..]),
+    ] {
+        let message = network_builder.create_message_with_interval(name, Duration::from_millis(10));
+        message.set_any_std_id(MessagePriority::Normal);
+        message.add_transmitter(tx);
+        for rx in receivers {
+            message.add_receiver(rx);
+        }
+        message.make_type_format().add_type("u8", "value");
+    }
+    network_builder
+}
+
+fn supervisions(network: &NetworkRef, node: &str) -> Vec<(String, Duration)> {
+    network
+        .nodes()
+        .iter()
+        .find(|n| n.name() == node)
+        .unwrap()
+        .supervisions()
+        .iter()
+        .map(|s: &Supervision| (s.node().to_owned(), *s.timeout()))
+        .collect()
+}
+
+#[test]
+fn transmitters_of_received_messages_are_supervised() {
+    let network = network_builder().build().unwrap();
+    assert_eq!(
+        supervisions(&network, "master"),
+        vec![
+            ("bms".to_owned(), DEFAULT_HEARTBEAT_TIMEOUT),
+            ("pdu".to_owned(), DEFAULT_HEARTBEAT_TIMEOUT)
+        ]
+    );
+    assert_eq!(
+        supervisions(&network, "bms"),
+        vec![
+            ("master".to_owned(), DEFAULT_HEARTBEAT_TIMEOUT),
+            ("pdu".to_owned(), DEFAULT_HEARTBEAT_TIMEOUT)
+        ]
+    );
+    assert_eq!(
+        supervisions(&network, "pdu"),
+        vec![("master".to_owned(), DEFAULT_HEARTBEAT_TIMEOUT)]
+    );
+    // heartbeats and get/set requests are not a dependency.
+    assert!(supervisions(&network, "logger").is_empty());
+}
+
+#[test]
+fn explicit_supervisions_override_and_extend() {
+    let network_builder = network_builder();
+    network_builder.set_heartbeat_timeout(Duration::from_millis(500));
+    let master = network_builder.create_node("master");
+    master.supervise("bms", Some(Duration::from_millis(50)));
+    master.supervise("logger", None);
+    let network = network_builder.build().unwrap();
+    assert_eq!(
+        supervisions(&network, "master"),
+        vec![
+            ("bms".to_owned(), Duration::from_millis(50)),
+            ("pdu".to_owned(), Duration::from_millis(500)),
+            ("logger".to_owned(), Duration::from_millis(500))
+        ]
+    );
+    assert_eq!(
+        supervisions(&network, "pdu"),
+        vec![("master".to_owned(), Duration::from_millis(500))]
+    );
+}
+
+#[test]
+fn supervision_of_unknown_node_is_rejected() {
+    let network_builder = network_builder();
+    network_builder.create_node("master").supervise("inverter", None);
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidSupervision(_))
+    ));
+
+    let network_builder = self::network_builder();
+    network_builder.create_node("master").supervise("master", None);
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidSupervision(_))
+    ));
+}
+
+#[test]
+fn supervision_matrix_is_exported() {
+    let network = network_builder().build().unwrap();
+    let markdown = network.generate_supervision_matrix();
+    let lines: Vec<&str> = markdown.lines().collect();
+    assert_eq!(lines[0], "| supervisor | master | bms | pdu | logger |");
+    assert_eq!(lines[2], "| master | - | 300ms | 300ms |  |");
+    assert_eq!(lines[4], "| pdu | 300ms |  | - |  |");
+
+    let json = network.to_json();
+    assert!(json.contains("\"supervisions\""));
+    assert!(json.contains("\"timeout_ms\": 300"));
+}
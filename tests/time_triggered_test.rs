@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    errors::ConfigError,
+};
+
+fn brake_network(basic_cycle: Duration) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", Some(1_000_000));
+    network_builder.create_bus("can1", Some(1_000_000));
+    network_builder.create_node("brake");
+    network_builder.create_node("master");
+    let schedule = network_builder.create_time_triggered_schedule("can1", basic_cycle, 4);
+    for (name, interval) in [
+        ("brake_command", 1),
+        ("brake_feedback", 1),
+        ("brake_temperature", 4),
+        ("brake_wear", 10),
+    ] {
+        let message =
+            network_builder.create_message_with_interval(name, Duration::from_millis(interval));
+        message.set_any_std_id(MessagePriority::High);
+        message.make_type_format().add_type("u32", "value");
+        message.add_transmitter("brake");
+        message.add_receiver("master");
+        schedule.add_message(&message);
+    }
+    network_builder
+}
+
+#[test]
+fn time_triggered_messages_get_exclusive_windows() {
+    let network = brake_network(Duration::from_millis(1)).build().unwrap();
+    let schedules = network.time_triggered_schedules();
+    assert_eq!(schedules.len(), 1);
+    let schedule = &schedules[0];
+    assert_eq!(schedule.bus().name(), "can1");
+    assert_eq!(schedule.matrix_cycle(), Duration::from_millis(4));
+    assert_eq!(schedule.windows().len(), 4);
+
+    for window in schedule.windows() {
+        assert_eq!(window.message().bus().name(), "can1");
+        assert!(*window.offset() + *window.length() <= *schedule.basic_cycle());
+        let expected_repeat = match window.message().name() {
+            "brake_command" | "brake_feedback" => 1,
+            _ => 4,
+        };
+        assert_eq!(window.repeat(), expected_repeat);
+    }
+    // windows never overlap in any basic cycle.
+    for cycle in 0..schedule.cycle_count() {
+        let mut active: Vec<_> = schedule
+            .windows()
+            .iter()
+            .filter(|w| cycle >= w.first_cycle() && (cycle - w.first_cycle()) % w.repeat() == 0)
+            .collect();
+        active.sort_by_key(|w| *w.offset());
+        for pair in active.windows(2) {
+            assert!(*pair[0].offset() + *pair[0].length() <= *pair[1].offset());
+        }
+    }
+    // infrequent messages share a window in different basic cycles.
+    let temperature = schedule.windows().iter().find(|w| w.message().name() == "brake_temperature").unwrap();
+    let wear = schedule.windows().iter().find(|w| w.message().name() == "brake_wear").unwrap();
+    assert_eq!(temperature.offset(), wear.offset());
+    assert_ne!(temperature.first_cycle(), wear.first_cycle());
+}
+
+#[test]
+fn window_table_lists_the_windows_of_a_node() {
+    let network = brake_network(Duration::from_millis(1)).build().unwrap();
+    let table = network.time_window_table_csv("master");
+    let mut lines = table.lines();
+    assert!(lines.next().unwrap().starts_with("bus,basic_cycle_ns,cycle_count,message"));
+    let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+    assert_eq!(rows.len(), 4);
+    assert!(rows.iter().all(|row| row[0] == "can1" && row[6] == "rx"));
+    let brake_table = network.time_window_table_csv("brake");
+    assert!(brake_table.lines().skip(1).all(|line| line.split(',').nth(6) == Some("tx")));
+}
+
+#[test]
+fn windows_have_to_fit_into_the_basic_cycle() {
+    match brake_network(Duration::from_micros(50)).build() {
+        Err(ConfigError::InvalidTimeTriggeredSchedule(msg)) => assert!(msg.contains("can1")),
+        other => panic!("expected InvalidTimeTriggeredSchedule, got {:?}", other.err()),
+    }
+}
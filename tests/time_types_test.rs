@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use canzero_config::{
+    builder::NetworkBuilder,
+    config::{layout::parse_layout_lock, NetworkRef, SignalType, TimeEpoch, TimeKind, TimeUnit, Type},
+    diagnostics::DiagnosticKind,
+};
+
+mod common;
+
+fn network(rx_type: &str) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    network_builder.set_time_epoch(TimeEpoch::Network);
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("uptime", "t32<ms>");
+    becu.create_object_entry("brake_time", "dt16<us>");
+    let stream = becu.create_stream("timing");
+    stream.add_entry("uptime");
+    stream.add_entry("brake_time");
+    let secu = network_builder.create_node("secu");
+    secu.create_object_entry("becu_uptime", rx_type);
+    secu.create_object_entry("becu_brake_time", "dt16<us>");
+    let rx_stream = secu.receive_stream("becu", "timing").unwrap();
+    rx_stream.map("uptime", "becu_uptime");
+    rx_stream.map("brake_time", "becu_brake_time");
+    network_builder.build().unwrap()
+}
+
+#[test]
+fn time_types_resolve() {
+    let types = NetworkBuilder::resolve_types(&[], &["t32<ms>", "dt16<us>", "t8<s>[3]"]).unwrap();
+    assert_eq!(
+        &types[0] as &Type,
+        &Type::Primitive(SignalType::Time {
+            size: 32,
+            unit: TimeUnit::Millis,
+            kind: TimeKind::Timestamp
+        })
+    );
+    assert_eq!(types[1].name(), "dt16<us>");
+    assert!(matches!(&types[2] as &Type, Type::Array { len: 3, ty } if ty.name() == "t8<s>"));
+    assert!(NetworkBuilder::resolve_types(&[], &["t32<ns>"]).is_err());
+    assert!(NetworkBuilder::resolve_types(&[], &["t0<ms>"]).is_err());
+}
+
+#[test]
+fn timestamps_roll_over_and_durations_saturate() {
+    assert_eq!(TimeUnit::Micros.ticks(3), Duration::from_micros(3));
+    let timestamp = SignalType::Time {
+        size: 16,
+        unit: TimeUnit::Micros,
+        kind: TimeKind::Timestamp,
+    };
+    assert_eq!(timestamp.rollover(), Some(Duration::from_micros(65536)));
+    assert_eq!(timestamp.scale(), 1e-6);
+    let duration = SignalType::Time {
+        size: 16,
+        unit: TimeUnit::Micros,
+        kind: TimeKind::Duration,
+    };
+    assert_eq!(duration.rollover(), None);
+}
+
+#[test]
+fn time_semantics_are_recorded() {
+    let network = network("t32<ms>");
+    assert_eq!(network.time_epoch(), TimeEpoch::Network);
+    let json = network.to_json();
+    assert!(json.contains("\"time_epoch\": \"network\""));
+    assert!(json.contains("\"kind\": \"timestamp\""));
+    assert!(json.contains("\"unit\": \"ms\""));
+    assert!(!network.diagnostics().iter().any(|d| d.kind() == DiagnosticKind::UnitMismatch));
+    let layouts = parse_layout_lock(&network.layout_lock()).unwrap();
+    assert!(network.check_layouts(&layouts).is_empty());
+    assert!(network.generate_dbc().contains("(0.001,0) [0|4294967.295] \"s\""));
+}
+
+#[test]
+#[should_panic(expected = "Stream mapping types don't match")]
+fn mismatched_time_units_are_rejected() {
+    network("t32<us>");
+}
+
+#[test]
+fn cpp_uses_chrono_durations() {
+    let header = network("t32<ms>").generate_cpp();
+    assert!(header.contains("std::chrono::duration<uint32_t, std::milli>"));
+    assert!(header.contains("std::chrono::duration<uint16_t, std::micro>"));
+    let Ok(compiler) = std::process::Command::new("g++").arg("--version").output() else {
+        return;
+    };
+    if !compiler.status.success() {
+        return;
+    }
+    let dir = std::env::temp_dir().join("canzero_time_types_cpp_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("canzero.hpp"), header).unwrap();
+    let source = dir.join("main.cpp");
+    std::fs::write(&source, "#include \"canzero.hpp\"\nint main() { return 0; }\n").unwrap();
+    for standard in ["-std=c++17", "-std=c++20"] {
+        let status = std::process::Command::new("g++")
+            .args([standard, "-fsyntax-only", "-Wall", "-Wextra", "-Werror"])
+            .arg(&source)
+            .status()
+            .unwrap();
+        assert!(status.success(), "{standard}");
+    }
+}
+
+#[test]
+fn python_wraps_timestamps_and_saturates_durations() {
+    if !common::python3_available("python_wraps_timestamps_and_saturates_durations") {
+        return;
+    }
+    let dir = std::env::temp_dir().join("canzero_time_types_python_test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("canzero.py"), network("t32<ms>").generate_python()).unwrap();
+    let script = r#"
+import canzero
+msg = canzero.becu_stream_timing()
+msg.uptime = 2 ** 32 * 0.001 + 1.5
+msg.brake_time = 1.0
+decoded = canzero.decode(msg.ID, msg.IDE, msg.encode())
+assert abs(decoded.uptime - 1.5) < 1e-9, decoded.uptime
+assert abs(decoded.brake_time - 0.065535) < 1e-9, decoded.brake_time
+"#;
+    let status = std::process::Command::new("python3")
+        .args(["-c", script])
+        .current_dir(&dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,62 @@
+use canzero_config::{
+    builder::{bus::BusBuilder, NetworkBuilder},
+    errors::ConfigError,
+};
+
+fn network_builder() -> (NetworkBuilder, BusBuilder) {
+    let network_builder = NetworkBuilder::new();
+    let can0 = network_builder.create_bus("can0", None);
+    can0.add_trunk_segment("front harness", 2.5);
+    can0.add_trunk_segment("rear harness", 1.5);
+    can0.add_stub_segment("bms connector", 0.2);
+    network_builder.create_bus("can1", None);
+    for name in ["bms", "pdu", "master"] {
+        network_builder.create_node(name).assign_bus("can0");
+    }
+    network_builder.create_node("motor").assign_bus("can1");
+    (network_builder, can0)
+}
+
+#[test]
+fn node_count_is_validated() {
+    let (network_builder, can0) = network_builder();
+    can0.set_max_nodes(3);
+    assert!(network_builder.build().is_ok());
+    can0.set_max_nodes(2);
+    let Err(ConfigError::InvalidTopology(msg)) = network_builder.build() else {
+        panic!("expected an invalid topology");
+    };
+    assert!(msg.contains("can0"), "{msg}");
+}
+
+#[test]
+fn stub_length_is_validated() {
+    let (network_builder, can0) = network_builder();
+    can0.set_max_stub_length(0.3);
+    assert!(network_builder.build().is_ok());
+    can0.add_stub_segment("pdu connector", 0.5);
+    let Err(ConfigError::InvalidTopology(msg)) = network_builder.build() else {
+        panic!("expected an invalid topology");
+    };
+    assert!(msg.contains("pdu connector"), "{msg}");
+}
+
+#[test]
+fn topology_is_part_of_the_config() {
+    let (network_builder, can0) = network_builder();
+    can0.set_max_nodes(8);
+    let network = network_builder.build().unwrap();
+    let can0 = network.buses().iter().find(|bus| bus.name() == "can0").unwrap();
+    assert_eq!(can0.topology().max_nodes(), Some(8));
+    assert_eq!(can0.topology().segments().len(), 3);
+    assert_eq!(can0.topology().trunk_length(), 4.0);
+    assert!((can0.topology().cable_length() - 4.2).abs() < 1e-9);
+    let nodes: Vec<&str> = network.bus_nodes(can0).iter().map(|node| node.name()).collect();
+    assert_eq!(nodes, vec!["bms", "pdu", "master"]);
+
+    let report = network.generate_topology_report();
+    assert!(report.contains("| can0 | 1000000 | 3 | 8 | 4m | - |"), "{report}");
+    assert!(report.contains("| bms connector | stub | 0.2m |"), "{report}");
+    assert!(!report.contains("### can1"));
+    assert!(network.to_json().contains("\"description\": \"front harness\""));
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use canzero_config::{
+    builder::{NetworkBuilder, TypeResolver},
+    config::{SignalType, Type},
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("state");
+    state.add_entry("Idle", None).unwrap();
+    state.add_entry("Running", None).unwrap();
+    let position = network_builder.define_struct("position");
+    position.add_attribute("x", "d16<0..10>").unwrap();
+    position.add_attribute("state", "state").unwrap();
+    network_builder
+}
+
+#[test]
+fn type_strings_resolve_against_defined_types() {
+    let network_builder = network_builder();
+    let ty = network_builder.resolve_type_str("u12").unwrap();
+    assert!(matches!(&ty as &Type, Type::Primitive(SignalType::UnsignedInt { size: 12 })));
+    let ty = network_builder.resolve_type_str("position[4]").unwrap();
+    let Type::Array { len, ty } = &ty as &Type else {
+        panic!("expected an array, got {ty:?}");
+    };
+    assert_eq!(*len, 4);
+    assert_eq!(ty.name(), "position");
+    assert!(matches!(
+        network_builder.resolve_type_str("velocity"),
+        Err(ConfigError::InvalidType(_))
+    ));
+    assert!(matches!(
+        network_builder.resolve_type_str("d8<1..0>"),
+        Err(ConfigError::InvalidRange(_))
+    ));
+}
+
+#[test]
+fn resolved_types_are_cached_by_type_string() {
+    let network_builder = network_builder();
+    let state = network_builder.resolve_type_str("state").unwrap();
+    let resolver = TypeResolver::new(std::slice::from_ref(&state));
+    let types = resolver.resolve_all(&["i64", "state", "i64", "state[2]", "state[2]"]).unwrap();
+    assert!(Arc::ptr_eq(&types[0], &types[2]));
+    assert!(Arc::ptr_eq(&types[3], &types[4]));
+    assert!(Arc::ptr_eq(&types[1], &state));
+    assert!(matches!(resolver.resolve_all(&["u8", "u65"]), Err(ConfigError::InvalidType(_))));
+    let batch = NetworkBuilder::resolve_types(&[state], &["u1", "d4<-1..1>"]).unwrap();
+    assert_eq!(batch.iter().map(|ty| ty.size()).collect::<Vec<_>>(), vec![1, 4]);
+}
@@ -0,0 +1,89 @@
+use canzero_config::{
+    builder::{network_builder::UnusedTypePolicy, NetworkBuilder},
+    config::NetworkRef,
+    diagnostics::DiagnosticKind,
+};
+
+fn network(policy: Option<UnusedTypePolicy>) -> NetworkRef {
+    let network_builder = NetworkBuilder::new();
+    let state = network_builder.define_enum("pump_state");
+    state.add_entry("Off", None).unwrap();
+    state.add_entry("On", None).unwrap();
+    let pump = network_builder.define_struct("pump");
+    pump.add_attribute("state", "pump_state").unwrap();
+    pump.add_attribute("pressure", "u16").unwrap();
+    // historical types, that nothing refers to anymore.
+    let legacy_mode = network_builder.define_enum("legacy_mode");
+    legacy_mode.add_entry("A", None).unwrap();
+    let legacy = network_builder.define_struct("legacy");
+    legacy.add_attribute("mode", "legacy_mode").unwrap();
+    let gear = network_builder.define_enum("gear");
+    gear.add_entry("Park", None).unwrap();
+
+    let cooling = network_builder.create_node("cooling");
+    let master = network_builder.create_node("master");
+    cooling.create_object_entry("pump", "pump");
+    let stream = cooling.create_stream("state");
+    stream.add_entry("pump");
+    master.receive_stream("cooling", "state").unwrap();
+    master.create_object_entry("gear", "gear");
+    if let Some(policy) = policy {
+        network_builder.set_unused_type_policy(policy);
+    }
+    network_builder.build().unwrap()
+}
+
+fn type_names(network: &NetworkRef) -> Vec<String> {
+    network.types().iter().map(|ty| ty.name()).collect()
+}
+
+#[test]
+fn usage_is_transitive() {
+    let network = network(None);
+    let usage = network.type_usage();
+    let of = |name: &str| usage.iter().find(|u| u.ty().name() == name).unwrap();
+    assert_eq!(of("pump").object_entries(), &vec!["cooling::pump".to_owned()]);
+    // the enum is only used as an attribute of the struct.
+    assert_eq!(of("pump_state").messages(), of("pump").messages());
+    assert!(!of("pump_state").messages().is_empty());
+    assert!(of("gear").is_used());
+    assert!(of("gear").messages().is_empty());
+    assert!(!of("legacy").is_used());
+    assert!(!of("legacy_mode").is_used());
+}
+
+#[test]
+fn unused_types_are_reported() {
+    let network = network(None);
+    let mut unused: Vec<&str> = network
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::UnusedType)
+        .map(|d| d.message())
+        .collect();
+    unused.sort();
+    assert_eq!(
+        unused,
+        vec![
+            "type legacy is not used by any message or object entry",
+            "type legacy_mode is not used by any message or object entry",
+        ]
+    );
+    assert!(type_names(&network).contains(&"legacy".to_owned()));
+}
+
+#[test]
+fn unused_types_can_be_pruned() {
+    let network = network(Some(UnusedTypePolicy::Prune));
+    let names = type_names(&network);
+    assert!(!names.contains(&"legacy".to_owned()));
+    assert!(!names.contains(&"legacy_mode".to_owned()));
+    assert!(names.contains(&"pump_state".to_owned()));
+    // types of the network builder itself are never pruned.
+    assert!(names.contains(&"command_resp_erno".to_owned()));
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.kind() == DiagnosticKind::UnusedType));
+    assert!(!network.generate_cpp().contains("legacy"));
+}
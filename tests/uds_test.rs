@@ -0,0 +1,82 @@
+use canzero_config::{
+    builder::{MessagePriority, NetworkBuilder},
+    config::MessageId,
+    errors::ConfigError,
+};
+
+fn network_builder() -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    for (i, tx) in ["becu", "master"].iter().enumerate() {
+        for j in 0..4 {
+            let message = network_builder.create_message(&format!("msg_{i}_{j}"));
+            message.set_any_std_id(MessagePriority::Normal);
+            message.add_transmitter(tx);
+            message.add_receiver(if *tx == "becu" { "master" } else { "becu" });
+        }
+    }
+    network_builder
+}
+
+#[test]
+fn uds_messages_per_node() {
+    let network_builder = network_builder();
+    network_builder.enable_uds();
+    let network = network_builder.build().unwrap();
+    for node in network.nodes() {
+        let i = node.id() as u32;
+        let req = node
+            .rx_messages()
+            .iter()
+            .find(|m| m.name() == format!("{}_uds_req", node.name()))
+            .unwrap();
+        assert_eq!(req.id(), &MessageId::StandardId(0x7E0 + i));
+        assert_eq!(req.dlc(), 8);
+        let resp = node
+            .tx_messages()
+            .iter()
+            .find(|m| m.name() == format!("{}_uds_resp", node.name()))
+            .unwrap();
+        assert_eq!(resp.id(), &MessageId::StandardId(0x7E8 + i));
+        assert_eq!(resp.dlc(), 8);
+    }
+    // the tester is not part of the network.
+    assert!(!network
+        .diagnostics()
+        .iter()
+        .any(|d| d.message().contains("_uds_")));
+}
+
+#[test]
+fn uds_too_many_nodes() {
+    let network_builder = network_builder();
+    for i in 0..8 {
+        network_builder.create_node(&format!("node{i}"));
+    }
+    network_builder.enable_uds();
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::InvalidUdsConfiguration(_))
+    ));
+}
+
+#[test]
+fn uds_reserved_id_range() {
+    let network = network_builder().build().unwrap();
+    let lowest = network
+        .messages()
+        .iter()
+        .filter(|m| m.name().starts_with("msg_"))
+        .map(|m| m.id().as_u32())
+        .min()
+        .unwrap();
+
+    let network_builder = network_builder();
+    network_builder.reserve_std_id_range(0..lowest + 4);
+    let network = network_builder.build().unwrap();
+    for message in network.messages() {
+        if message.name().starts_with("msg_") {
+            assert!(message.id().as_u32() >= lowest + 4, "{}", message.id());
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use canzero_config::{builder::NetworkBuilder, diagnostics::DiagnosticKind};
+
+fn network_builder(rx_unit: &str) -> NetworkBuilder {
+    let network_builder = NetworkBuilder::new();
+    network_builder.create_bus("can0", None);
+    let becu = network_builder.create_node("becu");
+    let pressure = becu.create_object_entry("pressure", "d16<0..200>");
+    pressure.add_unit("bar");
+    let stream = becu.create_stream("state");
+    stream.add_entry_builder(&pressure);
+    let master = network_builder.create_node("master");
+    let brake_pressure = master.create_object_entry("brake_pressure", "d16<0..200>");
+    brake_pressure.add_unit(rx_unit);
+    master
+        .receive_stream("becu", "state").unwrap()
+        .map_builder(&pressure, &brake_pressure);
+    network_builder
+}
+
+fn unit_mismatches(network_builder: NetworkBuilder) -> Vec<String> {
+    network_builder
+        .build()
+        .unwrap()
+        .diagnostics()
+        .iter()
+        .filter(|d| d.kind() == DiagnosticKind::UnitMismatch)
+        .map(|d| d.message().to_owned())
+        .collect()
+}
+
+#[test]
+fn units_compatible() {
+    assert!(unit_mismatches(network_builder("bar")).is_empty());
+}
+
+#[test]
+fn units_mismatch() {
+    let mismatches = unit_mismatches(network_builder("°C"));
+    assert_eq!(mismatches.len(), 1);
+    assert!(mismatches[0].contains("pressure [bar]"));
+    assert!(mismatches[0].contains("master::brake_pressure [°C]"));
+}
+
+#[test]
+fn units_exported_to_dbc() {
+    let network = network_builder("bar").build().unwrap();
+    let dbc = network.generate_dbc();
+    let parsed = can_dbc::DBC::from_slice(dbc.as_bytes()).unwrap();
+    let message = parsed
+        .messages()
+        .iter()
+        .find(|m| m.message_name() == "becu_stream_state")
+        .unwrap();
+    assert_eq!(message.signals().len(), 1);
+    assert_eq!(message.signals()[0].unit(), "bar");
+}
@@ -0,0 +1,74 @@
+use canzero_config::{
+    builder::{
+        stream_builder::{ReceiveStreamBuilder, UnmappedPolicy},
+        NetworkBuilder,
+    },
+    errors::ConfigError,
+};
+
+fn network_builder() -> (NetworkBuilder, ReceiveStreamBuilder) {
+    let network_builder = NetworkBuilder::new();
+    let becu = network_builder.create_node("becu");
+    becu.create_object_entry("pressure", "u16");
+    becu.create_object_entry("temperature", "u16");
+    becu.create_object_entry("debug", "u8");
+    let stream = becu.create_stream("state");
+    stream.add_entry("pressure");
+    stream.add_entry("temperature");
+    stream.add_entry("debug");
+    let secu = network_builder.create_node("secu");
+    let rx_stream = secu.receive_stream("becu", "state").unwrap();
+    rx_stream.map("pressure", "becu_pressure");
+    rx_stream.map("temperature", "becu_temperature");
+    (network_builder, rx_stream)
+}
+
+#[test]
+fn unmapped_entries_are_dropped_by_default() {
+    let (network_builder, _) = network_builder();
+    assert!(network_builder.build().is_ok());
+}
+
+#[test]
+fn unmapped_entries_fail_with_the_error_policy() {
+    let (network_builder, rx_stream) = network_builder();
+    rx_stream.set_unmapped_policy(UnmappedPolicy::Error);
+    match network_builder.build() {
+        Err(ConfigError::UnmappedStreamEntry(msg)) => {
+            assert!(msg.contains("becu::state -> secu"));
+            assert!(msg.contains("debug"));
+            assert!(!msg.contains("pressure"));
+        }
+        other => panic!("expected UnmappedStreamEntry, got {other:?}"),
+    }
+
+    let (network_builder, rx_stream) = self::network_builder();
+    rx_stream.set_unmapped_policy(UnmappedPolicy::Error);
+    rx_stream.ignore("debug");
+    assert!(network_builder.build().is_ok());
+}
+
+#[test]
+fn strict_mappings_apply_to_all_receive_streams() {
+    let (network_builder, _) = network_builder();
+    network_builder.strict_stream_mappings();
+    assert!(matches!(
+        network_builder.build(),
+        Err(ConfigError::UnmappedStreamEntry(_))
+    ));
+
+    let (network_builder, rx_stream) = self::network_builder();
+    network_builder.strict_stream_mappings();
+    rx_stream.ignore("debug");
+    assert!(network_builder.build().is_ok());
+}
+
+#[test]
+fn ignored_entries_have_to_exist() {
+    let (network_builder, rx_stream) = network_builder();
+    rx_stream.ignore("debgu");
+    match network_builder.build() {
+        Err(ConfigError::UnmappedStreamEntry(msg)) => assert!(msg.contains("debgu")),
+        other => panic!("expected UnmappedStreamEntry, got {other:?}"),
+    }
+}
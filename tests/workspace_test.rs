@@ -0,0 +1,65 @@
+use canzero_config::{builder::WorkspaceBuilder, config::Type, errors::ConfigError};
+
+fn workspace() -> WorkspaceBuilder {
+    let workspace = WorkspaceBuilder::new();
+    let powertrain = workspace.create_network("powertrain");
+    let state = workspace.define_enum("pod_state");
+    state.add_entry("Idle", None).unwrap();
+    state.add_entry("Levitating", None).unwrap();
+    // networks created after the type was defined share it as well.
+    let chassis = workspace.create_network("chassis");
+
+    for (network, node) in [(&powertrain, "motor"), (&chassis, "brakes")] {
+        let node = network.create_node(node);
+        node.create_object_entry("state", "pod_state");
+    }
+    let gateways = workspace.create_gateway("master", &["powertrain", "chassis"]);
+    assert_eq!(gateways.len(), 2);
+    for gateway in &gateways {
+        gateway.create_object_entry("state", "pod_state");
+    }
+    // adding an entry later is visible in all networks.
+    state.add_entry("Braking", None).unwrap();
+    workspace
+}
+
+#[test]
+fn shared_types_are_defined_in_all_networks() {
+    let workspace = workspace().build().unwrap();
+    assert_eq!(workspace.networks().len(), 2);
+    for name in ["powertrain", "chassis"] {
+        let network = workspace.network(name).unwrap();
+        let pod_state = network
+            .types()
+            .iter()
+            .find(|ty| ty.name() == "pod_state")
+            .unwrap_or_else(|| panic!("pod_state is missing in {name}"));
+        let Type::Enum { entries, .. } = pod_state as &Type else {
+            panic!("pod_state is not an enum");
+        };
+        assert_eq!(entries.len(), 3);
+    }
+    assert!(workspace.network("body").is_none());
+}
+
+#[test]
+fn gateways_are_part_of_all_their_networks() {
+    let workspace = workspace().build().unwrap();
+    assert_eq!(
+        workspace.gateways(),
+        vec![(
+            "master".to_owned(),
+            vec!["powertrain".to_owned(), "chassis".to_owned()]
+        )]
+    );
+}
+
+#[test]
+fn redefined_shared_types_are_rejected() {
+    let workspace = workspace();
+    workspace.create_network("chassis").define_enum("pod_state");
+    assert!(matches!(
+        workspace.build(),
+        Err(ConfigError::InvalidWorkspace(_))
+    ));
+}